@@ -0,0 +1,95 @@
+//! AArch64 instruction detail.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use robustone_core::traits::instruction::Detail;
+use robustone_core::types::operand::{Operand, OperandAccess};
+
+use crate::groups::AArch64InstructionGroup;
+use crate::registers;
+use crate::types::{AArch64Operand, AArch64OperandValue, Access};
+
+/// AArch64 instruction detail: register accesses, operands and groups.
+#[derive(Debug, Clone, Default)]
+pub struct AArch64InstructionDetail {
+    /// Registers read by this instruction
+    pub regs_read: Vec<u32>,
+    /// Registers written by this instruction
+    pub regs_write: Vec<u32>,
+    /// `(mnemonic, operands)` of the real instruction when an alias was printed
+    pub real_instruction: Option<(String, String)>,
+    /// Structured operands of the printed instruction
+    pub operands: Vec<AArch64Operand>,
+    /// Semantic groups of the instruction
+    pub groups: &'static [AArch64InstructionGroup],
+    /// Whether the base register of the memory operand is written back
+    pub writeback: bool,
+}
+
+impl AArch64InstructionDetail {
+    /// Creates an empty detail.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Detail for AArch64InstructionDetail {
+    fn architecture_name(&self) -> &'static str {
+        "aarch64"
+    }
+
+    fn registers_read(&self) -> &[u32] {
+        &self.regs_read
+    }
+
+    fn registers_written(&self) -> &[u32] {
+        &self.regs_write
+    }
+
+    fn real_instruction(&self) -> Option<(&str, &str)> {
+        self.real_instruction
+            .as_ref()
+            .map(|(mnemonic, operands)| (mnemonic.as_str(), operands.as_str()))
+    }
+
+    fn operands(&self) -> Vec<Operand> {
+        let access = |access: Access| OperandAccess {
+            read: access.read,
+            write: access.write,
+        };
+        self.operands
+            .iter()
+            .filter_map(|operand| match operand.value {
+                AArch64OperandValue::Register(reg) => Some(Operand::Register {
+                    name: registers::name(reg)?,
+                    access: access(operand.access),
+                }),
+                AArch64OperandValue::Immediate(imm) => Some(Operand::Immediate(imm)),
+                AArch64OperandValue::Memory(mem) => Some(Operand::Memory {
+                    base: registers::name(mem.base)?,
+                    index: mem.index.and_then(registers::name),
+                    disp: mem.disp,
+                    access: access(operand.access),
+                }),
+                // The common operand form has no floating-point immediates.
+                AArch64OperandValue::Float(_) => None,
+            })
+            .collect()
+    }
+
+    fn writeback(&self) -> bool {
+        self.writeback
+    }
+
+    fn signed_displacement(&self) -> bool {
+        true
+    }
+
+    fn groups(&self) -> Vec<&str> {
+        self.groups.iter().map(|group| group.name()).collect()
+    }
+
+    fn register_name(&self, reg: u32) -> Option<&'static str> {
+        registers::name(reg)
+    }
+}
@@ -0,0 +1,515 @@
+//! Branches, exception generation and system instructions.
+
+use alloc::format;
+use alloc::string::String;
+
+use super::{Writer, bit, bits, condition, sign_extend};
+use crate::registers::{Bank, LR, SP, gpr, gpr_sp, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    if bits(word, 30, 26) == 0b00101 {
+        return unconditional(word, address, w);
+    }
+    match bits(word, 31, 25) & 0b011_1111 {
+        0b01_1010 => return compare_branch(word, address, w),
+        0b01_1011 => return test_branch(word, address, w),
+        _ => {}
+    }
+    match bits(word, 31, 22) {
+        0b01_0101_0000..=0b01_0101_0011 => conditional(word, address, w),
+        0b11_0101_0000..=0b11_0101_0011 => exception(word, w),
+        0b11_0101_0100 => system(word, w),
+        0b11_0101_1000..=0b11_0101_1111 => branch_register(word, w),
+        _ => None,
+    }
+}
+
+/// `b` and `bl` with a 26-bit offset.
+fn unconditional(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let offset = sign_extend(bits(word, 25, 0), 26) << 2;
+    if bit(word, 31) {
+        w.insn("bl");
+        w.implicit_write(LR);
+    } else {
+        w.insn("b");
+    }
+    w.target(address.wrapping_add(offset as u64));
+    Some(())
+}
+
+/// `cbz` and `cbnz`.
+fn compare_branch(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let offset = sign_extend(bits(word, 23, 5), 19) << 2;
+    w.insn(if bit(word, 24) { "cbnz" } else { "cbz" });
+    w.reg(gpr(bit(word, 31), bits(word, 4, 0)), Access::read());
+    w.target(address.wrapping_add(offset as u64));
+    Some(())
+}
+
+/// `tbz` and `tbnz`.
+fn test_branch(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let offset = sign_extend(bits(word, 18, 5), 14) << 2;
+    let wide = bit(word, 31);
+    w.insn(if bit(word, 24) { "tbnz" } else { "tbz" });
+    w.reg(gpr(wide, bits(word, 4, 0)), Access::read());
+    w.uimm(u64::from(u32::from(wide) << 5 | bits(word, 23, 19)));
+    w.target(address.wrapping_add(offset as u64));
+    Some(())
+}
+
+/// `b.cond` and `bc.cond`.
+fn conditional(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    if bit(word, 24) {
+        return None;
+    }
+    let offset = sign_extend(bits(word, 23, 5), 19) << 2;
+    w.insn(if bit(word, 4) { "bc." } else { "b." });
+    w.suffix(condition(bits(word, 3, 0)));
+    w.target(address.wrapping_add(offset as u64));
+    Some(())
+}
+
+/// `svc`, `hvc`, `smc`, `brk`, `hlt` and `dcps1`-`dcps3`.
+fn exception(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 4, 2) != 0 {
+        return None;
+    }
+    let imm = u64::from(bits(word, 20, 5));
+    let mnemonic = match (bits(word, 23, 21), bits(word, 1, 0)) {
+        (0b000, 0b01) => "svc",
+        (0b000, 0b10) => "hvc",
+        (0b000, 0b11) => "smc",
+        (0b001, 0b00) => "brk",
+        (0b010, 0b00) => "hlt",
+        (0b101, 0b01) => "dcps1",
+        (0b101, 0b10) => "dcps2",
+        (0b101, 0b11) => "dcps3",
+        _ => return None,
+    };
+    w.insn(mnemonic);
+    if !mnemonic.starts_with("dcps") || imm != 0 {
+        w.uimm(imm);
+    }
+    Some(())
+}
+
+/// Branches to a register, returns and their pointer-authenticating
+/// forms.
+fn branch_register(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 20, 16) != 0b11111 {
+        return None;
+    }
+    let (opc, op3) = (bits(word, 24, 21), bits(word, 15, 10));
+    let (rn, op4) = (bits(word, 9, 5), bits(word, 4, 0));
+    // `op3` selects the plain form or the A and B keys.
+    let key = match op3 {
+        0b000000 => 0,
+        0b000010 => 1,
+        0b000011 => 2,
+        _ => return None,
+    };
+    // Apart from `braa` and friends, `op4` is 0 for the plain forms and
+    // 31 (a zero modifier) for the authenticating ones.
+    let modifier = opc >= 0b1000;
+    if !modifier && op4 != if key == 0 { 0 } else { 0b11111 } {
+        return None;
+    }
+    let names: [&str; 3] = match opc {
+        0b0000 => ["br", "braaz", "brabz"],
+        0b0001 => ["blr", "blraaz", "blrabz"],
+        0b0010 => ["ret", "retaa", "retab"],
+        0b0100 => ["eret", "eretaa", "eretab"],
+        0b0101 => ["drps", "", ""],
+        0b1000 => ["", "braa", "brab"],
+        0b1001 => ["", "blraa", "blrab"],
+        _ => return None,
+    };
+    let name = names[key];
+    if name.is_empty() {
+        return None;
+    }
+    w.insn(name);
+    match opc {
+        0b0010 if key != 0 => {
+            if rn != 0b11111 {
+                return None;
+            }
+            w.implicit_read(LR);
+            w.implicit_read(SP);
+        }
+        0b0010 if rn == 30 => w.implicit_read(LR),
+        0b0100 | 0b0101 if rn != 0b11111 => return None,
+        0b0100 | 0b0101 => {}
+        _ => w.reg(gpr(true, rn), Access::read()),
+    }
+    if modifier {
+        w.reg(gpr_sp(true, op4), Access::read());
+    }
+    if opc & 1 == 1 {
+        w.implicit_write(LR);
+    }
+    Some(())
+}
+
+/// Hints, barriers, PSTATE writes, `sys` and system register moves.
+fn system(word: u32, w: &mut Writer) -> Option<()> {
+    let (read, op0, op1) = (bit(word, 21), bits(word, 20, 19), bits(word, 18, 16));
+    let (crn, crm, op2) = (bits(word, 15, 12), bits(word, 11, 8), bits(word, 7, 5));
+    let rt = bits(word, 4, 0);
+    match (read, op0) {
+        (false, 0b00) if rt == 0b11111 => match crn {
+            0b0010 if op1 == 0b011 => hint(crm << 3 | op2, w),
+            0b0011 if op1 == 0b011 => barrier(crm, op2, w).or_else(|| register_move(word, w)),
+            0b0100 => pstate(word, w),
+            _ => register_move(word, w),
+        },
+        (_, 0b01) => sys(read, op1, crn, crm, op2, rt, w),
+        _ => register_move(word, w),
+    }
+}
+
+/// `mrs` and `msr` of a system register, named when known and printed as
+/// `s<op0>_<op1>_c<n>_c<m>_<op2>` otherwise.
+fn register_move(word: u32, w: &mut Writer) -> Option<()> {
+    let (op0, op1) = (bits(word, 20, 19), bits(word, 18, 16));
+    let (crn, crm, op2) = (bits(word, 15, 12), bits(word, 11, 8), bits(word, 7, 5));
+    let name = system_register(op0, op1, crn, crm, op2);
+    let rt = gpr(true, bits(word, 4, 0));
+    if bit(word, 21) {
+        w.insn("mrs");
+        w.reg(rt, Access::write());
+        w.token(&name);
+    } else {
+        w.insn("msr");
+        w.token(&name);
+        w.reg(rt, Access::read());
+    }
+    Some(())
+}
+
+/// Hint instructions, including the PAC and BTI ones in the hint space.
+fn hint(number: u32, w: &mut Writer) -> Option<()> {
+    let name = match number {
+        0 => "nop",
+        1 => "yield",
+        2 => "wfe",
+        3 => "wfi",
+        4 => "sev",
+        5 => "sevl",
+        6 => "dgh",
+        7 => "xpaclri",
+        8 => "pacia1716",
+        10 => "pacib1716",
+        12 => "autia1716",
+        14 => "autib1716",
+        16 => "esb",
+        17 => "psb csync",
+        18 => "tsb csync",
+        20 => "csdb",
+        24 => "paciaz",
+        25 => "paciasp",
+        26 => "pacibz",
+        27 => "pacibsp",
+        28 => "autiaz",
+        29 => "autiasp",
+        30 => "autibz",
+        31 => "autibsp",
+        32 => "bti",
+        34 => "bti c",
+        36 => "bti j",
+        38 => "bti jc",
+        _ => {
+            w.insn("hint");
+            w.uimm(u64::from(number));
+            return Some(());
+        }
+    };
+    // `psb csync` and `bti c` take their target as a keyword operand.
+    match name.split_once(' ') {
+        Some((mnemonic, operand)) => {
+            w.insn(mnemonic);
+            w.token(operand);
+        }
+        None => w.insn(name),
+    }
+    // The 1716 forms work on x17 with x16 as modifier, the others on x30
+    // with sp or zero as modifier.
+    match number {
+        7 => w.implicit_write(LR),
+        8 | 10 | 12 | 14 => {
+            w.implicit_read(reg(Bank::X, 16));
+            w.implicit_read(reg(Bank::X, 17));
+            w.implicit_write(reg(Bank::X, 17));
+        }
+        24..=31 => {
+            w.implicit_read(LR);
+            if number & 1 == 1 {
+                w.implicit_read(SP);
+            }
+            w.implicit_write(LR);
+        }
+        _ => {}
+    }
+    Some(())
+}
+
+/// `clrex`, `dsb`, `dmb`, `isb`, `sb`, `ssbb` and `pssbb`.
+fn barrier(crm: u32, op2: u32, w: &mut Writer) -> Option<()> {
+    match op2 {
+        0b010 => {
+            w.insn("clrex");
+            if crm != 0b1111 {
+                w.uimm(u64::from(crm));
+            }
+        }
+        0b100 if crm == 0 => w.insn("ssbb"),
+        0b100 if crm == 4 => w.insn("pssbb"),
+        0b100 | 0b101 => {
+            w.insn(if op2 == 0b100 { "dsb" } else { "dmb" });
+            let option = match crm {
+                0b0001 => "oshld",
+                0b0010 => "oshst",
+                0b0011 => "osh",
+                0b0101 => "nshld",
+                0b0110 => "nshst",
+                0b0111 => "nsh",
+                0b1001 => "ishld",
+                0b1010 => "ishst",
+                0b1011 => "ish",
+                0b1101 => "ld",
+                0b1110 => "st",
+                0b1111 => "sy",
+                _ => {
+                    w.uimm(u64::from(crm));
+                    return Some(());
+                }
+            };
+            w.token(option);
+        }
+        0b110 => {
+            w.insn("isb");
+            if crm != 0b1111 {
+                w.uimm(u64::from(crm));
+            }
+        }
+        0b111 if crm == 0 => w.insn("sb"),
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `msr <pstatefield>, #imm`, `cfinv`, `xaflag`, `axflag`, `smstart` and
+/// `smstop`. Unallocated fields, and immediates a one-bit field does not
+/// take, print as a write of `xzr` to the encoded system register.
+fn pstate(word: u32, w: &mut Writer) -> Option<()> {
+    let (op1, crm, op2) = (bits(word, 18, 16), bits(word, 11, 8), bits(word, 7, 5));
+    let field = match (op1, op2) {
+        (0b000, 0b000) if crm == 0 => {
+            w.insn("cfinv");
+            return Some(());
+        }
+        (0b000, 0b001 | 0b010) => {
+            if crm != 0 {
+                return None;
+            }
+            w.insn(if op2 == 0b001 { "xaflag" } else { "axflag" });
+            return Some(());
+        }
+        (0b000, 0b011) if crm <= 1 => "uao",
+        (0b000, 0b100) if crm <= 1 => "pan",
+        (0b000, 0b101) => "spsel",
+        (0b011, 0b011) if crm & 0b1110 != 0 && crm >> 3 == 0 => {
+            w.insn(if crm & 1 == 1 { "smstart" } else { "smstop" });
+            match crm >> 1 {
+                0b01 => w.token("sm"),
+                0b10 => w.token("za"),
+                _ => {}
+            }
+            return Some(());
+        }
+        (0b011, 0b001) if crm <= 1 => "ssbs",
+        (0b011, 0b010) => "dit",
+        (0b011, 0b100) => "tco",
+        (0b011, 0b110) => "daifset",
+        (0b011, 0b111) => "daifclr",
+        _ => return register_move(word, w),
+    };
+    w.insn("msr");
+    w.token(field);
+    w.uimm(u64::from(crm));
+    Some(())
+}
+
+/// Cache, address translation and TLB maintenance operations printed as
+/// `sys` aliases: `(op1, CRn, CRm, op2, mnemonic, operation, takes Xt)`.
+const SYS_ALIASES: &[(u32, u32, u32, u32, &str, &str, bool)] = &[
+    (0, 7, 1, 0, "ic", "ialluis", false),
+    (0, 7, 5, 0, "ic", "iallu", false),
+    (3, 7, 5, 1, "ic", "ivau", true),
+    (0, 7, 6, 1, "dc", "ivac", true),
+    (0, 7, 6, 2, "dc", "isw", true),
+    (0, 7, 10, 2, "dc", "csw", true),
+    (0, 7, 14, 2, "dc", "cisw", true),
+    (3, 7, 4, 1, "dc", "zva", true),
+    (3, 7, 10, 1, "dc", "cvac", true),
+    (3, 7, 11, 1, "dc", "cvau", true),
+    (3, 7, 12, 1, "dc", "cvap", true),
+    (3, 7, 14, 1, "dc", "civac", true),
+    (0, 7, 8, 0, "at", "s1e1r", true),
+    (0, 7, 8, 1, "at", "s1e1w", true),
+    (0, 7, 8, 2, "at", "s1e0r", true),
+    (0, 7, 8, 3, "at", "s1e0w", true),
+    (0, 8, 3, 0, "tlbi", "vmalle1is", false),
+    (0, 8, 3, 1, "tlbi", "vae1is", true),
+    (0, 8, 3, 2, "tlbi", "aside1is", true),
+    (0, 8, 7, 0, "tlbi", "vmalle1", false),
+    (0, 8, 7, 1, "tlbi", "vae1", true),
+    (0, 8, 7, 2, "tlbi", "aside1", true),
+    (0, 8, 7, 3, "tlbi", "vaae1", true),
+];
+
+/// `sys` and `sysl`, with the `dc`, `ic`, `at` and `tlbi` aliases.
+fn sys(read: bool, op1: u32, crn: u32, crm: u32, op2: u32, rt: u32, w: &mut Writer) -> Option<()> {
+    if read {
+        w.insn("sysl");
+        w.reg(gpr(true, rt), Access::write());
+    } else {
+        let alias = SYS_ALIASES
+            .iter()
+            .find(|entry| (entry.0, entry.1, entry.2, entry.3) == (op1, crn, crm, op2));
+        if w.aliases
+            && let Some(&(_, _, _, _, mnemonic, operation, register)) = alias
+            && register == (rt != 0b11111)
+        {
+            w.alias(mnemonic);
+            w.token(operation);
+            if register {
+                w.reg(gpr(true, rt), Access::read());
+            }
+            return Some(());
+        }
+        w.insn("sys");
+    }
+    w.uimm(u64::from(op1));
+    w.token(&format!("c{crn}"));
+    w.token(&format!("c{crm}"));
+    w.uimm(u64::from(op2));
+    if !read && rt != 0b11111 {
+        w.reg(gpr(true, rt), Access::read());
+    }
+    Some(())
+}
+
+/// Names of common system registers: `(op0, op1, CRn, CRm, op2, name)`.
+const SYSTEM_REGISTERS: &[(u32, u32, u32, u32, u32, &str)] = &[
+    (3, 0, 0, 0, 0, "midr_el1"),
+    (3, 0, 0, 0, 5, "mpidr_el1"),
+    (3, 0, 0, 4, 4, "id_aa64zfr0_el1"),
+    (3, 0, 0, 4, 5, "id_aa64smfr0_el1"),
+    (3, 0, 1, 0, 0, "sctlr_el1"),
+    (3, 0, 1, 0, 2, "cpacr_el1"),
+    (3, 0, 1, 2, 0, "zcr_el1"),
+    (3, 0, 1, 2, 4, "smpri_el1"),
+    (3, 0, 1, 2, 6, "smcr_el1"),
+    (3, 0, 2, 0, 0, "ttbr0_el1"),
+    (3, 0, 2, 0, 1, "ttbr1_el1"),
+    (3, 0, 2, 0, 2, "tcr_el1"),
+    (3, 0, 4, 0, 0, "spsr_el1"),
+    (3, 0, 4, 0, 1, "elr_el1"),
+    (3, 0, 4, 1, 0, "sp_el0"),
+    (3, 0, 4, 2, 2, "currentel"),
+    (3, 0, 5, 2, 0, "esr_el1"),
+    (3, 0, 6, 0, 0, "far_el1"),
+    (3, 0, 10, 2, 0, "mair_el1"),
+    (3, 0, 12, 0, 0, "vbar_el1"),
+    (3, 0, 13, 0, 1, "contextidr_el1"),
+    (3, 0, 13, 0, 4, "tpidr_el1"),
+    (3, 1, 0, 0, 6, "smidr_el1"),
+    (3, 3, 0, 0, 1, "ctr_el0"),
+    (3, 3, 0, 0, 7, "dczid_el0"),
+    (3, 3, 4, 2, 0, "nzcv"),
+    (3, 3, 4, 2, 1, "daif"),
+    (3, 3, 4, 2, 2, "svcr"),
+    (3, 3, 4, 4, 0, "fpcr"),
+    (3, 3, 4, 4, 1, "fpsr"),
+    (3, 3, 13, 0, 2, "tpidr_el0"),
+    (3, 3, 13, 0, 3, "tpidrro_el0"),
+    (3, 3, 13, 0, 5, "tpidr2_el0"),
+    (3, 3, 14, 0, 0, "cntfrq_el0"),
+    (3, 3, 14, 0, 1, "cntpct_el0"),
+    (3, 3, 14, 0, 2, "cntvct_el0"),
+    (3, 4, 1, 2, 0, "zcr_el2"),
+    (3, 4, 1, 2, 5, "smprimap_el2"),
+    (3, 4, 1, 2, 6, "smcr_el2"),
+    (3, 5, 1, 2, 0, "zcr_el12"),
+    (3, 5, 1, 2, 6, "smcr_el12"),
+    (3, 6, 1, 2, 0, "zcr_el3"),
+    (3, 6, 1, 2, 6, "smcr_el3"),
+];
+
+/// Name of a system register, or its generic `s<op0>_<op1>_c<n>_c<m>_<op2>`
+/// form.
+fn system_register(op0: u32, op1: u32, crn: u32, crm: u32, op2: u32) -> String {
+    SYSTEM_REGISTERS
+        .iter()
+        .find(|entry| (entry.0, entry.1, entry.2, entry.3, entry.4) == (op0, op1, crn, crm, op2))
+        .map(|entry| entry.5.into())
+        .unwrap_or_else(|| format!("s{op0}_{op1}_c{crn}_c{crm}_{op2}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_branches() {
+        let cases = [
+            (0x1400_0004, "b #0x1010"),
+            (0x97ff_ffff, "bl #0xffc"),
+            (0x5400_0041, "b.ne #0x1008"),
+            (0xb400_0040, "cbz x0, #0x1008"),
+            (0x3718_0040, "tbnz w0, #3, #0x1008"),
+            (0xb6f8_0040, "tbz x0, #0x3f, #0x1008"),
+            (0xd61f_0220, "br x17"),
+            (0xd63f_0100, "blr x8"),
+            (0xd65f_03c0, "ret"),
+            (0xd65f_0020, "ret x1"),
+            (0xd65f_0bff, "retaa"),
+            (0xd63f_081f, "blraaz x0"),
+            (0xd71f_0820, "braa x1, x0"),
+            (0xd69f_03e0, "eret"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0x1000), text, "{word:#010x}");
+        }
+    }
+
+    #[test]
+    fn test_system() {
+        let cases = [
+            (0xd400_0001, "svc #0"),
+            (0xd420_7d00, "brk #0x3e8"),
+            (0xd503_201f, "nop"),
+            (0xd503_233f, "paciasp"),
+            (0xd503_245f, "bti c"),
+            (0xd503_261f, "hint #0x30"),
+            (0xd503_3bbf, "dmb ish"),
+            (0xd503_3fdf, "isb"),
+            (0xd503_309f, "ssbb"),
+            (0xd503_47df, "msr daifset, #7"),
+            (0xd53b_d040, "mrs x0, tpidr_el0"),
+            (0xd518_c000, "msr vbar_el1, x0"),
+            (0xd53f_f040, "mrs x0, s3_7_c15_c0_2"),
+            (0xd50b_7e20, "dc civac, x0"),
+            (0xd508_871f, "tlbi vmalle1"),
+            (0xd503_447f, "smstop za"),
+            (0xd503_437f, "smstart sm"),
+            (0xd51b_4240, "msr svcr, x0"),
+            (0xd53b_d0a1, "mrs x1, tpidr2_el0"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Data processing with an immediate: PC-relative addressing, add/sub,
+//! logical, move wide, bitfield and extract.
+
+use super::{Writer, bit, bits, sign_extend};
+use crate::registers::{gpr, gpr_sp};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    match bits(word, 25, 23) {
+        0b000 | 0b001 => pc_relative(word, address, w),
+        0b010 => add_sub(word, w),
+        0b100 => logical(word, w),
+        0b101 => move_wide(word, w),
+        0b110 => bitfield(word, w),
+        0b111 => extract(word, w),
+        // Add/subtract with tags (MTE).
+        _ => None,
+    }
+}
+
+/// `adr` and `adrp`.
+fn pc_relative(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let imm = sign_extend(bits(word, 23, 5) << 2 | bits(word, 30, 29), 21);
+    let target = if bit(word, 31) {
+        w.insn("adrp");
+        (address & !0xfff).wrapping_add((imm << 12) as u64)
+    } else {
+        w.insn("adr");
+        address.wrapping_add(imm as u64)
+    };
+    w.reg(gpr(true, bits(word, 4, 0)), Access::write());
+    w.target(target);
+    Some(())
+}
+
+/// `add`, `adds`, `sub` and `subs` with a 12-bit immediate, and their
+/// `mov`, `cmp` and `cmn` aliases.
+fn add_sub(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let sub = bit(word, 30);
+    let flags = bit(word, 29);
+    let shifted = bit(word, 22);
+    let imm = bits(word, 21, 10);
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+
+    if w.aliases && flags && rd == 31 {
+        w.alias(if sub { "cmp" } else { "cmn" });
+    } else if w.aliases && !sub && !flags && !shifted && imm == 0 && (rd == 31 || rn == 31) {
+        w.alias("mov");
+        w.reg(gpr_sp(sf, rd), Access::write());
+        w.reg(gpr_sp(sf, rn), Access::read());
+        return Some(());
+    } else {
+        w.insn(match (sub, flags) {
+            (false, false) => "add",
+            (false, true) => "adds",
+            (true, false) => "sub",
+            (true, true) => "subs",
+        });
+        let rd = if flags { gpr(sf, rd) } else { gpr_sp(sf, rd) };
+        w.reg(rd, Access::write());
+    }
+    w.reg(gpr_sp(sf, rn), Access::read());
+    w.uimm(u64::from(imm));
+    if shifted {
+        w.shift("lsl", 12);
+    }
+    Some(())
+}
+
+/// Decodes a logical immediate (`DecodeBitMasks`), returning `None` for
+/// reserved encodings.
+pub(crate) fn bit_mask(n: bool, immr: u32, imms: u32, width: u32) -> Option<u64> {
+    let combined = (u32::from(n) << 6) | (!imms & 0x3f);
+    if combined == 0 {
+        return None;
+    }
+    let len = 31 - combined.leading_zeros();
+    let size = 1u32 << len;
+    if size > width {
+        return None;
+    }
+    let levels = size - 1;
+    let (s, r) = (imms & levels, immr & levels);
+    if s == levels {
+        return None;
+    }
+    let ones = (1u64 << (s + 1)) - 1;
+    let mask = if size == 64 {
+        u64::MAX
+    } else {
+        (1u64 << size) - 1
+    };
+    let element = if r == 0 {
+        ones
+    } else {
+        ((ones >> r) | (ones << (size - r))) & mask
+    };
+    let mut value = element;
+    let mut filled = size;
+    while filled < width {
+        value |= value << filled;
+        filled *= 2;
+    }
+    Some(if width == 32 {
+        value & 0xffff_ffff
+    } else {
+        value
+    })
+}
+
+/// Whether `value` can be built by a single `movz` or `movn`, in which case
+/// LLVM does not print an `orr` as `mov`.
+fn is_move_wide(value: u64, width: u32) -> bool {
+    let mask = if width == 32 { 0xffff_ffff } else { u64::MAX };
+    let single = |v: u64| (0..width / 16).any(|hw| v & !(0xffff << (hw * 16)) == 0);
+    single(value & mask) || single(!value & mask)
+}
+
+/// `and`, `orr`, `eor` and `ands` with a bit-mask immediate, and their
+/// `mov` and `tst` aliases.
+fn logical(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let opc = bits(word, 30, 29);
+    let n = bit(word, 22);
+    if !sf && n {
+        return None;
+    }
+    let width = if sf { 64 } else { 32 };
+    let value = bit_mask(n, bits(word, 21, 16), bits(word, 15, 10), width)?;
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+    // The `mov` alias prints the value signed.
+    let signed = if sf {
+        value as i64
+    } else {
+        i64::from(value as u32 as i32)
+    };
+
+    if w.aliases && opc == 0b11 && rd == 31 {
+        w.alias("tst");
+        w.reg(gpr(sf, rn), Access::read());
+        w.hex(value);
+        return Some(());
+    }
+    if w.aliases && opc == 0b01 && rn == 31 && !is_move_wide(value, width) {
+        w.alias("mov");
+        w.reg(gpr_sp(sf, rd), Access::write());
+        w.imm(signed);
+        return Some(());
+    }
+    w.insn(["and", "orr", "eor", "ands"][opc as usize]);
+    let rd = if opc == 0b11 {
+        gpr(sf, rd)
+    } else {
+        gpr_sp(sf, rd)
+    };
+    w.reg(rd, Access::write());
+    w.reg(gpr(sf, rn), Access::read());
+    w.hex(value);
+    Some(())
+}
+
+/// `movn`, `movz` and `movk`, and the `mov` alias of the first two.
+fn move_wide(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let opc = bits(word, 30, 29);
+    let hw = bits(word, 22, 21);
+    if opc == 0b01 || (!sf && hw >= 2) {
+        return None;
+    }
+    let imm = u64::from(bits(word, 20, 5));
+    let shift = hw * 16;
+    let rd = gpr(sf, bits(word, 4, 0));
+
+    let wide = imm << shift;
+    let alias = match opc {
+        0b10 if imm != 0 || hw == 0 => Some(wide as i64),
+        // `movn wN, #0xffff` stays `movn`: the `mov` would read as -65536.
+        0b00 if (imm != 0 || hw == 0) && (sf || imm != 0xffff) => Some(if sf {
+            !wide as i64
+        } else {
+            i64::from(!(wide as u32) as i32)
+        }),
+        _ => None,
+    };
+    if w.aliases
+        && let Some(value) = alias
+    {
+        w.alias("mov");
+        w.reg(rd, Access::write());
+        w.imm(if sf { value } else { i64::from(value as i32) });
+        return Some(());
+    }
+    w.insn(["movn", "", "movz", "movk"][opc as usize]);
+    let access = if opc == 0b11 {
+        Access::read_write()
+    } else {
+        Access::write()
+    };
+    w.reg(rd, access);
+    w.uimm(imm);
+    if shift != 0 {
+        w.shift("lsl", shift);
+    }
+    Some(())
+}
+
+/// `sbfm`, `bfm` and `ubfm`, with their shift, extend and bit-field
+/// aliases.
+fn bitfield(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let opc = bits(word, 30, 29);
+    if opc == 0b11 || bit(word, 22) != sf {
+        return None;
+    }
+    let (immr, imms) = (bits(word, 21, 16), bits(word, 15, 10));
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let size = if sf { 64 } else { 32 };
+    if !sf && (immr >= 32 || imms >= 32) {
+        return None;
+    }
+    let top = size - 1;
+
+    if w.aliases {
+        // The operands of the alias, after the destination and source.
+        let alias: Option<(&str, bool, &[u32])> = match opc {
+            0b00 if imms == top => Some(("asr", sf, &[immr])),
+            0b00 if immr == 0 && imms == 7 => Some(("sxtb", false, &[])),
+            0b00 if immr == 0 && imms == 15 => Some(("sxth", false, &[])),
+            0b00 if sf && immr == 0 && imms == 31 => Some(("sxtw", false, &[])),
+            0b10 if imms != top && imms + 1 == immr => Some(("lsl", sf, &[top - imms])),
+            0b10 if imms == top => Some(("lsr", sf, &[immr])),
+            0b10 if !sf && immr == 0 && imms == 7 => Some(("uxtb", false, &[])),
+            0b10 if !sf && immr == 0 && imms == 15 => Some(("uxth", false, &[])),
+            _ => None,
+        };
+        if let Some((mnemonic, wide_source, amounts)) = alias {
+            w.alias(mnemonic);
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(gpr(wide_source, rn), Access::read());
+            for &amount in amounts {
+                w.uimm(u64::from(amount));
+            }
+            return Some(());
+        }
+        // Insert (`*fiz`, `bfi`) when imms < immr, extract otherwise.
+        let (mnemonic, lsb, width) = if imms < immr {
+            let name = ["sbfiz", "bfi", "ubfiz"][opc as usize];
+            (name, (size - immr) % size, imms + 1)
+        } else {
+            let name = ["sbfx", "bfxil", "ubfx"][opc as usize];
+            (name, immr, imms - immr + 1)
+        };
+        let clear = opc == 0b01 && imms < immr && rn == 31;
+        w.alias(if clear { "bfc" } else { mnemonic });
+        let access = if opc == 0b01 {
+            Access::read_write()
+        } else {
+            Access::write()
+        };
+        w.reg(gpr(sf, rd), access);
+        if !clear {
+            w.reg(gpr(sf, rn), Access::read());
+        }
+        w.uimm(u64::from(lsb));
+        w.uimm(u64::from(width));
+        return Some(());
+    }
+    w.insn(["sbfm", "bfm", "ubfm"][opc as usize]);
+    let access = if opc == 0b01 {
+        Access::read_write()
+    } else {
+        Access::write()
+    };
+    w.reg(gpr(sf, rd), access);
+    w.reg(gpr(sf, rn), Access::read());
+    w.uimm(u64::from(immr));
+    w.uimm(u64::from(imms));
+    Some(())
+}
+
+/// `extr` and its `ror` alias.
+fn extract(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    if bits(word, 30, 29) != 0 || bit(word, 22) != sf || bit(word, 21) {
+        return None;
+    }
+    let lsb = bits(word, 15, 10);
+    if !sf && lsb >= 32 {
+        return None;
+    }
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    if w.aliases && rn == rm {
+        w.alias("ror");
+        w.reg(gpr(sf, rd), Access::write());
+        w.reg(gpr(sf, rn), Access::read());
+    } else {
+        w.insn("extr");
+        w.reg(gpr(sf, rd), Access::write());
+        w.reg(gpr(sf, rn), Access::read());
+        w.reg(gpr(sf, rm), Access::read());
+    }
+    w.uimm(u64::from(lsb));
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::render;
+
+    #[test]
+    fn test_bit_mask() {
+        assert_eq!(bit_mask(false, 0, 7, 32), Some(0xff));
+        assert_eq!(bit_mask(true, 0, 0, 64), Some(1));
+        assert_eq!(bit_mask(false, 1, 0x3c, 64), Some(0xaaaa_aaaa_aaaa_aaaa));
+        assert_eq!(bit_mask(false, 0, 0x3f, 32), None);
+    }
+
+    #[test]
+    fn test_data_processing_immediate() {
+        let cases = [
+            (0x9100_4020, "add x0, x1, #0x10"),
+            (0x9100_03e0, "mov x0, sp"),
+            (0xf100_0c1f, "cmp x0, #3"),
+            (0x1140_0420, "add w0, w1, #1, lsl #12"),
+            (0xd280_0020, "mov x0, #1"),
+            (0x9280_0000, "mov x0, #-1"),
+            (0x12bf_ffe0, "movn w0, #0xffff, lsl #16"),
+            (0x929f_ffe0, "mov x0, #-0x10000"),
+            (0xf2a2_4680, "movk x0, #0x1234, lsl #16"),
+            (0x9240_1c20, "and x0, x1, #0xff"),
+            (0xb200_e3e0, "mov x0, #0x1111111111111111"),
+            (0xb240_0fe0, "orr x0, xzr, #0xf"),
+            (0xf240_001f, "tst x0, #0x1"),
+            (0xd37c_ec20, "lsl x0, x1, #4"),
+            (0xd344_fc20, "lsr x0, x1, #4"),
+            (0x9340_7c20, "sxtw x0, w1"),
+            (0x5300_1c20, "uxtb w0, w1"),
+            (0xd344_2c20, "ubfx x0, x1, #4, #8"),
+            (0x331c_0c20, "bfi w0, w1, #4, #4"),
+            (0x331c_0fe0, "bfc w0, #4, #4"),
+            (0x9340_0c20, "sbfx x0, x1, #0, #4"),
+            (0x5318_5c20, "lsl w0, w1, #8"),
+            (0x93c2_1020, "extr x0, x1, x2, #4"),
+            (0x93c1_1020, "ror x0, x1, #4"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0x1000), text, "{word:#010x}");
+        }
+        assert_eq!(render(0x1000_0080, 0x1000), "adr x0, #0x1010");
+        assert_eq!(render(0xb000_0000, 0x1234), "adrp x0, #0x2000");
+    }
+}
@@ -0,0 +1,598 @@
+//! Loads and stores: exclusives, literals, pairs, the single-register
+//! addressing modes, LSE atomics and Advanced SIMD structures.
+
+use alloc::format;
+use alloc::string::String;
+
+use super::{Index, Writer, bit, bits, sign_extend};
+use crate::registers::{Bank, gpr, gpr_sp, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let vector = bit(word, 26);
+    match bits(word, 29, 27) {
+        0b001 if !bit(word, 31) && vector && !bit(word, 25) => {
+            if bit(word, 24) {
+                single_structure(word, w)
+            } else {
+                structures(word, w)
+            }
+        }
+        0b001 if !vector && bits(word, 25, 24) == 0 => exclusive(word, w),
+        0b011 if bits(word, 25, 24) == 0 => literal(word, address, w),
+        0b101 if !bit(word, 25) => pair(word, w),
+        0b111 if !bit(word, 25) => single(word, w),
+        _ => None,
+    }
+}
+
+/// SIMD&FP register bank of an access of `1 << scale` bytes.
+fn fp_bank(scale: u32) -> Bank {
+    [Bank::B, Bank::H, Bank::S, Bank::D, Bank::Q][scale as usize]
+}
+
+/// Data register and access of a load (`load`) or store.
+fn data(load: bool) -> (Access, Access) {
+    if load {
+        (Access::write(), Access::read())
+    } else {
+        (Access::read(), Access::write())
+    }
+}
+
+/// Exclusive, load-acquire/store-release and compare-and-swap
+/// instructions.
+fn exclusive(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 31, 30);
+    let (o2, load, o1, o0) = (bit(word, 23), bit(word, 22), bit(word, 21), bit(word, 15));
+    let (rs, rt2, rn, rt) = (
+        bits(word, 20, 16),
+        bits(word, 14, 10),
+        bits(word, 9, 5),
+        bits(word, 4, 0),
+    );
+    let wide = size == 0b11;
+    let suffix = ["b", "h", "", ""][size as usize];
+    let base = gpr_sp(true, rn);
+
+    // Compare and swap takes o1 together with o2, or, for `casp`, the
+    // byte and halfword sizes of the exclusive pairs.
+    if o1 && (o2 || size < 0b10) {
+        if rt2 != 0b11111 {
+            return None;
+        }
+        let pair = !o2;
+        w.insn(if pair { "casp" } else { "cas" });
+        w.suffix(if load { "a" } else { "" });
+        w.suffix(if o0 { "l" } else { "" });
+        if pair {
+            // `casp` works on even/odd register pairs, sized by bit 30.
+            if rs & 1 == 1 || rt & 1 == 1 {
+                return None;
+            }
+            let sf = bit(word, 30);
+            w.reg(gpr(sf, rs), Access::read_write());
+            w.reg(gpr(sf, rs + 1), Access::read_write());
+            w.reg(gpr(sf, rt), Access::read());
+            w.reg(gpr(sf, rt + 1), Access::read());
+        } else {
+            w.suffix(suffix);
+            w.reg(gpr(wide, rs), Access::read_write());
+            w.reg(gpr(wide, rt), Access::read());
+        }
+        w.mem(base, 0, Index::Offset, Access::read_write());
+        return Some(());
+    }
+
+    // The unused Rs and Rt2 fields below should be all ones but, as in
+    // LLVM, are not checked.
+    let (reg_access, mem_access) = data(load);
+    if o2 {
+        w.insn(match (load, o0) {
+            (false, false) => "stllr",
+            (false, true) => "stlr",
+            (true, false) => "ldlar",
+            (true, true) => "ldar",
+        });
+        w.suffix(suffix);
+        w.reg(gpr(wide, rt), reg_access);
+        w.mem(base, 0, Index::Offset, mem_access);
+        return Some(());
+    }
+
+    // Exclusives; the pair forms exist for words and doublewords only.
+    let pair = o1;
+    w.insn(match (load, o0) {
+        (false, false) => "stx",
+        (false, true) => "stlx",
+        (true, false) => "ldx",
+        (true, true) => "ldax",
+    });
+    let wide = if pair {
+        w.suffix("p");
+        bit(word, 30)
+    } else {
+        w.suffix("r");
+        w.suffix(suffix);
+        wide
+    };
+    if !load {
+        w.reg(gpr(false, rs), Access::write());
+    }
+    w.reg(gpr(wide, rt), reg_access);
+    if pair {
+        w.reg(gpr(wide, rt2), reg_access);
+    }
+    w.mem(base, 0, Index::Offset, mem_access);
+    Some(())
+}
+
+/// Writes the operand of a prefetch: a named operation or its number.
+fn prefetch_operation(w: &mut Writer, operation: u32) {
+    let kind = ["pld", "pli", "pst", ""][bits(operation, 4, 3) as usize];
+    let target = bits(operation, 2, 1);
+    if kind.is_empty() || target == 0b11 {
+        w.uimm(u64::from(operation));
+        return;
+    }
+    let policy = if bit(operation, 0) { "strm" } else { "keep" };
+    w.token(&format!("{kind}l{}{policy}", target + 1));
+}
+
+/// `ldr` from a PC-relative literal, `ldrsw` and `prfm`.
+fn literal(word: u32, address: u64, w: &mut Writer) -> Option<()> {
+    let opc = bits(word, 31, 30);
+    let rt = bits(word, 4, 0);
+    let target = address.wrapping_add((sign_extend(bits(word, 23, 5), 19) << 2) as u64);
+    if bit(word, 26) {
+        if opc == 0b11 {
+            return None;
+        }
+        w.insn("ldr");
+        w.reg(reg(fp_bank(opc + 2), rt), Access::write());
+    } else {
+        match opc {
+            0b00 | 0b01 => {
+                w.insn("ldr");
+                w.reg(gpr(opc == 0b01, rt), Access::write());
+            }
+            0b10 => {
+                w.insn("ldrsw");
+                w.reg(gpr(true, rt), Access::write());
+            }
+            _ => {
+                w.insn("prfm");
+                prefetch_operation(w, rt);
+            }
+        }
+    }
+    w.target(target);
+    Some(())
+}
+
+/// `ldp`, `stp`, `ldnp`, `stnp` and `ldpsw` in the offset, pre-index and
+/// post-index forms.
+fn pair(word: u32, w: &mut Writer) -> Option<()> {
+    let opc = bits(word, 31, 30);
+    let vector = bit(word, 26);
+    let load = bit(word, 22);
+    let (rt2, rn, rt) = (bits(word, 14, 10), bits(word, 9, 5), bits(word, 4, 0));
+    let (index, no_allocate) = match bits(word, 24, 23) {
+        0b00 => (Index::Offset, true),
+        0b01 => (Index::Post, false),
+        0b10 => (Index::Offset, false),
+        _ => (Index::Pre, false),
+    };
+    let (scale, bank, signed) = match (vector, opc) {
+        (false, 0b00) => (2, Bank::W, false),
+        (false, 0b01) if load && !no_allocate => (2, Bank::X, true),
+        (false, 0b10) => (3, Bank::X, false),
+        (true, 0b00) => (2, Bank::S, false),
+        (true, 0b01) => (3, Bank::D, false),
+        (true, 0b10) => (4, Bank::Q, false),
+        _ => return None,
+    };
+    let mnemonic = match (load, no_allocate) {
+        (false, false) => "stp",
+        (false, true) => "stnp",
+        (true, false) => "ldp",
+        (true, true) => "ldnp",
+    };
+    w.insn(mnemonic);
+    if signed {
+        w.suffix("sw");
+    }
+    let (reg_access, mem_access) = data(load);
+    w.reg(reg(bank, rt), reg_access);
+    w.reg(reg(bank, rt2), reg_access);
+    let disp = sign_extend(bits(word, 21, 15), 7) << scale;
+    w.mem(gpr_sp(true, rn), disp, index, mem_access);
+    Some(())
+}
+
+/// Register bank, access size and mnemonic suffix of a single-register
+/// load or store.
+struct Single {
+    bank: Bank,
+    scale: u32,
+    load: bool,
+    /// `b`, `h`, `sb`, `sh` or `sw`, appended to the mnemonic.
+    suffix: &'static str,
+}
+
+/// Classifies a single-register access, returning `None` for a prefetch
+/// or an unallocated combination.
+fn single_kind(size: u32, vector: bool, opc: u32) -> Option<Single> {
+    if vector {
+        let scale = if bit(opc, 1) {
+            if size != 0 {
+                return None;
+            }
+            4
+        } else {
+            size
+        };
+        return Some(Single {
+            bank: fp_bank(scale),
+            scale,
+            load: bit(opc, 0),
+            suffix: "",
+        });
+    }
+    let (bank, load, suffix) = match (size, opc) {
+        (0b00, 0b00) => (Bank::W, false, "b"),
+        (0b00, 0b01) => (Bank::W, true, "b"),
+        (0b00, 0b10) => (Bank::X, true, "sb"),
+        (0b00, 0b11) => (Bank::W, true, "sb"),
+        (0b01, 0b00) => (Bank::W, false, "h"),
+        (0b01, 0b01) => (Bank::W, true, "h"),
+        (0b01, 0b10) => (Bank::X, true, "sh"),
+        (0b01, 0b11) => (Bank::W, true, "sh"),
+        (0b10, 0b00) => (Bank::W, false, ""),
+        (0b10, 0b01) => (Bank::W, true, ""),
+        (0b10, 0b10) => (Bank::X, true, "sw"),
+        (0b11, 0b00) => (Bank::X, false, ""),
+        (0b11, 0b01) => (Bank::X, true, ""),
+        _ => return None,
+    };
+    Some(Single {
+        bank,
+        scale: size,
+        load,
+        suffix,
+    })
+}
+
+/// Single-register loads and stores: unsigned offset, unscaled, pre- and
+/// post-index, unprivileged and register offset; and the atomics and
+/// `ldraa`/`ldrab` sharing their encoding space.
+fn single(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 31, 30);
+    let vector = bit(word, 26);
+    let opc = bits(word, 23, 22);
+    let (rn, rt) = (bits(word, 9, 5), bits(word, 4, 0));
+    let base = gpr_sp(true, rn);
+    // `prfm` takes the place of a doubleword `ldrsw`.
+    let prefetch = !vector && size == 0b11 && opc == 0b10;
+
+    if bit(word, 24) {
+        let offset = i64::from(bits(word, 21, 10));
+        if prefetch {
+            w.insn("prfm");
+            prefetch_operation(w, rt);
+            w.mem(base, offset << 3, Index::Offset, Access::read());
+            return Some(());
+        }
+        let kind = single_kind(size, vector, opc)?;
+        w.insn(if kind.load { "ldr" } else { "str" });
+        w.suffix(kind.suffix);
+        let (reg_access, mem_access) = data(kind.load);
+        w.reg(reg(kind.bank, rt), reg_access);
+        w.mem(base, offset << kind.scale, Index::Offset, mem_access);
+        return Some(());
+    }
+
+    if bit(word, 21) {
+        return match bits(word, 11, 10) {
+            0b00 if !vector => atomic(word, w),
+            0b10 => register_offset(word, w),
+            _ if !vector && size == 0b11 => pointer_load(word, w),
+            _ => None,
+        };
+    }
+
+    let offset = sign_extend(bits(word, 20, 12), 9);
+    let mode = bits(word, 11, 10);
+    if prefetch {
+        if mode != 0b00 {
+            return None;
+        }
+        w.insn("prfum");
+        prefetch_operation(w, rt);
+        w.mem(base, offset, Index::Offset, Access::read());
+        return Some(());
+    }
+    let kind = single_kind(size, vector, opc)?;
+    let (prefix, index) = match mode {
+        0b00 => ("u", Index::Offset),
+        0b01 => ("", Index::Post),
+        0b10 if !vector => ("t", Index::Offset),
+        0b11 => ("", Index::Pre),
+        _ => return None,
+    };
+    w.insn(if kind.load { "ld" } else { "st" });
+    w.suffix(prefix);
+    w.suffix("r");
+    w.suffix(kind.suffix);
+    let (reg_access, mem_access) = data(kind.load);
+    w.reg(reg(kind.bank, rt), reg_access);
+    w.mem(base, offset, index, mem_access);
+    Some(())
+}
+
+/// `[base, index{, extend #amount}]` addressing.
+fn register_offset(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 31, 30);
+    let vector = bit(word, 26);
+    let opc = bits(word, 23, 22);
+    let option = bits(word, 15, 13);
+    let shifted = bit(word, 12);
+    let (rm, rn, rt) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let extend = match option {
+        0b010 => "uxtw",
+        0b011 => "lsl",
+        0b110 => "sxtw",
+        0b111 => "sxtx",
+        _ => return None,
+    };
+    let index = gpr(option & 1 == 1, rm);
+    let base = gpr_sp(true, rn);
+
+    let prefetch = !vector && size == 0b11 && opc == 0b10;
+    let (scale, mem_access) = if prefetch {
+        w.insn("prfm");
+        prefetch_operation(w, rt);
+        (3, Access::read())
+    } else {
+        let kind = single_kind(size, vector, opc)?;
+        w.insn(if kind.load { "ldr" } else { "str" });
+        w.suffix(kind.suffix);
+        let (reg_access, mem_access) = data(kind.load);
+        w.reg(reg(kind.bank, rt), reg_access);
+        (kind.scale, mem_access)
+    };
+    // A plain `lsl` is omitted when unscaled; the byte forms print the
+    // zero amount when S is set.
+    let extend = match (extend, shifted) {
+        ("lsl", false) => None,
+        (kind, false) => Some((kind, None)),
+        (kind, true) => Some((kind, Some(scale))),
+    };
+    w.mem_index(base, index, extend, mem_access);
+    Some(())
+}
+
+/// LSE atomic memory operations, `swp` and `ldapr`.
+fn atomic(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 31, 30);
+    let (acquire, release) = (bit(word, 23), bit(word, 22));
+    let (rs, rn, rt) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let wide = size == 0b11;
+    let suffix = ["b", "h", "", ""][size as usize];
+    let base = gpr_sp(true, rn);
+    let operation = match (bit(word, 15), bits(word, 14, 12)) {
+        (false, opc) => ["add", "clr", "eor", "set", "smax", "smin", "umax", "umin"][opc as usize],
+        (true, 0b000) => "swp",
+        (true, 0b100) if acquire && !release && rs == 0b11111 => {
+            w.insn("ldapr");
+            w.suffix(suffix);
+            w.reg(gpr(wide, rt), Access::write());
+            w.mem(base, 0, Index::Offset, Access::read());
+            return Some(());
+        }
+        _ => return None,
+    };
+    let ordering = format!(
+        "{}{}{}",
+        if acquire { "a" } else { "" },
+        if release { "l" } else { "" },
+        suffix
+    );
+    // Without acquire semantics and with the result discarded, the
+    // load-and-operate instructions print as `st<op>`.
+    if w.aliases && operation != "swp" && !acquire && rt == 0b11111 {
+        w.alias("st");
+        w.suffix(operation);
+        w.suffix(&ordering);
+        w.reg(gpr(wide, rs), Access::read());
+        w.mem(base, 0, Index::Offset, Access::read_write());
+        return Some(());
+    }
+    let mnemonic: String = if operation == "swp" {
+        operation.into()
+    } else {
+        format!("ld{operation}")
+    };
+    w.insn(&mnemonic);
+    w.suffix(&ordering);
+    w.reg(gpr(wide, rs), Access::read());
+    w.reg(gpr(wide, rt), Access::write());
+    w.mem(base, 0, Index::Offset, Access::read_write());
+    Some(())
+}
+
+/// `ldraa` and `ldrab`, loads through an authenticated pointer.
+fn pointer_load(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 10) {
+        return None;
+    }
+    let imm = u32::from(bit(word, 22)) << 9 | bits(word, 20, 12);
+    let disp = sign_extend(imm, 10) << 3;
+    let index = if bit(word, 11) {
+        Index::Pre
+    } else {
+        Index::Offset
+    };
+    w.insn(if bit(word, 23) { "ldrab" } else { "ldraa" });
+    w.reg(gpr(true, bits(word, 4, 0)), Access::write());
+    w.mem(gpr_sp(true, bits(word, 9, 5)), disp, index, Access::read());
+    Some(())
+}
+
+/// `ld1`-`ld4` and `st1`-`st4` (multiple structures), without offset or
+/// post-indexed.
+fn structures(word: u32, w: &mut Writer) -> Option<()> {
+    let post = bit(word, 23);
+    let load = bit(word, 22);
+    let rm = bits(word, 20, 16);
+    if !post && (rm != 0 || bit(word, 21)) || post && bit(word, 21) {
+        return None;
+    }
+    let (number, registers) = match bits(word, 15, 12) {
+        0b0000 => (4, 4),
+        0b0010 => (1, 4),
+        0b0100 => (3, 3),
+        0b0110 => (1, 3),
+        0b0111 => (1, 1),
+        0b1000 => (2, 2),
+        0b1010 => (1, 2),
+        _ => return None,
+    };
+    let q = bit(word, 30);
+    let size = bits(word, 11, 10);
+    if size == 0b11 && !q && number > 1 {
+        return None;
+    }
+    let arrangement = arrangement(size, q);
+    w.insn(if load { "ld" } else { "st" });
+    w.suffix(["", "1", "2", "3", "4"][number]);
+    let (reg_access, mem_access) = data(load);
+    w.vlist(bits(word, 4, 0), registers, arrangement, reg_access);
+    let bytes = i64::from(registers) * if q { 16 } else { 8 };
+    structure_address(word, bytes, mem_access, w);
+    Some(())
+}
+
+/// `ld1`-`ld4` and `st1`-`st4` of a single lane, and the `ld1r`-`ld4r`
+/// loads replicating a structure to all lanes.
+fn single_structure(word: u32, w: &mut Writer) -> Option<()> {
+    let post = bit(word, 23);
+    let load = bit(word, 22);
+    if !post && bits(word, 20, 16) != 0 {
+        return None;
+    }
+    let q = bit(word, 30);
+    let opcode = bits(word, 15, 13);
+    let (s, size) = (bit(word, 12), bits(word, 11, 10));
+    let number = ((opcode & 1) << 1 | u32::from(bit(word, 21))) + 1;
+    w.insn(if load { "ld" } else { "st" });
+    w.suffix(["", "1", "2", "3", "4"][number as usize]);
+    let rt = bits(word, 4, 0);
+    let mem_access = data(load).1;
+    // The lane index takes Q, S and the size bits a wider element leaves
+    // free.
+    let lane = u32::from(q) << 3 | u32::from(s) << 2 | size;
+    let (scale, index) = match opcode >> 1 {
+        0b00 => (0, lane),
+        0b01 if size & 1 == 0 => (1, lane >> 1),
+        0b10 if size == 0b00 => (2, lane >> 2),
+        0b10 if size == 0b01 && !s => (3, lane >> 3),
+        0b11 if load && !s => {
+            w.suffix("r");
+            w.vlist(rt, number, arrangement(size, q), Access::write());
+            structure_address(word, i64::from(number << size), mem_access, w);
+            return Some(());
+        }
+        _ => return None,
+    };
+    let kind = ['b', 'h', 's', 'd'][scale as usize];
+    // A lane load merges into the registers.
+    let access = if load {
+        Access::read_write()
+    } else {
+        Access::read()
+    };
+    w.vlist_lane(rt, number, kind, index, access);
+    structure_address(word, i64::from(number << scale), mem_access, w);
+    Some(())
+}
+
+/// Appends the address of a structure load or store of `bytes` bytes: the
+/// plain base, or with bit 23 set post-indexed by `bytes` or by `xm`.
+fn structure_address(word: u32, bytes: i64, access: Access, w: &mut Writer) {
+    let base = gpr_sp(true, bits(word, 9, 5));
+    let rm = bits(word, 20, 16);
+    if !bit(word, 23) {
+        w.mem(base, 0, Index::Offset, access);
+    } else if rm == 0b11111 {
+        w.mem(base, bytes, Index::Post, access);
+    } else {
+        w.mem(base, 0, Index::Offset, access);
+        w.reg(gpr(true, rm), Access::read());
+        w.set_writeback();
+    }
+}
+
+/// Vector arrangement of elements of `1 << size` bytes in a 64-bit or,
+/// with `q`, 128-bit register.
+pub(crate) fn arrangement(size: u32, q: bool) -> &'static str {
+    ["8b", "16b", "4h", "8h", "2s", "4s", "1d", "2d"][(size * 2 + u32::from(q)) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_loads_and_stores() {
+        let cases = [
+            (0xf940_0420, "ldr x0, [x1, #8]"),
+            (0xb940_1020, "ldr w0, [x1, #0x10]"),
+            (0x3940_0420, "ldrb w0, [x1, #1]"),
+            (0x7980_0020, "ldrsh x0, [x1]"),
+            (0xfd00_07e0, "str d0, [sp, #8]"),
+            (0x3dc0_0020, "ldr q0, [x1]"),
+            (0xf85f_8020, "ldur x0, [x1, #-8]"),
+            (0xf81f_0fe0, "str x0, [sp, #-0x10]!"),
+            (0xf841_0420, "ldr x0, [x1], #0x10"),
+            (0xb840_0820, "ldtr w0, [x1]"),
+            (0xf862_6820, "ldr x0, [x1, x2]"),
+            (0xf862_7820, "ldr x0, [x1, x2, lsl #3]"),
+            (0x3862_5820, "ldrb w0, [x1, w2, uxtw #0]"),
+            (0xb862_d820, "ldr w0, [x1, w2, sxtw #2]"),
+            (0xa9bf_7bfd, "stp x29, x30, [sp, #-0x10]!"),
+            (0xa8c1_7bfd, "ldp x29, x30, [sp], #0x10"),
+            (0x6940_8420, "ldpsw x0, x1, [x1, #4]"),
+            (0xad40_0420, "ldp q0, q1, [x1]"),
+            (0xf980_0020, "prfm pldl1keep, [x1]"),
+            (0x5800_0040, "ldr x0, #0x1008"),
+            (0xc85f_7c20, "ldxr x0, [x1]"),
+            (0xc802_fc20, "stlxr w2, x0, [x1]"),
+            (0xc8df_fc20, "ldar x0, [x1]"),
+            (0xc87f_0440, "ldxp x0, x1, [x2]"),
+            (0xc8e1_fc40, "casal x1, x0, [x2]"),
+            (0x0820_7c44, "casp w0, w1, w4, w5, [x2]"),
+            (0xf8e1_0040, "ldaddal x1, x0, [x2]"),
+            (0xb821_005f, "stadd w1, [x2]"),
+            (0x3861_805f, "swplb w1, wzr, [x2]"),
+            (0xf8bf_c020, "ldapr x0, [x1]"),
+            (0xf820_0420, "ldraa x0, [x1]"),
+            (0xf8e0_1c20, "ldrab x0, [x1, #-0xff8]!"),
+            (0x4c40_7020, "ld1 {v0.16b}, [x1]"),
+            (0x0c00_8020, "st2 {v0.8b, v1.8b}, [x1]"),
+            (0x4cdf_0820, "ld4 {v0.4s, v1.4s, v2.4s, v3.4s}, [x1], #0x40"),
+            (0x4cc2_7020, "ld1 {v0.16b}, [x1], x2"),
+            (0x4d40_0420, "ld1 {v0.b}[9], [x1]"),
+            (0x0d20_8020, "st2 {v0.s, v1.s}[0], [x1]"),
+            (0x4d40_a420, "ld3 {v0.d, v1.d, v2.d}[1], [x1]"),
+            (0x4ddf_c820, "ld1r {v0.4s}, [x1], #4"),
+            (
+                0x0dff_ec20,
+                "ld4r {v0.1d, v1.1d, v2.1d, v3.1d}, [x1], #0x20",
+            ),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0x1000), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,687 @@
+//! A64 instruction decoder.
+//!
+//! Instructions are 32 bits wide and aligned. The top-level encoding field
+//! (bits 25-28) selects one of the instruction classes, each decoded by its
+//! own module:
+//!
+//! - [`immediate`]: data processing with an immediate;
+//! - [`branch`]: branches, exception generation and system instructions;
+//! - [`load_store`]: loads and stores, including LSE atomics;
+//! - [`register`]: data processing on registers;
+//! - [`simd_fp`]: scalar floating point and Advanced SIMD;
+//! - [`sve`]: SVE and SVE2;
+//! - [`sme`]: the SME matrix instructions, in the reserved space with bit
+//!   31 set.
+//!
+//! Text follows Capstone: the aliases LLVM prints (`mov`, `cmp`, `lsl`,
+//! `cset`, ...), immediates above 9 in hexadecimal and PC-relative targets
+//! as absolute addresses. Unallocated encodings come out as `unknown`.
+
+mod branch;
+mod immediate;
+mod load_store;
+mod register;
+mod simd_fp;
+mod sme;
+mod sve;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use robustone_core::types::error::DisasmError;
+use robustone_core::types::options::ImmediateRadix;
+
+use crate::registers;
+use crate::types::{AArch64MemoryOperand, AArch64Operand, AArch64OperandValue, Access};
+
+/// Size of every A64 instruction, in bytes.
+pub const INSTRUCTION_SIZE: usize = 4;
+
+/// Condition code names, by encoding.
+const CONDITIONS: [&str; 16] = [
+    "eq", "ne", "hs", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "al", "nv",
+];
+
+/// A decoded instruction with its rendered text and operand details.
+#[derive(Debug, Clone)]
+pub struct AArch64DecodedInstruction {
+    /// The instruction word.
+    pub word: u32,
+    pub mnemonic: String,
+    pub operands: String,
+    /// Structured operands, in printed order.
+    pub operands_detail: Vec<AArch64Operand>,
+    /// Registers accessed without appearing as an operand, such as the
+    /// link register written by `bl`.
+    pub implicit_reads: Vec<u32>,
+    pub implicit_writes: Vec<u32>,
+    /// Whether the base register of the memory operand is written back.
+    pub writeback: bool,
+    /// Whether `mnemonic` is an alias of another instruction.
+    pub alias: bool,
+    /// Absolute target of a PC-relative branch.
+    pub target: Option<u64>,
+    pub size: usize,
+}
+
+impl AArch64DecodedInstruction {
+    /// Returns `true` for an encoding the decoder does not know.
+    pub fn is_unknown(&self) -> bool {
+        self.mnemonic == "unknown"
+    }
+}
+
+/// A64 decoder.
+#[derive(Debug, Clone)]
+pub struct AArch64Decoder {
+    big_endian: bool,
+    aliases: bool,
+    radix: ImmediateRadix,
+}
+
+impl Default for AArch64Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AArch64Decoder {
+    /// Creates a little-endian decoder printing aliases.
+    pub fn new() -> Self {
+        Self {
+            big_endian: false,
+            aliases: true,
+            radix: ImmediateRadix::Auto,
+        }
+    }
+
+    /// Creates a decoder reading big-endian instruction words.
+    pub fn big_endian() -> Self {
+        Self {
+            big_endian: true,
+            ..Self::new()
+        }
+    }
+
+    /// Returns whether instruction words are read big-endian.
+    pub fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+
+    /// Prints aliases such as `mov` and `cmp` instead of the instructions
+    /// they stand for when `aliases` is set.
+    pub fn set_aliases(&mut self, aliases: bool) {
+        self.aliases = aliases;
+    }
+
+    /// Sets the radix of immediate operands.
+    pub fn set_radix(&mut self, radix: ImmediateRadix) {
+        self.radix = radix;
+    }
+
+    /// Decodes the instruction at the start of `bytes`, located at
+    /// `address`.
+    pub fn decode(
+        &self,
+        bytes: &[u8],
+        address: u64,
+    ) -> Result<AArch64DecodedInstruction, DisasmError> {
+        if bytes.is_empty() {
+            return Err(DisasmError::DecodingError("No bytes provided".to_string()));
+        }
+        let Some(&[b0, b1, b2, b3]) = bytes.first_chunk::<INSTRUCTION_SIZE>() else {
+            return Err(DisasmError::DecodingError(
+                "Incomplete instruction".to_string(),
+            ));
+        };
+        let word = if self.big_endian {
+            u32::from_be_bytes([b0, b1, b2, b3])
+        } else {
+            u32::from_le_bytes([b0, b1, b2, b3])
+        };
+        Ok(self.decode_word(word, address))
+    }
+
+    /// Decodes the instruction `word` located at `address`.
+    pub fn decode_word(&self, word: u32, address: u64) -> AArch64DecodedInstruction {
+        let mut writer = Writer::new(self.aliases, self.radix);
+        let decoded = match bits(word, 28, 25) {
+            0b0000 if bit(word, 31) => sme::decode(word, &mut writer),
+            0b0000 => decode_reserved(word, &mut writer),
+            0b0010 => sve::decode(word, &mut writer),
+            0b1000 | 0b1001 => immediate::decode(word, address, &mut writer),
+            0b1010 | 0b1011 => branch::decode(word, address, &mut writer),
+            0b0100 | 0b0110 | 0b1100 | 0b1110 => load_store::decode(word, address, &mut writer),
+            0b0101 | 0b1101 => register::decode(word, &mut writer),
+            0b0111 | 0b1111 => simd_fp::decode(word, &mut writer),
+            _ => None,
+        };
+        if decoded.is_none() {
+            writer = Writer::new(self.aliases, self.radix);
+            writer.mnemonic = "unknown".to_string();
+            writer.text = format!("0x{word:08x}");
+        }
+        writer.finish(word)
+    }
+}
+
+/// `udf #imm16`, the permanently undefined encoding.
+fn decode_reserved(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 31, 16) != 0 {
+        return None;
+    }
+    w.insn("udf");
+    w.imm(i64::from(bits(word, 15, 0)));
+    Some(())
+}
+
+/// Extracts bits `hi..=lo` of `word`.
+pub(crate) fn bits(word: u32, hi: u32, lo: u32) -> u32 {
+    (word >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Tests bit `n` of `word`.
+pub(crate) fn bit(word: u32, n: u32) -> bool {
+    (word >> n) & 1 != 0
+}
+
+/// Sign-extends the low `width` bits of `value`.
+pub(crate) fn sign_extend(value: u32, width: u32) -> i64 {
+    let shift = 64 - width;
+    (i64::from(value) << shift) >> shift
+}
+
+/// Name of condition code `cond`.
+pub(crate) fn condition(cond: u32) -> &'static str {
+    CONDITIONS[(cond & 0xf) as usize]
+}
+
+/// Addressing mode of a memory operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Index {
+    /// `[base, #disp]`.
+    Offset,
+    /// `[base, #disp]!`, writing the address back.
+    Pre,
+    /// `[base], #disp`, writing the address plus `disp` back.
+    Post,
+}
+
+/// Offset of an SVE memory operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SveOffset<'a> {
+    /// `[base, #imm]`, printed as `[base]` when zero.
+    Imm(i64),
+    /// `[base, #imm, mul vl]`, scaled by the vector length.
+    MulVl(i64),
+    /// `[base, index{.size}{, extend {#amount}}]`, with a general or
+    /// vector index register.
+    Index(u32, &'a str, Option<(&'a str, Option<u32>)>),
+}
+
+/// Accumulates the mnemonic, operand text and operand details of one
+/// instruction.
+pub(crate) struct Writer {
+    /// Whether aliases are printed.
+    pub aliases: bool,
+    radix: ImmediateRadix,
+    mnemonic: String,
+    text: String,
+    operands: Vec<AArch64Operand>,
+    implicit_reads: Vec<u32>,
+    implicit_writes: Vec<u32>,
+    writeback: bool,
+    alias: bool,
+    target: Option<u64>,
+}
+
+impl Writer {
+    fn new(aliases: bool, radix: ImmediateRadix) -> Self {
+        Self {
+            aliases,
+            radix,
+            mnemonic: String::new(),
+            text: String::new(),
+            operands: Vec::new(),
+            implicit_reads: Vec::new(),
+            implicit_writes: Vec::new(),
+            writeback: false,
+            alias: false,
+            target: None,
+        }
+    }
+
+    fn finish(self, word: u32) -> AArch64DecodedInstruction {
+        AArch64DecodedInstruction {
+            word,
+            mnemonic: self.mnemonic,
+            operands: self.text,
+            operands_detail: self.operands,
+            implicit_reads: self.implicit_reads,
+            implicit_writes: self.implicit_writes,
+            writeback: self.writeback,
+            alias: self.alias,
+            target: self.target,
+            size: INSTRUCTION_SIZE,
+        }
+    }
+
+    /// Sets the mnemonic of a real instruction.
+    pub fn insn(&mut self, mnemonic: &str) {
+        self.mnemonic.clear();
+        self.mnemonic.push_str(mnemonic);
+    }
+
+    /// Sets the mnemonic of an alias.
+    pub fn alias(&mut self, mnemonic: &str) {
+        self.insn(mnemonic);
+        self.alias = true;
+    }
+
+    /// Appends to the mnemonic, for suffixes like `b.eq` or `ldaddal`.
+    pub fn suffix(&mut self, suffix: &str) {
+        self.mnemonic.push_str(suffix);
+    }
+
+    fn separate(&mut self) {
+        if !self.text.is_empty() {
+            self.text.push_str(", ");
+        }
+    }
+
+    fn push(&mut self, access: Access, value: AArch64OperandValue) {
+        self.operands.push(AArch64Operand { access, value });
+    }
+
+    /// Appends register `id`.
+    pub fn reg(&mut self, id: u32, access: Access) {
+        self.separate();
+        self.text.push_str(registers::name(id).unwrap_or("?"));
+        self.push(access, AArch64OperandValue::Register(id));
+    }
+
+    /// Appends vector register `num` with an arrangement, as `v0.16b`.
+    pub fn vreg(&mut self, num: u32, arrangement: &str, access: Access) {
+        self.separate();
+        let _ = write!(self.text, "v{num}.{arrangement}");
+        let id = registers::reg(registers::Bank::V, num);
+        self.push(access, AArch64OperandValue::Register(id));
+    }
+
+    /// Appends an element of vector register `num`, as `v0.s[1]`.
+    pub fn lane(&mut self, num: u32, kind: char, index: u32, access: Access) {
+        self.separate();
+        let _ = write!(self.text, "v{num}.{kind}[{index}]");
+        let id = registers::reg(registers::Bank::V, num);
+        self.push(access, AArch64OperandValue::Register(id));
+    }
+
+    /// Appends a list of consecutive vector registers, as `{v0.4s, v1.4s}`.
+    pub fn vlist(&mut self, first: u32, count: u32, arrangement: &str, access: Access) {
+        self.separate();
+        self.text.push('{');
+        for i in 0..count {
+            let num = (first + i) % 32;
+            if i > 0 {
+                self.text.push_str(", ");
+            }
+            let _ = write!(self.text, "v{num}.{arrangement}");
+            let id = registers::reg(registers::Bank::V, num);
+            self.push(access, AArch64OperandValue::Register(id));
+        }
+        self.text.push('}');
+    }
+
+    /// Appends a list of the same lane of consecutive vector registers, as
+    /// `{v0.s, v1.s}[1]`.
+    pub fn vlist_lane(&mut self, first: u32, count: u32, kind: char, index: u32, access: Access) {
+        let mut element = [0; 4];
+        self.vlist(first, count, kind.encode_utf8(&mut element), access);
+        let _ = write!(self.text, "[{index}]");
+    }
+
+    /// Appends SVE vector register `num` with an element size, as `z0.s`,
+    /// or alone as `z0` when `size` is empty.
+    pub fn zreg(&mut self, num: u32, size: &str, access: Access) {
+        self.sized(registers::reg(registers::Bank::Z, num), size, access);
+    }
+
+    /// Appends SVE predicate register `num` with an element size, as
+    /// `p0.b`, or alone as `p0` when `size` is empty.
+    pub fn preg(&mut self, num: u32, size: &str, access: Access) {
+        self.sized(registers::reg(registers::Bank::P, num), size, access);
+    }
+
+    fn sized(&mut self, id: u32, size: &str, access: Access) {
+        self.separate();
+        self.text.push_str(registers::name(id).unwrap_or("?"));
+        if !size.is_empty() {
+            self.text.push('.');
+            self.text.push_str(size);
+        }
+        self.push(access, AArch64OperandValue::Register(id));
+    }
+
+    /// Appends governing predicate `num` with its zeroing (`z`) or merging
+    /// (`m`) qualifier, as `p0/z`.
+    pub fn governing(&mut self, num: u32, qualifier: char) {
+        self.separate();
+        let _ = write!(self.text, "p{num}/{qualifier}");
+        let id = registers::reg(registers::Bank::P, num);
+        self.push(Access::read(), AArch64OperandValue::Register(id));
+    }
+
+    /// Appends an element of SVE vector register `num`, as `z0.s[1]`.
+    pub fn zlane(&mut self, num: u32, size: &str, index: u32, access: Access) {
+        self.zreg(num, size, access);
+        let _ = write!(self.text, "[{index}]");
+    }
+
+    /// Appends a list of consecutive SVE vector registers, as
+    /// `{z0.s, z1.s}`.
+    pub fn zlist(&mut self, first: u32, count: u32, size: &str, access: Access) {
+        self.separate();
+        self.text.push('{');
+        for i in 0..count {
+            let id = registers::reg(registers::Bank::Z, (first + i) % 32);
+            if i > 0 {
+                self.text.push_str(", ");
+            }
+            let _ = write!(self.text, "{}.{size}", registers::name(id).unwrap_or("?"));
+            self.push(access, AArch64OperandValue::Register(id));
+        }
+        self.text.push('}');
+    }
+
+    /// Appends an SME tile or slice, printed as `text`, standing for the
+    /// `za` array in the operand details.
+    pub fn za(&mut self, text: &str, access: Access) {
+        self.token(text);
+        self.push(access, AArch64OperandValue::Register(registers::ZA));
+    }
+
+    /// Appends the `[wv, offset]` selector of an SME slice or predicate
+    /// element to the previous operand, as in `p2.b[w15, 10]`.
+    pub fn slice_index(&mut self, wv: u32, offset: u32) {
+        let wv = registers::reg(registers::Bank::W, wv);
+        let _ = write!(
+            self.text,
+            "[{}, {offset}]",
+            registers::name(wv).unwrap_or("?")
+        );
+        self.implicit_reads.push(wv);
+    }
+
+    /// Appends a slice of SME tile `tile`, as `za0h.b[w12, 1]`, or as the
+    /// single-slice list `{za0h.b[w12, 1]}` of the loads and stores.
+    pub fn za_slice(&mut self, tile: &str, wv: u32, offset: u32, list: bool, access: Access) {
+        self.za(if list { "{" } else { "" }, access);
+        self.text.push_str(tile);
+        self.slice_index(wv, offset);
+        if list {
+            self.text.push('}');
+        }
+    }
+
+    /// Appends a signed immediate in the configured radix.
+    pub fn imm(&mut self, value: i64) {
+        self.separate();
+        write_imm(&mut self.text, value, self.radix);
+        self.push(Access::read(), AArch64OperandValue::Immediate(value));
+    }
+
+    /// Appends an unsigned immediate in the configured radix.
+    pub fn uimm(&mut self, value: u64) {
+        self.separate();
+        write_uimm(&mut self.text, value, self.radix);
+        self.push(Access::read(), AArch64OperandValue::Immediate(value as i64));
+    }
+
+    /// Appends an immediate that is always printed in hexadecimal, like
+    /// bit masks.
+    pub fn hex(&mut self, value: u64) {
+        self.separate();
+        let _ = write!(self.text, "#0x{value:x}");
+        self.push(Access::read(), AArch64OperandValue::Immediate(value as i64));
+    }
+
+    /// Appends the absolute address of a PC-relative branch.
+    pub fn target(&mut self, address: u64) {
+        self.target = Some(address);
+        self.uimm(address);
+    }
+
+    /// Appends a floating-point immediate.
+    pub fn float(&mut self, value: f64) {
+        self.separate();
+        let _ = write!(self.text, "#{value:.8}");
+        self.push(Access::read(), AArch64OperandValue::Float(value));
+    }
+
+    /// Appends a keyword operand, such as a condition or barrier option.
+    pub fn token(&mut self, token: &str) {
+        self.separate();
+        self.text.push_str(token);
+    }
+
+    /// Appends a multiplier of the previous operand, as `mul #4`.
+    pub fn multiplier(&mut self, value: u64) {
+        self.separate();
+        self.text.push_str("mul ");
+        write_uimm(&mut self.text, value, self.radix);
+        self.push(Access::read(), AArch64OperandValue::Immediate(value as i64));
+    }
+
+    /// Appends the rotation of a complex operation, always in decimal
+    /// degrees, as `#270`.
+    pub fn rotation(&mut self, degrees: u32) {
+        self.separate();
+        let _ = write!(self.text, "#{degrees}");
+        self.push(
+            Access::read(),
+            AArch64OperandValue::Immediate(i64::from(degrees)),
+        );
+    }
+
+    /// Appends a shift or extend of the previous operand, as `lsl #12`.
+    pub fn shift(&mut self, kind: &str, amount: u32) {
+        self.separate();
+        let _ = write!(self.text, "{kind} #{amount}");
+    }
+
+    /// Appends a memory operand `[base, #disp]` in the given mode. A
+    /// post-index displacement follows as an immediate operand.
+    pub fn mem(&mut self, base: u32, disp: i64, index: Index, access: Access) {
+        self.separate();
+        self.text.push('[');
+        self.text.push_str(registers::name(base).unwrap_or("?"));
+        let shown = match index {
+            Index::Offset if disp == 0 => 0,
+            Index::Post => 0,
+            _ => disp,
+        };
+        if shown != 0 || index == Index::Pre {
+            self.text.push_str(", ");
+            write_imm(&mut self.text, shown, self.radix);
+        }
+        self.text.push(']');
+        if index == Index::Pre {
+            self.text.push('!');
+        }
+        self.writeback = index != Index::Offset;
+        let memory = AArch64MemoryOperand {
+            base,
+            index: None,
+            disp: shown,
+        };
+        self.push(access, AArch64OperandValue::Memory(memory));
+        if index == Index::Post {
+            self.imm(disp);
+        }
+    }
+
+    /// Appends a register-offset memory operand `[base, index{, extend
+    /// {#amount}}]`.
+    pub fn mem_index(
+        &mut self,
+        base: u32,
+        index: u32,
+        extend: Option<(&str, Option<u32>)>,
+        access: Access,
+    ) {
+        self.separate();
+        let _ = write!(
+            self.text,
+            "[{}, {}",
+            registers::name(base).unwrap_or("?"),
+            registers::name(index).unwrap_or("?")
+        );
+        match extend {
+            Some((kind, Some(amount))) => {
+                let _ = write!(self.text, ", {kind} #{amount}");
+            }
+            Some((kind, None)) => {
+                let _ = write!(self.text, ", {kind}");
+            }
+            None => {}
+        }
+        self.text.push(']');
+        let memory = AArch64MemoryOperand {
+            base,
+            index: Some(index),
+            disp: 0,
+        };
+        self.push(access, AArch64OperandValue::Memory(memory));
+    }
+
+    /// Appends an SVE memory operand: `base`, with an element size for a
+    /// vector base, followed by `offset`.
+    pub fn mem_sve(&mut self, base: u32, size: &str, offset: SveOffset, access: Access) {
+        self.separate();
+        self.text.push('[');
+        self.text.push_str(registers::name(base).unwrap_or("?"));
+        if !size.is_empty() {
+            self.text.push('.');
+            self.text.push_str(size);
+        }
+        let (index, disp) = match offset {
+            SveOffset::Imm(0) | SveOffset::MulVl(0) => (None, 0),
+            SveOffset::Imm(disp) => {
+                self.text.push_str(", ");
+                write_imm(&mut self.text, disp, self.radix);
+                (None, disp)
+            }
+            SveOffset::MulVl(disp) => {
+                self.text.push_str(", ");
+                write_imm(&mut self.text, disp, self.radix);
+                self.text.push_str(", mul vl");
+                (None, disp)
+            }
+            SveOffset::Index(index, size, extend) => {
+                let _ = write!(self.text, ", {}", registers::name(index).unwrap_or("?"));
+                if !size.is_empty() {
+                    self.text.push('.');
+                    self.text.push_str(size);
+                }
+                match extend {
+                    Some((kind, Some(amount))) => {
+                        let _ = write!(self.text, ", {kind} #{amount}");
+                    }
+                    Some((kind, None)) => {
+                        let _ = write!(self.text, ", {kind}");
+                    }
+                    None => {}
+                }
+                (Some(index), 0)
+            }
+        };
+        self.text.push(']');
+        let memory = AArch64MemoryOperand { base, index, disp };
+        self.push(access, AArch64OperandValue::Memory(memory));
+    }
+
+    /// Marks the base register of the memory operand as written back, for
+    /// post-indexing by a register.
+    pub fn set_writeback(&mut self) {
+        self.writeback = true;
+    }
+
+    /// Records a register read without an operand.
+    pub fn implicit_read(&mut self, id: u32) {
+        self.implicit_reads.push(id);
+    }
+
+    /// Records a register written without an operand.
+    pub fn implicit_write(&mut self, id: u32) {
+        self.implicit_writes.push(id);
+    }
+}
+
+/// Writes `#value` the way Capstone prints immediates: values from -9 to
+/// 9 in decimal, others in hexadecimal, unless `radix` says otherwise.
+fn write_imm(out: &mut String, value: i64, radix: ImmediateRadix) {
+    let magnitude = value.unsigned_abs();
+    let sign = if value < 0 { "-" } else { "" };
+    let _ = match radix {
+        ImmediateRadix::Decimal => write!(out, "#{value}"),
+        ImmediateRadix::Auto if magnitude <= 9 => write!(out, "#{value}"),
+        _ => write!(out, "#{sign}0x{magnitude:x}"),
+    };
+}
+
+/// Unsigned counterpart of [`write_imm`].
+fn write_uimm(out: &mut String, value: u64, radix: ImmediateRadix) {
+    let _ = match radix {
+        ImmediateRadix::Decimal => write!(out, "#{value}"),
+        ImmediateRadix::Auto if value <= 9 => write!(out, "#{value}"),
+        _ => write!(out, "#0x{value:x}"),
+    };
+}
+
+/// Renders `word` decoded at `address` as `mnemonic operands`.
+#[cfg(test)]
+pub(crate) fn render(word: u32, address: u64) -> String {
+    let decoded = AArch64Decoder::new().decode_word(word, address);
+    if decoded.operands.is_empty() {
+        decoded.mnemonic
+    } else {
+        format!("{} {}", decoded.mnemonic, decoded.operands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let decoder = AArch64Decoder::new();
+        let nop = decoder.decode(&[0x1f, 0x20, 0x03, 0xd5], 0).unwrap();
+        assert_eq!((nop.mnemonic.as_str(), nop.size), ("nop", 4));
+        let nop = AArch64Decoder::big_endian()
+            .decode(&[0xd5, 0x03, 0x20, 0x1f], 0)
+            .unwrap();
+        assert_eq!(nop.mnemonic, "nop");
+        assert!(decoder.decode(&[0x1f, 0x20], 0).is_err());
+
+        assert_eq!(render(0x0000_0001, 0), "udf #1");
+        assert_eq!(render(0x25a0_c020, 0), "add z0.s, z0.s, #1");
+        assert_eq!(render(0xd503_477f, 0), "smstart");
+        // Bits 28-25 = 0b0001 are unallocated.
+        let unknown = decoder.decode_word(0x0200_0000, 0);
+        assert!(unknown.is_unknown());
+        assert_eq!(unknown.operands, "0x02000000");
+    }
+
+    #[test]
+    fn test_immediates() {
+        let mut text = String::new();
+        for value in [9, 10, -9, -16] {
+            write_imm(&mut text, value, ImmediateRadix::Auto);
+            text.push(' ');
+        }
+        write_imm(&mut text, 255, ImmediateRadix::Decimal);
+        assert_eq!(text, "#9 #0xa #-9 #-0x10 #255");
+    }
+}
@@ -0,0 +1,424 @@
+//! Data processing on registers: logical and arithmetic with shifted or
+//! extended operands, conditional compare and select, and the one-, two-
+//! and three-source instructions.
+
+use super::{Writer, bit, bits, condition};
+use crate::registers::{gpr, gpr_sp};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 28) {
+        return match (bit(word, 24), bit(word, 21)) {
+            (false, _) => logical(word, w),
+            (true, false) => add_sub_shifted(word, w),
+            (true, true) => add_sub_extended(word, w),
+        };
+    }
+    match bits(word, 24, 21) {
+        0b0000 if bits(word, 15, 10) == 0 => add_sub_carry(word, w),
+        0b0010 => conditional_compare(word, w),
+        0b0100 => conditional_select(word, w),
+        0b0110 if bit(word, 30) => one_source(word, w),
+        0b0110 => two_source(word, w),
+        0b1000..=0b1111 => three_source(word, w),
+        _ => None,
+    }
+}
+
+const SHIFTS: [&str; 4] = ["lsl", "lsr", "asr", "ror"];
+
+/// Appends the shift of a shifted-register operand, omitting `lsl #0`.
+fn shift(w: &mut Writer, kind: u32, amount: u32) {
+    if kind != 0 || amount != 0 {
+        w.shift(SHIFTS[kind as usize], amount);
+    }
+}
+
+/// `and`, `bic`, `orr`, `orn`, `eor`, `eon`, `ands` and `bics` with a
+/// shifted register, and the `mov`, `mvn` and `tst` aliases.
+fn logical(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let opc = bits(word, 30, 29);
+    let negate = bit(word, 21);
+    let (kind, amount) = (bits(word, 23, 22), bits(word, 15, 10));
+    if !sf && amount >= 32 {
+        return None;
+    }
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+
+    if w.aliases {
+        if opc == 0b01 && rn == 0b11111 && !(negate || kind != 0 || amount != 0) {
+            w.alias("mov");
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(gpr(sf, rm), Access::read());
+            return Some(());
+        }
+        if opc == 0b01 && rn == 0b11111 && negate {
+            w.alias("mvn");
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(gpr(sf, rm), Access::read());
+            shift(w, kind, amount);
+            return Some(());
+        }
+        if opc == 0b11 && !negate && rd == 0b11111 {
+            w.alias("tst");
+            w.reg(gpr(sf, rn), Access::read());
+            w.reg(gpr(sf, rm), Access::read());
+            shift(w, kind, amount);
+            return Some(());
+        }
+    }
+    let names = if negate {
+        ["bic", "orn", "eon", "bics"]
+    } else {
+        ["and", "orr", "eor", "ands"]
+    };
+    w.insn(names[opc as usize]);
+    w.reg(gpr(sf, rd), Access::write());
+    w.reg(gpr(sf, rn), Access::read());
+    w.reg(gpr(sf, rm), Access::read());
+    shift(w, kind, amount);
+    Some(())
+}
+
+/// Mnemonic of an add/subtract: `add`, `adds`, `sub` or `subs`.
+fn add_sub_name(sub: bool, flags: bool) -> &'static str {
+    match (sub, flags) {
+        (false, false) => "add",
+        (false, true) => "adds",
+        (true, false) => "sub",
+        (true, true) => "subs",
+    }
+}
+
+/// Add/subtract with a shifted register, and the `cmp`, `cmn`, `neg` and
+/// `negs` aliases.
+fn add_sub_shifted(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let (sub, flags) = (bit(word, 30), bit(word, 29));
+    let (kind, amount) = (bits(word, 23, 22), bits(word, 15, 10));
+    if kind == 0b11 || (!sf && amount >= 32) {
+        return None;
+    }
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+
+    if w.aliases && flags && rd == 0b11111 {
+        w.alias(if sub { "cmp" } else { "cmn" });
+        w.reg(gpr(sf, rn), Access::read());
+    } else if w.aliases && sub && rn == 0b11111 {
+        w.alias(if flags { "negs" } else { "neg" });
+        w.reg(gpr(sf, rd), Access::write());
+    } else {
+        w.insn(add_sub_name(sub, flags));
+        w.reg(gpr(sf, rd), Access::write());
+        w.reg(gpr(sf, rn), Access::read());
+    }
+    w.reg(gpr(sf, rm), Access::read());
+    shift(w, kind, amount);
+    Some(())
+}
+
+/// Add/subtract with an extended register. The extend prints as `lsl`,
+/// or not at all, when it is the identity and the stack pointer is
+/// involved.
+fn add_sub_extended(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let (sub, flags) = (bit(word, 30), bit(word, 29));
+    let (option, amount) = (bits(word, 15, 13), bits(word, 12, 10));
+    if bits(word, 23, 22) != 0 || amount > 4 {
+        return None;
+    }
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+
+    if w.aliases && flags && rd == 0b11111 {
+        w.alias(if sub { "cmp" } else { "cmn" });
+    } else {
+        w.insn(add_sub_name(sub, flags));
+        let rd = if flags { gpr(sf, rd) } else { gpr_sp(sf, rd) };
+        w.reg(rd, Access::write());
+    }
+    w.reg(gpr_sp(sf, rn), Access::read());
+    w.reg(gpr(sf && option & 0b11 == 0b11, rm), Access::read());
+
+    let uses_sp = (!flags && rd == 0b11111) || rn == 0b11111;
+    let identity = option == if sf { 0b011 } else { 0b010 };
+    if uses_sp && identity {
+        if amount != 0 {
+            w.shift("lsl", amount);
+        }
+    } else {
+        let name = [
+            "uxtb", "uxth", "uxtw", "uxtx", "sxtb", "sxth", "sxtw", "sxtx",
+        ][option as usize];
+        if amount == 0 {
+            w.token(name);
+        } else {
+            w.shift(name, amount);
+        }
+    }
+    Some(())
+}
+
+/// `adc`, `adcs`, `sbc` and `sbcs`, and the `ngc` and `ngcs` aliases.
+fn add_sub_carry(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let (sub, flags) = (bit(word, 30), bit(word, 29));
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    if w.aliases && sub && rn == 0b11111 {
+        w.alias(if flags { "ngcs" } else { "ngc" });
+        w.reg(gpr(sf, rd), Access::write());
+    } else {
+        w.insn(["adc", "adcs", "sbc", "sbcs"][usize::from(sub) * 2 + usize::from(flags)]);
+        w.reg(gpr(sf, rd), Access::write());
+        w.reg(gpr(sf, rn), Access::read());
+    }
+    w.reg(gpr(sf, rm), Access::read());
+    Some(())
+}
+
+/// `ccmn` and `ccmp` with a register or a 5-bit immediate.
+fn conditional_compare(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 29) || bit(word, 10) || bit(word, 4) {
+        return None;
+    }
+    let sf = bit(word, 31);
+    w.insn(if bit(word, 30) { "ccmp" } else { "ccmn" });
+    w.reg(gpr(sf, bits(word, 9, 5)), Access::read());
+    if bit(word, 11) {
+        w.uimm(u64::from(bits(word, 20, 16)));
+    } else {
+        w.reg(gpr(sf, bits(word, 20, 16)), Access::read());
+    }
+    w.uimm(u64::from(bits(word, 3, 0)));
+    w.token(condition(bits(word, 15, 12)));
+    Some(())
+}
+
+/// `csel`, `csinc`, `csinv` and `csneg`, with the `cset`, `csetm`,
+/// `cinc`, `cinv` and `cneg` aliases.
+fn conditional_select(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 29) || bit(word, 11) {
+        return None;
+    }
+    let sf = bit(word, 31);
+    let (invert, increment) = (bit(word, 30), bit(word, 10));
+    let cond = bits(word, 15, 12);
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let index = usize::from(invert) * 2 + usize::from(increment);
+
+    // The aliases test the inverted condition, which `al` and `nv` lack.
+    if w.aliases && index != 0 && rn == rm && cond < 0b1110 {
+        if rn == 0b11111 && index != 3 {
+            w.alias(if invert { "csetm" } else { "cset" });
+            w.reg(gpr(sf, rd), Access::write());
+        } else {
+            w.alias(["", "cinc", "cinv", "cneg"][index]);
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(gpr(sf, rn), Access::read());
+        }
+        w.token(condition(cond ^ 1));
+        return Some(());
+    }
+    w.insn(["csel", "csinc", "csinv", "csneg"][index]);
+    w.reg(gpr(sf, rd), Access::write());
+    w.reg(gpr(sf, rn), Access::read());
+    w.reg(gpr(sf, rm), Access::read());
+    w.token(condition(cond));
+    Some(())
+}
+
+/// Division, variable shifts, CRC32 and `pacga`.
+fn two_source(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 29) {
+        return None;
+    }
+    let sf = bit(word, 31);
+    let opcode = bits(word, 15, 10);
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let (rd, rn, rm) = match opcode {
+        0b000010 | 0b000011 => {
+            w.insn(if opcode == 0b000010 { "udiv" } else { "sdiv" });
+            (gpr(sf, rd), gpr(sf, rn), gpr(sf, rm))
+        }
+        0b001000..=0b001011 => {
+            let index = (opcode & 0b11) as usize;
+            if w.aliases {
+                w.alias(SHIFTS[index]);
+            } else {
+                w.insn(["lslv", "lsrv", "asrv", "rorv"][index]);
+            }
+            (gpr(sf, rd), gpr(sf, rn), gpr(sf, rm))
+        }
+        0b001100 if sf => {
+            w.insn("pacga");
+            (gpr(true, rd), gpr(true, rn), gpr_sp(true, rm))
+        }
+        0b010000..=0b010111 => {
+            let size = opcode & 0b11;
+            // Only the doubleword forms take a 64-bit data register.
+            if sf != (size == 0b11) {
+                return None;
+            }
+            w.insn(if opcode & 0b100 != 0 {
+                "crc32c"
+            } else {
+                "crc32"
+            });
+            w.suffix(["b", "h", "w", "x"][size as usize]);
+            (gpr(false, rd), gpr(false, rn), gpr(sf, rm))
+        }
+        _ => return None,
+    };
+    w.reg(rd, Access::write());
+    w.reg(rn, Access::read());
+    w.reg(rm, Access::read());
+    Some(())
+}
+
+/// Bit and byte reversal, `clz`, `cls` and pointer authentication.
+fn one_source(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 29) {
+        return None;
+    }
+    let sf = bit(word, 31);
+    let opcode = bits(word, 15, 10);
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 20, 16) {
+        0b00000 => {
+            let name = match (opcode, sf) {
+                (0b000000, _) => "rbit",
+                (0b000001, _) => "rev16",
+                (0b000010, false) | (0b000011, true) => "rev",
+                (0b000010, true) => "rev32",
+                (0b000100, _) => "clz",
+                (0b000101, _) => "cls",
+                _ => return None,
+            };
+            w.insn(name);
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(gpr(sf, rn), Access::read());
+        }
+        0b00001 if sf => {
+            const NAMES: [&str; 8] = [
+                "pacia", "pacib", "pacda", "pacdb", "autia", "autib", "autda", "autdb",
+            ];
+            match opcode {
+                0b000000..=0b000111 => {
+                    w.insn(NAMES[opcode as usize]);
+                    w.reg(gpr(true, rd), Access::read_write());
+                    w.reg(gpr_sp(true, rn), Access::read());
+                }
+                // The zero-modifier forms insert a `z` after the key.
+                0b001000..=0b001111 if rn == 0b11111 => {
+                    let name = NAMES[(opcode & 0b111) as usize];
+                    let (base, key) = name.split_at(name.len() - 1);
+                    w.insn(base);
+                    w.suffix("z");
+                    w.suffix(key);
+                    w.reg(gpr(true, rd), Access::read_write());
+                }
+                0b010000 | 0b010001 if rn == 0b11111 => {
+                    w.insn(if opcode == 0b010000 { "xpaci" } else { "xpacd" });
+                    w.reg(gpr(true, rd), Access::read_write());
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Multiply-add and multiply-subtract, with the `mul`, `mneg`, `smull`,
+/// `smnegl`, `umull` and `umnegl` aliases, and `smulh` and `umulh`.
+fn three_source(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 30, 29) != 0 {
+        return None;
+    }
+    let sf = bit(word, 31);
+    let subtract = bit(word, 15);
+    let (rm, ra, rn, rd) = (
+        bits(word, 20, 16),
+        bits(word, 14, 10),
+        bits(word, 9, 5),
+        bits(word, 4, 0),
+    );
+    let (name, alias, long) = match (bits(word, 23, 21), subtract) {
+        (0b000, false) => ("madd", "mul", false),
+        (0b000, true) => ("msub", "mneg", false),
+        (0b001, false) if sf => ("smaddl", "smull", true),
+        (0b001, true) if sf => ("smsubl", "smnegl", true),
+        (0b101, false) if sf => ("umaddl", "umull", true),
+        (0b101, true) if sf => ("umsubl", "umnegl", true),
+        // Ra should be all ones; LLVM ignores it.
+        (0b010, false) | (0b110, false) if sf => {
+            w.insn(if bit(word, 23) { "umulh" } else { "smulh" });
+            w.reg(gpr(true, rd), Access::write());
+            w.reg(gpr(true, rn), Access::read());
+            w.reg(gpr(true, rm), Access::read());
+            return Some(());
+        }
+        _ => return None,
+    };
+    let accumulate = !(w.aliases && ra == 0b11111);
+    if accumulate {
+        w.insn(name);
+    } else {
+        w.alias(alias);
+    }
+    w.reg(gpr(sf, rd), Access::write());
+    w.reg(gpr(sf && !long, rn), Access::read());
+    w.reg(gpr(sf && !long, rm), Access::read());
+    if accumulate {
+        w.reg(gpr(sf, ra), Access::read());
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_data_processing_register() {
+        let cases = [
+            (0xeb01_001f, "cmp x0, x1"),
+            (0xeb01_03ff, "cmp xzr, x1"),
+            (0xcb01_03e0, "neg x0, x1"),
+            (0xcb02_0820, "sub x0, x1, x2, lsl #2"),
+            (0x8b22_4020, "add x0, x1, w2, uxtw"),
+            (0x8b22_63e0, "add x0, sp, x2"),
+            (0x8b22_6020, "add x0, x1, x2, uxtx"),
+            (0xab22_63ff, "cmn sp, x2"),
+            (0x8b22_cc20, "add x0, x1, w2, sxtw #3"),
+            (0xaa02_03e0, "mov x0, x2"),
+            (0x2a22_0fe0, "mvn w0, w2, lsl #3"),
+            (0xea02_003f, "tst x1, x2"),
+            (0x5a1f_03e0, "ngc w0, wzr"),
+            (0x1a9f_17e0, "cset w0, eq"),
+            (0x1a81_04e0, "csinc w0, w7, w1, eq"),
+            (0x5a9f_13e0, "csetm w0, eq"),
+            (0xda81_3420, "cneg x0, x1, hs"),
+            (0x1a82_0020, "csel w0, w1, w2, eq"),
+            (0xfa41_0804, "ccmp x0, #1, #4, eq"),
+            (0x9ac2_0c20, "sdiv x0, x1, x2"),
+            (0x9ac2_2020, "lsl x0, x1, x2"),
+            (0x1ac2_4020, "crc32b w0, w1, w2"),
+            (0x9ac2_4c20, "crc32x w0, w1, x2"),
+            (0x9ac2_3020, "pacga x0, x1, x2"),
+            (0xdac0_0c20, "rev x0, x1"),
+            (0xdac0_1020, "clz x0, x1"),
+            (0xdac1_0020, "pacia x0, x1"),
+            (0xdac1_23e0, "paciza x0"),
+            (0xdac1_43e0, "xpaci x0"),
+            (0x9b02_7c20, "mul x0, x1, x2"),
+            (0x9b02_8c20, "msub x0, x1, x2, x3"),
+            (0x9b22_7c20, "smull x0, w1, w2"),
+            (0x9ba2_0c20, "umaddl x0, w1, w2, x3"),
+            (0x9bc2_7c20, "umulh x0, x1, x2"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! Scalar floating point and a core subset of Advanced SIMD: three-same
+//! arithmetic, element copies and modified-immediate moves. Other vector
+//! encodings are left to the `unknown` fallback.
+
+use super::load_store::arrangement;
+use super::{Writer, bit, bits, condition};
+use crate::registers::{Bank, gpr, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    // Scalar floating point lives at bits 30:24 = 0x1e/0x1f, bit 31
+    // selecting 64-bit general registers.
+    if bits(word, 30, 25) == 0b00_1111 {
+        return if bit(word, 24) {
+            three_source(word, w)
+        } else if bit(word, 21) {
+            scalar(word, w)
+        } else {
+            fixed_point(word, w)
+        };
+    }
+    if bit(word, 31) {
+        return None;
+    }
+    match bits(word, 28, 24) {
+        0b01110 if bit(word, 21) && bit(word, 10) => three_same(word, w),
+        0b01110 if bits(word, 23, 21) == 0 && !bit(word, 15) && bit(word, 10) => copy(word, w),
+        0b01111 if bits(word, 23, 19) == 0 && bits(word, 11, 10) == 0b01 => {
+            modified_immediate(word, w)
+        }
+        _ => None,
+    }
+}
+
+/// Register bank of a scalar of floating-point `ftype`: single, double or
+/// half precision.
+fn fp_bank(ftype: u32) -> Option<Bank> {
+    match ftype {
+        0b00 => Some(Bank::S),
+        0b01 => Some(Bank::D),
+        0b11 => Some(Bank::H),
+        _ => None,
+    }
+}
+
+/// Expands the 8-bit floating-point immediate of `fmov`.
+pub(super) fn float_immediate(imm8: u32) -> f64 {
+    let mut value = f64::from(16 + (imm8 & 0xf)) / 16.0;
+    let exponent = (((imm8 >> 4) & 0b111) ^ 0b100) as i32 - 3;
+    for _ in 0..exponent.max(0) {
+        value *= 2.0;
+    }
+    for _ in exponent..0 {
+        value /= 2.0;
+    }
+    if bit(imm8, 7) { -value } else { value }
+}
+
+/// Conversions between floating point and fixed point.
+fn fixed_point(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let bank = fp_bank(bits(word, 23, 22))?;
+    let scale = bits(word, 15, 10);
+    if !sf && scale < 32 {
+        return None;
+    }
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+    match (bits(word, 20, 19), bits(word, 18, 16)) {
+        (0b11, opcode @ (0b000 | 0b001)) => {
+            w.insn(if opcode == 0 { "fcvtzs" } else { "fcvtzu" });
+            w.reg(gpr(sf, rd), Access::write());
+            w.reg(reg(bank, rn), Access::read());
+        }
+        (0b00, opcode @ (0b010 | 0b011)) => {
+            w.insn(if opcode == 0b010 { "scvtf" } else { "ucvtf" });
+            w.reg(reg(bank, rd), Access::write());
+            w.reg(gpr(sf, rn), Access::read());
+        }
+        _ => return None,
+    }
+    w.uimm(u64::from(64 - scale));
+    Some(())
+}
+
+/// The scalar floating-point classes sharing bit 21: integer conversions,
+/// one- and two-source arithmetic, compares, immediates and selects.
+fn scalar(word: u32, w: &mut Writer) -> Option<()> {
+    let ftype = bits(word, 23, 22);
+    if bits(word, 15, 10) == 0 {
+        return integer_conversion(word, w);
+    }
+    let bank = fp_bank(ftype)?;
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    if bit(word, 31) {
+        return None;
+    }
+
+    if bits(word, 14, 10) == 0b10000 {
+        return one_source(word, bank, w);
+    }
+    if bits(word, 13, 10) == 0b1000 {
+        // Compares: bit 3 compares with zero, bit 4 signals on NaNs.
+        if bits(word, 15, 14) != 0 || bits(word, 2, 0) != 0 {
+            return None;
+        }
+        w.insn(if bit(word, 4) { "fcmpe" } else { "fcmp" });
+        w.reg(reg(bank, rn), Access::read());
+        if bit(word, 3) {
+            w.token("#0.0");
+        } else {
+            w.reg(reg(bank, rm), Access::read());
+        }
+        return Some(());
+    }
+    if bits(word, 12, 10) == 0b100 {
+        if bits(word, 9, 5) != 0 {
+            return None;
+        }
+        w.insn("fmov");
+        w.reg(reg(bank, rd), Access::write());
+        w.float(float_immediate(bits(word, 20, 13)));
+        return Some(());
+    }
+    match bits(word, 11, 10) {
+        0b01 => {
+            w.insn(if bit(word, 4) { "fccmpe" } else { "fccmp" });
+            w.reg(reg(bank, rn), Access::read());
+            w.reg(reg(bank, rm), Access::read());
+            w.uimm(u64::from(bits(word, 3, 0)));
+            w.token(condition(bits(word, 15, 12)));
+        }
+        0b10 => {
+            let name = [
+                "fmul", "fdiv", "fadd", "fsub", "fmax", "fmin", "fmaxnm", "fminnm", "fnmul",
+            ]
+            .get(bits(word, 15, 12) as usize)?;
+            w.insn(name);
+            w.reg(reg(bank, rd), Access::write());
+            w.reg(reg(bank, rn), Access::read());
+            w.reg(reg(bank, rm), Access::read());
+        }
+        _ => {
+            w.insn("fcsel");
+            w.reg(reg(bank, rd), Access::write());
+            w.reg(reg(bank, rn), Access::read());
+            w.reg(reg(bank, rm), Access::read());
+            w.token(condition(bits(word, 15, 12)));
+        }
+    }
+    Some(())
+}
+
+/// `fmov`, `fabs`, `fneg`, `fsqrt`, precision conversions and rounding.
+fn one_source(word: u32, bank: Bank, w: &mut Writer) -> Option<()> {
+    let opcode = bits(word, 20, 15);
+    let destination = match opcode {
+        0b000100 => Bank::S,
+        0b000101 => Bank::D,
+        0b000111 => Bank::H,
+        _ => bank,
+    };
+    let name = match opcode {
+        0b000000 => "fmov",
+        0b000001 => "fabs",
+        0b000010 => "fneg",
+        0b000011 => "fsqrt",
+        0b000100 | 0b000101 | 0b000111 if destination != bank => "fcvt",
+        0b001000 => "frintn",
+        0b001001 => "frintp",
+        0b001010 => "frintm",
+        0b001011 => "frintz",
+        0b001100 => "frinta",
+        0b001110 => "frintx",
+        0b001111 => "frinti",
+        _ => return None,
+    };
+    w.insn(name);
+    w.reg(reg(destination, bits(word, 4, 0)), Access::write());
+    w.reg(reg(bank, bits(word, 9, 5)), Access::read());
+    Some(())
+}
+
+/// Conversions between floating point and integers, and `fmov` between
+/// general and SIMD&FP registers.
+fn integer_conversion(word: u32, w: &mut Writer) -> Option<()> {
+    let sf = bit(word, 31);
+    let ftype = bits(word, 23, 22);
+    let (rmode, opcode) = (bits(word, 20, 19), bits(word, 18, 16));
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+
+    // `fmov x0, v1.d[1]` and back move the top half of a vector.
+    if sf && ftype == 0b10 && rmode == 0b01 && opcode & 0b110 == 0b110 {
+        w.insn("fmov");
+        if opcode == 0b110 {
+            w.reg(gpr(true, rd), Access::write());
+            w.lane(rn, 'd', 1, Access::read());
+        } else {
+            w.lane(rd, 'd', 1, Access::write());
+            w.reg(gpr(true, rn), Access::read());
+        }
+        return Some(());
+    }
+    let bank = fp_bank(ftype)?;
+    let to_integer = match (rmode, opcode) {
+        (_, 0b000 | 0b001) => {
+            let rounding = ["n", "p", "m", "z"][rmode as usize];
+            w.insn("fcvt");
+            w.suffix(rounding);
+            w.suffix(if opcode == 0 { "s" } else { "u" });
+            true
+        }
+        (0b00, 0b010 | 0b011) => {
+            w.insn(if opcode == 0b010 { "scvtf" } else { "ucvtf" });
+            false
+        }
+        (0b00, 0b100 | 0b101) => {
+            w.insn(if opcode == 0b100 { "fcvtas" } else { "fcvtau" });
+            true
+        }
+        (0b00, 0b110 | 0b111) => {
+            // Plain moves need matching sizes, apart from half precision.
+            if ftype != 0b11 && sf != (ftype == 0b01) {
+                return None;
+            }
+            w.insn("fmov");
+            opcode == 0b110
+        }
+        (0b11, 0b110) if !sf && ftype == 0b01 => {
+            w.insn("fjcvtzs");
+            true
+        }
+        _ => return None,
+    };
+    if to_integer {
+        w.reg(gpr(sf, rd), Access::write());
+        w.reg(reg(bank, rn), Access::read());
+    } else {
+        w.reg(reg(bank, rd), Access::write());
+        w.reg(gpr(sf, rn), Access::read());
+    }
+    Some(())
+}
+
+/// `fmadd`, `fmsub`, `fnmadd` and `fnmsub`.
+fn three_source(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 31) {
+        return None;
+    }
+    let bank = fp_bank(bits(word, 23, 22))?;
+    let index = usize::from(bit(word, 21)) * 2 + usize::from(bit(word, 15));
+    w.insn(["fmadd", "fmsub", "fnmadd", "fnmsub"][index]);
+    w.reg(reg(bank, bits(word, 4, 0)), Access::write());
+    w.reg(reg(bank, bits(word, 9, 5)), Access::read());
+    w.reg(reg(bank, bits(word, 20, 16)), Access::read());
+    w.reg(reg(bank, bits(word, 14, 10)), Access::read());
+    Some(())
+}
+
+/// Vector arithmetic with three registers of the same arrangement.
+fn three_same(word: u32, w: &mut Writer) -> Option<()> {
+    let q = bit(word, 30);
+    let unsigned = bit(word, 29);
+    let size = bits(word, 23, 22);
+    let opcode = bits(word, 15, 11);
+    let (rm, rn, rd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+
+    let (name, arrangement) = if opcode >= 0b11000 {
+        // Floating point: size<1> picks the operation, size<0> the
+        // precision.
+        let name = match (opcode, unsigned, bit(size, 1)) {
+            (0b11000, false, false) => "fmaxnm",
+            (0b11000, false, true) => "fminnm",
+            (0b11001, false, false) => "fmla",
+            (0b11001, false, true) => "fmls",
+            (0b11010, false, false) => "fadd",
+            (0b11010, false, true) => "fsub",
+            (0b11010, true, false) => "faddp",
+            (0b11010, true, true) => "fabd",
+            (0b11011, false, false) => "fmulx",
+            (0b11011, true, false) => "fmul",
+            (0b11100, false, false) => "fcmeq",
+            (0b11100, true, false) => "fcmge",
+            (0b11100, true, true) => "fcmgt",
+            (0b11110, false, false) => "fmax",
+            (0b11110, false, true) => "fmin",
+            (0b11110, true, false) => "fmaxp",
+            (0b11110, true, true) => "fminp",
+            (0b11111, true, false) => "fdiv",
+            _ => return None,
+        };
+        if bit(size, 0) && !q {
+            return None;
+        }
+        (name, arrangement(2 + (size & 1), q))
+    } else if opcode == 0b00011 {
+        // Bitwise operations use the size field as part of the opcode.
+        let names = if unsigned {
+            ["eor", "bsl", "bit", "bif"]
+        } else {
+            ["and", "bic", "orr", "orn"]
+        };
+        if w.aliases && !unsigned && size == 0b10 && rn == rm {
+            w.alias("mov");
+            let arrangement = arrangement(0, q);
+            w.vreg(rd, arrangement, Access::write());
+            w.vreg(rn, arrangement, Access::read());
+            return Some(());
+        }
+        (names[size as usize], arrangement(0, q))
+    } else {
+        let (signed, other) = match opcode {
+            0b00000 => ("shadd", "uhadd"),
+            0b00001 => ("sqadd", "uqadd"),
+            0b00010 => ("srhadd", "urhadd"),
+            0b00100 => ("shsub", "uhsub"),
+            0b00101 => ("sqsub", "uqsub"),
+            0b00110 => ("cmgt", "cmhi"),
+            0b00111 => ("cmge", "cmhs"),
+            0b01000 => ("sshl", "ushl"),
+            0b01001 => ("sqshl", "uqshl"),
+            0b01010 => ("srshl", "urshl"),
+            0b01011 => ("sqrshl", "uqrshl"),
+            0b01100 => ("smax", "umax"),
+            0b01101 => ("smin", "umin"),
+            0b01110 => ("sabd", "uabd"),
+            0b01111 => ("saba", "uaba"),
+            0b10000 => ("add", "sub"),
+            0b10001 => ("cmtst", "cmeq"),
+            0b10010 => ("mla", "mls"),
+            0b10011 => ("mul", "pmul"),
+            0b10100 => ("smaxp", "umaxp"),
+            0b10101 => ("sminp", "uminp"),
+            0b10111 if !unsigned => ("addp", ""),
+            _ => return None,
+        };
+        // Only the adds, compares and shifts have doubleword lanes.
+        let doublewords = matches!(
+            opcode,
+            0b00001 | 0b00101 | 0b00110 | 0b00111 | 0b01000..=0b01011 | 0b10000 | 0b10001 | 0b10111
+        );
+        if size == 0b11 && (!q || !doublewords) {
+            return None;
+        }
+        (if unsigned { other } else { signed }, arrangement(size, q))
+    };
+    w.insn(name);
+    let accumulate = matches!(
+        name,
+        "fmla" | "fmls" | "mla" | "mls" | "saba" | "uaba" | "bsl" | "bit" | "bif"
+    );
+    let access = if accumulate {
+        Access::read_write()
+    } else {
+        Access::write()
+    };
+    w.vreg(rd, arrangement, access);
+    w.vreg(rn, arrangement, Access::read());
+    w.vreg(rm, arrangement, Access::read());
+    Some(())
+}
+
+/// `dup`, `smov`, `umov` and `ins`, with the `mov` aliases of the last
+/// two.
+fn copy(word: u32, w: &mut Writer) -> Option<()> {
+    let q = bit(word, 30);
+    let imm5 = bits(word, 20, 16);
+    let imm4 = bits(word, 14, 11);
+    let (rn, rd) = (bits(word, 9, 5), bits(word, 4, 0));
+    if imm5 & 0b1111 == 0 {
+        return None;
+    }
+    // The lowest set bit of imm5 gives the element size, the bits above
+    // it the index.
+    let size = imm5.trailing_zeros();
+    let index = imm5 >> (size + 1);
+    let kind = ['b', 'h', 's', 'd'][size as usize];
+
+    if bit(word, 29) {
+        // `ins` (element); imm4 holds the source index.
+        if !q {
+            return None;
+        }
+        if w.aliases {
+            w.alias("mov");
+        } else {
+            w.insn("ins");
+        }
+        w.lane(rd, kind, index, Access::read_write());
+        w.lane(rn, kind, imm4 >> size, Access::read());
+        return Some(());
+    }
+    match imm4 {
+        0b0000 | 0b0001 => {
+            if size == 3 && !q {
+                return None;
+            }
+            w.insn("dup");
+            w.vreg(rd, arrangement(size, q), Access::write());
+            if imm4 == 0 {
+                w.lane(rn, kind, index, Access::read());
+            } else {
+                w.reg(gpr(size == 3, rn), Access::read());
+            }
+        }
+        0b0011 if q => {
+            if w.aliases {
+                w.alias("mov");
+            } else {
+                w.insn("ins");
+            }
+            w.lane(rd, kind, index, Access::read_write());
+            w.reg(gpr(size == 3, rn), Access::read());
+        }
+        0b0101 if size < 2 || (size == 2 && q) => {
+            w.insn("smov");
+            w.reg(gpr(q, rd), Access::write());
+            w.lane(rn, kind, index, Access::read());
+        }
+        0b0111 if q == (size == 3) => {
+            // `umov` of a full word or doubleword prints as `mov`.
+            if w.aliases && size >= 2 {
+                w.alias("mov");
+            } else {
+                w.insn("umov");
+            }
+            w.reg(gpr(q, rd), Access::write());
+            w.lane(rn, kind, index, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `movi`, `mvni`, `orr`, `bic` and `fmov` with a vector immediate.
+fn modified_immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let q = bit(word, 30);
+    let op = bit(word, 29);
+    let cmode = bits(word, 15, 12);
+    let imm8 = bits(word, 18, 16) << 5 | bits(word, 9, 5);
+    let rd = bits(word, 4, 0);
+
+    match cmode {
+        // 32-bit and 16-bit lanes, shifted by whole bytes.
+        0b0000..=0b1011 => {
+            let halfwords = cmode & 0b1000 != 0;
+            let amount = if halfwords {
+                (cmode >> 1 & 0b1) * 8
+            } else {
+                (cmode >> 1 & 0b11) * 8
+            };
+            let combine = cmode & 1 == 1;
+            let name = match (combine, op) {
+                (false, false) => "movi",
+                (false, true) => "mvni",
+                (true, false) => "orr",
+                (true, true) => "bic",
+            };
+            w.insn(name);
+            let access = if combine {
+                Access::read_write()
+            } else {
+                Access::write()
+            };
+            w.vreg(rd, arrangement(if halfwords { 1 } else { 2 }, q), access);
+            w.uimm(u64::from(imm8));
+            if amount != 0 {
+                w.shift("lsl", amount);
+            }
+        }
+        // 32-bit lanes shifting in ones.
+        0b1100 | 0b1101 => {
+            w.insn(if op { "mvni" } else { "movi" });
+            w.vreg(rd, arrangement(2, q), Access::write());
+            w.uimm(u64::from(imm8));
+            w.shift("msl", if cmode & 1 == 1 { 16 } else { 8 });
+        }
+        0b1110 if !op => {
+            w.insn("movi");
+            w.vreg(rd, arrangement(0, q), Access::write());
+            w.uimm(u64::from(imm8));
+        }
+        // Each bit of imm8 expands to a byte of ones or zeros.
+        0b1110 => {
+            let value = (0..8).fold(0u64, |value, i| {
+                if bit(imm8, i) {
+                    value | 0xff << (i * 8)
+                } else {
+                    value
+                }
+            });
+            w.insn("movi");
+            if q {
+                w.vreg(rd, "2d", Access::write());
+            } else {
+                w.reg(reg(Bank::D, rd), Access::write());
+            }
+            w.hex(value);
+        }
+        0b1111 if !op || q => {
+            w.insn("fmov");
+            w.vreg(
+                rd,
+                if op { "2d" } else { arrangement(2, q) },
+                Access::write(),
+            );
+            w.float(float_immediate(imm8));
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::float_immediate;
+    use crate::decoder::render;
+
+    #[test]
+    fn test_float_immediate() {
+        assert_eq!(float_immediate(0x70), 1.0);
+        assert_eq!(float_immediate(0x00), 2.0);
+        assert_eq!(float_immediate(0xf8), -1.5);
+        assert_eq!(float_immediate(0x40), 0.125);
+    }
+
+    #[test]
+    fn test_floating_point() {
+        let cases = [
+            (0x1e22_0020, "scvtf s0, w1"),
+            (0x1e78_0020, "fcvtzs w0, d1"),
+            (0x1e26_0020, "fmov w0, s1"),
+            (0x9e67_0020, "fmov d0, x1"),
+            (0x9eae_0020, "fmov x0, v1.d[1]"),
+            (0x1e18_c020, "fcvtzs w0, s1, #0x10"),
+            (0x1e61_c020, "fsqrt d0, d1"),
+            (0x1e22_c020, "fcvt d0, s1"),
+            (0x1ee2_4020, "fcvt s0, h1"),
+            (0x1e25_2020, "fcmp s1, s5"),
+            (0x1e20_2018, "fcmpe s0, #0.0"),
+            (0x1e2e_1000, "fmov s0, #1.00000000"),
+            (0x1e3f_1000, "fmov s0, #-1.50000000"),
+            (0x1e62_3c20, "fcsel d0, d1, d2, lo"),
+            (0x1e22_2820, "fadd s0, s1, s2"),
+            (0x1f22_8c20, "fnmsub s0, s1, s2, s3"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+
+    #[test]
+    fn test_advanced_simd() {
+        let cases = [
+            (0x4e22_8420, "add v0.16b, v1.16b, v2.16b"),
+            (0x6e22_8420, "sub v0.16b, v1.16b, v2.16b"),
+            (0x4ea2_1c20, "orr v0.16b, v1.16b, v2.16b"),
+            (0x4ea1_1c20, "mov v0.16b, v1.16b"),
+            (0x6e22_1c20, "eor v0.16b, v1.16b, v2.16b"),
+            (0x6ea0_8c00, "cmeq v0.4s, v0.4s, v0.4s"),
+            (0x4e22_d420, "fadd v0.4s, v1.4s, v2.4s"),
+            (0x2e22_fc20, "fdiv v0.2s, v1.2s, v2.2s"),
+            (0x4e0c_0420, "dup v0.4s, v1.s[1]"),
+            (0x4e04_0c20, "dup v0.4s, w1"),
+            (0x0e0c_3c20, "mov w0, v1.s[1]"),
+            (0x4e18_3c20, "mov x0, v1.d[1]"),
+            (0x4e0c_1c20, "mov v0.s[1], w1"),
+            (0x6e0c_2420, "mov v0.s[1], v1.s[1]"),
+            (0x4f00_0400, "movi v0.4s, #0"),
+            (0x4f00_e7e0, "movi v0.16b, #0x1f"),
+            (0x2f00_e7e0, "movi d0, #0xffffffffff"),
+            (0x4f00_25e0, "movi v0.4s, #0xf, lsl #8"),
+            (0x4f00_c5e0, "movi v0.4s, #0xf, msl #8"),
+            (0x2f00_8400, "mvni v0.4h, #0"),
+            (0x6f03_f600, "fmov v0.2d, #1.00000000"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! SME instructions at bits 28-25 = 0b0000 with bit 31 set: the outer
+//! products, the tile moves, `addha`/`addva` and `zero`, and the tile
+//! loads and stores.
+//!
+//! Tiles print as `za1.s`, their horizontal and vertical slices as
+//! `za1h.s[w12, 3]`; the operand details record the `za` array.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{SveOffset, Writer, bit, bits};
+use crate::registers::{gpr, gpr_sp};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    match bits(word, 31, 29) {
+        0b100 => float_outer_product(word, w),
+        0b101 => integer_outer_product(word, w),
+        0b110 => tile(word, w),
+        0b111 => load_store(word, w),
+        _ => None,
+    }
+}
+
+/// Tile element sizes, by the two-bit size field, then the quadword.
+const TILE_SIZES: [&str; 5] = ["b", "h", "s", "d", "q"];
+
+/// Appends the tile accumulating an outer product and its operands: the
+/// two merging predicates and the two vectors of `size` elements. A
+/// 32-bit tile leaves bits 3-2 clear, a 64-bit one bit 3.
+fn outer_product(w: &mut Writer, word: u32, wide: bool, size: &str) -> Option<()> {
+    let (tile, tile_size) = if wide {
+        (bits(word, 2, 0), "d")
+    } else {
+        (bits(word, 1, 0), "s")
+    };
+    if bit(word, 3) || (!wide && bit(word, 2)) {
+        return None;
+    }
+    w.suffix(if bit(word, 4) { "s" } else { "a" });
+    w.za(&format!("za{tile}.{tile_size}"), Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    w.governing(bits(word, 15, 13), 'm');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.zreg(bits(word, 20, 16), size, Access::read());
+    Some(())
+}
+
+/// `fmopa`, `fmops`, `bfmopa` and `bfmops`.
+fn float_outer_product(word: u32, w: &mut Writer) -> Option<()> {
+    let (name, wide, size) = match (bit(word, 24), bits(word, 23, 21)) {
+        (false, 0b100) => ("fmop", false, "s"),
+        (false, 0b110) => ("fmop", true, "d"),
+        (true, 0b100) => ("bfmop", false, "h"),
+        (true, 0b101) => ("fmop", false, "h"),
+        _ => return None,
+    };
+    w.insn(name);
+    outer_product(w, word, wide, size)
+}
+
+/// `smopa`, `umopa`, `sumopa`, `usmopa` and their subtracting forms, of
+/// bytes into 32-bit tiles or halfwords into 64-bit ones.
+fn integer_outer_product(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 23) {
+        return None;
+    }
+    w.insn(match (bit(word, 24), bit(word, 21)) {
+        (false, false) => "smop",
+        (false, true) => "sumop",
+        (true, false) => "usmop",
+        (true, true) => "umop",
+    });
+    let wide = bit(word, 22);
+    outer_product(w, word, wide, if wide { "h" } else { "b" })
+}
+
+/// Returns the text of the slice of the tile in the four-bit field
+/// `field` of `size`-sized elements, and its index: the tile number takes
+/// the high bits, as many as the size allows, and the index the others.
+fn slice(field: u32, size: u32, vertical: bool) -> (String, u32) {
+    let index_bits = 4 - size.min(4);
+    let tile = field >> index_bits;
+    let index = field & ((1 << index_bits) - 1);
+    let direction = if vertical { 'v' } else { 'h' };
+    let text = format!("za{tile}{direction}.{}", TILE_SIZES[size as usize]);
+    (text, index)
+}
+
+/// Moves between tile slices and vectors, `addha` and `addva`, and
+/// `zero`.
+fn tile(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 24) {
+        return None;
+    }
+    if bits(word, 23, 8) == 0b0000_1000_0000_0000 {
+        return zero(bits(word, 7, 0), w);
+    }
+    let size = bits(word, 23, 22);
+    match bits(word, 21, 17) {
+        op @ (0b00000 | 0b00001) => {
+            let quad = bit(word, 16);
+            if quad && size != 0b11 {
+                return None;
+            }
+            let size = if quad { 4 } else { size };
+            let element = TILE_SIZES[size as usize];
+            let wv = 12 + bits(word, 14, 13);
+            let pg = bits(word, 12, 10);
+            w.alias("mov");
+            if op == 0b00000 {
+                if bit(word, 4) {
+                    return None;
+                }
+                let (text, index) = slice(bits(word, 3, 0), size, bit(word, 15));
+                w.za_slice(&text, wv, index, false, Access::read_write());
+                w.governing(pg, 'm');
+                w.zreg(bits(word, 9, 5), element, Access::read());
+            } else {
+                if bit(word, 9) {
+                    return None;
+                }
+                let (text, index) = slice(bits(word, 8, 5), size, bit(word, 15));
+                w.zreg(bits(word, 4, 0), element, Access::read_write());
+                w.governing(pg, 'm');
+                w.za_slice(&text, wv, index, false, Access::read());
+            }
+        }
+        0b01000 if size >= 0b10 => {
+            let wide = size == 0b11;
+            let tile = bits(word, 2, 0);
+            if bits(word, 4, 3) != 0 || (!wide && bit(word, 2)) {
+                return None;
+            }
+            let element = TILE_SIZES[size as usize];
+            w.insn(if bit(word, 16) { "addva" } else { "addha" });
+            w.za(&format!("za{tile}.{element}"), Access::read_write());
+            w.governing(bits(word, 12, 10), 'm');
+            w.governing(bits(word, 15, 13), 'm');
+            w.zreg(bits(word, 9, 5), element, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `zero` of the 64-bit tiles in `mask`, printed as the fewest larger
+/// tiles that cover them exactly: the whole array, a 16-bit tile or a
+/// set of 32-bit tiles.
+fn zero(mask: u32, w: &mut Writer) -> Option<()> {
+    w.insn("zero");
+    let text = match mask {
+        0xff => String::from("{za}"),
+        0x55 | 0xaa => format!("{{za{}.h}}", mask >> 7),
+        _ if mask != 0 && mask & 0xf == mask >> 4 => tile_list(mask & 0xf, "s", 4),
+        _ => tile_list(mask, "d", 8),
+    };
+    w.za(&text, Access::write());
+    Some(())
+}
+
+/// Returns the list of the `count` tiles of `size` elements set in
+/// `mask`, as `{za0.s, za2.s}`.
+fn tile_list(mask: u32, size: &str, count: u32) -> String {
+    let tiles: Vec<String> = (0..count)
+        .filter(|tile| mask >> tile & 1 == 1)
+        .map(|tile| format!("za{tile}.{size}"))
+        .collect();
+    format!("{{{}}}", tiles.join(", "))
+}
+
+/// `ld1b`-`ld1q` and `st1b`-`st1q` of a tile slice, and `ldr` and `str` of
+/// an array vector.
+fn load_store(word: u32, w: &mut Writer) -> Option<()> {
+    let store = bit(word, 21);
+    let (rm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    let wv = 12 + bits(word, 14, 13);
+    if bit(word, 4) {
+        return None;
+    }
+    let size = match (bit(word, 24), bits(word, 23, 22)) {
+        (false, size) => size,
+        (true, 0b11) => 4,
+        (true, 0b00) => return array_vector(word, w),
+        _ => return None,
+    };
+    w.insn(if store { "st1" } else { "ld1" });
+    w.suffix(["b", "h", "w", "d", "q"][size as usize]);
+    let (text, index) = slice(bits(word, 3, 0), size, bit(word, 15));
+    let pg = bits(word, 12, 10);
+    if store {
+        w.za_slice(&text, wv, index, true, Access::read());
+        w.preg(pg, "", Access::read());
+    } else {
+        w.za_slice(&text, wv, index, true, Access::write());
+        w.governing(pg, 'z');
+    }
+    let offset = match rm {
+        0b11111 => SveOffset::Imm(0),
+        _ => SveOffset::Index(
+            gpr(true, rm),
+            "",
+            (size != 0).then_some(("lsl", Some(size))),
+        ),
+    };
+    let access = if store {
+        Access::write()
+    } else {
+        Access::read()
+    };
+    w.mem_sve(gpr_sp(true, rn), "", offset, access);
+    Some(())
+}
+
+/// `ldr za[w12, 1], [x0, #1, mul vl]` and `str`, the offset both indexing
+/// the array and scaling the address.
+fn array_vector(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 20, 15) != 0 || bits(word, 12, 10) != 0 {
+        return None;
+    }
+    let store = bit(word, 21);
+    let offset = bits(word, 3, 0);
+    let wv = 12 + bits(word, 14, 13);
+    w.insn(if store { "str" } else { "ldr" });
+    let (za, memory) = if store {
+        (Access::read(), Access::write())
+    } else {
+        (Access::write(), Access::read())
+    };
+    w.za_slice("za", wv, offset, false, za);
+    let disp = SveOffset::MulVl(i64::from(offset));
+    w.mem_sve(gpr_sp(true, bits(word, 9, 5)), "", disp, memory);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_sme() {
+        let cases = [
+            (0x8083_2041, "fmopa za1.s, p0/m, p1/m, z2.s, z3.s"),
+            (0x8185_6893, "bfmops za3.s, p2/m, p3/m, z4.h, z5.h"),
+            (0x80c2_0027, "fmopa za7.d, p0/m, p0/m, z1.d, z2.d"),
+            (0xa084_4462, "smopa za2.s, p1/m, p2/m, z3.b, z4.b"),
+            (0xa1c4_4476, "usmops za6.d, p1/m, p2/m, z3.h, z4.h"),
+            (0xc000_0023, "mov za0h.b[w12, 3], p0/m, z1.b"),
+            (0xc082_e5a2, "mov z2.s, p1/m, za3v.s[w15, 1]"),
+            (0xc0c1_a8ef, "mov za15v.q[w13, 0], p2/m, z7.q"),
+            (0xc090_2041, "addha za1.s, p0/m, p1/m, z2.s"),
+            (0xc008_00ff, "zero {za}"),
+            (0xc008_0055, "zero {za0.h}"),
+            (0xc008_0021, "zero {za0.d, za5.d}"),
+            (0xe001_000f, "ld1b {za0h.b[w12, 15]}, p0/z, [x0, x1]"),
+            (0xe0a2_c7ee, "st1w {za3v.s[w14, 2]}, p1, [sp, x2, lsl #2]"),
+            (0xe1df_7c0f, "ld1q {za15h.q[w15, 0]}, p7/z, [x0]"),
+            (0xe100_0023, "ldr za[w12, 3], [x1, #3, mul vl]"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! SVE floating point at bits 31-29 = 0b011: complex arithmetic, multiply
+//! by indexed element, the widening and BFloat16 operations, arithmetic,
+//! compares, reductions, rounding and conversions.
+
+use super::super::{Writer, bit, bits};
+use super::{SIZES, destructive, element, scalar, three};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 24) {
+        return if bit(word, 21) {
+            widening(word, w)
+        } else {
+            complex(word, w)
+        };
+    }
+    if bit(word, 21) {
+        return multiply_add(word, w);
+    }
+    match bits(word, 15, 13) {
+        0b000 => unpredicated(word, w),
+        0b001 => reduction(word, w),
+        0b100 => arithmetic(word, w),
+        0b101 => unary(word, w),
+        _ => compare(word, w),
+    }
+}
+
+/// Returns the element size of a floating-point operation, which has no
+/// byte form.
+fn float_size(word: u32) -> Option<&'static str> {
+    match bits(word, 23, 22) {
+        0 => None,
+        size => Some(SIZES[size as usize]),
+    }
+}
+
+/// `fcmla` and `fcadd` by vector, the pairwise operations and the
+/// conversions of odd elements.
+fn complex(word: u32, w: &mut Writer) -> Option<()> {
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    if !bit(word, 15) {
+        let size = float_size(word)?;
+        w.insn("fcmla");
+        w.zreg(zd, size, Access::read_write());
+        w.governing(bits(word, 12, 10), 'm');
+        w.zreg(zn, size, Access::read());
+        w.zreg(bits(word, 20, 16), size, Access::read());
+        w.rotation(bits(word, 14, 13) * 90);
+        return Some(());
+    }
+    match (bits(word, 15, 13), bits(word, 20, 16)) {
+        (0b100, 0b00000 | 0b00001) => {
+            let size = float_size(word)?;
+            w.insn("fcadd");
+            destructive(w, word, size);
+            w.rotation(if bit(word, 16) { 270 } else { 90 });
+        }
+        (0b100, 0b10000..=0b10111) => {
+            let name = match bits(word, 18, 16) {
+                0b000 => "faddp",
+                0b100 => "fmaxnmp",
+                0b101 => "fminnmp",
+                0b110 => "fmaxp",
+                0b111 => "fminp",
+                _ => return None,
+            };
+            let size = float_size(word)?;
+            w.insn(name);
+            destructive(w, word, size);
+        }
+        (0b101, 0b01000..=0b01011) => {
+            let (name, dest, source) = match (bits(word, 23, 22), bits(word, 17, 16)) {
+                (0b00, 0b10) => ("fcvtxnt", "s", "d"),
+                (0b10, 0b00) => ("fcvtnt", "h", "s"),
+                (0b10, 0b01) => ("fcvtlt", "s", "h"),
+                (0b10, 0b10) => ("bfcvtnt", "h", "s"),
+                (0b11, 0b10) => ("fcvtnt", "s", "d"),
+                (0b11, 0b11) => ("fcvtlt", "d", "s"),
+                _ => return None,
+            };
+            w.insn(name);
+            w.zreg(zd, dest, Access::read_write());
+            w.governing(bits(word, 12, 10), 'm');
+            w.zreg(zn, source, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// The multiplies by indexed element, the widening multiply-adds, the
+/// BFloat16 dot products and the matrix multiply-accumulates.
+fn widening(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 15, 10) {
+        op @ (0b000_000 | 0b000_001) => {
+            let (size, zm, index) = element(word);
+            w.insn(if op == 0 { "fmla" } else { "fmls" });
+            w.zreg(zd, size, Access::read_write());
+            w.zreg(zn, size, Access::read());
+            w.zlane(zm, size, index, Access::read());
+        }
+        0b000_100..=0b000_111 => {
+            let (size, zm, index) = match size {
+                0b10 => ("h", bits(word, 18, 16), bits(word, 20, 19)),
+                0b11 => ("s", bits(word, 19, 16), u32::from(bit(word, 20))),
+                _ => return None,
+            };
+            w.insn("fcmla");
+            w.zreg(zd, size, Access::read_write());
+            w.zreg(zn, size, Access::read());
+            w.zlane(zm, size, index, Access::read());
+            w.rotation(bits(word, 11, 10) * 90);
+        }
+        0b001_000 => {
+            let (size, zm, index) = element(word);
+            w.insn("fmul");
+            w.zreg(zd, size, Access::write());
+            w.zreg(zn, size, Access::read());
+            w.zlane(zm, size, index, Access::read());
+        }
+        0b010_000 if size == 0b01 => {
+            w.insn("bfdot");
+            w.zreg(zd, "s", Access::read_write());
+            w.zreg(zn, "h", Access::read());
+            w.zlane(bits(word, 18, 16), "h", bits(word, 20, 19), Access::read());
+        }
+        op @ 0b010_000..=0b011_111 if op & 0b100 == 0 => {
+            let name = multiply_add_long(size, op)?;
+            let index = bits(word, 20, 19) << 1 | u32::from(bit(word, 11));
+            w.insn(name);
+            w.zreg(zd, "s", Access::read_write());
+            w.zreg(zn, "h", Access::read());
+            w.zlane(bits(word, 18, 16), "h", index, Access::read());
+        }
+        0b100_000 if size == 0b01 => {
+            w.insn("bfdot");
+            w.zreg(zd, "s", Access::read_write());
+            w.zreg(zn, "h", Access::read());
+            w.zreg(bits(word, 20, 16), "h", Access::read());
+        }
+        op @ 0b100_000..=0b101_111 if op & 0b110 == 0 => {
+            let name = multiply_add_long(size, op)?;
+            w.insn(name);
+            w.zreg(zd, "s", Access::read_write());
+            w.zreg(zn, "h", Access::read());
+            w.zreg(bits(word, 20, 16), "h", Access::read());
+        }
+        0b111_001 => {
+            let (name, dest, source) = match size {
+                0b01 => ("bfmmla", "s", "h"),
+                0b10 => ("fmmla", "s", "s"),
+                0b11 => ("fmmla", "d", "d"),
+                _ => return None,
+            };
+            w.insn(name);
+            w.zreg(zd, dest, Access::read_write());
+            w.zreg(zn, source, Access::read());
+            w.zreg(bits(word, 20, 16), source, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Names the widening multiply-add selected by size field `size` and
+/// bits 13 and 10 of `op`: half precision, or BFloat16 without the
+/// subtracting forms.
+fn multiply_add_long(size: u32, op: u32) -> Option<&'static str> {
+    let top = op & 1 == 1;
+    Some(match (size, bit(op, 3), top) {
+        (0b10, false, false) => "fmlalb",
+        (0b10, false, true) => "fmlalt",
+        (0b10, true, false) => "fmlslb",
+        (0b10, true, true) => "fmlslt",
+        (0b11, false, false) => "bfmlalb",
+        (0b11, false, true) => "bfmlalt",
+        _ => return None,
+    })
+}
+
+/// `fmla`, `fmls`, `fnmla` and `fnmls` accumulating into the first
+/// operand, `fmad`, `fmsb`, `fnmad` and `fnmsb` into the multiplicand.
+fn multiply_add(word: u32, w: &mut Writer) -> Option<()> {
+    let size = float_size(word)?;
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let op = bits(word, 14, 13) as usize;
+    if bit(word, 15) {
+        w.insn(["fmad", "fmsb", "fnmad", "fnmsb"][op]);
+    } else {
+        w.insn(["fmla", "fmls", "fnmla", "fnmls"][op]);
+    }
+    w.zreg(zd, size, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    if bit(word, 15) {
+        // `fmad zdn, pg/m, zm, za`.
+        w.zreg(zn, size, Access::read());
+        w.zreg(zm, size, Access::read());
+    } else {
+        w.zreg(zn, size, Access::read());
+        w.zreg(zm, size, Access::read());
+    }
+    Some(())
+}
+
+/// Unpredicated arithmetic, `fadd z0.s, z1.s, z2.s`.
+fn unpredicated(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match bits(word, 12, 10) {
+        0b000 => "fadd",
+        0b001 => "fsub",
+        0b010 => "fmul",
+        0b011 => "ftsmul",
+        0b110 => "frecps",
+        0b111 => "frsqrts",
+        _ => return None,
+    };
+    let size = float_size(word)?;
+    w.insn(name);
+    three(
+        w,
+        size,
+        bits(word, 4, 0),
+        bits(word, 9, 5),
+        bits(word, 20, 16),
+    );
+    Some(())
+}
+
+/// The reductions to a scalar, `fadda`, the unpredicated estimates and the
+/// compares with zero.
+fn reduction(word: u32, w: &mut Writer) -> Option<()> {
+    let size_field = bits(word, 23, 22);
+    let size = float_size(word)?;
+    let (pg, zn, d) = (bits(word, 12, 10), bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 20, 16) {
+        op @ 0b00000..=0b00111 => {
+            let name = match op {
+                0b000 => "faddv",
+                0b100 => "fmaxnmv",
+                0b101 => "fminnmv",
+                0b110 => "fmaxv",
+                0b111 => "fminv",
+                _ => return None,
+            };
+            w.insn(name);
+            w.reg(scalar(size_field, d), Access::write());
+            w.preg(pg, "", Access::read());
+            w.zreg(zn, size, Access::read());
+        }
+        op @ (0b01110 | 0b01111) if bits(word, 12, 10) == 0b100 => {
+            w.insn(if op == 0b01110 { "frecpe" } else { "frsqrte" });
+            w.zreg(d, size, Access::write());
+            w.zreg(zn, size, Access::read());
+        }
+        op @ 0b10000..=0b10011 => {
+            let name = match (op & 0b11, bit(word, 4)) {
+                (0b00, false) => "fcmge",
+                (0b00, true) => "fcmgt",
+                (0b01, false) => "fcmlt",
+                (0b01, true) => "fcmle",
+                (0b10, false) => "fcmeq",
+                (0b11, false) => "fcmne",
+                _ => return None,
+            };
+            w.insn(name);
+            w.preg(bits(word, 3, 0), size, Access::write());
+            w.governing(pg, 'z');
+            w.zreg(zn, size, Access::read());
+            w.token("#0.0");
+        }
+        0b11000 => {
+            w.insn("fadda");
+            w.reg(scalar(size_field, d), Access::read_write());
+            w.preg(pg, "", Access::read());
+            w.reg(scalar(size_field, d), Access::read());
+            w.zreg(zn, size, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Predicated arithmetic by vector and by immediate, and `ftmad`.
+fn arithmetic(word: u32, w: &mut Writer) -> Option<()> {
+    let size = float_size(word)?;
+    let zdn = bits(word, 4, 0);
+    match bits(word, 20, 19) {
+        0b00 | 0b01 => {
+            let name = match bits(word, 19, 16) {
+                0b0000 => "fadd",
+                0b0001 => "fsub",
+                0b0010 => "fmul",
+                0b0011 => "fsubr",
+                0b0100 => "fmaxnm",
+                0b0101 => "fminnm",
+                0b0110 => "fmax",
+                0b0111 => "fmin",
+                0b1000 => "fabd",
+                0b1001 => "fscale",
+                0b1010 => "fmulx",
+                0b1100 => "fdivr",
+                0b1101 => "fdiv",
+                _ => return None,
+            };
+            w.insn(name);
+            destructive(w, word, size);
+        }
+        0b10 => {
+            if bits(word, 12, 10) != 0 {
+                return None;
+            }
+            w.insn("ftmad");
+            w.zreg(zdn, size, Access::read_write());
+            w.zreg(zdn, size, Access::read());
+            w.zreg(bits(word, 9, 5), size, Access::read());
+            w.uimm(u64::from(bits(word, 18, 16)));
+        }
+        _ => {
+            if bits(word, 9, 6) != 0 {
+                return None;
+            }
+            let op = bits(word, 18, 16) as usize;
+            let name = [
+                "fadd", "fsub", "fmul", "fsubr", "fmaxnm", "fminnm", "fmax", "fmin",
+            ][op];
+            let immediates = match op {
+                0b010 => ["#0.5", "#2.0"],
+                0b100..=0b111 => ["#0.0", "#1.0"],
+                _ => ["#0.5", "#1.0"],
+            };
+            w.insn(name);
+            w.zreg(zdn, size, Access::read_write());
+            w.governing(bits(word, 12, 10), 'm');
+            w.zreg(zdn, size, Access::read());
+            w.token(immediates[usize::from(bit(word, 5))]);
+        }
+    }
+    Some(())
+}
+
+/// Predicated unary operations: rounding, `frecpx`, `fsqrt`, `flogb` and
+/// the conversions.
+fn unary(word: u32, w: &mut Writer) -> Option<()> {
+    let opc = bits(word, 23, 22);
+    let (pg, zn, zd) = (bits(word, 12, 10), bits(word, 9, 5), bits(word, 4, 0));
+    let (name, dest, source) = match bits(word, 20, 16) {
+        op @ 0b00000..=0b00111 => {
+            let name = match op {
+                0b000 => "frintn",
+                0b001 => "frintp",
+                0b010 => "frintm",
+                0b011 => "frintz",
+                0b100 => "frinta",
+                0b110 => "frintx",
+                0b111 => "frinti",
+                _ => return None,
+            };
+            let size = float_size(word)?;
+            (name, size, size)
+        }
+        0b01000..=0b01011 => match (opc, bits(word, 17, 16)) {
+            (0b00, 0b10) => ("fcvtx", "s", "d"),
+            (0b10, 0b00) => ("fcvt", "h", "s"),
+            (0b10, 0b01) => ("fcvt", "s", "h"),
+            (0b10, 0b10) => ("bfcvt", "h", "s"),
+            (0b11, 0b00) => ("fcvt", "h", "d"),
+            (0b11, 0b01) => ("fcvt", "d", "h"),
+            (0b11, 0b10) => ("fcvt", "s", "d"),
+            (0b11, 0b11) => ("fcvt", "d", "s"),
+            _ => return None,
+        },
+        op @ (0b01100 | 0b01101) => {
+            let size = float_size(word)?;
+            (if op == 0b01100 { "frecpx" } else { "fsqrt" }, size, size)
+        }
+        op @ 0b10000..=0b11111 => {
+            let to_integer = bit(op, 3);
+            let unsigned = bit(op, 0);
+            if !to_integer && opc == 0b00 {
+                return None;
+            }
+            if to_integer && opc == 0b00 {
+                // `flogb`, with the size in bits 18-17.
+                let size = bits(op, 2, 1);
+                if unsigned || size == 0 {
+                    return None;
+                }
+                let size = SIZES[size as usize];
+                ("flogb", size, size)
+            } else {
+                let (dest, source) = match (opc, bits(op, 2, 1)) {
+                    (0b01, 0b01) => ("h", "h"),
+                    (0b01, 0b10) => ("h", "s"),
+                    (0b01, 0b11) => ("h", "d"),
+                    (0b10, 0b10) => ("s", "s"),
+                    (0b11, 0b00) => ("d", "s"),
+                    (0b11, 0b10) => ("s", "d"),
+                    (0b11, 0b11) => ("d", "d"),
+                    _ => return None,
+                };
+                let name = match (to_integer, unsigned) {
+                    (false, false) => "scvtf",
+                    (false, true) => "ucvtf",
+                    (true, false) => "fcvtzs",
+                    (true, true) => "fcvtzu",
+                };
+                // The conversions to integers swap the operand sizes.
+                if to_integer {
+                    (name, source, dest)
+                } else {
+                    (name, dest, source)
+                }
+            }
+        }
+        _ => return None,
+    };
+    w.insn(name);
+    w.zreg(zd, dest, Access::read_write());
+    w.governing(pg, 'm');
+    w.zreg(zn, source, Access::read());
+    Some(())
+}
+
+/// Compares of two vectors, `fcmge p0.s, p1/z, z0.s, z1.s`.
+fn compare(word: u32, w: &mut Writer) -> Option<()> {
+    let size = float_size(word)?;
+    let name = match (bit(word, 15), bit(word, 13), bit(word, 4)) {
+        (false, false, false) => "fcmge",
+        (false, false, true) => "fcmgt",
+        (false, true, false) => "fcmeq",
+        (false, true, true) => "fcmne",
+        (true, false, false) => "fcmuo",
+        (true, false, true) => "facge",
+        (true, true, true) => "facgt",
+        (true, true, false) => return None,
+    };
+    w.insn(name);
+    w.preg(bits(word, 3, 0), size, Access::write());
+    w.governing(bits(word, 12, 10), 'z');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.zreg(bits(word, 20, 16), size, Access::read());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_float() {
+        let cases = [
+            (0x6580_8020, "fadd z0.s, p0/m, z0.s, z1.s"),
+            (0x65e5_0c82, "fmla z2.d, p3/m, z4.d, z5.d"),
+            (0x643a_2020, "fmul z0.h, z1.h, z2.h[3]"),
+            (0x65cb_a020, "fcvt z0.d, p0/m, z1.s"),
+            (0x6590_2051, "fcmgt p1.s, p0/z, z2.s, #0.0"),
+            (0x6441_8020, "fcadd z0.h, p0/m, z0.h, z1.h, #270"),
+            (0x6580_2440, "faddv s0, p1, z2.s"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
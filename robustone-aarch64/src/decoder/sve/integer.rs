@@ -0,0 +1,960 @@
+//! SVE integer data processing at bits 31-29 = 0b000: arithmetic, logical
+//! and shift operations, element counts, logical immediates and permutes.
+
+use super::super::immediate::bit_mask;
+use super::super::{SveOffset, Writer, bit, bits, sign_extend};
+use super::{SIZES, destructive, pattern_multiplier, scalar, three};
+use crate::registers::{Bank, gpr, gpr_sp, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 24) {
+        return if bit(word, 21) {
+            permute(word, w)
+        } else {
+            immediate(word, w)
+        };
+    }
+    if !bit(word, 21) {
+        return match bits(word, 15, 13) {
+            0b000 => binary_predicated(word, w),
+            0b001 => reduction(word, w),
+            0b010 | 0b011 | 0b110 | 0b111 => multiply_add(word, w),
+            0b100 => shift_predicated(word, w),
+            _ => unary_predicated(word, w),
+        };
+    }
+    match bits(word, 15, 10) {
+        0b000_000..=0b000_111 => add_sub(word, w),
+        0b001_100 => logical(word, w),
+        0b001_101 => xar(word, w),
+        0b001_110 | 0b001_111 => ternary(word, w),
+        0b010_000..=0b010_011 => index(word, w),
+        0b010_100..=0b010_111 => stack(word, w),
+        0b011_000..=0b011_111 => multiply(word, w),
+        0b100_000..=0b100_111 => shift(word, w),
+        0b101_000..=0b101_011 => adr(word, w),
+        0b101_100..=0b101_111 => misc(word, w),
+        _ => count(word, w),
+    }
+}
+
+/// Destructive predicated arithmetic, `add z0.s, p0/m, z0.s, z1.s`.
+fn binary_predicated(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let name = match bits(word, 20, 16) {
+        0b00000 => "add",
+        0b00001 => "sub",
+        0b00011 => "subr",
+        0b01000 => "smax",
+        0b01001 => "umax",
+        0b01010 => "smin",
+        0b01011 => "umin",
+        0b01100 => "sabd",
+        0b01101 => "uabd",
+        0b10000 => "mul",
+        0b10010 => "smulh",
+        0b10011 => "umulh",
+        0b10100 if size >= 2 => "sdiv",
+        0b10101 if size >= 2 => "udiv",
+        0b10110 if size >= 2 => "sdivr",
+        0b10111 if size >= 2 => "udivr",
+        0b11000 => "orr",
+        0b11001 => "eor",
+        0b11010 => "and",
+        0b11011 => "bic",
+        _ => return None,
+    };
+    w.insn(name);
+    destructive(w, word, SIZES[size as usize]);
+    Some(())
+}
+
+/// Reductions to a scalar, `uaddv d0, p0, z1.s`, and the predicated
+/// `movprfx`.
+fn reduction(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (pg, zn, d) = (bits(word, 12, 10), bits(word, 9, 5), bits(word, 4, 0));
+    let name = match bits(word, 20, 16) {
+        0b00000 if size != 0b11 => "saddv",
+        0b00001 => "uaddv",
+        0b01000 => "smaxv",
+        0b01001 => "umaxv",
+        0b01010 => "sminv",
+        0b01011 => "uminv",
+        opc @ (0b10000 | 0b10001) => {
+            w.insn("movprfx");
+            w.zreg(d, SIZES[size as usize], Access::write());
+            w.governing(pg, if opc & 1 == 1 { 'm' } else { 'z' });
+            w.zreg(zn, SIZES[size as usize], Access::read());
+            return Some(());
+        }
+        0b11000 => "orv",
+        0b11001 => "eorv",
+        0b11010 => "andv",
+        _ => return None,
+    };
+    w.insn(name);
+    // The additions widen to a doubleword.
+    let destination = if name.ends_with("addv") { 3 } else { size };
+    w.reg(scalar(destination, d), Access::write());
+    w.preg(pg, "", Access::read());
+    w.zreg(zn, SIZES[size as usize], Access::read());
+    Some(())
+}
+
+/// `mla` and `mls` accumulating into the first operand, `mad` and `msb`
+/// into the multiplicand.
+fn multiply_add(word: u32, w: &mut Writer) -> Option<()> {
+    let size = SIZES[bits(word, 23, 22) as usize];
+    let subtract = bit(word, 13);
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    if bit(word, 15) {
+        w.insn(if subtract { "msb" } else { "mad" });
+    } else {
+        w.insn(if subtract { "mls" } else { "mla" });
+    }
+    w.zreg(zd, size, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    if bit(word, 15) {
+        // `mad zdn, pg/m, zm, za`.
+        w.zreg(zm, size, Access::read());
+        w.zreg(zn, size, Access::read());
+    } else {
+        w.zreg(zn, size, Access::read());
+        w.zreg(zm, size, Access::read());
+    }
+    Some(())
+}
+
+/// Element size and shift amount of a shift by immediate: the position of
+/// the highest set bit of `tsize` gives the size, the bits below it and
+/// `imm3` the amount.
+pub(super) fn shift_immediate(tsize: u32, imm3: u32, left: bool) -> Option<(u32, u32)> {
+    if tsize == 0 {
+        return None;
+    }
+    let size = 31 - tsize.leading_zeros();
+    let esize = 8 << size;
+    let value = tsize << 3 | imm3;
+    Some(if left {
+        (size, value - esize)
+    } else {
+        (size, 2 * esize - value)
+    })
+}
+
+/// Predicated shifts by immediate, by vector and by wide elements.
+fn shift_predicated(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    if !bit(word, 20) {
+        let (name, left) = match bits(word, 19, 16) {
+            0b0000 => ("asr", false),
+            0b0001 => ("lsr", false),
+            0b0011 => ("lsl", true),
+            0b0100 => ("asrd", false),
+            0b0110 => ("sqshl", true),
+            0b0111 => ("uqshl", true),
+            0b1100 => ("srshr", false),
+            0b1101 => ("urshr", false),
+            0b1111 => ("sqshlu", true),
+            _ => return None,
+        };
+        let tsize = size << 2 | bits(word, 9, 8);
+        let (size, amount) = shift_immediate(tsize, bits(word, 7, 5), left)?;
+        let size = SIZES[size as usize];
+        let zdn = bits(word, 4, 0);
+        w.insn(name);
+        w.zreg(zdn, size, Access::read_write());
+        w.governing(bits(word, 12, 10), 'm');
+        w.zreg(zdn, size, Access::read());
+        w.uimm(u64::from(amount));
+        return Some(());
+    }
+    let wide = bit(word, 19);
+    let name = match bits(word, 18, 16) {
+        0b000 => "asr",
+        0b001 => "lsr",
+        0b011 => "lsl",
+        0b100 if !wide => "asrr",
+        0b101 if !wide => "lsrr",
+        0b111 if !wide => "lslr",
+        _ => return None,
+    };
+    if wide && size == 0b11 {
+        return None;
+    }
+    let zdn = bits(word, 4, 0);
+    let elements = SIZES[size as usize];
+    w.insn(name);
+    w.zreg(zdn, elements, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    w.zreg(zdn, elements, Access::read());
+    w.zreg(
+        bits(word, 9, 5),
+        if wide { "d" } else { elements },
+        Access::read(),
+    );
+    Some(())
+}
+
+/// Predicated unary operations, `abs z0.s, p0/m, z1.s`.
+fn unary_predicated(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let name = match bits(word, 20, 16) {
+        0b10000 if size >= 1 => "sxtb",
+        0b10001 if size >= 1 => "uxtb",
+        0b10010 if size >= 2 => "sxth",
+        0b10011 if size >= 2 => "uxth",
+        0b10100 if size == 3 => "sxtw",
+        0b10101 if size == 3 => "uxtw",
+        0b10110 => "abs",
+        0b10111 => "neg",
+        0b11000 => "cls",
+        0b11001 => "clz",
+        0b11010 => "cnt",
+        0b11011 => "cnot",
+        0b11100 if size >= 1 => "fabs",
+        0b11101 if size >= 1 => "fneg",
+        0b11110 => "not",
+        _ => return None,
+    };
+    let size = SIZES[size as usize];
+    w.insn(name);
+    w.zreg(bits(word, 4, 0), size, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    Some(())
+}
+
+/// Unpredicated `add`, `sub` and their saturating forms.
+fn add_sub(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match bits(word, 12, 10) {
+        0b000 => "add",
+        0b001 => "sub",
+        0b100 => "sqadd",
+        0b101 => "uqadd",
+        0b110 => "sqsub",
+        0b111 => "uqsub",
+        _ => return None,
+    };
+    w.insn(name);
+    let size = SIZES[bits(word, 23, 22) as usize];
+    three(
+        w,
+        size,
+        bits(word, 4, 0),
+        bits(word, 9, 5),
+        bits(word, 20, 16),
+    );
+    Some(())
+}
+
+/// Unpredicated `and`, `orr`, `eor` and `bic`, with the `mov` alias of
+/// `orr`.
+fn logical(word: u32, w: &mut Writer) -> Option<()> {
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    let opc = bits(word, 23, 22);
+    if w.aliases && opc == 0b01 && zn == zm {
+        w.alias("mov");
+        w.zreg(zd, "d", Access::write());
+        w.zreg(zn, "d", Access::read());
+        return Some(());
+    }
+    w.insn(["and", "orr", "eor", "bic"][opc as usize]);
+    three(w, "d", zd, zn, zm);
+    Some(())
+}
+
+/// `xar`, exclusive-or and rotate by an immediate.
+fn xar(word: u32, w: &mut Writer) -> Option<()> {
+    let tsize = bits(word, 23, 22) << 2 | bits(word, 20, 19);
+    let (size, amount) = shift_immediate(tsize, bits(word, 18, 16), false)?;
+    let size = SIZES[size as usize];
+    let zdn = bits(word, 4, 0);
+    w.insn("xar");
+    w.zreg(zdn, size, Access::read_write());
+    w.zreg(zdn, size, Access::read());
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.uimm(u64::from(amount));
+    Some(())
+}
+
+/// The SVE2 bitwise ternary operations, `eor3 z0.d, z0.d, z1.d, z2.d`.
+fn ternary(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match (bits(word, 23, 22), bit(word, 10)) {
+        (0b00, false) => "eor3",
+        (0b01, false) => "bcax",
+        (0b00, true) => "bsl",
+        (0b01, true) => "bsl1n",
+        (0b10, true) => "bsl2n",
+        (0b11, true) => "nbsl",
+        _ => return None,
+    };
+    let zdn = bits(word, 4, 0);
+    w.insn(name);
+    w.zreg(zdn, "d", Access::read_write());
+    w.zreg(zdn, "d", Access::read());
+    w.zreg(bits(word, 20, 16), "d", Access::read());
+    w.zreg(bits(word, 9, 5), "d", Access::read());
+    Some(())
+}
+
+/// `index`, from an immediate or register start and increment.
+fn index(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (rm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    w.insn("index");
+    w.zreg(bits(word, 4, 0), SIZES[size as usize], Access::write());
+    let wide = size == 0b11;
+    if bit(word, 10) {
+        w.reg(gpr(wide, rn), Access::read());
+    } else {
+        w.imm(sign_extend(rn, 5));
+    }
+    if bit(word, 11) {
+        w.reg(gpr(wide, rm), Access::read());
+    } else {
+        w.imm(sign_extend(rm, 5));
+    }
+    Some(())
+}
+
+/// `addvl`, `addpl` and `rdvl`, and their streaming counterparts `addsvl`,
+/// `addspl` and `rdsvl`.
+fn stack(word: u32, w: &mut Writer) -> Option<()> {
+    let streaming = bit(word, 11);
+    let imm = sign_extend(bits(word, 10, 5), 6);
+    let (rn, rd) = (bits(word, 20, 16), bits(word, 4, 0));
+    match bits(word, 23, 22) {
+        op @ (0b00 | 0b01) => {
+            let name = match (op, streaming) {
+                (0b00, false) => "addvl",
+                (0b00, true) => "addsvl",
+                (_, false) => "addpl",
+                (_, true) => "addspl",
+            };
+            w.insn(name);
+            w.reg(gpr_sp(true, rd), Access::write());
+            w.reg(gpr_sp(true, rn), Access::read());
+        }
+        0b10 if rn == 0b11111 => {
+            w.insn(if streaming { "rdsvl" } else { "rdvl" });
+            w.reg(gpr(true, rd), Access::write());
+        }
+        _ => return None,
+    }
+    w.imm(imm);
+    Some(())
+}
+
+/// The SVE2 unpredicated multiplies.
+fn multiply(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let name = match bits(word, 12, 10) {
+        0b000 => "mul",
+        0b001 if size == 0 => "pmul",
+        0b010 => "smulh",
+        0b011 => "umulh",
+        0b100 => "sqdmulh",
+        0b101 => "sqrdmulh",
+        _ => return None,
+    };
+    w.insn(name);
+    three(
+        w,
+        SIZES[size as usize],
+        bits(word, 4, 0),
+        bits(word, 9, 5),
+        bits(word, 20, 16),
+    );
+    Some(())
+}
+
+/// Unpredicated shifts by wide elements and by immediate.
+fn shift(word: u32, w: &mut Writer) -> Option<()> {
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let opc = bits(word, 11, 10);
+    let name = match opc {
+        0b00 => "asr",
+        0b01 => "lsr",
+        0b11 => "lsl",
+        _ => return None,
+    };
+    if !bit(word, 12) {
+        let size = bits(word, 23, 22);
+        if size == 0b11 {
+            return None;
+        }
+        w.insn(name);
+        w.zreg(zd, SIZES[size as usize], Access::write());
+        w.zreg(zn, SIZES[size as usize], Access::read());
+        w.zreg(bits(word, 20, 16), "d", Access::read());
+        return Some(());
+    }
+    let tsize = bits(word, 23, 22) << 2 | bits(word, 20, 19);
+    let (size, amount) = shift_immediate(tsize, bits(word, 18, 16), opc == 0b11)?;
+    w.insn(name);
+    w.zreg(zd, SIZES[size as usize], Access::write());
+    w.zreg(zn, SIZES[size as usize], Access::read());
+    w.uimm(u64::from(amount));
+    Some(())
+}
+
+/// `adr z0.s, [z1.s, z2.s, lsl #2]`.
+fn adr(word: u32, w: &mut Writer) -> Option<()> {
+    let amount = bits(word, 11, 10);
+    let (size, extend) = match bits(word, 23, 22) {
+        0b00 => ("d", "sxtw"),
+        0b01 => ("d", "uxtw"),
+        0b10 => ("s", "lsl"),
+        _ => ("d", "lsl"),
+    };
+    let extend = match (extend, amount) {
+        ("lsl", 0) => None,
+        (_, 0) => Some((extend, None)),
+        _ => Some((extend, Some(amount))),
+    };
+    w.insn("adr");
+    w.zreg(bits(word, 4, 0), size, Access::write());
+    let base = reg(Bank::Z, bits(word, 9, 5));
+    let index = reg(Bank::Z, bits(word, 20, 16));
+    w.mem_sve(
+        base,
+        size,
+        SveOffset::Index(index, size, extend),
+        Access::none(),
+    );
+    Some(())
+}
+
+/// `ftssel`, `fexpa` and the unpredicated `movprfx`.
+fn misc(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 11, 10) {
+        0b00 if size != 0 => {
+            w.insn("ftssel");
+            three(w, SIZES[size as usize], zd, zn, zm);
+        }
+        0b10 if size != 0 && zm == 0 => {
+            w.insn("fexpa");
+            w.zreg(zd, SIZES[size as usize], Access::write());
+            w.zreg(zn, SIZES[size as usize], Access::read());
+        }
+        0b11 if size == 0 && zm == 0 => {
+            w.insn("movprfx");
+            w.zreg(zd, "", Access::write());
+            w.zreg(zn, "", Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Element counts: `cnt`, `inc` and `dec` of registers and vectors, and
+/// their saturating forms.
+fn count(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let letter = ["b", "h", "w", "d"][size as usize];
+    let rd = bits(word, 4, 0);
+    let op = bits(word, 15, 10);
+    let to_register = bit(word, 13);
+    match (op, bit(word, 20)) {
+        (0b111_000, false) => {
+            w.insn("cnt");
+            w.suffix(letter);
+            w.reg(gpr(true, rd), Access::write());
+        }
+        (0b111_000 | 0b111_001, true) | (0b110_000 | 0b110_001, true) => {
+            if !to_register && size == 0 {
+                return None;
+            }
+            w.insn(if bit(word, 10) { "dec" } else { "inc" });
+            w.suffix(letter);
+            if to_register {
+                w.reg(gpr(true, rd), Access::read_write());
+            } else {
+                w.zreg(rd, SIZES[size as usize], Access::read_write());
+            }
+        }
+        (0b110_000..=0b110_011, false) | (0b111_100..=0b111_111, _) => {
+            if !to_register && size == 0 {
+                return None;
+            }
+            let unsigned = bit(word, 10);
+            let name = match (bit(word, 11), unsigned) {
+                (false, false) => "sqinc",
+                (false, true) => "uqinc",
+                (true, false) => "sqdec",
+                (true, true) => "uqdec",
+            };
+            w.insn(name);
+            w.suffix(letter);
+            if !to_register {
+                w.zreg(rd, SIZES[size as usize], Access::read_write());
+            } else if bit(word, 20) {
+                w.reg(gpr(true, rd), Access::read_write());
+            } else if unsigned {
+                w.reg(gpr(false, rd), Access::read_write());
+            } else {
+                // The signed 32-bit forms sign-extend into the 64-bit
+                // register.
+                w.reg(gpr(true, rd), Access::write());
+                w.reg(gpr(false, rd), Access::read());
+            }
+        }
+        _ => return None,
+    }
+    pattern_multiplier(w, word);
+    Some(())
+}
+
+/// Logical immediates, `dupm`, and the predicated copies of an immediate.
+fn immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let zd = bits(word, 4, 0);
+    if bits(word, 21, 20) == 0b01 {
+        return copy_immediate(word, w);
+    }
+    if bits(word, 21, 18) != 0 {
+        return None;
+    }
+    // The element size is encoded with the immediate, in N:imms.
+    let (n, immr, imms) = (bit(word, 17), bits(word, 16, 11), bits(word, 10, 5));
+    let size = if n {
+        3
+    } else {
+        match imms {
+            0b000000..=0b011111 => 2,
+            0b100000..=0b101111 => 1,
+            0b110000..=0b111101 => 0,
+            _ => return None,
+        }
+    };
+    let value = bit_mask(n, immr, imms, 64)? & element_mask(size);
+    let elements = SIZES[size as usize];
+    let opc = bits(word, 23, 22);
+    if opc == 0b11 {
+        if w.aliases && !is_dup_immediate(value, size) {
+            w.alias("mov");
+            w.zreg(zd, elements, Access::write());
+            // LLVM prints the alias's immediate in decimal when it fits
+            // in 16 bits.
+            let signed = sign_extend_element(value, size);
+            if i64::from(signed as i16) == signed {
+                w.imm(signed);
+            } else if value <= 0xffff {
+                w.uimm(value);
+            } else {
+                w.hex(value);
+            }
+            return Some(());
+        }
+        w.insn("dupm");
+        w.zreg(zd, elements, Access::write());
+    } else {
+        w.insn(["orr", "eor", "and"][opc as usize]);
+        w.zreg(zd, elements, Access::read_write());
+        w.zreg(zd, elements, Access::read());
+    }
+    w.hex(value);
+    Some(())
+}
+
+/// Mask of the bits of an element of size field `size`.
+fn element_mask(size: u32) -> u64 {
+    u64::MAX >> (64 - (8 << size))
+}
+
+/// Sign-extends an element of size field `size`.
+fn sign_extend_element(value: u64, size: u32) -> i64 {
+    let shift = 64 - (8 << size);
+    (value << shift) as i64 >> shift
+}
+
+/// Whether `value` fits the signed 8-bit, optionally shifted, immediate of
+/// `dup`, in which case LLVM prints a `dupm` as `dupm` rather than `mov`.
+fn is_dup_immediate(value: u64, size: u32) -> bool {
+    let signed = sign_extend_element(value, size);
+    if (-128..128).contains(&signed) {
+        return true;
+    }
+    size != 0 && signed % 256 == 0 && (-128 * 256..128 * 256).contains(&signed)
+}
+
+/// `cpy` and `fcpy` of an immediate under a predicate, printed as `mov`
+/// and `fmov`.
+fn copy_immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let (pg, zd) = (bits(word, 19, 16), bits(word, 4, 0));
+    let imm8 = bits(word, 12, 5);
+    if bits(word, 15, 13) == 0b110 {
+        if size == 0 {
+            return None;
+        }
+        w.insn(if w.aliases { "fmov" } else { "fcpy" });
+        w.zreg(zd, elements, Access::read_write());
+        w.governing(pg, 'm');
+        w.float(super::super::simd_fp::float_immediate(imm8));
+        return Some(());
+    }
+    if bit(word, 15) {
+        return None;
+    }
+    let shifted = bit(word, 13);
+    if shifted && size == 0 {
+        return None;
+    }
+    let merging = bit(word, 14);
+    w.insn(if w.aliases { "mov" } else { "cpy" });
+    let access = if merging {
+        Access::read_write()
+    } else {
+        Access::write()
+    };
+    w.zreg(zd, elements, access);
+    w.governing(pg, if merging { 'm' } else { 'z' });
+    shifted_immediate(w, sign_extend(imm8, 8), shifted);
+    Some(())
+}
+
+/// Appends an 8-bit immediate shifted left by 8 when `shifted`, which LLVM
+/// prints scaled apart from a shifted zero.
+pub(super) fn shifted_immediate(w: &mut Writer, value: i64, shifted: bool) {
+    if shifted && value == 0 {
+        w.imm(0);
+        w.shift("lsl", 8);
+    } else if shifted {
+        w.imm(value << 8);
+    } else {
+        w.imm(value);
+    }
+}
+
+/// The permutes of bit 21 = 1: `ext`, `dup`, `tbl`, `insr`, `rev`,
+/// unpacks, zips, the predicated permutes and `sel`.
+fn permute(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 15, 13) {
+        0b000 => extract(word, w),
+        0b001 => match bits(word, 12, 10) {
+            0b000 => dup_indexed(word, w),
+            0b010 | 0b011 => {
+                w.insn(if bit(word, 10) { "tbx" } else { "tbl" });
+                if bit(word, 10) {
+                    w.zreg(zd, elements, Access::read_write());
+                    w.zreg(zn, elements, Access::read());
+                } else {
+                    w.zreg(zd, elements, Access::write());
+                    w.zlist(zn, 2, elements, Access::read());
+                }
+                w.zreg(zm, elements, Access::read());
+                Some(())
+            }
+            0b100 => {
+                w.insn("tbl");
+                w.zreg(zd, elements, Access::write());
+                w.zlist(zn, 1, elements, Access::read());
+                w.zreg(zm, elements, Access::read());
+                Some(())
+            }
+            0b110 => permute_unpredicated(word, w),
+            _ => None,
+        },
+        0b010 => permute_predicates(word, w),
+        0b011 => {
+            let name = ["zip1", "zip2", "uzp1", "uzp2", "trn1", "trn2", "", ""]
+                [bits(word, 12, 10) as usize];
+            if name.is_empty() {
+                return None;
+            }
+            w.insn(name);
+            three(w, elements, zd, zn, zm);
+            Some(())
+        }
+        0b100 | 0b101 => permute_predicated(word, w),
+        _ => {
+            let pg = bits(word, 13, 10);
+            if w.aliases && zd == zm {
+                w.alias("mov");
+                w.zreg(zd, elements, Access::read_write());
+                w.governing(pg, 'm');
+                w.zreg(zn, elements, Access::read());
+                return Some(());
+            }
+            w.insn("sel");
+            w.zreg(zd, elements, Access::write());
+            w.preg(pg, "", Access::read());
+            w.zreg(zn, elements, Access::read());
+            w.zreg(zm, elements, Access::read());
+            Some(())
+        }
+    }
+}
+
+/// `ext` in its destructive and constructive forms, and the 128-bit zips.
+fn extract(word: u32, w: &mut Writer) -> Option<()> {
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let imm = u64::from(bits(word, 20, 16) << 3 | bits(word, 12, 10));
+    match bits(word, 23, 21) {
+        0b001 => {
+            w.insn("ext");
+            w.zreg(zd, "b", Access::read_write());
+            w.zreg(zd, "b", Access::read());
+            w.zreg(zn, "b", Access::read());
+            w.uimm(imm);
+        }
+        0b011 => {
+            w.insn("ext");
+            w.zreg(zd, "b", Access::write());
+            w.zlist(zn, 2, "b", Access::read());
+            w.uimm(imm);
+        }
+        0b101 => {
+            let name = ["zip1", "zip2", "uzp1", "uzp2", "", "", "trn1", "trn2"]
+                [bits(word, 12, 10) as usize];
+            if name.is_empty() {
+                return None;
+            }
+            w.insn(name);
+            three(w, "q", zd, zn, bits(word, 20, 16));
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `dup` of an indexed element, printed as `mov`.
+fn dup_indexed(word: u32, w: &mut Writer) -> Option<()> {
+    let tsize = bits(word, 20, 16);
+    if tsize == 0 {
+        return None;
+    }
+    let size = tsize.trailing_zeros();
+    let index = (bits(word, 23, 22) << 5 | tsize) >> (size + 1);
+    let elements = ["b", "h", "s", "d", "q"][size as usize];
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    if !w.aliases {
+        w.insn("dup");
+        w.zreg(zd, elements, Access::write());
+        w.zlane(zn, elements, index, Access::read());
+        return Some(());
+    }
+    w.alias("mov");
+    w.zreg(zd, elements, Access::write());
+    if index == 0 {
+        let bank = [Bank::B, Bank::H, Bank::S, Bank::D, Bank::Q][size as usize];
+        w.reg(reg(bank, zn), Access::read());
+    } else {
+        w.zlane(zn, elements, index, Access::read());
+    }
+    Some(())
+}
+
+/// `dup` of a general register, `insr`, `rev` and the unpacks.
+fn permute_unpredicated(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let (n, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let wide = size == 0b11;
+    match bits(word, 20, 16) {
+        0b00000 => {
+            w.insn(if w.aliases { "mov" } else { "dup" });
+            w.zreg(zd, elements, Access::write());
+            w.reg(gpr_sp(wide, n), Access::read());
+        }
+        0b00100 => {
+            w.insn("insr");
+            w.zreg(zd, elements, Access::read_write());
+            w.reg(gpr(wide, n), Access::read());
+        }
+        0b10100 => {
+            w.insn("insr");
+            w.zreg(zd, elements, Access::read_write());
+            w.reg(scalar(size, n), Access::read());
+        }
+        0b11000 => {
+            w.insn("rev");
+            w.zreg(zd, elements, Access::write());
+            w.zreg(n, elements, Access::read());
+        }
+        opc @ 0b10000..=0b10011 if size != 0 => {
+            w.insn(["sunpklo", "sunpkhi", "uunpklo", "uunpkhi"][opc as usize & 3]);
+            w.zreg(zd, elements, Access::write());
+            w.zreg(n, SIZES[size as usize - 1], Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Predicate permutes: zips, `rev` and the unpacks.
+fn permute_predicates(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    if bit(word, 9) || bit(word, 4) {
+        return None;
+    }
+    let (pn, pd) = (bits(word, 8, 5), bits(word, 3, 0));
+    if !bit(word, 20) {
+        if bit(word, 13) {
+            return None;
+        }
+        let name =
+            ["zip1", "zip2", "uzp1", "uzp2", "trn1", "trn2", "", ""][bits(word, 12, 10) as usize];
+        if name.is_empty() {
+            return None;
+        }
+        w.insn(name);
+        w.preg(pd, elements, Access::write());
+        w.preg(pn, elements, Access::read());
+        w.preg(bits(word, 19, 16), elements, Access::read());
+        return Some(());
+    }
+    match (bits(word, 19, 16), bits(word, 15, 10)) {
+        (0b0100, 0b010000) => {
+            w.insn("rev");
+            w.preg(pd, elements, Access::write());
+            w.preg(pn, elements, Access::read());
+        }
+        (0b0000 | 0b0001, 0b010000) if size == 0 => {
+            w.insn(if bit(word, 16) { "punpkhi" } else { "punpklo" });
+            w.preg(pd, "h", Access::write());
+            w.preg(pn, "b", Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Permutes under a governing predicate: copies, `compact`, `last`,
+/// `clast`, the element reversals and `splice`.
+fn permute_predicated(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let pg = bits(word, 12, 10);
+    let (n, d) = (bits(word, 9, 5), bits(word, 4, 0));
+    let wide = size == 0b11;
+    let after = if bit(word, 16) { "b" } else { "a" };
+    match (bit(word, 13), bits(word, 20, 16)) {
+        (false, 0b00000) => {
+            w.insn(if w.aliases { "mov" } else { "cpy" });
+            w.zreg(d, elements, Access::read_write());
+            w.governing(pg, 'm');
+            w.reg(scalar(size, n), Access::read());
+        }
+        (false, 0b00001) if size >= 2 => {
+            w.insn("compact");
+            w.zreg(d, elements, Access::write());
+            w.preg(pg, "", Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (false, 0b00010 | 0b00011) => {
+            w.insn("last");
+            w.suffix(after);
+            w.reg(scalar(size, d), Access::write());
+            w.preg(pg, "", Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (false, opc @ 0b00100..=0b00111) => {
+            let (name, minimum) = match opc {
+                0b00100 => ("revb", 1),
+                0b00101 => ("revh", 2),
+                0b00110 => ("revw", 3),
+                _ => ("rbit", 0),
+            };
+            if size < minimum {
+                return None;
+            }
+            w.insn(name);
+            w.zreg(d, elements, Access::read_write());
+            w.governing(pg, 'm');
+            w.zreg(n, elements, Access::read());
+        }
+        (false, 0b01110) if size == 0 => {
+            w.insn("revd");
+            w.zreg(d, "q", Access::read_write());
+            w.governing(pg, 'm');
+            w.zreg(n, "q", Access::read());
+        }
+        (false, 0b01000 | 0b01001) => {
+            w.insn("clast");
+            w.suffix(after);
+            w.zreg(d, elements, Access::read_write());
+            w.preg(pg, "", Access::read());
+            w.zreg(d, elements, Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (false, 0b01010 | 0b01011) => {
+            w.insn("clast");
+            w.suffix(after);
+            w.reg(scalar(size, d), Access::read_write());
+            w.preg(pg, "", Access::read());
+            w.reg(scalar(size, d), Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (false, 0b01100) => {
+            w.insn("splice");
+            w.zreg(d, elements, Access::read_write());
+            w.preg(pg, "", Access::read());
+            w.zreg(d, elements, Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (false, 0b01101) => {
+            w.insn("splice");
+            w.zreg(d, elements, Access::write());
+            w.preg(pg, "", Access::read());
+            w.zlist(n, 2, elements, Access::read());
+        }
+        (true, 0b01000) => {
+            w.insn(if w.aliases { "mov" } else { "cpy" });
+            w.zreg(d, elements, Access::read_write());
+            w.governing(pg, 'm');
+            w.reg(gpr_sp(wide, n), Access::read());
+        }
+        (true, 0b00000 | 0b00001) => {
+            w.insn("last");
+            w.suffix(after);
+            w.reg(gpr(wide, d), Access::write());
+            w.preg(pg, "", Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        (true, 0b10000 | 0b10001) => {
+            w.insn("clast");
+            w.suffix(after);
+            w.reg(gpr(wide, d), Access::read_write());
+            w.preg(pg, "", Access::read());
+            w.reg(gpr(wide, d), Access::read());
+            w.zreg(n, elements, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_integer() {
+        let cases = [
+            (0x04a2_0020, "add z0.s, z1.s, z2.s"),
+            (0x0480_0460, "add z0.s, p1/m, z0.s, z3.s"),
+            (0x04b3_e100, "incw x0, vl8, mul #4"),
+            (0x0420_e3e3, "cntb x3"),
+            (0x0560_3865, "mov z5.h, w3"),
+            (0x0462_3041, "mov z1.d, z2.d"),
+            (0x0524_c861, "sel z1.b, p2, z3.b, z4.b"),
+            (0x0562_6820, "uzp1 z0.h, z1.h, z2.h"),
+            (0x042d_3420, "xar z0.b, z0.b, z1.b, #3"),
+            (0x05d1_cc03, "fmov z3.d, p1/m, #0.50000000"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! SVE loads, stores and prefetches at bits 31-29 = 0b100 to 0b111: the
+//! 32-bit gathers and unsized accesses, the contiguous loads, the 64-bit
+//! gathers and the stores and scatters.
+
+use alloc::format;
+
+use super::super::{SveOffset, Writer, bit, bits, sign_extend};
+use super::SIZES;
+use crate::registers::{Bank, gpr, gpr_sp, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    match bits(word, 31, 29) {
+        0b100 => gather_32(word, w),
+        0b101 => contiguous_load(word, w),
+        0b110 => gather_64(word, w),
+        _ => store(word, w),
+    }
+}
+
+/// Memory element size suffixes, by the two-bit `msz` field.
+const MEMORY_SIZES: [&str; 4] = ["b", "h", "w", "d"];
+
+/// Mnemonic suffix, memory element size field and register element size
+/// of the contiguous loads and broadcasts, by their four-bit `dtype`.
+const DTYPES: [(&str, u32, &str); 16] = [
+    ("b", 0, "b"),
+    ("b", 0, "h"),
+    ("b", 0, "s"),
+    ("b", 0, "d"),
+    ("sw", 2, "d"),
+    ("h", 1, "h"),
+    ("h", 1, "s"),
+    ("h", 1, "d"),
+    ("sh", 1, "d"),
+    ("sh", 1, "s"),
+    ("w", 2, "s"),
+    ("w", 2, "d"),
+    ("sb", 0, "d"),
+    ("sb", 0, "s"),
+    ("sb", 0, "h"),
+    ("d", 3, "d"),
+];
+
+/// Returns the offset of a scaled register index, `lsl #msz`, left out for
+/// bytes.
+fn scaled(msz: u32) -> Option<(&'static str, Option<u32>)> {
+    (msz != 0).then_some(("lsl", Some(msz)))
+}
+
+/// Returns the extend of a vector index of 32-bit offsets: `sxtw` when bit
+/// 22 is set, scaled by the element size when `scale` is set.
+fn extend(word: u32, msz: u32, scale: bool) -> Option<(&'static str, Option<u32>)> {
+    let kind = if bit(word, 22) { "sxtw" } else { "uxtw" };
+    Some((kind, scale.then_some(msz)))
+}
+
+/// Appends the loaded register list and the zeroing governing predicate.
+fn loaded(w: &mut Writer, word: u32, count: u32, size: &str) {
+    w.zlist(bits(word, 4, 0), count, size, Access::write());
+    w.governing(bits(word, 12, 10), 'z');
+}
+
+/// Appends the stored register list and the governing predicate.
+fn stored(w: &mut Writer, word: u32, count: u32, size: &str) {
+    w.zlist(bits(word, 4, 0), count, size, Access::read());
+    w.preg(bits(word, 12, 10), "", Access::read());
+}
+
+/// Names a gather load of `msz`-sized elements: bit 14 clear for the
+/// sign-extending forms, bit 13 set for the first-faulting ones.
+fn gather_name(w: &mut Writer, word: u32, msz: u32) -> Option<()> {
+    let signed = !bit(word, 14);
+    if signed && msz == 0b11 {
+        return None;
+    }
+    w.insn(if bit(word, 13) { "ldff1" } else { "ld1" });
+    if signed {
+        w.suffix("s");
+    }
+    w.suffix(MEMORY_SIZES[msz as usize]);
+    Some(())
+}
+
+/// Names a non-temporal gather `ldnt1`, sign-extending unless `unsigned`.
+fn non_temporal_name(w: &mut Writer, msz: u32, unsigned: bool) -> Option<()> {
+    if !unsigned && msz == 0b11 {
+        return None;
+    }
+    w.insn("ldnt1");
+    if !unsigned {
+        w.suffix("s");
+    }
+    w.suffix(MEMORY_SIZES[msz as usize]);
+    Some(())
+}
+
+/// Returns the `[zn.size, xm]` offset of the non-temporal gathers and
+/// scatters, leaving out an `xzr` index.
+fn vector_scalar(word: u32) -> SveOffset<'static> {
+    match bits(word, 20, 16) {
+        0b11111 => SveOffset::Imm(0),
+        rm => SveOffset::Index(gpr(true, rm), "", None),
+    }
+}
+
+/// Appends a prefetch operation: a named one or its number.
+fn prefetch_operation(w: &mut Writer, operation: u32) {
+    let target = bits(operation, 2, 1);
+    if target == 0b11 {
+        w.uimm(u64::from(operation));
+        return;
+    }
+    let kind = if bit(operation, 3) { "pst" } else { "pld" };
+    let policy = if bit(operation, 0) { "strm" } else { "keep" };
+    w.token(&format!("{kind}l{}{policy}", target + 1));
+}
+
+/// Names the prefetch of `msz`-sized elements and appends its operation
+/// and governing predicate.
+fn prefetch(w: &mut Writer, word: u32, msz: u32) -> Option<()> {
+    if bit(word, 4) {
+        return None;
+    }
+    w.insn(["prfb", "prfh", "prfw", "prfd"][msz as usize]);
+    prefetch_operation(w, bits(word, 3, 0));
+    w.preg(bits(word, 12, 10), "", Access::read());
+    Some(())
+}
+
+/// The 32-bit gathers, the broadcasts, `ldr` of predicates and vectors and
+/// the prefetches.
+fn gather_32(word: u32, w: &mut Writer) -> Option<()> {
+    let msz = bits(word, 24, 23);
+    let (zm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    if !bit(word, 15) {
+        if msz == 0b11 {
+            return if bit(word, 22) {
+                let msz = bits(word, 14, 13);
+                prefetch(w, word, msz)?;
+                let offset = SveOffset::MulVl(sign_extend(bits(word, 21, 16), 6));
+                w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+                Some(())
+            } else {
+                fill(word, w)
+            };
+        }
+        if bit(word, 21) && msz == 0 {
+            let msz = bits(word, 14, 13);
+            prefetch(w, word, msz)?;
+            let offset = SveOffset::Index(reg(Bank::Z, zm), "s", extend(word, msz, msz != 0));
+            w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+            return Some(());
+        }
+        if msz == 0b10 && !bit(word, 14) {
+            return None;
+        }
+        gather_name(w, word, msz)?;
+        loaded(w, word, 1, "s");
+        let offset = SveOffset::Index(reg(Bank::Z, zm), "s", extend(word, msz, bit(word, 21)));
+        w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+        return Some(());
+    }
+    if bit(word, 22) {
+        return broadcast(word, w);
+    }
+    let base = reg(Bank::Z, rn);
+    if bit(word, 21) {
+        if msz == 0b11 || (msz == 0b10 && !bit(word, 14)) {
+            return None;
+        }
+        gather_name(w, word, msz)?;
+        loaded(w, word, 1, "s");
+        let offset = SveOffset::Imm(i64::from(zm << msz));
+        w.mem_sve(base, "s", offset, Access::read());
+        return Some(());
+    }
+    match bits(word, 14, 13) {
+        op @ (0b00 | 0b01) => {
+            let unsigned = op == 0b01;
+            if msz == 0b11 || (msz == 0b10 && !unsigned) {
+                return None;
+            }
+            non_temporal_name(w, msz, unsigned)?;
+            loaded(w, word, 1, "s");
+            w.mem_sve(base, "s", vector_scalar(word), Access::read());
+        }
+        0b10 => {
+            if zm == 0b11111 {
+                return None;
+            }
+            prefetch(w, word, msz)?;
+            let offset = SveOffset::Index(gpr(true, zm), "", scaled(msz));
+            w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+        }
+        _ => {
+            prefetch(w, word, msz)?;
+            w.mem_sve(
+                base,
+                "s",
+                SveOffset::Imm(i64::from(zm << msz)),
+                Access::read(),
+            );
+        }
+    }
+    Some(())
+}
+
+/// `ldr` of a predicate or vector register, `ldr z0, [x0, #1, mul vl]`.
+fn fill(word: u32, w: &mut Writer) -> Option<()> {
+    let offset = sign_extend(bits(word, 21, 16) << 3 | bits(word, 12, 10), 9);
+    let (rn, rt) = (bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 15, 13) {
+        0b000 if !bit(word, 4) => {
+            w.insn("ldr");
+            w.preg(rt, "", Access::write());
+        }
+        0b010 => {
+            w.insn("ldr");
+            w.zreg(rt, "", Access::write());
+        }
+        _ => return None,
+    }
+    w.mem_sve(
+        gpr_sp(true, rn),
+        "",
+        SveOffset::MulVl(offset),
+        Access::read(),
+    );
+    Some(())
+}
+
+/// The loads broadcasting an element, `ld1rb {z0.b}, p0/z, [x0, #1]`.
+fn broadcast(word: u32, w: &mut Writer) -> Option<()> {
+    let dtype = bits(word, 24, 23) << 2 | bits(word, 14, 13);
+    let (suffix, msz, size) = DTYPES[dtype as usize];
+    w.insn("ld1r");
+    w.suffix(suffix);
+    loaded(w, word, 1, size);
+    let offset = SveOffset::Imm(i64::from(bits(word, 21, 16) << msz));
+    w.mem_sve(gpr_sp(true, bits(word, 9, 5)), "", offset, Access::read());
+    Some(())
+}
+
+/// The contiguous loads, their first-faulting, non-faulting and
+/// non-temporal forms, the structure loads and the quadword and
+/// octaword broadcasts.
+fn contiguous_load(word: u32, w: &mut Writer) -> Option<()> {
+    let msz = bits(word, 24, 23);
+    let (rm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    let base = gpr_sp(true, rn);
+    let immediate = sign_extend(bits(word, 19, 16), 4);
+    let op = bits(word, 15, 13);
+    match op {
+        0b000 | 0b001 => {
+            let (name, bytes) = match bits(word, 22, 21) {
+                0b00 => ("ld1rq", 16),
+                0b01 => ("ld1ro", 32),
+                _ => return None,
+            };
+            let offset = if op == 0b000 {
+                if rm == 0b11111 {
+                    return None;
+                }
+                SveOffset::Index(gpr(true, rm), "", scaled(msz))
+            } else {
+                if bit(word, 20) {
+                    return None;
+                }
+                SveOffset::Imm(immediate * bytes)
+            };
+            w.insn(name);
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            loaded(w, word, 1, SIZES[msz as usize]);
+            w.mem_sve(base, "", offset, Access::read());
+        }
+        0b010 | 0b011 | 0b101 => {
+            let (suffix, msz, size) = DTYPES[bits(word, 24, 21) as usize];
+            let offset = match op {
+                0b010 if rm == 0b11111 => return None,
+                0b011 if rm == 0b11111 => SveOffset::Imm(0),
+                0b010 | 0b011 => SveOffset::Index(gpr(true, rm), "", scaled(msz)),
+                _ => SveOffset::MulVl(immediate),
+            };
+            let name = match op {
+                0b011 => "ldff1",
+                0b101 if bit(word, 20) => "ldnf1",
+                _ => "ld1",
+            };
+            w.insn(name);
+            w.suffix(suffix);
+            loaded(w, word, 1, size);
+            w.mem_sve(base, "", offset, Access::read());
+        }
+        0b110 | 0b111 => {
+            let count = bits(word, 22, 21) + 1;
+            let offset = if op == 0b110 {
+                if rm == 0b11111 {
+                    return None;
+                }
+                SveOffset::Index(gpr(true, rm), "", scaled(msz))
+            } else {
+                if bit(word, 20) {
+                    return None;
+                }
+                SveOffset::MulVl(immediate * i64::from(count))
+            };
+            w.insn(structure_name(count, true));
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            loaded(w, word, count, SIZES[msz as usize]);
+            w.mem_sve(base, "", offset, Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Returns the stem of a structure load or store of `count` registers,
+/// the non-temporal form standing for a single register.
+fn structure_name(count: u32, load: bool) -> &'static str {
+    match (count, load) {
+        (1, true) => "ldnt1",
+        (2, true) => "ld2",
+        (3, true) => "ld3",
+        (_, true) => "ld4",
+        (1, false) => "stnt1",
+        (2, false) => "st2",
+        (3, false) => "st3",
+        (_, false) => "st4",
+    }
+}
+
+/// The 64-bit gathers, by vectors of 64-bit and unpacked 32-bit offsets
+/// and by a vector base, and their prefetches.
+fn gather_64(word: u32, w: &mut Writer) -> Option<()> {
+    let msz = bits(word, 24, 23);
+    let (zm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    let index = reg(Bank::Z, zm);
+    if !bit(word, 15) {
+        if bit(word, 21) && msz == 0 {
+            let msz = bits(word, 14, 13);
+            prefetch(w, word, msz)?;
+            let offset = SveOffset::Index(index, "d", extend(word, msz, msz != 0));
+            w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+            return Some(());
+        }
+        gather_name(w, word, msz)?;
+        loaded(w, word, 1, "d");
+        let offset = SveOffset::Index(index, "d", extend(word, msz, bit(word, 21)));
+        w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+        return Some(());
+    }
+    match (bit(word, 22), bit(word, 21)) {
+        (true, true) if msz == 0 => {
+            let msz = bits(word, 14, 13);
+            prefetch(w, word, msz)?;
+            let offset = SveOffset::Index(index, "d", scaled(msz));
+            w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+        }
+        (true, scale) => {
+            gather_name(w, word, msz)?;
+            loaded(w, word, 1, "d");
+            let shift = if scale { scaled(msz) } else { None };
+            let offset = SveOffset::Index(index, "d", shift);
+            w.mem_sve(gpr_sp(true, rn), "", offset, Access::read());
+        }
+        (false, true) => {
+            gather_name(w, word, msz)?;
+            loaded(w, word, 1, "d");
+            let offset = SveOffset::Imm(i64::from(zm << msz));
+            w.mem_sve(reg(Bank::Z, rn), "d", offset, Access::read());
+        }
+        (false, false) => match bits(word, 14, 13) {
+            op @ (0b00 | 0b10) => {
+                non_temporal_name(w, msz, op == 0b10)?;
+                loaded(w, word, 1, "d");
+                w.mem_sve(reg(Bank::Z, rn), "d", vector_scalar(word), Access::read());
+            }
+            0b11 => {
+                prefetch(w, word, msz)?;
+                let offset = SveOffset::Imm(i64::from(zm << msz));
+                w.mem_sve(reg(Bank::Z, rn), "d", offset, Access::read());
+            }
+            _ => return None,
+        },
+    }
+    Some(())
+}
+
+/// `str` of predicates and vectors, the contiguous and structure stores and
+/// the scatters.
+fn store(word: u32, w: &mut Writer) -> Option<()> {
+    let msz = bits(word, 24, 23);
+    let size = bits(word, 22, 21);
+    let (rm, rn) = (bits(word, 20, 16), bits(word, 9, 5));
+    let base = gpr_sp(true, rn);
+    let immediate = sign_extend(bits(word, 19, 16), 4);
+    let spill = bits(word, 24, 22) == 0b110;
+    match bits(word, 15, 13) {
+        op @ (0b000 | 0b010) if spill => {
+            let offset = sign_extend(bits(word, 21, 16) << 3 | bits(word, 12, 10), 9);
+            let rt = bits(word, 4, 0);
+            w.insn("str");
+            if op == 0b000 {
+                if bit(word, 4) {
+                    return None;
+                }
+                w.preg(rt, "", Access::read());
+            } else {
+                w.zreg(rt, "", Access::read());
+            }
+            w.mem_sve(base, "", SveOffset::MulVl(offset), Access::write());
+        }
+        0b010 => {
+            if size < msz || rm == 0b11111 {
+                return None;
+            }
+            w.insn("st1");
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            stored(w, word, 1, SIZES[size as usize]);
+            let offset = SveOffset::Index(gpr(true, rm), "", scaled(msz));
+            w.mem_sve(base, "", offset, Access::write());
+        }
+        0b001 => {
+            let elements = match size {
+                0b00 => "d",
+                0b10 if msz != 0b11 => "s",
+                _ => return None,
+            };
+            w.insn("stnt1");
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            stored(w, word, 1, elements);
+            w.mem_sve(
+                reg(Bank::Z, rn),
+                elements,
+                vector_scalar(word),
+                Access::write(),
+            );
+        }
+        0b011 => {
+            if rm == 0b11111 {
+                return None;
+            }
+            let count = size + 1;
+            w.insn(structure_name(count, false));
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            stored(w, word, count, SIZES[msz as usize]);
+            let offset = SveOffset::Index(gpr(true, rm), "", scaled(msz));
+            w.mem_sve(base, "", offset, Access::write());
+        }
+        0b111 => {
+            if bit(word, 20) {
+                let count = size + 1;
+                w.insn(structure_name(count, false));
+                w.suffix(MEMORY_SIZES[msz as usize]);
+                stored(w, word, count, SIZES[msz as usize]);
+                let offset = SveOffset::MulVl(immediate * i64::from(count));
+                w.mem_sve(base, "", offset, Access::write());
+            } else {
+                if size < msz {
+                    return None;
+                }
+                w.insn("st1");
+                w.suffix(MEMORY_SIZES[msz as usize]);
+                stored(w, word, 1, SIZES[size as usize]);
+                w.mem_sve(base, "", SveOffset::MulVl(immediate), Access::write());
+            }
+        }
+        op @ (0b100 | 0b110) => {
+            let scale = bit(word, 21);
+            let elements = if bit(word, 22) { "s" } else { "d" };
+            if (scale && msz == 0) || (elements == "s" && msz == 0b11) {
+                return None;
+            }
+            let kind = if op == 0b110 { "sxtw" } else { "uxtw" };
+            w.insn("st1");
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            stored(w, word, 1, elements);
+            let index = reg(Bank::Z, rm);
+            let offset = SveOffset::Index(index, elements, Some((kind, scale.then_some(msz))));
+            w.mem_sve(base, "", offset, Access::write());
+        }
+        0b101 => {
+            let (elements, offset, vector_base) = match size {
+                0b00 => ("d", SveOffset::Index(reg(Bank::Z, rm), "d", None), false),
+                0b01 if msz != 0 => (
+                    "d",
+                    SveOffset::Index(reg(Bank::Z, rm), "d", scaled(msz)),
+                    false,
+                ),
+                0b10 => ("d", SveOffset::Imm(i64::from(rm << msz)), true),
+                0b11 if msz != 0b11 => ("s", SveOffset::Imm(i64::from(rm << msz)), true),
+                _ => return None,
+            };
+            w.insn("st1");
+            w.suffix(MEMORY_SIZES[msz as usize]);
+            stored(w, word, 1, elements);
+            if vector_base {
+                w.mem_sve(reg(Bank::Z, rn), elements, offset, Access::write());
+            } else {
+                w.mem_sve(base, "", offset, Access::write());
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_memory() {
+        let cases = [
+            (0xa540_a000, "ld1w {z0.s}, p0/z, [x0]"),
+            (0xa5e3_a441, "ld1d {z1.d}, p1/z, [x2, #3, mul vl]"),
+            (0xa401_4000, "ld1b {z0.b}, p0/z, [x0, x1]"),
+            (0xe5e1_4000, "st1d {z0.d}, p0, [x0, x1, lsl #3]"),
+            (0x8521_4000, "ld1w {z0.s}, p0/z, [x0, z1.s, uxtw #2]"),
+            (0xc4a3_ec82, "ldff1h {z2.d}, p3/z, [z4.d, #6]"),
+            (
+                0xa56f_e000,
+                "ld4w {z0.s, z1.s, z2.s, z3.s}, p0/z, [x0, #-4, mul vl]",
+            ),
+            (0x8580_4800, "ldr z0, [x0, #2, mul vl]"),
+            (0xe580_03e1, "str p1, [sp]"),
+            (0x85c0_0000, "prfb pldl1keep, p0, [x0]"),
+            (0xa401_2000, "ld1rqb {z0.b}, p0/z, [x0, #0x10]"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! SVE and SVE2, the scalable vector encodings at bits 28-25 = 0b0010.
+//!
+//! Bits 31-29 select the class, each decoded by its own module:
+//!
+//! - [`integer`]: unpredicated and predicated integer arithmetic, element
+//!   counts, immediates and permutes;
+//! - [`predicate`]: integer compares and predicate operations;
+//! - [`sve2`]: the integer multiply-add, widening, narrowing and
+//!   accumulating classes added by SVE2;
+//! - [`float`]: floating point;
+//! - [`memory`]: loads, stores and prefetches.
+
+mod float;
+mod integer;
+mod memory;
+mod predicate;
+mod sve2;
+
+use super::{Writer, bit, bits};
+use crate::registers::{Bank, reg};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    match bits(word, 31, 29) {
+        0b000 => integer::decode(word, w),
+        0b001 => predicate::decode(word, w),
+        0b010 => sve2::decode(word, w),
+        0b011 => float::decode(word, w),
+        _ => memory::decode(word, w),
+    }
+}
+
+/// Element size suffixes, by the two-bit size field.
+pub(super) const SIZES: [&str; 4] = ["b", "h", "s", "d"];
+
+/// Names of the predicate constraint patterns, by encoding; the others
+/// print as immediates.
+const PATTERNS: [&str; 32] = [
+    "pow2", "vl1", "vl2", "vl3", "vl4", "vl5", "vl6", "vl7", "vl8", "vl16", "vl32", "vl64",
+    "vl128", "vl256", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "mul4", "mul3",
+    "all",
+];
+
+/// Returns the register of the scalar holding an element of size field
+/// `size`.
+pub(super) fn scalar(size: u32, num: u32) -> u32 {
+    reg([Bank::B, Bank::H, Bank::S, Bank::D][size as usize & 3], num)
+}
+
+/// Appends predicate constraint `pattern`.
+pub(super) fn pattern(w: &mut Writer, pattern: u32) {
+    match PATTERNS[pattern as usize & 0x1f] {
+        "" => w.uimm(u64::from(pattern)),
+        name => w.token(name),
+    }
+}
+
+/// Appends the `pattern, mul #imm` operands of the element count
+/// instructions, leaving out a default `all` pattern and multiplier of 1.
+pub(super) fn pattern_multiplier(w: &mut Writer, word: u32) {
+    let pattern_field = bits(word, 9, 5);
+    let multiplier = bits(word, 19, 16) + 1;
+    if pattern_field == 0b11111 && multiplier == 1 {
+        return;
+    }
+    pattern(w, pattern_field);
+    if multiplier != 1 {
+        w.multiplier(u64::from(multiplier));
+    }
+}
+
+/// Appends destination `zd` and sources `zn` and `zm` with elements of size
+/// field `size`.
+pub(super) fn three(w: &mut Writer, size: &str, zd: u32, zn: u32, zm: u32) {
+    w.zreg(zd, size, Access::write());
+    w.zreg(zn, size, Access::read());
+    w.zreg(zm, size, Access::read());
+}
+
+/// Appends the operands of a destructive predicated operation,
+/// `zdn, pg/m, zdn, zm`.
+pub(super) fn destructive(w: &mut Writer, word: u32, size: &str) {
+    let zdn = bits(word, 4, 0);
+    w.zreg(zdn, size, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    w.zreg(zdn, size, Access::read());
+    w.zreg(bits(word, 9, 5), size, Access::read());
+}
+
+/// Element size, `zm` and index of the same-size operations by indexed
+/// element, the index taking the bits `zm` leaves free.
+pub(super) fn element(word: u32) -> (&'static str, u32, u32) {
+    match bits(word, 23, 22) {
+        0b10 => ("s", bits(word, 18, 16), bits(word, 20, 19)),
+        0b11 => ("d", bits(word, 19, 16), u32::from(bit(word, 20))),
+        _ => (
+            "h",
+            bits(word, 18, 16),
+            u32::from(bit(word, 22)) << 2 | bits(word, 20, 19),
+        ),
+    }
+}
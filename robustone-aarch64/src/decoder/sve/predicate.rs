@@ -0,0 +1,499 @@
+//! SVE compares and predicates at bits 31-29 = 0b001: integer compares,
+//! predicate logic, breaks, counts, `while` and the wide immediates.
+
+use super::super::{Writer, bit, bits, sign_extend};
+use super::integer::shifted_immediate;
+use super::{SIZES, pattern};
+use crate::registers::{FFR, gpr};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 24) {
+        return if bit(word, 21) {
+            compare_unsigned_immediate(word, w)
+        } else {
+            compare_vectors(word, w)
+        };
+    }
+    if !bit(word, 21) {
+        if !bit(word, 14) {
+            return compare_signed_immediate(word, w);
+        }
+        return match (bits(word, 21, 20), bit(word, 15)) {
+            (0b00, false) => logical(word, w),
+            (0b00, true) => break_partition(word, w),
+            (_, false) => break_next(word, w),
+            (_, true) => predicate_misc(word, w),
+        };
+    }
+    match bits(word, 15, 14) {
+        0b00 => compare_scalars(word, w),
+        0b01 => select(word, w),
+        0b10 => count(word, w),
+        _ => wide_immediate(word, w),
+    }
+}
+
+/// Appends the `pd, pg/z, zn` operands common to the compares.
+fn compare_operands(w: &mut Writer, word: u32, size: &str) {
+    w.preg(bits(word, 3, 0), size, Access::write());
+    w.governing(bits(word, 12, 10), 'z');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+}
+
+/// Compares of two vectors, or of a vector with wide elements.
+fn compare_vectors(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let ne = bit(word, 4);
+    let (name, wide) = match (bits(word, 15, 13), ne) {
+        (0b000, false) => ("cmphs", false),
+        (0b000, true) => ("cmphi", false),
+        (0b001, false) => ("cmpeq", true),
+        (0b001, true) => ("cmpne", true),
+        (0b010, false) => ("cmpge", true),
+        (0b010, true) => ("cmpgt", true),
+        (0b011, false) => ("cmplt", true),
+        (0b011, true) => ("cmple", true),
+        (0b100, false) => ("cmpge", false),
+        (0b100, true) => ("cmpgt", false),
+        (0b101, false) => ("cmpeq", false),
+        (0b101, true) => ("cmpne", false),
+        (0b110, false) => ("cmphs", true),
+        (0b110, true) => ("cmphi", true),
+        (0b111, false) => ("cmplo", true),
+        (0b111, true) => ("cmpls", true),
+        _ => return None,
+    };
+    if wide && size == 0b11 {
+        return None;
+    }
+    let elements = SIZES[size as usize];
+    w.insn(name);
+    compare_operands(w, word, elements);
+    w.zreg(
+        bits(word, 20, 16),
+        if wide { "d" } else { elements },
+        Access::read(),
+    );
+    Some(())
+}
+
+/// Compares with an unsigned 7-bit immediate.
+fn compare_unsigned_immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match (bit(word, 13), bit(word, 4)) {
+        (false, false) => "cmphs",
+        (false, true) => "cmphi",
+        (true, false) => "cmplo",
+        (true, true) => "cmpls",
+    };
+    w.insn(name);
+    compare_operands(w, word, SIZES[bits(word, 23, 22) as usize]);
+    w.uimm(u64::from(bits(word, 20, 14)));
+    Some(())
+}
+
+/// Compares with a signed 5-bit immediate.
+fn compare_signed_immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match (bit(word, 15), bit(word, 13), bit(word, 4)) {
+        (false, false, false) => "cmpge",
+        (false, false, true) => "cmpgt",
+        (false, true, false) => "cmplt",
+        (false, true, true) => "cmple",
+        (true, false, false) => "cmpeq",
+        (true, false, true) => "cmpne",
+        _ => return None,
+    };
+    w.insn(name);
+    compare_operands(w, word, SIZES[bits(word, 23, 22) as usize]);
+    w.imm(sign_extend(bits(word, 20, 16), 5));
+    Some(())
+}
+
+/// Predicate logic under a zeroing predicate, with the `mov` and `not`
+/// aliases.
+fn logical(word: u32, w: &mut Writer) -> Option<()> {
+    let flags = bit(word, 22);
+    let (pm, pg, pn, pd) = (
+        bits(word, 19, 16),
+        bits(word, 13, 10),
+        bits(word, 8, 5),
+        bits(word, 3, 0),
+    );
+    // Bits 9 and 4 select the operation within the half picked by bit 23.
+    let name = match (bit(word, 23), bit(word, 9), bit(word, 4)) {
+        (false, false, false) => "and",
+        (false, false, true) => "bic",
+        (false, true, false) => "eor",
+        (false, true, true) => "sel",
+        (true, false, false) => "orr",
+        (true, false, true) => "orn",
+        (true, true, false) => "nor",
+        (true, true, true) => "nand",
+    };
+    if name == "sel" && flags {
+        return None;
+    }
+    if w.aliases {
+        let alias = match name {
+            "orr" if pn == pm && pn == pg => {
+                w.alias(if flags { "movs" } else { "mov" });
+                w.preg(pd, "b", Access::write());
+                w.preg(pn, "b", Access::read());
+                return Some(());
+            }
+            "and" if pn == pm => Some(if flags { "movs" } else { "mov" }),
+            "eor" if pm == pg => Some(if flags { "nots" } else { "not" }),
+            "sel" if pd == pm => {
+                w.alias("mov");
+                w.preg(pd, "b", Access::read_write());
+                w.governing(pg, 'm');
+                w.preg(pn, "b", Access::read());
+                return Some(());
+            }
+            _ => None,
+        };
+        if let Some(alias) = alias {
+            w.alias(alias);
+            w.preg(pd, "b", Access::write());
+            w.governing(pg, 'z');
+            w.preg(pn, "b", Access::read());
+            return Some(());
+        }
+    }
+    w.insn(name);
+    if flags {
+        w.suffix("s");
+    }
+    w.preg(pd, "b", Access::write());
+    if name == "sel" {
+        w.preg(pg, "", Access::read());
+    } else {
+        w.governing(pg, 'z');
+    }
+    w.preg(pn, "b", Access::read());
+    w.preg(pm, "b", Access::read());
+    Some(())
+}
+
+/// `brkpa` and `brkpb`, breaks propagated from the previous partition.
+fn break_partition(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 23) || bit(word, 9) {
+        return None;
+    }
+    w.insn(if bit(word, 4) { "brkpb" } else { "brkpa" });
+    if bit(word, 22) {
+        w.suffix("s");
+    }
+    w.preg(bits(word, 3, 0), "b", Access::write());
+    w.governing(bits(word, 13, 10), 'z');
+    w.preg(bits(word, 8, 5), "b", Access::read());
+    w.preg(bits(word, 19, 16), "b", Access::read());
+    Some(())
+}
+
+/// `brka`, `brkb` and `brkn`.
+fn break_next(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 21, 20) != 0b01 || bit(word, 9) {
+        return None;
+    }
+    let flags = bit(word, 22);
+    let (pg, pn, pd) = (bits(word, 13, 10), bits(word, 8, 5), bits(word, 3, 0));
+    match bits(word, 19, 16) {
+        0b0000 => {
+            let merging = bit(word, 4);
+            if merging && flags {
+                return None;
+            }
+            w.insn(if bit(word, 23) { "brkb" } else { "brka" });
+            if flags {
+                w.suffix("s");
+            }
+            w.preg(
+                pd,
+                "b",
+                if merging {
+                    Access::read_write()
+                } else {
+                    Access::write()
+                },
+            );
+            w.governing(pg, if merging { 'm' } else { 'z' });
+            w.preg(pn, "b", Access::read());
+        }
+        0b1000 if !bit(word, 23) && !bit(word, 4) => {
+            w.insn(if flags { "brkns" } else { "brkn" });
+            w.preg(pd, "b", Access::read_write());
+            w.governing(pg, 'z');
+            w.preg(pn, "b", Access::read());
+            w.preg(pd, "b", Access::read());
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `ptest`, `pfirst`, `pnext`, `ptrue`, `pfalse` and `rdffr`.
+fn predicate_misc(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let flags = bit(word, 22);
+    let pd = bits(word, 3, 0);
+    if bit(word, 4) {
+        return None;
+    }
+    match (bits(word, 21, 16), bits(word, 13, 10)) {
+        (0b010000, pg) if size == 0b01 && !bit(word, 9) && pd == 0 => {
+            w.insn("ptest");
+            w.preg(pg, "", Access::read());
+            w.preg(bits(word, 8, 5), "b", Access::read());
+        }
+        (0b011000, 0b0000) if size == 0b01 && !bit(word, 9) => {
+            w.insn("pfirst");
+            w.preg(pd, "b", Access::read_write());
+            w.preg(bits(word, 8, 5), "", Access::read());
+            w.preg(pd, "b", Access::read());
+        }
+        (0b011001, 0b0001) if !bit(word, 9) => {
+            w.insn("pnext");
+            let elements = SIZES[size as usize];
+            w.preg(pd, elements, Access::read_write());
+            w.preg(bits(word, 8, 5), "", Access::read());
+            w.preg(pd, elements, Access::read());
+        }
+        (0b011000 | 0b011001, 0b1000) if !bit(word, 4) => {
+            w.insn(if bit(word, 16) { "ptrues" } else { "ptrue" });
+            w.preg(pd, SIZES[size as usize], Access::write());
+            let pattern_field = bits(word, 9, 5);
+            if pattern_field != 0b11111 {
+                pattern(w, pattern_field);
+            }
+        }
+        (0b011000, 0b1001) if size == 0 && bits(word, 9, 5) == 0 => {
+            w.insn("pfalse");
+            w.preg(pd, "b", Access::write());
+        }
+        (0b011000, 0b1100) if !bit(word, 23) && !bit(word, 9) => {
+            w.insn(if flags { "rdffrs" } else { "rdffr" });
+            w.preg(pd, "b", Access::write());
+            w.governing(bits(word, 8, 5), 'z');
+            w.implicit_read(FFR);
+        }
+        (0b011001, 0b1100) if size == 0 && bits(word, 9, 5) == 0 => {
+            w.insn("rdffr");
+            w.preg(pd, "b", Access::write());
+            w.implicit_read(FFR);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// `whilelt` and the other `while` comparisons, `ctermeq`, `ctermne`,
+/// `whilerw` and `whilewr`.
+fn compare_scalars(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (rm, rn, pd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 3, 0));
+    match bits(word, 13, 10) {
+        0b1000 if bits(word, 3, 0) == 0 && bit(word, 23) => {
+            w.insn(if bit(word, 4) { "ctermne" } else { "ctermeq" });
+            let wide = bit(word, 22);
+            w.reg(gpr(wide, rn), Access::read());
+            w.reg(gpr(wide, rm), Access::read());
+            return Some(());
+        }
+        0b1100 if bits(word, 15, 14) == 0 => {
+            w.insn(if bit(word, 4) { "whilerw" } else { "whilewr" });
+            w.preg(pd, SIZES[size as usize], Access::write());
+            w.reg(gpr(true, rn), Access::read());
+            w.reg(gpr(true, rm), Access::read());
+            return Some(());
+        }
+        _ => {}
+    }
+    if bit(word, 13) {
+        return None;
+    }
+    let name = match (bit(word, 11), bit(word, 10), bit(word, 4)) {
+        (false, false, false) => "whilege",
+        (false, false, true) => "whilegt",
+        (false, true, false) => "whilelt",
+        (false, true, true) => "whilele",
+        (true, false, false) => "whilehs",
+        (true, false, true) => "whilehi",
+        (true, true, false) => "whilelo",
+        (true, true, true) => "whilels",
+    };
+    let wide = bit(word, 12);
+    w.insn(name);
+    w.preg(pd, SIZES[size as usize], Access::write());
+    w.reg(gpr(wide, rn), Access::read());
+    w.reg(gpr(wide, rm), Access::read());
+    Some(())
+}
+
+/// The SME `psel`, selecting a predicate by an element of another.
+fn select(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 9) || bit(word, 4) {
+        return None;
+    }
+    // The lowest set bit of i1:tszh:tszl gives the element size, the bits
+    // above it the index.
+    let imm = bits(word, 23, 22) << 3 | bits(word, 20, 18);
+    let size = imm.trailing_zeros();
+    if size > 3 {
+        return None;
+    }
+    w.insn("psel");
+    w.preg(bits(word, 3, 0), "", Access::write());
+    w.preg(bits(word, 13, 10), "", Access::read());
+    w.preg(bits(word, 8, 5), SIZES[size as usize], Access::read());
+    w.slice_index(12 + bits(word, 17, 16), imm >> (size + 1));
+    Some(())
+}
+
+/// `cntp`, `incp`, `decp` and their saturating forms, `wrffr` and
+/// `setffr`.
+fn count(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let (pm, rd) = (bits(word, 8, 5), bits(word, 4, 0));
+    match (bits(word, 21, 16), bits(word, 15, 9)) {
+        (0b100000, op) if op & 1 == 0 => {
+            w.insn("cntp");
+            w.reg(gpr(true, rd), Access::write());
+            w.preg(bits(word, 13, 10), "", Access::read());
+            w.preg(pm, elements, Access::read());
+        }
+        (0b101100 | 0b101101, 0b1000100) => {
+            w.insn(if bit(word, 16) { "decp" } else { "incp" });
+            w.reg(gpr(true, rd), Access::read_write());
+            w.preg(pm, elements, Access::read());
+        }
+        (0b101100 | 0b101101, 0b1000000) if size != 0 => {
+            w.insn(if bit(word, 16) { "decp" } else { "incp" });
+            w.zreg(rd, elements, Access::read_write());
+            w.preg(pm, elements, Access::read());
+        }
+        (0b101000..=0b101011, 0b1000100 | 0b1000110) => {
+            let unsigned = bit(word, 16);
+            w.insn(saturating_name(bit(word, 17), unsigned));
+            if bit(word, 10) {
+                w.reg(gpr(true, rd), Access::read_write());
+                w.preg(pm, elements, Access::read());
+            } else if unsigned {
+                w.reg(gpr(false, rd), Access::read_write());
+                w.preg(pm, elements, Access::read());
+            } else {
+                w.reg(gpr(true, rd), Access::write());
+                w.preg(pm, elements, Access::read());
+                w.reg(gpr(false, rd), Access::read());
+            }
+        }
+        (0b101000..=0b101011, 0b1000000) if size != 0 => {
+            w.insn(saturating_name(bit(word, 17), bit(word, 16)));
+            w.zreg(rd, elements, Access::read_write());
+            w.preg(pm, elements, Access::read());
+        }
+        (0b101000, 0b1001000) if size == 0 && rd == 0 && !bit(word, 9) => {
+            w.insn("wrffr");
+            w.preg(pm, "b", Access::read());
+            w.implicit_write(FFR);
+        }
+        (0b101100, 0b1001000) if size == 0 && bits(word, 8, 0) == 0 => {
+            w.insn("setffr");
+            w.implicit_write(FFR);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Name of a saturating increment or decrement by a predicate count.
+fn saturating_name(decrement: bool, unsigned: bool) -> &'static str {
+    match (decrement, unsigned) {
+        (false, false) => "sqincp",
+        (false, true) => "uqincp",
+        (true, false) => "sqdecp",
+        (true, true) => "uqdecp",
+    }
+}
+
+/// Arithmetic with an immediate, `dup` and `fdup`.
+fn wide_immediate(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let elements = SIZES[size as usize];
+    let zd = bits(word, 4, 0);
+    let shifted = bit(word, 13);
+    let imm8 = bits(word, 12, 5);
+    if shifted && size == 0 {
+        return None;
+    }
+    let op = bits(word, 18, 16);
+    match bits(word, 21, 19) {
+        0b100 => {
+            let name = ["add", "sub", "", "subr", "sqadd", "uqadd", "sqsub", "uqsub"][op as usize];
+            if name.is_empty() {
+                return None;
+            }
+            w.insn(name);
+            w.zreg(zd, elements, Access::read_write());
+            w.zreg(zd, elements, Access::read());
+            shifted_immediate(w, i64::from(imm8), shifted);
+        }
+        0b101 if !shifted => {
+            let name = ["smax", "umax", "smin", "umin", "", "", "", ""][op as usize];
+            if name.is_empty() {
+                return None;
+            }
+            w.insn(name);
+            w.zreg(zd, elements, Access::read_write());
+            w.zreg(zd, elements, Access::read());
+            if name.starts_with('s') {
+                w.imm(sign_extend(imm8, 8));
+            } else {
+                w.uimm(u64::from(imm8));
+            }
+        }
+        0b110 if op == 0 && !shifted => {
+            w.insn("mul");
+            w.zreg(zd, elements, Access::read_write());
+            w.zreg(zd, elements, Access::read());
+            w.imm(sign_extend(imm8, 8));
+        }
+        0b111 if op == 0 => {
+            w.insn(if w.aliases { "mov" } else { "dup" });
+            w.zreg(zd, elements, Access::write());
+            shifted_immediate(w, sign_extend(imm8, 8), shifted);
+        }
+        0b111 if op == 1 && !shifted && size != 0 => {
+            w.insn(if w.aliases { "fmov" } else { "fdup" });
+            w.zreg(zd, elements, Access::write());
+            w.float(super::super::simd_fp::float_immediate(imm8));
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_predicate() {
+        let cases = [
+            (0x25f0_dfa4, "mul z4.d, z4.d, #-3"),
+            (0x2505_8041, "cmpeq p1.b, p0/z, z2.b, #5"),
+            (0x2484_0472, "cmphi p2.s, p1/z, z3.s, z4.s"),
+            (0x2598_e160, "ptrue p0.s, vl64"),
+            (0x2518_e3e1, "ptrue p1.b"),
+            (0x25e1_1c02, "whilelo p2.d, x0, x1"),
+            (0x2503_4440, "and p0.b, p1/z, p2.b, p3.b"),
+            (0x2585_54a4, "mov p4.b, p5.b"),
+            (0x2550_c440, "ptest p1, p2.b"),
+            (0x2519_f003, "rdffr p3.b"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
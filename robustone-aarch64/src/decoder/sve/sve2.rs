@@ -0,0 +1,742 @@
+//! SVE integer multiply-add and SVE2 integer data processing at bits 31-29
+//! = 0b010: dot products, complex arithmetic, widening, narrowing and
+//! accumulating operations, by vector and by indexed element, and the SVE2
+//! crypto extensions.
+
+use super::super::{Writer, bit, bits};
+use super::integer::shift_immediate;
+use super::{SIZES, destructive, element, three};
+use crate::types::Access;
+
+pub(super) fn decode(word: u32, w: &mut Writer) -> Option<()> {
+    let op = bits(word, 15, 13);
+    match (bit(word, 24), bit(word, 21)) {
+        (false, false) => match op {
+            0b000 => dot(word, w),
+            0b001 => complex(word, w),
+            0b010 => multiply_add_long(word, w),
+            0b011 => saturating_multiply_add(word, w),
+            0b100 => binary_predicated(word, w),
+            0b101 => pairwise(word, w),
+            0b110 => clamp(word, w),
+            _ => None,
+        },
+        (false, true) => match op {
+            0b000 => indexed_multiply_add(word, w),
+            0b001 => indexed_saturating_long(word, w),
+            0b010 => indexed_complex_dot(word, w),
+            0b011 => indexed_complex(word, w),
+            0b100 | 0b101 => indexed_multiply_add_long(word, w),
+            0b110 => indexed_multiply_long(word, w),
+            _ => indexed_multiply(word, w),
+        },
+        (true, false) => match op {
+            0b000 => add_sub_long(word, w),
+            0b001 => absolute_difference_long(word, w),
+            0b010 => add_sub_wide(word, w),
+            0b011 => multiply_long(word, w),
+            0b100 => interleaved(word, w),
+            0b101 => shift_long(word, w),
+            0b110 => accumulate_long(word, w),
+            _ => shift_accumulate(word, w),
+        },
+        (true, true) => match op {
+            0b000 | 0b001 => shift_narrow(word, w),
+            0b010 => extract_narrow(word, w),
+            0b011 => high_narrow(word, w),
+            0b100 => string_match(word, w),
+            0b101 => histogram_segment(word, w),
+            0b110 => histogram_count(word, w),
+            _ => crypto(word, w),
+        },
+    }
+}
+
+/// Element sizes of a widening operation with size field `size`: the
+/// destination and the half-width sources.
+fn long(size: u32) -> Option<(&'static str, &'static str)> {
+    match size {
+        0 => None,
+        _ => Some((SIZES[size as usize], SIZES[size as usize - 1])),
+    }
+}
+
+/// Element sizes, `zm` and index of the indexed dot products, where a
+/// word or doubleword accumulates four bytes or halfwords.
+fn dot_element(word: u32) -> Option<(&'static str, &'static str, u32, u32)> {
+    match bits(word, 23, 22) {
+        0b10 => Some(("s", "b", bits(word, 18, 16), bits(word, 20, 19))),
+        0b11 => Some(("d", "h", bits(word, 19, 16), u32::from(bit(word, 20)))),
+        _ => None,
+    }
+}
+
+/// Element sizes, `zm` and index of the widening indexed operations, whose
+/// index has its low bit at bit 11.
+fn long_element(word: u32) -> Option<(&'static str, &'static str, u32, u32)> {
+    let low = u32::from(bit(word, 11));
+    match bits(word, 23, 22) {
+        0b10 => Some(("s", "h", bits(word, 18, 16), bits(word, 20, 19) << 1 | low)),
+        0b11 => Some((
+            "d",
+            "s",
+            bits(word, 19, 16),
+            u32::from(bit(word, 20)) << 1 | low,
+        )),
+        _ => None,
+    }
+}
+
+/// Appends accumulator `zda` and sources `zn` and `zm`.
+fn accumulate(w: &mut Writer, word: u32, size: &str, source: &str) {
+    w.zreg(bits(word, 4, 0), size, Access::read_write());
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zreg(bits(word, 20, 16), source, Access::read());
+}
+
+/// Appends accumulator `zda`, source `zn` and element `index` of `zm`.
+fn accumulate_element(w: &mut Writer, word: u32, size: &str, source: &str, zm: u32, index: u32) {
+    w.zreg(bits(word, 4, 0), size, Access::read_write());
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zlane(zm, source, index, Access::read());
+}
+
+/// Appends the rotation in bits 11-10, in steps of 90 degrees.
+fn rotation(w: &mut Writer, word: u32) {
+    w.rotation(bits(word, 11, 10) * 90);
+}
+
+/// Returns the bottom (`b`) or top (`t`) suffix selected by bit 10.
+fn half(word: u32) -> &'static str {
+    if bit(word, 10) { "t" } else { "b" }
+}
+
+/// Appends the name built from `stem` and the bottom or top suffix.
+fn insn_half(w: &mut Writer, word: u32, stem: &str) {
+    w.insn(stem);
+    w.suffix(half(word));
+}
+
+/// `sdot`, `udot` and `cdot` by vector, and the interleaved `sqdmlalbt`
+/// and `sqdmlslbt`.
+fn dot(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (dest, source) = match size {
+        0b10 => ("s", "b"),
+        0b11 => ("d", "h"),
+        _ => ("", ""),
+    };
+    match bits(word, 12, 10) {
+        op @ (0b010 | 0b011) => {
+            let (dest, source) = long(size)?;
+            w.insn(if op == 0b010 {
+                "sqdmlalbt"
+            } else {
+                "sqdmlslbt"
+            });
+            accumulate(w, word, dest, source);
+        }
+        _ if dest.is_empty() => return None,
+        op @ (0b000 | 0b001) => {
+            w.insn(if op == 0b000 { "sdot" } else { "udot" });
+            accumulate(w, word, dest, source);
+        }
+        _ => {
+            w.insn("cdot");
+            accumulate(w, word, dest, source);
+            rotation(w, word);
+        }
+    }
+    Some(())
+}
+
+/// `cmla` and `sqrdcmlah` by vector.
+fn complex(word: u32, w: &mut Writer) -> Option<()> {
+    let size = SIZES[bits(word, 23, 22) as usize];
+    w.insn(if bit(word, 12) { "sqrdcmlah" } else { "cmla" });
+    accumulate(w, word, size, size);
+    rotation(w, word);
+    Some(())
+}
+
+/// The widening multiply-adds, `smlalb z0.s, z1.h, z2.h`.
+fn multiply_add_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source) = long(bits(word, 23, 22))?;
+    let stem = match bits(word, 12, 11) {
+        0b00 => "smlal",
+        0b01 => "umlal",
+        0b10 => "smlsl",
+        _ => "umlsl",
+    };
+    insn_half(w, word, stem);
+    accumulate(w, word, dest, source);
+    Some(())
+}
+
+/// The saturating doubling multiply-adds by vector and `usdot`.
+fn saturating_multiply_add(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    match bits(word, 12, 10) {
+        op @ 0b000..=0b011 => {
+            let (dest, source) = long(size)?;
+            insn_half(w, word, if op & 0b10 == 0 { "sqdmlal" } else { "sqdmlsl" });
+            accumulate(w, word, dest, source);
+        }
+        op @ (0b100 | 0b101) => {
+            w.insn(if op == 0b100 { "sqrdmlah" } else { "sqrdmlsh" });
+            let size = SIZES[size as usize];
+            accumulate(w, word, size, size);
+        }
+        0b110 if size == 0b10 => {
+            w.insn("usdot");
+            accumulate(w, word, "s", "b");
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// The SVE2 predicated halving, saturating and rounding arithmetic,
+/// `shadd z0.s, p0/m, z0.s, z1.s`.
+fn binary_predicated(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match bits(word, 20, 16) {
+        0b00010 => "srshl",
+        0b00011 => "urshl",
+        0b00110 => "srshlr",
+        0b00111 => "urshlr",
+        0b01000 => "sqshl",
+        0b01001 => "uqshl",
+        0b01010 => "sqrshl",
+        0b01011 => "uqrshl",
+        0b01100 => "sqshlr",
+        0b01101 => "uqshlr",
+        0b01110 => "sqrshlr",
+        0b01111 => "uqrshlr",
+        0b10000 => "shadd",
+        0b10001 => "uhadd",
+        0b10010 => "shsub",
+        0b10011 => "uhsub",
+        0b10100 => "srhadd",
+        0b10101 => "urhadd",
+        0b10110 => "shsubr",
+        0b10111 => "uhsubr",
+        0b11000 => "sqadd",
+        0b11001 => "uqadd",
+        0b11010 => "sqsub",
+        0b11011 => "uqsub",
+        0b11100 => "suqadd",
+        0b11101 => "usqadd",
+        0b11110 => "sqsubr",
+        0b11111 => "uqsubr",
+        _ => return None,
+    };
+    w.insn(name);
+    destructive(w, word, SIZES[bits(word, 23, 22) as usize]);
+    Some(())
+}
+
+/// Predicated pairwise operations, `sadalp` and `uadalp`, and the SVE2
+/// predicated unary operations.
+fn pairwise(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let (name, dest, source) = match bits(word, 20, 16) {
+        0b10001 => ("addp", "", ""),
+        0b10100 => ("smaxp", "", ""),
+        0b10101 => ("umaxp", "", ""),
+        0b10110 => ("sminp", "", ""),
+        0b10111 => ("uminp", "", ""),
+        op @ (0b00100 | 0b00101) => {
+            let (dest, source) = long(size)?;
+            (if op & 1 == 0 { "sadalp" } else { "uadalp" }, dest, source)
+        }
+        0b00000 if size == 0b10 => ("urecpe", "s", "s"),
+        0b00001 if size == 0b10 => ("ursqrte", "s", "s"),
+        0b01000 => ("sqabs", SIZES[size as usize], SIZES[size as usize]),
+        0b01001 => ("sqneg", SIZES[size as usize], SIZES[size as usize]),
+        _ => return None,
+    };
+    w.insn(name);
+    if dest.is_empty() {
+        destructive(w, word, SIZES[size as usize]);
+        return Some(());
+    }
+    w.zreg(zd, dest, Access::read_write());
+    w.governing(bits(word, 12, 10), 'm');
+    w.zreg(zn, source, Access::read());
+    Some(())
+}
+
+/// `sclamp` and `uclamp`.
+fn clamp(word: u32, w: &mut Writer) -> Option<()> {
+    let name = match bits(word, 12, 10) {
+        0b000 => "sclamp",
+        0b001 => "uclamp",
+        _ => return None,
+    };
+    let size = SIZES[bits(word, 23, 22) as usize];
+    w.insn(name);
+    w.zreg(bits(word, 4, 0), size, Access::read_write());
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.zreg(bits(word, 20, 16), size, Access::read());
+    Some(())
+}
+
+/// The dot products and multiply-adds by indexed element,
+/// `mla z0.h, z1.h, z2.h[7]`.
+fn indexed_multiply_add(word: u32, w: &mut Writer) -> Option<()> {
+    let op = bits(word, 12, 10);
+    match op {
+        0b000 | 0b001 => {
+            let (dest, source, zm, index) = dot_element(word)?;
+            w.insn(if op == 0b000 { "sdot" } else { "udot" });
+            accumulate_element(w, word, dest, source, zm, index);
+        }
+        0b110 | 0b111 => {
+            if bits(word, 23, 22) != 0b10 {
+                return None;
+            }
+            w.insn(if op == 0b110 { "usdot" } else { "sudot" });
+            let (zm, index) = (bits(word, 18, 16), bits(word, 20, 19));
+            accumulate_element(w, word, "s", "b", zm, index);
+        }
+        _ => {
+            let name = ["mla", "mls", "sqrdmlah", "sqrdmlsh"][(op as usize - 2) & 3];
+            let (size, zm, index) = element(word);
+            w.insn(name);
+            accumulate_element(w, word, size, size, zm, index);
+        }
+    }
+    Some(())
+}
+
+/// `sqdmlalb`, `sqdmlalt`, `sqdmlslb` and `sqdmlslt` by indexed element.
+fn indexed_saturating_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source, zm, index) = long_element(word)?;
+    insn_half(w, word, if bit(word, 12) { "sqdmlsl" } else { "sqdmlal" });
+    accumulate_element(w, word, dest, source, zm, index);
+    Some(())
+}
+
+/// `cdot` by indexed element.
+fn indexed_complex_dot(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 12) {
+        return None;
+    }
+    let (dest, source, zm, index) = dot_element(word)?;
+    w.insn("cdot");
+    accumulate_element(w, word, dest, source, zm, index);
+    rotation(w, word);
+    Some(())
+}
+
+/// `cmla` and `sqrdcmlah` by indexed element, whose index selects a pair
+/// of halfwords or words.
+fn indexed_complex(word: u32, w: &mut Writer) -> Option<()> {
+    let (size, zm, index) = match bits(word, 23, 22) {
+        0b10 => ("h", bits(word, 18, 16), bits(word, 20, 19)),
+        0b11 => ("s", bits(word, 19, 16), u32::from(bit(word, 20))),
+        _ => return None,
+    };
+    w.insn(if bit(word, 12) { "sqrdcmlah" } else { "cmla" });
+    accumulate_element(w, word, size, size, zm, index);
+    rotation(w, word);
+    Some(())
+}
+
+/// The widening multiply-adds by indexed element,
+/// `smlalb z0.s, z1.h, z2.h[7]`.
+fn indexed_multiply_add_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source, zm, index) = long_element(word)?;
+    let stem = match (bit(word, 13), bit(word, 12)) {
+        (false, false) => "smlal",
+        (false, true) => "umlal",
+        (true, false) => "smlsl",
+        (true, true) => "umlsl",
+    };
+    insn_half(w, word, stem);
+    accumulate_element(w, word, dest, source, zm, index);
+    Some(())
+}
+
+/// `smullb`, `smullt`, `umullb` and `umullt` by indexed element.
+fn indexed_multiply_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source, zm, index) = long_element(word)?;
+    insn_half(w, word, if bit(word, 12) { "umull" } else { "smull" });
+    w.zreg(bits(word, 4, 0), dest, Access::write());
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zlane(zm, source, index, Access::read());
+    Some(())
+}
+
+/// The saturating doubling multiplies and `mul` by indexed element.
+fn indexed_multiply(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source, zm, index) = if bit(word, 12) {
+        let name = match bits(word, 11, 10) {
+            0b00 => "sqdmulh",
+            0b01 => "sqrdmulh",
+            0b10 => "mul",
+            _ => return None,
+        };
+        w.insn(name);
+        let (size, zm, index) = element(word);
+        (size, size, zm, index)
+    } else {
+        let element = long_element(word)?;
+        insn_half(w, word, "sqdmull");
+        element
+    };
+    w.zreg(bits(word, 4, 0), dest, Access::write());
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zlane(zm, source, index, Access::read());
+    Some(())
+}
+
+/// Appends a widening operation's destination and two narrow sources.
+fn widen(w: &mut Writer, word: u32, dest: &str, source: &str) {
+    w.zreg(bits(word, 4, 0), dest, Access::write());
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zreg(bits(word, 20, 16), source, Access::read());
+}
+
+/// `saddlb`, `uaddlt`, `ssublb` and the other long additions and
+/// subtractions.
+fn add_sub_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source) = long(bits(word, 23, 22))?;
+    let stem = match bits(word, 12, 11) {
+        0b00 => "saddl",
+        0b01 => "uaddl",
+        0b10 => "ssubl",
+        _ => "usubl",
+    };
+    insn_half(w, word, stem);
+    widen(w, word, dest, source);
+    Some(())
+}
+
+/// `sabdlb`, `sabdlt`, `uabdlb` and `uabdlt`.
+fn absolute_difference_long(word: u32, w: &mut Writer) -> Option<()> {
+    if !bit(word, 12) {
+        return None;
+    }
+    let (dest, source) = long(bits(word, 23, 22))?;
+    insn_half(w, word, if bit(word, 11) { "uabdl" } else { "sabdl" });
+    widen(w, word, dest, source);
+    Some(())
+}
+
+/// `saddwb`, `uaddwt`, `ssubwb` and the other wide additions and
+/// subtractions.
+fn add_sub_wide(word: u32, w: &mut Writer) -> Option<()> {
+    let (dest, source) = long(bits(word, 23, 22))?;
+    let stem = match bits(word, 12, 11) {
+        0b00 => "saddw",
+        0b01 => "uaddw",
+        0b10 => "ssubw",
+        _ => "usubw",
+    };
+    insn_half(w, word, stem);
+    w.zreg(bits(word, 4, 0), dest, Access::write());
+    w.zreg(bits(word, 9, 5), dest, Access::read());
+    w.zreg(bits(word, 20, 16), source, Access::read());
+    Some(())
+}
+
+/// The widening multiplies by vector, including the polynomial `pmullb`
+/// and `pmullt`, which also produce quadwords from doublewords.
+fn multiply_long(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    let stem = match bits(word, 12, 11) {
+        0b00 => "sqdmull",
+        0b01 => "pmull",
+        0b10 => "smull",
+        _ => "umull",
+    };
+    let (dest, source) = match (stem, size) {
+        ("pmull", 0b00) => ("q", "d"),
+        ("pmull", 0b10) => return None,
+        _ => long(size)?,
+    };
+    insn_half(w, word, stem);
+    widen(w, word, dest, source);
+    Some(())
+}
+
+/// The interleaving long additions and subtractions, `eorbt` and `eortb`,
+/// and the matrix multiply-accumulates.
+fn interleaved(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    match bits(word, 12, 10) {
+        op @ (0b000 | 0b010 | 0b011) => {
+            let (dest, source) = long(size)?;
+            w.insn(["saddlbt", "", "ssublbt", "ssubltb"][op as usize]);
+            widen(w, word, dest, source);
+        }
+        op @ (0b100 | 0b101) => {
+            w.insn(if op == 0b100 { "eorbt" } else { "eortb" });
+            let size = SIZES[size as usize];
+            accumulate(w, word, size, size);
+        }
+        0b110 => {
+            let name = match size {
+                0b00 => "smmla",
+                0b10 => "usmmla",
+                0b11 => "ummla",
+                _ => return None,
+            };
+            w.insn(name);
+            accumulate(w, word, "s", "b");
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// The long shifts by immediate, `sshllb z0.h, z1.b, #1`, and the bit
+/// permutations `bext`, `bdep` and `bgrp`.
+fn shift_long(word: u32, w: &mut Writer) -> Option<()> {
+    let (zn, zd) = (bits(word, 9, 5), bits(word, 4, 0));
+    let op = bits(word, 12, 10);
+    if op & 0b100 != 0 {
+        let name = match op {
+            0b100 => "bext",
+            0b101 => "bdep",
+            0b110 => "bgrp",
+            _ => return None,
+        };
+        w.insn(name);
+        three(
+            w,
+            SIZES[bits(word, 23, 22) as usize],
+            zd,
+            zn,
+            bits(word, 20, 16),
+        );
+        return Some(());
+    }
+    if bit(word, 23) {
+        return None;
+    }
+    let tsize = u32::from(bit(word, 22)) << 2 | bits(word, 20, 19);
+    let (size, amount) = shift_immediate(tsize, bits(word, 18, 16), true)?;
+    insn_half(w, word, if bit(word, 11) { "ushll" } else { "sshll" });
+    w.zreg(zd, SIZES[size as usize + 1], Access::write());
+    w.zreg(zn, SIZES[size as usize], Access::read());
+    w.uimm(u64::from(amount));
+    Some(())
+}
+
+/// The long absolute difference accumulates, the long additions and
+/// subtractions with carry and the complex integer additions.
+fn accumulate_long(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    match bits(word, 12, 10) {
+        0b000..=0b011 => {
+            let (dest, source) = long(size)?;
+            insn_half(w, word, if bit(word, 11) { "uabal" } else { "sabal" });
+            accumulate(w, word, dest, source);
+        }
+        0b100 | 0b101 => {
+            insn_half(w, word, if bit(word, 23) { "sbcl" } else { "adcl" });
+            let size = if bit(word, 22) { "d" } else { "s" };
+            accumulate(w, word, size, size);
+        }
+        _ => {
+            if bits(word, 20, 17) != 0 {
+                return None;
+            }
+            w.insn(if bit(word, 16) { "sqcadd" } else { "cadd" });
+            let size = SIZES[size as usize];
+            let zdn = bits(word, 4, 0);
+            w.zreg(zdn, size, Access::read_write());
+            w.zreg(zdn, size, Access::read());
+            w.zreg(bits(word, 9, 5), size, Access::read());
+            w.rotation(if bit(word, 10) { 270 } else { 90 });
+        }
+    }
+    Some(())
+}
+
+/// Shift right and accumulate, shift and insert, and the absolute
+/// difference accumulates.
+fn shift_accumulate(word: u32, w: &mut Writer) -> Option<()> {
+    let op = bits(word, 12, 10);
+    if op & 0b110 == 0b110 {
+        w.insn(if op == 0b110 { "saba" } else { "uaba" });
+        let size = SIZES[bits(word, 23, 22) as usize];
+        accumulate(w, word, size, size);
+        return Some(());
+    }
+    let name = ["ssra", "usra", "srsra", "ursra", "sri", "sli"][op as usize];
+    let tsize = bits(word, 23, 22) << 2 | bits(word, 20, 19);
+    let (size, amount) = shift_immediate(tsize, bits(word, 18, 16), name == "sli")?;
+    let size = SIZES[size as usize];
+    w.insn(name);
+    w.zreg(bits(word, 4, 0), size, Access::read_write());
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.uimm(u64::from(amount));
+    Some(())
+}
+
+/// Appends the destination of a narrowing operation: the bottom forms
+/// write whole elements and the top forms keep the bottom halves.
+fn narrow_destination(w: &mut Writer, word: u32, size: &str) {
+    let access = if bit(word, 10) {
+        Access::read_write()
+    } else {
+        Access::write()
+    };
+    w.zreg(bits(word, 4, 0), size, access);
+}
+
+/// The narrowing shifts right by immediate, `shrnb z0.b, z1.h, #1`.
+fn shift_narrow(word: u32, w: &mut Writer) -> Option<()> {
+    if bit(word, 23) {
+        return None;
+    }
+    let stems = if bit(word, 13) {
+        ["sqshrn", "sqrshrn", "uqshrn", "uqrshrn"]
+    } else {
+        ["sqshrun", "sqrshrun", "shrn", "rshrn"]
+    };
+    let tsize = u32::from(bit(word, 22)) << 2 | bits(word, 20, 19);
+    let (size, amount) = shift_immediate(tsize, bits(word, 18, 16), false)?;
+    insn_half(w, word, stems[bits(word, 12, 11) as usize]);
+    narrow_destination(w, word, SIZES[size as usize]);
+    w.zreg(bits(word, 9, 5), SIZES[size as usize + 1], Access::read());
+    w.uimm(u64::from(amount));
+    Some(())
+}
+
+/// The saturating extracts, `sqxtnb z0.b, z1.h`.
+fn extract_narrow(word: u32, w: &mut Writer) -> Option<()> {
+    let tsize = u32::from(bit(word, 22)) << 2 | bits(word, 20, 19);
+    if bit(word, 23) || bits(word, 18, 16) != 0 || !tsize.is_power_of_two() {
+        return None;
+    }
+    let stem = match bits(word, 12, 11) {
+        0b00 => "sqxtn",
+        0b01 => "uqxtn",
+        0b10 => "sqxtun",
+        _ => return None,
+    };
+    let size = tsize.trailing_zeros() as usize;
+    insn_half(w, word, stem);
+    narrow_destination(w, word, SIZES[size]);
+    w.zreg(bits(word, 9, 5), SIZES[size + 1], Access::read());
+    Some(())
+}
+
+/// The high halves of additions and subtractions, `addhnb z0.b, z1.h,
+/// z2.h`.
+fn high_narrow(word: u32, w: &mut Writer) -> Option<()> {
+    let (source, dest) = long(bits(word, 23, 22))?;
+    let stem = ["addhn", "raddhn", "subhn", "rsubhn"][bits(word, 12, 11) as usize];
+    insn_half(w, word, stem);
+    narrow_destination(w, word, dest);
+    w.zreg(bits(word, 9, 5), source, Access::read());
+    w.zreg(bits(word, 20, 16), source, Access::read());
+    Some(())
+}
+
+/// `match` and `nmatch`, setting a predicate where an element occurs in
+/// the other vector's segment.
+fn string_match(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    if size > 0b01 {
+        return None;
+    }
+    let size = SIZES[size as usize];
+    w.insn(if bit(word, 4) { "nmatch" } else { "match" });
+    w.preg(bits(word, 3, 0), size, Access::write());
+    w.governing(bits(word, 12, 10), 'z');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.zreg(bits(word, 20, 16), size, Access::read());
+    Some(())
+}
+
+/// `histseg z0.b, z1.b, z2.b`.
+fn histogram_segment(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 23, 22) != 0 || bits(word, 12, 10) != 0 {
+        return None;
+    }
+    w.insn("histseg");
+    three(
+        w,
+        "b",
+        bits(word, 4, 0),
+        bits(word, 9, 5),
+        bits(word, 20, 16),
+    );
+    Some(())
+}
+
+/// `histcnt z0.s, p0/z, z1.s, z2.s`.
+fn histogram_count(word: u32, w: &mut Writer) -> Option<()> {
+    let size = bits(word, 23, 22);
+    if size < 0b10 {
+        return None;
+    }
+    let size = SIZES[size as usize];
+    w.insn("histcnt");
+    w.zreg(bits(word, 4, 0), size, Access::write());
+    w.governing(bits(word, 12, 10), 'z');
+    w.zreg(bits(word, 9, 5), size, Access::read());
+    w.zreg(bits(word, 20, 16), size, Access::read());
+    Some(())
+}
+
+/// The SVE2 AES, SM4 and SHA3 instructions.
+fn crypto(word: u32, w: &mut Writer) -> Option<()> {
+    if bits(word, 23, 22) != 0 {
+        return None;
+    }
+    let (zm, zn, zd) = (bits(word, 20, 16), bits(word, 9, 5), bits(word, 4, 0));
+    match bits(word, 12, 10) {
+        op @ (0b000 | 0b001) => {
+            let (name, size) = match (zm, op) {
+                (0b00000, 0b000) if zn == 0 => ("aesmc", "b"),
+                (0b00000, 0b001) if zn == 0 => ("aesimc", "b"),
+                (0b00010, 0b000) => ("aese", "b"),
+                (0b00010, 0b001) => ("aesd", "b"),
+                (0b00011, 0b000) => ("sm4e", "s"),
+                _ => return None,
+            };
+            w.insn(name);
+            w.zreg(zd, size, Access::read_write());
+            w.zreg(zd, size, Access::read());
+            if zm != 0 {
+                w.zreg(zn, size, Access::read());
+            }
+        }
+        0b100 => {
+            w.insn("sm4ekey");
+            three(w, "s", zd, zn, zm);
+        }
+        0b101 => {
+            w.insn("rax1");
+            three(w, "d", zd, zn, zm);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::render;
+
+    #[test]
+    fn test_sve2() {
+        let cases = [
+            (0x4482_6020, "sqdmlalb z0.s, z1.h, z2.h"),
+            (0x4542_0020, "saddlb z0.h, z1.b, z2.b"),
+            (0x4528_4020, "sqxtnb z0.b, z1.h"),
+            (0x44e5_9c83, "umlalt z3.d, z4.s, z5.s[1]"),
+            (0x45a2_c020, "histcnt z0.s, p0/z, z1.s, z2.s"),
+        ];
+        for (word, text) in cases {
+            assert_eq!(render(word, 0), text, "{word:#010x}");
+        }
+    }
+}
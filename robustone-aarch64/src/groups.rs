@@ -0,0 +1,71 @@
+//! Semantic instruction groups (mirrors Capstone's `arm64_insn_group`).
+//!
+//! The numeric values of the shared groups match Capstone's generic
+//! `cs_group_type`, as for RISC-V.
+
+/// Instruction group identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AArch64InstructionGroup {
+    Invalid = 0,
+    /// Transfers control to another location.
+    Jump = 1,
+    /// Calls a subroutine and records the return address in `x30`.
+    Call = 2,
+    /// Returns from a subroutine.
+    Ret = 3,
+    /// Raises an exception (`svc`, `hvc`, `smc`, `brk`, `hlt`).
+    Int = 4,
+    /// Returns from an exception.
+    Iret = 5,
+    /// Requires a privileged exception level.
+    Privilege = 6,
+    /// Uses a PC-relative target.
+    BranchRelative = 7,
+}
+
+impl AArch64InstructionGroup {
+    /// Returns the Capstone group name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Invalid => "invalid",
+            Self::Jump => "jump",
+            Self::Call => "call",
+            Self::Ret => "ret",
+            Self::Int => "int",
+            Self::Iret => "iret",
+            Self::Privilege => "privilege",
+            Self::BranchRelative => "branch_relative",
+        }
+    }
+}
+
+use AArch64InstructionGroup::*;
+
+/// Classifies a decoded instruction by its printed or real mnemonic.
+pub fn classify(mnemonic: &str) -> &'static [AArch64InstructionGroup] {
+    match mnemonic {
+        "bl" => &[Call, BranchRelative],
+        "blr" | "blraa" | "blrab" | "blraaz" | "blrabz" => &[Call],
+        "b" | "cbz" | "cbnz" | "tbz" | "tbnz" => &[Jump, BranchRelative],
+        "br" | "braa" | "brab" | "braaz" | "brabz" => &[Jump],
+        "ret" | "retaa" | "retab" => &[Ret],
+        "eret" | "eretaa" | "eretab" => &[Iret, Privilege],
+        "svc" | "hvc" | "smc" | "brk" | "hlt" => &[Int],
+        _ if mnemonic.starts_with("b.") || mnemonic.starts_with("bc.") => &[Jump, BranchRelative],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("bl"), [Call, BranchRelative]);
+        assert_eq!(classify("b.ne"), [Jump, BranchRelative]);
+        assert_eq!(classify("ret"), [Ret]);
+        assert!(classify("add").is_empty());
+    }
+}
@@ -0,0 +1,1005 @@
+//! Instruction identifiers.
+//!
+//! Instructions are numbered like Capstone 5's `arm64_insn`, so `-d` prints
+//! the same `ID:` line as cstool. The names below were generated from
+//! Capstone's `AArch64MappingInsnName.inc` and the aliases it appends after
+//! it; the order is frozen, so never insert or reorder names. As in
+//! Capstone, an alias gets the id of its own mnemonic, and `b.<cond>` is `b`.
+
+/// Mnemonics of the ids from 1, sorted.
+const NAMES: &[&str] = &[
+    "abs",
+    "adc",
+    "adcs",
+    "add",
+    "addhn",
+    "addhn2",
+    "addp",
+    "addpl",
+    "adds",
+    "addv",
+    "addvl",
+    "adr",
+    "adrp",
+    "aesd",
+    "aese",
+    "aesimc",
+    "aesmc",
+    "and",
+    "ands",
+    "andv",
+    "asr",
+    "asrd",
+    "asrr",
+    "asrv",
+    "autda",
+    "autdb",
+    "autdza",
+    "autdzb",
+    "autia",
+    "autia1716",
+    "autiasp",
+    "autiaz",
+    "autib",
+    "autib1716",
+    "autibsp",
+    "autibz",
+    "autiza",
+    "autizb",
+    "b",
+    "bcax",
+    "bfm",
+    "bic",
+    "bics",
+    "bif",
+    "bit",
+    "bl",
+    "blr",
+    "blraa",
+    "blraaz",
+    "blrab",
+    "blrabz",
+    "br",
+    "braa",
+    "braaz",
+    "brab",
+    "brabz",
+    "brk",
+    "brka",
+    "brkas",
+    "brkb",
+    "brkbs",
+    "brkn",
+    "brkns",
+    "brkpa",
+    "brkpas",
+    "brkpb",
+    "brkpbs",
+    "bsl",
+    "cas",
+    "casa",
+    "casab",
+    "casah",
+    "casal",
+    "casalb",
+    "casalh",
+    "casb",
+    "cash",
+    "casl",
+    "caslb",
+    "caslh",
+    "casp",
+    "caspa",
+    "caspal",
+    "caspl",
+    "cbnz",
+    "cbz",
+    "ccmn",
+    "ccmp",
+    "cfinv",
+    "cinc",
+    "cinv",
+    "clasta",
+    "clastb",
+    "clrex",
+    "cls",
+    "clz",
+    "cmeq",
+    "cmge",
+    "cmgt",
+    "cmhi",
+    "cmhs",
+    "cmle",
+    "cmlo",
+    "cmls",
+    "cmlt",
+    "cmn",
+    "cmp",
+    "cmpeq",
+    "cmpge",
+    "cmpgt",
+    "cmphi",
+    "cmphs",
+    "cmple",
+    "cmplo",
+    "cmpls",
+    "cmplt",
+    "cmpne",
+    "cmtst",
+    "cneg",
+    "cnot",
+    "cnt",
+    "cntb",
+    "cntd",
+    "cnth",
+    "cntp",
+    "cntw",
+    "compact",
+    "cpy",
+    "crc32b",
+    "crc32cb",
+    "crc32ch",
+    "crc32cw",
+    "crc32cx",
+    "crc32h",
+    "crc32w",
+    "crc32x",
+    "csdb",
+    "csel",
+    "cset",
+    "csetm",
+    "csinc",
+    "csinv",
+    "csneg",
+    "ctermeq",
+    "ctermne",
+    "dcps1",
+    "dcps2",
+    "dcps3",
+    "decb",
+    "decd",
+    "dech",
+    "decp",
+    "decw",
+    "dmb",
+    "drps",
+    "dsb",
+    "dup",
+    "dupm",
+    "eon",
+    "eor",
+    "eor3",
+    "eors",
+    "eorv",
+    "eret",
+    "eretaa",
+    "eretab",
+    "esb",
+    "ext",
+    "extr",
+    "fabd",
+    "fabs",
+    "facge",
+    "facgt",
+    "facle",
+    "faclt",
+    "fadd",
+    "fadda",
+    "faddp",
+    "faddv",
+    "fcadd",
+    "fccmp",
+    "fccmpe",
+    "fcmeq",
+    "fcmge",
+    "fcmgt",
+    "fcmla",
+    "fcmle",
+    "fcmlt",
+    "fcmne",
+    "fcmp",
+    "fcmpe",
+    "fcmuo",
+    "fcpy",
+    "fcsel",
+    "fcvt",
+    "fcvtas",
+    "fcvtau",
+    "fcvtl",
+    "fcvtl2",
+    "fcvtms",
+    "fcvtmu",
+    "fcvtn",
+    "fcvtn2",
+    "fcvtns",
+    "fcvtnu",
+    "fcvtps",
+    "fcvtpu",
+    "fcvtxn",
+    "fcvtxn2",
+    "fcvtzs",
+    "fcvtzu",
+    "fdiv",
+    "fdivr",
+    "fdup",
+    "fexpa",
+    "fjcvtzs",
+    "fmad",
+    "fmadd",
+    "fmax",
+    "fmaxnm",
+    "fmaxnmp",
+    "fmaxnmv",
+    "fmaxp",
+    "fmaxv",
+    "fmin",
+    "fminnm",
+    "fminnmp",
+    "fminnmv",
+    "fminp",
+    "fminv",
+    "fmla",
+    "fmls",
+    "fmov",
+    "fmsb",
+    "fmsub",
+    "fmul",
+    "fmulx",
+    "fneg",
+    "fnmad",
+    "fnmadd",
+    "fnmla",
+    "fnmls",
+    "fnmsb",
+    "fnmsub",
+    "fnmul",
+    "frecpe",
+    "frecps",
+    "frecpx",
+    "frinta",
+    "frinti",
+    "frintm",
+    "frintn",
+    "frintp",
+    "frintx",
+    "frintz",
+    "frsqrte",
+    "frsqrts",
+    "fscale",
+    "fsqrt",
+    "fsub",
+    "fsubr",
+    "ftmad",
+    "ftsmul",
+    "ftssel",
+    "hint",
+    "hlt",
+    "hvc",
+    "incb",
+    "incd",
+    "inch",
+    "incp",
+    "incw",
+    "index",
+    "ins",
+    "insr",
+    "isb",
+    "lasta",
+    "lastb",
+    "ld1",
+    "ld1b",
+    "ld1d",
+    "ld1h",
+    "ld1r",
+    "ld1rb",
+    "ld1rd",
+    "ld1rh",
+    "ld1rqb",
+    "ld1rqd",
+    "ld1rqh",
+    "ld1rqw",
+    "ld1rsb",
+    "ld1rsh",
+    "ld1rsw",
+    "ld1rw",
+    "ld1sb",
+    "ld1sh",
+    "ld1sw",
+    "ld1w",
+    "ld2",
+    "ld2b",
+    "ld2d",
+    "ld2h",
+    "ld2r",
+    "ld2w",
+    "ld3",
+    "ld3b",
+    "ld3d",
+    "ld3h",
+    "ld3r",
+    "ld3w",
+    "ld4",
+    "ld4b",
+    "ld4d",
+    "ld4h",
+    "ld4r",
+    "ld4w",
+    "ldadd",
+    "ldadda",
+    "ldaddab",
+    "ldaddah",
+    "ldaddal",
+    "ldaddalb",
+    "ldaddalh",
+    "ldaddb",
+    "ldaddh",
+    "ldaddl",
+    "ldaddlb",
+    "ldaddlh",
+    "ldapr",
+    "ldaprb",
+    "ldaprh",
+    "ldapur",
+    "ldapurb",
+    "ldapurh",
+    "ldapursb",
+    "ldapursh",
+    "ldapursw",
+    "ldar",
+    "ldarb",
+    "ldarh",
+    "ldaxp",
+    "ldaxr",
+    "ldaxrb",
+    "ldaxrh",
+    "ldclr",
+    "ldclra",
+    "ldclrab",
+    "ldclrah",
+    "ldclral",
+    "ldclralb",
+    "ldclralh",
+    "ldclrb",
+    "ldclrh",
+    "ldclrl",
+    "ldclrlb",
+    "ldclrlh",
+    "ldeor",
+    "ldeora",
+    "ldeorab",
+    "ldeorah",
+    "ldeoral",
+    "ldeoralb",
+    "ldeoralh",
+    "ldeorb",
+    "ldeorh",
+    "ldeorl",
+    "ldeorlb",
+    "ldeorlh",
+    "ldff1b",
+    "ldff1d",
+    "ldff1h",
+    "ldff1sb",
+    "ldff1sh",
+    "ldff1sw",
+    "ldff1w",
+    "ldlar",
+    "ldlarb",
+    "ldlarh",
+    "ldnf1b",
+    "ldnf1d",
+    "ldnf1h",
+    "ldnf1sb",
+    "ldnf1sh",
+    "ldnf1sw",
+    "ldnf1w",
+    "ldnp",
+    "ldnt1b",
+    "ldnt1d",
+    "ldnt1h",
+    "ldnt1w",
+    "ldp",
+    "ldpsw",
+    "ldr",
+    "ldraa",
+    "ldrab",
+    "ldrb",
+    "ldrh",
+    "ldrsb",
+    "ldrsh",
+    "ldrsw",
+    "ldset",
+    "ldseta",
+    "ldsetab",
+    "ldsetah",
+    "ldsetal",
+    "ldsetalb",
+    "ldsetalh",
+    "ldsetb",
+    "ldseth",
+    "ldsetl",
+    "ldsetlb",
+    "ldsetlh",
+    "ldsmax",
+    "ldsmaxa",
+    "ldsmaxab",
+    "ldsmaxah",
+    "ldsmaxal",
+    "ldsmaxalb",
+    "ldsmaxalh",
+    "ldsmaxb",
+    "ldsmaxh",
+    "ldsmaxl",
+    "ldsmaxlb",
+    "ldsmaxlh",
+    "ldsmin",
+    "ldsmina",
+    "ldsminab",
+    "ldsminah",
+    "ldsminal",
+    "ldsminalb",
+    "ldsminalh",
+    "ldsminb",
+    "ldsminh",
+    "ldsminl",
+    "ldsminlb",
+    "ldsminlh",
+    "ldtr",
+    "ldtrb",
+    "ldtrh",
+    "ldtrsb",
+    "ldtrsh",
+    "ldtrsw",
+    "ldumax",
+    "ldumaxa",
+    "ldumaxab",
+    "ldumaxah",
+    "ldumaxal",
+    "ldumaxalb",
+    "ldumaxalh",
+    "ldumaxb",
+    "ldumaxh",
+    "ldumaxl",
+    "ldumaxlb",
+    "ldumaxlh",
+    "ldumin",
+    "ldumina",
+    "lduminab",
+    "lduminah",
+    "lduminal",
+    "lduminalb",
+    "lduminalh",
+    "lduminb",
+    "lduminh",
+    "lduminl",
+    "lduminlb",
+    "lduminlh",
+    "ldur",
+    "ldurb",
+    "ldurh",
+    "ldursb",
+    "ldursh",
+    "ldursw",
+    "ldxp",
+    "ldxr",
+    "ldxrb",
+    "ldxrh",
+    "lsl",
+    "lslr",
+    "lslv",
+    "lsr",
+    "lsrr",
+    "lsrv",
+    "mad",
+    "madd",
+    "mla",
+    "mls",
+    "mneg",
+    "mov",
+    "movi",
+    "movk",
+    "movn",
+    "movprfx",
+    "movs",
+    "movz",
+    "mrs",
+    "msb",
+    "msr",
+    "msub",
+    "mul",
+    "mvn",
+    "mvni",
+    "nand",
+    "nands",
+    "neg",
+    "negs",
+    "ngc",
+    "ngcs",
+    "nop",
+    "nor",
+    "nors",
+    "not",
+    "nots",
+    "orn",
+    "orns",
+    "orr",
+    "orrs",
+    "orv",
+    "pacda",
+    "pacdb",
+    "pacdza",
+    "pacdzb",
+    "pacga",
+    "pacia",
+    "pacia1716",
+    "paciasp",
+    "paciaz",
+    "pacib",
+    "pacib1716",
+    "pacibsp",
+    "pacibz",
+    "paciza",
+    "pacizb",
+    "pfalse",
+    "pfirst",
+    "pmul",
+    "pmull",
+    "pmull2",
+    "pnext",
+    "prfb",
+    "prfd",
+    "prfh",
+    "prfm",
+    "prfum",
+    "prfw",
+    "psb",
+    "ptest",
+    "ptrue",
+    "ptrues",
+    "punpkhi",
+    "punpklo",
+    "raddhn",
+    "raddhn2",
+    "rax1",
+    "rbit",
+    "rdffr",
+    "rdffrs",
+    "rdvl",
+    "ret",
+    "retaa",
+    "retab",
+    "rev",
+    "rev16",
+    "rev32",
+    "rev64",
+    "revb",
+    "revh",
+    "revw",
+    "rmif",
+    "ror",
+    "rorv",
+    "rshrn",
+    "rshrn2",
+    "rsubhn",
+    "rsubhn2",
+    "saba",
+    "sabal",
+    "sabal2",
+    "sabd",
+    "sabdl",
+    "sabdl2",
+    "sadalp",
+    "saddl",
+    "saddl2",
+    "saddlp",
+    "saddlv",
+    "saddv",
+    "saddw",
+    "saddw2",
+    "sbc",
+    "sbcs",
+    "sbfm",
+    "scvtf",
+    "sdiv",
+    "sdivr",
+    "sdot",
+    "sel",
+    "setf16",
+    "setf8",
+    "setffr",
+    "sev",
+    "sevl",
+    "sha1c",
+    "sha1h",
+    "sha1m",
+    "sha1p",
+    "sha1su0",
+    "sha1su1",
+    "sha256h",
+    "sha256h2",
+    "sha256su0",
+    "sha256su1",
+    "sha512h",
+    "sha512h2",
+    "sha512su0",
+    "sha512su1",
+    "shadd",
+    "shl",
+    "shll",
+    "shll2",
+    "shrn",
+    "shrn2",
+    "shsub",
+    "sli",
+    "sm3partw1",
+    "sm3partw2",
+    "sm3ss1",
+    "sm3tt1a",
+    "sm3tt1b",
+    "sm3tt2a",
+    "sm3tt2b",
+    "sm4e",
+    "sm4ekey",
+    "smaddl",
+    "smax",
+    "smaxp",
+    "smaxv",
+    "smc",
+    "smin",
+    "sminp",
+    "sminv",
+    "smlal",
+    "smlal2",
+    "smlsl",
+    "smlsl2",
+    "smnegl",
+    "smov",
+    "smsubl",
+    "smulh",
+    "smull",
+    "smull2",
+    "splice",
+    "sqabs",
+    "sqadd",
+    "sqdecb",
+    "sqdecd",
+    "sqdech",
+    "sqdecp",
+    "sqdecw",
+    "sqdmlal",
+    "sqdmlal2",
+    "sqdmlsl",
+    "sqdmlsl2",
+    "sqdmulh",
+    "sqdmull",
+    "sqdmull2",
+    "sqincb",
+    "sqincd",
+    "sqinch",
+    "sqincp",
+    "sqincw",
+    "sqneg",
+    "sqrdmlah",
+    "sqrdmlsh",
+    "sqrdmulh",
+    "sqrshl",
+    "sqrshrn",
+    "sqrshrn2",
+    "sqrshrun",
+    "sqrshrun2",
+    "sqshl",
+    "sqshlu",
+    "sqshrn",
+    "sqshrn2",
+    "sqshrun",
+    "sqshrun2",
+    "sqsub",
+    "sqxtn",
+    "sqxtn2",
+    "sqxtun",
+    "sqxtun2",
+    "srhadd",
+    "sri",
+    "srshl",
+    "srshr",
+    "srsra",
+    "sshl",
+    "sshll",
+    "sshll2",
+    "sshr",
+    "ssra",
+    "ssubl",
+    "ssubl2",
+    "ssubw",
+    "ssubw2",
+    "st1",
+    "st1b",
+    "st1d",
+    "st1h",
+    "st1w",
+    "st2",
+    "st2b",
+    "st2d",
+    "st2h",
+    "st2w",
+    "st3",
+    "st3b",
+    "st3d",
+    "st3h",
+    "st3w",
+    "st4",
+    "st4b",
+    "st4d",
+    "st4h",
+    "st4w",
+    "stadd",
+    "staddb",
+    "staddh",
+    "staddl",
+    "staddlb",
+    "staddlh",
+    "stclr",
+    "stclrb",
+    "stclrh",
+    "stclrl",
+    "stclrlb",
+    "stclrlh",
+    "steor",
+    "steorb",
+    "steorh",
+    "steorl",
+    "steorlb",
+    "steorlh",
+    "stllr",
+    "stllrb",
+    "stllrh",
+    "stlr",
+    "stlrb",
+    "stlrh",
+    "stlur",
+    "stlurb",
+    "stlurh",
+    "stlxp",
+    "stlxr",
+    "stlxrb",
+    "stlxrh",
+    "stnp",
+    "stnt1b",
+    "stnt1d",
+    "stnt1h",
+    "stnt1w",
+    "stp",
+    "str",
+    "strb",
+    "strh",
+    "stset",
+    "stsetb",
+    "stseth",
+    "stsetl",
+    "stsetlb",
+    "stsetlh",
+    "stsmax",
+    "stsmaxb",
+    "stsmaxh",
+    "stsmaxl",
+    "stsmaxlb",
+    "stsmaxlh",
+    "stsmin",
+    "stsminb",
+    "stsminh",
+    "stsminl",
+    "stsminlb",
+    "stsminlh",
+    "sttr",
+    "sttrb",
+    "sttrh",
+    "stumax",
+    "stumaxb",
+    "stumaxh",
+    "stumaxl",
+    "stumaxlb",
+    "stumaxlh",
+    "stumin",
+    "stuminb",
+    "stuminh",
+    "stuminl",
+    "stuminlb",
+    "stuminlh",
+    "stur",
+    "sturb",
+    "sturh",
+    "stxp",
+    "stxr",
+    "stxrb",
+    "stxrh",
+    "sub",
+    "subhn",
+    "subhn2",
+    "subr",
+    "subs",
+    "sunpkhi",
+    "sunpklo",
+    "suqadd",
+    "svc",
+    "swp",
+    "swpa",
+    "swpab",
+    "swpah",
+    "swpal",
+    "swpalb",
+    "swpalh",
+    "swpb",
+    "swph",
+    "swpl",
+    "swplb",
+    "swplh",
+    "sxtb",
+    "sxth",
+    "sxtl",
+    "sxtl2",
+    "sxtw",
+    "sys",
+    "sysl",
+    "tbl",
+    "tbnz",
+    "tbx",
+    "tbz",
+    "trn1",
+    "trn2",
+    "tsb",
+    "tst",
+    "uaba",
+    "uabal",
+    "uabal2",
+    "uabd",
+    "uabdl",
+    "uabdl2",
+    "uadalp",
+    "uaddl",
+    "uaddl2",
+    "uaddlp",
+    "uaddlv",
+    "uaddv",
+    "uaddw",
+    "uaddw2",
+    "ubfm",
+    "ucvtf",
+    "udiv",
+    "udivr",
+    "udot",
+    "uhadd",
+    "uhsub",
+    "umaddl",
+    "umax",
+    "umaxp",
+    "umaxv",
+    "umin",
+    "uminp",
+    "uminv",
+    "umlal",
+    "umlal2",
+    "umlsl",
+    "umlsl2",
+    "umnegl",
+    "umov",
+    "umsubl",
+    "umulh",
+    "umull",
+    "umull2",
+    "uqadd",
+    "uqdecb",
+    "uqdecd",
+    "uqdech",
+    "uqdecp",
+    "uqdecw",
+    "uqincb",
+    "uqincd",
+    "uqinch",
+    "uqincp",
+    "uqincw",
+    "uqrshl",
+    "uqrshrn",
+    "uqrshrn2",
+    "uqshl",
+    "uqshrn",
+    "uqshrn2",
+    "uqsub",
+    "uqxtn",
+    "uqxtn2",
+    "urecpe",
+    "urhadd",
+    "urshl",
+    "urshr",
+    "ursqrte",
+    "ursra",
+    "ushl",
+    "ushll",
+    "ushll2",
+    "ushr",
+    "usqadd",
+    "usra",
+    "usubl",
+    "usubl2",
+    "usubw",
+    "usubw2",
+    "uunpkhi",
+    "uunpklo",
+    "uxtb",
+    "uxth",
+    "uxtl",
+    "uxtl2",
+    "uxtw",
+    "uzp1",
+    "uzp2",
+    "wfe",
+    "wfi",
+    "whilele",
+    "whilelo",
+    "whilels",
+    "whilelt",
+    "wrffr",
+    "xar",
+    "xpacd",
+    "xpaci",
+    "xpaclri",
+    "xtn",
+    "xtn2",
+    "yield",
+    "zip1",
+    "zip2",
+];
+
+/// Aliases Capstone numbers after [`NAMES`].
+const ALIASES: &[&str] = &[
+    "sbfiz", "ubfiz", "sbfx", "ubfx", "bfi", "bfxil", "ic", "dc", "at", "tlbi",
+];
+
+/// Returns the Capstone id of `mnemonic`, or `0` when Capstone has none.
+pub fn insn_id(mnemonic: &str) -> u32 {
+    let base = mnemonic.split('.').next().unwrap_or(mnemonic);
+    let index = match NAMES.binary_search(&base) {
+        Ok(index) => index,
+        Err(_) => match ALIASES.iter().position(|name| *name == base) {
+            Some(position) => NAMES.len() + position,
+            None => return 0,
+        },
+    };
+    index as u32 + 1
+}
+
+/// Returns the mnemonic for a raw instruction id, or `None` when unknown.
+pub fn insn_name(id: u32) -> Option<&'static str> {
+    let index = (id as usize).checked_sub(1)?;
+    NAMES
+        .get(index)
+        .or_else(|| ALIASES.get(index - NAMES.len()))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capstone_ids() {
+        assert!(NAMES.windows(2).all(|pair| pair[0] < pair[1]));
+        for (mnemonic, id) in [
+            ("abs", 1),
+            ("b", 39),
+            ("b.eq", 39),
+            ("ldr", 393),
+            ("mov", 488),
+            ("stp", 761),
+            ("zip2", 939),
+            ("sbfiz", 940),
+            ("tlbi", 949),
+        ] {
+            assert_eq!(insn_id(mnemonic), id, "{mnemonic}");
+        }
+        assert_eq!(insn_name(761), Some("stp"));
+        assert_eq!(insn_name(949), Some("tlbi"));
+        assert_eq!(insn_name(0), None);
+        assert_eq!(insn_name(950), None);
+        assert_eq!(insn_id("smstart"), 0);
+    }
+}
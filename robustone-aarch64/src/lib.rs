@@ -0,0 +1,345 @@
+//! AArch64 disassembly module.
+//!
+//! Provides instruction decoding for the A64 instruction set of ARMv8 and
+//! ARMv9, including:
+//! - The base integer instructions, system instructions and barriers
+//! - LSE atomics, pointer authentication (PAC) and BTI
+//! - Scalar floating point and a core subset of Advanced SIMD
+//! - SVE and SVE2 scalable vectors and predicates
+//! - SME outer products, tile moves and tile loads and stores
+//!
+//! Output follows Capstone's `cstool`.
+//!
+//! # `no_std` Support
+//!
+//! Disabling the default `std` feature builds the decoder as `#![no_std]`
+//! on top of `alloc`, like the RISC-V crate.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod arch;
+pub mod decoder;
+pub mod groups;
+pub mod insn;
+pub mod registers;
+pub mod types;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use arch::AArch64InstructionDetail;
+use decoder::{AArch64DecodedInstruction, AArch64Decoder};
+use robustone_core::{
+    traits::{ArchitectureHandler, Symbolizer},
+    types::error::DisasmError,
+    types::instruction::Instruction,
+    types::options::{AliasMode, HandlerOptions, SyntaxStyle},
+};
+use types::AArch64OperandValue;
+
+/// Architecture handler implementation for AArch64 targets.
+pub struct AArch64Handler {
+    /// Decoder used to translate raw bytes into structured instructions.
+    decoder: AArch64Decoder,
+    /// Runtime options set through [`ArchitectureHandler::set_option`].
+    options: HandlerOptions,
+    /// Names branch targets and PC-relative addresses when installed.
+    symbolizer: Option<Box<dyn Symbolizer>>,
+}
+
+impl AArch64Handler {
+    /// Creates a handler for little-endian AArch64.
+    pub fn new() -> Self {
+        Self::from_decoder(AArch64Decoder::new())
+    }
+
+    /// Creates a handler for big-endian AArch64, whose instruction words
+    /// are stored most significant byte first.
+    pub fn big_endian() -> Self {
+        Self::from_decoder(AArch64Decoder::big_endian())
+    }
+
+    fn from_decoder(decoder: AArch64Decoder) -> Self {
+        Self {
+            decoder,
+            options: HandlerOptions::default(),
+            symbolizer: None,
+        }
+    }
+
+    /// Returns the underlying decoder, for callers that need structured operands.
+    pub fn decoder(&self) -> &AArch64Decoder {
+        &self.decoder
+    }
+
+    /// Builds the detail of `decoded`: register accesses, groups and, for
+    /// an alias, the real instruction, whose operands replace the alias's
+    /// with real detail on.
+    fn detail(&self, decoded: AArch64DecodedInstruction, address: u64) -> AArch64InstructionDetail {
+        let mut detail = AArch64InstructionDetail::new();
+        for operand in &decoded.operands_detail {
+            match operand.value {
+                AArch64OperandValue::Register(reg) => {
+                    if operand.access.read {
+                        push_unique(&mut detail.regs_read, reg);
+                    }
+                    if operand.access.write {
+                        push_unique(&mut detail.regs_write, reg);
+                    }
+                }
+                AArch64OperandValue::Memory(mem) => {
+                    push_unique(&mut detail.regs_read, mem.base);
+                    if let Some(index) = mem.index {
+                        push_unique(&mut detail.regs_read, index);
+                    }
+                    if decoded.writeback {
+                        push_unique(&mut detail.regs_write, mem.base);
+                    }
+                }
+                AArch64OperandValue::Immediate(_) | AArch64OperandValue::Float(_) => {}
+            }
+        }
+        for &reg in &decoded.implicit_reads {
+            push_unique(&mut detail.regs_read, reg);
+        }
+        for &reg in &decoded.implicit_writes {
+            push_unique(&mut detail.regs_write, reg);
+        }
+        detail.operands = decoded.operands_detail;
+        detail.writeback = decoded.writeback;
+        if decoded.alias {
+            let mut decoder = self.decoder.clone();
+            decoder.set_aliases(false);
+            let real = decoder.decode_word(decoded.word, address);
+            detail.groups = groups::classify(&real.mnemonic);
+            if self.options.real_detail {
+                detail.operands = real.operands_detail;
+            }
+            detail.real_instruction = Some((real.mnemonic, real.operands));
+        } else {
+            detail.groups = groups::classify(&decoded.mnemonic);
+        }
+        detail
+    }
+
+    /// Replaces the PC-relative target at the end of `operands` with its
+    /// symbol.
+    fn symbolize(&self, target: Option<u64>, operands: &mut String) {
+        let (Some(symbolizer), Some(target)) = (&self.symbolizer, target) else {
+            return;
+        };
+        if symbolizer.symbolize(target).is_none() {
+            return;
+        }
+        let start = operands.rfind(", ").map_or(0, |comma| comma + 2);
+        operands.truncate(start);
+        symbolizer.write_symbol(target, operands);
+    }
+}
+
+impl Default for AArch64Handler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchitectureHandler for AArch64Handler {
+    fn disassemble(&self, bytes: &[u8], addr: u64) -> Result<(Instruction, usize), DisasmError> {
+        let decoded = self.decoder.decode(bytes, addr)?;
+        let size = decoded.size;
+        let mut operands = decoded.operands.clone();
+        self.symbolize(decoded.target, &mut operands);
+
+        let mut instruction = Instruction::new(
+            addr,
+            bytes[..size].to_vec(),
+            decoded.mnemonic.clone(),
+            operands,
+        )
+        .with_id(insn::insn_id(&decoded.mnemonic));
+        if self.options.detail {
+            instruction.detail = Some(Box::new(self.detail(decoded, addr)));
+        }
+        Ok((instruction, size))
+    }
+
+    fn options(&self) -> HandlerOptions {
+        self.options
+    }
+
+    fn set_options(&mut self, options: HandlerOptions) -> Result<(), DisasmError> {
+        if options.syntax != SyntaxStyle::Capstone {
+            return Err(DisasmError::UnsupportedOption(
+                "aarch64 handler only supports Capstone syntax".into(),
+            ));
+        }
+        if options.unsigned_immediates {
+            return Err(DisasmError::UnsupportedOption(
+                "aarch64 handler does not print unsigned immediates".into(),
+            ));
+        }
+        // AArch64 registers have no ABI names, so both namings print the
+        // same text.
+        self.decoder.set_aliases(options.aliases != AliasMode::None);
+        self.decoder.set_radix(options.immediate_radix);
+        self.options = options;
+        Ok(())
+    }
+
+    fn set_symbolizer(
+        &mut self,
+        symbolizer: Option<Box<dyn Symbolizer>>,
+    ) -> Result<(), DisasmError> {
+        self.symbolizer = symbolizer;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "aarch64"
+    }
+
+    fn supports(&self, arch_name: &str) -> bool {
+        if self.decoder.is_big_endian() {
+            arch_name == "aarch64be"
+        } else {
+            matches!(arch_name, "aarch64" | "arm64")
+        }
+    }
+}
+
+/// Appends `reg` to `regs` unless already listed.
+fn push_unique(regs: &mut Vec<u32>, reg: u32) {
+    if !regs.contains(&reg) {
+        regs.push(reg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robustone_core::types::options::{HandlerOption, ImmediateRadix};
+
+    struct Symbols;
+
+    impl Symbolizer for Symbols {
+        fn symbolize(&self, address: u64) -> Option<(&str, u64)> {
+            (address == 0x2000).then_some(("main", 0))
+        }
+    }
+
+    #[test]
+    fn test_aarch64_handler_creation() {
+        let handler = AArch64Handler::new();
+        assert_eq!(handler.name(), "aarch64");
+        assert!(handler.supports("aarch64"));
+        assert!(handler.supports("arm64"));
+        assert!(!handler.supports("aarch64be"));
+        assert!(AArch64Handler::big_endian().supports("aarch64be"));
+    }
+
+    #[test]
+    fn test_disassemble_detail() {
+        let mut handler = AArch64Handler::new();
+        // stp x29, x30, [sp, #-0x10]!
+        let (instruction, size) = handler.disassemble(&[0xfd, 0x7b, 0xbf, 0xa9], 0).unwrap();
+        assert_eq!(size, 4);
+        assert_eq!(instruction.mnemonic, "stp");
+        assert_eq!(instruction.operands, "x29, x30, [sp, #-0x10]!");
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(
+            detail.registers_read(),
+            [
+                registers::reg(registers::Bank::X, 29),
+                registers::LR,
+                registers::SP
+            ]
+        );
+        assert_eq!(detail.registers_written(), [registers::SP]);
+
+        // bl #0x1000 writes the link register.
+        let (instruction, _) = handler.disassemble(&[0x00, 0x04, 0x00, 0x94], 0).unwrap();
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(detail.registers_written(), [registers::LR]);
+        assert_eq!(detail.groups(), ["call", "branch_relative"]);
+
+        // mov x0, x1 is an alias of orr.
+        let (instruction, _) = handler.disassemble(&[0xe0, 0x03, 0x01, 0xaa], 0).unwrap();
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(instruction.mnemonic, "mov");
+        assert_eq!(detail.real_instruction(), Some(("orr", "x0, xzr, x1")));
+        assert_eq!(detail.operands().len(), 2);
+
+        handler.set_option(HandlerOption::RealDetail(true)).unwrap();
+        let (instruction, _) = handler.disassemble(&[0xe0, 0x03, 0x01, 0xaa], 0).unwrap();
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(instruction.mnemonic, "mov");
+        assert_eq!(detail.operands().len(), 3);
+    }
+
+    #[test]
+    fn test_writeback_and_big_endian() {
+        let x = |n| registers::reg(registers::Bank::X, n);
+        // ldr x0, [x1], #0x10 writes the base back.
+        let little = [0x20, 0x04, 0x41, 0xf8];
+        let (instruction, _) = AArch64Handler::new().disassemble(&little, 0).unwrap();
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(detail.registers_read(), [x(1)]);
+        assert_eq!(detail.registers_written(), [x(0), x(1)]);
+
+        let big = [0xf8, 0x41, 0x04, 0x20];
+        let (instruction, size) = AArch64Handler::big_endian().disassemble(&big, 0).unwrap();
+        assert_eq!(size, 4);
+        assert_eq!(instruction.bytes, big);
+        assert_eq!(instruction.operands, "x0, [x1], #0x10");
+
+        // casal x1, x0, [x2] reads and writes its compare register.
+        let (instruction, _) = AArch64Handler::new()
+            .disassemble(&[0x40, 0xfc, 0xe1, 0xc8], 0)
+            .unwrap();
+        let detail = instruction.detail.as_ref().unwrap();
+        assert_eq!(detail.registers_read(), [x(1), x(0), x(2)]);
+        assert_eq!(detail.registers_written(), [x(1)]);
+    }
+
+    #[test]
+    fn test_options() {
+        let mut handler = AArch64Handler::new();
+        let bytes = [0x20, 0x40, 0x00, 0x91];
+        handler
+            .set_option(HandlerOption::ImmediateRadix(ImmediateRadix::Decimal))
+            .unwrap();
+        let (instruction, _) = handler.disassemble(&bytes, 0).unwrap();
+        assert_eq!(instruction.operands, "x0, x1, #16");
+
+        handler
+            .set_option(HandlerOption::Aliases(AliasMode::None))
+            .unwrap();
+        let (instruction, _) = handler.disassemble(&[0xe0, 0x03, 0x01, 0xaa], 0).unwrap();
+        assert_eq!(instruction.mnemonic, "orr");
+        assert!(instruction.detail.unwrap().real_instruction().is_none());
+
+        assert!(
+            handler
+                .set_option(HandlerOption::Syntax(SyntaxStyle::Gnu))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_symbolizer() {
+        let mut handler = AArch64Handler::new();
+        handler.set_symbolizer(Some(Box::new(Symbols))).unwrap();
+        // bl #0x2000
+        let (instruction, _) = handler
+            .disassemble(&[0x00, 0x04, 0x00, 0x94], 0x1000)
+            .unwrap();
+        assert_eq!(instruction.operands, "<main>");
+        // cbz x0, #0x2000
+        let (instruction, _) = handler
+            .disassemble(&[0x00, 0x80, 0x00, 0xb4], 0x1000)
+            .unwrap();
+        assert_eq!(instruction.operands, "x0, <main>");
+    }
+}
@@ -0,0 +1,165 @@
+//! AArch64 register identifiers.
+//!
+//! A register id packs the register bank in bits 5-8 and the register
+//! number in bits 0-4, so `x7` is `X << 5 | 7`. Register 31 of the integer
+//! banks is either the zero register or the stack pointer depending on the
+//! instruction; those four get ids of their own above the banks, like the
+//! SVE first-fault register and the SME `za` array.
+
+/// Register bank, the view of a register an operand uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Bank {
+    /// 32-bit general-purpose registers `w0`-`w30`.
+    W = 0,
+    /// 64-bit general-purpose registers `x0`-`x30`.
+    X = 1,
+    /// 8-bit SIMD&FP registers `b0`-`b31`.
+    B = 2,
+    /// 16-bit SIMD&FP registers `h0`-`h31`.
+    H = 3,
+    /// 32-bit SIMD&FP registers `s0`-`s31`.
+    S = 4,
+    /// 64-bit SIMD&FP registers `d0`-`d31`.
+    D = 5,
+    /// 128-bit SIMD&FP registers `q0`-`q31`.
+    Q = 6,
+    /// Vector registers `v0`-`v31`.
+    V = 7,
+    /// SVE scalable vector registers `z0`-`z31`.
+    Z = 8,
+    /// SVE predicate registers `p0`-`p15`.
+    P = 9,
+}
+
+/// Number of register banks.
+const BANKS: u32 = 10;
+
+/// 32-bit zero register.
+pub const WZR: u32 = BANKS << 5;
+/// 64-bit zero register.
+pub const XZR: u32 = WZR + 1;
+/// 32-bit stack pointer.
+pub const WSP: u32 = WZR + 2;
+/// 64-bit stack pointer.
+pub const SP: u32 = WZR + 3;
+/// SVE first-fault register.
+pub const FFR: u32 = WZR + 4;
+/// SME matrix array, standing for all its tiles.
+pub const ZA: u32 = WZR + 5;
+/// Link register `x30`.
+pub const LR: u32 = (Bank::X as u32) << 5 | 30;
+
+/// Returns the id of register `num` of `bank`. Register 31 of the integer
+/// banks is the zero register.
+pub fn reg(bank: Bank, num: u32) -> u32 {
+    match (bank, num) {
+        (Bank::W, 31) => WZR,
+        (Bank::X, 31) => XZR,
+        _ => (bank as u32) << 5 | (num & 0x1f),
+    }
+}
+
+/// Returns the id of general-purpose register `num`, 64-bit when `sf` is
+/// set, with register 31 being the zero register.
+pub fn gpr(sf: bool, num: u32) -> u32 {
+    reg(if sf { Bank::X } else { Bank::W }, num)
+}
+
+/// Like [`gpr`], with register 31 being the stack pointer.
+pub fn gpr_sp(sf: bool, num: u32) -> u32 {
+    match (sf, num) {
+        (true, 31) => SP,
+        (false, 31) => WSP,
+        _ => gpr(sf, num),
+    }
+}
+
+macro_rules! bank {
+    ($prefix:literal) => {
+        [
+            concat!($prefix, "0"),
+            concat!($prefix, "1"),
+            concat!($prefix, "2"),
+            concat!($prefix, "3"),
+            concat!($prefix, "4"),
+            concat!($prefix, "5"),
+            concat!($prefix, "6"),
+            concat!($prefix, "7"),
+            concat!($prefix, "8"),
+            concat!($prefix, "9"),
+            concat!($prefix, "10"),
+            concat!($prefix, "11"),
+            concat!($prefix, "12"),
+            concat!($prefix, "13"),
+            concat!($prefix, "14"),
+            concat!($prefix, "15"),
+            concat!($prefix, "16"),
+            concat!($prefix, "17"),
+            concat!($prefix, "18"),
+            concat!($prefix, "19"),
+            concat!($prefix, "20"),
+            concat!($prefix, "21"),
+            concat!($prefix, "22"),
+            concat!($prefix, "23"),
+            concat!($prefix, "24"),
+            concat!($prefix, "25"),
+            concat!($prefix, "26"),
+            concat!($prefix, "27"),
+            concat!($prefix, "28"),
+            concat!($prefix, "29"),
+            concat!($prefix, "30"),
+            concat!($prefix, "31"),
+        ]
+    };
+}
+
+/// Register names by bank and number.
+static NAMES: [[&str; 32]; BANKS as usize] = [
+    bank!("w"),
+    bank!("x"),
+    bank!("b"),
+    bank!("h"),
+    bank!("s"),
+    bank!("d"),
+    bank!("q"),
+    bank!("v"),
+    bank!("z"),
+    bank!("p"),
+];
+
+/// Returns the name of register `id`, or `None` for an invalid id.
+pub fn name(id: u32) -> Option<&'static str> {
+    match id {
+        WZR => Some("wzr"),
+        XZR => Some("xzr"),
+        WSP => Some("wsp"),
+        SP => Some("sp"),
+        FFR => Some("ffr"),
+        ZA => Some("za"),
+        // Only the first 16 predicate registers exist.
+        _ if id >> 5 == Bank::P as u32 && id & 0x1f > 15 => None,
+        0..WZR => Some(NAMES[(id >> 5) as usize][(id & 0x1f) as usize]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_names() {
+        assert_eq!(name(gpr(true, 0)), Some("x0"));
+        assert_eq!(name(gpr(false, 31)), Some("wzr"));
+        assert_eq!(name(gpr_sp(true, 31)), Some("sp"));
+        assert_eq!(name(gpr_sp(false, 30)), Some("w30"));
+        assert_eq!(name(reg(Bank::Q, 31)), Some("q31"));
+        assert_eq!(name(LR), Some("x30"));
+        assert_eq!(name(reg(Bank::Z, 31)), Some("z31"));
+        assert_eq!(name(reg(Bank::P, 15)), Some("p15"));
+        assert_eq!(name(reg(Bank::P, 16)), None);
+        assert_eq!(name(ZA), Some("za"));
+        assert_eq!(name(0x200), None);
+    }
+}
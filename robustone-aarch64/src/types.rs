@@ -0,0 +1,70 @@
+//! AArch64 operand types, shaped like Capstone's `cs_arm64_op`.
+
+/// Register access flags (mirrors `cs_ac_type`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Access {
+    /// The operand is read.
+    pub read: bool,
+    /// The operand is written.
+    pub write: bool,
+}
+
+impl Access {
+    pub fn read() -> Self {
+        Self {
+            read: true,
+            write: false,
+        }
+    }
+
+    pub fn write() -> Self {
+        Self {
+            read: false,
+            write: true,
+        }
+    }
+
+    pub fn read_write() -> Self {
+        Self {
+            read: true,
+            write: true,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+/// Memory operand descriptor (matches `ARM64_OP_MEM`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AArch64MemoryOperand {
+    /// Base register id.
+    pub base: u32,
+    /// Index register id of register-offset addressing.
+    pub index: Option<u32>,
+    /// Displacement added to the base.
+    pub disp: i64,
+}
+
+/// Concrete value carried by an operand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AArch64OperandValue {
+    /// Register id; see [`crate::registers`].
+    Register(u32),
+    /// Integer immediate, or the absolute address of a PC-relative operand.
+    Immediate(i64),
+    /// Floating-point immediate.
+    Float(f64),
+    /// Memory reference.
+    Memory(AArch64MemoryOperand),
+}
+
+/// One decoded operand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AArch64Operand {
+    /// How the instruction accesses the operand.
+    pub access: Access,
+    /// The operand itself.
+    pub value: AArch64OperandValue,
+}
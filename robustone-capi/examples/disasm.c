@@ -0,0 +1,72 @@
+/*
+ * Minimal Capstone-style client linked against librobustone_capi.
+ *
+ *   make test-capi
+ */
+
+#include <inttypes.h>
+#include <stdio.h>
+
+#include <capstone/capstone.h>
+
+static const uint8_t CODE[] = {
+	0x13, 0x01, 0x01, 0xff, // addi sp, sp, -16
+	0x23, 0x34, 0x11, 0x00, // sd ra, 8(sp)
+	0xef, 0x00, 0x80, 0x00, // jal ra, 8
+	0x03, 0x35, 0x81, 0x00, // ld a0, 8(sp)
+	0x82, 0x80,             // c.jr ra
+};
+
+int main(void)
+{
+	csh handle;
+	cs_insn *insn;
+	size_t count;
+
+	if (cs_open(CS_ARCH_RISCV, CS_MODE_RISCV64 | CS_MODE_RISCVC, &handle) != CS_ERR_OK) {
+		fprintf(stderr, "cs_open failed\n");
+		return 1;
+	}
+	cs_option(handle, CS_OPT_DETAIL, CS_OPT_ON);
+
+	count = cs_disasm(handle, CODE, sizeof(CODE), 0x1000, 0, &insn);
+	if (count == 0) {
+		fprintf(stderr, "cs_disasm failed: %s\n", cs_strerror(cs_errno(handle)));
+		cs_close(&handle);
+		return 1;
+	}
+
+	for (size_t i = 0; i < count; i++) {
+		cs_riscv *riscv = &insn[i].detail->riscv;
+
+		printf("0x%" PRIx64 ":\t%s\t%s\n", insn[i].address, insn[i].mnemonic, insn[i].op_str);
+		for (uint8_t n = 0; n < riscv->op_count; n++) {
+			cs_riscv_op *op = &riscv->operands[n];
+
+			switch (op->type) {
+			case RISCV_OP_REG:
+				printf("\t\toperands[%u].type: REG = %s\n", n, cs_reg_name(handle, op->reg));
+				break;
+			case RISCV_OP_IMM:
+				printf("\t\toperands[%u].type: IMM = 0x%" PRIx64 "\n", n, op->imm);
+				break;
+			case RISCV_OP_MEM:
+				printf("\t\toperands[%u].type: MEM\n", n);
+				printf("\t\t\toperands[%u].mem.base: REG = %s\n", n,
+						cs_reg_name(handle, op->mem.base));
+				printf("\t\t\toperands[%u].mem.disp: 0x%" PRIx64 "\n", n, op->mem.disp);
+				break;
+			default:
+				break;
+			}
+		}
+		if (cs_insn_group(handle, &insn[i], CS_GRP_CALL))
+			printf("\t\tGroups: call\n");
+		if (cs_insn_group(handle, &insn[i], CS_GRP_RET))
+			printf("\t\tGroups: ret\n");
+	}
+
+	cs_free(insn, count);
+	cs_close(&handle);
+	return 0;
+}
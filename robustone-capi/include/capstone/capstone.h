@@ -0,0 +1,208 @@
+#ifndef CAPSTONE_ENGINE_H
+#define CAPSTONE_ENGINE_H
+
+/*
+ * Capstone-compatible C API backed by Robustone.
+ *
+ * Source- and layout-compatible with the Capstone 5 declarations used by
+ * typical disassembly tools. Only CS_ARCH_RISCV is implemented; opening any
+ * other architecture fails with CS_ERR_ARCH.
+ *
+ * Keep in sync with robustone-capi/src/types.rs and src/lib.rs; the
+ * test_headers_match_rust_definitions test checks every layout and constant.
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define CS_API_MAJOR 5
+#define CS_API_MINOR 0
+
+/// Macro to create combined version which can be compared to
+/// result of cs_version() API.
+#define CS_MAKE_VERSION(major, minor) ((major << 8) + minor)
+
+/// Maximum size of an instruction mnemonic string.
+#define CS_MNEMONIC_SIZE 32
+
+/// Handle using with all API
+typedef size_t csh;
+
+/// Architecture type
+typedef enum cs_arch {
+	CS_ARCH_ARM = 0,    ///< ARM architecture (including Thumb, Thumb-2)
+	CS_ARCH_ARM64,      ///< ARM-64, also called AArch64
+	CS_ARCH_MIPS,       ///< Mips architecture
+	CS_ARCH_X86,        ///< X86 architecture (including x86 & x86-64)
+	CS_ARCH_PPC,        ///< PowerPC architecture
+	CS_ARCH_SPARC,      ///< Sparc architecture
+	CS_ARCH_SYSZ,       ///< SystemZ architecture
+	CS_ARCH_XCORE,      ///< XCore architecture
+	CS_ARCH_M68K,       ///< 68K architecture
+	CS_ARCH_TMS320C64X, ///< TMS320C64x architecture
+	CS_ARCH_M680X,      ///< 680X architecture
+	CS_ARCH_EVM,        ///< Ethereum architecture
+	CS_ARCH_MOS65XX,    ///< MOS65XX architecture (including MOS6502)
+	CS_ARCH_WASM,       ///< WebAssembly architecture
+	CS_ARCH_BPF,        ///< Berkeley Packet Filter architecture (including eBPF)
+	CS_ARCH_RISCV,      ///< RISCV architecture
+	CS_ARCH_SH,         ///< SH architecture
+	CS_ARCH_TRICORE,    ///< TriCore architecture
+	CS_ARCH_MAX,
+	CS_ARCH_ALL = 0xFFFF, // All architectures - for cs_support()
+} cs_arch;
+
+/// Support value to verify diet mode of the engine.
+#define CS_SUPPORT_DIET (CS_ARCH_ALL + 1)
+
+/// Support value to verify X86 reduce mode of the engine.
+#define CS_SUPPORT_X86_REDUCE (CS_ARCH_ALL + 2)
+
+/// Mode type
+typedef enum cs_mode {
+	CS_MODE_LITTLE_ENDIAN = 0,    ///< little-endian mode (default mode)
+	CS_MODE_RISCV32 = 1 << 0,     ///< RISCV RV32G
+	CS_MODE_RISCV64 = 1 << 1,     ///< RISCV RV64G
+	CS_MODE_RISCVC = 1 << 2,      ///< RISCV compressed instructure mode
+	CS_MODE_BIG_ENDIAN = 1U << 31, ///< big-endian mode
+} cs_mode;
+
+/// Runtime option for the disassembled engine
+typedef enum cs_opt_type {
+	CS_OPT_INVALID = 0,    ///< No option specified
+	CS_OPT_SYNTAX,         ///< Assembly output syntax
+	CS_OPT_DETAIL,         ///< Break down instruction structure into details
+	CS_OPT_MODE,           ///< Change engine's mode at run-time
+	CS_OPT_MEM,            ///< User-defined dynamic memory related functions
+	CS_OPT_SKIPDATA,       ///< Skip data when disassembling. Then engine is in SKIPDATA mode.
+	CS_OPT_SKIPDATA_SETUP, ///< Setup user-defined function for SKIPDATA option
+	CS_OPT_MNEMONIC,       ///< Customize instruction mnemonic
+	CS_OPT_UNSIGNED,       ///< print immediate operands in unsigned form
+} cs_opt_type;
+
+/// Runtime option value (associated with option type above)
+typedef enum cs_opt_value {
+	CS_OPT_OFF = 0,            ///< Turn OFF an option - default for CS_OPT_DETAIL, CS_OPT_SKIPDATA, CS_OPT_UNSIGNED.
+	CS_OPT_ON = 3,             ///< Turn ON an option (CS_OPT_DETAIL, CS_OPT_SKIPDATA).
+	CS_OPT_SYNTAX_DEFAULT = 0, ///< Default asm syntax (CS_OPT_SYNTAX).
+} cs_opt_value;
+
+/// Common instruction groups - to be consistent across all architectures.
+typedef enum cs_group_type {
+	CS_GRP_INVALID = 0,     ///< uninitialized/invalid group.
+	CS_GRP_JUMP,            ///< all jump instructions (conditional+direct+indirect jumps)
+	CS_GRP_CALL,            ///< all call instructions
+	CS_GRP_RET,             ///< all return instructions
+	CS_GRP_INT,             ///< all interrupt instructions (int+syscall)
+	CS_GRP_IRET,            ///< all interrupt return instructions
+	CS_GRP_PRIVILEGE,       ///< all privileged instructions
+	CS_GRP_BRANCH_RELATIVE, ///< all relative branching instructions
+} cs_group_type;
+
+#include "riscv.h"
+
+/// NOTE: All information in cs_detail is only available when CS_OPT_DETAIL = CS_OPT_ON
+typedef struct cs_detail {
+	uint16_t regs_read[20]; ///< list of registers read by this insn
+	uint8_t regs_read_count; ///< number of registers in regs_read[] array
+
+	uint16_t regs_write[20]; ///< list of registers modified by this insn
+	uint8_t regs_write_count; ///< number of registers in regs_write[] array
+
+	uint8_t groups[8]; ///< list of group this instruction belong to
+	uint8_t groups_count; ///< number of groups this insn belongs to
+
+	/// Instruction has writeback operands.
+	bool writeback;
+
+	/// Architecture-specific instruction info
+	union {
+		cs_riscv riscv; ///< RISCV architecture
+	};
+} cs_detail;
+
+/// Detail information of disassembled instruction
+typedef struct cs_insn {
+	/// Instruction ID (basically a numeric ID for the instruction mnemonic)
+	unsigned int id;
+
+	/// Address (EIP) of this instruction
+	uint64_t address;
+
+	/// Size of this instruction
+	uint16_t size;
+
+	/// Machine bytes of this instruction, with number of bytes indicated by @size above
+	uint8_t bytes[24];
+
+	/// Ascii text of instruction mnemonic
+	char mnemonic[CS_MNEMONIC_SIZE];
+
+	/// Ascii text of instruction operands
+	char op_str[160];
+
+	/// Pointer to cs_detail.
+	/// NOTE: detail pointer is only valid when CS_OPT_DETAIL = CS_OPT_ON
+	cs_detail *detail;
+} cs_insn;
+
+/// Calculate the offset of a disassembled instruction in its buffer, given
+/// the offset of its first instruction.
+#define CS_INSN_OFFSET(insns, post) (insns[post - 1].address - insns[0].address)
+
+/// All type of errors encountered by Capstone API.
+typedef enum cs_err {
+	CS_ERR_OK = 0,    ///< No error: everything was fine
+	CS_ERR_MEM,       ///< Out-Of-Memory error: cs_open(), cs_disasm(), cs_disasm_iter()
+	CS_ERR_ARCH,      ///< Unsupported architecture: cs_open()
+	CS_ERR_HANDLE,    ///< Invalid handle: cs_op_count(), cs_op_index()
+	CS_ERR_CSH,       ///< Invalid csh argument: cs_close(), cs_errno(), cs_option()
+	CS_ERR_MODE,      ///< Invalid/unsupported mode: cs_open()
+	CS_ERR_OPTION,    ///< Invalid/unsupported option: cs_option()
+	CS_ERR_DETAIL,    ///< Information is unavailable because detail option is OFF
+	CS_ERR_MEMSETUP,  ///< Dynamic memory management uninitialized (see CS_OPT_MEM)
+	CS_ERR_VERSION,   ///< Unsupported version (bindings)
+	CS_ERR_DIET,      ///< Access irrelevant data in "diet" engine
+	CS_ERR_SKIPDATA,  ///< Access irrelevant data for "data" instruction in SKIPDATA mode
+	CS_ERR_X86_ATT,   ///< X86 AT&T syntax is unsupported (opt-out at compile time)
+	CS_ERR_X86_INTEL, ///< X86 Intel syntax is unsupported (opt-out at compile time)
+	CS_ERR_X86_MASM,  ///< X86 Masm syntax is unsupported (opt-out at compile time)
+} cs_err;
+
+unsigned int cs_version(int *major, int *minor);
+bool cs_support(int query);
+
+cs_err cs_open(cs_arch arch, cs_mode mode, csh *handle);
+cs_err cs_close(csh *handle);
+cs_err cs_option(csh handle, cs_opt_type type, size_t value);
+cs_err cs_errno(csh handle);
+const char *cs_strerror(cs_err code);
+
+size_t cs_disasm(csh handle, const uint8_t *code, size_t code_size,
+		uint64_t address, size_t count, cs_insn **insn);
+void cs_free(cs_insn *insn, size_t count);
+cs_insn *cs_malloc(csh handle);
+bool cs_disasm_iter(csh handle, const uint8_t **code, size_t *size,
+		uint64_t *address, cs_insn *insn);
+
+const char *cs_reg_name(csh handle, unsigned int reg_id);
+const char *cs_insn_name(csh handle, unsigned int insn_id);
+const char *cs_group_name(csh handle, unsigned int group_id);
+
+bool cs_insn_group(csh handle, const cs_insn *insn, unsigned int group_id);
+bool cs_reg_read(csh handle, const cs_insn *insn, unsigned int reg_id);
+bool cs_reg_write(csh handle, const cs_insn *insn, unsigned int reg_id);
+int cs_op_count(csh handle, const cs_insn *insn, unsigned int op_type);
+int cs_op_index(csh handle, const cs_insn *insn, unsigned int op_type,
+		unsigned int position);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
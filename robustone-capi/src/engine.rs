@@ -0,0 +1,224 @@
+//! Per-handle disassembly state behind a `csh`.
+
+use std::os::raw::c_char;
+
+use robustone_riscv::decoder::{RiscVDecodedInstruction, RiscVDecoder, Xlen};
+use robustone_riscv::extensions::Extensions;
+use robustone_riscv::groups;
+use robustone_riscv::types::{RiscVOperandType, RiscVOperandValue};
+
+use crate::types::*;
+
+/// Mode bits accepted for `CS_ARCH_RISCV`.
+const RISCV_MODE_MASK: cs_mode = CS_MODE_RISCV32 | CS_MODE_RISCV64 | CS_MODE_RISCVC;
+
+/// State owned by an open handle.
+pub(crate) struct Engine {
+    mode: cs_mode,
+    decoder: RiscVDecoder,
+    detail: bool,
+    pub(crate) skipdata: bool,
+    pub(crate) errno: cs_err,
+}
+
+impl Engine {
+    /// Creates an engine for `arch`/`mode`, validating both.
+    pub(crate) fn new(arch: cs_arch, mode: cs_mode) -> Result<Self, cs_err> {
+        if arch != CS_ARCH_RISCV {
+            return Err(CS_ERR_ARCH);
+        }
+        Ok(Self {
+            mode,
+            decoder: Self::decoder_for(mode, false)?,
+            detail: false,
+            skipdata: false,
+            errno: CS_ERR_OK,
+        })
+    }
+
+    /// Switches the engine to a new mode (`CS_OPT_MODE`).
+    pub(crate) fn set_mode(&mut self, mode: cs_mode) -> Result<(), cs_err> {
+        self.decoder = Self::decoder_for(mode, self.detail)?;
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Turns detail on or off (`CS_OPT_DETAIL`); with detail off the decoder
+    /// skips operand bookkeeping entirely.
+    pub(crate) fn set_detail(&mut self, detail: bool) {
+        self.decoder.set_detail(detail);
+        self.detail = detail;
+    }
+
+    fn decoder_for(mode: cs_mode, detail: bool) -> Result<RiscVDecoder, cs_err> {
+        if mode & !RISCV_MODE_MASK != 0 {
+            return Err(CS_ERR_MODE);
+        }
+        let (xlen, extensions) = match mode & (CS_MODE_RISCV32 | CS_MODE_RISCV64) {
+            CS_MODE_RISCV32 => (Xlen::X32, Extensions::rv32gc()),
+            CS_MODE_RISCV64 => (Xlen::X64, Extensions::rv64gc()),
+            _ => return Err(CS_ERR_MODE),
+        };
+        let extensions = if mode & CS_MODE_RISCVC != 0 {
+            extensions
+        } else {
+            extensions.without_compressed()
+        };
+        let mut decoder = RiscVDecoder::new(xlen, extensions);
+        decoder.set_detail(detail);
+        Ok(decoder)
+    }
+
+    /// Whether detail mode is on.
+    pub(crate) fn detail(&self) -> bool {
+        self.detail
+    }
+
+    /// Number of bytes consumed by a `.byte` entry in SKIPDATA mode.
+    fn skipdata_size(&self) -> usize {
+        if self.mode & CS_MODE_RISCVC != 0 {
+            2
+        } else {
+            4
+        }
+    }
+
+    /// Decodes the instruction at the start of `code` into `insn`.
+    ///
+    /// Returns the number of bytes consumed, or `None` when nothing could be
+    /// decoded (and SKIPDATA did not apply). `insn.detail` is filled when
+    /// detail mode is on and the pointer is non-null.
+    pub(crate) fn decode_into(
+        &self,
+        code: &[u8],
+        address: u64,
+        insn: &mut cs_insn,
+    ) -> Option<usize> {
+        let decoded = self
+            .decoder
+            .decode(code, address)
+            .ok()
+            .filter(|decoded| !matches!(decoded.mnemonic, "unknown" | "c.unknown"));
+
+        match decoded {
+            Some(decoded) => {
+                self.fill_instruction(&code[..decoded.size], address, &decoded, insn);
+                Some(decoded.size)
+            }
+            None if self.skipdata && code.len() >= self.skipdata_size() => {
+                let data = &code[..self.skipdata_size()];
+                self.fill_data(data, address, insn);
+                Some(data.len())
+            }
+            None => None,
+        }
+    }
+
+    fn fill_instruction(
+        &self,
+        bytes: &[u8],
+        address: u64,
+        decoded: &RiscVDecodedInstruction,
+        insn: &mut cs_insn,
+    ) {
+        insn.id = decoded.id().id();
+        insn.address = address;
+        insn.size = bytes.len() as u16;
+        insn.bytes = [0; CS_INSN_BYTES_SIZE];
+        insn.bytes[..bytes.len()].copy_from_slice(bytes);
+        copy_c_string(&mut insn.mnemonic, decoded.mnemonic);
+        copy_c_string(&mut insn.op_str, &decoded.operands.to_string());
+
+        if !self.detail || insn.detail.is_null() {
+            return;
+        }
+        // SAFETY: non-null detail pointers are allocated by this crate.
+        let detail = unsafe { &mut *insn.detail };
+        *detail = cs_detail::zeroed();
+
+        for operand in decoded.operands_detail.iter().take(RISCV_MAX_OPERANDS) {
+            let op = &mut detail.riscv.operands[detail.riscv.op_count as usize];
+            match (operand.op_type, &operand.value) {
+                (RiscVOperandType::Register, RiscVOperandValue::Register(reg)) => {
+                    op.type_ = RISCV_OP_REG;
+                    op.value.reg = capstone_register(*reg);
+                    if operand.access.read {
+                        push_register(&mut detail.regs_read, &mut detail.regs_read_count, *reg);
+                    }
+                    if operand.access.write {
+                        push_register(&mut detail.regs_write, &mut detail.regs_write_count, *reg);
+                    }
+                }
+                (_, RiscVOperandValue::Immediate(imm)) => {
+                    op.type_ = RISCV_OP_IMM;
+                    op.value.imm = *imm;
+                }
+                (_, RiscVOperandValue::Memory(mem)) => {
+                    op.type_ = RISCV_OP_MEM;
+                    op.value.mem = riscv_op_mem {
+                        base: capstone_register(mem.base),
+                        disp: mem.disp,
+                    };
+                    detail.riscv.need_effective_addr = true;
+                    push_register(&mut detail.regs_read, &mut detail.regs_read_count, mem.base);
+                }
+                _ => continue,
+            }
+            detail.riscv.op_count += 1;
+        }
+
+        for group in groups::classify(decoded.mnemonic, &decoded.operands_detail)
+            .iter()
+            .take(CS_MAX_GROUPS)
+        {
+            detail.groups[detail.groups_count as usize] = *group as u8;
+            detail.groups_count += 1;
+        }
+    }
+
+    fn fill_data(&self, bytes: &[u8], address: u64, insn: &mut cs_insn) {
+        let op_str = bytes
+            .iter()
+            .map(|byte| format!("0x{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        insn.id = 0;
+        insn.address = address;
+        insn.size = bytes.len() as u16;
+        insn.bytes = [0; CS_INSN_BYTES_SIZE];
+        insn.bytes[..bytes.len()].copy_from_slice(bytes);
+        copy_c_string(&mut insn.mnemonic, ".byte");
+        copy_c_string(&mut insn.op_str, &op_str);
+        if !insn.detail.is_null() {
+            // SAFETY: non-null detail pointers are allocated by this crate.
+            unsafe { *insn.detail = cs_detail::zeroed() };
+        }
+    }
+}
+
+/// Maps a decoder register id (see `RiscVRegisterClass::id`) to Capstone's
+/// `RISCV_REG_*` id, one above it for both integer and floating-point
+/// registers.
+fn capstone_register(reg: u32) -> u32 {
+    reg + 1
+}
+
+/// Appends a register to a detail list, skipping duplicates and overflow.
+fn push_register(list: &mut [u16], count: &mut u8, reg: u32) {
+    let reg = capstone_register(reg) as u16;
+    let len = *count as usize;
+    if len < list.len() && !list[..len].contains(&reg) {
+        list[len] = reg;
+        *count += 1;
+    }
+}
+
+/// Copies `value` into a fixed C buffer, truncating and NUL-terminating it.
+fn copy_c_string(buffer: &mut [c_char], value: &str) {
+    let len = value.len().min(buffer.len() - 1);
+    for (slot, byte) in buffer.iter_mut().zip(&value.as_bytes()[..len]) {
+        *slot = *byte as c_char;
+    }
+    buffer[len] = 0;
+}
@@ -0,0 +1,955 @@
+//! Capstone-compatible C API for Robustone.
+//!
+//! This crate builds a `cdylib` and a `staticlib` exporting the subset of the
+//! Capstone 5 API needed by typical disassembly tools: `cs_open`, `cs_close`,
+//! `cs_option`, `cs_disasm`, `cs_disasm_iter`, `cs_malloc`, `cs_free`,
+//! `cs_errno`, `cs_strerror`, `cs_reg_name`, `cs_insn_name`, `cs_group_name`, `cs_insn_group`,
+//! `cs_reg_read`, `cs_reg_write`, `cs_op_count` and `cs_op_index`. Only
+//! `CS_ARCH_RISCV` is backed by a decoder; other architectures are rejected
+//! with `CS_ERR_ARCH`.
+//!
+//! The matching headers live in `include/capstone/`, so existing C and C++
+//! tools can keep `#include <capstone/capstone.h>` and link against
+//! `librobustone_capi` instead of `libcapstone`.
+
+pub mod types;
+
+mod engine;
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_uint};
+use std::ptr;
+use std::slice;
+use std::sync::OnceLock;
+
+use robustone_riscv::groups::RiscVInstructionGroup;
+use robustone_riscv::insn::RiscVInstructionId;
+
+use engine::Engine;
+use types::*;
+
+/// Register names indexed by `RISCV_REG_*` id.
+static REGISTER_NAMES: [&CStr; 97] = [
+    c"invalid", c"zero", c"ra", c"sp", c"gp", c"tp", c"t0", c"t1", c"t2", c"s0", c"s1", c"a0",
+    c"a1", c"a2", c"a3", c"a4", c"a5", c"a6", c"a7", c"s2", c"s3", c"s4", c"s5", c"s6", c"s7",
+    c"s8", c"s9", c"s10", c"s11", c"t3", c"t4", c"t5", c"t6", c"ft0", c"ft1", c"ft2", c"ft3",
+    c"ft4", c"ft5", c"ft6", c"ft7", c"fs0", c"fs1", c"fa0", c"fa1", c"fa2", c"fa3", c"fa4", c"fa5",
+    c"fa6", c"fa7", c"fs2", c"fs3", c"fs4", c"fs5", c"fs6", c"fs7", c"fs8", c"fs9", c"fs10",
+    c"fs11", c"ft8", c"ft9", c"ft10", c"ft11", c"ft0", c"ft1", c"ft2", c"ft3", c"ft4", c"ft5",
+    c"ft6", c"ft7", c"fs0", c"fs1", c"fa0", c"fa1", c"fa2", c"fa3", c"fa4", c"fa5", c"fa6", c"fa7",
+    c"fs2", c"fs3", c"fs4", c"fs5", c"fs6", c"fs7", c"fs8", c"fs9", c"fs10", c"fs11", c"ft8",
+    c"ft9", c"ft10", c"ft11",
+];
+
+/// Group names indexed by `RISCV_GRP_*` id.
+static GROUP_NAMES: [&CStr; 8] = [
+    c"invalid",
+    c"jump",
+    c"call",
+    c"ret",
+    c"int",
+    c"iret",
+    c"privilege",
+    c"branch_relative",
+];
+
+/// Instruction names indexed by `RISCV_INS_*` id, built on first use.
+fn instruction_names() -> &'static [CString] {
+    static NAMES: OnceLock<Vec<CString>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        std::iter::once(RiscVInstructionId::Invalid)
+            .chain(RiscVInstructionId::ALL.iter().copied())
+            .map(|id| CString::new(id.name()).expect("mnemonics contain no NUL bytes"))
+            .collect()
+    })
+}
+
+/// Resolves a handle into its engine, or `None` for a null handle.
+///
+/// # Safety
+///
+/// `handle` must be zero or a value produced by `cs_open` that has not been
+/// closed yet.
+unsafe fn engine<'a>(handle: csh) -> Option<&'a mut Engine> {
+    unsafe { (handle as *mut Engine).as_mut() }
+}
+
+/// Releases the detail record attached to `insn`, if any.
+///
+/// # Safety
+///
+/// A non-null `insn.detail` must have been allocated by this crate and not
+/// freed yet.
+unsafe fn free_detail(insn: &cs_insn) {
+    if !insn.detail.is_null() {
+        drop(unsafe { Box::from_raw(insn.detail) });
+    }
+}
+
+/// Reports the API version, Capstone style.
+///
+/// # Safety
+///
+/// `major` and `minor` must each be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_version(major: *mut c_int, minor: *mut c_int) -> c_uint {
+    unsafe {
+        if let Some(major) = major.as_mut() {
+            *major = CS_API_MAJOR;
+        }
+        if let Some(minor) = minor.as_mut() {
+            *minor = CS_API_MINOR;
+        }
+    }
+    ((CS_API_MAJOR << 8) + CS_API_MINOR) as c_uint
+}
+
+/// Returns whether an architecture (or build feature) is supported.
+#[unsafe(no_mangle)]
+pub extern "C" fn cs_support(query: c_int) -> bool {
+    query == CS_ARCH_RISCV
+}
+
+/// Opens a disassembly handle for `arch`/`mode`.
+///
+/// # Safety
+///
+/// `handle` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_open(arch: cs_arch, mode: cs_mode, handle: *mut csh) -> cs_err {
+    if handle.is_null() {
+        return CS_ERR_CSH;
+    }
+    match Engine::new(arch, mode) {
+        Ok(engine) => {
+            unsafe { *handle = Box::into_raw(Box::new(engine)) as csh };
+            CS_ERR_OK
+        }
+        Err(err) => {
+            unsafe { *handle = 0 };
+            err
+        }
+    }
+}
+
+/// Closes a handle and resets it to zero.
+///
+/// # Safety
+///
+/// `handle` must point to a handle obtained from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_close(handle: *mut csh) -> cs_err {
+    let Some(handle) = (unsafe { handle.as_mut() }) else {
+        return CS_ERR_CSH;
+    };
+    if *handle == 0 {
+        return CS_ERR_CSH;
+    }
+    drop(unsafe { Box::from_raw(*handle as *mut Engine) });
+    *handle = 0;
+    CS_ERR_OK
+}
+
+/// Changes a runtime option on an open handle.
+///
+/// Supported options are `CS_OPT_DETAIL`, `CS_OPT_SKIPDATA`, `CS_OPT_MODE`
+/// and `CS_OPT_SYNTAX` with `CS_OPT_SYNTAX_DEFAULT`; anything else returns
+/// `CS_ERR_OPTION`.
+///
+/// # Safety
+///
+/// `handle` must be zero or a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_option(handle: csh, option: cs_opt_type, value: usize) -> cs_err {
+    let Some(engine) = (unsafe { engine(handle) }) else {
+        return CS_ERR_CSH;
+    };
+    let result = match option {
+        CS_OPT_DETAIL => {
+            engine.set_detail(value == CS_OPT_ON);
+            Ok(())
+        }
+        CS_OPT_SKIPDATA => {
+            engine.skipdata = value == CS_OPT_ON;
+            Ok(())
+        }
+        CS_OPT_MODE => engine.set_mode(value as cs_mode),
+        CS_OPT_SYNTAX if value == CS_OPT_SYNTAX_DEFAULT => Ok(()),
+        _ => Err(CS_ERR_OPTION),
+    };
+    let err = result.err().unwrap_or(CS_ERR_OK);
+    engine.errno = err;
+    err
+}
+
+/// Returns the last error recorded on a handle.
+///
+/// # Safety
+///
+/// `handle` must be zero or a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_errno(handle: csh) -> cs_err {
+    unsafe { engine(handle) }.map_or(CS_ERR_CSH, |engine| engine.errno)
+}
+
+/// Returns a static description of an error code.
+#[unsafe(no_mangle)]
+pub extern "C" fn cs_strerror(code: cs_err) -> *const c_char {
+    let message = match code {
+        CS_ERR_OK => c"OK (CS_ERR_OK)",
+        CS_ERR_MEM => c"Out of memory (CS_ERR_MEM)",
+        CS_ERR_ARCH => c"Invalid/unsupported architecture(CS_ERR_ARCH)",
+        CS_ERR_HANDLE => c"Invalid handle (CS_ERR_HANDLE)",
+        CS_ERR_CSH => c"Invalid csh (CS_ERR_CSH)",
+        CS_ERR_MODE => c"Invalid mode (CS_ERR_MODE)",
+        CS_ERR_OPTION => c"Invalid option (CS_ERR_OPTION)",
+        CS_ERR_DETAIL => c"Details are unavailable (CS_ERR_DETAIL)",
+        CS_ERR_MEMSETUP => c"Dynamic memory management uninitialized (CS_ERR_MEMSETUP)",
+        CS_ERR_VERSION => c"Different API version between core & binding (CS_ERR_VERSION)",
+        CS_ERR_DIET => c"Information irrelevant in diet engine (CS_ERR_DIET)",
+        CS_ERR_SKIPDATA => {
+            c"Information irrelevant for 'data' instruction in SKIPDATA mode (CS_ERR_SKIPDATA)"
+        }
+        CS_ERR_X86_ATT => c"AT&T syntax is unavailable (CS_ERR_X86_ATT)",
+        CS_ERR_X86_INTEL => c"INTEL syntax is unavailable (CS_ERR_X86_INTEL)",
+        CS_ERR_X86_MASM => c"MASM syntax is unavailable (CS_ERR_X86_MASM)",
+        _ => c"Unknown error code",
+    };
+    message.as_ptr()
+}
+
+/// Disassembles up to `count` instructions (all when `count` is zero).
+///
+/// On success `*insn` points to an array that must be released with
+/// [`cs_free`]; on failure it is set to null and zero is returned.
+///
+/// # Safety
+///
+/// `handle` must be a live handle, `code` must be valid for `code_size` reads
+/// and `insn` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_disasm(
+    handle: csh,
+    code: *const u8,
+    code_size: usize,
+    address: u64,
+    count: usize,
+    insn: *mut *mut cs_insn,
+) -> usize {
+    let Some(engine) = (unsafe { engine(handle) }) else {
+        return 0;
+    };
+    if insn.is_null() {
+        engine.errno = CS_ERR_HANDLE;
+        return 0;
+    }
+    engine.errno = CS_ERR_OK;
+    unsafe { *insn = ptr::null_mut() };
+    if code.is_null() || code_size == 0 {
+        return 0;
+    }
+
+    let code = unsafe { slice::from_raw_parts(code, code_size) };
+    let mut instructions = Vec::new();
+    let mut offset = 0;
+    while offset < code.len() && (count == 0 || instructions.len() < count) {
+        let mut current = cs_insn::zeroed();
+        if engine.detail() {
+            current.detail = Box::into_raw(Box::new(cs_detail::zeroed()));
+        }
+        let address = address.wrapping_add(offset as u64);
+        match engine.decode_into(&code[offset..], address, &mut current) {
+            Some(size) => {
+                instructions.push(current);
+                offset += size;
+            }
+            None => {
+                unsafe { free_detail(&current) };
+                break;
+            }
+        }
+    }
+
+    let decoded = instructions.len();
+    if decoded > 0 {
+        unsafe { *insn = Box::into_raw(instructions.into_boxed_slice()) as *mut cs_insn };
+    }
+    decoded
+}
+
+/// Releases instructions allocated by [`cs_disasm`] or [`cs_malloc`].
+///
+/// # Safety
+///
+/// `insn` must be null or an array of exactly `count` instructions returned
+/// by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_free(insn: *mut cs_insn, count: usize) {
+    if insn.is_null() {
+        return;
+    }
+    let instructions = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(insn, count)) };
+    for insn in instructions.iter() {
+        unsafe { free_detail(insn) };
+    }
+}
+
+/// Allocates a single instruction for use with [`cs_disasm_iter`].
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_malloc(handle: csh) -> *mut cs_insn {
+    if unsafe { engine(handle) }.is_none() {
+        return ptr::null_mut();
+    }
+    // Like Capstone, the detail record is always allocated, so detail can
+    // be turned on after the instruction was allocated.
+    let mut insn = cs_insn::zeroed();
+    insn.detail = Box::into_raw(Box::new(cs_detail::zeroed()));
+    // Allocated as a one-element slice so `cs_free(insn, 1)` can release it.
+    Box::into_raw(Box::new([insn]) as Box<[cs_insn]>) as *mut cs_insn
+}
+
+/// Disassembles one instruction and advances `code`, `size` and `address`.
+///
+/// Returns `false` when no instruction could be decoded.
+///
+/// # Safety
+///
+/// `handle` must be a live handle; `code`, `size` and `address` must be valid
+/// for reads and writes, `*code` valid for `*size` reads, and `insn` must come
+/// from [`cs_malloc`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_disasm_iter(
+    handle: csh,
+    code: *mut *const u8,
+    size: *mut usize,
+    address: *mut u64,
+    insn: *mut cs_insn,
+) -> bool {
+    let Some(engine) = (unsafe { engine(handle) }) else {
+        return false;
+    };
+    engine.errno = CS_ERR_OK;
+    let (Some(code), Some(size), Some(address), Some(insn)) = (unsafe {
+        (
+            code.as_mut(),
+            size.as_mut(),
+            address.as_mut(),
+            insn.as_mut(),
+        )
+    }) else {
+        engine.errno = CS_ERR_HANDLE;
+        return false;
+    };
+    if code.is_null() || *size == 0 {
+        return false;
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(*code, *size) };
+    let Some(consumed) = engine.decode_into(bytes, *address, insn) else {
+        return false;
+    };
+    *code = unsafe { code.add(consumed) };
+    *size -= consumed;
+    *address = address.wrapping_add(consumed as u64);
+    true
+}
+
+/// Returns the name of a `RISCV_REG_*` register, or null when unknown.
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_reg_name(handle: csh, reg_id: c_uint) -> *const c_char {
+    if unsafe { engine(handle) }.is_none() || reg_id == 0 {
+        return ptr::null();
+    }
+    REGISTER_NAMES
+        .get(reg_id as usize)
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Returns the name of a `RISCV_INS_*` instruction, or null when unknown.
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_insn_name(handle: csh, insn_id: c_uint) -> *const c_char {
+    if unsafe { engine(handle) }.is_none() || insn_id == 0 {
+        return ptr::null();
+    }
+    instruction_names()
+        .get(insn_id as usize)
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Returns the name of a `RISCV_GRP_*` group, or null when unknown.
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`cs_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_group_name(handle: csh, group_id: c_uint) -> *const c_char {
+    if unsafe { engine(handle) }.is_none()
+        || RiscVInstructionGroup::from_id(group_id) == RiscVInstructionGroup::Invalid
+    {
+        return ptr::null();
+    }
+    GROUP_NAMES[group_id as usize].as_ptr()
+}
+
+/// Resolves the detail of `insn`, recording `CS_ERR_DETAIL` when it is absent.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` null or a valid instruction.
+unsafe fn instruction_detail<'a>(handle: csh, insn: *const cs_insn) -> Option<&'a cs_detail> {
+    let engine = unsafe { engine(handle) }?;
+    let detail = unsafe { insn.as_ref() }.and_then(|insn| unsafe { insn.detail.as_ref() });
+    if !engine.detail() || detail.is_none() {
+        engine.errno = CS_ERR_DETAIL;
+        return None;
+    }
+    detail
+}
+
+/// Returns whether `insn` belongs to group `group_id`.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` a valid instruction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_insn_group(
+    handle: csh,
+    insn: *const cs_insn,
+    group_id: c_uint,
+) -> bool {
+    unsafe { instruction_detail(handle, insn) }.is_some_and(|detail| {
+        detail.groups[..detail.groups_count as usize].contains(&(group_id as u8))
+    })
+}
+
+/// Returns whether `insn` reads register `reg_id`.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` a valid instruction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_reg_read(handle: csh, insn: *const cs_insn, reg_id: c_uint) -> bool {
+    unsafe { instruction_detail(handle, insn) }.is_some_and(|detail| {
+        detail.regs_read[..detail.regs_read_count as usize].contains(&(reg_id as u16))
+    })
+}
+
+/// Returns whether `insn` writes register `reg_id`.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` a valid instruction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_reg_write(handle: csh, insn: *const cs_insn, reg_id: c_uint) -> bool {
+    unsafe { instruction_detail(handle, insn) }.is_some_and(|detail| {
+        detail.regs_write[..detail.regs_write_count as usize].contains(&(reg_id as u16))
+    })
+}
+
+/// Counts the operands of type `op_type`, or returns -1 without detail.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` a valid instruction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_op_count(handle: csh, insn: *const cs_insn, op_type: c_uint) -> c_int {
+    unsafe { instruction_detail(handle, insn) }.map_or(-1, |detail| {
+        operands(detail)
+            .iter()
+            .filter(|op| op.type_ == op_type as riscv_op_type)
+            .count() as c_int
+    })
+}
+
+/// Returns the index of the `position`-th (1-based) operand of type
+/// `op_type`, or -1 when there is none.
+///
+/// # Safety
+///
+/// `handle` must be a live handle and `insn` a valid instruction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cs_op_index(
+    handle: csh,
+    insn: *const cs_insn,
+    op_type: c_uint,
+    position: c_uint,
+) -> c_int {
+    unsafe { instruction_detail(handle, insn) }
+        .and_then(|detail| {
+            operands(detail)
+                .iter()
+                .enumerate()
+                .filter(|(_, op)| op.type_ == op_type as riscv_op_type)
+                .nth((position as usize).checked_sub(1)?)
+                .map(|(index, _)| index as c_int)
+        })
+        .unwrap_or(-1)
+}
+
+fn operands(detail: &cs_detail) -> &[cs_riscv_op] {
+    &detail.riscv.operands[..detail.riscv.op_count as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(mode: cs_mode) -> csh {
+        let mut handle: csh = 0;
+        assert_eq!(
+            unsafe { cs_open(CS_ARCH_RISCV, mode, &mut handle) },
+            CS_ERR_OK
+        );
+        handle
+    }
+
+    fn text(buffer: &[c_char]) -> &str {
+        unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn test_open_rejects_unsupported_arch_and_mode() {
+        let mut handle: csh = 0;
+        assert_eq!(
+            unsafe { cs_open(CS_ARCH_X86, CS_MODE_RISCV64, &mut handle) },
+            CS_ERR_ARCH
+        );
+        assert_eq!(
+            unsafe { cs_open(CS_ARCH_RISCV, CS_MODE_BIG_ENDIAN, &mut handle) },
+            CS_ERR_MODE
+        );
+        assert_eq!(handle, 0);
+        assert!(cs_support(CS_ARCH_RISCV));
+        assert!(!cs_support(CS_ARCH_X86));
+    }
+
+    #[test]
+    fn test_disasm_and_free() {
+        let mut handle = open(CS_MODE_RISCV64 | CS_MODE_RISCVC);
+        let code = [0x93, 0x00, 0x10, 0x00, 0x82, 0x80, 0xff, 0xff];
+        let mut insn = ptr::null_mut();
+
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0x1000, 0, &mut insn) };
+        assert_eq!(count, 2);
+        let instructions = unsafe { slice::from_raw_parts(insn, count) };
+        assert_eq!(instructions[0].address, 0x1000);
+        assert_eq!(instructions[0].size, 4);
+        assert_eq!(instructions[0].id, RiscVInstructionId::Addi.id());
+        assert_eq!(text(&instructions[0].mnemonic), "addi");
+        assert_eq!(text(&instructions[0].op_str), "ra, zero, 1");
+        assert_eq!(instructions[1].address, 0x1004);
+        assert_eq!(&instructions[1].bytes[..2], &[0x82, 0x80]);
+        assert!(instructions[1].detail.is_null());
+
+        unsafe { cs_free(insn, count) };
+        assert_eq!(unsafe { cs_close(&mut handle) }, CS_ERR_OK);
+        assert_eq!(handle, 0);
+    }
+
+    #[test]
+    fn test_compressed_requires_riscvc_mode() {
+        let handle = open(CS_MODE_RISCV64);
+        let code = [0x82, 0x80];
+        let mut insn = ptr::null_mut();
+
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0, 0, &mut insn) };
+        assert_eq!(count, 0);
+        assert!(insn.is_null());
+
+        assert_eq!(
+            unsafe { cs_option(handle, CS_OPT_SKIPDATA, CS_OPT_ON) },
+            CS_ERR_OK
+        );
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0, 0, &mut insn) };
+        assert_eq!(count, 0);
+
+        let code = [0x82, 0x80, 0x00, 0x00];
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0, 0, &mut insn) };
+        assert_eq!(count, 1);
+        let data = unsafe { &*insn };
+        assert_eq!(text(&data.mnemonic), ".byte");
+        assert_eq!(text(&data.op_str), "0x82, 0x80, 0x00, 0x00");
+        unsafe { cs_free(insn, count) };
+    }
+
+    #[test]
+    fn test_detail_operands_registers_and_groups() {
+        let handle = open(CS_MODE_RISCV64 | CS_MODE_RISCVC);
+        assert_eq!(
+            unsafe { cs_option(handle, CS_OPT_DETAIL, CS_OPT_ON) },
+            CS_ERR_OK
+        );
+        // jal ra, 8 ; ld a0, 8(sp)
+        let code = [0xef, 0x00, 0x80, 0x00, 0x03, 0x35, 0x81, 0x00];
+        let mut insn = ptr::null_mut();
+
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0, 0, &mut insn) };
+        assert_eq!(count, 2);
+        let instructions = unsafe { slice::from_raw_parts(insn, count) };
+
+        let call = &instructions[0];
+        assert!(unsafe { cs_insn_group(handle, call, RiscVInstructionGroup::Call as c_uint) });
+        assert!(!unsafe { cs_insn_group(handle, call, RiscVInstructionGroup::Ret as c_uint) });
+        assert!(unsafe { cs_reg_write(handle, call, 2) });
+
+        let load = &instructions[1];
+        let detail = unsafe { &*load.detail };
+        assert_eq!(detail.riscv.op_count, 2);
+        assert_eq!(detail.riscv.operands[0].type_, RISCV_OP_REG);
+        assert_eq!(unsafe { detail.riscv.operands[0].value.reg }, 11);
+        assert_eq!(detail.riscv.operands[1].type_, RISCV_OP_MEM);
+        let mem = unsafe { detail.riscv.operands[1].value.mem };
+        assert_eq!((mem.base, mem.disp), (3, 8));
+        assert!(unsafe { cs_reg_read(handle, load, 3) });
+        assert_eq!(
+            unsafe { cs_op_count(handle, load, RISCV_OP_MEM as c_uint) },
+            1
+        );
+        assert_eq!(
+            unsafe { cs_op_index(handle, load, RISCV_OP_MEM as c_uint, 1) },
+            1
+        );
+
+        unsafe { cs_free(insn, count) };
+    }
+
+    #[test]
+    fn test_detail_floating_point_registers() {
+        let handle = open(CS_MODE_RISCV64);
+        assert_eq!(
+            unsafe { cs_option(handle, CS_OPT_DETAIL, CS_OPT_ON) },
+            CS_ERR_OK
+        );
+        // fadd.s fa0, fa1, fa2 ; fld fa0, 8(a0) ; feq.d a0, fa0, fa1
+        let code = [
+            0x53, 0x85, 0xc5, 0x00, 0x07, 0x35, 0x85, 0x00, 0x53, 0x25, 0xb5, 0xa2,
+        ];
+        let mut insn = ptr::null_mut();
+
+        let count = unsafe { cs_disasm(handle, code.as_ptr(), code.len(), 0, 0, &mut insn) };
+        assert_eq!(count, 3);
+        let instructions = unsafe { slice::from_raw_parts(insn, count) };
+        let registers = |index: usize| -> Vec<c_uint> {
+            let detail = unsafe { &*instructions[index].detail };
+            operands(detail)
+                .iter()
+                .map(|op| match op.type_ {
+                    RISCV_OP_REG => unsafe { op.value.reg },
+                    _ => unsafe { op.value.mem.base },
+                })
+                .collect()
+        };
+
+        // RISCV_REG_F10_32 = 43, RISCV_REG_F10_64 = 75, RISCV_REG_X10 = 11.
+        assert_eq!(registers(0), [43, 44, 45]);
+        let fadd = unsafe { &*instructions[0].detail };
+        assert_eq!(&fadd.regs_read[..fadd.regs_read_count as usize], [44, 45]);
+        assert_eq!(&fadd.regs_write[..fadd.regs_write_count as usize], [43]);
+        assert!(!unsafe { cs_reg_write(handle, &instructions[0], 11) });
+        assert_eq!(registers(1), [75, 11]);
+        assert_eq!(registers(2), [11, 75, 76]);
+
+        unsafe { cs_free(insn, count) };
+    }
+
+    #[test]
+    fn test_disasm_iter_advances_cursor() {
+        let handle = open(CS_MODE_RISCV32 | CS_MODE_RISCVC);
+        let code = [0x05, 0x05, 0x33, 0x85, 0xc5, 0x00];
+        let insn = unsafe { cs_malloc(handle) };
+        let mut cursor = code.as_ptr();
+        let mut size = code.len();
+        let mut address = 0x100;
+
+        let mut mnemonics = Vec::new();
+        while unsafe { cs_disasm_iter(handle, &mut cursor, &mut size, &mut address, insn) } {
+            mnemonics.push(text(unsafe { &(*insn).mnemonic }).to_string());
+        }
+        assert_eq!(mnemonics, ["c.addi", "add"]);
+        assert_eq!(size, 0);
+        assert_eq!(address, 0x106);
+        unsafe { cs_free(insn, 1) };
+    }
+
+    #[test]
+    fn test_detail_enabled_after_malloc() {
+        let handle = open(CS_MODE_RISCV64);
+        let insn = unsafe { cs_malloc(handle) };
+        assert_eq!(
+            unsafe { cs_option(handle, CS_OPT_DETAIL, CS_OPT_ON) },
+            CS_ERR_OK
+        );
+        // addi a0, a0, 1
+        let code = [0x13, 0x05, 0x15, 0x00];
+        let mut cursor = code.as_ptr();
+        let mut size = code.len();
+        let mut address = 0;
+        assert!(unsafe { cs_disasm_iter(handle, &mut cursor, &mut size, &mut address, insn) });
+        let detail = unsafe { (*insn).detail.as_ref() }.expect("detail is allocated");
+        assert_eq!(detail.riscv.op_count, 3);
+        unsafe { cs_free(insn, 1) };
+    }
+
+    #[test]
+    fn test_names_and_errors() {
+        let handle = open(CS_MODE_RISCV64);
+        let name = |reg| unsafe { CStr::from_ptr(cs_reg_name(handle, reg)) };
+        assert_eq!(name(1), c"zero");
+        assert_eq!(name(11), c"a0");
+        assert_eq!(name(43), c"fa0");
+        assert!(unsafe { cs_reg_name(handle, 97) }.is_null());
+        assert_eq!(unsafe { CStr::from_ptr(cs_group_name(handle, 1)) }, c"jump");
+        let insn = RiscVInstructionId::CAddi.id();
+        assert_eq!(
+            unsafe { CStr::from_ptr(cs_insn_name(handle, insn)) },
+            c"c.addi"
+        );
+        assert!(unsafe { cs_insn_name(handle, 0) }.is_null());
+        assert!(unsafe { cs_insn_name(handle, RiscVInstructionId::ENDING) }.is_null());
+
+        assert_eq!(
+            unsafe { cs_option(handle, CS_OPT_UNSIGNED, CS_OPT_ON) },
+            CS_ERR_OPTION
+        );
+        assert_eq!(unsafe { cs_errno(handle) }, CS_ERR_OPTION);
+        assert_eq!(
+            unsafe { CStr::from_ptr(cs_strerror(CS_ERR_OPTION)) },
+            c"Invalid option (CS_ERR_OPTION)"
+        );
+
+        let mut major = 0;
+        let mut minor = 0;
+        assert_eq!(unsafe { cs_version(&mut major, &mut minor) }, 0x500);
+        assert_eq!((major, minor), (5, 0));
+    }
+
+    #[test]
+    fn test_register_names_match_decoder() {
+        use robustone_riscv::types::RiscVRegister;
+
+        for id in 0..96 {
+            let name = REGISTER_NAMES[id as usize + 1].to_str().unwrap();
+            assert_eq!(name, RiscVRegister::from_id(id).name());
+        }
+    }
+
+    /// Compiles a C program against `include/capstone/capstone.h` that
+    /// prints the layout of every struct field and the value of every
+    /// constant, and compares the output with the Rust definitions, so the
+    /// hand-written headers cannot drift from `types.rs`. Skipped when no C
+    /// compiler is installed.
+    #[test]
+    fn test_headers_match_rust_definitions() {
+        use std::fmt::Write as _;
+        use std::mem::{offset_of, size_of};
+        use std::process::Command;
+
+        let mut expected: Vec<(String, i64)> = Vec::new();
+        let mut check = |name: &str, value: i64| expected.push((name.to_string(), value));
+
+        macro_rules! layout {
+            ($c:literal, $rust:ty { $($c_field:literal => $field:ident),* $(,)? }) => {
+                check(concat!("sizeof(", $c, ")"), size_of::<$rust>() as i64);
+                $(check(
+                    concat!("offsetof(", $c, ", ", $c_field, ")"),
+                    offset_of!($rust, $field) as i64,
+                );)*
+            };
+        }
+        layout!("riscv_op_mem", riscv_op_mem { "base" => base, "disp" => disp });
+        layout!("cs_riscv_op", cs_riscv_op { "type" => type_, "reg" => value });
+        check(
+            "offsetof(cs_riscv_op, imm)",
+            offset_of!(cs_riscv_op, value) as i64,
+        );
+        check(
+            "offsetof(cs_riscv_op, mem)",
+            offset_of!(cs_riscv_op, value) as i64,
+        );
+        layout!("cs_riscv", cs_riscv {
+            "need_effective_addr" => need_effective_addr,
+            "op_count" => op_count,
+            "operands" => operands,
+        });
+        layout!("cs_detail", cs_detail {
+            "regs_read" => regs_read,
+            "regs_read_count" => regs_read_count,
+            "regs_write" => regs_write,
+            "regs_write_count" => regs_write_count,
+            "groups" => groups,
+            "groups_count" => groups_count,
+            "writeback" => writeback,
+            "riscv" => riscv,
+        });
+        layout!("cs_insn", cs_insn {
+            "id" => id,
+            "address" => address,
+            "size" => size,
+            "bytes" => bytes,
+            "mnemonic" => mnemonic,
+            "op_str" => op_str,
+            "detail" => detail,
+        });
+        check("sizeof(csh)", size_of::<csh>() as i64);
+
+        macro_rules! constants {
+            ($($name:ident),* $(,)?) => {
+                $(check(stringify!($name), $name as i64);)*
+            };
+        }
+        constants!(
+            CS_API_MAJOR,
+            CS_API_MINOR,
+            CS_MNEMONIC_SIZE,
+            CS_ARCH_ARM,
+            CS_ARCH_ARM64,
+            CS_ARCH_MIPS,
+            CS_ARCH_X86,
+            CS_ARCH_PPC,
+            CS_ARCH_SPARC,
+            CS_ARCH_SYSZ,
+            CS_ARCH_XCORE,
+            CS_ARCH_M68K,
+            CS_ARCH_TMS320C64X,
+            CS_ARCH_M680X,
+            CS_ARCH_EVM,
+            CS_ARCH_MOS65XX,
+            CS_ARCH_WASM,
+            CS_ARCH_BPF,
+            CS_ARCH_RISCV,
+            CS_ARCH_SH,
+            CS_ARCH_TRICORE,
+            CS_ARCH_MAX,
+            CS_ARCH_ALL,
+            CS_SUPPORT_DIET,
+            CS_SUPPORT_X86_REDUCE,
+            CS_MODE_LITTLE_ENDIAN,
+            CS_MODE_RISCV32,
+            CS_MODE_RISCV64,
+            CS_MODE_RISCVC,
+            CS_MODE_BIG_ENDIAN,
+            CS_ERR_OK,
+            CS_ERR_MEM,
+            CS_ERR_ARCH,
+            CS_ERR_HANDLE,
+            CS_ERR_CSH,
+            CS_ERR_MODE,
+            CS_ERR_OPTION,
+            CS_ERR_DETAIL,
+            CS_ERR_MEMSETUP,
+            CS_ERR_VERSION,
+            CS_ERR_DIET,
+            CS_ERR_SKIPDATA,
+            CS_ERR_X86_ATT,
+            CS_ERR_X86_INTEL,
+            CS_ERR_X86_MASM,
+            CS_OPT_INVALID,
+            CS_OPT_SYNTAX,
+            CS_OPT_DETAIL,
+            CS_OPT_MODE,
+            CS_OPT_MEM,
+            CS_OPT_SKIPDATA,
+            CS_OPT_SKIPDATA_SETUP,
+            CS_OPT_MNEMONIC,
+            CS_OPT_UNSIGNED,
+            CS_OPT_OFF,
+            CS_OPT_ON,
+            CS_OPT_SYNTAX_DEFAULT,
+            RISCV_OP_INVALID,
+            RISCV_OP_REG,
+            RISCV_OP_IMM,
+            RISCV_OP_MEM,
+        );
+        check("sizeof(((cs_insn *)0)->bytes)", CS_INSN_BYTES_SIZE as i64);
+        check("sizeof(((cs_insn *)0)->op_str)", CS_OP_STR_SIZE as i64);
+        check(
+            "sizeof(((cs_detail *)0)->regs_read) / 2",
+            CS_MAX_REGS_READ as i64,
+        );
+        check(
+            "sizeof(((cs_detail *)0)->regs_write) / 2",
+            CS_MAX_REGS_WRITE as i64,
+        );
+        check("sizeof(((cs_detail *)0)->groups)", CS_MAX_GROUPS as i64);
+        check(
+            "sizeof(((cs_riscv *)0)->operands) / sizeof(cs_riscv_op)",
+            RISCV_MAX_OPERANDS as i64,
+        );
+
+        // Register, group and instruction ids, named like the header.
+        for n in 0..32 {
+            check(&format!("RISCV_REG_X{n}"), n + 1);
+            check(&format!("RISCV_REG_F{n}_32"), n + 33);
+            check(&format!("RISCV_REG_F{n}_64"), n + 65);
+        }
+        check("RISCV_REG_ENDING", REGISTER_NAMES.len() as i64);
+        for (id, name) in GROUP_NAMES.iter().enumerate() {
+            let name = name.to_str().unwrap().to_uppercase();
+            check(&format!("RISCV_GRP_{name}"), id as i64);
+        }
+        check("RISCV_GRP_ENDING", GROUP_NAMES.len() as i64);
+        for &id in RiscVInstructionId::ALL {
+            check(&format!("RISCV_INS_{}", id.constant_name()), id.id() as i64);
+        }
+        check("RISCV_INS_ENDING", RiscVInstructionId::ENDING as i64);
+
+        let mut source =
+            String::from("#include <stdio.h>\n#include <capstone/capstone.h>\nint main(void) {\n");
+        for (name, _) in &expected {
+            // `int` matches the Rust constants, which are `c_int` even for
+            // `CS_MODE_BIG_ENDIAN`.
+            writeln!(source, "    printf(\"%d\\n\", (int)({name}));").unwrap();
+        }
+        source.push_str("    return 0;\n}\n");
+
+        let dir = std::env::temp_dir().join(format!("robustone-capi-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let program = dir.join("layout");
+        let source_path = dir.join("layout.c");
+        std::fs::write(&source_path, source).unwrap();
+        let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+        let include = concat!(env!("CARGO_MANIFEST_DIR"), "/include");
+        let compiled = match Command::new(&compiler)
+            .args(["-std=c11", "-Wall", "-Werror", "-I", include])
+            .arg(&source_path)
+            .arg("-o")
+            .arg(&program)
+            .output()
+        {
+            Ok(output) => output,
+            Err(err) => {
+                eprintln!("skipping header check: cannot run {compiler}: {err}");
+                return;
+            }
+        };
+        assert!(
+            compiled.status.success(),
+            "the headers do not compile against the Rust definitions:\n{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        );
+        let output = Command::new(&program).output().unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let actual = String::from_utf8(output.stdout).unwrap();
+        let mismatches: Vec<String> = expected
+            .iter()
+            .zip(actual.lines())
+            .filter(|((_, rust), c)| c.parse::<i64>() != Ok(*rust))
+            .map(|((name, rust), c)| format!("{name}: header {c}, Rust {rust}"))
+            .collect();
+        assert_eq!(actual.lines().count(), expected.len());
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+}
@@ -0,0 +1,209 @@
+//! C-compatible type definitions mirroring Capstone 5's public headers.
+//!
+//! Names intentionally follow Capstone (`cs_insn`, `CS_ERR_OK`, ...) so the
+//! declarations in `include/capstone/*.h` map one-to-one onto this module.
+//! Every struct is `#[repr(C)]` and laid out exactly like its C counterpart.
+
+#![allow(non_camel_case_types)]
+
+use std::os::raw::{c_char, c_int, c_uint};
+
+/// Opaque engine handle returned by `cs_open`.
+pub type csh = usize;
+
+/// Architecture selector (`cs_arch`).
+pub type cs_arch = c_int;
+/// Mode bitmask (`cs_mode`).
+pub type cs_mode = c_int;
+/// Error code (`cs_err`).
+pub type cs_err = c_int;
+/// Runtime option selector (`cs_opt_type`).
+pub type cs_opt_type = c_int;
+/// RISC-V operand type (`riscv_op_type`).
+pub type riscv_op_type = c_int;
+
+pub const CS_API_MAJOR: c_int = 5;
+pub const CS_API_MINOR: c_int = 0;
+
+pub const CS_ARCH_ARM: cs_arch = 0;
+pub const CS_ARCH_ARM64: cs_arch = 1;
+pub const CS_ARCH_MIPS: cs_arch = 2;
+pub const CS_ARCH_X86: cs_arch = 3;
+pub const CS_ARCH_PPC: cs_arch = 4;
+pub const CS_ARCH_SPARC: cs_arch = 5;
+pub const CS_ARCH_SYSZ: cs_arch = 6;
+pub const CS_ARCH_XCORE: cs_arch = 7;
+pub const CS_ARCH_M68K: cs_arch = 8;
+pub const CS_ARCH_TMS320C64X: cs_arch = 9;
+pub const CS_ARCH_M680X: cs_arch = 10;
+pub const CS_ARCH_EVM: cs_arch = 11;
+pub const CS_ARCH_MOS65XX: cs_arch = 12;
+pub const CS_ARCH_WASM: cs_arch = 13;
+pub const CS_ARCH_BPF: cs_arch = 14;
+pub const CS_ARCH_RISCV: cs_arch = 15;
+pub const CS_ARCH_SH: cs_arch = 16;
+pub const CS_ARCH_TRICORE: cs_arch = 17;
+pub const CS_ARCH_MAX: cs_arch = 18;
+pub const CS_ARCH_ALL: cs_arch = 0xFFFF;
+
+/// `cs_support` query for the diet build.
+pub const CS_SUPPORT_DIET: c_int = CS_ARCH_ALL + 1;
+/// `cs_support` query for the X86 reduce build.
+pub const CS_SUPPORT_X86_REDUCE: c_int = CS_ARCH_ALL + 2;
+
+pub const CS_MODE_LITTLE_ENDIAN: cs_mode = 0;
+pub const CS_MODE_RISCV32: cs_mode = 1 << 0;
+pub const CS_MODE_RISCV64: cs_mode = 1 << 1;
+pub const CS_MODE_RISCVC: cs_mode = 1 << 2;
+pub const CS_MODE_BIG_ENDIAN: cs_mode = 1 << 31;
+
+pub const CS_ERR_OK: cs_err = 0;
+pub const CS_ERR_MEM: cs_err = 1;
+pub const CS_ERR_ARCH: cs_err = 2;
+pub const CS_ERR_HANDLE: cs_err = 3;
+pub const CS_ERR_CSH: cs_err = 4;
+pub const CS_ERR_MODE: cs_err = 5;
+pub const CS_ERR_OPTION: cs_err = 6;
+pub const CS_ERR_DETAIL: cs_err = 7;
+pub const CS_ERR_MEMSETUP: cs_err = 8;
+pub const CS_ERR_VERSION: cs_err = 9;
+pub const CS_ERR_DIET: cs_err = 10;
+pub const CS_ERR_SKIPDATA: cs_err = 11;
+pub const CS_ERR_X86_ATT: cs_err = 12;
+pub const CS_ERR_X86_INTEL: cs_err = 13;
+pub const CS_ERR_X86_MASM: cs_err = 14;
+
+pub const CS_OPT_INVALID: cs_opt_type = 0;
+pub const CS_OPT_SYNTAX: cs_opt_type = 1;
+pub const CS_OPT_DETAIL: cs_opt_type = 2;
+pub const CS_OPT_MODE: cs_opt_type = 3;
+pub const CS_OPT_MEM: cs_opt_type = 4;
+pub const CS_OPT_SKIPDATA: cs_opt_type = 5;
+pub const CS_OPT_SKIPDATA_SETUP: cs_opt_type = 6;
+pub const CS_OPT_MNEMONIC: cs_opt_type = 7;
+pub const CS_OPT_UNSIGNED: cs_opt_type = 8;
+
+pub const CS_OPT_OFF: usize = 0;
+pub const CS_OPT_ON: usize = 3;
+pub const CS_OPT_SYNTAX_DEFAULT: usize = 0;
+
+pub const CS_MNEMONIC_SIZE: usize = 32;
+pub const CS_OP_STR_SIZE: usize = 160;
+pub const CS_INSN_BYTES_SIZE: usize = 24;
+pub const CS_MAX_REGS_READ: usize = 20;
+pub const CS_MAX_REGS_WRITE: usize = 20;
+pub const CS_MAX_GROUPS: usize = 8;
+pub const RISCV_MAX_OPERANDS: usize = 8;
+
+pub const RISCV_OP_INVALID: riscv_op_type = 0;
+pub const RISCV_OP_REG: riscv_op_type = 1;
+pub const RISCV_OP_IMM: riscv_op_type = 2;
+pub const RISCV_OP_MEM: riscv_op_type = 3;
+
+/// Memory operand payload (`riscv_op_mem`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct riscv_op_mem {
+    pub base: c_uint,
+    pub disp: i64,
+}
+
+/// Anonymous operand union inside `cs_riscv_op`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union cs_riscv_op_value {
+    pub reg: c_uint,
+    pub imm: i64,
+    pub mem: riscv_op_mem,
+}
+
+/// Single RISC-V operand (`cs_riscv_op`).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cs_riscv_op {
+    pub type_: riscv_op_type,
+    pub value: cs_riscv_op_value,
+}
+
+/// RISC-V specific instruction detail (`cs_riscv`).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cs_riscv {
+    pub need_effective_addr: bool,
+    pub op_count: u8,
+    pub operands: [cs_riscv_op; RISCV_MAX_OPERANDS],
+}
+
+/// Architecture-independent instruction detail (`cs_detail`).
+///
+/// Capstone stores the architecture payload in an anonymous union; RISC-V is
+/// the only member here, which yields the same field offsets.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cs_detail {
+    pub regs_read: [u16; CS_MAX_REGS_READ],
+    pub regs_read_count: u8,
+    pub regs_write: [u16; CS_MAX_REGS_WRITE],
+    pub regs_write_count: u8,
+    pub groups: [u8; CS_MAX_GROUPS],
+    pub groups_count: u8,
+    pub writeback: bool,
+    pub riscv: cs_riscv,
+}
+
+/// Decoded instruction (`cs_insn`).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct cs_insn {
+    pub id: c_uint,
+    pub address: u64,
+    pub size: u16,
+    pub bytes: [u8; CS_INSN_BYTES_SIZE],
+    pub mnemonic: [c_char; CS_MNEMONIC_SIZE],
+    pub op_str: [c_char; CS_OP_STR_SIZE],
+    pub detail: *mut cs_detail,
+}
+
+impl cs_detail {
+    /// Returns an all-zero detail record.
+    pub fn zeroed() -> Self {
+        // SAFETY: every field is an integer, bool, or union of integers, for
+        // which the all-zero bit pattern is valid.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl cs_insn {
+    /// Returns an all-zero instruction with no detail attached.
+    pub fn zeroed() -> Self {
+        // SAFETY: integers and a null raw pointer are valid when zeroed.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{offset_of, size_of};
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_layout_matches_capstone_5() {
+        assert_eq!(offset_of!(cs_insn, address), 8);
+        assert_eq!(offset_of!(cs_insn, size), 16);
+        assert_eq!(offset_of!(cs_insn, bytes), 18);
+        assert_eq!(offset_of!(cs_insn, mnemonic), 42);
+        assert_eq!(offset_of!(cs_insn, op_str), 74);
+        assert_eq!(offset_of!(cs_insn, detail), 240);
+        assert_eq!(size_of::<cs_insn>(), 248);
+
+        assert_eq!(offset_of!(cs_detail, regs_write), 42);
+        assert_eq!(offset_of!(cs_detail, groups), 83);
+        assert_eq!(offset_of!(cs_detail, writeback), 92);
+        assert_eq!(offset_of!(cs_detail, riscv), 96);
+
+        assert_eq!(size_of::<cs_riscv_op>(), 24);
+        assert_eq!(offset_of!(cs_riscv, operands), 8);
+        assert_eq!(size_of::<cs_riscv>(), 200);
+    }
+}
@@ -90,6 +90,15 @@ impl ArchitectureSpec {
                 // Common syntax toggles
                 "noregname" => options.push("noregname".to_string()),
                 "regalias" => options.push("regalias".to_string()),
+
+                // Pseudo-instruction selection
+                "aliases" => options.push("aliases".to_string()),
+                "noalias" => options.push("noalias".to_string()),
+
+                // Output syntax profiles
+                "capstone" => options.push("capstone".to_string()),
+                "llvm" => options.push("llvm".to_string()),
+                "gnu" => options.push("gnu".to_string()),
                 "moto" => options.push("moto".to_string()),
                 "percentage" => options.push("percentage".to_string()),
                 "nodollar" => options.push("nodollar".to_string()),
@@ -118,6 +127,9 @@ impl ArchitectureSpec {
                 // SPARC options
                 "v9" => options.push("v9".to_string()),
 
+                // Decoder extensions, given as an ISA string like `rv32imac`
+                isa if isa.len() > 4 && isa.starts_with("isa=") => options.push(isa.to_string()),
+
                 // Endianness modifiers
                 "little" | "le" => mode |= 0x0, // CS_MODE_LITTLE_ENDIAN
                 "big" | "be" => mode |= 0x100,  // CS_MODE_BIG_ENDIAN
@@ -126,6 +138,13 @@ impl ArchitectureSpec {
             }
         }
 
+        // AArch64 picks its byte order by name, so `+be` selects the
+        // big-endian target like `aarch64be`.
+        let arch = match arch {
+            Architecture::Aarch64 if mode & 0x100 != 0 => Architecture::Aarch64BE,
+            arch => arch,
+        };
+
         Ok(ArchitectureSpec {
             arch,
             mode,
@@ -283,7 +302,13 @@ impl Architecture {
     }
 
     pub fn is_implemented(&self) -> bool {
-        matches!(self, Architecture::Riscv32 | Architecture::Riscv64)
+        matches!(
+            self,
+            Architecture::Riscv32
+                | Architecture::Riscv64
+                | Architecture::Aarch64
+                | Architecture::Aarch64BE
+        )
     }
 
     pub fn implementation_status(&self) -> &'static str {
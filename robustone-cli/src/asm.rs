@@ -0,0 +1,461 @@
+//! Assembler source output (`--format asm`).
+//!
+//! The output is meant to be fed back to GNU as or llvm-mc and reassemble
+//! to exactly the input bytes:
+//!
+//! - `.attribute arch` names the extensions the decoder used, and
+//!   `.option norelax` stops the assembler from rewriting branches.
+//! - Compressed instructions are printed as the 32-bit instruction they
+//!   expand to inside `.option rvc`, where the assembler compresses them
+//!   again; full-size instructions are printed inside `.option norvc`.
+//! - Branch and jump targets become `.L<address>` labels. Targets outside
+//!   the input, or inside an instruction, are defined with `.set` relative
+//!   to the first instruction.
+//! - Encodings the decoder does not know, vendor instructions and the
+//!   encodings an assembler would not reproduce are emitted with `.insn`:
+//!   compressed HINTs, floating-point operations with a rounding mode
+//!   other than the one the assembler picks, and SYSTEM encodings other
+//!   than `ecall` and `ebreak`. Bytes too short for an instruction are
+//!   emitted with `.byte`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::config::DisasmConfig;
+use crate::disasm::{self, DisassemblyEngine};
+use robustone_core::types::options::{AliasMode, HandlerOption, SyntaxStyle};
+use robustone_core::{DisasmError, Instruction, Symbolizer};
+use robustone_riscv::compressed;
+use robustone_riscv::decoder::Xlen;
+use robustone_riscv::targets;
+
+/// How a piece of the input is emitted.
+enum Unit {
+    /// A decoded instruction, printed as assembly.
+    Instruction,
+    /// A compressed instruction spelled with its `c.` mnemonic.
+    Compressed(String),
+    /// An encoding emitted with `.insn`, with an optional comment.
+    Insn(Option<String>),
+    /// Bytes emitted with `.byte`.
+    Data,
+}
+
+/// A piece of the input at `offset`.
+struct Piece {
+    offset: usize,
+    size: usize,
+    unit: Unit,
+}
+
+/// Branch target labels, printed bare so the assembler can resolve them.
+struct Labels(BTreeMap<u64, String>);
+
+impl Symbolizer for Labels {
+    fn symbolize(&self, address: u64) -> Option<(&str, u64)> {
+        self.0.get(&address).map(|label| (label.as_str(), 0))
+    }
+
+    fn write_symbol(&self, address: u64, out: &mut String) -> bool {
+        let Some(label) = self.0.get(&address) else {
+            return false;
+        };
+        out.push_str(label);
+        true
+    }
+}
+
+/// Formats the input bytes of `config` as an assembly file.
+pub fn format_asm(config: &DisasmConfig) -> Result<String, DisasmError> {
+    let arch = config.arch_name();
+    let (xlen, extensions) = disasm::config_target(config)?;
+    // Both assemblers read LLVM's operand syntax, and only the aliased
+    // profile prints compressed instructions expanded.
+    let options = config
+        .handler_options()
+        .with(HandlerOption::Syntax(SyntaxStyle::Llvm))
+        .with(HandlerOption::Aliases(AliasMode::All))
+        .with(HandlerOption::UnsignedImmediates(false))
+        .with(HandlerOption::Detail(false));
+    let mut engine = DisassemblyEngine::for_config(config, options)?;
+
+    let bytes = &config.hex_bytes;
+    let start = config.start_address;
+    let end = start.wrapping_add(bytes.len() as u64);
+    let address = |offset: usize| start.wrapping_add(offset as u64);
+
+    let mut pieces = Vec::new();
+    let mut labels = BTreeMap::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let rest = &bytes[offset..];
+        let piece = match engine.disassemble_single(rest, arch, address(offset)) {
+            Ok((instruction, size)) if size > 0 && !is_unknown(&instruction) => {
+                let word = instruction_word(&rest[..size], xlen);
+                if let Some(target) =
+                    word.and_then(|word| targets::branch_target(word, address(offset), xlen))
+                {
+                    labels.insert(target, format!(".L{target:x}"));
+                }
+                let half = (size == 2).then(|| u16::from_le_bytes([rest[0], rest[1]]));
+                let hint =
+                    half.is_some_and(|half| !word.is_some_and(|word| is_canonical(half, word)));
+                let unit = if hint || is_vendor(&instruction.mnemonic) {
+                    Unit::Insn(Some(text(&instruction)))
+                } else if size == 4 && word.is_some_and(is_system) {
+                    // The decoder prints these as CSR accesses.
+                    Unit::Insn(None)
+                } else if size == 4 && word.is_some_and(has_explicit_rm) {
+                    Unit::Insn(Some(text(&instruction)))
+                } else if let Some(explicit) = half
+                    .zip(word)
+                    .and_then(|(half, word)| explicit_compressed(half, word))
+                {
+                    Unit::Compressed(explicit)
+                } else {
+                    Unit::Instruction
+                };
+                Piece { offset, size, unit }
+            }
+            _ => raw_piece(offset, rest),
+        };
+        offset += piece.size;
+        pieces.push(piece);
+    }
+
+    let boundaries: Vec<u64> = pieces.iter().map(|piece| address(piece.offset)).collect();
+    let strays: Vec<u64> = labels
+        .keys()
+        .copied()
+        .filter(|target| *target != end && !boundaries.contains(target))
+        .collect();
+    if !strays.is_empty() {
+        labels.insert(start, format!(".L{start:x}"));
+    }
+    engine.set_symbolizer(arch, Box::new(Labels(labels.clone())))?;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "\t.attribute\tarch, \"{}\"", extensions.to_isa(xlen));
+    let _ = writeln!(out, "\t.option\tnorelax");
+    let _ = writeln!(out, "\t.text");
+    let mut rvc = None;
+    for piece in &pieces {
+        let address = address(piece.offset);
+        if let Some(label) = labels.get(&address) {
+            let _ = writeln!(out, "{label}:");
+        }
+        let encoding = &bytes[piece.offset..piece.offset + piece.size];
+        if !matches!(piece.unit, Unit::Data) {
+            let compressed = piece.size == 2;
+            if rvc != Some(compressed) {
+                let option = if compressed { "rvc" } else { "norvc" };
+                let _ = writeln!(out, "\t.option\t{option}");
+                rvc = Some(compressed);
+            }
+        }
+        match &piece.unit {
+            Unit::Instruction => {
+                let (instruction, _) =
+                    engine.disassemble_single(&bytes[piece.offset..], arch, address)?;
+                if instruction.operands.is_empty() {
+                    let _ = writeln!(out, "\t{}", instruction.mnemonic);
+                } else {
+                    let _ = writeln!(out, "\t{}\t{}", instruction.mnemonic, instruction.operands);
+                }
+            }
+            Unit::Compressed(text) => {
+                let _ = writeln!(out, "\t{text}");
+            }
+            Unit::Insn(comment) => {
+                let value = encoding
+                    .iter()
+                    .rev()
+                    .fold(0u32, |value, b| (value << 8) | u32::from(*b));
+                let width = piece.size * 2;
+                let _ = write!(out, "\t.insn\t{}, 0x{value:0width$x}", piece.size);
+                match comment {
+                    Some(comment) => {
+                        let _ = writeln!(out, "\t# {comment}");
+                    }
+                    None => out.push('\n'),
+                }
+            }
+            Unit::Data => {
+                let data: Vec<String> = encoding.iter().map(|b| format!("0x{b:02x}")).collect();
+                let _ = writeln!(out, "\t.byte\t{}", data.join(", "));
+            }
+        }
+    }
+    if let Some(label) = labels.get(&end) {
+        let _ = writeln!(out, "{label}:");
+    }
+    for target in strays {
+        let distance = i128::from(target) - i128::from(start);
+        let sign = if distance < 0 { '-' } else { '+' };
+        let _ = writeln!(
+            out,
+            "\t.set\t{}, {} {sign} 0x{:x}",
+            labels[&target],
+            labels[&start],
+            distance.unsigned_abs()
+        );
+    }
+    Ok(out)
+}
+
+/// Emits undecodable bytes: a whole instruction of the length its low bits
+/// claim becomes `.insn`, anything else `.byte`.
+fn raw_piece(offset: usize, bytes: &[u8]) -> Piece {
+    let size = match bytes[0] & 0b1_1111 {
+        low if low & 0b11 != 0b11 => 2,
+        0b1_1111 => 0,
+        _ => 4,
+    };
+    if size != 0 && bytes.len() >= size {
+        return Piece {
+            offset,
+            size,
+            unit: Unit::Insn(None),
+        };
+    }
+    let claimed = if bytes[0] & 0b11 == 0b11 { 4 } else { 2 };
+    Piece {
+        offset,
+        size: claimed.min(bytes.len()),
+        unit: Unit::Data,
+    }
+}
+
+/// Returns the 32-bit form of `encoding`, expanding compressed encodings.
+pub(crate) fn instruction_word(encoding: &[u8], xlen: Xlen) -> Option<u32> {
+    match *encoding {
+        [b0, b1] => compressed::expand(u16::from_le_bytes([b0, b1]), xlen),
+        [b0, b1, b2, b3] => Some(u32::from_le_bytes([b0, b1, b2, b3])),
+        _ => None,
+    }
+}
+
+/// Returns `false` for compressed HINTs, the encodings that expand to an
+/// instruction writing `zero` (other than `c.nop`) or to `addi rd, rd, 0`,
+/// which assemblers do not compress back to the same encoding.
+fn is_canonical(half: u16, word: u32) -> bool {
+    const C_NOP: u16 = 0x0001;
+    let rd = (word >> 7) & 0x1f;
+    let writes_zero = rd == 0 && matches!(word & 0x7f, 0x13 | 0x1b | 0x33 | 0x37 | 0x3b);
+    let c_addi = half & 0xe003 == 0x0001;
+    half == C_NOP || !(writes_zero || (c_addi && (word >> 20) == 0))
+}
+
+/// Spells out compressed encodings whose expansion has more than one
+/// compressed form: `addi sp, sp, imm` is both `c.addi` and `c.addi16sp`.
+fn explicit_compressed(half: u16, word: u32) -> Option<String> {
+    if word & 0xf_ffff != 0x1_0113 {
+        return None;
+    }
+    let name = if half & 0xe003 == 0x6001 {
+        "c.addi16sp"
+    } else {
+        "c.addi"
+    };
+    Some(format!("{name}\tsp, {}", (word as i32) >> 20))
+}
+
+/// Returns `true` for SYSTEM encodings with `funct3` 0 other than `ecall`
+/// and `ebreak`: `mret`, `wfi`, `sfence.vma` and the like.
+fn is_system(word: u32) -> bool {
+    const ECALL: u32 = 0x0000_0073;
+    const EBREAK: u32 = 0x0010_0073;
+    word & 0x707f == 0x73 && word != ECALL && word != EBREAK
+}
+
+/// Returns `true` for floating-point operations whose rounding mode is not
+/// the one an assembler fills in when the operand is left out, which the
+/// printer does not show: `fcvt.w.s a0, fa0, rne` prints as
+/// `fcvt.w.s a0, fa0`.
+fn has_explicit_rm(word: u32) -> bool {
+    const DYN: u32 = 0b111;
+    let rm = (word >> 12) & 0b111;
+    let funct7 = word >> 25;
+    match word & 0x7f {
+        // fmadd, fmsub, fnmsub, fnmadd
+        0x43 | 0x47 | 0x4b | 0x4f => rm != DYN,
+        0x53 => {
+            let rs2 = (word >> 20) & 0x1f;
+            // fcvt.d.s, fcvt.d.w and fcvt.d.wu are exact and assemble
+            // with rne.
+            let exact = funct7 == 0b010_0001 || (funct7 == 0b110_1001 && rs2 <= 1);
+            match funct7 >> 2 {
+                // fadd, fsub, fmul, fdiv, fsqrt, and the conversions
+                0b00000 | 0b00001 | 0b00010 | 0b00011 | 0b01011 | 0b01000 | 0b11000 | 0b11010 => {
+                    rm != if exact { 0 } else { DYN }
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` for mnemonics of vendor extensions, which assemblers only
+/// accept with the vendor extension enabled, if they know it at all.
+fn is_vendor(mnemonic: &str) -> bool {
+    mnemonic.starts_with("th.")
+}
+
+/// Returns `true` when the decoder did not recognise the encoding.
+pub(crate) fn is_unknown(instruction: &Instruction) -> bool {
+    instruction.is_unknown() || instruction.mnemonic == "c.unknown"
+}
+
+/// Returns `mnemonic operands`.
+fn text(instruction: &Instruction) -> String {
+    if instruction.operands.is_empty() {
+        instruction.mnemonic.clone()
+    } else {
+        format!("{} {}", instruction.mnemonic, instruction.operands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::ValidatedConfig;
+    use crate::config::OutputFormat;
+
+    use std::process::Command;
+
+    fn config(arch: &str, code: &str) -> DisasmConfig {
+        let config = ValidatedConfig {
+            arch_mode: Some(arch.to_string()),
+            hex_code: Some(hex::decode(code).unwrap()),
+            address: Some(0x8000_0000),
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Asm,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+        DisasmConfig::from_validated_config(config).unwrap()
+    }
+
+    /// Assembles `source` with llvm-mc and returns its `.text`, or `None`
+    /// when llvm-mc is not installed. Releases before LLVM 16 know neither
+    /// `.insn <length>, <value>` nor `zicsr` in the arch attribute, so a
+    /// source they reject is retried with `.half`/`.word` and `-mattr`.
+    fn llvm_mc(source: &str) -> Option<Vec<u8>> {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("robustone-asm-{}.s", std::process::id()));
+        let object = input.with_extension("o");
+        let run = |source: &str| {
+            std::fs::write(&input, source).unwrap();
+            Command::new("llvm-mc")
+                .args(["-triple=riscv64", "-mattr=+m,+a,+f,+d,+c", "-filetype=obj"])
+                .arg(&input)
+                .arg("-o")
+                .arg(&object)
+                .output()
+        };
+        let output = run(source).ok()?;
+        if !output.status.success() {
+            let legacy: String = source
+                .lines()
+                .filter(|line| !line.contains(".attribute"))
+                .map(|line| {
+                    let line = line.replace(".insn\t2,", ".half\t");
+                    line.replace(".insn\t4,", ".word\t") + "\n"
+                })
+                .collect();
+            let output = run(&legacy).unwrap();
+            assert!(
+                output.status.success(),
+                "{}\n{legacy}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        let image = crate::loader::elf::load(&std::fs::read(&object).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&object);
+        let text = image
+            .regions
+            .into_iter()
+            .find(|region| region.name == ".text");
+        Some(text.unwrap().bytes)
+    }
+
+    #[test]
+    fn test_format_asm() {
+        let config = config(
+            "riscv32",
+            "012081a06305b5006f00a003ffffffff7f0000001540010541014161010009c11300",
+        );
+        assert_eq!(
+            format_asm(&config).unwrap(),
+            "\t.attribute\tarch, \"rv32imafdc_zicsr_zifencei\"\n\
+             \t.option\tnorelax\n\
+             \t.text\n\
+             .L80000000:\n\
+             \t.option\trvc\n\
+             \tjal\t.L80000000\n\
+             \tj\t.L80000042\n\
+             \t.option\tnorvc\n\
+             \tbeq\ta0, a1, .L8000000e\n\
+             \tj\t.L80000042\n\
+             \t.byte\t0xff, 0xff, 0xff, 0xff\n\
+             \t.byte\t0x7f, 0x00, 0x00, 0x00\n\
+             \t.option\trvc\n\
+             \t.insn\t2, 0x4015\t# c.li zero, 5\n\
+             \t.insn\t2, 0x0501\t# mv a0, a0\n\
+             \tc.addi\tsp, 16\n\
+             \tc.addi16sp\tsp, 16\n\
+             \tnop\n\
+             \tbeqz\ta0, .L80000020\n\
+             .L80000020:\n\
+             \t.byte\t0x13, 0x00\n\
+             \t.set\t.L8000000e, .L80000000 + 0xe\n\
+             \t.set\t.L80000042, .L80000000 + 0x42\n"
+        );
+    }
+
+    #[test]
+    fn test_format_asm_lossy() {
+        // fcvt.w.s a0, fa0, rne, mret, wfi and sfence.vma a0, a1; fcvt.d.s,
+        // which assembles with rne, and ecall stay instructions.
+        let config = config(
+            "riscv64",
+            "530505c073002030730050107300b5125385054273000000",
+        );
+        assert_eq!(
+            format_asm(&config).unwrap(),
+            "\t.attribute\tarch, \"rv64imafdc_zicsr_zifencei\"\n\
+             \t.option\tnorelax\n\
+             \t.text\n\
+             \t.option\tnorvc\n\
+             \t.insn\t4, 0xc0050553\t# fcvt.w.s a0, fa0\n\
+             \t.insn\t4, 0x30200073\n\
+             \t.insn\t4, 0x10500073\n\
+             \t.insn\t4, 0x12b50073\n\
+             \tfcvt.d.s\tfa0, fa1\n\
+             \tecall\n"
+        );
+    }
+
+    #[test]
+    fn test_format_asm_round_trip() {
+        let code = "011106ec226505054c4153f5c5025395c502530505c05385054243b5c5682fa50514\
+                    7325003073000000029073002030730050107300b5126304b500ef0040008280";
+        let source = format_asm(&config("riscv64", code)).unwrap();
+        let Some(text) = llvm_mc(&source) else {
+            return;
+        };
+        assert_eq!(hex::encode(text), code, "{source}");
+    }
+}
@@ -0,0 +1,379 @@
+//! Batch mode (`robustone batch MANIFEST [ARCH]`).
+//!
+//! The manifest has one input per line, `[arch] hex [address]`, with `#`
+//! starting a comment, so `test/architectures/*/test_cases.txt` can be used
+//! as is by giving ARCH for lines without one. Every line is disassembled
+//! like a separate invocation, but engines are built once per architecture
+//! and shared by the lines of a worker; `--jobs` splits the lines across
+//! worker threads.
+//!
+//! Results are printed in manifest order, one per input line:
+//!
+//! - with `--format text`, the text listing of the line, or
+//!   `Error: line N: message`;
+//! - with `--format json` or `jsonl`, one object per line holding `line`,
+//!   `arch`, `address` and the `instructions` and `errors` of
+//!   [`crate::json`], or `line` and `error` when the line failed.
+
+use std::collections::HashMap;
+use std::thread;
+
+use serde::Serialize;
+
+use crate::arch::ArchitectureSpec;
+use crate::command::DisplayOptions;
+use crate::config::{DisasmConfig, OutputConfig, OutputFormat};
+use crate::disasm::{DisassemblyEngine, DisassemblyFormatter, DisassemblyResult};
+use crate::error::{CliError, Result};
+use crate::input;
+use crate::json::InstructionRecord;
+use crate::utils::parse_address;
+use robustone_core::SymbolMap;
+
+/// Settings shared by every line of a manifest.
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Architecture of lines that do not name one.
+    pub default_arch: Option<String>,
+    pub display_options: DisplayOptions,
+    pub skip_data: bool,
+    pub symbols: Option<SymbolMap>,
+    /// Number of worker threads.
+    pub jobs: usize,
+}
+
+/// One input of the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// 1-based line number in the manifest.
+    pub line: usize,
+    pub arch: Option<String>,
+    pub hex: String,
+    pub address: Option<String>,
+}
+
+/// Splits a manifest into its inputs, skipping blank and comment lines.
+pub fn parse_manifest(text: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let content = line.split('#').next().unwrap_or_default();
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+        let names_arch = |token: &str| ArchitectureSpec::parse(token).is_ok();
+        let (arch, hex, address) = match tokens.as_slice() {
+            [] => continue,
+            [hex] => (None, *hex, None),
+            [arch, hex] if names_arch(arch) => (Some(*arch), *hex, None),
+            [hex, address] => (None, *hex, Some(*address)),
+            [arch, hex, address, ..] => (Some(*arch), *hex, Some(*address)),
+        };
+        entries.push(Entry {
+            line: index + 1,
+            arch: arch.map(str::to_string),
+            hex: hex.to_string(),
+            address: address.map(str::to_string),
+        });
+    }
+    entries
+}
+
+/// Disassembles every input of `manifest` and returns the output.
+pub fn run_batch(manifest: &str, options: &BatchOptions) -> BatchOutput {
+    let entries = parse_manifest(manifest);
+    let jobs = options.jobs.clamp(1, entries.len().max(1));
+    let chunk = entries.len().div_ceil(jobs).max(1);
+
+    let results: Vec<Result<String>> = if jobs == 1 {
+        Worker::new(options).process(&entries)
+    } else {
+        thread::scope(|scope| {
+            let workers: Vec<_> = entries
+                .chunks(chunk)
+                .map(|entries| scope.spawn(move || Worker::new(options).process(entries)))
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("batch worker panicked"))
+                .collect()
+        })
+    };
+
+    let mut output = BatchOutput::default();
+    for (entry, result) in entries.iter().zip(results) {
+        match result {
+            Ok(text) => output.text.push_str(&text),
+            Err(error) => {
+                output.failures += 1;
+                output
+                    .text
+                    .push_str(&format_failure(entry, &error, options));
+            }
+        }
+    }
+    output.inputs = entries.len();
+    output
+}
+
+/// Output of [`run_batch`].
+#[derive(Debug, Default)]
+pub struct BatchOutput {
+    pub text: String,
+    /// Number of inputs in the manifest.
+    pub inputs: usize,
+    /// Number of inputs that could not be disassembled.
+    pub failures: usize,
+}
+
+/// JSON result of a line that was disassembled.
+#[derive(Serialize)]
+struct Record<'a> {
+    line: usize,
+    arch: &'a str,
+    address: u64,
+    instructions: Vec<InstructionRecord<'a>>,
+    errors: &'a [String],
+}
+
+/// JSON result of a line that failed.
+#[derive(Serialize)]
+struct Failure<'a> {
+    line: usize,
+    error: &'a str,
+}
+
+/// Disassembles entries with one engine per architecture.
+struct Worker<'a> {
+    options: &'a BatchOptions,
+    engines: HashMap<String, DisassemblyEngine>,
+}
+
+impl<'a> Worker<'a> {
+    fn new(options: &'a BatchOptions) -> Self {
+        Self {
+            options,
+            engines: HashMap::new(),
+        }
+    }
+
+    fn process(&mut self, entries: &[Entry]) -> Vec<Result<String>> {
+        entries
+            .iter()
+            .map(|entry| self.disassemble(entry))
+            .collect()
+    }
+
+    fn disassemble(&mut self, entry: &Entry) -> Result<String> {
+        let options = self.options;
+        let arch = entry
+            .arch
+            .as_deref()
+            .or(options.default_arch.as_deref())
+            .ok_or_else(|| {
+                CliError::validation("arch_mode", "Architecture specification is required")
+            })?;
+        let config = DisasmConfig {
+            arch_spec: ArchitectureSpec::parse(arch)
+                .map_err(|e| CliError::parse("architecture", e.to_string()))?,
+            hex_bytes: input::parse_hex_text(&entry.hex)?,
+            start_address: entry
+                .address
+                .as_deref()
+                .map(parse_address)
+                .transpose()?
+                .unwrap_or(0),
+            display_options: options.display_options.clone(),
+            skip_data: options.skip_data,
+            // Lines are spread across the workers instead.
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            // The engine carries the symbolizer.
+            symbols: None,
+        };
+        config.validate_for_disassembly()?;
+
+        let engine = match self.engines.entry(arch.to_string()) {
+            std::collections::hash_map::Entry::Occupied(engine) => engine.into_mut(),
+            std::collections::hash_map::Entry::Vacant(slot) => {
+                let mut engine = DisassemblyEngine::for_config(&config, config.handler_options())
+                    .map_err(|e| CliError::Disassembly(e.to_string()))?;
+                if let Some(symbols) = &options.symbols {
+                    engine
+                        .set_symbolizer(config.arch_name(), Box::new(symbols.clone()))
+                        .map_err(|e| CliError::Disassembly(e.to_string()))?;
+                }
+                slot.insert(engine)
+            }
+        };
+        let result = engine
+            .disassemble(&config)
+            .map_err(|e| CliError::Disassembly(e.to_string()))?;
+        Ok(format_result(entry, &result, options))
+    }
+}
+
+/// Formats the result of one line.
+fn format_result(entry: &Entry, result: &DisassemblyResult, options: &BatchOptions) -> String {
+    match options.display_options.format {
+        OutputFormat::Json | OutputFormat::JsonLines => {
+            let record = Record {
+                line: entry.line,
+                arch: &result.architecture,
+                address: result.start_address,
+                instructions: result
+                    .instructions
+                    .iter()
+                    .map(InstructionRecord::new)
+                    .collect(),
+                errors: &result.errors,
+            };
+            let mut out = serde_json::to_string(&record).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Text | OutputFormat::Asm => {
+            let output_config = OutputConfig::from_display_options(&options.display_options);
+            DisassemblyFormatter::new(output_config).format(result)
+        }
+    }
+}
+
+/// Formats the failure of one line.
+fn format_failure(entry: &Entry, error: &CliError, options: &BatchOptions) -> String {
+    match options.display_options.format {
+        OutputFormat::Json | OutputFormat::JsonLines => {
+            let error = error.to_string();
+            let failure = Failure {
+                line: entry.line,
+                error: &error,
+            };
+            let mut out = serde_json::to_string(&failure).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        OutputFormat::Text | OutputFormat::Asm => {
+            format!("Error: line {}: {error}\n", entry.line)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(format: OutputFormat, jobs: usize) -> BatchOptions {
+        BatchOptions {
+            default_arch: Some("riscv32".to_string()),
+            display_options: DisplayOptions {
+                detailed: false,
+                alias_regs: false,
+                real_detail: false,
+                unsigned_immediate: false,
+                format,
+            },
+            skip_data: false,
+            symbols: None,
+            jobs,
+        }
+    }
+
+    const MANIFEST: &str = "\
+# comment
+37010000  # 0  37 01 00 00  lui    sp, 0
+
+riscv64 13055000 1000
+ef000004 80000000
+riscv64+noalias 6f00
+riscv32+isa=rv32i 33057502
+";
+
+    #[test]
+    fn test_parse_manifest() {
+        let entries = parse_manifest(MANIFEST);
+        assert_eq!(entries.len(), 5);
+        assert_eq!(
+            entries[1],
+            Entry {
+                line: 4,
+                arch: Some("riscv64".to_string()),
+                hex: "13055000".to_string(),
+                address: Some("1000".to_string()),
+            }
+        );
+        assert_eq!(entries[2].arch, None);
+        assert_eq!(entries[2].address.as_deref(), Some("80000000"));
+    }
+
+    #[test]
+    fn test_run_batch() {
+        let expected = [
+            " 0    lui\tsp, 0",
+            "1000    addi\ta0, zero, 5",
+            "80000000    jal\t0x40",
+            "Error: line 6: Disassembly error: ERROR: Decoding failed: Incomplete instruction",
+            " 0    unknown\t0x02750533",
+            "",
+        ]
+        .join("\n");
+        for jobs in [1, 2, 8] {
+            let output = run_batch(MANIFEST, &options(OutputFormat::Text, jobs));
+            assert_eq!(output.text, expected, "{jobs} jobs");
+            assert_eq!((output.inputs, output.failures), (5, 1));
+        }
+
+        let output = run_batch(MANIFEST, &options(OutputFormat::JsonLines, 3));
+        let lines: Vec<serde_json::Value> = output
+            .text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1]["line"], 4);
+        assert_eq!(lines[1]["arch"], "riscv64");
+        assert_eq!(lines[1]["address"], 0x1000);
+        assert_eq!(lines[1]["instructions"][0]["mnemonic"], "addi");
+        assert_eq!(lines[3]["line"], 6);
+        assert!(lines[3]["error"].is_string());
+    }
+
+    #[test]
+    fn test_short_addresses() {
+        let output = run_batch(
+            "riscv32 13051500 0x100\nriscv32 13051500 100\n",
+            &options(OutputFormat::Text, 1),
+        );
+        assert_eq!(
+            output.text,
+            "100    addi\ta0, a0, 1\n100    addi\ta0, a0, 1\n"
+        );
+        assert_eq!(output.failures, 0);
+    }
+
+    #[test]
+    fn test_aarch64_cases() {
+        // The fixture of the cstool comparator. Without `-d` robustone
+        // leaves out the encoding, and cstool pads the mnemonic with spaces
+        // where robustone prints a tab, so only the words after the address
+        // and the encoding are compared.
+        let cases = include_str!("../../test/architectures/aarch64/test_cases.txt");
+        let mut options = options(OutputFormat::Text, 2);
+        options.default_arch = Some("aarch64".to_string());
+        let output = run_batch(cases, &options);
+        let words = |text: &str, skip: usize| {
+            text.split_whitespace()
+                .skip(skip)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let expected: Vec<String> = cases
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once("  # "))
+            .map(|(_, text)| words(text, 5))
+            .collect();
+        let actual: Vec<String> = output.text.lines().map(|line| words(line, 1)).collect();
+        assert_eq!(actual, expected);
+        assert_eq!(output.failures, 0);
+    }
+}
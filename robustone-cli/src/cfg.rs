@@ -0,0 +1,416 @@
+//! Basic blocks and control-flow graphs (`--cfg`).
+//!
+//! A graph is built from a recursive [`Traversal`], so only reached code is
+//! in it. A block starts at an entry point, at the target of a branch, jump
+//! or call, after an instruction that does not simply fall through, or
+//! where the code before it is data; it runs until the next such start.
+//! Every block has the edges of its last instruction:
+//!
+//! - `fallthrough` to the next block, also after calls and conditional
+//!   branches;
+//! - `taken` to the target of a branch or jump;
+//! - `call` to the callee;
+//! - `return`, and `indirect` for jumps and calls whose target is unknown,
+//!   which have no destination block.
+//!
+//! Targets outside the input keep their address even though no block
+//! starts there. The graph prints as Graphviz DOT (`--cfg dot`), one node
+//! per block, or as JSON (`--cfg json`):
+//!
+//! ```json
+//! {
+//!   "blocks": [{ "start": 4096, "end": 4104, "instructions": [...] }],
+//!   "edges": [{ "from": 4096, "to": 4104, "kind": "fallthrough" }]
+//! }
+//! ```
+//!
+//! `instructions` holds the objects of [`crate::json`] and `to` is `null`
+//! for `return` and `indirect` edges.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::config::DisasmConfig;
+use crate::disasm::DisassemblyEngine;
+use crate::json::InstructionRecord;
+use crate::loader::Image;
+use crate::recursive::{Traversal, region_entries};
+use robustone_core::{DisasmError, Instruction, SymbolMap};
+use robustone_riscv::targets::Flow;
+
+/// How `--cfg` prints the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfgFormat {
+    Dot,
+    Json,
+}
+
+impl FromStr for CfgFormat {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_ascii_lowercase().as_str() {
+            "dot" => Ok(Self::Dot),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown graph format {input} (expected dot or json)"
+            )),
+        }
+    }
+}
+
+/// Kind of a control-flow edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    Fallthrough,
+    Taken,
+    Call,
+    Return,
+    Indirect,
+}
+
+impl EdgeKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Fallthrough => "fallthrough",
+            Self::Taken => "taken",
+            Self::Call => "call",
+            Self::Return => "return",
+            Self::Indirect => "indirect",
+        }
+    }
+}
+
+/// An edge leaving the block starting at `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    pub from: u64,
+    /// Destination address; `None` for returns and unknown targets.
+    pub to: Option<u64>,
+    pub kind: EdgeKind,
+}
+
+/// A run of instructions entered only at the top and left only at the
+/// bottom.
+#[derive(Debug)]
+pub struct BasicBlock {
+    pub start: u64,
+    /// Address just past the last instruction.
+    pub end: u64,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Basic blocks keyed by start address, and the edges between them.
+#[derive(Debug, Default)]
+pub struct Cfg {
+    pub blocks: BTreeMap<u64, BasicBlock>,
+    pub edges: Vec<Edge>,
+}
+
+impl Cfg {
+    /// Split the instructions of `traversal` into basic blocks; `entries`
+    /// are the addresses it started from.
+    pub fn new(traversal: Traversal, entries: &[u64]) -> Self {
+        let flows = traversal.flows;
+        let mut leaders: BTreeSet<u64> = entries.iter().copied().collect();
+        for flow in flows.values() {
+            if let Flow::Branch(target) | Flow::Jump(target) | Flow::Call(target) = flow {
+                leaders.insert(*target);
+            }
+        }
+
+        let mut cfg = Cfg::default();
+        let mut block: Option<BasicBlock> = None;
+        for (address, instruction) in traversal.instructions {
+            let flow = flows.get(&address).copied().unwrap_or(Flow::Next);
+            let mut current = match block.take() {
+                Some(current) if current.end == address && !leaders.contains(&address) => current,
+                previous => {
+                    if let Some(previous) = previous {
+                        cfg.finish(previous, &flows);
+                    }
+                    BasicBlock {
+                        start: address,
+                        end: address,
+                        instructions: Vec::new(),
+                    }
+                }
+            };
+            current.end = address.wrapping_add(instruction.size as u64);
+            current.instructions.push(instruction);
+            if flow == Flow::Next {
+                block = Some(current);
+            } else {
+                cfg.finish(current, &flows);
+            }
+        }
+        if let Some(last) = block {
+            cfg.finish(last, &flows);
+        }
+
+        // Fall-through edges only lead to code that was reached.
+        let blocks = &cfg.blocks;
+        cfg.edges.retain(|edge| {
+            edge.kind != EdgeKind::Fallthrough || edge.to.is_some_and(|to| blocks.contains_key(&to))
+        });
+        cfg
+    }
+
+    /// Add `block` with the edges of its last instruction.
+    fn finish(&mut self, block: BasicBlock, flows: &BTreeMap<u64, Flow>) {
+        let last = block
+            .instructions
+            .last()
+            .map_or(block.start, |instruction| instruction.address);
+        let from = block.start;
+        let next = Some(block.end);
+        let mut edge = |to, kind| self.edges.push(Edge { from, to, kind });
+        match flows.get(&last).copied().unwrap_or(Flow::Next) {
+            Flow::Next => edge(next, EdgeKind::Fallthrough),
+            Flow::Branch(target) => {
+                edge(Some(target), EdgeKind::Taken);
+                edge(next, EdgeKind::Fallthrough);
+            }
+            Flow::Jump(target) => edge(Some(target), EdgeKind::Taken),
+            Flow::Call(target) => {
+                edge(Some(target), EdgeKind::Call);
+                edge(next, EdgeKind::Fallthrough);
+            }
+            Flow::Return => edge(None, EdgeKind::Return),
+            Flow::Indirect => edge(None, EdgeKind::Indirect),
+            Flow::IndirectCall => {
+                edge(None, EdgeKind::Indirect);
+                edge(next, EdgeKind::Fallthrough);
+            }
+            Flow::Stop => {}
+        }
+        self.blocks.insert(block.start, block);
+    }
+
+    /// Add the blocks and edges of `other`, such as another region.
+    pub fn extend(&mut self, other: Cfg) {
+        self.blocks.extend(other.blocks);
+        self.edges.extend(other.edges);
+    }
+
+    /// Render the graph as Graphviz DOT, naming blocks after the symbols
+    /// at their start.
+    pub fn to_dot(&self, symbols: Option<&SymbolMap>) -> String {
+        let mut out = String::from("digraph cfg {\n");
+        out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+        for block in self.blocks.values() {
+            let mut label = String::new();
+            if let Some(name) = symbols.and_then(|symbols| symbols.get(block.start)) {
+                let _ = write!(label, "{name}:\\l");
+            }
+            for instruction in &block.instructions {
+                let _ = write!(
+                    label,
+                    "{:x}:  {}",
+                    instruction.address, instruction.mnemonic
+                );
+                if !instruction.operands.is_empty() {
+                    let _ = write!(label, " {}", instruction.operands.replace('\t', " "));
+                }
+                label.push_str("\\l");
+            }
+            let _ = writeln!(
+                out,
+                "    \"{:#x}\" [label=\"{}\"];",
+                block.start,
+                escape(&label)
+            );
+        }
+        for edge in &self.edges {
+            let to = match (edge.to, edge.kind) {
+                (Some(to), _) => format!("{to:#x}"),
+                (None, kind) => kind.name().to_string(),
+            };
+            let style = match edge.kind {
+                EdgeKind::Call => ", style=dashed",
+                EdgeKind::Return | EdgeKind::Indirect => ", style=dotted",
+                EdgeKind::Fallthrough | EdgeKind::Taken => "",
+            };
+            let _ = writeln!(
+                out,
+                "    \"{:#x}\" -> \"{to}\" [label=\"{}\"{style}];",
+                edge.from,
+                edge.kind.name()
+            );
+        }
+        for kind in [EdgeKind::Return, EdgeKind::Indirect] {
+            if self
+                .edges
+                .iter()
+                .any(|edge| edge.to.is_none() && edge.kind == kind)
+            {
+                let _ = writeln!(out, "    \"{}\" [shape=plaintext];", kind.name());
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph as the JSON document described in the module
+    /// documentation.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Block<'a> {
+            start: u64,
+            end: u64,
+            instructions: Vec<InstructionRecord<'a>>,
+        }
+        #[derive(Serialize)]
+        struct Graph<'a> {
+            blocks: Vec<Block<'a>>,
+            edges: &'a [Edge],
+        }
+
+        let graph = Graph {
+            blocks: self
+                .blocks
+                .values()
+                .map(|block| Block {
+                    start: block.start,
+                    end: block.end,
+                    instructions: block
+                        .instructions
+                        .iter()
+                        .map(InstructionRecord::new)
+                        .collect(),
+                })
+                .collect(),
+            edges: &self.edges,
+        };
+        let mut out = serde_json::to_string_pretty(&graph).unwrap_or_default();
+        out.push('\n');
+        out
+    }
+
+    /// Render the graph in `format`.
+    pub fn format(&self, format: CfgFormat, symbols: Option<&SymbolMap>) -> String {
+        match format {
+            CfgFormat::Dot => self.to_dot(symbols),
+            CfgFormat::Json => self.to_json(),
+        }
+    }
+}
+
+/// Escapes a DOT string, keeping the `\l` line breaks.
+fn escape(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
+
+/// Build the graph of `config.hex_bytes`, traversed from the start address
+/// and the `--entry` addresses.
+pub fn build(engine: &DisassemblyEngine, config: &DisasmConfig) -> Cfg {
+    let mut entries = vec![config.start_address];
+    entries.extend(config.recursive.iter().flatten());
+    let traversal = engine.disassemble_recursive(
+        &config.hex_bytes,
+        config.arch_name(),
+        config.start_address,
+        &entries,
+    );
+    Cfg::new(traversal, &entries)
+}
+
+/// Build the graph of every region of `image` and print it in `format`,
+/// naming blocks after the image symbols.
+pub fn format_image(
+    image: &Image,
+    config: &DisasmConfig,
+    format: CfgFormat,
+) -> Result<String, DisasmError> {
+    let options = config.handler_options();
+    let mut engine = match &image.target {
+        Some(target) => DisassemblyEngine::for_target(target, options)?,
+        None => DisassemblyEngine::with_options(config.arch_name(), options)?,
+    };
+    let arch = config.arch_name();
+    let mut symbols = config.symbols.clone().unwrap_or_default();
+    for region in &image.regions {
+        for (address, name) in region.symbols.iter() {
+            symbols.insert(address, name);
+        }
+    }
+    engine.set_symbolizer(arch, Box::new(symbols.clone()))?;
+
+    let extra = config.recursive.clone().unwrap_or_default();
+    let mut cfg = Cfg::default();
+    for region in &image.regions {
+        let entries = region_entries(image, region, &extra);
+        let traversal = engine.disassemble_recursive(&region.bytes, arch, region.address, &entries);
+        cfg.extend(Cfg::new(traversal, &entries));
+    }
+    Ok(cfg.format(format, Some(&symbols)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cfg() {
+        // jal ra, f ; j 1f ; <data> ; 1: ret ; f: addi a0, a0, 1 ;
+        // beqz a0, 2f ; jr t0 ; 2: c.ret
+        let bytes = [
+            0xef, 0x00, 0x00, 0x01, 0x6f, 0x00, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0x67, 0x80,
+            0x00, 0x00, 0x13, 0x05, 0x15, 0x00, 0x63, 0x04, 0x05, 0x00, 0x67, 0x80, 0x02, 0x00,
+            0x82, 0x80,
+        ];
+        let engine = DisassemblyEngine::new("riscv64");
+        let traversal = engine.disassemble_recursive(&bytes, "riscv64", 0x1000, &[0x1000]);
+        let cfg = Cfg::new(traversal, &[0x1000]);
+
+        let blocks: Vec<(u64, u64)> = cfg
+            .blocks
+            .values()
+            .map(|block| (block.start, block.end))
+            .collect();
+        assert_eq!(
+            blocks,
+            [
+                (0x1000, 0x1004),
+                (0x1004, 0x1008),
+                (0x100c, 0x1010),
+                (0x1010, 0x1018),
+                (0x1018, 0x101c),
+                (0x101c, 0x101e),
+            ]
+        );
+        let edge = |from, to, kind| Edge { from, to, kind };
+        assert_eq!(
+            cfg.edges,
+            [
+                edge(0x1000, Some(0x1010), EdgeKind::Call),
+                edge(0x1000, Some(0x1004), EdgeKind::Fallthrough),
+                edge(0x1004, Some(0x100c), EdgeKind::Taken),
+                edge(0x100c, None, EdgeKind::Return),
+                edge(0x1010, Some(0x101c), EdgeKind::Taken),
+                edge(0x1010, Some(0x1018), EdgeKind::Fallthrough),
+                edge(0x1018, None, EdgeKind::Indirect),
+                edge(0x101c, None, EdgeKind::Return),
+            ]
+        );
+
+        let mut symbols = SymbolMap::new();
+        symbols.insert(0x1010, "f");
+        let dot = cfg.to_dot(Some(&symbols));
+        assert!(dot.starts_with("digraph cfg {\n"));
+        assert!(dot.contains(
+            "    \"0x1010\" [label=\"f:\\l1010:  addi a0, a0, 1\\l1014:  beqz a0, 8\\l\"];\n"
+        ));
+        assert!(dot.contains("    \"0x1000\" -> \"0x1010\" [label=\"call\", style=dashed];\n"));
+        assert!(dot.contains("    \"return\" [shape=plaintext];\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&cfg.to_json()).unwrap();
+        assert_eq!(json["blocks"][3]["instructions"][1]["mnemonic"], "beqz");
+        assert_eq!(json["edges"][3]["kind"], "return");
+        assert!(json["edges"][3]["to"].is_null());
+    }
+}
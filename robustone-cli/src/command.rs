@@ -1,6 +1,16 @@
+use crate::batch::BatchOptions;
+use crate::cfg::CfgFormat;
+use crate::config::OutputFormat;
 use crate::error::{CliError, Result};
+use crate::input;
+use crate::loader::{FileFormat, Image};
+use crate::utils::parse_address;
 use crate::utils::validate_architecture_legacy as validate_architecture;
 use clap::Parser;
+use robustone_core::types::options::{HandlerOption, RegisterNaming};
+use robustone_core::{HandlerOptions, SymbolMap};
+use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Robustone - Capstone-compatible disassembly engine CLI tool (cstool style)
 #[derive(Parser, Debug)]
@@ -16,16 +26,24 @@ pub struct Cli {
     #[arg(
         help = "Target architecture with optional modes (e.g., riscv32, arm+thumb, x86+intel)",
         long_help = "Specify the target architecture and optional mode modifiers.\n\
-Examples: riscv32, riscv64, arm+thumb, arm+v8, x86+intel, x86+att"
+Examples: riscv32, riscv64, arm+thumb, arm+v8, x86+intel, x86+att\n\
+Use +aliases to print every objdump/LLVM pseudo-instruction, or +noalias to\n\
+always print the real instruction (objdump -M no-aliases).\n\
+Use +llvm or +gnu to match llvm-mc or GNU objdump output instead of cstool.\n\
+Use +isa=<ISA> to pick the decoder extensions, e.g. riscv32+isa=rv32imac.\n\
+Use repl [ARCH] [ADDRESS] to decode hex typed interactively, or\n\
+batch MANIFEST [ARCH] to disassemble every `[arch] hex [address]` line of MANIFEST"
     )]
-    #[arg(value_parser = validate_architecture)]
+    #[arg(value_parser = parse_arch_mode)]
     pub arch_mode: Option<String>,
 
     /// Hexadecimal machine code to disassemble (for example `"00100093"`).
     #[arg(
-        help = "Hexadecimal machine code to disassemble",
+        help = "Hexadecimal machine code to disassemble, or - to read it from stdin",
         long_help = "Provide the machine code as a hexadecimal string to be disassembled.\n\
-Example: \"00100093\" for RISC-V addi instruction"
+Example: \"00100093\" for RISC-V addi instruction\n\
+Use - to read hex text from stdin; xxd, hexdump -C and od -x dumps and\n\
+comma-separated 0x.. lists are recognised"
     )]
     #[arg(value_names = ["HEX_CODE"])]
     pub hex_code: Option<String>,
@@ -39,13 +57,55 @@ If not provided, defaults to 0. Prefix with 0x or use plain hex."
     #[arg(value_names = ["ADDRESS"])]
     pub address: Option<String>,
 
+    // Input options group
+    /// `--file`: read raw machine code from a file instead of `HEX_CODE`.
+    #[arg(
+        long = "file",
+        value_name = "PATH",
+        help = "Disassemble a raw binary or ELF file (- for stdin)",
+        long_help = "Read raw machine code from PATH, or from stdin when PATH is -.\n\
+The HEX_CODE argument is then omitted, so the next positional argument is the start address.\n\
+ELF files are listed like objdump -d: executable sections at their load addresses with\n\
+function labels, for the target recorded in the file (the architecture argument is optional).\n\
+Intel HEX and S-record files are listed region by region, reporting the gaps between them"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// `--file-format`: file format of `--file`, detected from the contents by default.
+    #[arg(
+        long = "file-format",
+        value_name = "FORMAT",
+        value_parser = FileFormat::from_str,
+        help = "Format of --file: raw, elf, ihex or srec (default: detected)"
+    )]
+    pub file_format: Option<FileFormat>,
+
+    /// `--offset`: skip this many bytes of input.
+    #[arg(
+        long = "offset",
+        value_name = "BYTES",
+        value_parser = input::parse_size,
+        help = "Skip BYTES bytes of input (decimal or 0x hex)"
+    )]
+    pub offset: Option<u64>,
+
+    /// `--length`: disassemble at most this many bytes of input.
+    #[arg(
+        long = "length",
+        value_name = "BYTES",
+        value_parser = input::parse_size,
+        help = "Disassemble at most BYTES bytes of input (decimal or 0x hex)"
+    )]
+    pub length: Option<u64>,
+
     // Display options group
     /// `-d`: emit detailed instruction metadata alongside the mnemonic.
     #[arg(
         short = 'd',
         long = "detailed",
         help = "Show detailed instruction information",
-        long_help = "Display additional instruction metadata including operands, encoding format, and size"
+        long_help = "Print cstool's detail block below each instruction: the instruction id,\n\
+op_count and each operand with its access, the registers read and modified, and the groups"
     )]
     pub detailed: bool,
 
@@ -58,12 +118,12 @@ If not provided, defaults to 0. Prefix with 0x or use plain hex."
     )]
     pub alias_regs: bool,
 
-    /// `-r`: request detailed instruction information including alias resolutions.
+    /// `-r`: like `-d`, but describe the real instruction behind an alias.
     #[arg(
         short = 'r',
         long = "real-detail",
-        help = "Show detailed real instruction information (including aliases)",
-        long_help = "Show comprehensive instruction details including register alias mappings and operand details"
+        help = "Show detailed real instruction information (without aliases)",
+        long_help = "Like -d, but list the operands of the real instruction behind an alias (beq a0, zero, 8 for beqz a0, 8) instead of the operands the alias prints"
     )]
     pub real_detail: bool,
 
@@ -76,6 +136,21 @@ If not provided, defaults to 0. Prefix with 0x or use plain hex."
     )]
     pub unsigned_immediate: bool,
 
+    /// `--format`: output format for the disassembly.
+    #[arg(
+        long = "format",
+        value_name = "FORMAT",
+        value_parser = OutputFormat::from_str,
+        default_value = "text",
+        help = "Output format: text, json, jsonl or asm",
+        long_help = "Print cstool-style text (default), one JSON document (json) or one JSON object\n\
+per instruction (jsonl) with operands, registers read/written and groups. Operands follow\n\
+the real instruction behind an alias, so `jal 8` also lists its ra operand. See the\n\
+robustone_cli::json module for the schema. Object files print one document per section.\n\
+asm prints an assembly file for GNU as or llvm-mc that reassembles to the input bytes"
+    )]
+    pub format: OutputFormat,
+
     // Decoding options group
     /// `-s`: enable SKIPDATA mode to step past undecodable bytes.
     #[arg(
@@ -86,6 +161,82 @@ If not provided, defaults to 0. Prefix with 0x or use plain hex."
     )]
     pub skip_data: bool,
 
+    /// `--recursive`: decode only what control flow reaches.
+    #[arg(
+        long = "recursive",
+        help = "Follow control flow from the entry points instead of a linear sweep",
+        long_help = "Disassemble recursively: decode from the entry points, follow branches, jumps and\n\
+calls, and show the bytes no path reaches as data. The entry points are the start\n\
+address, or for ELF files the entry address and the symbols, plus every --entry"
+    )]
+    pub recursive: bool,
+
+    /// `--entry`: extra entry point for `--recursive`.
+    #[arg(
+        long = "entry",
+        value_name = "ADDR",
+        help = "Add an entry point for --recursive or --cfg (hex, repeatable)"
+    )]
+    pub entry: Vec<String>,
+
+    /// `--cfg`: print the control-flow graph instead of the disassembly.
+    #[arg(
+        long = "cfg",
+        value_name = "FORMAT",
+        value_parser = CfgFormat::from_str,
+        help = "Print the control-flow graph as dot or json",
+        long_help = "Split the code reached by --recursive into basic blocks and print the\n\
+control-flow graph instead of the disassembly: Graphviz DOT (dot) or JSON (json).\n\
+Edges are fallthrough, taken, call, return or indirect; see the robustone_cli::cfg\n\
+module for the schema"
+    )]
+    pub cfg: Option<CfgFormat>,
+
+    /// `--xrefs`: print the cross-reference report instead of the disassembly.
+    #[arg(
+        long = "xrefs",
+        conflicts_with_all = ["recursive", "cfg"],
+        help = "Print the addresses the code refers to and where from",
+        long_help = "Print a cross-reference report instead of the disassembly: every branch, jump\n\
+and call target, auipc-based address, load and store, and lui-based constant, grouped by\n\
+the address referred to. --format json and jsonl print {from, to, kind} objects"
+    )]
+    pub xrefs: bool,
+
+    /// `--constants`: annotate materialised `lui`/`auipc` values.
+    #[arg(
+        long = "constants",
+        conflicts_with_all = ["cfg", "xrefs"],
+        help = "Annotate the values built by lui/auipc instruction pairs",
+        long_help = "Track register values set by lui and auipc through straight-line code and\n\
+annotate the addi, load, store or jalr completing each one with the value and its\n\
+symbol, e.g. `addi a0, a0, 0x234 # 0x80001234 <uart_base>`"
+    )]
+    pub constants: bool,
+
+    /// `--symbols`: name branch targets and PC-relative addresses from a file.
+    #[arg(
+        long = "symbols",
+        value_name = "FILE",
+        help = "Print symbol names from an nm-style listing for branch targets",
+        long_help = "Read symbols from FILE, one `address [type] name` line each (the output of `nm`),\n\
+and print branch and jump targets as <name+offset>; auipc pairs get a `# <name>` comment"
+    )]
+    pub symbols: Option<PathBuf>,
+
+    /// `--jobs`: worker threads for large inputs and batch mode.
+    #[arg(
+        short = 'j',
+        long = "jobs",
+        value_name = "N",
+        default_value_t = 1,
+        help = "Number of worker threads (0: one per CPU)",
+        long_help = "Decode with N threads: large inputs are split into chunks joined in address\n\
+order, and batch mode spreads the manifest lines. The output does not depend on N.\n\
+0 uses one thread per CPU"
+    )]
+    pub jobs: usize,
+
     // System options group
     /// `-v`: print version and build metadata instead of disassembling input.
     #[arg(
@@ -97,11 +248,135 @@ If not provided, defaults to 0. Prefix with 0x or use plain hex."
     pub version: bool,
 }
 
+/// The architecture argument that starts the interactive mode.
+pub const REPL: &str = "repl";
+
+/// The architecture argument that starts batch mode.
+pub const BATCH: &str = "batch";
+
+/// Accepts an architecture, [`REPL`] or [`BATCH`].
+fn parse_arch_mode(arch_mode: &str) -> Result<String> {
+    if arch_mode == REPL || arch_mode == BATCH {
+        return Ok(arch_mode.to_string());
+    }
+    validate_architecture(arch_mode)
+}
+
 impl Cli {
+    /// Number of worker threads, with `--jobs 0` meaning one per CPU.
+    pub fn jobs(&self) -> usize {
+        match self.jobs {
+            0 => std::thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+            jobs => jobs,
+        }
+    }
+
+    /// Check if the interactive mode was requested (`robustone repl`).
+    pub fn is_repl(&self) -> bool {
+        self.arch_mode.as_deref() == Some(REPL)
+    }
+
+    /// Validate the arguments of `robustone repl [ARCH] [ADDRESS]`, where
+    /// the architecture defaults to riscv64.
+    pub fn validate_repl(&self) -> Result<ValidatedConfig> {
+        if self.file.is_some() {
+            return Err(CliError::validation(
+                "file",
+                "--file cannot be used with repl",
+            ));
+        }
+        if self.recursive || self.cfg.is_some() || self.xrefs {
+            return Err(CliError::validation(
+                "recursive",
+                "--recursive, --cfg and --xrefs cannot be used with repl",
+            ));
+        }
+        let arch_mode = match &self.hex_code {
+            Some(arch_mode) => validate_architecture(arch_mode)?,
+            None => "riscv64".to_string(),
+        };
+
+        Ok(ValidatedConfig {
+            arch_mode: Some(arch_mode),
+            hex_code: Some(Vec::new()),
+            address: self.validate_address()?,
+            detailed: self.detailed,
+            alias_regs: self.alias_regs,
+            real_detail: self.real_detail,
+            skip_data: self.skip_data,
+            unsigned_immediate: self.unsigned_immediate,
+            format: self.format,
+            jobs: self.jobs(),
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: self.constants,
+            symbols: self.load_symbols()?,
+            image: None,
+            version: self.version,
+        })
+    }
+
+    /// Check if batch mode was requested (`robustone batch`).
+    pub fn is_batch(&self) -> bool {
+        self.arch_mode.as_deref() == Some(BATCH)
+    }
+
+    /// Validate the arguments of `robustone batch MANIFEST [ARCH]` and read
+    /// the manifest, from stdin when MANIFEST is `-`.
+    pub fn validate_batch(&self) -> Result<(String, BatchOptions)> {
+        let Some(path) = &self.hex_code else {
+            return Err(CliError::MissingArgument("MANIFEST".to_string()));
+        };
+        if self.file.is_some() {
+            return Err(CliError::validation(
+                "file",
+                "--file cannot be used with batch",
+            ));
+        }
+        if self.recursive || self.cfg.is_some() || self.xrefs || self.constants {
+            return Err(CliError::validation(
+                "recursive",
+                "--recursive, --cfg, --xrefs and --constants cannot be used with batch",
+            ));
+        }
+        if self.format == OutputFormat::Asm {
+            return Err(CliError::validation(
+                "format",
+                "batch mode supports --format text, json and jsonl",
+            ));
+        }
+        let default_arch = self
+            .address
+            .as_deref()
+            .map(validate_architecture)
+            .transpose()?;
+        let manifest = if path == "-" {
+            input::read_stdin_text()?
+        } else {
+            std::fs::read_to_string(path).map_err(|e| CliError::Io(format!("{path}: {e}")))?
+        };
+        let options = BatchOptions {
+            default_arch,
+            display_options: DisplayOptions {
+                detailed: self.detailed,
+                alias_regs: self.alias_regs,
+                real_detail: self.real_detail,
+                unsigned_immediate: self.unsigned_immediate,
+                format: self.format,
+            },
+            skip_data: self.skip_data,
+            symbols: self.load_symbols()?,
+            jobs: self.jobs(),
+        };
+        Ok((manifest, options))
+    }
+
     /// Validate the CLI arguments and return a configuration.
     pub fn validate(&self) -> Result<ValidatedConfig> {
-        let hex_code = self.validate_hex_code()?;
+        let (hex_code, image) = self.validate_hex_code()?;
         let address = self.validate_address()?;
+        let symbols = self.load_symbols()?;
 
         Ok(ValidatedConfig {
             arch_mode: self.arch_mode.clone(),
@@ -112,52 +387,107 @@ impl Cli {
             real_detail: self.real_detail,
             skip_data: self.skip_data,
             unsigned_immediate: self.unsigned_immediate,
+            format: self.format,
+            jobs: self.jobs(),
+            recursive: self.validate_entries()?,
+            cfg: self.cfg,
+            xrefs: self.validate_xrefs()?,
+            constants: self.constants,
+            symbols,
+            image,
             version: self.version,
         })
     }
 
-    /// Validate hexadecimal code input.
-    fn validate_hex_code(&self) -> Result<Option<Vec<u8>>> {
-        match &self.hex_code {
-            Some(code) => {
-                let clean_code = code
-                    .trim()
-                    .replace("0x", "")
-                    .replace("0X", "")
-                    .replace(" ", "");
-                if clean_code.is_empty() {
-                    return Err(CliError::validation("hex_code", "Empty hex code provided"));
+    /// Read the machine code from `HEX_CODE`, stdin or `--file`, then apply
+    /// `--offset` and `--length`. ELF, Intel HEX and S-record files are
+    /// loaded as an image instead.
+    fn validate_hex_code(&self) -> Result<(Option<Vec<u8>>, Option<Image>)> {
+        let bytes = match (&self.file, self.hex_code.as_deref()) {
+            (Some(_), Some(_)) if self.address.is_some() => {
+                return Err(CliError::validation(
+                    "hex_code",
+                    "HEX_CODE cannot be combined with --file",
+                ));
+            }
+            (Some(path), _) => {
+                let bytes = input::read_binary(path)?;
+                let format = self
+                    .file_format
+                    .unwrap_or_else(|| FileFormat::detect(&bytes));
+                if let Some(image) = format.load(&bytes)? {
+                    if self.offset.is_some() || self.length.is_some() {
+                        return Err(CliError::validation(
+                            "offset",
+                            "--offset and --length only apply to raw binaries",
+                        ));
+                    }
+                    return Ok((None, Some(image)));
                 }
-
-                let trimmed_code = if clean_code.len() % 4 != 0 {
-                    &clean_code[..clean_code.len() - clean_code.len() % 4]
-                } else {
-                    &clean_code
-                };
-
-                hex::decode(trimmed_code)
-                    .map(Some)
-                    .map_err(|e| CliError::validation("hex_code", format!("Invalid hex code: {e}")))
+                bytes
             }
-            None => Ok(None),
-        }
+            (None, Some("-")) => input::parse_hex_text(&input::read_stdin_text()?)?,
+            (None, Some(code)) => input::parse_hex_text(code)?,
+            (None, None) => return Ok((None, None)),
+        };
+        Ok((Some(input::slice(bytes, self.offset, self.length)?), None))
     }
 
     /// Validate address input.
     fn validate_address(&self) -> Result<Option<u64>> {
-        match &self.address {
-            Some(addr) => {
-                let clean_addr = addr.trim().replace("0x", "").replace("0X", "");
-                if clean_addr.is_empty() {
-                    return Err(CliError::validation("address", "Empty address provided"));
-                }
+        // With `--file` there is no HEX_CODE, so the address comes first.
+        let address = match &self.file {
+            Some(_) if self.address.is_none() => &self.hex_code,
+            _ => &self.address,
+        };
+        address.as_deref().map(parse_address).transpose()
+    }
 
-                u64::from_str_radix(&clean_addr, 16).map(Some).map_err(|_| {
-                    CliError::validation("address", "Invalid hexadecimal address format")
-                })
+    /// The `--entry` addresses when `--recursive` or `--cfg` is given.
+    fn validate_entries(&self) -> Result<Option<Vec<u64>>> {
+        if !self.recursive && self.cfg.is_none() {
+            if !self.entry.is_empty() {
+                return Err(CliError::validation(
+                    "entry",
+                    "--entry needs --recursive or --cfg",
+                ));
             }
-            None => Ok(None),
+            return Ok(None);
+        }
+        if self.format == OutputFormat::Asm {
+            return Err(CliError::validation(
+                "format",
+                "--recursive and --cfg do not support --format asm",
+            ));
+        }
+        self.entry
+            .iter()
+            .map(|entry| parse_address(entry))
+            .collect::<Result<_>>()
+            .map(Some)
+    }
+
+    /// Check that the report format suits `--xrefs`.
+    fn validate_xrefs(&self) -> Result<bool> {
+        if self.xrefs && self.format == OutputFormat::Asm {
+            return Err(CliError::validation(
+                "format",
+                "--xrefs supports --format text, json and jsonl",
+            ));
         }
+        Ok(self.xrefs)
+    }
+
+    /// Read and parse the `--symbols` file.
+    fn load_symbols(&self) -> Result<Option<SymbolMap>> {
+        let Some(path) = &self.symbols else {
+            return Ok(None);
+        };
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| CliError::Io(format!("{}: {e}", path.display())))?;
+        SymbolMap::parse(&text)
+            .map(Some)
+            .map_err(|e| CliError::parse("symbols", e.to_string()))
     }
 
     /// Check if version information should be displayed.
@@ -167,7 +497,7 @@ impl Cli {
 
     /// Check if the CLI has valid input for disassembly.
     pub fn has_disassembly_input(&self) -> bool {
-        self.hex_code.is_some()
+        self.hex_code.is_some() || self.file.is_some()
     }
 }
 
@@ -182,6 +512,20 @@ pub struct ValidatedConfig {
     pub real_detail: bool,
     pub skip_data: bool,
     pub unsigned_immediate: bool,
+    pub format: OutputFormat,
+    /// Worker threads, already resolved from `--jobs 0`.
+    pub jobs: usize,
+    /// Extra entry points when `--recursive` is given.
+    pub recursive: Option<Vec<u64>>,
+    /// `--cfg`: graph format printed instead of the disassembly.
+    pub cfg: Option<CfgFormat>,
+    /// `--xrefs`: print the cross-reference report.
+    pub xrefs: bool,
+    /// `--constants`: annotate values completed from `lui`/`auipc`.
+    pub constants: bool,
+    pub symbols: Option<SymbolMap>,
+    /// Program image loaded from an object file given with `--file`.
+    pub image: Option<Image>,
     pub version: bool,
 }
 
@@ -203,6 +547,7 @@ impl ValidatedConfig {
             alias_regs: self.alias_regs,
             real_detail: self.real_detail,
             unsigned_immediate: self.unsigned_immediate,
+            format: self.format,
         }
     }
 }
@@ -214,4 +559,24 @@ pub struct DisplayOptions {
     pub alias_regs: bool,
     pub real_detail: bool,
     pub unsigned_immediate: bool,
+    pub format: OutputFormat,
+}
+
+impl DisplayOptions {
+    /// Runtime handler options implied by the display flags.
+    ///
+    /// cstool already prints ABI aliases for RISC-V, so `-a` keeps the
+    /// default register naming; detail is only computed for `-d`/`-r` and
+    /// the JSON formats. `-d` lists the operands an alias prints, while `-r`
+    /// and the JSON formats describe the real instruction.
+    pub fn handler_options(&self) -> HandlerOptions {
+        let json = self.format != OutputFormat::Text;
+        HandlerOptions::default()
+            .with(HandlerOption::RegisterNaming(RegisterNaming::Alias))
+            .with(HandlerOption::UnsignedImmediates(self.unsigned_immediate))
+            .with(HandlerOption::Detail(
+                self.detailed || self.real_detail || json,
+            ))
+            .with(HandlerOption::RealDetail(self.real_detail || json))
+    }
 }
@@ -1,6 +1,11 @@
 use crate::arch::ArchitectureSpec;
+use crate::cfg::CfgFormat;
 use crate::command::{DisplayOptions, ValidatedConfig};
 use crate::error::{CliError, Result};
+use crate::loader::Image;
+use robustone_core::types::options::{AliasMode, HandlerOption, SyntaxStyle};
+use robustone_core::{HandlerOptions, SymbolMap};
+use std::str::FromStr;
 
 /// High-level disassembly configuration that unifies all options.
 #[derive(Debug, Clone)]
@@ -10,6 +15,19 @@ pub struct DisasmConfig {
     pub start_address: u64,
     pub display_options: DisplayOptions,
     pub skip_data: bool,
+    /// Worker threads for large inputs (`--jobs`).
+    pub jobs: usize,
+    /// Entry points for recursive-descent disassembly (`--recursive`,
+    /// `--entry`); `None` for a linear sweep. See [`crate::recursive`].
+    pub recursive: Option<Vec<u64>>,
+    /// Print the control-flow graph in this format instead (`--cfg`).
+    pub cfg: Option<CfgFormat>,
+    /// Print the cross-reference report instead (`--xrefs`).
+    pub xrefs: bool,
+    /// Annotate values completed from `lui`/`auipc` (`--constants`).
+    pub constants: bool,
+    /// Symbols printed for branch targets (`--symbols`).
+    pub symbols: Option<SymbolMap>,
 }
 
 impl DisasmConfig {
@@ -33,6 +51,65 @@ impl DisasmConfig {
             start_address: config.address_or_default(),
             display_options: config.display_options(),
             skip_data: config.skip_data,
+            jobs: config.jobs,
+            recursive: config.recursive.take(),
+            cfg: config.cfg,
+            xrefs: config.xrefs,
+            constants: config.constants,
+            symbols: config.symbols.take(),
+        })
+    }
+
+    /// Create the configuration for disassembling a loaded image.
+    ///
+    /// The image's own target replaces the base architecture; modifiers
+    /// given on the command line still apply. Images without a target need
+    /// the architecture argument. Listings use the GNU syntax unless another
+    /// syntax profile is requested, matching `objdump -d`.
+    pub fn for_image(mut config: ValidatedConfig, image: &Image) -> Result<Self> {
+        let requested = config
+            .arch_mode
+            .take()
+            .map(|arch_mode| ArchitectureSpec::parse(&arch_mode))
+            .transpose()
+            .map_err(|e| CliError::parse("architecture", e.to_string()))?;
+        let mut arch_spec = match (&image.target, requested) {
+            (Some(target), requested) => {
+                let mut spec = ArchitectureSpec::parse(target.arch)
+                    .map_err(|e| CliError::parse("architecture", e.to_string()))?;
+                if let Some(requested) = requested {
+                    spec.options = requested.options;
+                }
+                spec
+            }
+            (None, Some(requested)) => requested,
+            (None, None) => {
+                return Err(CliError::validation(
+                    "arch_mode",
+                    "Architecture specification is required",
+                ));
+            }
+        };
+        if !arch_spec
+            .options
+            .iter()
+            .any(|option| matches!(option.as_str(), "capstone" | "llvm" | "gnu"))
+        {
+            arch_spec.options.push("gnu".to_string());
+        }
+
+        Ok(DisasmConfig {
+            arch_spec,
+            hex_bytes: Vec::new(),
+            start_address: 0,
+            display_options: config.display_options(),
+            skip_data: true,
+            jobs: config.jobs,
+            recursive: config.recursive.take(),
+            cfg: config.cfg,
+            xrefs: config.xrefs,
+            constants: config.constants,
+            symbols: config.symbols.take(),
         })
     }
 
@@ -48,11 +125,41 @@ impl DisasmConfig {
         self.arch_spec.arch.name()
     }
 
+    /// Get the ISA string of the `+isa=` modifier, if given.
+    pub fn isa(&self) -> Option<&str> {
+        self.arch_spec
+            .options
+            .iter()
+            .find_map(|option| option.strip_prefix("isa="))
+    }
+
     /// Check if detailed output is enabled.
     pub fn is_detailed(&self) -> bool {
         self.display_options.detailed || self.display_options.real_detail
     }
 
+    /// Get the runtime handler options implied by the display flags and
+    /// the architecture modifiers.
+    pub fn handler_options(&self) -> HandlerOptions {
+        let mut options = self.display_options.handler_options();
+        for modifier in &self.arch_spec.options {
+            match modifier.as_str() {
+                "aliases" => options.apply(HandlerOption::Aliases(AliasMode::All)),
+                "noalias" => options.apply(HandlerOption::Aliases(AliasMode::None)),
+                "capstone" => options.apply(HandlerOption::Syntax(SyntaxStyle::Capstone)),
+                "llvm" => options.apply(HandlerOption::Syntax(SyntaxStyle::Llvm)),
+                "gnu" => options.apply(HandlerOption::Syntax(SyntaxStyle::Gnu)),
+                _ => {}
+            }
+        }
+        // Graph JSON carries the operand details like --format json.
+        if self.cfg == Some(CfgFormat::Json) {
+            options.apply(HandlerOption::Detail(true));
+            options.apply(HandlerOption::RealDetail(true));
+        }
+        options
+    }
+
     /// Get the hex code as formatted words for display.
     pub fn hex_words(&self) -> Vec<String> {
         self.hex_bytes
@@ -101,17 +208,66 @@ impl DisasmConfig {
             ));
         }
 
+        // The control-flow, cross-reference, constant and assembly passes
+        // read RISC-V instructions.
+        if !self.arch_spec.arch.name().starts_with("riscv")
+            && (self.recursive.is_some()
+                || self.cfg.is_some()
+                || self.xrefs
+                || self.constants
+                || self.display_options.format == OutputFormat::Asm)
+        {
+            return Err(CliError::validation(
+                "arch_mode",
+                "--recursive, --cfg, --xrefs, --constants and --format asm only support RISC-V",
+            ));
+        }
+
         Ok(())
     }
 }
 
+/// How the disassembly is printed (`--format`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// cstool-style text.
+    #[default]
+    Text,
+    /// One JSON document; see [`crate::json`].
+    Json,
+    /// One JSON object per line; see [`crate::json`].
+    JsonLines,
+    /// An assembly file that reassembles to the input; see [`crate::asm`].
+    Asm,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        match input.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" | "ndjson" => Ok(Self::JsonLines),
+            "asm" | "s" => Ok(Self::Asm),
+            _ => Err(format!(
+                "unknown output format {input} (expected text, json, jsonl or asm)"
+            )),
+        }
+    }
+}
+
 /// Configuration for output formatting and display options.
 #[derive(Debug, Clone)]
 pub struct OutputConfig {
     pub show_hex: bool,
-    pub show_bytes: bool,
+    /// Name the real instruction behind an alias in the detail block (`-r`).
+    pub show_real: bool,
+    /// Print cstool's detail block below each instruction (`-d`/`-r`).
+    pub show_detail: bool,
     pub address_width: usize,
     pub hex_width: usize,
+    pub format: OutputFormat,
 }
 
 impl OutputConfig {
@@ -119,9 +275,11 @@ impl OutputConfig {
     pub fn from_display_options(display: &DisplayOptions) -> Self {
         Self {
             show_hex: display.detailed,
-            show_bytes: display.real_detail,
+            show_real: display.real_detail,
+            show_detail: display.detailed || display.real_detail,
             address_width: 8,
             hex_width: 8,
+            format: display.format,
         }
     }
 
@@ -129,9 +287,11 @@ impl OutputConfig {
     pub fn minimal() -> Self {
         Self {
             show_hex: false,
-            show_bytes: false,
+            show_real: false,
+            show_detail: false,
             address_width: 0,
             hex_width: 0,
+            format: OutputFormat::Text,
         }
     }
 }
@@ -152,6 +312,14 @@ mod tests {
             real_detail: false,
             skip_data: false,
             unsigned_immediate: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
             version: false,
         };
 
@@ -161,6 +329,58 @@ mod tests {
         assert_eq!(disasm_config.hex_bytes.len(), 4);
     }
 
+    #[test]
+    fn test_alias_and_syntax_modifiers() {
+        let config = |arch_mode: &str| {
+            let validated = ValidatedConfig {
+                arch_mode: Some(arch_mode.to_string()),
+                hex_code: Some(vec![0x13, 0x05, 0x50, 0x00]),
+                address: None,
+                detailed: false,
+                alias_regs: false,
+                real_detail: false,
+                skip_data: false,
+                unsigned_immediate: false,
+                format: OutputFormat::Text,
+                jobs: 1,
+                recursive: None,
+                cfg: None,
+                xrefs: false,
+                constants: false,
+                symbols: None,
+                image: None,
+                version: false,
+            };
+            DisasmConfig::from_validated_config(validated).unwrap()
+        };
+
+        assert_eq!(
+            config("riscv64").handler_options().aliases,
+            AliasMode::Capstone
+        );
+        assert_eq!(
+            config("riscv64+aliases").handler_options().aliases,
+            AliasMode::All
+        );
+        assert_eq!(
+            config("riscv64+noalias").handler_options().aliases,
+            AliasMode::None
+        );
+        assert_eq!(
+            config("riscv64").handler_options().syntax,
+            SyntaxStyle::Capstone
+        );
+        assert_eq!(
+            config("riscv64+gnu").handler_options().syntax,
+            SyntaxStyle::Gnu
+        );
+        let options = config("riscv32+llvm+noalias").handler_options();
+        assert_eq!(
+            (options.syntax, options.aliases),
+            (SyntaxStyle::Llvm, AliasMode::None)
+        );
+    }
+
     #[test]
     fn test_output_config() {
         let display = DisplayOptions {
@@ -168,10 +388,16 @@ mod tests {
             alias_regs: false,
             real_detail: false,
             unsigned_immediate: false,
+            format: OutputFormat::Json,
         };
 
         let output = OutputConfig::from_display_options(&display);
         assert!(output.show_hex);
-        assert!(!output.show_bytes);
+        assert!(!output.show_real);
+        assert!(output.show_detail);
+        assert_eq!(output.format, OutputFormat::Json);
+        assert!(display.handler_options().detail);
+        assert_eq!("jsonl".parse(), Ok(OutputFormat::JsonLines));
+        assert!("yaml".parse::<OutputFormat>().is_err());
     }
 }
@@ -0,0 +1,171 @@
+//! Annotations for `lui`/`auipc` address materialisation (`--constants`).
+//!
+//! RISC-V splits 32-bit constants and addresses across `lui` + `addi`,
+//! `auipc` + `ld`, `auipc` + `jalr` and the like, and the printer shows
+//! each half on its own. With `--constants`, register values are tracked
+//! through straight-line code with a [`ConstantTracker`] and the
+//! instruction completing each value gets it as a comment, named from the
+//! symbols when one covers it:
+//!
+//! ```text
+//! lui     a0, 0x80001
+//! addi    a0, a0, 0x234 # 0x80001234 <uart_base>
+//! ```
+//!
+//! The comment is left out when the `auipc` of the pair already carries
+//! it. Tracking restarts at every gap between instructions and at data.
+
+use std::fmt::Write;
+
+use crate::asm::instruction_word;
+use crate::config::DisasmConfig;
+use crate::disasm::config_target;
+use robustone_core::types::options::SyntaxStyle;
+use robustone_core::{DisasmError, Instruction, SymbolMap, Symbolizer};
+use robustone_riscv::constants::ConstantTracker;
+use robustone_riscv::decoder::{RiscVDecoder, Xlen};
+use robustone_riscv::extensions::Extensions;
+
+/// Appends materialised values to the operands of a run of instructions.
+pub struct Annotator {
+    decoder: RiscVDecoder,
+    tracker: ConstantTracker,
+    /// Print values without `0x`, as objdump does.
+    gnu: bool,
+    /// Address continuing the current run of instructions.
+    next: Option<u64>,
+    /// Operands of the previous instruction of the run.
+    previous: String,
+}
+
+impl Annotator {
+    /// Create an annotator for code of the given target.
+    pub fn new(xlen: Xlen, extensions: Extensions, gnu: bool) -> Self {
+        Self {
+            decoder: RiscVDecoder::new(xlen, extensions),
+            tracker: ConstantTracker::new(xlen),
+            gnu,
+            next: None,
+            previous: String::new(),
+        }
+    }
+
+    /// Create an annotator for the target and syntax of `config`.
+    pub fn for_config(config: &DisasmConfig) -> Result<Self, DisasmError> {
+        let (xlen, extensions) = config_target(config)?;
+        let gnu = config.handler_options().syntax == SyntaxStyle::Gnu;
+        Ok(Self::new(xlen, extensions, gnu))
+    }
+
+    /// Forget the tracked values, such as after data.
+    pub fn reset(&mut self) {
+        self.tracker.reset();
+        self.next = None;
+        self.previous.clear();
+    }
+
+    /// Track `instruction` and append the value it completes, if any, to
+    /// its operands. Instructions must be given in address order.
+    pub fn annotate(&mut self, instruction: &mut Instruction, symbols: Option<&SymbolMap>) {
+        let comment = self.comment(instruction, symbols);
+        instruction.operands.push_str(&comment);
+    }
+
+    /// Track `instruction` and return the comment naming the value it
+    /// completes, empty when there is none.
+    pub fn comment(&mut self, instruction: &Instruction, symbols: Option<&SymbolMap>) -> String {
+        let address = instruction.address;
+        if self.next != Some(address) {
+            self.reset();
+        }
+        let xlen = self.decoder.xlen();
+        let decoded = instruction_word(&instruction.bytes, xlen).and_then(|word| {
+            let decoded = self.decoder.decode(&instruction.bytes, address).ok()?;
+            Some((word, decoded))
+        });
+        let Some((word, decoded)) = decoded.filter(|_| !instruction.mnemonic.starts_with('.'))
+        else {
+            self.reset();
+            return String::new();
+        };
+        self.next = Some(address.wrapping_add(instruction.bytes.len() as u64));
+
+        let mut comment = String::new();
+        if let Some(materialized) = self.tracker.step(word, address, &decoded.operands_detail) {
+            let value = materialized.value;
+            let _ = if self.gnu {
+                write!(comment, " # {value:x}")
+            } else {
+                write!(comment, " # 0x{value:x}")
+            };
+            // The `auipc` of a pair names the address itself.
+            if self.previous.ends_with(&comment) || self.previous.contains(&format!("{comment} ")) {
+                comment.clear();
+            } else if let Some(symbols) = symbols {
+                comment.push(' ');
+                if !symbols.write_symbol(value, &mut comment) {
+                    comment.pop();
+                }
+            }
+        }
+        self.previous.clone_from(&instruction.operands);
+        self.previous.push_str(&comment);
+        comment
+    }
+}
+
+/// Annotate a run of `instructions` in address order.
+pub fn annotate_all(
+    annotator: &mut Annotator,
+    instructions: &mut [Instruction],
+    symbols: Option<&SymbolMap>,
+) {
+    for instruction in instructions {
+        annotator.annotate(instruction, symbols);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(address: u64, word: u32, mnemonic: &str, operands: &str) -> Instruction {
+        Instruction::new(
+            address,
+            word.to_le_bytes().to_vec(),
+            mnemonic.to_string(),
+            operands.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_annotate() {
+        let mut instructions = vec![
+            instruction(0x1000, 0x8000_1537, "lui", "a0, 0x80001"),
+            instruction(0x1004, 0x2345_0513, "addi", "a0, a0, 0x234"),
+            instruction(0x1008, 0x0000_1297, "auipc", "t0, 1 # 0x2000 <main>"),
+            instruction(0x100c, 0xff82_80e7, "jalr", "-8(t0)"),
+            // Not contiguous: the value of a0 is forgotten.
+            instruction(0x1014, 0x0045_2583, "lw", "a1, 4(a0)"),
+        ];
+        let mut symbols = SymbolMap::new();
+        symbols.insert(0x2000, "main");
+        symbols.insert(0x8000_1234, "uart_base");
+        let mut annotator = Annotator::new(Xlen::X32, Extensions::rv32gc(), false);
+        annotate_all(&mut annotator, &mut instructions, Some(&symbols));
+        let operands: Vec<&str> = instructions
+            .iter()
+            .map(|instruction| instruction.operands.as_str())
+            .collect();
+        assert_eq!(
+            operands,
+            [
+                "a0, 0x80001",
+                "a0, a0, 0x234 # 0x80001234 <uart_base>",
+                "t0, 1 # 0x2000 <main>",
+                "-8(t0)",
+                "a1, 4(a0)",
+            ]
+        );
+    }
+}
@@ -1,17 +1,86 @@
-use crate::config::{DisasmConfig, OutputConfig};
-use robustone_core::{ArchitectureDispatcher, DisasmError, Instruction};
+use std::thread;
+
+use crate::config::{DisasmConfig, OutputConfig, OutputFormat};
+use crate::constants::{Annotator, annotate_all};
+use crate::json;
+use crate::loader::Target;
+use robustone_aarch64::AArch64Handler;
+use robustone_core::{
+    ArchitectureDispatcher, ArchitectureHandler, DisasmError, HandlerOptions, Instruction,
+    Symbolizer,
+};
 use robustone_riscv::RiscVHandler;
+use robustone_riscv::decoder::Xlen;
+use robustone_riscv::extensions::Extensions;
+
+/// Returns the register width and decoder extensions used for `arch`.
+pub fn riscv_target(arch: &str) -> (Xlen, Extensions) {
+    match arch {
+        "riscv32" => (Xlen::X32, Extensions::rv32gc()),
+        _ => (Xlen::X64, Extensions::rv64gc()),
+    }
+}
+
+/// Returns the register width and decoder extensions for `config`: those
+/// named by its `+isa=` modifier, or the GC profile of its architecture.
+pub fn config_target(config: &DisasmConfig) -> Result<(Xlen, Extensions), DisasmError> {
+    let arch = config.arch_name();
+    let (xlen, extensions) = riscv_target(arch);
+    let Some(isa) = config.isa() else {
+        return Ok((xlen, extensions));
+    };
+    match Extensions::from_isa(isa) {
+        Some((isa_xlen, extensions)) if isa_xlen == xlen => Ok((xlen, extensions)),
+        Some(_) => Err(DisasmError::UnsupportedOption(format!(
+            "ISA {isa} does not match {arch}"
+        ))),
+        None => Err(DisasmError::UnsupportedOption(format!(
+            "unsupported ISA {isa}"
+        ))),
+    }
+}
 
-fn create_dispatcher(arch: &str) -> ArchitectureDispatcher {
+/// Returns whether `arch` names an AArch64 target.
+pub fn is_aarch64(arch: &str) -> bool {
+    matches!(arch, "aarch64" | "aarch64be")
+}
+
+fn create_dispatcher(
+    arch: &str,
+    options: HandlerOptions,
+) -> Result<ArchitectureDispatcher, DisasmError> {
+    if is_aarch64(arch) {
+        return aarch64_dispatcher(arch, options);
+    }
+    let (xlen, extensions) = riscv_target(arch);
+    dispatcher_with_extensions(xlen, extensions, options)
+}
+
+fn aarch64_dispatcher(
+    arch: &str,
+    options: HandlerOptions,
+) -> Result<ArchitectureDispatcher, DisasmError> {
     let mut dispatcher = ArchitectureDispatcher::new();
-    // Create handler based on architecture
-    let handler = match arch {
-        "riscv32" => RiscVHandler::rv32(),
-        "riscv64" | "riscv" => RiscVHandler::rv64(),
-        _ => RiscVHandler::new(),
+    let mut handler = if arch == "aarch64be" {
+        AArch64Handler::big_endian()
+    } else {
+        AArch64Handler::new()
     };
+    handler.set_options(options)?;
+    dispatcher.register(Box::new(handler));
+    Ok(dispatcher)
+}
+
+fn dispatcher_with_extensions(
+    xlen: Xlen,
+    extensions: Extensions,
+    options: HandlerOptions,
+) -> Result<ArchitectureDispatcher, DisasmError> {
+    let mut dispatcher = ArchitectureDispatcher::new();
+    let mut handler = RiscVHandler::with_extensions(xlen, extensions);
+    handler.set_options(options)?;
     dispatcher.register(Box::new(handler));
-    dispatcher
+    Ok(dispatcher)
 }
 
 /// Result of a disassembly operation with additional metadata.
@@ -97,9 +166,55 @@ pub struct DisassemblyEngine {
 impl DisassemblyEngine {
     /// Create a new disassembly engine for the given architecture.
     pub fn new(arch: &str) -> Self {
-        Self {
-            dispatcher: create_dispatcher(arch),
+        Self::with_options(arch, HandlerOptions::default())
+            .expect("default handler options are always supported")
+    }
+
+    /// Create a disassembly engine whose handler uses the given runtime options.
+    pub fn with_options(arch: &str, options: HandlerOptions) -> Result<Self, DisasmError> {
+        Ok(Self {
+            dispatcher: create_dispatcher(arch, options)?,
+        })
+    }
+
+    /// Create a disassembly engine for the target recorded in a loaded image,
+    /// with the decoder extensions named by its ISA string.
+    pub fn for_target(target: &Target, options: HandlerOptions) -> Result<Self, DisasmError> {
+        let (xlen, extensions) = Extensions::from_isa(&target.isa).ok_or_else(|| {
+            DisasmError::UnsupportedArchitecture(format!("unsupported ISA {}", target.isa))
+        })?;
+        Ok(Self {
+            dispatcher: dispatcher_with_extensions(xlen, extensions, options)?,
+        })
+    }
+
+    /// Create a disassembly engine for the architecture of `config`,
+    /// honouring its `+isa=` modifier.
+    pub fn for_config(config: &DisasmConfig, options: HandlerOptions) -> Result<Self, DisasmError> {
+        let arch = config.arch_name();
+        if is_aarch64(arch) {
+            if let Some(isa) = config.isa() {
+                return Err(DisasmError::UnsupportedOption(format!(
+                    "ISA {isa} does not apply to {arch}"
+                )));
+            }
+            return Ok(Self {
+                dispatcher: aarch64_dispatcher(arch, options)?,
+            });
         }
+        let (xlen, extensions) = config_target(config)?;
+        Ok(Self {
+            dispatcher: dispatcher_with_extensions(xlen, extensions, options)?,
+        })
+    }
+
+    /// Install a symbolizer on the handler for `arch`.
+    pub fn set_symbolizer(
+        &mut self,
+        arch: &str,
+        symbolizer: Box<dyn Symbolizer>,
+    ) -> Result<(), DisasmError> {
+        self.dispatcher.set_symbolizer(arch, Some(symbolizer))
     }
 
     /// Create a new engine instance for riscv64 (default).
@@ -108,51 +223,161 @@ impl DisassemblyEngine {
     }
 
     /// Disassemble bytes using the provided configuration.
+    ///
+    /// With `config.jobs` above one, buffers of at least [`PARALLEL_CHUNK`]
+    /// bytes per job are split into instruction-aligned chunks decoded on that
+    /// many threads; see [`disassemble_split`](Self::disassemble_split).
+    ///
+    /// With `config.recursive` set, only the bytes reached from the start
+    /// address and the extra entry points are decoded and the rest is shown
+    /// as `.byte` data; see [`crate::recursive`].
+    ///
+    /// With `config.constants` set, instructions completing a `lui`/`auipc`
+    /// value are annotated with it; see [`crate::constants`].
     pub fn disassemble(&self, config: &DisasmConfig) -> Result<DisassemblyResult, DisasmError> {
+        let mut result = if let Some(entries) = &config.recursive {
+            config
+                .validate_for_disassembly()
+                .map_err(|e| DisasmError::DecodingError(e.to_string()))?;
+            let mut starts = vec![config.start_address];
+            starts.extend(entries);
+            let traversal = self.disassemble_recursive(
+                &config.hex_bytes,
+                config.arch_name(),
+                config.start_address,
+                &starts,
+            );
+            traversal.into_result(config)
+        } else {
+            let len = config.hex_bytes.len();
+            let jobs = config.jobs.min(len / PARALLEL_CHUNK).max(1);
+            // Chunks start on instruction boundaries: 4 bytes for AArch64,
+            // 2 bytes for RISC-V with compressed instructions.
+            let align = if is_aarch64(config.arch_name()) { 4 } else { 2 };
+            let starts: Vec<usize> = (0..jobs)
+                .map(|job| (len * job / jobs) & !(align - 1))
+                .collect();
+            self.disassemble_split(config, &starts)?
+        };
+        if config.constants {
+            let mut annotator = Annotator::for_config(config)?;
+            annotate_all(
+                &mut annotator,
+                &mut result.instructions,
+                config.symbols.as_ref(),
+            );
+        }
+        Ok(result)
+    }
+
+    /// Disassemble bytes with one thread per chunk, the chunks starting at
+    /// the given offsets into the input, such as known function starts.
+    ///
+    /// A start may fall inside an instruction, most often in the middle of
+    /// a 4-byte instruction next to compressed ones, and the thread then
+    /// decodes a wrong instruction stream. The chunks are therefore joined
+    /// in address order: from the end of the previous chunk, instructions
+    /// are decoded again one at a time until they meet an instruction
+    /// boundary of the next chunk, from which both streams agree. The
+    /// result is always that of a single-threaded pass.
+    pub fn disassemble_split(
+        &self,
+        config: &DisasmConfig,
+        starts: &[usize],
+    ) -> Result<DisassemblyResult, DisasmError> {
         config
             .validate_for_disassembly()
             .map_err(|e| DisasmError::DecodingError(e.to_string()))?;
 
+        let len = config.hex_bytes.len();
+        let mut starts: Vec<usize> = starts
+            .iter()
+            .copied()
+            .filter(|&start| start < len)
+            .collect();
+        starts.push(0);
+        starts.sort_unstable();
+        starts.dedup();
+        let stops: Vec<usize> = starts.iter().skip(1).copied().chain([len]).collect();
+
+        let runs: Vec<Vec<(usize, Step)>> = if starts.len() == 1 {
+            vec![self.decode_range(config, 0, len)]
+        } else {
+            thread::scope(|scope| {
+                let workers: Vec<_> = starts
+                    .iter()
+                    .zip(&stops)
+                    .map(|(&start, &stop)| {
+                        scope.spawn(move || self.decode_range(config, start, stop))
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .map(|worker| worker.join().expect("disassembly worker panicked"))
+                    .collect()
+            })
+        };
+
         let mut result =
             DisassemblyResult::new(config.start_address, config.arch_name().to_string());
         let mut offset = 0;
-        let mut current_address = config.start_address;
+        for (mut run, stop) in runs.into_iter().zip(stops) {
+            loop {
+                if let Ok(index) = run.binary_search_by_key(&offset, |(offset, _)| *offset) {
+                    for (_, step) in run.drain(index..) {
+                        offset += step.apply(&mut result)?;
+                    }
+                    break;
+                }
+                if offset >= stop {
+                    break;
+                }
+                for (_, step) in self.decode_range(config, offset, offset + 1) {
+                    offset += step.apply(&mut result)?;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Decode from `start` until an instruction reaches `stop` or decoding
+    /// fails, returning each step with its offset.
+    fn decode_range(&self, config: &DisasmConfig, start: usize, stop: usize) -> Vec<(usize, Step)> {
         let arch_name = config.arch_name();
+        let mut steps = Vec::new();
+        let mut offset = start;
 
-        while offset < config.hex_bytes.len() {
+        while offset < stop && offset < config.hex_bytes.len() {
             let slice = &config.hex_bytes[offset..];
+            let current_address = config.start_address.saturating_add(offset as u64);
 
-            match self
+            let step = match self
                 .dispatcher
                 .disassemble_bytes(slice, arch_name, current_address)
             {
-                Ok((instruction, size)) => {
-                    if size == 0 {
-                        return Err(DisasmError::DecodingError(
-                            "Decoder returned zero-length instruction".to_string(),
-                        ));
-                    }
-
-                    result.add_instruction(instruction);
-                    offset += size;
-                    current_address = current_address.saturating_add(size as u64);
+                Ok((_, 0)) => Step::Failed(DisasmError::DecodingError(
+                    "Decoder returned zero-length instruction".to_string(),
+                )),
+                Ok((instruction, size)) => Step::Decoded(instruction, size),
+                // Skip the problematic byte and continue
+                Err(err) if config.skip_data => Step::Skipped(err),
+                Err(err) => Step::Failed(err),
+            };
+            let size = match &step {
+                Step::Decoded(_, size) => *size,
+                Step::Skipped(_) => 1,
+                Step::Failed(_) => {
+                    steps.push((offset, step));
+                    break;
                 }
-                Err(err) => {
-                    if config.skip_data {
-                        // Skip the problematic byte and continue
-                        result.add_error(err.to_string());
-                        offset += 1;
-                        current_address += 1;
-                    } else {
-                        return Err(err);
-                    }
-                }
-            }
+            };
+            steps.push((offset, step));
+            offset += size;
         }
 
-        Ok(result)
+        steps
     }
-
     /// Disassemble a single instruction at the given address.
     pub fn disassemble_single(
         &self,
@@ -164,6 +389,37 @@ impl DisassemblyEngine {
     }
 }
 
+/// Smallest input, in bytes per job, that [`DisassemblyEngine::disassemble`]
+/// splits across threads.
+pub const PARALLEL_CHUNK: usize = 64 * 1024;
+
+/// One decoding step of [`DisassemblyEngine::decode_range`].
+enum Step {
+    /// An instruction and its size.
+    Decoded(Instruction, usize),
+    /// An undecodable byte skipped in SKIPDATA mode.
+    Skipped(DisasmError),
+    /// Decoding failed and stops here.
+    Failed(DisasmError),
+}
+
+impl Step {
+    /// Record the step in `result` and return the bytes it covers.
+    fn apply(self, result: &mut DisassemblyResult) -> Result<usize, DisasmError> {
+        match self {
+            Step::Decoded(instruction, size) => {
+                result.add_instruction(instruction);
+                Ok(size)
+            }
+            Step::Skipped(err) => {
+                result.add_error(err.to_string());
+                Ok(1)
+            }
+            Step::Failed(err) => Err(err),
+        }
+    }
+}
+
 /// Formatter for disassembly output with multiple display modes.
 pub struct DisassemblyFormatter {
     output_config: OutputConfig,
@@ -177,6 +433,17 @@ impl DisassemblyFormatter {
 
     /// Format the disassembly result for display.
     pub fn format(&self, result: &DisassemblyResult) -> String {
+        match self.output_config.format {
+            // Assembler output is built from the input bytes rather than a
+            // result; see `crate::asm`.
+            OutputFormat::Text | OutputFormat::Asm => self.format_text(result),
+            OutputFormat::Json => json::format_json(result),
+            OutputFormat::JsonLines => json::format_json_lines(result),
+        }
+    }
+
+    /// Format the disassembly result as cstool-style text.
+    fn format_text(&self, result: &DisassemblyResult) -> String {
         let mut output = String::new();
 
         if result.instructions.is_empty() {
@@ -189,6 +456,9 @@ impl DisassemblyFormatter {
             let formatted = self.format_instruction(instruction, current_address);
             output.push_str(&formatted);
             output.push('\n');
+            if self.output_config.show_detail {
+                output.push_str(&self.detail_listing(instruction));
+            }
             current_address += instruction.size as u64;
         }
 
@@ -200,6 +470,32 @@ impl DisassemblyFormatter {
         output
     }
 
+    /// Renders cstool's detail block, naming the real instruction behind an
+    /// alias after the `ID:` line when `-r` is given.
+    fn detail_listing(&self, instruction: &Instruction) -> String {
+        let name = match &instruction.detail {
+            Some(detail) if detail.architecture_name() == "aarch64" => {
+                robustone_aarch64::insn::insn_name(instruction.id)
+            }
+            _ => robustone_riscv::insn::insn_name(instruction.id),
+        };
+        let mut listing = instruction.detail_listing(name);
+        let real = instruction
+            .detail
+            .as_ref()
+            .and_then(|detail| detail.real_instruction())
+            .filter(|_| self.output_config.show_real);
+        if let Some((mnemonic, operands)) = real {
+            let at = if listing.starts_with("\tID: ") {
+                listing.find('\n').map_or(0, |end| end + 1)
+            } else {
+                0
+            };
+            listing.insert_str(at, &format!("\tReal instruction: {mnemonic} {operands}\n"));
+        }
+        listing
+    }
+
     /// Format a single instruction.
     fn format_instruction(&self, instr: &Instruction, address: u64) -> String {
         let address_str = format!("{address:2x}");
@@ -238,8 +534,7 @@ impl DisassemblyFormatter {
 /// Convenience functions for backward compatibility.
 /// Disassembles the supplied byte tokens using the provided configuration.
 pub fn process_input(config: &DisasmConfig) -> Result<DisassemblyResult, DisasmError> {
-    let arch = config.arch_name();
-    let engine = DisassemblyEngine::new(arch);
+    let engine = DisassemblyEngine::for_config(config, config.handler_options())?;
     engine.disassemble(config)
 }
 
@@ -277,4 +572,252 @@ mod tests {
         assert_eq!(result.error_count(), 1);
         assert!(!result.is_successful());
     }
+
+    #[test]
+    fn test_handler_options_reach_handler() {
+        use crate::command::DisplayOptions;
+
+        let display = DisplayOptions {
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            unsigned_immediate: true,
+            format: crate::config::OutputFormat::Text,
+        };
+        let engine = DisassemblyEngine::with_options("riscv32", display.handler_options()).unwrap();
+        let (instruction, _) = engine
+            .disassemble_single(&[0x13, 0x01, 0x01, 0xff], "riscv32", 0)
+            .unwrap();
+        assert_eq!(instruction.operands, "sp, sp, 0xfffffff0");
+        assert!(instruction.detail.is_none());
+    }
+
+    #[test]
+    fn test_detail_listing() {
+        use crate::command::DisplayOptions;
+
+        let display = DisplayOptions {
+            detailed: true,
+            alias_regs: false,
+            real_detail: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+        };
+        let engine = DisassemblyEngine::with_options("riscv64", display.handler_options()).unwrap();
+        let mut result = DisassemblyResult::new(0x1000, "riscv64".to_string());
+        let (instruction, _) = engine
+            .disassemble_single(&[0x83, 0x35, 0x81, 0xff], "riscv64", 0x1000)
+            .unwrap();
+        result.add_instruction(instruction);
+
+        let formatter = DisassemblyFormatter::new(OutputConfig::from_display_options(&display));
+        assert_eq!(
+            formatter.format(&result),
+            "1000  83 35 81 ff  ld\ta1, -8(sp)\n\
+             \tID: 210 (ld)\n\
+             \top_count: 2\n\
+             \t\toperands[0].type: REG = a1\n\
+             \t\toperands[0].access: WRITE\n\
+             \t\toperands[1].type: MEM\n\
+             \t\t\toperands[1].mem.base: REG = sp\n\
+             \t\t\toperands[1].mem.disp: 0xfffffffffffffff8\n\
+             \t\toperands[1].access: READ\n\
+             \tRegisters read: sp\n\
+             \tRegisters modified: a1\n\n"
+        );
+    }
+
+    #[test]
+    fn test_aarch64_detail_listing() {
+        use crate::command::DisplayOptions;
+
+        let display = DisplayOptions {
+            detailed: true,
+            alias_regs: false,
+            real_detail: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+        };
+        let engine = DisassemblyEngine::with_options("aarch64", display.handler_options()).unwrap();
+        let mut result = DisassemblyResult::new(0, "aarch64".to_string());
+        // stp x29, x30, [sp, #-0x10]! ; ldr x0, [x0, x10, lsl #3]
+        for word in [[0xfd, 0x7b, 0xbf, 0xa9], [0x00, 0x78, 0x6a, 0xf8]] {
+            let (instruction, _) = engine.disassemble_single(&word, "aarch64", 0).unwrap();
+            result.add_instruction(instruction);
+        }
+
+        let formatter = DisassemblyFormatter::new(OutputConfig::from_display_options(&display));
+        assert_eq!(
+            formatter.format(&result),
+            " 0  fd 7b bf a9  stp\tx29, x30, [sp, #-0x10]!\n\
+             \tID: 761 (stp)\n\
+             \top_count: 3\n\
+             \t\toperands[0].type: REG = x29\n\
+             \t\toperands[0].access: READ\n\
+             \t\toperands[1].type: REG = x30\n\
+             \t\toperands[1].access: READ\n\
+             \t\toperands[2].type: MEM\n\
+             \t\t\toperands[2].mem.base: REG = sp\n\
+             \t\t\toperands[2].mem.disp: -0x10\n\
+             \t\toperands[2].access: WRITE\n\
+             \tWrite-back: True\n\
+             \tRegisters read: x29 x30 sp\n\
+             \tRegisters modified: sp\n\n\
+             \x204  00 78 6a f8  ldr\tx0, [x0, x10, lsl #3]\n\
+             \tID: 393 (ldr)\n\
+             \top_count: 2\n\
+             \t\toperands[0].type: REG = x0\n\
+             \t\toperands[0].access: WRITE\n\
+             \t\toperands[1].type: MEM\n\
+             \t\t\toperands[1].mem.base: REG = x0\n\
+             \t\t\toperands[1].mem.index: REG = x10\n\
+             \t\toperands[1].access: READ\n\
+             \tRegisters read: x0 x10\n\
+             \tRegisters modified: x0\n\n"
+        );
+    }
+
+    #[test]
+    fn test_real_detail_listing() {
+        use crate::command::DisplayOptions;
+
+        // beqz a0, 8 is an alias of beq a0, zero, 8.
+        let listing = |real_detail: bool| {
+            let display = DisplayOptions {
+                detailed: !real_detail,
+                alias_regs: false,
+                real_detail,
+                unsigned_immediate: false,
+                format: OutputFormat::Text,
+            };
+            let engine =
+                DisassemblyEngine::with_options("riscv64", display.handler_options()).unwrap();
+            let mut result = DisassemblyResult::new(0, "riscv64".to_string());
+            let (instruction, _) = engine
+                .disassemble_single(&[0x63, 0x04, 0x05, 0x00], "riscv64", 0)
+                .unwrap();
+            result.add_instruction(instruction);
+            DisassemblyFormatter::new(OutputConfig::from_display_options(&display)).format(&result)
+        };
+
+        let alias = listing(false);
+        assert!(alias.starts_with(" 0  63 04 05 00  beqz\ta0, 8\n\tID: 80 (beq)\n"));
+        assert!(alias.contains("\top_count: 2\n"));
+        assert!(!alias.contains("REG = zero"));
+
+        let real = listing(true);
+        assert!(
+            real.contains("\tID: 80 (beq)\n\tReal instruction: beq a0, zero, 8\n\top_count: 3\n")
+        );
+        assert!(real.contains("\t\toperands[1].type: REG = zero\n"));
+    }
+
+    #[test]
+    fn test_split_matches_sequential() {
+        use crate::arch::ArchitectureSpec;
+        use crate::command::DisplayOptions;
+
+        // Pseudo-random mix of compressed and full-size encodings, with
+        // some invalid ones for SKIPDATA.
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut bytes = Vec::new();
+        while bytes.len() < 4096 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let word = seed as u32;
+            if word & 0b11 == 0b11 {
+                bytes.extend_from_slice(&word.to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&(word as u16).to_le_bytes());
+            }
+        }
+        let config = |skip_data: bool| DisasmConfig {
+            arch_spec: ArchitectureSpec::parse("riscv64").unwrap(),
+            hex_bytes: bytes.clone(),
+            start_address: 0x8000_0000,
+            display_options: DisplayOptions {
+                detailed: false,
+                alias_regs: false,
+                real_detail: false,
+                unsigned_immediate: false,
+                format: OutputFormat::Text,
+            },
+            skip_data,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+        };
+        let text = |result: &DisassemblyResult| {
+            DisassemblyFormatter::new(OutputConfig::minimal()).format(result)
+        };
+
+        let engine = DisassemblyEngine::new("riscv64");
+        let skipping = config(true);
+        let expected = engine.disassemble(&skipping).unwrap();
+        assert!(!expected.errors.is_empty());
+        for starts in [
+            vec![2, 6, 10, 1000, 1002],
+            (0..4096).step_by(2).collect(),
+            (0..4096).step_by(7).collect(),
+            vec![4094, 17, 4000],
+        ] {
+            let result = engine.disassemble_split(&skipping, &starts).unwrap();
+            assert_eq!(text(&result), text(&expected), "starts {starts:?}");
+            assert_eq!(result.errors, expected.errors);
+            assert_eq!(result.bytes_processed, expected.bytes_processed);
+        }
+
+        // Without SKIPDATA the first invalid encoding fails either way.
+        let strict = config(false);
+        let expected = engine.disassemble(&strict).unwrap_err().to_string();
+        let result = engine.disassemble_split(&strict, &[2, 100, 3000]);
+        assert_eq!(result.unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_symbolizer_reaches_handler() {
+        let symbols = robustone_core::SymbolMap::parse("0000000000001040 T memcpy").unwrap();
+        let mut engine = DisassemblyEngine::new("riscv64");
+        engine.set_symbolizer("riscv64", Box::new(symbols)).unwrap();
+        let (instruction, _) = engine
+            .disassemble_single(&[0xef, 0x00, 0x00, 0x04], "riscv64", 0x1000)
+            .unwrap();
+        assert_eq!(instruction.operands, "<memcpy>");
+    }
+
+    #[test]
+    fn test_aarch64_workflow() {
+        use crate::command::Cli;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["robustone", "aarch64be", "d503201fd65f03c0"]).unwrap();
+        let config = DisasmConfig::config_from_cli(&cli).unwrap();
+        let result = process_input(&config).unwrap();
+        let mnemonics: Vec<&str> = result
+            .instructions
+            .iter()
+            .map(|instruction| instruction.mnemonic.as_str())
+            .collect();
+        assert_eq!(mnemonics, ["nop", "ret"]);
+
+        // `+be` selects the big-endian decoder like `aarch64be`.
+        for arch in ["aarch64be", "aarch64+be"] {
+            let cli = Cli::try_parse_from(["robustone", arch, "a9bf7bfd"]).unwrap();
+            let config = DisasmConfig::config_from_cli(&cli).unwrap();
+            assert_eq!(config.arch_name(), "aarch64be");
+            let result = process_input(&config).unwrap();
+            let instruction = &result.instructions[0];
+            assert_eq!(instruction.mnemonic, "stp", "{arch}");
+            assert_eq!(instruction.operands, "x29, x30, [sp, #-0x10]!", "{arch}");
+        }
+
+        // The analysis passes only read RISC-V.
+        let cli = Cli::try_parse_from(["robustone", "aarch64", "1f2003d5", "--xrefs"]).unwrap();
+        let config = DisasmConfig::config_from_cli(&cli).unwrap();
+        assert!(config.validate_for_disassembly().is_err());
+    }
 }
@@ -3,11 +3,18 @@
 //! This module wires together argument parsing, configuration building,
 //! and the actual disassembly pipeline exposed through the CLI.
 
+use crate::asm::format_asm;
+use crate::batch::run_batch;
+use crate::cfg;
 use crate::command::Cli;
-use crate::config::{DisasmConfig, OutputConfig};
+use crate::config::{DisasmConfig, OutputConfig, OutputFormat};
 use crate::disasm::{DisassemblyEngine, DisassemblyFormatter};
 use crate::error::{CliError, Result};
+use crate::json;
+use crate::listing::format_image;
+use crate::repl;
 use crate::version_info::print_version_info;
+use crate::xrefs;
 
 use clap::Parser;
 
@@ -38,8 +45,73 @@ impl CliExecutor {
             return Ok(());
         }
 
+        // Interactive mode keeps its own loop
+        if cli.is_repl() {
+            let config = DisasmConfig::from_validated_config(cli.validate_repl()?)?;
+            return repl::run(config);
+        }
+
+        // Batch mode disassembles a whole manifest
+        if cli.is_batch() {
+            let (manifest, options) = cli.validate_batch()?;
+            let output = run_batch(&manifest, &options);
+            print!("{}", output.text);
+            if output.failures > 0 {
+                return Err(CliError::Disassembly(format!(
+                    "{} of {} inputs could not be disassembled",
+                    output.failures, output.inputs
+                )));
+            }
+            return Ok(());
+        }
+
         // Validate and process the command-line arguments
-        let validated_config = cli.validate()?;
+        let mut validated_config = cli.validate()?;
+
+        // Object files carry their own regions, target and symbols
+        if let Some(image) = validated_config.image.take() {
+            if validated_config.xrefs {
+                let format = validated_config.format;
+                let config = DisasmConfig::for_image(validated_config, &image)?;
+                let (index, symbols) = xrefs::scan_image(&image, &config)
+                    .map_err(|e| CliError::Disassembly(e.to_string()))?;
+                print!("{}", xrefs::format_report(&index, Some(&symbols), format));
+                return Ok(());
+            }
+            if let Some(format) = validated_config.cfg {
+                let config = DisasmConfig::for_image(validated_config, &image)?;
+                let graph = cfg::format_image(&image, &config, format)
+                    .map_err(|e| CliError::Disassembly(e.to_string()))?;
+                print!("{graph}");
+                return Ok(());
+            }
+            match validated_config.format {
+                OutputFormat::Text => {}
+                OutputFormat::Json | OutputFormat::JsonLines => {
+                    let config = DisasmConfig::for_image(validated_config, &image)?;
+                    let documents = json::format_image(&image, &config)
+                        .map_err(|e| CliError::Disassembly(e.to_string()))?;
+                    print!("{documents}");
+                    return Ok(());
+                }
+                OutputFormat::Asm => {
+                    return Err(CliError::validation(
+                        "format",
+                        "object file listings do not support --format asm",
+                    ));
+                }
+            }
+            let name = cli
+                .file
+                .as_deref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default();
+            let config = DisasmConfig::for_image(validated_config, &image)?;
+            let listing = format_image(&name, &image, &config)
+                .map_err(|e| CliError::Disassembly(e.to_string()))?;
+            print!("{listing}");
+            return Ok(());
+        }
 
         // Create disassembly configuration
         let disasm_config = DisasmConfig::from_validated_config(validated_config)?;
@@ -57,9 +129,38 @@ impl CliExecutor {
         // Validate the configuration for disassembly
         config.validate_for_disassembly()?;
 
+        if config.xrefs {
+            let index = xrefs::scan(config).map_err(|e| CliError::Disassembly(e.to_string()))?;
+            let report = xrefs::format_report(
+                &index,
+                config.symbols.as_ref(),
+                config.display_options.format,
+            );
+            print!("{report}");
+            return Ok(());
+        }
+
+        if config.display_options.format == OutputFormat::Asm {
+            let source = format_asm(config).map_err(|e| CliError::Disassembly(e.to_string()))?;
+            print!("{source}");
+            return Ok(());
+        }
+
         // Create engine with correct architecture
         let arch = config.arch_name();
-        let engine = DisassemblyEngine::new(arch);
+        let mut engine = DisassemblyEngine::for_config(config, config.handler_options())
+            .map_err(|e| CliError::Disassembly(e.to_string()))?;
+        if let Some(symbols) = &config.symbols {
+            engine
+                .set_symbolizer(arch, Box::new(symbols.clone()))
+                .map_err(|e| CliError::Disassembly(e.to_string()))?;
+        }
+
+        if let Some(format) = config.cfg {
+            let graph = cfg::build(&engine, config);
+            print!("{}", graph.format(format, config.symbols.as_ref()));
+            return Ok(());
+        }
 
         // Perform the disassembly
         let result = engine
@@ -169,4 +270,17 @@ mod tests {
         let _executor = CliExecutor::default();
         // Basic test that default executor works
     }
+
+    #[test]
+    fn test_batch_failures_are_errors() {
+        let manifest = std::env::temp_dir().join("robustone-batch-failures.txt");
+        std::fs::write(&manifest, "13055000\n1305\n").unwrap();
+        let cli = Cli::parse_from(["robustone", "batch", manifest.to_str().unwrap(), "riscv64"]);
+        let result = CliExecutor::new().execute_cli(cli);
+        std::fs::remove_file(&manifest).unwrap();
+        assert!(matches!(
+            result,
+            Err(CliError::Disassembly(message)) if message == "1 of 2 inputs could not be disassembled"
+        ));
+    }
 }
@@ -0,0 +1,386 @@
+//! Machine code input: hex strings, hex dumps, raw files and stdin.
+//!
+//! Hex text is accepted in the formats people usually have at hand:
+//!
+//! - plain hex, optionally split into words and `0x`-prefixed or
+//!   comma-separated (`"13055000"`, `"0x13, 0x05, 0x50, 0x00"`), with bytes
+//!   in the order written;
+//! - `xxd` (`00000000: 1305 5000  ..P.`);
+//! - `hexdump -C` (`00000000  13 05 50 00  |..P.|`);
+//! - `od -x` (`0000000 0513 0050`), whose words are little-endian.
+//!
+//! The format is detected from the first line. Dumps are reassembled from
+//! their offsets, so `*` lines for repeated data and the trailing length
+//! line are honoured.
+
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::{CliError, Result};
+
+/// A recognised hex text layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Bare hex digits or `0x` tokens.
+    Plain,
+    /// `xxd` output.
+    Xxd,
+    /// `hexdump -C` output.
+    HexdumpCanonical,
+    /// `od -x` (or `od -t x1`/`x4`) output.
+    Od,
+}
+
+impl DumpFormat {
+    /// Detects the format of `text` from its first non-empty line.
+    pub fn detect(text: &str) -> Self {
+        let Some(line) = text.lines().map(str::trim).find(|line| !line.is_empty()) else {
+            return Self::Plain;
+        };
+        let first = line.split_whitespace().next().unwrap_or_default();
+        let is_offset = |digits: &str| !digits.is_empty() && is_hex(digits);
+        if line.contains('|') && is_offset(first) {
+            Self::HexdumpCanonical
+        } else if first.strip_suffix(':').is_some_and(is_offset) {
+            Self::Xxd
+        } else if first.len() == 7 && first.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+            // Plain hex words have an even number of digits; od offsets have 7.
+            Self::Od
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// Parses hex text in any supported format into bytes.
+pub fn parse_hex_text(text: &str) -> Result<Vec<u8>> {
+    let bytes = match DumpFormat::detect(text) {
+        DumpFormat::Plain => parse_plain(text)?,
+        format => parse_dump(text, format)?,
+    };
+    if bytes.is_empty() {
+        return Err(CliError::validation("hex_code", "Empty hex code provided"));
+    }
+    Ok(bytes)
+}
+
+/// Reads raw machine code from `path`, or from stdin when `path` is `-`.
+pub fn read_binary(path: &Path) -> Result<Vec<u8>> {
+    let bytes = if path == Path::new("-") {
+        let mut bytes = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|e| CliError::Io(format!("stdin: {e}")))?;
+        bytes
+    } else {
+        std::fs::read(path).map_err(|e| CliError::Io(format!("{}: {e}", path.display())))?
+    };
+    if bytes.is_empty() {
+        return Err(CliError::validation("file", "Input file is empty"));
+    }
+    Ok(bytes)
+}
+
+/// Reads hex text from stdin.
+pub fn read_stdin_text() -> Result<String> {
+    let mut text = String::new();
+    std::io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|e| CliError::Io(format!("stdin: {e}")))?;
+    Ok(text)
+}
+
+/// Keeps `length` bytes (or everything) starting at `offset`.
+pub fn slice(mut bytes: Vec<u8>, offset: Option<u64>, length: Option<u64>) -> Result<Vec<u8>> {
+    let offset = offset.unwrap_or(0);
+    let start = usize::try_from(offset)
+        .ok()
+        .filter(|&start| start < bytes.len())
+        .ok_or_else(|| {
+            CliError::validation(
+                "offset",
+                format!("Offset {offset} is beyond the {} input bytes", bytes.len()),
+            )
+        })?;
+    bytes.drain(..start);
+    if let Some(length) = length {
+        bytes.truncate(usize::try_from(length).unwrap_or(usize::MAX));
+    }
+    if bytes.is_empty() {
+        return Err(CliError::validation("length", "No bytes selected"));
+    }
+    Ok(bytes)
+}
+
+/// Parses a byte count given in decimal or as `0x` hex.
+pub fn parse_size(input: &str) -> std::result::Result<u64, String> {
+    let input = input.trim();
+    let parsed = match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => input.parse(),
+    };
+    parsed.map_err(|_| format!("invalid byte count: {input}"))
+}
+
+/// Parses hex digits, ignoring `0x` prefixes and separators, except that `0x`
+/// tokens (`0x13, 0x5`) are read one byte each when they are comma-separated
+/// or none has more than two digits.
+fn parse_plain(text: &str) -> Result<Vec<u8>> {
+    let invalid = |message: String| CliError::validation("hex_code", message);
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+    if tokens.is_empty() {
+        return Err(invalid("Empty hex code provided".to_string()));
+    }
+    let prefixed: Option<Vec<&str>> = tokens
+        .iter()
+        .map(|token| {
+            token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+        })
+        .collect();
+    if let Some(digits) = prefixed
+        && (text.contains(',') || digits.iter().all(|digits| digits.len() <= 2))
+    {
+        return digits
+            .into_iter()
+            .map(|digits| {
+                u8::from_str_radix(digits, 16)
+                    .map_err(|_| invalid(format!("Invalid hex byte: 0x{digits}")))
+            })
+            .collect();
+    }
+
+    let digits: String = tokens
+        .iter()
+        .map(|token| {
+            token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+                .unwrap_or(token)
+        })
+        .collect();
+    if digits.is_empty() {
+        return Err(invalid("Empty hex code provided".to_string()));
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err(invalid(format!(
+            "Hex code has an odd number of digits ({})",
+            digits.len()
+        )));
+    }
+    hex::decode(&digits).map_err(|e| invalid(format!("Invalid hex code: {e}")))
+}
+
+/// Reassembles an offset-annotated dump.
+fn parse_dump(text: &str, format: DumpFormat) -> Result<Vec<u8>> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let radix = match format {
+        DumpFormat::Od => od_offset_radix(&lines),
+        _ => 16,
+    };
+
+    let mut bytes = Vec::new();
+    let mut previous: Vec<u8> = Vec::new();
+    let mut repeat = false;
+    for (index, line) in lines.iter().enumerate() {
+        let error =
+            |message: &str| CliError::parse("hex dump", format!("line {}: {message}", index + 1));
+        if *line == "*" {
+            repeat = true;
+            continue;
+        }
+        let (offset, data) = split_dump_line(line, format);
+        let offset = u64::from_str_radix(offset, radix)
+            .ok()
+            .and_then(|offset| usize::try_from(offset).ok())
+            .ok_or_else(|| error("invalid offset"))?;
+        if repeat {
+            if previous.is_empty() {
+                return Err(error("`*` without a preceding line"));
+            }
+            while bytes.len() < offset {
+                let missing = offset - bytes.len();
+                bytes.extend_from_slice(&previous[..missing.min(previous.len())]);
+            }
+            repeat = false;
+        }
+        if offset > bytes.len() {
+            return Err(error("offset skips over missing data"));
+        }
+        // od pads the last line; the final offset gives the real length.
+        bytes.truncate(offset);
+
+        previous.clear();
+        for token in data.split_whitespace() {
+            let word = hex::decode(token).map_err(|_| error("invalid hex data"))?;
+            if format == DumpFormat::Od {
+                previous.extend(word.iter().rev());
+            } else {
+                previous.extend_from_slice(&word);
+            }
+        }
+        bytes.extend_from_slice(&previous);
+    }
+    Ok(bytes)
+}
+
+/// Splits a dump line into its offset and its hex data columns.
+fn split_dump_line(line: &str, format: DumpFormat) -> (&str, &str) {
+    let (offset, rest) = match format {
+        DumpFormat::Xxd => line.split_once(':').unwrap_or((line, "")),
+        _ => line.split_once(char::is_whitespace).unwrap_or((line, "")),
+    };
+    let data = match format {
+        // The ASCII column follows the hex columns after two spaces.
+        DumpFormat::Xxd => rest
+            .strip_prefix(' ')
+            .unwrap_or(rest)
+            .split("  ")
+            .next()
+            .unwrap_or_default(),
+        DumpFormat::HexdumpCanonical => rest.split('|').next().unwrap_or_default(),
+        _ => rest,
+    };
+    (offset, data)
+}
+
+/// Infers the offset radix of `od` output (octal by default, `-A x` or
+/// `-A d` otherwise) from the offset following the first data line.
+fn od_offset_radix(lines: &[&str]) -> u32 {
+    let (Some(first), Some(second)) = (lines.first(), lines.get(1)) else {
+        return 8;
+    };
+    let (_, data) = split_dump_line(first, DumpFormat::Od);
+    let length: usize = data.split_whitespace().map(|token| token.len() / 2).sum();
+    let (offset, _) = split_dump_line(second, DumpFormat::Od);
+    [8, 16, 10]
+        .into_iter()
+        .find(|&radix| u64::from_str_radix(offset, radix).ok() == Some(length as u64))
+        .unwrap_or(8)
+}
+
+fn is_hex(text: &str) -> bool {
+    text.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: [u8; 10] = [0x13, 0x05, 0x50, 0x00, 0x67, 0x80, 0x00, 0x00, 0x01, 0x00];
+
+    #[test]
+    fn test_plain_hex_and_lists() {
+        assert_eq!(
+            parse_hex_text("13055000 67800000 0100").unwrap(),
+            CODE.to_vec()
+        );
+        assert_eq!(
+            parse_hex_text("0x13, 0x05, 0x50, 0x00,\n0x67, 0x80, 0x00, 0x00, 0x01, 0x00").unwrap(),
+            CODE.to_vec()
+        );
+        // Unpadded bytes in a `0x` list stay one byte each.
+        assert_eq!(
+            parse_hex_text("0x13, 0x5, 0x50, 0x0, 0x67, 0x80, 0x0, 0x0, 0x1, 0x0").unwrap(),
+            CODE.to_vec()
+        );
+        assert!(parse_hex_text("0x13, 0x5, 0x50, 0x100").is_err());
+        assert!(parse_hex_text("1305500").is_err());
+        assert!(parse_hex_text("  \n").is_err());
+        assert!(parse_hex_text("13zz").is_err());
+    }
+
+    #[test]
+    fn test_xxd() {
+        let dump = "00000000: 1305 5000 6780 0000 0100                 ..P.g.....\n";
+        assert_eq!(DumpFormat::detect(dump), DumpFormat::Xxd);
+        assert_eq!(parse_hex_text(dump).unwrap(), CODE.to_vec());
+    }
+
+    #[test]
+    fn test_hexdump_canonical_with_repeats() {
+        let dump = "\
+00000000  13 05 50 00 67 80 00 00  01 00 00 00 00 00 00 00  |..P.g...........|
+00000010  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+*
+00000040  13 05 50 00                                       |..P.|
+00000044
+";
+        assert_eq!(DumpFormat::detect(dump), DumpFormat::HexdumpCanonical);
+        let bytes = parse_hex_text(dump).unwrap();
+        assert_eq!(bytes.len(), 0x44);
+        assert_eq!(bytes[..10], CODE);
+        assert!(bytes[10..0x40].iter().all(|&b| b == 0));
+        assert_eq!(bytes[0x40..], CODE[..4]);
+    }
+
+    #[test]
+    fn test_od() {
+        // od -x pads the odd trailing byte; the final offset trims it.
+        let dump = "0000000 0513 0050 8067 0000 0001\n0000011\n";
+        assert_eq!(DumpFormat::detect(dump), DumpFormat::Od);
+        assert_eq!(parse_hex_text(dump).unwrap(), CODE[..9].to_vec());
+
+        let hex_offsets = "0000000 0513 0050 8067 0000 0001\n000000a\n";
+        assert_eq!(parse_hex_text(hex_offsets).unwrap(), CODE.to_vec());
+    }
+
+    #[test]
+    fn test_slice() {
+        let bytes = CODE.to_vec();
+        assert_eq!(
+            slice(bytes.clone(), Some(4), Some(4)).unwrap(),
+            CODE[4..8].to_vec()
+        );
+        assert_eq!(slice(bytes.clone(), None, None).unwrap(), CODE.to_vec());
+        assert!(slice(bytes, Some(10), None).is_err());
+        assert_eq!(parse_size("0x10"), Ok(16));
+        assert_eq!(parse_size("16"), Ok(16));
+        assert!(parse_size("ten").is_err());
+    }
+
+    #[test]
+    fn test_cli_file_input() {
+        use crate::command::Cli;
+        use clap::Parser;
+
+        let path = std::env::temp_dir().join(format!("robustone-input-{}.bin", std::process::id()));
+        std::fs::write(&path, CODE).unwrap();
+        let file = path.to_str().unwrap();
+
+        // With --file the first positional after the architecture is the address.
+        let cli = Cli::try_parse_from([
+            "robustone",
+            "riscv32",
+            "--file",
+            file,
+            "--offset",
+            "4",
+            "--length",
+            "0x4",
+            "1000",
+        ])
+        .unwrap();
+        assert!(cli.has_disassembly_input());
+        let config = cli.validate().unwrap();
+        assert_eq!(config.hex_code, Some(CODE[4..8].to_vec()));
+        assert_eq!(config.address, Some(0x1000));
+
+        let cli = Cli::try_parse_from(["robustone", "riscv32", "--file", file, "13055000", "1000"])
+            .unwrap();
+        assert!(cli.validate().is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,479 @@
+//! JSON and JSON Lines output.
+//!
+//! The schema is stable: fields are only ever added, never renamed or
+//! removed, and `version` is bumped on any incompatible change.
+//!
+//! `--format json` prints one document:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "arch": "riscv64",
+//!   "start_address": 4096,
+//!   "instructions": [
+//!     {
+//!       "address": 4096,
+//!       "bytes": "13055000",
+//!       "size": 4,
+//!       "mnemonic": "addi",
+//!       "op_str": "a0, zero, 5",
+//!       "operands": [
+//!         { "type": "reg", "reg": "a0", "access": "write" },
+//!         { "type": "reg", "reg": "zero", "access": "read" },
+//!         { "type": "imm", "imm": 5 }
+//!       ],
+//!       "regs_read": ["zero"],
+//!       "regs_write": ["a0"],
+//!       "groups": []
+//!     }
+//!   ],
+//!   "errors": [{ "message": "...", "address": 4100 }]
+//! }
+//! ```
+//!
+//! - `address` and `size` are numbers; `bytes` is the instruction encoding
+//!   as lowercase hex in memory order.
+//! - `mnemonic` and `op_str` are the printed text, including aliases and
+//!   symbols.
+//! - `operands` describe the real instruction behind any alias. Each has a
+//!   `type` of `reg` (`reg`, `access`), `imm` (`imm`) or `mem` (`base`,
+//!   `index` when there is an index register, `disp`, `access`), where `access` is `read`, `write`, `readwrite` or
+//!   `none`.
+//! - `regs_read`, `regs_write` and `groups` are lists of names.
+//! - `errors` lists the undecodable data skipped with `-s`, then one entry
+//!   per `unknown` instruction record. The latter carry the record's
+//!   `address`; the instruction itself stays in `instructions` so the
+//!   listing still covers every byte.
+//!
+//! `--format jsonl` prints each instruction object on its own line,
+//! followed by one `{"error": "..."}` line per error (with `address` when
+//! known), so output can be streamed and filtered line by line.
+//!
+//! Object files (ELF, Intel HEX, S-record) print one document per section
+//! or region, in file order, each with a `section` field naming it and
+//! `start_address` set to its load address. With `jsonl`, the regions'
+//! lines simply follow each other.
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use crate::asm::is_unknown;
+use crate::config::{DisasmConfig, OutputFormat};
+use crate::disasm::DisassemblyResult;
+use crate::listing::{image_engine, region_symbols};
+use crate::loader::Image;
+use robustone_core::{DisasmError, Instruction, Operand};
+
+/// Version of the output schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Top-level `--format json` document.
+#[derive(Debug, Serialize)]
+pub struct Document<'a> {
+    pub version: u32,
+    pub arch: &'a str,
+    /// Section or region name; only set for object files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<&'a str>,
+    pub start_address: u64,
+    pub instructions: Vec<InstructionRecord<'a>>,
+    pub errors: Vec<ErrorRecord<'a>>,
+}
+
+/// One instruction.
+#[derive(Debug, Serialize)]
+pub struct InstructionRecord<'a> {
+    pub address: u64,
+    pub bytes: String,
+    pub size: usize,
+    pub mnemonic: &'a str,
+    pub op_str: &'a str,
+    pub operands: Vec<OperandRecord>,
+    pub regs_read: Vec<&'static str>,
+    pub regs_write: Vec<&'static str>,
+    pub groups: Vec<String>,
+}
+
+/// One structured operand, tagged by `type`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OperandRecord {
+    Reg {
+        reg: &'static str,
+        access: &'static str,
+    },
+    Imm {
+        imm: i64,
+    },
+    Mem {
+        base: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<&'static str>,
+        disp: i64,
+        access: &'static str,
+    },
+}
+
+/// One error in a `--format json` document.
+#[derive(Debug, Serialize)]
+pub struct ErrorRecord<'a> {
+    pub message: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<u64>,
+}
+
+/// Message for an instruction record the decoder did not recognise.
+const UNKNOWN_MESSAGE: &str = "invalid instruction encoding";
+
+/// Collects the errors of `result`: skipped data first, then one entry per
+/// unrecognised instruction.
+fn error_records(result: &DisassemblyResult) -> Vec<ErrorRecord<'_>> {
+    let skipped = result.errors.iter().map(|message| ErrorRecord {
+        message: Cow::Borrowed(message),
+        address: None,
+    });
+    let unknown = result
+        .instructions
+        .iter()
+        .filter(|instruction| is_unknown(instruction))
+        .map(|instruction| ErrorRecord {
+            message: Cow::Owned(format!(
+                "{UNKNOWN_MESSAGE}: {}",
+                hex::encode(&instruction.bytes)
+            )),
+            address: Some(instruction.address),
+        });
+    skipped.chain(unknown).collect()
+}
+
+impl<'a> InstructionRecord<'a> {
+    /// Builds the record for `instruction`; the structured fields are empty
+    /// unless it was decoded with detail.
+    pub fn new(instruction: &'a Instruction) -> Self {
+        let mut record = Self {
+            address: instruction.address,
+            bytes: hex::encode(&instruction.bytes),
+            size: instruction.size,
+            mnemonic: &instruction.mnemonic,
+            op_str: &instruction.operands,
+            operands: Vec::new(),
+            regs_read: Vec::new(),
+            regs_write: Vec::new(),
+            groups: Vec::new(),
+        };
+        if let Some(detail) = &instruction.detail {
+            let names = |regs: &[u32]| {
+                let mut names = Vec::new();
+                for name in regs.iter().filter_map(|reg| detail.register_name(*reg)) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                names
+            };
+            record.operands = detail
+                .operands()
+                .into_iter()
+                .map(OperandRecord::from)
+                .collect();
+            record.regs_read = names(detail.registers_read());
+            record.regs_write = names(detail.registers_written());
+            record.groups = detail.groups().into_iter().map(str::to_string).collect();
+        }
+        record
+    }
+}
+
+impl From<Operand> for OperandRecord {
+    fn from(operand: Operand) -> Self {
+        match operand {
+            Operand::Register { name, access } => Self::Reg {
+                reg: name,
+                access: access.name(),
+            },
+            Operand::Immediate(imm) => Self::Imm { imm },
+            Operand::Memory {
+                base,
+                index,
+                disp,
+                access,
+            } => Self::Mem {
+                base,
+                index,
+                disp,
+                access: access.name(),
+            },
+        }
+    }
+}
+
+/// Formats `result` as a single JSON document.
+pub fn format_json(result: &DisassemblyResult) -> String {
+    format_document(result, None)
+}
+
+/// Formats `result` as one document, naming the `section` it came from.
+fn format_document(result: &DisassemblyResult, section: Option<&str>) -> String {
+    let document = Document {
+        version: SCHEMA_VERSION,
+        arch: &result.architecture,
+        section,
+        start_address: result.start_address,
+        instructions: result
+            .instructions
+            .iter()
+            .map(InstructionRecord::new)
+            .collect(),
+        errors: error_records(result),
+    };
+    let mut out = serde_json::to_string_pretty(&document).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+/// Formats `result` as JSON Lines: one instruction object per line, then
+/// one `{"error": ...}` object per error.
+pub fn format_json_lines(result: &DisassemblyResult) -> String {
+    let mut out = String::new();
+    for instruction in &result.instructions {
+        out.push_str(
+            &serde_json::to_string(&InstructionRecord::new(instruction)).unwrap_or_default(),
+        );
+        out.push('\n');
+    }
+    for error in error_records(result) {
+        let line = match error.address {
+            Some(address) => serde_json::json!({ "error": error.message, "address": address }),
+            None => serde_json::json!({ "error": error.message }),
+        };
+        out.push_str(&line.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+/// Formats every region of `image` in `config`'s JSON format, one
+/// document per region.
+pub fn format_image(image: &Image, config: &DisasmConfig) -> Result<String, DisasmError> {
+    let mut engine = image_engine(image, config)?;
+    let arch = config.arch_name();
+    let mut out = String::new();
+    for region in &image.regions {
+        engine.set_symbolizer(arch, Box::new(region_symbols(region, config)))?;
+        // RISC-V decodes whole 2-byte parcels; an odd trailing byte is
+        // reported as an error instead.
+        let len = if arch.starts_with("riscv") {
+            region.bytes.len() & !1
+        } else {
+            region.bytes.len()
+        };
+        let mut result = if len == 0 {
+            DisassemblyResult::new(region.address, arch.to_string())
+        } else {
+            let region_config = DisasmConfig {
+                hex_bytes: region.bytes[..len].to_vec(),
+                start_address: region.address,
+                skip_data: true,
+                ..config.clone()
+            };
+            engine.disassemble(&region_config)?
+        };
+        for (offset, byte) in region.bytes.iter().enumerate().skip(len) {
+            let address = region.address.wrapping_add(offset as u64);
+            result.add_error(format!("trailing byte {byte:#04x} at {address:#x}"));
+        }
+        match config.display_options.format {
+            OutputFormat::JsonLines => out.push_str(&format_json_lines(&result)),
+            _ => out.push_str(&format_document(&result, Some(&region.name))),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::DisassemblyEngine;
+    use robustone_core::HandlerOptions;
+    use robustone_core::types::options::HandlerOption;
+
+    #[test]
+    fn test_json_lines_schema() {
+        let options = HandlerOptions::default().with(HandlerOption::Detail(true));
+        let engine = DisassemblyEngine::with_options("riscv64", options).unwrap();
+        let mut result = DisassemblyResult::new(0x1000, "riscv64".to_string());
+        // addi a0, zero, 5 ; ld a1, 8(sp)
+        for (bytes, address) in [
+            ([0x13, 0x05, 0x50, 0x00], 0x1000),
+            ([0x83, 0x35, 0x81, 0x00], 0x1004),
+        ] {
+            let (instruction, _) = engine
+                .disassemble_single(&bytes, "riscv64", address)
+                .unwrap();
+            result.add_instruction(instruction);
+        }
+        result.add_error("bad bytes".to_string());
+
+        let lines: Vec<serde_json::Value> = format_json_lines(&result)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            serde_json::json!({
+                "address": 0x1000,
+                "bytes": "13055000",
+                "size": 4,
+                "mnemonic": "addi",
+                "op_str": "a0, zero, 5",
+                "operands": [
+                    { "type": "reg", "reg": "a0", "access": "write" },
+                    { "type": "reg", "reg": "zero", "access": "read" },
+                    { "type": "imm", "imm": 5 }
+                ],
+                "regs_read": ["zero"],
+                "regs_write": ["a0"],
+                "groups": []
+            })
+        );
+        assert_eq!(
+            lines[1]["operands"][1],
+            serde_json::json!({ "type": "mem", "base": "sp", "disp": 8, "access": "read" })
+        );
+        assert_eq!(lines[2], serde_json::json!({ "error": "bad bytes" }));
+
+        let document: serde_json::Value = serde_json::from_str(&format_json(&result)).unwrap();
+        assert_eq!(document["version"], SCHEMA_VERSION);
+        assert_eq!(document["start_address"], 0x1000);
+        assert_eq!(document["instructions"].as_array().unwrap().len(), 2);
+        assert_eq!(document["errors"][0]["message"], "bad bytes");
+    }
+
+    #[test]
+    fn test_unknown_instructions_are_errors() {
+        let options = HandlerOptions::default().with(HandlerOption::Detail(true));
+        let engine = DisassemblyEngine::with_options("riscv64", options).unwrap();
+        let mut result = DisassemblyResult::new(0, "riscv64".to_string());
+        // fadd.s fa0, fa1, fa2 ; an invalid word
+        for (bytes, address) in [([0x53, 0x85, 0xc5, 0x00], 0), ([0xff; 4], 4)] {
+            let (instruction, _) = engine
+                .disassemble_single(&bytes, "riscv64", address)
+                .unwrap();
+            result.add_instruction(instruction);
+        }
+
+        let document: serde_json::Value = serde_json::from_str(&format_json(&result)).unwrap();
+        let fadd = &document["instructions"][0];
+        assert_eq!(fadd["operands"][0]["reg"], "fa0");
+        assert_eq!(fadd["regs_read"], serde_json::json!(["fa1", "fa2"]));
+        assert_eq!(fadd["regs_write"], serde_json::json!(["fa0"]));
+        assert_eq!(document["instructions"][1]["mnemonic"], "unknown");
+        assert_eq!(
+            document["errors"],
+            serde_json::json!([
+                { "message": "invalid instruction encoding: ffffffff", "address": 4 }
+            ])
+        );
+
+        let last = format_json_lines(&result)
+            .lines()
+            .last()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&last).unwrap(),
+            serde_json::json!({ "error": "invalid instruction encoding: ffffffff", "address": 4 })
+        );
+    }
+
+    #[test]
+    fn test_alias_operands_follow_real_instruction() {
+        let options = HandlerOptions::default()
+            .with(HandlerOption::Detail(true))
+            .with(HandlerOption::RealDetail(true));
+        let engine = DisassemblyEngine::with_options("riscv64", options).unwrap();
+        // jal 8, which hides its ra link register
+        let (instruction, _) = engine
+            .disassemble_single(&[0xef, 0x00, 0x80, 0x00], "riscv64", 0)
+            .unwrap();
+
+        let record = serde_json::to_value(InstructionRecord::new(&instruction)).unwrap();
+        assert_eq!(record["op_str"], "8");
+        assert_eq!(
+            record["operands"],
+            serde_json::json!([
+                { "type": "reg", "reg": "ra", "access": "write" },
+                { "type": "imm", "imm": 8 }
+            ])
+        );
+        assert_eq!(record["regs_read"], serde_json::json!([]));
+        assert_eq!(record["regs_write"], serde_json::json!(["ra"]));
+    }
+
+    #[test]
+    fn test_format_image_documents_per_region() {
+        use crate::command::ValidatedConfig;
+        use crate::loader::Region;
+        use robustone_core::SymbolMap;
+
+        let region = |name: &str, address, bytes: Vec<u8>| Region {
+            name: name.to_string(),
+            address,
+            bytes,
+            symbols: SymbolMap::new(),
+        };
+        let image = Image {
+            format: "ihex".to_string(),
+            target: None,
+            regions: vec![
+                // addi a0, a0, 1
+                region(".sec1", 0x1000, vec![0x13, 0x05, 0x15, 0x00]),
+                // ld a1, 8(sp) ; a truncated parcel
+                region(".sec2", 0x2000, vec![0x83, 0x35, 0x81, 0x00, 0x03]),
+            ],
+            entry: None,
+            gaps: Vec::new(),
+        };
+        let config = |format| ValidatedConfig {
+            arch_mode: Some("riscv64".to_string()),
+            hex_code: None,
+            address: None,
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+
+        let config_json = DisasmConfig::for_image(config(OutputFormat::Json), &image).unwrap();
+        let out = format_image(&image, &config_json).unwrap();
+        let documents: Vec<serde_json::Value> = serde_json::Deserializer::from_str(&out)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0]["section"], ".sec1");
+        assert_eq!(documents[0]["start_address"], 0x1000);
+        assert_eq!(documents[0]["instructions"][0]["op_str"], "a0,a0,1");
+        assert_eq!(documents[1]["section"], ".sec2");
+        assert_eq!(documents[1]["instructions"][0]["address"], 0x2000);
+        assert_eq!(documents[1]["instructions"][0]["operands"][1]["base"], "sp");
+        assert_eq!(documents[1]["errors"].as_array().unwrap().len(), 1);
+
+        let config_lines =
+            DisasmConfig::for_image(config(OutputFormat::JsonLines), &image).unwrap();
+        let out = format_image(&image, &config_lines).unwrap();
+        assert_eq!(out.lines().count(), 3);
+        assert!(out.lines().all(|line| !line.contains("section")));
+    }
+}
@@ -5,18 +5,29 @@
 //! across multiple architectures with extensive configuration options.
 
 pub mod arch;
+pub mod asm;
+pub mod batch;
+pub mod cfg;
 pub mod command;
 pub mod config;
+pub mod constants;
 pub mod disasm;
 pub mod error;
 pub mod executor;
+pub mod input;
+pub mod json;
+pub mod listing;
+pub mod loader;
+pub mod recursive;
+pub mod repl;
 pub mod utils;
 pub mod version_info;
+pub mod xrefs;
 
 // Re-export modern API surface for convenient use
 pub use arch::{Architecture, ArchitectureSpec};
 pub use command::{Cli, DisplayOptions, ValidatedConfig};
-pub use config::{DisasmConfig, OutputConfig};
+pub use config::{DisasmConfig, OutputConfig, OutputFormat};
 pub use disasm::{DisassemblyEngine, DisassemblyFormatter, DisassemblyResult};
 pub use error::{CliError, ParseError, Result, ValidationError};
 pub use executor::CliExecutor;
@@ -77,6 +88,14 @@ pub fn disassemble_hex(hex_code: &str, architecture: &str, address: Option<u64>)
         real_detail: false,
         skip_data: false,
         unsigned_immediate: false,
+        format: OutputFormat::Text,
+        jobs: 1,
+        recursive: None,
+        cfg: None,
+        xrefs: false,
+        constants: false,
+        symbols: None,
+        image: None,
         version: false,
     })?;
 
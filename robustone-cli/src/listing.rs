@@ -0,0 +1,326 @@
+//! `objdump -d` style listings of loaded images.
+//!
+//! Each region is disassembled at its load address with its own labels
+//! installed as the symbolizer, so branch targets print as `<name>`.
+//! Bytes that do not decode are shown as `.2byte`/`.4byte` data, and
+//! disassembly resumes after them. Gaps in sparse images are reported
+//! between the regions they separate, never disassembled.
+//!
+//! With `--recursive`, each region is traversed from the image entry point,
+//! the region's labels and the `--entry` addresses that fall inside it, or
+//! from its start when none do, and the bytes left unreached are shown as
+//! data too.
+//!
+//! With `--constants`, instructions completing a `lui`/`auipc` value are
+//! annotated with it, named from the region's labels; see
+//! [`crate::constants`].
+
+use std::fmt::Write;
+
+use crate::config::DisasmConfig;
+use crate::constants::Annotator;
+use crate::disasm::{DisassemblyEngine, config_target};
+use crate::loader::{Image, Region};
+use crate::recursive::{Traversal, region_entries};
+use robustone_core::types::options::SyntaxStyle;
+use robustone_core::{DisasmError, Instruction, SymbolMap};
+use robustone_riscv::extensions::Extensions;
+
+/// Formats the disassembly of every region in `image`.
+///
+/// `name` is the file name shown in the header.
+pub fn format_image(
+    name: &str,
+    image: &Image,
+    config: &DisasmConfig,
+) -> Result<String, DisasmError> {
+    let options = config.handler_options();
+    let mut engine = image_engine(image, config)?;
+    let arch = config.arch_name();
+    let mut annotator = if config.constants {
+        let (xlen, extensions) = match &image.target {
+            Some(target) => Extensions::from_isa(&target.isa).ok_or_else(|| {
+                DisasmError::UnsupportedArchitecture(format!("unsupported ISA {}", target.isa))
+            })?,
+            None => config_target(config)?,
+        };
+        Some(Annotator::new(
+            xlen,
+            extensions,
+            options.syntax == SyntaxStyle::Gnu,
+        ))
+    } else {
+        None
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "\n{name}:     file format {}", image.format);
+    if let Some(target) = &image.target {
+        let _ = write!(out, "architecture: {}", target.isa);
+        if !target.flags.is_empty() {
+            let _ = write!(out, ", flags: {}", target.flags.join(", "));
+        }
+        out.push('\n');
+    }
+
+    for region in &image.regions {
+        let symbols = region_symbols(region, config);
+        engine.set_symbolizer(arch, Box::new(symbols.clone()))?;
+        if let Some(gap) = image.gaps.iter().find(|gap| gap.end == region.address) {
+            let _ = writeln!(
+                out,
+                "\n\n... gap: {:#x}-{:#x} ({} bytes not present in the file)",
+                gap.start,
+                gap.end,
+                gap.end - gap.start
+            );
+        }
+        let _ = writeln!(out, "\n\nDisassembly of section {}:", region.name);
+        let traversal = config.recursive.as_ref().map(|entries| {
+            let entries = region_entries(image, region, entries);
+            engine.disassemble_recursive(&region.bytes, arch, region.address, &entries)
+        });
+        format_region(
+            &mut out,
+            &engine,
+            arch,
+            region,
+            &symbols,
+            traversal.as_ref(),
+            annotator.as_mut(),
+        );
+    }
+    Ok(out)
+}
+
+/// Builds the engine for `image`'s recorded target, or for the
+/// architecture given on the command line when it records none.
+pub(crate) fn image_engine(
+    image: &Image,
+    config: &DisasmConfig,
+) -> Result<DisassemblyEngine, DisasmError> {
+    let options = config.handler_options();
+    match &image.target {
+        Some(target) => DisassemblyEngine::for_target(target, options),
+        None => DisassemblyEngine::with_options(config.arch_name(), options),
+    }
+}
+
+/// Returns the labels of `region` merged with the `--symbols` map.
+pub(crate) fn region_symbols(region: &Region, config: &DisasmConfig) -> SymbolMap {
+    let mut symbols = region.symbols.clone();
+    if let Some(extra) = &config.symbols {
+        for (address, symbol) in extra.iter() {
+            symbols.insert(address, symbol);
+        }
+    }
+    symbols
+}
+
+/// Lists `region`, decoding it linearly unless a `traversal` of it says
+/// which instructions were reached, and annotating materialised constants
+/// when an `annotator` is given.
+fn format_region(
+    out: &mut String,
+    engine: &DisassemblyEngine,
+    arch: &str,
+    region: &Region,
+    labels: &SymbolMap,
+    traversal: Option<&Traversal>,
+    mut annotator: Option<&mut Annotator>,
+) {
+    let address_width = if arch.ends_with("64") { 16 } else { 8 };
+    let mut comment = |instruction: &Instruction| match annotator.as_deref_mut() {
+        Some(annotator) => annotator.comment(instruction, Some(labels)),
+        None => String::new(),
+    };
+    let mut offset = 0;
+    while offset < region.bytes.len() {
+        let address = region.address.wrapping_add(offset as u64);
+        if let Some(label) = labels.get(address) {
+            let _ = writeln!(out, "\n{address:0address_width$x} <{label}>:");
+        }
+        let bytes = &region.bytes[offset..];
+        let size = match traversal {
+            Some(traversal) => match traversal.instructions.get(&address) {
+                Some(instruction) => {
+                    let comment = comment(instruction);
+                    format_instruction(out, address, bytes, instruction, &comment)
+                }
+                None => {
+                    // Data stops where the next reached instruction starts.
+                    let next = traversal
+                        .instructions
+                        .range(address..)
+                        .next()
+                        .map_or(bytes.len(), |(next, _)| (next - address) as usize);
+                    format_data(out, address, &bytes[..next])
+                }
+            },
+            None => match engine.disassemble_single(bytes, arch, address) {
+                Ok((instruction, size)) if size > 0 && !instruction.is_unknown() => {
+                    let comment = comment(&instruction);
+                    format_instruction(out, address, &bytes[..size], &instruction, &comment)
+                }
+                _ => format_data(out, address, bytes),
+            },
+        };
+        offset += size;
+    }
+}
+
+/// Lists one instruction, followed by `comment`, and returns its size.
+fn format_instruction(
+    out: &mut String,
+    address: u64,
+    bytes: &[u8],
+    instruction: &Instruction,
+    comment: &str,
+) -> usize {
+    let size = instruction.size;
+    let _ = write!(out, "{address:>8x}:\t{:<8}\t", encoding(&bytes[..size]));
+    if instruction.operands.is_empty() {
+        let _ = writeln!(out, "{}", instruction.mnemonic);
+    } else {
+        let _ = writeln!(
+            out,
+            "{}\t{}{comment}",
+            instruction.mnemonic, instruction.operands
+        );
+    }
+    size
+}
+
+/// Lists the first data unit of `bytes` and returns its size.
+fn format_data(out: &mut String, address: u64, bytes: &[u8]) -> usize {
+    let size = data_unit(bytes);
+    let data = encoding(&bytes[..size]);
+    let _ = writeln!(out, "{address:>8x}:\t{data:<8}\t.{size}byte\t0x{data}");
+    size
+}
+
+/// Prints an instruction's bytes as one little-endian number, the way
+/// objdump shows RISC-V parcels (`00000513`, `4501`).
+fn encoding(bytes: &[u8]) -> String {
+    bytes.iter().rev().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Returns how many bytes to show as data when decoding fails: the length
+/// the encoding claims (4 when the low bits are `11`, else 2), limited to
+/// what is left.
+fn data_unit(bytes: &[u8]) -> usize {
+    let claimed = if bytes[0] & 0x3 == 0x3 { 4 } else { 2 };
+    claimed.min(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::ValidatedConfig;
+    use crate::config::OutputFormat;
+    use crate::loader::Target;
+
+    #[test]
+    fn test_format_image() {
+        let mut symbols = SymbolMap::new();
+        symbols.insert(0x1000, "_start");
+        symbols.insert(0x100a, "done");
+        let image = Image {
+            format: "elf32-littleriscv".to_string(),
+            target: Some(Target {
+                arch: "riscv32",
+                isa: "rv32imc".to_string(),
+                flags: vec!["RVC".to_string()],
+            }),
+            regions: vec![Region {
+                name: ".text".to_string(),
+                address: 0x1000,
+                // addi a0, a0, 1 ; c.beqz a0, done ; <invalid> ; c.jr ra
+                bytes: vec![
+                    0x13, 0x05, 0x15, 0x00, 0x19, 0xc1, 0xff, 0xff, 0xff, 0xff, 0x82, 0x80,
+                ],
+                symbols,
+            }],
+            entry: Some(0x1000),
+            gaps: Vec::new(),
+        };
+        let config = ValidatedConfig {
+            arch_mode: None,
+            hex_code: None,
+            address: None,
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+        let config = DisasmConfig::for_image(config, &image).unwrap();
+        assert_eq!(
+            format_image("a.out", &image, &config).unwrap(),
+            "\na.out:     file format elf32-littleriscv\n\
+             architecture: rv32imc, flags: RVC\n\
+             \n\nDisassembly of section .text:\n\
+             \n00001000 <_start>:\n\
+             \x20   1000:\t00150513\taddi\ta0,a0,1\n\
+             \x20   1004:\tc119    \tbeqz\ta0,100a <done>\n\
+             \x20   1006:\tffffffff\t.4byte\t0xffffffff\n\
+             \n0000100a <done>:\n\
+             \x20   100a:\t8082    \tret\n"
+        );
+    }
+
+    #[test]
+    fn test_format_image_recursive() {
+        let image = Image {
+            format: "elf64-littleriscv".to_string(),
+            target: None,
+            regions: vec![Region {
+                name: ".text".to_string(),
+                address: 0x1000,
+                // c.j 1006 ; <addi a0, a0, 1 as data> ; c.jr ra
+                bytes: vec![0x19, 0xa0, 0x13, 0x05, 0x15, 0x00, 0x82, 0x80],
+                symbols: SymbolMap::new(),
+            }],
+            entry: Some(0x1000),
+            gaps: Vec::new(),
+        };
+        let config = ValidatedConfig {
+            arch_mode: Some("riscv64".to_string()),
+            hex_code: None,
+            address: None,
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+            recursive: Some(Vec::new()),
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+        let config = DisasmConfig::for_image(config, &image).unwrap();
+        // A linear sweep would decode the skipped bytes as `addi`.
+        assert_eq!(
+            format_image("a.out", &image, &config).unwrap(),
+            "\na.out:     file format elf64-littleriscv\n\
+             \n\nDisassembly of section .text:\n\
+             \x20   1000:\ta019    \tj\t1006\n\
+             \x20   1002:\t00150513\t.4byte\t0x00150513\n\
+             \x20   1006:\t8082    \tret\n"
+        );
+    }
+}
@@ -0,0 +1,661 @@
+//! ELF front-end.
+//!
+//! Reads the header, section table, symbol table and `.riscv.attributes`
+//! of 32- and 64-bit ELF files in either byte order. Executable
+//! `SHT_PROGBITS` sections become regions at their load addresses; files
+//! without a section table fall back to executable `PT_LOAD` segments.
+//!
+//! The target comes from `Tag_RISCV_arch` when present and otherwise from
+//! `EI_CLASS` and `e_flags` (RVC, RVE and the float ABI), defaulting to
+//! the G profile like objdump.
+
+use super::{Image, Region, Target};
+use crate::error::{CliError, Result};
+use robustone_core::SymbolMap;
+
+const MAGIC: &[u8] = b"\x7fELF";
+
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+
+const ET_REL: u16 = 1;
+const EM_RISCV: u16 = 243;
+
+const EF_RISCV_RVC: u32 = 0x1;
+const EF_RISCV_FLOAT_ABI: u32 = 0x6;
+const EF_RISCV_RVE: u32 = 0x8;
+const EF_RISCV_TSO: u32 = 0x10;
+
+const SHT_PROGBITS: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const SHT_RISCV_ATTRIBUTES: u32 = 0x7000_0003;
+const SHF_ALLOC: u64 = 0x2;
+const SHF_EXECINSTR: u64 = 0x4;
+
+const PT_LOAD: u32 = 1;
+const PF_X: u32 = 0x1;
+
+const STT_NOTYPE: u8 = 0;
+const STT_FUNC: u8 = 2;
+const STB_LOCAL: u8 = 0;
+const SHN_UNDEF: u16 = 0;
+const SHN_LORESERVE: u16 = 0xff00;
+
+const TAG_FILE: u64 = 1;
+const TAG_RISCV_ARCH: u64 = 5;
+
+/// Returns `true` when `bytes` start with the ELF magic number.
+pub fn is_elf(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Loads the executable code of an ELF file.
+pub fn load(bytes: &[u8]) -> Result<Image> {
+    let file = ElfFile::parse(bytes)?;
+    let target = file.target()?;
+    let regions = file.regions()?;
+    Ok(Image {
+        format: format!(
+            "elf{}-{}riscv",
+            if file.reader.is64 { 64 } else { 32 },
+            if file.reader.little { "little" } else { "big" }
+        ),
+        target: Some(target),
+        regions,
+        entry: file.entry,
+        gaps: Vec::new(),
+    })
+}
+
+fn error(message: impl Into<String>) -> CliError {
+    CliError::parse("elf", message)
+}
+
+/// Bounds-checked reads in the file's byte order and class.
+struct Reader<'a> {
+    data: &'a [u8],
+    is64: bool,
+    little: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&self, offset: u64, len: u64) -> Result<&'a [u8]> {
+        usize::try_from(offset)
+            .ok()
+            .zip(usize::try_from(len).ok())
+            .and_then(|(start, len)| self.data.get(start..start.checked_add(len)?))
+            .ok_or_else(|| error(format!("truncated file reading {len} bytes at {offset:#x}")))
+    }
+
+    /// Returns the offset of entry `index` of a table at `start` with
+    /// entries of `size` bytes, checking that the fields of the entry are
+    /// addressable.
+    fn entry(&self, start: u64, index: u64, size: u64) -> Result<u64> {
+        // The largest entry read, a 64-bit section header.
+        const ENTRY_MAX: u64 = 64;
+        index
+            .checked_mul(size)
+            .and_then(|offset| start.checked_add(offset))
+            .filter(|base| base.checked_add(ENTRY_MAX).is_some())
+            .ok_or_else(|| {
+                error(format!(
+                    "malformed ELF: entry {index} of the table at {start:#x}"
+                ))
+            })
+    }
+
+    fn array<const N: usize>(&self, offset: u64) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(offset, N as u64)?);
+        if !self.little {
+            array.reverse();
+        }
+        Ok(array)
+    }
+
+    fn u8(&self, offset: u64) -> Result<u8> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: u64) -> Result<u16> {
+        self.array(offset).map(u16::from_le_bytes)
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32> {
+        self.array(offset).map(u32::from_le_bytes)
+    }
+
+    fn u64(&self, offset: u64) -> Result<u64> {
+        self.array(offset).map(u64::from_le_bytes)
+    }
+}
+
+struct Section {
+    name: String,
+    kind: u32,
+    flags: u64,
+    address: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+}
+
+struct Symbol {
+    name: String,
+    value: u64,
+    kind: u8,
+    bind: u8,
+    section: u16,
+}
+
+struct ElfFile<'a> {
+    reader: Reader<'a>,
+    kind: u16,
+    flags: u32,
+    /// `e_entry`, unless the file is relocatable or leaves it zero.
+    entry: Option<u64>,
+    sections: Vec<Section>,
+}
+
+impl<'a> ElfFile<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        if !is_elf(data) {
+            return Err(error("missing ELF magic"));
+        }
+        let is64 = match data.get(4) {
+            Some(&ELFCLASS32) => false,
+            Some(&ELFCLASS64) => true,
+            _ => return Err(error("unknown ELF class")),
+        };
+        let little = match data.get(5) {
+            Some(&ELFDATA2LSB) => true,
+            Some(&ELFDATA2MSB) => false,
+            _ => return Err(error("unknown ELF byte order")),
+        };
+        let reader = Reader { data, is64, little };
+
+        let machine = reader.u16(18)?;
+        if machine != EM_RISCV {
+            return Err(error(format!("unsupported machine {machine}")));
+        }
+        let kind = reader.u16(16)?;
+        let entry = if is64 {
+            reader.u64(24)?
+        } else {
+            u64::from(reader.u32(24)?)
+        };
+        let (flags, shoff, shentsize, shnum, shstrndx) = if is64 {
+            (
+                reader.u32(48)?,
+                reader.u64(40)?,
+                reader.u16(58)?,
+                reader.u16(60)?,
+                reader.u16(62)?,
+            )
+        } else {
+            (
+                reader.u32(36)?,
+                u64::from(reader.u32(32)?),
+                reader.u16(46)?,
+                reader.u16(48)?,
+                reader.u16(50)?,
+            )
+        };
+
+        let mut file = Self {
+            reader,
+            kind,
+            flags,
+            entry: Some(entry).filter(|&entry| kind != ET_REL && entry != 0),
+            sections: Vec::new(),
+        };
+        if shoff != 0 {
+            file.read_sections(shoff, shentsize, shnum, shstrndx)?;
+        }
+        Ok(file)
+    }
+
+    fn read_sections(&mut self, shoff: u64, entsize: u16, count: u16, names: u16) -> Result<()> {
+        let reader = &self.reader;
+        let mut sections = Vec::with_capacity(usize::from(count));
+        let mut name_offsets = Vec::with_capacity(usize::from(count));
+        for index in 0..u64::from(count) {
+            let base = reader.entry(shoff, index, u64::from(entsize))?;
+            name_offsets.push(reader.u32(base)?);
+            let section = if reader.is64 {
+                Section {
+                    name: String::new(),
+                    kind: reader.u32(base + 4)?,
+                    flags: reader.u64(base + 8)?,
+                    address: reader.u64(base + 16)?,
+                    offset: reader.u64(base + 24)?,
+                    size: reader.u64(base + 32)?,
+                    link: reader.u32(base + 40)?,
+                }
+            } else {
+                Section {
+                    name: String::new(),
+                    kind: reader.u32(base + 4)?,
+                    flags: u64::from(reader.u32(base + 8)?),
+                    address: u64::from(reader.u32(base + 12)?),
+                    offset: u64::from(reader.u32(base + 16)?),
+                    size: u64::from(reader.u32(base + 20)?),
+                    link: reader.u32(base + 24)?,
+                }
+            };
+            sections.push(section);
+        }
+        if let Some(strtab) = sections.get(usize::from(names)) {
+            let strtab = reader.bytes(strtab.offset, strtab.size)?;
+            for (section, offset) in sections.iter_mut().zip(name_offsets) {
+                section.name = string_at(strtab, offset);
+            }
+        }
+        self.sections = sections;
+        Ok(())
+    }
+
+    fn section_data(&self, section: &Section) -> Result<&'a [u8]> {
+        self.reader.bytes(section.offset, section.size)
+    }
+
+    /// Returns the defined symbols worth printing as labels, from
+    /// `.symtab` or, for stripped files, `.dynsym`.
+    fn symbols(&self) -> Result<Vec<Symbol>> {
+        let Some(table) = self
+            .sections
+            .iter()
+            .find(|section| section.kind == SHT_SYMTAB)
+            .or_else(|| {
+                self.sections
+                    .iter()
+                    .find(|section| section.kind == SHT_DYNSYM)
+            })
+        else {
+            return Ok(Vec::new());
+        };
+        let strtab = match self.sections.get(table.link as usize) {
+            Some(strtab) => self.section_data(strtab)?,
+            None => return Err(error("symbol table without a string table")),
+        };
+        let reader = &self.reader;
+        let entsize = if reader.is64 { 24 } else { 16 };
+        let mut symbols = Vec::new();
+        for index in 1..table.size / entsize {
+            let base = reader.entry(table.offset, index, entsize)?;
+            let (value, info, section) = if reader.is64 {
+                (
+                    reader.u64(base + 8)?,
+                    reader.u8(base + 4)?,
+                    reader.u16(base + 6)?,
+                )
+            } else {
+                (
+                    u64::from(reader.u32(base + 4)?),
+                    reader.u8(base + 12)?,
+                    reader.u16(base + 14)?,
+                )
+            };
+            let symbol = Symbol {
+                name: string_at(strtab, reader.u32(base)?),
+                value,
+                kind: info & 0xf,
+                bind: info >> 4,
+                section,
+            };
+            // `$x`/`$d` are RISC-V mapping symbols, not labels.
+            if matches!(symbol.kind, STT_NOTYPE | STT_FUNC)
+                && !symbol.name.is_empty()
+                && !symbol.name.starts_with('$')
+                && symbol.section != SHN_UNDEF
+                && symbol.section < SHN_LORESERVE
+            {
+                symbols.push(symbol);
+            }
+        }
+        // Later inserts win, so prefer global functions at shared addresses.
+        symbols.sort_by_key(|symbol| (symbol.kind == STT_FUNC, symbol.bind != STB_LOCAL));
+        Ok(symbols)
+    }
+
+    /// Returns the target from `.riscv.attributes` and `e_flags`.
+    fn target(&self) -> Result<Target> {
+        let is64 = self.reader.is64;
+        let rve = self.flags & EF_RISCV_RVE != 0;
+        let rvc = self.flags & EF_RISCV_RVC != 0;
+
+        let mut flags = Vec::new();
+        if rvc {
+            flags.push("RVC".to_string());
+        }
+        if rve {
+            flags.push("RVE".to_string());
+        }
+        flags.push(
+            match self.flags & EF_RISCV_FLOAT_ABI {
+                0x0 => "soft-float ABI",
+                0x2 => "single-float ABI",
+                0x4 => "double-float ABI",
+                _ => "quad-float ABI",
+            }
+            .to_string(),
+        );
+        if self.flags & EF_RISCV_TSO != 0 {
+            flags.push("TSO".to_string());
+        }
+
+        let mut isa = None;
+        for section in &self.sections {
+            if section.kind == SHT_RISCV_ATTRIBUTES {
+                isa = arch_attribute(self.section_data(section)?, self.reader.little);
+            }
+        }
+        let isa = isa.unwrap_or_else(|| match (is64, rve) {
+            (false, true) => format!("rv32ema{}", if rvc { "c" } else { "" }),
+            _ => format!(
+                "rv{}g{}",
+                if is64 { 64 } else { 32 },
+                if rvc { "c" } else { "" }
+            ),
+        });
+        Ok(Target {
+            arch: if is64 { "riscv64" } else { "riscv32" },
+            isa,
+            flags,
+        })
+    }
+
+    fn regions(&self) -> Result<Vec<Region>> {
+        let symbols = self.symbols()?;
+        let mut regions = Vec::new();
+        for (index, section) in self.sections.iter().enumerate() {
+            let executable = SHF_ALLOC | SHF_EXECINSTR;
+            if section.kind != SHT_PROGBITS
+                || section.flags & executable != executable
+                || section.size == 0
+            {
+                continue;
+            }
+            // Symbol values in relocatable objects are section offsets.
+            let symbols = symbols
+                .iter()
+                .filter(|symbol| self.kind != ET_REL || usize::from(symbol.section) == index)
+                .map(|symbol| (symbol.value, symbol.name.clone()));
+            regions.push(Region {
+                name: section.name.clone(),
+                address: section.address,
+                bytes: self.section_data(section)?.to_vec(),
+                symbols: symbols_in_order(symbols),
+            });
+        }
+        if self.sections.is_empty() {
+            regions = self.segments(&symbols)?;
+        }
+        Ok(regions)
+    }
+
+    /// Returns the executable `PT_LOAD` segments.
+    fn segments(&self, symbols: &[Symbol]) -> Result<Vec<Region>> {
+        let reader = &self.reader;
+        let (phoff, phentsize, phnum) = if reader.is64 {
+            (reader.u64(32)?, reader.u16(54)?, reader.u16(56)?)
+        } else {
+            (u64::from(reader.u32(28)?), reader.u16(42)?, reader.u16(44)?)
+        };
+        let mut regions = Vec::new();
+        for index in 0..u64::from(phnum) {
+            let base = reader.entry(phoff, index, u64::from(phentsize))?;
+            let (kind, flags, offset, address, size) = if reader.is64 {
+                (
+                    reader.u32(base)?,
+                    reader.u32(base + 4)?,
+                    reader.u64(base + 8)?,
+                    reader.u64(base + 16)?,
+                    reader.u64(base + 32)?,
+                )
+            } else {
+                (
+                    reader.u32(base)?,
+                    reader.u32(base + 24)?,
+                    u64::from(reader.u32(base + 4)?),
+                    u64::from(reader.u32(base + 8)?),
+                    u64::from(reader.u32(base + 16)?),
+                )
+            };
+            if kind != PT_LOAD || flags & PF_X == 0 || size == 0 {
+                continue;
+            }
+            regions.push(Region {
+                name: format!("segment {index}"),
+                address,
+                bytes: reader.bytes(offset, size)?.to_vec(),
+                symbols: symbols_in_order(
+                    symbols
+                        .iter()
+                        .map(|symbol| (symbol.value, symbol.name.clone())),
+                ),
+            });
+        }
+        Ok(regions)
+    }
+}
+
+/// Builds a map where later symbols replace earlier ones at the same address.
+fn symbols_in_order(symbols: impl Iterator<Item = (u64, String)>) -> SymbolMap {
+    let mut map = SymbolMap::new();
+    for (address, name) in symbols {
+        map.insert(address, name);
+    }
+    map
+}
+
+/// Reads the NUL-terminated string at `offset` of a string table.
+fn string_at(table: &[u8], offset: u32) -> String {
+    let bytes = table.get(offset as usize..).unwrap_or_default();
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// Extracts `Tag_RISCV_arch` from a `.riscv.attributes` section.
+///
+/// The section is a version byte `A` followed by vendor subsections, each
+/// holding tagged sub-subsections of attributes. RISC-V attributes with
+/// odd tags are strings and those with even tags are ULEB128 integers.
+fn arch_attribute(data: &[u8], little: bool) -> Option<String> {
+    let read_u32 = |bytes: &[u8]| -> Option<usize> {
+        let array: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+        let value = if little {
+            u32::from_le_bytes(array)
+        } else {
+            u32::from_be_bytes(array)
+        };
+        Some(value as usize)
+    };
+
+    let mut rest = data.strip_prefix(b"A")?;
+    while !rest.is_empty() {
+        let length = read_u32(rest)?;
+        let subsection = rest.get(4..length)?;
+        rest = &rest[length..];
+        let vendor_end = subsection.iter().position(|&b| b == 0)?;
+        if &subsection[..vendor_end] != b"riscv" {
+            continue;
+        }
+        let mut attributes = &subsection[vendor_end + 1..];
+        while !attributes.is_empty() {
+            let mut cursor = 0;
+            let tag = uleb128(attributes, &mut cursor)?;
+            let size = read_u32(attributes.get(cursor..)?)?;
+            let body = attributes.get(cursor + 4..size)?;
+            attributes = &attributes[size..];
+            if tag != TAG_FILE {
+                continue;
+            }
+            let mut cursor = 0;
+            while cursor < body.len() {
+                let tag = uleb128(body, &mut cursor)?;
+                if tag % 2 == 0 {
+                    uleb128(body, &mut cursor)?;
+                    continue;
+                }
+                let end = cursor + body[cursor..].iter().position(|&b| b == 0)?;
+                let value = &body[cursor..end];
+                cursor = end + 1;
+                if tag == TAG_RISCV_ARCH {
+                    return Some(String::from_utf8_lossy(value).into_owned());
+                }
+            }
+        }
+    }
+    None
+}
+
+fn uleb128(bytes: &[u8], cursor: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*cursor)?;
+        *cursor += 1;
+        value |= u64::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an ELF64 relocatable object with `.text`, a symbol table and
+    /// a `.riscv.attributes` section naming `arch`.
+    fn object(text: &[u8], symbols: &[(&str, u64, u8)], arch: &str) -> Vec<u8> {
+        let mut strtab = vec![0u8];
+        let mut symtab = vec![0u8; 24];
+        for &(name, value, kind) in symbols {
+            symtab.extend_from_slice(&(strtab.len() as u32).to_le_bytes());
+            symtab.extend_from_slice(&[0x10 | kind, 0]);
+            symtab.extend_from_slice(&1u16.to_le_bytes());
+            symtab.extend_from_slice(&value.to_le_bytes());
+            symtab.extend_from_slice(&0u64.to_le_bytes());
+            strtab.extend_from_slice(name.as_bytes());
+            strtab.push(0);
+        }
+
+        let mut file_attributes = vec![TAG_RISCV_ARCH as u8];
+        file_attributes.extend_from_slice(arch.as_bytes());
+        file_attributes.push(0);
+        let mut vendor = b"riscv\0".to_vec();
+        vendor.push(TAG_FILE as u8);
+        vendor.extend_from_slice(&(file_attributes.len() as u32 + 5).to_le_bytes());
+        vendor.extend_from_slice(&file_attributes);
+        let mut attributes = b"A".to_vec();
+        attributes.extend_from_slice(&(vendor.len() as u32 + 4).to_le_bytes());
+        attributes.extend_from_slice(&vendor);
+
+        let shstrtab = b"\0.text\0.symtab\0.strtab\0.shstrtab\0.riscv.attributes\0";
+        // (name offset, type, flags, data, link)
+        let sections: [(u32, u32, u64, &[u8], u32); 5] = [
+            (1, SHT_PROGBITS, SHF_ALLOC | SHF_EXECINSTR, text, 0),
+            (7, SHT_SYMTAB, 0, &symtab, 3),
+            (15, 3, 0, &strtab, 0),
+            (23, 3, 0, shstrtab, 0),
+            (33, SHT_RISCV_ATTRIBUTES, 0, &attributes, 0),
+        ];
+
+        let mut data = vec![0u8; 64];
+        let mut offsets = Vec::new();
+        for (_, _, _, bytes, _) in &sections {
+            offsets.push(data.len() as u64);
+            data.extend_from_slice(bytes);
+        }
+        let shoff = data.len() as u64;
+        data.extend_from_slice(&[0u8; 64]);
+        for ((name, kind, flags, bytes, link), offset) in sections.iter().zip(offsets) {
+            data.extend_from_slice(&name.to_le_bytes());
+            data.extend_from_slice(&kind.to_le_bytes());
+            data.extend_from_slice(&flags.to_le_bytes());
+            data.extend_from_slice(&0u64.to_le_bytes());
+            data.extend_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            data.extend_from_slice(&link.to_le_bytes());
+            data.extend_from_slice(&[0u8; 20]);
+        }
+
+        data[..8].copy_from_slice(&[0x7f, b'E', b'L', b'F', ELFCLASS64, ELFDATA2LSB, 1, 0]);
+        data[16..18].copy_from_slice(&ET_REL.to_le_bytes());
+        data[18..20].copy_from_slice(&EM_RISCV.to_le_bytes());
+        data[40..48].copy_from_slice(&shoff.to_le_bytes());
+        data[48..52].copy_from_slice(&(EF_RISCV_RVC | 0x4).to_le_bytes());
+        data[58..60].copy_from_slice(&64u16.to_le_bytes());
+        data[60..62].copy_from_slice(&6u16.to_le_bytes());
+        data[62..64].copy_from_slice(&4u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_load_object() {
+        // addi a0, a0, 1 ; c.jr ra
+        let text = [0x13, 0x05, 0x15, 0x00, 0x82, 0x80];
+        let bytes = object(
+            &text,
+            &[
+                ("_start", 0, STT_FUNC),
+                ("$x", 0, STT_NOTYPE),
+                ("ret", 4, 0),
+            ],
+            "rv64i2p0_m2p0_c2p0",
+        );
+        assert!(is_elf(&bytes));
+
+        let image = load(&bytes).unwrap();
+        assert_eq!(image.format, "elf64-littleriscv");
+        let target = image.target.unwrap();
+        assert_eq!(target.arch, "riscv64");
+        assert_eq!(target.isa, "rv64i2p0_m2p0_c2p0");
+        assert_eq!(target.flags, ["RVC", "double-float ABI"]);
+        // Relocatable objects have no entry point.
+        assert_eq!(image.entry, None);
+
+        assert_eq!(image.regions.len(), 1);
+        let text_region = &image.regions[0];
+        assert_eq!(text_region.name, ".text");
+        assert_eq!(text_region.bytes, text);
+        assert_eq!(text_region.symbols.get(0), Some("_start"));
+        assert_eq!(text_region.symbols.get(4), Some("ret"));
+        assert_eq!(text_region.symbols.len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(!is_elf(b"\x13\x05"));
+        let mut bytes = object(&[0x13, 0x05, 0x15, 0x00], &[], "rv64i");
+        bytes[18] = 62; // EM_X86_64
+        assert!(load(&bytes).is_err());
+        assert!(load(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_overflowing_offsets() {
+        let mut bytes = object(
+            &[0x13, 0x05, 0x15, 0x00],
+            &[("_start", 0, STT_FUNC)],
+            "rv64i",
+        );
+        let shoff = u64::from_le_bytes(bytes[40..48].try_into().unwrap()) as usize;
+        // sh_offset of `.symtab`, the second section after the null one.
+        let symtab_offset = shoff + 2 * 64 + 24;
+        bytes[symtab_offset..symtab_offset + 8].copy_from_slice(&(u64::MAX - 16).to_le_bytes());
+        let message = load(&bytes).unwrap_err().to_string();
+        assert!(message.contains("malformed ELF"), "{message}");
+
+        // e_shoff near the end of the address space.
+        let mut bytes = object(&[0x13, 0x05, 0x15, 0x00], &[], "rv64i");
+        bytes[40..48].copy_from_slice(&(u64::MAX - 64).to_le_bytes());
+        assert!(load(&bytes).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Intel HEX front-end.
+//!
+//! Each record is `:LLAAAATT<data>CC`: a byte count, a 16-bit address, a
+//! record type, the data and a two's-complement checksum. Data records
+//! (`00`) are placed relative to the base set by extended segment (`02`,
+//! base = value × 16) or extended linear (`04`, base = value << 16)
+//! records; start address records (`03`, `05`) are ignored and `01` ends
+//! the file.
+
+use super::{Image, MemoryMap};
+use crate::error::{CliError, Result};
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Loads Intel HEX text into an image with one region per contiguous run
+/// of data.
+pub fn load(text: &str) -> Result<Image> {
+    let mut memory = MemoryMap::new();
+    let mut base = 0u64;
+    for (index, line) in text.lines().enumerate() {
+        let error = |message: String| {
+            CliError::parse("intel hex", format!("line {}: {message}", index + 1))
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let record = record(line).map_err(error)?;
+        let (offset, kind) = (u16::from_be_bytes([record[1], record[2]]), record[3]);
+        let data = &record[4..record.len() - 1];
+        let value = || match data {
+            [high, low] => Ok(u64::from(u16::from_be_bytes([*high, *low]))),
+            _ => Err(error(format!("record type {kind:02x} needs 2 data bytes"))),
+        };
+
+        match kind {
+            DATA => {
+                let address = base + u64::from(offset);
+                memory.insert(address, data).map_err(error)?;
+            }
+            END_OF_FILE => break,
+            EXTENDED_SEGMENT_ADDRESS => base = value()? << 4,
+            EXTENDED_LINEAR_ADDRESS => base = value()? << 16,
+            START_SEGMENT_ADDRESS | START_LINEAR_ADDRESS => {}
+            _ => return Err(error(format!("unknown record type {kind:02x}"))),
+        }
+    }
+    if memory.is_empty() {
+        return Err(CliError::parse("intel hex", "no data records"));
+    }
+    Ok(memory.into_image("ihex"))
+}
+
+/// Returns `true` when `line` is a well-formed record with a valid
+/// checksum.
+pub fn is_record(line: &str) -> bool {
+    record(line.trim()).is_ok()
+}
+
+/// Decodes the bytes of one record line, checking its length and checksum.
+fn record(line: &str) -> std::result::Result<Vec<u8>, String> {
+    let record = line
+        .strip_prefix(':')
+        .ok_or_else(|| "record does not start with ':'".to_string())?;
+    let record = hex::decode(record).map_err(|e| format!("invalid hex: {e}"))?;
+    let [count, _, _, _, ..] = record[..] else {
+        return Err("record too short".to_string());
+    };
+    if record.len() != usize::from(count) + 5 {
+        return Err(format!("expected {count} data bytes"));
+    }
+    if record.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0 {
+        return Err("checksum mismatch".to_string());
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Range;
+
+    #[test]
+    fn test_load_with_gaps_and_bases() {
+        let image = load(
+            ":0400000013051500CF\n\
+             :020004008280F8\n\
+             :020000040800F2\n\
+             :0400100013051500BF\n\
+             :00000001FF\n",
+        )
+        .unwrap();
+        assert_eq!(image.format, "ihex");
+        assert!(image.target.is_none());
+        let regions: Vec<_> = image
+            .regions
+            .iter()
+            .map(|region| (region.name.as_str(), region.address, region.bytes.len()))
+            .collect();
+        assert_eq!(regions, [(".sec1", 0, 6), (".sec2", 0x0800_0010, 4)]);
+        assert_eq!(
+            image.gaps,
+            [Range {
+                start: 6,
+                end: 0x0800_0010
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_records() {
+        assert!(load(":0400000013051500C0\n").is_err());
+        assert!(load("0400000013051500CF\n").is_err());
+        assert!(load(":0400000013051500CF\n:020002008280FA\n").is_err());
+        assert!(load(":00000001FF\n").is_err());
+    }
+}
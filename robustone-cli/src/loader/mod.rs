@@ -0,0 +1,269 @@
+//! Program image loaders.
+//!
+//! A loader turns a file format into an [`Image`]: the code regions to
+//! disassemble at their load addresses, plus whatever target and symbol
+//! information the format records. Record-based firmware formats (Intel
+//! HEX, S-records) fill a [`MemoryMap`] first, so data arriving in any
+//! order is merged into contiguous regions and the holes between them are
+//! reported as gaps.
+
+pub mod elf;
+pub mod ihex;
+pub mod srec;
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::error::{CliError, Result};
+use robustone_core::SymbolMap;
+
+/// File formats accepted by `--file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Flat machine code, disassembled from the start address.
+    Raw,
+    /// ELF object, executable or shared library.
+    Elf,
+    /// Intel HEX records (`:10000000...`).
+    IntelHex,
+    /// Motorola S-records (`S1130000...`).
+    SRecord,
+}
+
+impl FileFormat {
+    /// Guesses the format from the file contents.
+    ///
+    /// A text format is only chosen when the first line is a valid record,
+    /// checksum included, so binaries that happen to start with `:` or `S`
+    /// stay raw.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if elf::is_elf(bytes) {
+            return Self::Elf;
+        }
+        let start = bytes
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(bytes.len());
+        let line = bytes[start..]
+            .split(|&b| b == b'\n')
+            .next()
+            .unwrap_or_default();
+        match std::str::from_utf8(line) {
+            Ok(line) if ihex::is_record(line) => Self::IntelHex,
+            Ok(line) if srec::is_record(line) => Self::SRecord,
+            _ => Self::Raw,
+        }
+    }
+
+    /// Loads `bytes` as an image; raw files have no image.
+    pub fn load(self, bytes: &[u8]) -> Result<Option<Image>> {
+        match self {
+            Self::Raw => Ok(None),
+            Self::Elf => elf::load(bytes).map(Some),
+            Self::IntelHex => ihex::load(&text(bytes, "intel hex")?).map(Some),
+            Self::SRecord => srec::load(&text(bytes, "s-record")?).map(Some),
+        }
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        match input.to_ascii_lowercase().as_str() {
+            "raw" | "bin" | "binary" => Ok(Self::Raw),
+            "elf" => Ok(Self::Elf),
+            "ihex" | "hex" => Ok(Self::IntelHex),
+            "srec" | "s19" | "s28" | "s37" => Ok(Self::SRecord),
+            _ => Err(format!(
+                "unknown file format {input} (expected raw, elf, ihex or srec)"
+            )),
+        }
+    }
+}
+
+fn text<'a>(bytes: &'a [u8], context: &str) -> Result<std::borrow::Cow<'a, str>> {
+    if bytes.is_ascii() {
+        Ok(String::from_utf8_lossy(bytes))
+    } else {
+        Err(CliError::parse(context, "file is not ASCII text"))
+    }
+}
+
+/// Machine code placed at a load address.
+#[derive(Debug, Clone)]
+pub struct Region {
+    /// Section or segment name, used as the listing heading.
+    pub name: String,
+    /// Address of the first byte.
+    pub address: u64,
+    /// The code itself.
+    pub bytes: Vec<u8>,
+    /// Labels printed inside this region; also used to symbolize its
+    /// branch targets.
+    pub symbols: SymbolMap,
+}
+
+/// The target an image was built for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    /// Architecture name understood by the disassembly engine.
+    pub arch: &'static str,
+    /// ISA string selecting the decoder extensions, e.g. `rv64imafdc`.
+    pub isa: String,
+    /// Human-readable flags worth reporting, such as the float ABI.
+    pub flags: Vec<String>,
+}
+
+/// A loaded program image.
+#[derive(Debug, Clone)]
+pub struct Image {
+    /// Format description printed in the listing header, like objdump's
+    /// `file format elf64-littleriscv`.
+    pub format: String,
+    /// Target recorded in the file, if the format has one.
+    pub target: Option<Target>,
+    /// Code regions in file order.
+    pub regions: Vec<Region>,
+    /// Address execution starts at, if the file records one.
+    pub entry: Option<u64>,
+    /// Address ranges between regions that the file leaves undefined,
+    /// in ascending order. Only sparse formats report gaps.
+    pub gaps: Vec<Range<u64>>,
+}
+
+/// Sparse memory contents built from address-tagged records.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryMap {
+    /// Contiguous chunks keyed by start address; chunks never touch.
+    chunks: BTreeMap<u64, Vec<u8>>,
+}
+
+impl MemoryMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `data` at `address`, merging it with adjacent chunks.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `data` overlaps bytes that were already stored.
+    pub fn insert(&mut self, address: u64, data: &[u8]) -> std::result::Result<(), String> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let end = address
+            .checked_add(data.len() as u64)
+            .ok_or_else(|| format!("data at {address:#x} wraps around the address space"))?;
+        let overlap = || format!("data at {address:#x} overlaps earlier records");
+
+        let mut start = address;
+        let mut bytes = data.to_vec();
+        if let Some((&before, chunk)) = self.chunks.range(..=address).next_back() {
+            let before_end = before + chunk.len() as u64;
+            if before_end > address {
+                return Err(overlap());
+            }
+            if before_end == address {
+                let mut merged = self.chunks.remove(&before).unwrap_or_default();
+                merged.append(&mut bytes);
+                start = before;
+                bytes = merged;
+            }
+        }
+        if let Some((&after, _)) = self.chunks.range(address + 1..).next() {
+            if after < end {
+                return Err(overlap());
+            }
+            if after == end {
+                let mut tail = self.chunks.remove(&after).unwrap_or_default();
+                bytes.append(&mut tail);
+            }
+        }
+        self.chunks.insert(start, bytes);
+        Ok(())
+    }
+
+    /// Returns `true` when nothing has been stored.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Iterates over contiguous `(address, bytes)` chunks in address order.
+    pub fn chunks(&self) -> impl Iterator<Item = (u64, &[u8])> {
+        self.chunks
+            .iter()
+            .map(|(address, bytes)| (*address, bytes.as_slice()))
+    }
+
+    /// Turns the map into an image whose regions are the contiguous
+    /// chunks, named `.sec1`, `.sec2`, ... like objdump does for these
+    /// formats.
+    pub fn into_image(self, format: &str) -> Image {
+        let mut gaps = Vec::new();
+        let mut regions: Vec<Region> = Vec::new();
+        for (index, (address, bytes)) in self.chunks.into_iter().enumerate() {
+            if let Some(previous) = regions.last() {
+                gaps.push(previous.address + previous.bytes.len() as u64..address);
+            }
+            regions.push(Region {
+                name: format!(".sec{}", index + 1),
+                address,
+                bytes,
+                symbols: SymbolMap::new(),
+            });
+        }
+        Image {
+            format: format.to_string(),
+            target: None,
+            regions,
+            entry: None,
+            gaps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_map_merges_and_rejects_overlaps() {
+        let mut memory = MemoryMap::new();
+        memory.insert(0x10, &[3, 4]).unwrap();
+        memory.insert(0x0c, &[1, 2, 3, 4]).unwrap();
+        memory.insert(0x20, &[5]).unwrap();
+        memory.insert(0x0e, &[9, 9]).unwrap_err();
+        memory.insert(0x11, &[9]).unwrap_err();
+        memory.insert(0x1f, &[9, 9]).unwrap_err();
+
+        let chunks: Vec<_> = memory.chunks().collect();
+        assert_eq!(chunks, [(0x0c, &[1, 2, 3, 4, 3, 4][..]), (0x20, &[5][..])]);
+        let image = memory.into_image("ihex");
+        assert_eq!(
+            image.gaps,
+            [Range {
+                start: 0x12,
+                end: 0x20
+            }]
+        );
+    }
+
+    #[test]
+    fn test_detect_file_format() {
+        assert_eq!(FileFormat::detect(b"\x7fELF\x02\x01"), FileFormat::Elf);
+        assert_eq!(FileFormat::detect(b"\n:00000001FF\n"), FileFormat::IntelHex);
+        assert_eq!(FileFormat::detect(b"S00600004844521B"), FileFormat::SRecord);
+        assert_eq!(FileFormat::detect(b"\x13\x05\x15\x00"), FileFormat::Raw);
+        // Binaries that merely start like a record stay raw.
+        assert_eq!(FileFormat::detect(b":0\x05\x15\x00"), FileFormat::Raw);
+        assert_eq!(FileFormat::detect(b":00000001FE\n"), FileFormat::Raw);
+        assert_eq!(FileFormat::detect(b"S1\x13\x05\x15\x00"), FileFormat::Raw);
+        assert_eq!(FileFormat::detect(b"S00600004844521C"), FileFormat::Raw);
+        assert_eq!("s19".parse(), Ok(FileFormat::SRecord));
+        assert!("coff".parse::<FileFormat>().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Motorola S-record front-end.
+//!
+//! Each record is `S<type><count><address><data><checksum>`, where the
+//! count covers the address, data and checksum bytes and the checksum is
+//! the ones' complement of their sum. `S1`/`S2`/`S3` carry data at 16-,
+//! 24- and 32-bit addresses; the header (`S0`), record counts (`S5`,
+//! `S6`) and start addresses (`S7`-`S9`) carry no code.
+
+use super::{Image, MemoryMap};
+use crate::error::{CliError, Result};
+
+/// Loads S-record text into an image with one region per contiguous run
+/// of data.
+pub fn load(text: &str) -> Result<Image> {
+    let mut memory = MemoryMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let error =
+            |message: String| CliError::parse("s-record", format!("line {}: {message}", index + 1));
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (kind, record) = record(line).map_err(error)?;
+        let rest = &record[1..];
+
+        let address_size = match kind {
+            '1' => 2,
+            '2' => 3,
+            '3' => 4,
+            '0' | '5' | '6' | '7' | '8' | '9' => continue,
+            _ => return Err(error(format!("unknown record type S{kind}"))),
+        };
+        let Some((address, data)) = rest[..rest.len() - 1].split_at_checked(address_size) else {
+            return Err(error("record too short".to_string()));
+        };
+        let address = address
+            .iter()
+            .fold(0u64, |address, b| (address << 8) | u64::from(*b));
+        memory.insert(address, data).map_err(error)?;
+    }
+    if memory.is_empty() {
+        return Err(CliError::parse("s-record", "no data records"));
+    }
+    Ok(memory.into_image("srec"))
+}
+
+/// Returns `true` when `line` is a well-formed record with a valid
+/// checksum.
+pub fn is_record(line: &str) -> bool {
+    record(line.trim()).is_ok_and(|(kind, _)| kind.is_ascii_digit())
+}
+
+/// Splits one record line into its type and bytes, checking the count and
+/// checksum.
+fn record(line: &str) -> std::result::Result<(char, Vec<u8>), String> {
+    let (kind, record) = line
+        .strip_prefix('S')
+        .and_then(|rest| Some((rest.chars().next()?, rest.get(1..)?)))
+        .ok_or_else(|| "record does not start with 'S<type>'".to_string())?;
+    let record = hex::decode(record).map_err(|e| format!("invalid hex: {e}"))?;
+    let Some((&count, rest)) = record.split_first() else {
+        return Err("record too short".to_string());
+    };
+    if rest.len() != usize::from(count) {
+        return Err(format!("expected {count} bytes after the count"));
+    }
+    if record.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0xff {
+        return Err("checksum mismatch".to_string());
+    }
+    Ok((kind, record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Range;
+
+    #[test]
+    fn test_load_with_gaps() {
+        let image = load(
+            "S00600004844521B\n\
+             S107000013051500CB\n\
+             S20801000413051500C5\n\
+             S3090001000882801305D3\n\
+             S70500000000FA\n",
+        )
+        .unwrap();
+        assert_eq!(image.format, "srec");
+        let regions: Vec<_> = image
+            .regions
+            .iter()
+            .map(|region| (region.address, region.bytes.clone()))
+            .collect();
+        assert_eq!(
+            regions,
+            [
+                (0, vec![0x13, 0x05, 0x15, 0x00]),
+                (
+                    0x10004,
+                    vec![0x13, 0x05, 0x15, 0x00, 0x82, 0x80, 0x13, 0x05]
+                ),
+            ]
+        );
+        assert_eq!(
+            image.gaps,
+            [Range {
+                start: 4,
+                end: 0x10004
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_records() {
+        assert!(load("S107000013051500CC\n").is_err());
+        assert!(load("S407000013051500CB\n").is_err());
+        assert!(load("S00600004844521B\n").is_err());
+    }
+}
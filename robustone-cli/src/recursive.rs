@@ -0,0 +1,350 @@
+//! Recursive-descent disassembly (`--recursive`).
+//!
+//! A linear sweep decodes every byte as code, so jump tables, literal pools
+//! and padding inside `.text` come out as nonsense instructions that can
+//! also throw the following real instructions out of step. Recursive
+//! traversal instead decodes only what control flow reaches: starting from
+//! the entry points, it follows each path through fall-throughs, branches,
+//! jumps and calls, and stops at returns, indirect jumps and instructions
+//! that never continue. `auipc` + `jalr` pairs are resolved like direct
+//! jumps. Bytes no path reaches are reported as data.
+//!
+//! Targets outside the input are not followed; they usually belong to
+//! another section. Indirect jumps whose target is unknown are listed in
+//! [`Traversal::unresolved`].
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::asm::instruction_word;
+use crate::config::DisasmConfig;
+use crate::disasm::{DisassemblyEngine, DisassemblyResult, riscv_target};
+use crate::loader::{Image, Region};
+use robustone_core::Instruction;
+use robustone_riscv::targets::{self, Flow};
+
+/// Bytes per `.byte` line when data is shown in a [`DisassemblyResult`].
+const DATA_LINE: usize = 4;
+
+/// What a recursive traversal found.
+#[derive(Debug, Default)]
+pub struct Traversal {
+    /// Reached instructions keyed by address, in address order.
+    pub instructions: BTreeMap<u64, Instruction>,
+    /// How control leaves each reached instruction, with `auipc` + `jalr`
+    /// pairs resolved to jumps and calls.
+    pub flows: BTreeMap<u64, Flow>,
+    /// Address ranges no path reached, in address order.
+    pub data: Vec<Range<u64>>,
+    /// Addresses of indirect jumps and calls with an unknown target.
+    pub unresolved: Vec<u64>,
+    /// Entry points outside the input, invalid encodings reached by a path
+    /// and paths that run into the middle of another instruction.
+    pub errors: Vec<String>,
+}
+
+/// Ownership of each input byte during the traversal.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Claim {
+    Free,
+    /// First byte of an instruction.
+    Start,
+    /// Later byte of an instruction.
+    Inside,
+}
+
+impl DisassemblyEngine {
+    /// Disassemble `bytes`, loaded at `address`, by following control flow
+    /// from `entries`.
+    pub fn disassemble_recursive(
+        &self,
+        bytes: &[u8],
+        arch_name: &str,
+        address: u64,
+        entries: &[u64],
+    ) -> Traversal {
+        let (xlen, _) = riscv_target(arch_name);
+        let mut traversal = Traversal::default();
+        let mut claims = vec![Claim::Free; bytes.len()];
+        let offset_of = |target: u64| {
+            usize::try_from(target.wrapping_sub(address))
+                .ok()
+                .filter(|&offset| offset < bytes.len())
+        };
+
+        let mut pending: Vec<usize> = Vec::new();
+        for &entry in entries.iter().rev() {
+            match offset_of(entry) {
+                Some(offset) => pending.push(offset),
+                None => traversal
+                    .errors
+                    .push(format!("entry point {entry:#x} is outside the input")),
+            }
+        }
+
+        while let Some(mut offset) = pending.pop() {
+            // Previous word on this path, to resolve `auipc` + `jalr`.
+            let mut previous: Option<(u32, u64)> = None;
+            while offset < bytes.len() {
+                let current = address.wrapping_add(offset as u64);
+                match claims[offset] {
+                    Claim::Start => break,
+                    Claim::Inside => {
+                        traversal.errors.push(format!(
+                            "{current:#x}: jump into the middle of an instruction"
+                        ));
+                        break;
+                    }
+                    Claim::Free => {}
+                }
+                let (instruction, size) =
+                    match self.disassemble_single(&bytes[offset..], arch_name, current) {
+                        Ok((instruction, size)) if size > 0 && !instruction.is_unknown() => {
+                            (instruction, size)
+                        }
+                        _ => {
+                            traversal
+                                .errors
+                                .push(format!("{current:#x}: invalid instruction reached"));
+                            break;
+                        }
+                    };
+                let end = (offset + size).min(bytes.len());
+                if claims[offset..end]
+                    .iter()
+                    .any(|&claim| claim != Claim::Free)
+                {
+                    traversal.errors.push(format!(
+                        "{current:#x}: instruction overlaps another instruction"
+                    ));
+                    break;
+                }
+                claims[offset] = Claim::Start;
+                claims[offset + 1..end].fill(Claim::Inside);
+
+                let word = instruction_word(&bytes[offset..end], xlen);
+                traversal.instructions.insert(current, instruction);
+                let flow = word.map_or(Flow::Next, |word| {
+                    targets::control_flow(word, current, xlen)
+                });
+                let pair = previous.zip(word).and_then(|((auipc, at), word)| {
+                    targets::auipc_pair_target(auipc, word, at, xlen)
+                });
+                previous = word.map(|word| (word, current));
+
+                let flow = match (flow, pair) {
+                    (Flow::Indirect, Some(target)) => Flow::Jump(target),
+                    (Flow::IndirectCall, Some(target)) => Flow::Call(target),
+                    (flow, _) => flow,
+                };
+                traversal.flows.insert(current, flow);
+
+                let follow = match flow {
+                    Flow::Next => true,
+                    Flow::Branch(target) | Flow::Call(target) => {
+                        pending.extend(offset_of(target));
+                        true
+                    }
+                    Flow::Jump(target) => {
+                        pending.extend(offset_of(target));
+                        false
+                    }
+                    Flow::Indirect => {
+                        traversal.unresolved.push(current);
+                        false
+                    }
+                    Flow::IndirectCall => {
+                        traversal.unresolved.push(current);
+                        true
+                    }
+                    Flow::Return | Flow::Stop => false,
+                };
+                if !follow {
+                    break;
+                }
+                offset = end;
+            }
+        }
+
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let run = claims[offset..]
+                .iter()
+                .position(|&claim| (claim == Claim::Free) != (claims[offset] == Claim::Free))
+                .map_or(bytes.len(), |run| offset + run);
+            if claims[offset] == Claim::Free {
+                let start = address.wrapping_add(offset as u64);
+                traversal
+                    .data
+                    .push(start..start.wrapping_add((run - offset) as u64));
+            }
+            offset = run;
+        }
+        traversal
+    }
+}
+
+/// Entry points of `region` in `image`: the image entry point, the region's
+/// labels and `extra` addresses that fall inside it, or its start when none
+/// do.
+pub fn region_entries(image: &Image, region: &Region, extra: &[u64]) -> Vec<u64> {
+    let end = region.address.wrapping_add(region.bytes.len() as u64);
+    let mut entries: Vec<u64> = image
+        .entry
+        .into_iter()
+        .chain(region.symbols.iter().map(|(address, _)| address))
+        .chain(extra.iter().copied())
+        .filter(|address| (region.address..end).contains(address))
+        .collect();
+    if entries.is_empty() {
+        entries.push(region.address);
+    }
+    entries
+}
+
+impl Traversal {
+    /// Lay the traversal out as a linear result over `config.hex_bytes`:
+    /// the instructions in address order, with the data in between shown
+    /// as `.byte` lines, and the errors.
+    pub fn into_result(self, config: &DisasmConfig) -> DisassemblyResult {
+        let start = config.start_address;
+        let mut result = DisassemblyResult::new(start, config.arch_name().to_string());
+        let mut data = self.data.into_iter().peekable();
+        let mut instructions = self.instructions.into_values().peekable();
+        loop {
+            let next_data = data.peek().map(|range| range.start);
+            let next_instruction = instructions.peek().map(|instruction| instruction.address);
+            match (next_data, next_instruction) {
+                (Some(at), Some(next)) if at > next => {
+                    result.add_instruction(instructions.next().expect("peeked"));
+                }
+                (Some(_), _) => {
+                    let range = data.next().expect("peeked");
+                    let offset = range.start.wrapping_sub(start) as usize;
+                    let len = range.end.wrapping_sub(range.start) as usize;
+                    for (index, chunk) in config.hex_bytes[offset..offset + len]
+                        .chunks(DATA_LINE)
+                        .enumerate()
+                    {
+                        result.add_instruction(data_line(
+                            range.start.wrapping_add((index * DATA_LINE) as u64),
+                            chunk,
+                        ));
+                    }
+                }
+                (None, Some(_)) => result.add_instruction(instructions.next().expect("peeked")),
+                (None, None) => break,
+            }
+        }
+        for error in self.errors {
+            result.add_error(error);
+        }
+        result
+    }
+}
+
+/// A `.byte` pseudo-instruction holding `bytes`, as cstool prints skipped
+/// data.
+fn data_line(address: u64, bytes: &[u8]) -> Instruction {
+    let operands = bytes
+        .iter()
+        .map(|b| format!("0x{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Instruction::new(address, bytes.to_vec(), ".byte".to_string(), operands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{Cli, ValidatedConfig};
+    use crate::config::OutputFormat;
+    use clap::Parser;
+
+    /// `jal ra, f ; j 1f ; <data> ; 1: ret ; f: addi a0, a0, 1 ;
+    /// beqz a0, 2f ; jr t0 ; 2: c.ret ; <data>` at 0x1000.
+    const PROGRAM: [u8; 32] = [
+        0xef, 0x00, 0x00, 0x01, 0x6f, 0x00, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0x67, 0x80, 0x00,
+        0x00, 0x13, 0x05, 0x15, 0x00, 0x63, 0x04, 0x05, 0x00, 0x67, 0x80, 0x02, 0x00, 0x82, 0x80,
+        0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_traversal() {
+        let engine = DisassemblyEngine::new("riscv64");
+        let traversal = engine.disassemble_recursive(&PROGRAM, "riscv64", 0x1000, &[0x1000]);
+        let reached: Vec<u64> = traversal.instructions.keys().copied().collect();
+        assert_eq!(
+            reached,
+            [0x1000, 0x1004, 0x100c, 0x1010, 0x1014, 0x1018, 0x101c]
+        );
+        assert_eq!(traversal.data, [0x1008..0x100c, 0x101e..0x1020]);
+        assert_eq!(traversal.unresolved, [0x1018]);
+        assert!(traversal.errors.is_empty());
+
+        // An entry inside the jal, and one past the end.
+        let traversal =
+            engine.disassemble_recursive(&PROGRAM, "riscv64", 0x1000, &[0x1000, 0x1002, 0x2000]);
+        assert_eq!(
+            traversal.errors,
+            [
+                "entry point 0x2000 is outside the input",
+                "0x1002: jump into the middle of an instruction",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_result() {
+        let config = ValidatedConfig {
+            arch_mode: Some("riscv64".to_string()),
+            hex_code: Some(PROGRAM.to_vec()),
+            address: Some(0x1000),
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+            recursive: Some(Vec::new()),
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+        let config = DisasmConfig::from_validated_config(config).unwrap();
+        let result = DisassemblyEngine::new("riscv64")
+            .disassemble(&config)
+            .unwrap();
+        let lines: Vec<(u64, &str, &str)> = result
+            .instructions
+            .iter()
+            .map(|i| (i.address, i.mnemonic.as_str(), i.operands.as_str()))
+            .collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[2], (0x1008, ".byte", "0xff, 0xff, 0xff, 0xff"));
+        assert_eq!(lines[3], (0x100c, "jalr", "0(ra)"));
+        assert_eq!(lines[8], (0x101e, ".byte", "0x00, 0x00"));
+        assert_eq!(result.bytes_processed, PROGRAM.len());
+    }
+
+    #[test]
+    fn test_entry_arguments() {
+        // Entry points may have any number of digits, like the start address.
+        let cli = Cli::try_parse_from([
+            "robustone",
+            "riscv64",
+            "13051500",
+            "--recursive",
+            "--entry",
+            "0x4",
+            "--entry",
+            "8",
+        ])
+        .unwrap();
+        assert_eq!(cli.validate().unwrap().recursive, Some(vec![0x4, 0x8]));
+    }
+}
@@ -0,0 +1,268 @@
+//! Interactive mode (`robustone repl`).
+//!
+//! Each line is either a command starting with `:` or hex machine code,
+//! which is decoded at once at the current address. The address then moves
+//! past the decoded bytes, so words typed one after another read like a
+//! listing. The architecture and display options persist between lines:
+//!
+//! ```text
+//! riscv64@0x0> :arch riscv32+isa=rv32imac
+//! riscv32+isa=rv32imac@0x0> :addr 0x80000000
+//! riscv32+isa=rv32imac@0x80000000> 13055000
+//! 80000000  13 05 50 00  addi    a0, zero, 5
+//! ```
+
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+
+use crate::arch::ArchitectureSpec;
+use crate::asm::format_asm;
+use crate::config::{DisasmConfig, OutputConfig, OutputFormat};
+use crate::disasm::{DisassemblyEngine, DisassemblyFormatter};
+use crate::error::{CliError, Result};
+use crate::input;
+use crate::utils::parse_address;
+
+/// Summary printed by `:help`.
+const HELP: &str = "\
+<hex>                  decode machine code at the current address
+:arch [SPEC]           show or set the architecture, e.g. riscv32+isa=rv32imac
+:addr [ADDR]           show or set the address of the next instruction
+:detail on|off         print cstool's operand detail (-d)
+:unsigned on|off       print immediates as unsigned (-u)
+:skipdata on|off       skip undecodable bytes (-s)
+:format FORMAT         print text, json, jsonl or asm
+:help                  show this help
+:quit                  leave (also Ctrl-D)
+";
+
+/// What the session wants done after a line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Print this text and read the next line.
+    Output(String),
+    /// Leave the REPL.
+    Quit,
+}
+
+/// REPL state: the configuration each line is decoded with, and an engine
+/// built for it.
+pub struct Session {
+    config: DisasmConfig,
+    engine: DisassemblyEngine,
+}
+
+impl Session {
+    /// Start a session; `config` supplies the initial architecture, address
+    /// and display options, its bytes are ignored.
+    pub fn new(config: DisasmConfig) -> Result<Self> {
+        let engine = Self::engine(&config)?;
+        Ok(Self { config, engine })
+    }
+
+    /// Build the engine for `config`, with its symbols.
+    fn engine(config: &DisasmConfig) -> Result<DisassemblyEngine> {
+        let mut engine = DisassemblyEngine::for_config(config, config.handler_options())
+            .map_err(|e| CliError::Disassembly(e.to_string()))?;
+        if let Some(symbols) = &config.symbols {
+            engine
+                .set_symbolizer(config.arch_name(), Box::new(symbols.clone()))
+                .map_err(|e| CliError::Disassembly(e.to_string()))?;
+        }
+        Ok(engine)
+    }
+
+    /// Rebuild the engine after the options changed, keeping the old state
+    /// when the new one is unusable.
+    fn update(&mut self, change: impl FnOnce(&mut DisasmConfig)) -> Result<()> {
+        let mut config = self.config.clone();
+        change(&mut config);
+        self.engine = Self::engine(&config)?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// The architecture with its modifiers, as accepted by `:arch`.
+    pub fn arch(&self) -> String {
+        let spec = &self.config.arch_spec;
+        let mut arch = spec.arch.name().to_string();
+        for option in &spec.options {
+            arch.push('+');
+            arch.push_str(option);
+        }
+        arch
+    }
+
+    /// Address of the next decoded instruction.
+    pub fn address(&self) -> u64 {
+        self.config.start_address
+    }
+
+    /// The prompt, showing the architecture and address.
+    pub fn prompt(&self) -> String {
+        format!("{}@{:#x}> ", self.arch(), self.address())
+    }
+
+    /// Handle one input line.
+    pub fn execute(&mut self, line: &str) -> Result<Outcome> {
+        let line = line.trim();
+        let Some(command) = line.strip_prefix(':') else {
+            return self.decode(line).map(Outcome::Output);
+        };
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command, ""),
+        };
+
+        match (name, argument) {
+            ("q" | "quit" | "exit", _) => return Ok(Outcome::Quit),
+            ("h" | "help", _) => return Ok(Outcome::Output(HELP.to_string())),
+            ("arch", "") => {}
+            ("arch", spec) => {
+                let spec = ArchitectureSpec::parse(spec)
+                    .map_err(|e| CliError::parse("architecture", e.to_string()))?;
+                self.update(|config| config.arch_spec = spec)?;
+            }
+            ("addr", "") => {}
+            ("addr", address) => self.config.start_address = parse_address(address)?,
+            ("detail", value) => {
+                let on = parse_switch(name, value)?;
+                self.update(|config| config.display_options.detailed = on)?;
+            }
+            ("unsigned", value) => {
+                let on = parse_switch(name, value)?;
+                self.update(|config| config.display_options.unsigned_immediate = on)?;
+            }
+            ("skipdata", value) => self.config.skip_data = parse_switch(name, value)?,
+            ("format", format) => {
+                let format: OutputFormat = format
+                    .parse()
+                    .map_err(|e: String| CliError::validation("format", e))?;
+                self.update(|config| config.display_options.format = format)?;
+            }
+            _ => {
+                return Err(CliError::InvalidCommand(format!(
+                    ":{name} (type :help for the list of commands)"
+                )));
+            }
+        }
+        Ok(Outcome::Output(format!(
+            "{} at {:#x}\n",
+            self.arch(),
+            self.address()
+        )))
+    }
+
+    /// Decode `hex` at the current address and move past it.
+    fn decode(&mut self, hex: &str) -> Result<String> {
+        self.config.hex_bytes = input::parse_hex_text(hex)?;
+        self.config.validate_for_disassembly()?;
+
+        let output = if self.config.display_options.format == OutputFormat::Asm {
+            format_asm(&self.config).map_err(|e| CliError::Disassembly(e.to_string()))?
+        } else {
+            let result = self
+                .engine
+                .disassemble(&self.config)
+                .map_err(|e| CliError::Disassembly(e.to_string()))?;
+            let output_config = OutputConfig::from_display_options(&self.config.display_options);
+            DisassemblyFormatter::new(output_config).format(&result)
+        };
+        let consumed = self.config.hex_bytes.len() as u64;
+        self.config.start_address = self.config.start_address.wrapping_add(consumed);
+        Ok(output)
+    }
+}
+
+/// Parses the `on`/`off` argument of `:name`.
+fn parse_switch(name: &str, value: &str) -> Result<bool> {
+    match value {
+        "on" | "1" | "true" => Ok(true),
+        "off" | "0" | "false" => Ok(false),
+        _ => Err(CliError::validation(name, "expected on or off")),
+    }
+}
+
+/// Run the REPL on the terminal until `:quit` or end of input.
+pub fn run(config: DisasmConfig) -> Result<()> {
+    let mut session = Session::new(config)?;
+    let mut editor = DefaultEditor::new().map_err(|e| CliError::Io(e.to_string()))?;
+    loop {
+        let line = match editor.readline(&session.prompt()) {
+            Ok(line) => line,
+            // Ctrl-C abandons the line, like a shell.
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(CliError::Io(e.to_string())),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.as_str());
+        match session.execute(&line) {
+            Ok(Outcome::Output(output)) => print!("{output}"),
+            Ok(Outcome::Quit) => break,
+            Err(e) => eprintln!("Error: {e}"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::ValidatedConfig;
+
+    #[test]
+    fn test_session() {
+        let config = ValidatedConfig {
+            arch_mode: Some("riscv64".to_string()),
+            hex_code: Some(Vec::new()),
+            address: None,
+            detailed: false,
+            alias_regs: false,
+            real_detail: false,
+            skip_data: false,
+            unsigned_immediate: false,
+            format: OutputFormat::Text,
+            jobs: 1,
+            recursive: None,
+            cfg: None,
+            xrefs: false,
+            constants: false,
+            symbols: None,
+            image: None,
+            version: false,
+        };
+        let mut session =
+            Session::new(DisasmConfig::from_validated_config(config).unwrap()).unwrap();
+        assert_eq!(session.prompt(), "riscv64@0x0> ");
+
+        let output = |session: &mut Session, line: &str| match session.execute(line).unwrap() {
+            Outcome::Output(output) => output,
+            Outcome::Quit => panic!("unexpected quit"),
+        };
+        assert_eq!(
+            output(&mut session, ":arch riscv32+isa=rv32imac"),
+            "riscv32+isa=rv32imac at 0x0\n"
+        );
+        output(&mut session, ":addr 0x80000000");
+        assert_eq!(output(&mut session, "0d05"), "80000000    c.addi\ta0, 3\n");
+        // Decoding continues after the previous bytes.
+        assert_eq!(
+            output(&mut session, "33057502"),
+            "80000002    mul\ta0, a0, t2\n"
+        );
+        output(&mut session, ":detail on");
+        assert!(output(&mut session, "33057502").contains("\top_count: 3\n"));
+
+        // A bad command leaves the state alone.
+        assert!(session.execute(":arch riscv32+isa=rv64gc").is_err());
+        assert!(session.execute(":detail maybe").is_err());
+        assert!(session.execute(":frobnicate").is_err());
+        assert_eq!(session.prompt(), "riscv32+isa=rv32imac@0x8000000a> ");
+        output(&mut session, ":addr 0x100");
+        assert_eq!(session.prompt(), "riscv32+isa=rv32imac@0x100> ");
+        assert_eq!(session.execute(":quit").unwrap(), Outcome::Quit);
+    }
+}
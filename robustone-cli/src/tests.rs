@@ -226,6 +226,7 @@ fn test_architecture_implementation_status() {
     // Implementation status should distinguish supported and pending targets.
     assert!(Architecture::Riscv32.is_implemented());
     assert!(Architecture::Riscv64.is_implemented());
+    assert!(Architecture::Aarch64.is_implemented());
     assert!(!Architecture::Arm.is_implemented());
     assert!(!Architecture::X86_32.is_implemented());
 }
@@ -122,14 +122,19 @@ pub fn parse_hex_code(input: &str) -> Result<Vec<String>> {
 }
 
 /// Parse a hexadecimal address with validation.
+///
+/// Unlike hex code, an address may have any number of digits (`0x4`).
 pub fn parse_address(input: &str) -> Result<u64> {
-    if input.trim().is_empty() {
+    let trimmed = input.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if digits.is_empty() {
         return Err(CliError::validation("address", "Empty address provided"));
     }
 
-    let normalized = normalize_hex_token(input)?;
-
-    u64::from_str_radix(&normalized[2..], 16)
+    u64::from_str_radix(digits, 16)
         .map_err(|_| CliError::validation("address", "Invalid hexadecimal address format"))
 }
 
@@ -259,6 +264,9 @@ mod tests {
     fn test_parse_address() {
         assert_eq!(parse_address("0x1000").unwrap(), 0x1000);
         assert_eq!(parse_address("1000").unwrap(), 0x1000);
+        assert_eq!(parse_address("0x100").unwrap(), 0x100);
+        assert!(parse_address("0x").is_err());
+        assert!(parse_address("0x10g").is_err());
     }
 
     #[test]
@@ -0,0 +1,158 @@
+//! Cross-reference reports (`--xrefs`).
+//!
+//! The input, or every region of a loaded image, is indexed with
+//! [`XrefIndex::scan`] and the references are printed grouped by the
+//! address they point at, with symbols where known:
+//!
+//! ```text
+//! 0x1010 <f>:
+//!     call      0x1000 <_start>
+//! 0x200c:
+//!     read      0x1008 <_start+0x8>
+//! ```
+//!
+//! With `--format json` the report is `{"xrefs": [{"from", "to", "kind"}]}`
+//! ordered by `from`; `--format jsonl` prints one such object per line.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::config::{DisasmConfig, OutputFormat};
+use crate::disasm::config_target;
+use crate::loader::Image;
+use robustone_core::{DisasmError, SymbolMap, Symbolizer};
+use robustone_riscv::decoder::RiscVDecoder;
+use robustone_riscv::extensions::Extensions;
+use robustone_riscv::xrefs::{Xref, XrefIndex};
+
+/// Index the references made by `config.hex_bytes`.
+pub fn scan(config: &DisasmConfig) -> Result<XrefIndex, DisasmError> {
+    let (xlen, extensions) = config_target(config)?;
+    let decoder = RiscVDecoder::new(xlen, extensions);
+    Ok(XrefIndex::scan(
+        &decoder,
+        &config.hex_bytes,
+        config.start_address,
+    ))
+}
+
+/// Index the references made by every region of `image`, and return them
+/// with the image symbols and those of `config`.
+pub fn scan_image(
+    image: &Image,
+    config: &DisasmConfig,
+) -> Result<(XrefIndex, SymbolMap), DisasmError> {
+    let (xlen, extensions) = match &image.target {
+        Some(target) => Extensions::from_isa(&target.isa).ok_or_else(|| {
+            DisasmError::UnsupportedArchitecture(format!("unsupported ISA {}", target.isa))
+        })?,
+        None => config_target(config)?,
+    };
+    let decoder = RiscVDecoder::new(xlen, extensions);
+    let mut index = XrefIndex::new();
+    let mut symbols = config.symbols.clone().unwrap_or_default();
+    for region in &image.regions {
+        index.extend(XrefIndex::scan(&decoder, &region.bytes, region.address));
+        for (address, name) in region.symbols.iter() {
+            symbols.insert(address, name);
+        }
+    }
+    Ok((index, symbols))
+}
+
+/// One reference in the JSON report.
+#[derive(Serialize)]
+struct Record {
+    from: u64,
+    to: u64,
+    kind: &'static str,
+}
+
+impl From<&Xref> for Record {
+    fn from(xref: &Xref) -> Self {
+        Self {
+            from: xref.from,
+            to: xref.to,
+            kind: xref.kind.name(),
+        }
+    }
+}
+
+/// Print `index` in `format`, naming addresses from `symbols`.
+pub fn format_report(
+    index: &XrefIndex,
+    symbols: Option<&SymbolMap>,
+    format: OutputFormat,
+) -> String {
+    let mut out = String::new();
+    match format {
+        OutputFormat::Json => {
+            let xrefs: Vec<Record> = index.iter().map(Record::from).collect();
+            out = serde_json::to_string_pretty(&serde_json::json!({ "xrefs": xrefs }))
+                .unwrap_or_default();
+            out.push('\n');
+        }
+        OutputFormat::JsonLines => {
+            for xref in index.iter() {
+                out.push_str(&serde_json::to_string(&Record::from(xref)).unwrap_or_default());
+                out.push('\n');
+            }
+        }
+        OutputFormat::Text | OutputFormat::Asm => {
+            let name = |address: u64, out: &mut String| {
+                let _ = write!(out, "{address:#x}");
+                if let Some(symbols) = symbols {
+                    out.push(' ');
+                    if !symbols.write_symbol(address, out) {
+                        out.pop();
+                    }
+                }
+            };
+            for (target, xrefs) in index.targets() {
+                name(target, &mut out);
+                out.push_str(":\n");
+                for xref in xrefs {
+                    let _ = write!(out, "    {:<9} ", xref.kind.name());
+                    name(xref.from, &mut out);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robustone_riscv::xrefs::XrefKind;
+
+    #[test]
+    fn test_format_report() {
+        let mut index = XrefIndex::new();
+        index.add(Xref {
+            from: 0x1000,
+            to: 0x1010,
+            kind: XrefKind::Call,
+        });
+        index.add(Xref {
+            from: 0x1008,
+            to: 0x200c,
+            kind: XrefKind::Read,
+        });
+        let mut symbols = SymbolMap::new();
+        symbols.insert(0x1000, "_start");
+        symbols.insert(0x1010, "f");
+        assert_eq!(
+            format_report(&index, Some(&symbols), OutputFormat::Text),
+            "0x1010 <f>:\n    call      0x1000 <_start>\n\
+             0x200c <f+0xffc>:\n    read      0x1008 <_start+0x8>\n"
+        );
+        assert_eq!(
+            format_report(&index, None, OutputFormat::JsonLines),
+            "{\"from\":4096,\"to\":4112,\"kind\":\"call\"}\n\
+             {\"from\":4104,\"to\":8204,\"kind\":\"read\"}\n"
+        );
+    }
+}
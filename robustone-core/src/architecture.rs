@@ -3,8 +3,9 @@
 //! This module provides utility functions for working with different
 //! instruction set architectures in a consistent way.
 
-use std::fmt;
-use std::fmt::Display;
+use alloc::string::String;
+use core::fmt;
+use core::fmt::Display;
 
 #[derive(Debug, PartialEq)]
 pub enum Architecture {
@@ -39,6 +39,15 @@
 //!     }
 //! }
 //! ```
+//!
+//! # `no_std` Support
+//!
+//! Disabling the default `std` feature builds the crate as `#![no_std]`; only
+//! `alloc` is required, so it can be embedded in bare-metal environments.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod architecture;
 pub mod traits;
@@ -52,17 +61,28 @@ pub mod utils;
 /// using the disassembly engine.
 pub mod prelude {
     pub use crate::architecture::{Architecture, is_address_aligned};
-    pub use crate::traits::{ArchitectureHandler, BasicInstructionDetail, Detail};
-    pub use crate::types::{DisasmError, Instruction};
+    pub use crate::traits::{ArchitectureHandler, BasicInstructionDetail, Detail, Symbolizer};
+    pub use crate::types::{
+        DisasmError, HandlerOption, HandlerOptions, Instruction, InstructionSlot, SymbolMap,
+    };
     pub use crate::utils::{Endianness, HexParser};
 }
 
 pub use traits::ArchitectureHandler;
+pub use traits::Symbolizer;
 pub use traits::instruction::Detail;
 pub use types::error::DisasmError;
 pub use types::instruction::Instruction;
+pub use types::operand::{Operand, OperandAccess};
+pub use types::options::{HandlerOption, HandlerOptions};
+pub use types::slot::{DeferredOperands, InstructionSlot, LiteInstruction, SlotOperand};
+pub use types::symbols::SymbolMap;
 
 use crate::utils::HexParser;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
 
 /// Runtime dispatcher that selects the appropriate architecture handler.
 ///
@@ -132,6 +152,7 @@ impl ArchitectureDispatcher {
             Err(_) => {
                 // If parsing fails, create a minimal unknown instruction
                 return Instruction {
+                    id: 0,
                     address: 0,
                     bytes: vec![],
                     mnemonic: "unknown".to_string(),
@@ -149,6 +170,7 @@ impl ArchitectureDispatcher {
                 // Create an unknown instruction with the parsed bytes
                 let size = bytes.len();
                 Instruction {
+                    id: 0,
                     address: 0,
                     bytes,
                     mnemonic: "unknown".to_string(),
@@ -215,6 +237,105 @@ impl ArchitectureDispatcher {
         Err(DisasmError::UnsupportedArchitecture(arch.to_string()))
     }
 
+    /// Disassembles raw instruction bytes into a reusable slot.
+    ///
+    /// Behaves like [`disassemble_bytes`](Self::disassemble_bytes) but writes
+    /// the decoded instruction into `slot` instead of allocating a new
+    /// `Instruction`. Reusing one slot across a whole buffer avoids the
+    /// per-instruction allocations of the owned API, and handlers that defer
+    /// operand formatting only build the operand text when
+    /// [`InstructionSlot::operands`] is called.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes consumed from the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedArchitecture` if no handler supports
+    /// the specified architecture, or the handler's decoding error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use robustone_core::{ArchitectureDispatcher, InstructionSlot};
+    /// let dispatcher = ArchitectureDispatcher::default();
+    /// let mut slot = InstructionSlot::new();
+    /// let code = [0x13, 0x05, 0x00, 0x00, 0x93, 0x05, 0x00, 0x00];
+    /// let mut offset = 0;
+    /// while offset < code.len() {
+    ///     match dispatcher.disassemble_into(&code[offset..], "riscv32", offset as u64, &mut slot) {
+    ///         Ok(size) => {
+    ///             println!("{} {}", slot.mnemonic(), slot.operands());
+    ///             offset += size;
+    ///         }
+    ///         Err(e) => {
+    ///             eprintln!("Error: {:?}", e);
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn disassemble_into(
+        &self,
+        bytes: &[u8],
+        arch: &str,
+        address: u64,
+        slot: &mut InstructionSlot,
+    ) -> Result<usize, DisasmError> {
+        match self.get_handler(arch) {
+            Some(handler) => handler.disassemble_into(bytes, address, slot),
+            None => Err(DisasmError::UnsupportedArchitecture(arch.to_string())),
+        }
+    }
+
+    /// Disassembles a whole buffer into `(address, size, mnemonic, op_str)`
+    /// tuples, like Capstone's `disasm_lite`.
+    ///
+    /// Decoding reuses a single [`InstructionSlot`] and never builds
+    /// [`Instruction`] values; configure the handler with
+    /// `HandlerOption::Detail(false)` to also skip register and operand
+    /// bookkeeping. As in Capstone, decoding stops at the first byte sequence
+    /// that is not a valid instruction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedArchitecture` if no handler supports
+    /// the specified architecture.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use robustone_core::ArchitectureDispatcher;
+    /// let dispatcher = ArchitectureDispatcher::default();
+    /// assert!(dispatcher.disassemble_lite(&[0x13, 0x00, 0x00, 0x00], "mips", 0).is_err());
+    /// ```
+    pub fn disassemble_lite(
+        &self,
+        bytes: &[u8],
+        arch: &str,
+        address: u64,
+    ) -> Result<Vec<LiteInstruction>, DisasmError> {
+        let handler = self
+            .get_handler(arch)
+            .ok_or_else(|| DisasmError::UnsupportedArchitecture(arch.to_string()))?;
+
+        let mut slot = InstructionSlot::new();
+        let mut instructions = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let address = address.wrapping_add(offset as u64);
+            match handler.disassemble_into(&bytes[offset..], address, &mut slot) {
+                Ok(size) if size > 0 && !slot.is_unknown() => {
+                    instructions.push(slot.to_lite());
+                    offset += size;
+                }
+                _ => break,
+            }
+        }
+        Ok(instructions)
+    }
+
     /// Returns a list of all registered architecture names.
     ///
     /// This method returns the canonical names of all architectures that
@@ -290,6 +411,58 @@ impl ArchitectureDispatcher {
             .find(|h| h.supports(arch_name))
             .map(|h| h.as_ref())
     }
+
+    /// Gets a mutable handler for a specific architecture, if available.
+    pub fn get_handler_mut(&mut self, arch_name: &str) -> Option<&mut dyn ArchitectureHandler> {
+        match self.handlers.iter_mut().find(|h| h.supports(arch_name)) {
+            Some(handler) => Some(handler.as_mut()),
+            None => None,
+        }
+    }
+
+    /// Changes a runtime option on the handler for `arch_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedArchitecture` if no handler supports
+    /// the architecture, or the handler's `DisasmError::UnsupportedOption`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use robustone_core::ArchitectureDispatcher;
+    /// use robustone_core::types::options::HandlerOption;
+    /// let mut dispatcher = ArchitectureDispatcher::default();
+    /// assert!(dispatcher.set_option("riscv32", HandlerOption::Detail(false)).is_err());
+    /// ```
+    pub fn set_option(
+        &mut self,
+        arch_name: &str,
+        option: HandlerOption,
+    ) -> Result<(), DisasmError> {
+        match self.get_handler_mut(arch_name) {
+            Some(handler) => handler.set_option(option),
+            None => Err(DisasmError::UnsupportedArchitecture(arch_name.to_string())),
+        }
+    }
+
+    /// Installs `symbolizer` on the handler for `arch_name`, or removes the
+    /// current one with `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedArchitecture` if no handler supports
+    /// the architecture, or the handler's `DisasmError::UnsupportedOption`.
+    pub fn set_symbolizer(
+        &mut self,
+        arch_name: &str,
+        symbolizer: Option<Box<dyn Symbolizer>>,
+    ) -> Result<(), DisasmError> {
+        match self.get_handler_mut(arch_name) {
+            Some(handler) => handler.set_symbolizer(symbolizer),
+            None => Err(DisasmError::UnsupportedArchitecture(arch_name.to_string())),
+        }
+    }
 }
 
 impl Default for ArchitectureDispatcher {
@@ -325,4 +498,16 @@ mod tests {
         assert_eq!(instruction.bytes, vec![0x12, 0x34]);
         assert_eq!(instruction.size, 2);
     }
+
+    #[test]
+    fn test_disassemble_into_unsupported_architecture() {
+        let dispatcher = ArchitectureDispatcher::default();
+        let mut slot = InstructionSlot::new();
+
+        let result = dispatcher.disassemble_into(&[0x13, 0x00, 0x00, 0x00], "mips", 0, &mut slot);
+        assert!(matches!(
+            result,
+            Err(DisasmError::UnsupportedArchitecture(arch)) if arch == "mips"
+        ));
+    }
 }
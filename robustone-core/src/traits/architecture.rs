@@ -5,6 +5,12 @@
 
 use crate::types::error::DisasmError;
 use crate::types::instruction::Instruction;
+use crate::types::options::{HandlerOption, HandlerOptions};
+use crate::types::slot::InstructionSlot;
+use alloc::boxed::Box;
+use alloc::format;
+
+use super::Symbolizer;
 
 /// Trait that all architecture-specific disassemblers must implement.
 ///
@@ -24,6 +30,14 @@ use crate::types::instruction::Instruction;
 /// * `name`: Returns the canonical name of the architecture
 /// * `supports`: Checks if the handler supports a given architecture name
 ///
+/// # Provided Methods
+///
+/// * `disassemble_into`: Decodes into a reusable [`InstructionSlot`]
+/// * `options` / `set_options` / `set_option`: Runtime configuration, like
+///   Capstone's `cs_option`
+/// * `set_symbolizer`: Installs a [`Symbolizer`] for branch targets and
+///   PC-relative addresses
+///
 /// # Example Implementation
 ///
 /// ```rust
@@ -72,6 +86,96 @@ pub trait ArchitectureHandler: Sync {
     /// - An architecture-specific error occurs
     fn disassemble(&self, bytes: &[u8], addr: u64) -> Result<(Instruction, usize), DisasmError>;
 
+    /// Disassembles a single instruction into a caller-owned slot.
+    ///
+    /// This is the allocation-conscious counterpart of [`disassemble`]: the
+    /// slot is reset and overwritten in place so it can be reused for every
+    /// instruction in a buffer. The default implementation falls back to
+    /// [`disassemble`] and copies the result; handlers should override it to
+    /// fill the slot directly, ideally recording
+    /// [`DeferredOperands`](crate::types::slot::DeferredOperands) so the
+    /// operand text is only formatted when it is read.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes consumed from the input.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`disassemble`]. Instructions longer than
+    /// [`MAX_INSTRUCTION_SIZE`](crate::types::slot::MAX_INSTRUCTION_SIZE)
+    /// produce a `DisasmError::DecodingError`.
+    ///
+    /// [`disassemble`]: ArchitectureHandler::disassemble
+    fn disassemble_into(
+        &self,
+        bytes: &[u8],
+        addr: u64,
+        slot: &mut InstructionSlot,
+    ) -> Result<usize, DisasmError> {
+        let (instruction, size) = self.disassemble(bytes, addr)?;
+        slot.load_instruction(&instruction).map_err(|len| {
+            DisasmError::DecodingError(format!("instruction too long for slot: {len} bytes"))
+        })?;
+        Ok(size)
+    }
+
+    /// Returns the options currently in effect.
+    ///
+    /// The default implementation reports [`HandlerOptions::default`], which
+    /// is what handlers without configurable output produce.
+    fn options(&self) -> HandlerOptions {
+        HandlerOptions::default()
+    }
+
+    /// Replaces all runtime options at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedOption` when the handler cannot honour
+    /// the requested options; the previous options stay in effect. The
+    /// default implementation only accepts the defaults.
+    fn set_options(&mut self, options: HandlerOptions) -> Result<(), DisasmError> {
+        if options == HandlerOptions::default() {
+            Ok(())
+        } else {
+            Err(DisasmError::UnsupportedOption(format!(
+                "{} handler has no configurable options",
+                self.name()
+            )))
+        }
+    }
+
+    /// Changes a single runtime option, keeping the others.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set_options`](ArchitectureHandler::set_options).
+    fn set_option(&mut self, option: HandlerOption) -> Result<(), DisasmError> {
+        let options = self.options().with(option);
+        self.set_options(options)
+    }
+
+    /// Installs a symbolizer consulted for branch targets and PC-relative
+    /// addresses, or removes it with `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::UnsupportedOption` when the handler cannot
+    /// print symbols. The default implementation only accepts `None`.
+    fn set_symbolizer(
+        &mut self,
+        symbolizer: Option<Box<dyn Symbolizer>>,
+    ) -> Result<(), DisasmError> {
+        match symbolizer {
+            None => Ok(()),
+            Some(_) => Err(DisasmError::UnsupportedOption(format!(
+                "{} handler does not support symbolization",
+                self.name()
+            ))),
+        }
+    }
+
     /// Returns the canonical name of this architecture.
     ///
     /// This should return the primary, canonical name for the architecture.
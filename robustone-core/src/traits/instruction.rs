@@ -1,11 +1,15 @@
 //! Instruction detail traits.
 
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::types::operand::Operand;
 
 /// Trait for architecture-specific instruction details.
 ///
 /// All architecture-specific detail types should implement this trait.
-pub trait Detail: std::fmt::Debug + Send + Sync {
+pub trait Detail: core::fmt::Debug + Send + Sync {
     /// Returns the name of the architecture that produced this detail.
     fn architecture_name(&self) -> &'static str;
 
@@ -14,6 +18,43 @@ pub trait Detail: std::fmt::Debug + Send + Sync {
 
     /// Returns a list of register identifiers that are written by this instruction.
     fn registers_written(&self) -> &[u32];
+
+    /// Returns `(mnemonic, operands)` of the real instruction when the
+    /// printed form is a pseudo-instruction, like Capstone's `-r` detail.
+    fn real_instruction(&self) -> Option<(&str, &str)> {
+        None
+    }
+
+    /// Returns the structured operands of the real instruction, when the
+    /// architecture records them.
+    fn operands(&self) -> Vec<Operand> {
+        Vec::new()
+    }
+
+    /// Returns `true` when the instruction writes the updated base address
+    /// of its memory operand back, like Capstone's `writeback`.
+    fn writeback(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` when cstool prints the memory displacements of this
+    /// architecture as signed numbers rather than 64-bit two's complement.
+    fn signed_displacement(&self) -> bool {
+        false
+    }
+
+    /// Returns the names of the semantic groups this instruction belongs
+    /// to, such as `"jump"` or `"call"`.
+    fn groups(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Returns the name of a register id from
+    /// [`registers_read`](Self::registers_read) or
+    /// [`registers_written`](Self::registers_written).
+    fn register_name(&self, _reg: u32) -> Option<&'static str> {
+        None
+    }
 }
 
 /// A generic implementation of `Detail` for simple use cases.
@@ -23,7 +64,7 @@ pub struct BasicInstructionDetail {
     pub regs_read: Vec<u32>,
     pub regs_write: Vec<u32>,
     pub groups: Vec<String>,
-    pub properties: HashMap<String, String>,
+    pub properties: BTreeMap<String, String>,
 }
 
 impl BasicInstructionDetail {
@@ -33,7 +74,7 @@ impl BasicInstructionDetail {
             regs_read: Vec::new(),
             regs_write: Vec::new(),
             groups: Vec::new(),
-            properties: HashMap::new(),
+            properties: BTreeMap::new(),
         }
     }
 
@@ -70,6 +111,10 @@ impl Detail for BasicInstructionDetail {
     fn registers_written(&self) -> &[u32] {
         &self.regs_write
     }
+
+    fn groups(&self) -> Vec<&str> {
+        self.groups.iter().map(String::as_str).collect()
+    }
 }
 
 /// Convenience macro for creating basic instruction details.
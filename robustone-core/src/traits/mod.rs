@@ -5,6 +5,8 @@
 
 pub mod architecture;
 pub mod instruction;
+pub mod symbolizer;
 
 pub use architecture::ArchitectureHandler;
 pub use instruction::{BasicInstructionDetail, Detail};
+pub use symbolizer::Symbolizer;
@@ -0,0 +1,34 @@
+//! Symbolizer trait definition.
+//!
+//! A symbolizer maps addresses back to names, like LLVM's `MCSymbolizer`.
+//! Handlers that have one installed consult it for branch targets and
+//! PC-relative address computations and print `<name>` or `<name+0x10>`
+//! instead of (or next to) the raw number.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Resolves addresses to symbol names.
+///
+/// Handlers may be shared across threads, so symbolizers must be `Send` and
+/// `Sync` as well.
+pub trait Symbolizer: Send + Sync {
+    /// Returns the symbol covering `address` and the offset of `address`
+    /// from the start of that symbol, or `None` when nothing covers it.
+    fn symbolize(&self, address: u64) -> Option<(&str, u64)>;
+
+    /// Appends `<name>` or `<name+0xoffset>` for `address` to `out`.
+    ///
+    /// Returns `false`, leaving `out` untouched, when `address` has no symbol.
+    fn write_symbol(&self, address: u64, out: &mut String) -> bool {
+        let Some((name, offset)) = self.symbolize(address) else {
+            return false;
+        };
+        let _ = if offset == 0 {
+            write!(out, "<{name}>")
+        } else {
+            write!(out, "<{name}+0x{offset:x}>")
+        };
+        true
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::string::String;
 use thiserror::Error;
 
 /// Errors produced by the architecture-agnostic disassembly layer.
@@ -11,4 +12,6 @@ pub enum DisasmError {
     InvalidHexCode(String),
     #[error("ERROR: invalid address argument: {0}")]
     InvalidAddress(String),
+    #[error("ERROR: Unsupported option: {0}")]
+    UnsupportedOption(String),
 }
@@ -1,10 +1,19 @@
 //! Instruction type definition.
 
 use crate::traits::instruction::{BasicInstructionDetail, Detail};
+use crate::types::operand::{Operand, OperandAccess};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
 
 /// Decoded instruction returned by the disassembler.
 #[derive(Debug)]
 pub struct Instruction {
+    /// Architecture-specific instruction id (like Capstone's `cs_insn.id`);
+    /// `0` when the handler does not assign ids or the bytes are invalid.
+    pub id: u32,
     pub address: u64,
     pub bytes: Vec<u8>,
     pub mnemonic: String,
@@ -16,6 +25,7 @@ pub struct Instruction {
 impl Default for Instruction {
     fn default() -> Self {
         Self {
+            id: 0,
             address: 0,
             bytes: Vec::new(),
             mnemonic: "unknown".to_string(),
@@ -30,6 +40,7 @@ impl Instruction {
     pub fn new(address: u64, bytes: Vec<u8>, mnemonic: String, operands: String) -> Self {
         let size = bytes.len();
         Self {
+            id: 0,
             address,
             bytes,
             mnemonic,
@@ -48,6 +59,7 @@ impl Instruction {
     ) -> Self {
         let size = bytes.len();
         Self {
+            id: 0,
             address,
             bytes,
             mnemonic,
@@ -67,6 +79,7 @@ impl Instruction {
         let size = bytes.len();
         let detail = BasicInstructionDetail::new(architecture);
         Self {
+            id: 0,
             address,
             bytes,
             mnemonic,
@@ -80,6 +93,7 @@ impl Instruction {
         let size = bytes.len();
         let hex_repr = format!("0x{}", hex::encode(&bytes));
         Self {
+            id: 0,
             address,
             bytes,
             mnemonic: "unknown".to_string(),
@@ -89,6 +103,17 @@ impl Instruction {
         }
     }
 
+    /// Sets the architecture-specific instruction id.
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Architecture-specific instruction id; `0` means invalid or unassigned.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
     pub fn is_unknown(&self) -> bool {
         self.mnemonic == "unknown"
     }
@@ -99,11 +124,103 @@ impl Instruction {
             self.address, self.mnemonic, self.operands
         )
     }
+
+    /// Renders the detail block cstool prints below an instruction with
+    /// `-d`: the id, operands with their access, the registers read and
+    /// modified, and the groups, followed by a blank line. `name` is the
+    /// name of [`id`](Self::id). Returns an empty string without detail.
+    pub fn detail_listing(&self, name: Option<&str>) -> String {
+        let Some(detail) = &self.detail else {
+            return String::new();
+        };
+        let mut out = String::new();
+        if let Some(name) = name {
+            let _ = writeln!(out, "\tID: {} ({name})", self.id);
+        }
+
+        let operands = detail.operands();
+        if !operands.is_empty() {
+            let _ = writeln!(out, "\top_count: {}", operands.len());
+        }
+        for (i, operand) in operands.iter().enumerate() {
+            let access = match operand {
+                Operand::Register { name, access } => {
+                    let _ = writeln!(out, "\t\toperands[{i}].type: REG = {name}");
+                    *access
+                }
+                Operand::Immediate(imm) => {
+                    let _ = writeln!(out, "\t\toperands[{i}].type: IMM = 0x{imm:x}");
+                    OperandAccess::default()
+                }
+                Operand::Memory {
+                    base,
+                    index,
+                    disp,
+                    access,
+                } => {
+                    let _ = writeln!(out, "\t\toperands[{i}].type: MEM");
+                    let _ = writeln!(out, "\t\t\toperands[{i}].mem.base: REG = {base}");
+                    if let Some(index) = index {
+                        let _ = writeln!(out, "\t\t\toperands[{i}].mem.index: REG = {index}");
+                    }
+                    if *disp < 0 && detail.signed_displacement() {
+                        let _ = writeln!(
+                            out,
+                            "\t\t\toperands[{i}].mem.disp: -0x{:x}",
+                            disp.unsigned_abs()
+                        );
+                    } else if *disp != 0 {
+                        let _ = writeln!(out, "\t\t\toperands[{i}].mem.disp: 0x{disp:x}");
+                    }
+                    *access
+                }
+            };
+            let access = match (access.read, access.write) {
+                (true, true) => "READ | WRITE",
+                (true, false) => "READ",
+                (false, true) => "WRITE",
+                (false, false) => continue,
+            };
+            let _ = writeln!(out, "\t\toperands[{i}].access: {access}");
+        }
+
+        if detail.writeback() {
+            out.push_str("\tWrite-back: True\n");
+        }
+
+        for (label, regs) in [
+            ("Registers read", detail.registers_read()),
+            ("Registers modified", detail.registers_written()),
+        ] {
+            let mut names: Vec<&str> = Vec::new();
+            for name in regs.iter().filter_map(|reg| detail.register_name(*reg)) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            if !names.is_empty() {
+                let _ = writeln!(out, "\t{label}: {}", names.join(" "));
+            }
+        }
+
+        let groups = detail.groups();
+        if !groups.is_empty() {
+            out.push_str("\tGroups: ");
+            for group in groups {
+                out.push_str(group);
+                out.push(' ');
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_instruction_creation() {
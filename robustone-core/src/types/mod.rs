@@ -2,6 +2,14 @@
 
 pub mod error;
 pub mod instruction;
+pub mod operand;
+pub mod options;
+pub mod slot;
+pub mod symbols;
 
 pub use error::DisasmError;
 pub use instruction::Instruction;
+pub use operand::{Operand, OperandAccess};
+pub use options::{HandlerOption, HandlerOptions};
+pub use slot::{DeferredOperands, InstructionSlot, LiteInstruction, SlotOperand};
+pub use symbols::SymbolMap;
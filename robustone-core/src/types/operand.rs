@@ -0,0 +1,46 @@
+//! Architecture-neutral operand descriptions.
+//!
+//! Handlers keep their own operand types; [`Detail::operands`] converts them
+//! to this common form so front-ends can serialize any architecture the
+//! same way.
+//!
+//! [`Detail::operands`]: crate::traits::instruction::Detail::operands
+
+/// How an instruction accesses an operand (mirrors `cs_ac_type`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperandAccess {
+    pub read: bool,
+    pub write: bool,
+}
+
+impl OperandAccess {
+    /// Returns `"read"`, `"write"`, `"readwrite"` or `"none"`.
+    pub fn name(self) -> &'static str {
+        match (self.read, self.write) {
+            (true, true) => "readwrite",
+            (true, false) => "read",
+            (false, true) => "write",
+            (false, false) => "none",
+        }
+    }
+}
+
+/// One structured operand, with registers given by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// A register.
+    Register {
+        name: &'static str,
+        access: OperandAccess,
+    },
+    /// An immediate value.
+    Immediate(i64),
+    /// A memory reference `disp(base)`, or `[base, index]` with an index
+    /// register.
+    Memory {
+        base: &'static str,
+        index: Option<&'static str>,
+        disp: i64,
+        access: OperandAccess,
+    },
+}
@@ -0,0 +1,172 @@
+//! Runtime options understood by architecture handlers.
+//!
+//! These mirror Capstone's `cs_option`: a caller configures a handler once
+//! with [`ArchitectureHandler::set_option`](crate::traits::ArchitectureHandler::set_option)
+//! and every subsequent decode honours the new settings. Handlers reject
+//! options they cannot implement instead of silently ignoring them.
+
+/// Assembly syntax used to render instructions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SyntaxStyle {
+    /// Output compatible with Capstone's `cstool`.
+    #[default]
+    Capstone,
+    /// Output compatible with `llvm-mc --disassemble`.
+    Llvm,
+    /// Output compatible with GNU `objdump -d`.
+    Gnu,
+}
+
+/// How registers are named in operand text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RegisterNaming {
+    /// ABI aliases such as `sp` or `a0`.
+    #[default]
+    Alias,
+    /// Architectural numbers such as `x2` or `x10`.
+    Numeric,
+}
+
+/// Radix used for immediate operands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ImmediateRadix {
+    /// The syntax's own choice: Capstone prints small values in decimal and
+    /// larger ones in hexadecimal, LLVM and GNU print decimal.
+    #[default]
+    Auto,
+    /// Always decimal.
+    Decimal,
+    /// Always hexadecimal.
+    Hex,
+}
+
+/// Which pseudo-instructions (aliases) replace the real instruction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AliasMode {
+    /// The aliases the selected syntax prints by default: for Capstone
+    /// syntax the ones `cstool` prints and nothing more, for LLVM and GNU
+    /// syntax the same set as [`All`](Self::All).
+    #[default]
+    Capstone,
+    /// Every pseudo-instruction objdump and LLVM print (`li`, `mv`, `ret`, ...).
+    All,
+    /// Always print the real instruction, like objdump `-M no-aliases`.
+    None,
+}
+
+/// A single option change, the equivalent of one `cs_option` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HandlerOption {
+    /// Select the output syntax.
+    Syntax(SyntaxStyle),
+    /// Choose between register aliases and architectural numbers.
+    RegisterNaming(RegisterNaming),
+    /// Select the radix of immediate operands.
+    ImmediateRadix(ImmediateRadix),
+    /// Select which pseudo-instructions are printed.
+    Aliases(AliasMode),
+    /// Print negative immediates as their unsigned two's-complement value.
+    UnsignedImmediates(bool),
+    /// Compute instruction detail (register accesses, structured operands).
+    Detail(bool),
+    /// Describe the real instruction behind an alias in detail, like
+    /// Capstone's `CS_OPT_DETAIL_REAL`; otherwise an alias lists only the
+    /// operands it prints.
+    RealDetail(bool),
+}
+
+/// The complete set of runtime options of a handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerOptions {
+    pub syntax: SyntaxStyle,
+    pub register_naming: RegisterNaming,
+    pub immediate_radix: ImmediateRadix,
+    pub aliases: AliasMode,
+    pub unsigned_immediates: bool,
+    pub detail: bool,
+    pub real_detail: bool,
+}
+
+impl HandlerOptions {
+    /// Returns the default options: Capstone syntax, register aliases,
+    /// automatic radix, Capstone's pseudo-instructions, signed immediates and
+    /// detail enabled, describing aliases as printed.
+    pub const fn new() -> Self {
+        Self {
+            syntax: SyntaxStyle::Capstone,
+            register_naming: RegisterNaming::Alias,
+            immediate_radix: ImmediateRadix::Auto,
+            aliases: AliasMode::Capstone,
+            unsigned_immediates: false,
+            detail: true,
+            real_detail: false,
+        }
+    }
+
+    /// Returns a copy with `option` applied.
+    pub const fn with(mut self, option: HandlerOption) -> Self {
+        self.apply(option);
+        self
+    }
+
+    /// Applies `option` in place.
+    pub const fn apply(&mut self, option: HandlerOption) {
+        match option {
+            HandlerOption::Syntax(syntax) => self.syntax = syntax,
+            HandlerOption::RegisterNaming(naming) => self.register_naming = naming,
+            HandlerOption::ImmediateRadix(radix) => self.immediate_radix = radix,
+            HandlerOption::Aliases(aliases) => self.aliases = aliases,
+            HandlerOption::UnsignedImmediates(unsigned) => self.unsigned_immediates = unsigned,
+            HandlerOption::Detail(detail) => self.detail = detail,
+            HandlerOption::RealDetail(real) => self.real_detail = real,
+        }
+    }
+
+    /// Returns `true` when operands are styled differently from the default
+    /// rendering; pseudo-instruction selection is governed by `aliases` alone.
+    pub fn changes_formatting(&self) -> bool {
+        let defaults = Self::new();
+        self.syntax != defaults.syntax
+            || self.register_naming != defaults.register_naming
+            || self.immediate_radix != defaults.immediate_radix
+            || self.unsigned_immediates != defaults.unsigned_immediates
+    }
+}
+
+impl Default for HandlerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_options() {
+        let options = HandlerOptions::new()
+            .with(HandlerOption::RegisterNaming(RegisterNaming::Numeric))
+            .with(HandlerOption::Detail(false));
+
+        assert_eq!(options.register_naming, RegisterNaming::Numeric);
+        assert!(!options.detail);
+        assert!(options.changes_formatting());
+        assert_eq!(options.aliases, AliasMode::Capstone);
+        assert!(
+            !HandlerOptions::new()
+                .with(HandlerOption::Aliases(AliasMode::None))
+                .changes_formatting()
+        );
+        assert!(
+            !HandlerOptions::new()
+                .with(HandlerOption::Detail(false))
+                .changes_formatting()
+        );
+    }
+}
@@ -0,0 +1,529 @@
+//! Reusable instruction storage for allocation-free decoding.
+//!
+//! [`Instruction`] owns its bytes, mnemonic, operand text, and a boxed detail
+//! payload, so every decode performs several heap allocations. High-volume
+//! callers can instead keep a single [`InstructionSlot`] alive and let the
+//! handler overwrite it for each instruction via
+//! [`ArchitectureHandler::disassemble_into`](crate::traits::ArchitectureHandler::disassemble_into).
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cell::{Cell, OnceCell};
+use core::fmt;
+
+use crate::types::instruction::Instruction;
+
+/// Maximum number of instruction bytes stored inline in a slot.
+pub const MAX_INSTRUCTION_SIZE: usize = 16;
+
+/// Maximum number of registers tracked per access list in a slot.
+pub const MAX_SLOT_REGISTERS: usize = 8;
+
+/// Maximum number of operands a slot keeps for deferred formatting.
+pub const MAX_SLOT_OPERANDS: usize = 6;
+
+/// `(address, size, mnemonic, op_str)`, the tuple yielded by Capstone's
+/// `disasm_lite`.
+pub type LiteInstruction = (u64, usize, String, String);
+
+/// Fixed-capacity register list stored inline in an [`InstructionSlot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegisterList {
+    regs: [u32; MAX_SLOT_REGISTERS],
+    len: usize,
+}
+
+impl RegisterList {
+    /// Creates an empty register list.
+    pub const fn new() -> Self {
+        Self {
+            regs: [0; MAX_SLOT_REGISTERS],
+            len: 0,
+        }
+    }
+
+    /// Appends a register, returning `false` when the list is already full.
+    pub fn push(&mut self, reg: u32) -> bool {
+        if self.len == MAX_SLOT_REGISTERS {
+            return false;
+        }
+        self.regs[self.len] = reg;
+        self.len += 1;
+        true
+    }
+
+    /// Removes all registers while keeping the storage.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the populated registers.
+    pub fn as_slice(&self) -> &[u32] {
+        &self.regs[..self.len]
+    }
+
+    /// Returns the number of populated registers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when no register has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// One operand recorded for deferred formatting; the meaning of each field
+/// is defined by the handler that wrote it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlotOperand {
+    /// Operand kind.
+    pub kind: u8,
+    /// Main payload, such as a register number or an immediate.
+    pub value: u64,
+    /// Secondary payload, such as the base register of a memory operand.
+    pub extra: u64,
+}
+
+/// Operands whose text is only formatted when the slot's operands are read.
+///
+/// Handlers store the structured operands together with their formatting
+/// settings and a `writer` that renders them, so scanning callers that
+/// never look at the operand text skip formatting altogether.
+#[derive(Debug, Clone, Copy)]
+pub struct DeferredOperands {
+    /// Handler-defined formatting settings, such as syntax and radix.
+    pub style: u64,
+    /// Address the operands are formatted for.
+    pub address: u64,
+    operands: [SlotOperand; MAX_SLOT_OPERANDS],
+    len: usize,
+    writer: fn(&DeferredOperands, &mut dyn fmt::Write) -> fmt::Result,
+}
+
+impl DeferredOperands {
+    /// Creates an empty operand list rendered by `writer`.
+    pub const fn new(
+        style: u64,
+        address: u64,
+        writer: fn(&DeferredOperands, &mut dyn fmt::Write) -> fmt::Result,
+    ) -> Self {
+        Self {
+            style,
+            address,
+            operands: [SlotOperand {
+                kind: 0,
+                value: 0,
+                extra: 0,
+            }; MAX_SLOT_OPERANDS],
+            len: 0,
+            writer,
+        }
+    }
+
+    /// Appends an operand, returning `false` when the list is already full.
+    pub fn push(&mut self, operand: SlotOperand) -> bool {
+        if self.len == MAX_SLOT_OPERANDS {
+            return false;
+        }
+        self.operands[self.len] = operand;
+        self.len += 1;
+        true
+    }
+
+    /// Returns the recorded operands.
+    pub fn operands(&self) -> &[SlotOperand] {
+        &self.operands[..self.len]
+    }
+
+    /// Renders the operand text into `out`.
+    pub fn write_to(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        (self.writer)(self, out)
+    }
+}
+
+/// Caller-owned, reusable storage for a single decoded instruction.
+///
+/// Bytes and register lists live inline, and the mnemonic is a static string
+/// for handlers that support it. Handlers either write the operand text
+/// into a buffer whose capacity survives across decodes, or record
+/// [`DeferredOperands`] that are only formatted into that buffer when
+/// [`operands`](Self::operands) is first called. Once the buffer has grown
+/// to fit typical operand strings, decoding into a slot no longer
+/// allocates on the caller side.
+///
+/// # Example
+///
+/// ```rust
+/// use robustone_core::InstructionSlot;
+///
+/// let mut slot = InstructionSlot::new();
+/// slot.reset(0x1000);
+/// slot.set_bytes(&[0x13, 0x00, 0x00, 0x00]).unwrap();
+/// slot.set_mnemonic("nop");
+/// assert_eq!(slot.size(), 4);
+/// assert_eq!(slot.assembly_line(), "0x00001000: nop     ");
+/// ```
+pub struct InstructionSlot {
+    id: u32,
+    address: u64,
+    bytes: [u8; MAX_INSTRUCTION_SIZE],
+    size: usize,
+    mnemonic: Cow<'static, str>,
+    /// Operand text, formatted from `deferred` on first read.
+    operands: OnceCell<String>,
+    /// Operands waiting to be formatted.
+    deferred: Option<DeferredOperands>,
+    /// Spare buffer holding the operand text's capacity while unset.
+    buffer: Cell<String>,
+    regs_read: RegisterList,
+    regs_write: RegisterList,
+}
+
+impl fmt::Debug for InstructionSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstructionSlot")
+            .field("id", &self.id)
+            .field("address", &self.address)
+            .field("bytes", &self.bytes())
+            .field("mnemonic", &self.mnemonic)
+            .field("operands", &self.operands())
+            .field("regs_read", &self.regs_read)
+            .field("regs_write", &self.regs_write)
+            .finish()
+    }
+}
+
+impl Clone for InstructionSlot {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            address: self.address,
+            bytes: self.bytes,
+            size: self.size,
+            mnemonic: self.mnemonic.clone(),
+            operands: self.operands.clone(),
+            deferred: self.deferred,
+            buffer: Cell::new(String::new()),
+            regs_read: self.regs_read,
+            regs_write: self.regs_write,
+        }
+    }
+}
+
+impl Default for InstructionSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InstructionSlot {
+    /// Creates an empty slot describing an unknown instruction.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a slot whose operand buffer can hold `capacity` bytes up front.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            id: 0,
+            address: 0,
+            bytes: [0; MAX_INSTRUCTION_SIZE],
+            size: 0,
+            mnemonic: Cow::Borrowed("unknown"),
+            operands: OnceCell::new(),
+            deferred: None,
+            buffer: Cell::new(String::with_capacity(capacity)),
+            regs_read: RegisterList::new(),
+            regs_write: RegisterList::new(),
+        }
+    }
+
+    /// Clears the previous contents and prepares the slot for `address`.
+    ///
+    /// The operand buffer keeps its capacity so it can be reused.
+    pub fn reset(&mut self, address: u64) {
+        self.id = 0;
+        self.address = address;
+        self.size = 0;
+        self.mnemonic = Cow::Borrowed("unknown");
+        self.clear_operands();
+        self.regs_read.clear();
+        self.regs_write.clear();
+    }
+
+    /// Stores the encoded bytes of the instruction inline.
+    ///
+    /// Returns `Err` with the rejected length when `bytes` exceeds
+    /// [`MAX_INSTRUCTION_SIZE`].
+    pub fn set_bytes(&mut self, bytes: &[u8]) -> Result<(), usize> {
+        if bytes.len() > MAX_INSTRUCTION_SIZE {
+            return Err(bytes.len());
+        }
+        self.bytes[..bytes.len()].copy_from_slice(bytes);
+        self.size = bytes.len();
+        Ok(())
+    }
+
+    /// Sets the instruction mnemonic.
+    ///
+    /// Passing a `&'static str` never allocates; owned strings are accepted so
+    /// handlers without static mnemonics can still fill a slot.
+    pub fn set_mnemonic(&mut self, mnemonic: impl Into<Cow<'static, str>>) {
+        self.mnemonic = mnemonic.into();
+    }
+
+    /// Sets the architecture-specific instruction id.
+    pub fn set_id(&mut self, id: u32) {
+        self.id = id;
+    }
+
+    /// Gives mutable access to the operand text, formatting any deferred
+    /// operands first.
+    pub fn operands_mut(&mut self) -> &mut String {
+        self.operands();
+        self.deferred = None;
+        self.operands
+            .get_mut()
+            .expect("operands() initialises the operand text")
+    }
+
+    /// Replaces the operand text with `operands`, formatted on first read.
+    pub fn defer_operands(&mut self, operands: DeferredOperands) {
+        self.clear_operands();
+        self.deferred = Some(operands);
+    }
+
+    /// Returns the operands recorded for deferred formatting, if the text
+    /// has not been written directly.
+    pub fn deferred_operands(&self) -> Option<&DeferredOperands> {
+        self.deferred.as_ref()
+    }
+
+    /// Drops the operand text, keeping its buffer for reuse.
+    fn clear_operands(&mut self) {
+        if let Some(mut text) = self.operands.take() {
+            text.clear();
+            self.buffer.set(text);
+        }
+        self.deferred = None;
+    }
+
+    /// Records a register read by the instruction.
+    pub fn reads_register(&mut self, reg: u32) {
+        self.regs_read.push(reg);
+    }
+
+    /// Records a register written by the instruction.
+    pub fn writes_register(&mut self, reg: u32) {
+        self.regs_write.push(reg);
+    }
+
+    /// Architecture-specific instruction id; `0` means invalid or unassigned.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Address of the decoded instruction.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Encoded bytes of the decoded instruction.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.size]
+    }
+
+    /// Size of the decoded instruction in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Instruction mnemonic.
+    pub fn mnemonic(&self) -> &str {
+        &self.mnemonic
+    }
+
+    /// Formatted operand string.
+    ///
+    /// Deferred operands are formatted into the reusable buffer on the
+    /// first call after each decode.
+    pub fn operands(&self) -> &str {
+        self.operands.get_or_init(|| {
+            let mut text = self.buffer.take();
+            text.clear();
+            if let Some(deferred) = &self.deferred {
+                // Writing into a `String` cannot fail.
+                let _ = deferred.write_to(&mut text);
+            }
+            text
+        })
+    }
+
+    /// Registers read by the instruction.
+    pub fn registers_read(&self) -> &[u32] {
+        self.regs_read.as_slice()
+    }
+
+    /// Registers written by the instruction.
+    pub fn registers_written(&self) -> &[u32] {
+        self.regs_write.as_slice()
+    }
+
+    /// Returns `true` if the slot holds an undecodable instruction.
+    pub fn is_unknown(&self) -> bool {
+        self.mnemonic == "unknown"
+    }
+
+    /// Copies an already decoded [`Instruction`] into the slot.
+    ///
+    /// This is the fallback used by handlers that do not implement a native
+    /// slot-based decoder; it allocates for the mnemonic.
+    pub fn load_instruction(&mut self, instruction: &Instruction) -> Result<(), usize> {
+        self.reset(instruction.address);
+        self.set_bytes(&instruction.bytes)?;
+        self.id = instruction.id;
+        self.mnemonic = Cow::Owned(instruction.mnemonic.clone());
+        self.operands_mut().push_str(&instruction.operands);
+        if let Some(detail) = &instruction.detail {
+            for &reg in detail.registers_read() {
+                self.reads_register(reg);
+            }
+            for &reg in detail.registers_written() {
+                self.writes_register(reg);
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts the slot contents into an owned [`Instruction`].
+    pub fn to_instruction(&self) -> Instruction {
+        Instruction::new(
+            self.address,
+            self.bytes().to_vec(),
+            self.mnemonic.to_string(),
+            self.operands().to_string(),
+        )
+        .with_id(self.id)
+    }
+
+    /// Converts the slot contents into a [`LiteInstruction`] tuple.
+    pub fn to_lite(&self) -> LiteInstruction {
+        (
+            self.address,
+            self.size,
+            self.mnemonic.to_string(),
+            self.operands().to_string(),
+        )
+    }
+
+    /// Formats the instruction like [`Instruction::assembly_line`].
+    pub fn assembly_line(&self) -> String {
+        format!(
+            "0x{:08x}: {:<7} {}",
+            self.address,
+            self.mnemonic,
+            self.operands()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_slot_reuse_keeps_capacity() {
+        let mut slot = InstructionSlot::with_capacity(32);
+        slot.reset(0x1000);
+        slot.operands_mut().push_str("a0, a1, a2");
+        let capacity = slot.operands_mut().capacity();
+
+        slot.reset(0x1004);
+        assert_eq!(slot.address(), 0x1004);
+        assert!(slot.operands().is_empty());
+        assert!(slot.is_unknown());
+        assert_eq!(slot.operands_mut().capacity(), capacity);
+    }
+
+    #[test]
+    fn test_deferred_operands_format_on_read() {
+        fn write_registers(operands: &DeferredOperands, out: &mut dyn fmt::Write) -> fmt::Result {
+            for (index, operand) in operands.operands().iter().enumerate() {
+                if index > 0 {
+                    out.write_str(", ")?;
+                }
+                write!(out, "r{}", operand.value)?;
+            }
+            Ok(())
+        }
+
+        let mut slot = InstructionSlot::new();
+        slot.reset(0);
+        let mut deferred = DeferredOperands::new(0, 0, write_registers);
+        for value in [1, 2] {
+            deferred.push(SlotOperand {
+                value,
+                ..SlotOperand::default()
+            });
+        }
+        slot.defer_operands(deferred);
+        assert_eq!(
+            slot.deferred_operands().map(|d| d.operands().len()),
+            Some(2)
+        );
+        assert_eq!(slot.operands(), "r1, r2");
+
+        slot.operands_mut().push_str(", r3");
+        assert!(slot.deferred_operands().is_none());
+        assert_eq!(slot.to_instruction().operands, "r1, r2, r3");
+
+        slot.reset(4);
+        assert!(slot.operands().is_empty());
+    }
+
+    #[test]
+    fn test_slot_bytes_and_registers() {
+        let mut slot = InstructionSlot::new();
+        slot.reset(0);
+        assert!(slot.set_bytes(&[0x01, 0x02]).is_ok());
+        assert_eq!(slot.bytes(), &[0x01, 0x02]);
+        assert_eq!(slot.set_bytes(&[0; MAX_INSTRUCTION_SIZE + 1]), Err(17));
+
+        slot.reads_register(2);
+        slot.writes_register(10);
+        assert_eq!(slot.registers_read(), &[2]);
+        assert_eq!(slot.registers_written(), &[10]);
+    }
+
+    #[test]
+    fn test_register_list_capacity() {
+        let mut list = RegisterList::new();
+        for reg in 0..MAX_SLOT_REGISTERS as u32 {
+            assert!(list.push(reg));
+        }
+        assert!(!list.push(99));
+        assert_eq!(list.len(), MAX_SLOT_REGISTERS);
+    }
+
+    #[test]
+    fn test_slot_round_trip_instruction() {
+        let instruction = Instruction::new(
+            0x200,
+            vec![0x93, 0x00, 0x10, 0x00],
+            "addi".to_string(),
+            "ra, zero, 1".to_string(),
+        );
+        let mut slot = InstructionSlot::new();
+        slot.load_instruction(&instruction).unwrap();
+
+        let copy = slot.to_instruction();
+        assert_eq!(copy.address, 0x200);
+        assert_eq!(copy.bytes, instruction.bytes);
+        assert_eq!(copy.mnemonic, "addi");
+        assert_eq!(copy.operands, "ra, zero, 1");
+        assert_eq!(slot.assembly_line(), instruction.assembly_line());
+    }
+}
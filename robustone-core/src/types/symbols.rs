@@ -0,0 +1,154 @@
+//! A map-based [`Symbolizer`].
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::traits::Symbolizer;
+use crate::types::error::DisasmError;
+
+/// Symbols keyed by start address.
+///
+/// Symbols have no size: an address belongs to the closest symbol at or
+/// below it, which is how objdump labels code without size information.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolMap {
+    symbols: BTreeMap<u64, String>,
+}
+
+impl SymbolMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a symbol starting at `address`, replacing any symbol already
+    /// defined there.
+    pub fn insert(&mut self, address: u64, name: impl Into<String>) {
+        self.symbols.insert(address, name.into());
+    }
+
+    /// Returns the number of symbols.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns `true` when the map holds no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Returns the name of the symbol starting exactly at `address`.
+    pub fn get(&self, address: u64) -> Option<&str> {
+        self.symbols.get(&address).map(String::as_str)
+    }
+
+    /// Iterates over `(address, name)` pairs in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> {
+        self.symbols
+            .iter()
+            .map(|(address, name)| (*address, name.as_str()))
+    }
+
+    /// Parses a symbol listing in `nm` format.
+    ///
+    /// Each line holds a hexadecimal address (with or without `0x`), an
+    /// optional one-letter symbol type and the name, for example
+    /// `0000000000001040 T memcpy` or `0x1040 memcpy`. Blank lines and lines
+    /// starting with `#` are ignored, as are undefined symbols, which `nm`
+    /// lists without an address.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DisasmError::InvalidAddress` naming the first malformed line.
+    pub fn parse(text: &str) -> Result<Self, DisasmError> {
+        let mut map = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            // `nm` pads the missing address of undefined symbols with blanks.
+            let undefined = line.starts_with(char::is_whitespace);
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            let (address, name) = match fields.as_slice() {
+                [_, _] if undefined => continue,
+                [address, name] | [address, _, name] => (*address, *name),
+                _ => {
+                    return Err(DisasmError::InvalidAddress(format!(
+                        "symbol line {}: expected `address [type] name`",
+                        index + 1
+                    )));
+                }
+            };
+            let digits = address
+                .strip_prefix("0x")
+                .or_else(|| address.strip_prefix("0X"))
+                .unwrap_or(address);
+            let address = u64::from_str_radix(digits, 16).map_err(|_| {
+                DisasmError::InvalidAddress(format!("symbol line {}: {address}", index + 1))
+            })?;
+            map.insert(address, name.to_string());
+        }
+        Ok(map)
+    }
+}
+
+impl Symbolizer for SymbolMap {
+    fn symbolize(&self, address: u64) -> Option<(&str, u64)> {
+        let (start, name) = self.symbols.range(..=address).next_back()?;
+        Some((name.as_str(), address - start))
+    }
+}
+
+impl FromIterator<(u64, String)> for SymbolMap {
+    fn from_iter<I: IntoIterator<Item = (u64, String)>>(iter: I) -> Self {
+        Self {
+            symbols: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbolize_nearest_preceding_symbol() {
+        let mut map = SymbolMap::new();
+        map.insert(0x1000, "_start");
+        map.insert(0x1040, "memcpy");
+
+        assert_eq!(map.symbolize(0x1040), Some(("memcpy", 0)));
+        assert_eq!(map.symbolize(0x1048), Some(("memcpy", 8)));
+        assert_eq!(map.symbolize(0x103c), Some(("_start", 0x3c)));
+        assert_eq!(map.symbolize(0xfff), None);
+
+        let mut out = String::new();
+        assert!(map.write_symbol(0x1050, &mut out));
+        assert_eq!(out, "<memcpy+0x10>");
+        assert!(!map.write_symbol(0, &mut out));
+    }
+
+    #[test]
+    fn test_parse_nm_listing() {
+        let map = SymbolMap::parse(
+            "# comment\n\
+             0000000000001000 T _start\n\
+             \n\
+             0x1040 memcpy\n\
+             \x20                U puts\n",
+        )
+        .unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(0x1000), Some("_start"));
+        assert_eq!(map.get(0x1040), Some("memcpy"));
+
+        assert!(matches!(
+            SymbolMap::parse("1000 T main extra"),
+            Err(DisasmError::InvalidAddress(msg)) if msg.starts_with("symbol line 1")
+        ));
+        assert!(SymbolMap::parse("zz main").is_err());
+    }
+}
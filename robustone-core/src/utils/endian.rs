@@ -4,6 +4,8 @@
 //! across various architectures, making it easier to support both little-endian
 //! and big-endian systems in a generic way.
 
+use alloc::vec::Vec;
+
 /// Endianness enumeration for byte ordering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Endianness {
@@ -204,6 +206,7 @@ impl EndianConvert for u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_architecture_endianness() {
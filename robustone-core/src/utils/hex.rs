@@ -6,6 +6,9 @@
 
 use crate::types::error::DisasmError;
 use crate::utils::Endianness;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// Parser for hexadecimal strings with various formats and prefixes.
 ///
@@ -166,6 +169,7 @@ impl HexParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_basic_hex_parsing() {
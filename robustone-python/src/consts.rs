@@ -0,0 +1,158 @@
+//! Capstone constants exported at module level.
+//!
+//! Values match the `capstone` Python package (and `robustone-capi`), so
+//! scripts can swap `from capstone import *` for `from robustone_py import *`.
+
+use pyo3::prelude::*;
+use robustone_riscv::insn::RiscVInstructionId;
+
+pub const CS_API_MAJOR: u32 = 5;
+pub const CS_API_MINOR: u32 = 0;
+
+pub const CS_ARCH_RISCV: u32 = 15;
+pub const CS_ARCH_ALL: u32 = 0xFFFF;
+
+pub const CS_MODE_LITTLE_ENDIAN: u32 = 0;
+pub const CS_MODE_RISCV32: u32 = 1 << 0;
+pub const CS_MODE_RISCV64: u32 = 1 << 1;
+pub const CS_MODE_RISCVC: u32 = 1 << 2;
+pub const CS_MODE_BIG_ENDIAN: u32 = 1 << 31;
+
+pub const CS_ERR_OK: u32 = 0;
+pub const CS_ERR_MEM: u32 = 1;
+pub const CS_ERR_ARCH: u32 = 2;
+pub const CS_ERR_HANDLE: u32 = 3;
+pub const CS_ERR_CSH: u32 = 4;
+pub const CS_ERR_MODE: u32 = 5;
+pub const CS_ERR_OPTION: u32 = 6;
+pub const CS_ERR_DETAIL: u32 = 7;
+pub const CS_ERR_MEMSETUP: u32 = 8;
+pub const CS_ERR_VERSION: u32 = 9;
+pub const CS_ERR_DIET: u32 = 10;
+pub const CS_ERR_SKIPDATA: u32 = 11;
+
+pub const CS_AC_INVALID: u8 = 0;
+pub const CS_AC_READ: u8 = 1 << 0;
+pub const CS_AC_WRITE: u8 = 1 << 1;
+
+pub const RISCV_OP_INVALID: u8 = 0;
+pub const RISCV_OP_REG: u8 = 1;
+pub const RISCV_OP_IMM: u8 = 2;
+pub const RISCV_OP_MEM: u8 = 3;
+
+pub const RISCV_GRP_INVALID: u8 = 0;
+pub const RISCV_GRP_JUMP: u8 = 1;
+pub const RISCV_GRP_CALL: u8 = 2;
+pub const RISCV_GRP_RET: u8 = 3;
+pub const RISCV_GRP_INT: u8 = 4;
+pub const RISCV_GRP_IRET: u8 = 5;
+pub const RISCV_GRP_PRIVILEGE: u8 = 6;
+pub const RISCV_GRP_BRANCH_RELATIVE: u8 = 7;
+
+/// Register names indexed by `RISCV_REG_*` id.
+pub static REGISTER_NAMES: [&str; 97] = [
+    "invalid", "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2",
+    "a3", "a4", "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11",
+    "t3", "t4", "t5", "t6", "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1",
+    "fa0", "fa1", "fa2", "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6",
+    "fs7", "fs8", "fs9", "fs10", "fs11", "ft8", "ft9", "ft10", "ft11", "ft0", "ft1", "ft2", "ft3",
+    "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1", "fa2", "fa3", "fa4", "fa5", "fa6",
+    "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7", "fs8", "fs9", "fs10", "fs11", "ft8", "ft9",
+    "ft10", "ft11",
+];
+
+/// Returns the Capstone description of an error code.
+pub fn strerror(code: u32) -> &'static str {
+    match code {
+        CS_ERR_OK => "OK (CS_ERR_OK)",
+        CS_ERR_MEM => "Out of memory (CS_ERR_MEM)",
+        CS_ERR_ARCH => "Invalid/unsupported architecture(CS_ERR_ARCH)",
+        CS_ERR_HANDLE => "Invalid handle (CS_ERR_HANDLE)",
+        CS_ERR_CSH => "Invalid csh (CS_ERR_CSH)",
+        CS_ERR_MODE => "Invalid mode (CS_ERR_MODE)",
+        CS_ERR_OPTION => "Invalid option (CS_ERR_OPTION)",
+        CS_ERR_DETAIL => "Details are unavailable (CS_ERR_DETAIL)",
+        CS_ERR_MEMSETUP => "Dynamic memory management uninitialized (CS_ERR_MEMSETUP)",
+        CS_ERR_VERSION => "Different API version between core & binding (CS_ERR_VERSION)",
+        CS_ERR_DIET => "Information irrelevant in diet engine (CS_ERR_DIET)",
+        CS_ERR_SKIPDATA => {
+            "Information irrelevant for 'data' instruction in SKIPDATA mode (CS_ERR_SKIPDATA)"
+        }
+        _ => "Unknown error code",
+    }
+}
+
+/// Returns the `RISCV_INS_*` constant name of an instruction (`c.addi` becomes
+/// `RISCV_INS_C_ADDI`, `amoadd.w.aqrl` becomes `RISCV_INS_AMOADD_W_AQ_RL`).
+pub fn insn_constant(id: RiscVInstructionId) -> String {
+    format!("RISCV_INS_{}", id.constant_name())
+}
+
+/// Adds every constant above to the module namespace.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    macro_rules! add {
+        ($($name:ident),+ $(,)?) => {
+            $(module.add(stringify!($name), $name)?;)+
+        };
+    }
+
+    add!(
+        CS_API_MAJOR,
+        CS_API_MINOR,
+        CS_ARCH_RISCV,
+        CS_ARCH_ALL,
+        CS_MODE_LITTLE_ENDIAN,
+        CS_MODE_RISCV32,
+        CS_MODE_RISCV64,
+        CS_MODE_RISCVC,
+        CS_MODE_BIG_ENDIAN,
+        CS_ERR_OK,
+        CS_ERR_MEM,
+        CS_ERR_ARCH,
+        CS_ERR_HANDLE,
+        CS_ERR_CSH,
+        CS_ERR_MODE,
+        CS_ERR_OPTION,
+        CS_ERR_DETAIL,
+        CS_ERR_MEMSETUP,
+        CS_ERR_VERSION,
+        CS_ERR_DIET,
+        CS_ERR_SKIPDATA,
+        CS_AC_INVALID,
+        CS_AC_READ,
+        CS_AC_WRITE,
+        RISCV_OP_INVALID,
+        RISCV_OP_REG,
+        RISCV_OP_IMM,
+        RISCV_OP_MEM,
+        RISCV_GRP_INVALID,
+        RISCV_GRP_JUMP,
+        RISCV_GRP_CALL,
+        RISCV_GRP_RET,
+        RISCV_GRP_INT,
+        RISCV_GRP_IRET,
+        RISCV_GRP_PRIVILEGE,
+        RISCV_GRP_BRANCH_RELATIVE,
+    );
+
+    // General-purpose registers under both their numeric and ABI names.
+    for (id, name) in REGISTER_NAMES.iter().enumerate().skip(1).take(32) {
+        module.add(format!("RISCV_REG_X{}", id - 1), id)?;
+        module.add(format!("RISCV_REG_{}", name.to_uppercase()), id)?;
+    }
+    // Floating-point registers, single then double precision.
+    for id in 33..REGISTER_NAMES.len() {
+        let (suffix, number) = if id < 65 {
+            (32, id - 33)
+        } else {
+            (64, id - 65)
+        };
+        module.add(format!("RISCV_REG_F{number}_{suffix}"), id)?;
+    }
+    module.add("RISCV_INS_INVALID", RiscVInstructionId::Invalid.id())?;
+    for id in RiscVInstructionId::ALL {
+        module.add(insn_constant(*id), id.id())?;
+    }
+    module.add("RISCV_INS_ENDING", RiscVInstructionId::ENDING)?;
+    Ok(())
+}
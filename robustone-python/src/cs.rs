@@ -0,0 +1,317 @@
+//! The `Cs` disassembler object.
+
+use std::borrow::Cow;
+
+use pyo3::prelude::*;
+
+use robustone_core::types::options::HandlerOption;
+use robustone_core::{ArchitectureHandler, InstructionSlot};
+use robustone_riscv::RiscVHandler;
+use robustone_riscv::decoder::Xlen;
+use robustone_riscv::extensions::Extensions;
+
+use crate::consts::*;
+use crate::cs_error;
+use crate::insn::CsInsn;
+
+/// Mode bits accepted for `CS_ARCH_RISCV`.
+const RISCV_MODE_MASK: u32 = CS_MODE_RISCV32 | CS_MODE_RISCV64 | CS_MODE_RISCVC;
+
+/// A disassembler handle, mirroring `capstone.Cs`.
+///
+/// Instructions are decoded by a [`RiscVHandler`]; detail mode additionally
+/// reads the structured operands from its decoder. Handles are bound to the
+/// thread that created them.
+#[pyclass(module = "robustone_py", unsendable)]
+pub struct Cs {
+    /// Architecture passed to the constructor.
+    #[pyo3(get)]
+    arch: u32,
+    mode: u32,
+    handler: RiscVHandler,
+    slot: InstructionSlot,
+    detail: bool,
+    /// Whether undecodable bytes are emitted as `.byte` entries.
+    #[pyo3(get, set)]
+    skipdata: bool,
+}
+
+impl Cs {
+    /// Builds the handler for `mode`, with detail decoding set to `detail`.
+    fn handler_for(mode: u32, detail: bool) -> PyResult<RiscVHandler> {
+        if mode & !RISCV_MODE_MASK != 0 {
+            return Err(cs_error(CS_ERR_MODE));
+        }
+        let (xlen, extensions) = match mode & (CS_MODE_RISCV32 | CS_MODE_RISCV64) {
+            CS_MODE_RISCV32 => (Xlen::X32, Extensions::rv32gc()),
+            CS_MODE_RISCV64 => (Xlen::X64, Extensions::rv64gc()),
+            _ => return Err(cs_error(CS_ERR_MODE)),
+        };
+        let extensions = if mode & CS_MODE_RISCVC != 0 {
+            extensions
+        } else {
+            extensions.without_compressed()
+        };
+        let mut handler = RiscVHandler::with_extensions(xlen, extensions);
+        handler
+            .set_option(HandlerOption::Detail(detail))
+            .map_err(|_| cs_error(CS_ERR_OPTION))?;
+        Ok(handler)
+    }
+
+    /// Number of bytes consumed by a `.byte` entry in SKIPDATA mode.
+    fn skipdata_size(&self) -> usize {
+        if self.mode & CS_MODE_RISCVC != 0 {
+            2
+        } else {
+            4
+        }
+    }
+
+    /// Decodes the instruction at the start of `code` without detail.
+    ///
+    /// Leaves the result in `self.slot` and returns its size, or `None` when
+    /// the bytes do not form a valid instruction.
+    fn decode_lite(&mut self, code: &[u8], address: u64) -> Option<usize> {
+        self.handler
+            .disassemble_into(code, address, &mut self.slot)
+            .ok()
+            .filter(|_| !is_unknown(self.slot.mnemonic()))
+    }
+
+    /// Decodes one instruction (or SKIPDATA entry) at the start of `code`.
+    fn decode_one(&mut self, code: &[u8], address: u64) -> Option<CsInsn> {
+        if self.detail {
+            let decoded = self
+                .handler
+                .decoder()
+                .decode(code, address)
+                .ok()
+                .filter(|decoded| !is_unknown(decoded.mnemonic));
+            if let Some(decoded) = decoded {
+                return Some(CsInsn::detailed(address, &code[..decoded.size], &decoded));
+            }
+        } else if let Some(size) = self.decode_lite(code, address) {
+            let slot = &self.slot;
+            return Some(CsInsn::lite(
+                slot.id(),
+                address,
+                &code[..size],
+                slot.mnemonic(),
+                slot.operands(),
+            ));
+        }
+        self.data_entry(code, address)
+    }
+
+    /// Text-only decoding loop behind `disasm_lite`.
+    fn disasm_text(
+        &mut self,
+        code: &[u8],
+        offset: u64,
+        count: usize,
+    ) -> Vec<(u64, usize, String, String)> {
+        let mut instructions = Vec::new();
+        let mut position = 0;
+        while position < code.len() && (count == 0 || instructions.len() < count) {
+            let address = offset.wrapping_add(position as u64);
+            let entry = match self.decode_lite(&code[position..], address) {
+                Some(size) => (
+                    address,
+                    size,
+                    self.slot.mnemonic().to_string(),
+                    self.slot.operands().to_string(),
+                ),
+                None => match self.data_entry(&code[position..], address) {
+                    Some(data) => (address, data.size, data.mnemonic, data.op_str),
+                    None => break,
+                },
+            };
+            position += entry.1;
+            instructions.push(entry);
+        }
+        instructions
+    }
+
+    /// Returns a `.byte` entry when SKIPDATA is on and enough bytes remain.
+    fn data_entry(&self, code: &[u8], address: u64) -> Option<CsInsn> {
+        let size = self.skipdata_size();
+        (self.skipdata && code.len() >= size).then(|| CsInsn::data(address, &code[..size]))
+    }
+}
+
+#[pymethods]
+impl Cs {
+    #[new]
+    fn new(arch: u32, mode: u32) -> PyResult<Self> {
+        if arch != CS_ARCH_RISCV {
+            return Err(cs_error(CS_ERR_ARCH));
+        }
+        Ok(Self {
+            arch,
+            mode,
+            handler: Self::handler_for(mode, false)?,
+            slot: InstructionSlot::new(),
+            detail: false,
+            skipdata: false,
+        })
+    }
+
+    /// Current mode bits; assigning switches the decoder.
+    #[getter]
+    fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    #[setter]
+    fn set_mode(&mut self, mode: u32) -> PyResult<()> {
+        self.handler = Self::handler_for(mode, self.detail)?;
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Whether `disasm` fills operands, registers and groups.
+    ///
+    /// With detail off the decoder skips operand bookkeeping entirely.
+    #[getter]
+    fn detail(&self) -> bool {
+        self.detail
+    }
+
+    #[setter]
+    fn set_detail(&mut self, detail: bool) -> PyResult<()> {
+        self.handler
+            .set_option(HandlerOption::Detail(detail))
+            .map_err(|_| cs_error(CS_ERR_OPTION))?;
+        self.detail = detail;
+        Ok(())
+    }
+
+    /// Disassembles up to `count` instructions (all when `count` is zero).
+    ///
+    /// Decoding stops at the first invalid instruction unless SKIPDATA is on.
+    #[pyo3(signature = (code, offset, count = 0))]
+    fn disasm(&mut self, code: Cow<'_, [u8]>, offset: u64, count: usize) -> Vec<CsInsn> {
+        let mut instructions = Vec::new();
+        let mut position = 0;
+        while position < code.len() && (count == 0 || instructions.len() < count) {
+            let address = offset.wrapping_add(position as u64);
+            let Some(insn) = self.decode_one(&code[position..], address) else {
+                break;
+            };
+            position += insn.size;
+            instructions.push(insn);
+        }
+        instructions
+    }
+
+    /// Like `disasm`, but yields `(address, size, mnemonic, op_str)` tuples
+    /// and never computes detail.
+    #[pyo3(signature = (code, offset, count = 0))]
+    fn disasm_lite(
+        &mut self,
+        code: Cow<'_, [u8]>,
+        offset: u64,
+        count: usize,
+    ) -> Vec<(u64, usize, String, String)> {
+        // The lite path only needs text, so skip operand bookkeeping even
+        // when detail is on; `set_option(Detail(_))` cannot fail for RISC-V.
+        if self.detail {
+            let _ = self.handler.set_option(HandlerOption::Detail(false));
+        }
+        let instructions = self.disasm_text(&code, offset, count);
+        if self.detail {
+            let _ = self.handler.set_option(HandlerOption::Detail(true));
+        }
+        instructions
+    }
+
+    /// Returns the name of a `RISCV_REG_*` register, or `None` when unknown.
+    fn reg_name(&self, reg_id: u32) -> Option<&'static str> {
+        crate::reg_name(reg_id)
+    }
+
+    /// Returns the name of a `RISCV_GRP_*` group, or `None` when unknown.
+    fn group_name(&self, group_id: u32) -> Option<&'static str> {
+        crate::group_name(group_id)
+    }
+
+    /// Returns the name of a `RISCV_INS_*` instruction, or `None` when unknown.
+    fn insn_name(&self, insn_id: u32) -> Option<&'static str> {
+        crate::insn_name(insn_id)
+    }
+}
+
+/// Returns `true` for the decoder's placeholder mnemonics.
+fn is_unknown(mnemonic: &str) -> bool {
+    matches!(mnemonic, "unknown" | "c.unknown")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // jal ra, 8 ; ld a0, 8(sp) ; one compressed instruction ; two invalid bytes
+    const CODE: [u8; 12] = [
+        0xef, 0x00, 0x80, 0x00, 0x03, 0x35, 0x81, 0x00, 0x82, 0x80, 0xff, 0xff,
+    ];
+
+    fn open() -> Cs {
+        Cs::new(CS_ARCH_RISCV, CS_MODE_RISCV64 | CS_MODE_RISCVC).unwrap()
+    }
+
+    #[test]
+    fn test_disasm_stops_at_invalid_bytes() {
+        let mut md = open();
+        let instructions = md.disasm(Cow::Borrowed(&CODE), 0x1000, 0);
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[1].address, 0x1004);
+        assert_eq!(instructions[1].mnemonic, "ld");
+        assert_eq!(instructions[1].op_str, "a0, 8(sp)");
+        assert_eq!(instructions[1].id, 210);
+        assert_eq!(md.insn_name(instructions[1].id), Some("ld"));
+
+        let limited = md.disasm(Cow::Borrowed(&CODE), 0, 1);
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_disasm_lite_matches_disasm() {
+        let mut md = open();
+        md.set_detail(true).unwrap();
+        let lite = md.disasm_lite(Cow::Borrowed(&CODE), 0x1000, 0);
+        let full = md.disasm(Cow::Borrowed(&CODE), 0x1000, 0);
+        assert_eq!(lite.len(), full.len());
+        for (entry, insn) in lite.iter().zip(&full) {
+            assert_eq!(
+                entry,
+                &(
+                    insn.address,
+                    insn.size,
+                    insn.mnemonic.clone(),
+                    insn.op_str.clone()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_disasm_lite_skips_detail() {
+        let mut md = open();
+        md.set_detail(true).unwrap();
+        md.disasm_lite(Cow::Borrowed(&CODE[4..8]), 0, 0);
+        assert!(md.slot.registers_read().is_empty());
+        assert!(md.handler.decoder().detail());
+    }
+
+    #[test]
+    fn test_skipdata_emits_byte_entries() {
+        let mut md = open();
+        md.skipdata = true;
+        let lite = md.disasm_lite(Cow::Borrowed(&CODE), 0, 0);
+        assert_eq!(
+            lite.last().unwrap(),
+            &(10, 2, ".byte".to_string(), "0xff, 0xff".to_string())
+        );
+    }
+}
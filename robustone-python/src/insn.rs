@@ -0,0 +1,347 @@
+//! Instruction and operand objects returned by `Cs.disasm`.
+
+use pyo3::prelude::*;
+use pyo3::types::PyByteArray;
+
+use robustone_riscv::decoder::RiscVDecodedInstruction;
+use robustone_riscv::groups;
+use robustone_riscv::types::{RiscVOperand, RiscVOperandValue};
+
+use crate::consts::*;
+use crate::cs_error;
+
+/// Memory operand payload (`op.mem`).
+#[pyclass(module = "robustone_py", frozen, skip_from_py_object, get_all)]
+#[derive(Debug, Clone)]
+pub struct RiscVOpMem {
+    /// Base register (`RISCV_REG_*`).
+    pub base: u32,
+    /// Displacement added to the base register.
+    pub disp: i64,
+}
+
+/// One operand of a detailed instruction (`insn.operands[i]`).
+#[pyclass(module = "robustone_py", frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct RiscVOp {
+    /// Operand kind (`RISCV_OP_*`).
+    #[pyo3(get, name = "type")]
+    pub op_type: u8,
+    /// Access flags (`CS_AC_*`).
+    #[pyo3(get)]
+    pub access: u8,
+    value: RiscVOperandValue,
+}
+
+#[pymethods]
+impl RiscVOp {
+    /// Register id of a `RISCV_OP_REG` operand, or zero.
+    #[getter]
+    fn reg(&self) -> u32 {
+        match self.value {
+            RiscVOperandValue::Register(reg) => capstone_register(reg),
+            _ => 0,
+        }
+    }
+
+    /// Value of a `RISCV_OP_IMM` operand, or zero.
+    #[getter]
+    fn imm(&self) -> i64 {
+        match self.value {
+            RiscVOperandValue::Immediate(imm) => imm,
+            _ => 0,
+        }
+    }
+
+    /// Payload of a `RISCV_OP_MEM` operand, or `None`.
+    #[getter]
+    fn mem(&self) -> Option<RiscVOpMem> {
+        match self.value {
+            RiscVOperandValue::Memory(mem) => Some(RiscVOpMem {
+                base: capstone_register(mem.base),
+                disp: mem.disp,
+            }),
+            _ => None,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        match self.value {
+            RiscVOperandValue::Register(reg) => format!("<RiscVOp reg={}>", register_name(reg)),
+            RiscVOperandValue::Immediate(imm) => format!("<RiscVOp imm={imm:#x}>"),
+            RiscVOperandValue::Memory(mem) => {
+                format!("<RiscVOp mem={}({})>", mem.disp, register_name(mem.base))
+            }
+        }
+    }
+}
+
+impl RiscVOp {
+    fn from_operand(operand: &RiscVOperand) -> Self {
+        let op_type = match operand.value {
+            RiscVOperandValue::Register(_) => RISCV_OP_REG,
+            RiscVOperandValue::Immediate(_) => RISCV_OP_IMM,
+            RiscVOperandValue::Memory(_) => RISCV_OP_MEM,
+        };
+        let mut access = CS_AC_INVALID;
+        if operand.access.read {
+            access |= CS_AC_READ;
+        }
+        if operand.access.write {
+            access |= CS_AC_WRITE;
+        }
+        Self {
+            op_type,
+            access,
+            value: operand.value,
+        }
+    }
+}
+
+/// Operand and register bookkeeping, present only when detail mode is on.
+#[derive(Debug, Clone, Default)]
+struct InsnDetail {
+    operands: Vec<RiscVOp>,
+    regs_read: Vec<u32>,
+    regs_write: Vec<u32>,
+    groups: Vec<u32>,
+}
+
+/// A disassembled instruction, mirroring `capstone.CsInsn`.
+#[pyclass(module = "robustone_py", frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct CsInsn {
+    /// Instruction id (`RISCV_INS_*`); zero for SKIPDATA entries.
+    #[pyo3(get)]
+    pub id: u32,
+    /// Address of the first byte.
+    #[pyo3(get)]
+    pub address: u64,
+    /// Encoded length in bytes.
+    #[pyo3(get)]
+    pub size: usize,
+    /// Mnemonic text.
+    #[pyo3(get)]
+    pub mnemonic: String,
+    /// Operand text.
+    #[pyo3(get)]
+    pub op_str: String,
+    raw: Vec<u8>,
+    detail: Option<InsnDetail>,
+    data: bool,
+}
+
+impl CsInsn {
+    /// Builds an instruction without detail from its text form.
+    pub(crate) fn lite(id: u32, address: u64, bytes: &[u8], mnemonic: &str, op_str: &str) -> Self {
+        Self {
+            id,
+            address,
+            size: bytes.len(),
+            mnemonic: mnemonic.to_string(),
+            op_str: op_str.to_string(),
+            raw: bytes.to_vec(),
+            detail: None,
+            data: false,
+        }
+    }
+
+    /// Builds a detailed instruction from the decoder output.
+    pub(crate) fn detailed(address: u64, bytes: &[u8], decoded: &RiscVDecodedInstruction) -> Self {
+        let mut detail = InsnDetail::default();
+        for operand in &decoded.operands_detail {
+            match operand.value {
+                RiscVOperandValue::Register(reg) => {
+                    if operand.access.read {
+                        push_register(&mut detail.regs_read, reg);
+                    }
+                    if operand.access.write {
+                        push_register(&mut detail.regs_write, reg);
+                    }
+                }
+                RiscVOperandValue::Memory(mem) => push_register(&mut detail.regs_read, mem.base),
+                RiscVOperandValue::Immediate(_) => {}
+            }
+            detail.operands.push(RiscVOp::from_operand(operand));
+        }
+        detail.groups = groups::classify(decoded.mnemonic, &decoded.operands_detail)
+            .iter()
+            .map(|group| *group as u32)
+            .collect();
+
+        Self {
+            detail: Some(detail),
+            ..Self::lite(
+                decoded.id().id(),
+                address,
+                bytes,
+                decoded.mnemonic,
+                &decoded.operands.to_string(),
+            )
+        }
+    }
+
+    /// Builds a SKIPDATA `.byte` entry.
+    pub(crate) fn data(address: u64, bytes: &[u8]) -> Self {
+        let op_str = bytes
+            .iter()
+            .map(|byte| format!("0x{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            data: true,
+            ..Self::lite(0, address, bytes, ".byte", &op_str)
+        }
+    }
+
+    fn detail(&self) -> PyResult<&InsnDetail> {
+        if self.data {
+            return Err(cs_error(CS_ERR_SKIPDATA));
+        }
+        self.detail.as_ref().ok_or_else(|| cs_error(CS_ERR_DETAIL))
+    }
+}
+
+#[pymethods]
+impl CsInsn {
+    /// Raw instruction bytes.
+    #[getter]
+    fn bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyByteArray> {
+        PyByteArray::new(py, &self.raw)
+    }
+
+    /// Structured operands; requires detail mode.
+    #[getter]
+    fn operands(&self) -> PyResult<Vec<RiscVOp>> {
+        Ok(self.detail()?.operands.clone())
+    }
+
+    /// Registers read by the instruction; requires detail mode.
+    #[getter]
+    fn regs_read(&self) -> PyResult<Vec<u32>> {
+        Ok(self.detail()?.regs_read.clone())
+    }
+
+    /// Registers written by the instruction; requires detail mode.
+    #[getter]
+    fn regs_write(&self) -> PyResult<Vec<u32>> {
+        Ok(self.detail()?.regs_write.clone())
+    }
+
+    /// Semantic groups (`RISCV_GRP_*`); requires detail mode.
+    #[getter]
+    fn groups(&self) -> PyResult<Vec<u32>> {
+        Ok(self.detail()?.groups.clone())
+    }
+
+    /// Returns `(regs_read, regs_write)` including operand registers.
+    fn regs_access(&self) -> PyResult<(Vec<u32>, Vec<u32>)> {
+        let detail = self.detail()?;
+        Ok((detail.regs_read.clone(), detail.regs_write.clone()))
+    }
+
+    /// Returns `True` if the instruction belongs to `group_id`.
+    fn group(&self, group_id: u32) -> PyResult<bool> {
+        Ok(self.detail()?.groups.contains(&group_id))
+    }
+
+    /// Returns `True` if the instruction reads `reg_id`.
+    fn reg_read(&self, reg_id: u32) -> PyResult<bool> {
+        Ok(self.detail()?.regs_read.contains(&reg_id))
+    }
+
+    /// Returns `True` if the instruction writes `reg_id`.
+    fn reg_write(&self, reg_id: u32) -> PyResult<bool> {
+        Ok(self.detail()?.regs_write.contains(&reg_id))
+    }
+
+    /// Counts operands of type `op_type`.
+    fn op_count(&self, op_type: u8) -> PyResult<usize> {
+        let operands = &self.detail()?.operands;
+        Ok(operands.iter().filter(|op| op.op_type == op_type).count())
+    }
+
+    /// Returns the `position`-th (1-based) operand of type `op_type`.
+    fn op_find(&self, op_type: u8, position: usize) -> PyResult<Option<RiscVOp>> {
+        let operands = &self.detail()?.operands;
+        Ok(operands
+            .iter()
+            .filter(|op| op.op_type == op_type)
+            .nth(position.wrapping_sub(1))
+            .cloned())
+    }
+
+    /// Returns the name of `reg_id`, or `None` when unknown.
+    fn reg_name(&self, reg_id: u32) -> Option<&'static str> {
+        crate::reg_name(reg_id)
+    }
+
+    /// Returns the name of `group_id`, or `None` when unknown.
+    fn group_name(&self, group_id: u32) -> Option<&'static str> {
+        crate::group_name(group_id)
+    }
+
+    /// Returns the canonical name of the instruction id, or `None` for
+    /// SKIPDATA entries.
+    fn insn_name(&self) -> Option<&'static str> {
+        crate::insn_name(self.id)
+    }
+
+    fn __repr__(&self) -> String {
+        let bytes = self
+            .raw
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!(
+            "<CsInsn {:#x} [{}]: {} {}>",
+            self.address, bytes, self.mnemonic, self.op_str
+        )
+    }
+}
+
+/// Maps a decoder register id (see `RiscVRegisterClass::id`) to Capstone's
+/// `RISCV_REG_*` id; both number x0, f0_32 and f0_64 in the same order.
+fn capstone_register(reg: u32) -> u32 {
+    reg + 1
+}
+
+fn register_name(reg: u32) -> &'static str {
+    crate::reg_name(capstone_register(reg)).unwrap_or("invalid")
+}
+
+/// Appends a register in Capstone numbering, skipping duplicates.
+fn push_register(list: &mut Vec<u32>, reg: u32) {
+    let reg = capstone_register(reg);
+    if !list.contains(&reg) {
+        list.push(reg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robustone_riscv::decoder::RiscVDecoder;
+
+    fn decode(bytes: [u8; 4]) -> CsInsn {
+        let decoder = RiscVDecoder::rv64gc();
+        let decoded = decoder.decode(&bytes, 0).unwrap();
+        CsInsn::detailed(0, &bytes, &decoded)
+    }
+
+    #[test]
+    fn test_floating_point_registers() {
+        // fadd.s fa0, fa1, fa2
+        let insn = decode([0x53, 0x85, 0xc5, 0x00]);
+        let regs: Vec<u32> = insn.operands().unwrap().iter().map(RiscVOp::reg).collect();
+        assert_eq!(regs, [43, 44, 45]);
+        assert_eq!(insn.regs_access().unwrap(), (vec![44, 45], vec![43]));
+        assert_eq!(insn.operands().unwrap()[0].__repr__(), "<RiscVOp reg=fa0>");
+
+        // feq.d a0, fa0, fa1
+        let insn = decode([0x53, 0x25, 0xb5, 0xa2]);
+        let regs: Vec<u32> = insn.operands().unwrap().iter().map(RiscVOp::reg).collect();
+        assert_eq!(regs, [11, 75, 76]);
+        assert_eq!(insn.regs_access().unwrap(), (vec![75, 76], vec![11]));
+    }
+}
@@ -0,0 +1,147 @@
+//! Capstone-compatible Python bindings for Robustone.
+//!
+//! This crate builds the `robustone_py` extension module with PyO3. It mirrors
+//! the parts of the `capstone` Python package used by typical analysis
+//! scripts: `Cs(arch, mode)`, `disasm()`, `disasm_lite()`, the `detail` and
+//! `skipdata` toggles, and `CsInsn` accessors such as `operands`,
+//! `regs_access()` and `group()`. Only `CS_ARCH_RISCV` is backed by a decoder.
+//!
+//! ```python
+//! from robustone_py import *
+//!
+//! md = Cs(CS_ARCH_RISCV, CS_MODE_RISCV64 | CS_MODE_RISCVC)
+//! md.detail = True
+//! for insn in md.disasm(b"\x93\x00\x10\x00", 0x1000):
+//!     print(hex(insn.address), insn.mnemonic, insn.op_str, insn.regs_access())
+//! ```
+//!
+//! Build and install it into the active virtualenv with
+//! `maturin develop -m robustone-python/Cargo.toml`.
+
+mod consts;
+mod cs;
+mod insn;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use robustone_riscv::groups::RiscVInstructionGroup;
+
+use consts::{CS_API_MAJOR, CS_API_MINOR, CS_ARCH_ALL, CS_ARCH_RISCV, REGISTER_NAMES};
+
+create_exception!(
+    robustone_py,
+    CsError,
+    PyException,
+    "Raised for Capstone error codes; the code is stored in `errno`."
+);
+
+/// Builds a `CsError` carrying `code` in its `errno` attribute.
+pub(crate) fn cs_error(code: u32) -> PyErr {
+    Python::attach(|py| {
+        let err = CsError::new_err(consts::strerror(code));
+        if let Err(setattr_err) = err.value(py).setattr("errno", code) {
+            return setattr_err;
+        }
+        err
+    })
+}
+
+/// Returns the name of a `RISCV_REG_*` register, or `None` when unknown.
+pub(crate) fn reg_name(reg_id: u32) -> Option<&'static str> {
+    match reg_id {
+        0 => None,
+        _ => REGISTER_NAMES.get(reg_id as usize).copied(),
+    }
+}
+
+/// Returns the name of a `RISCV_GRP_*` group, or `None` when unknown.
+pub(crate) fn group_name(group_id: u32) -> Option<&'static str> {
+    match RiscVInstructionGroup::from_id(group_id) {
+        RiscVInstructionGroup::Invalid => None,
+        group => Some(group.name()),
+    }
+}
+
+/// Returns the name of a `RISCV_INS_*` instruction, or `None` when unknown.
+pub(crate) fn insn_name(insn_id: u32) -> Option<&'static str> {
+    robustone_riscv::insn::insn_name(insn_id)
+}
+
+/// Returns `(major, minor, combined)` like `capstone.cs_version()`.
+#[pyfunction]
+fn cs_version() -> (u32, u32, u32) {
+    (
+        CS_API_MAJOR,
+        CS_API_MINOR,
+        (CS_API_MAJOR << 8) | CS_API_MINOR,
+    )
+}
+
+/// Returns `True` if `query` names a supported architecture.
+#[pyfunction]
+fn cs_support(query: u32) -> bool {
+    matches!(query, CS_ARCH_RISCV | CS_ARCH_ALL)
+}
+
+#[pymodule]
+fn robustone_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<cs::Cs>()?;
+    module.add_class::<insn::CsInsn>()?;
+    module.add_class::<insn::RiscVOp>()?;
+    module.add_class::<insn::RiscVOpMem>()?;
+    module.add("CsError", module.py().get_type::<CsError>())?;
+    module.add_function(wrap_pyfunction!(cs_version, module)?)?;
+    module.add_function(wrap_pyfunction!(cs_support, module)?)?;
+    consts::register(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_names_match_decoder() {
+        use robustone_riscv::types::RiscVRegister;
+
+        for id in 0..96 {
+            assert_eq!(reg_name(id + 1), Some(RiscVRegister::from_id(id).name()));
+        }
+        assert_eq!(reg_name(0), None);
+        assert_eq!(reg_name(43), Some("fa0"));
+        assert_eq!(reg_name(97), None);
+    }
+
+    #[test]
+    fn test_group_names() {
+        assert_eq!(group_name(2), Some("call"));
+        assert_eq!(group_name(0), None);
+        assert_eq!(group_name(8), None);
+    }
+
+    #[test]
+    fn test_insn_names() {
+        use robustone_riscv::insn::RiscVInstructionId;
+
+        let id = RiscVInstructionId::CAddi.id();
+        assert_eq!(id, 93);
+        assert_eq!(insn_name(id), Some("c.addi"));
+        assert_eq!(
+            consts::insn_constant(RiscVInstructionId::CAddi),
+            "RISCV_INS_C_ADDI"
+        );
+        assert_eq!(
+            consts::insn_constant(RiscVInstructionId::AmoaddWAqRl),
+            "RISCV_INS_AMOADD_W_AQ_RL"
+        );
+        assert_eq!(insn_name(0), None);
+    }
+
+    #[test]
+    fn test_version_and_support() {
+        assert_eq!(cs_version(), (5, 0, 0x500));
+        assert!(cs_support(CS_ARCH_RISCV));
+        assert!(!cs_support(3));
+    }
+}
@@ -0,0 +1,605 @@
+//! Pseudo-instruction (alias) resolution.
+//!
+//! The decoder renders instructions the way `cstool` does, which already
+//! folds a few encodings into aliases (`beqz`, `j`, `csrr`, ...). This module
+//! re-renders 32-bit encodings for the other [`AliasMode`]s: either the full
+//! set of pseudo-instructions printed by objdump and LLVM, or no aliases at
+//! all. Like objdump's opcode table, each rule matches the raw instruction
+//! word under a mask, so resolution works without instruction detail.
+//!
+//! Compressed encodings keep their `c.*` mnemonics in every mode.
+
+use robustone_core::types::options::AliasMode;
+
+use crate::decoder::Xlen;
+use crate::shared::encoding::convenience::{extract_b_type, extract_i_type, extract_j_type};
+use crate::shared::operands::unsigned_offset;
+use crate::types::{
+    RiscVOperand, RiscVOperandValue, RiscVOperands, RiscVRegisterClass, RiscVTextOperand,
+    RiscVTextOperands,
+};
+
+const OPCODE: u32 = 0x0000_007f;
+const RD: u32 = 0x0000_0f80;
+const FUNCT3: u32 = 0x0000_7000;
+const RS1: u32 = 0x000f_8000;
+const RS2: u32 = 0x01f0_0000;
+const FUNCT7: u32 = 0xfe00_0000;
+const IMM_I: u32 = 0xfff0_0000;
+const EXACT: u32 = 0xffff_ffff;
+
+const OP_IMM: u32 = 0x13;
+const OP_IMM_32: u32 = 0x1b;
+const OP: u32 = 0x33;
+const OP_32: u32 = 0x3b;
+const BRANCH: u32 = 0x63;
+const JALR: u32 = 0x67;
+const JAL: u32 = 0x6f;
+const SYSTEM: u32 = 0x73;
+const OP_FP: u32 = 0x53;
+
+/// Builds the fixed bits of an encoding from its fields.
+const fn bits(opcode: u32, funct3: u32, funct7: u32) -> u32 {
+    opcode | (funct3 << 12) | (funct7 << 25)
+}
+
+const fn rd(reg: u32) -> u32 {
+    reg << 7
+}
+
+const fn rs1(reg: u32) -> u32 {
+    reg << 15
+}
+
+const fn imm_i(imm: u32) -> u32 {
+    imm << 20
+}
+
+/// Operand rendered from an instruction field.
+#[derive(Debug, Clone, Copy)]
+enum Arg {
+    Rd,
+    Rs1,
+    Rs2,
+    FRd,
+    FRs1,
+    /// Sign-extended I-type immediate.
+    Imm,
+    /// I-type immediate as a CSR number.
+    Csr,
+    /// `rs1` as a 5-bit unsigned immediate.
+    Zimm,
+    /// `imm(rs1)` addressing.
+    Mem,
+    /// B-type branch offset.
+    Branch,
+    /// J-type jump offset.
+    Jump,
+}
+
+/// One row of the alias table.
+struct Rule {
+    mask: u32,
+    matches: u32,
+    mnemonic: &'static str,
+    args: &'static [Arg],
+    /// Restricts the rule to one register width.
+    xlen: Option<Xlen>,
+    /// Additionally requires `rs1 == rs2`.
+    same_sources: bool,
+}
+
+impl Rule {
+    const fn new(mask: u32, matches: u32, mnemonic: &'static str, args: &'static [Arg]) -> Self {
+        Self {
+            mask,
+            matches,
+            mnemonic,
+            args,
+            xlen: None,
+            same_sources: false,
+        }
+    }
+
+    const fn rv32(mut self) -> Self {
+        self.xlen = Some(Xlen::X32);
+        self
+    }
+
+    const fn same_sources(mut self) -> Self {
+        self.same_sources = true;
+        self
+    }
+
+    fn applies(&self, word: u32, xlen: Xlen) -> bool {
+        word & self.mask == self.matches
+            && self.xlen.is_none_or(|only| only == xlen)
+            && (!self.same_sources || (word & RS1) >> 15 == (word & RS2) >> 20)
+    }
+}
+
+use Arg::*;
+
+/// Pseudo-instructions printed by objdump and LLVM, most specific first.
+const PSEUDO: &[Rule] = &[
+    Rule::new(EXACT, bits(OP_IMM, 0, 0), "nop", &[]),
+    Rule::new(OPCODE | FUNCT3 | RS1, bits(OP_IMM, 0, 0), "li", &[Rd, Imm]),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(OP_IMM, 0, 0),
+        "mv",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(OP_IMM, 4, 0) | imm_i(0xfff),
+        "not",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(OP_IMM, 3, 0) | imm_i(1),
+        "seqz",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(OP_IMM_32, 0, 0),
+        "sext.w",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7 | RS1,
+        bits(OP, 0, 0x20),
+        "neg",
+        &[Rd, Rs2],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7 | RS1,
+        bits(OP_32, 0, 0x20),
+        "negw",
+        &[Rd, Rs2],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7 | RS1,
+        bits(OP, 3, 0),
+        "snez",
+        &[Rd, Rs2],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7 | RS2,
+        bits(OP, 2, 0),
+        "sltz",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7 | RS1,
+        bits(OP, 2, 0),
+        "sgtz",
+        &[Rd, Rs2],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS2,
+        bits(BRANCH, 0, 0),
+        "beqz",
+        &[Rs1, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS2,
+        bits(BRANCH, 1, 0),
+        "bnez",
+        &[Rs1, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1,
+        bits(BRANCH, 5, 0),
+        "blez",
+        &[Rs2, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS2,
+        bits(BRANCH, 5, 0),
+        "bgez",
+        &[Rs1, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS2,
+        bits(BRANCH, 4, 0),
+        "bltz",
+        &[Rs1, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1,
+        bits(BRANCH, 4, 0),
+        "bgtz",
+        &[Rs2, Branch],
+    ),
+    Rule::new(OPCODE | RD, bits(JAL, 0, 0), "j", &[Jump]),
+    Rule::new(OPCODE | RD, bits(JAL, 0, 0) | rd(1), "jal", &[Jump]),
+    Rule::new(EXACT, bits(JALR, 0, 0) | rs1(1), "ret", &[]),
+    Rule::new(OPCODE | FUNCT3 | RD | IMM_I, bits(JALR, 0, 0), "jr", &[Rs1]),
+    Rule::new(
+        OPCODE | FUNCT3 | RD | IMM_I,
+        bits(JALR, 0, 0) | rd(1),
+        "jalr",
+        &[Rs1],
+    ),
+    Rule::new(OPCODE | FUNCT3 | RD, bits(JALR, 0, 0), "jr", &[Mem]),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(JALR, 0, 0) | rd(1),
+        "jalr",
+        &[Mem],
+    ),
+    Rule::new(EXACT, bits(SYSTEM, 1, 0) | imm_i(0xc00), "unimp", &[]),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc00),
+        "rdcycle",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc01),
+        "rdtime",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc02),
+        "rdinstret",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc80),
+        "rdcycleh",
+        &[Rd],
+    )
+    .rv32(),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc81),
+        "rdtimeh",
+        &[Rd],
+    )
+    .rv32(),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0xc82),
+        "rdinstreth",
+        &[Rd],
+    )
+    .rv32(),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0x001),
+        "frflags",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0x002),
+        "frrm",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1 | IMM_I,
+        bits(SYSTEM, 2, 0) | imm_i(0x003),
+        "frcsr",
+        &[Rd],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x001),
+        "fsflags",
+        &[Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x001),
+        "fsflags",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x002),
+        "fsrm",
+        &[Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x002),
+        "fsrm",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x003),
+        "fscsr",
+        &[Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | IMM_I,
+        bits(SYSTEM, 1, 0) | imm_i(0x003),
+        "fscsr",
+        &[Rd, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RS1,
+        bits(SYSTEM, 2, 0),
+        "csrr",
+        &[Rd, Csr],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 1, 0),
+        "csrw",
+        &[Csr, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 2, 0),
+        "csrs",
+        &[Csr, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 3, 0),
+        "csrc",
+        &[Csr, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 5, 0),
+        "csrwi",
+        &[Csr, Zimm],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 6, 0),
+        "csrsi",
+        &[Csr, Zimm],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | RD,
+        bits(SYSTEM, 7, 0),
+        "csrci",
+        &[Csr, Zimm],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 0, 0x10),
+        "fmv.s",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 1, 0x10),
+        "fneg.s",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 2, 0x10),
+        "fabs.s",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 0, 0x11),
+        "fmv.d",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 1, 0x11),
+        "fneg.d",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+    Rule::new(
+        OPCODE | FUNCT3 | FUNCT7,
+        bits(OP_FP, 2, 0x11),
+        "fabs.d",
+        &[FRd, FRs1],
+    )
+    .same_sources(),
+];
+
+/// Real-instruction forms of the encodings the decoder prints as aliases or
+/// with implicit operands (`beqz`, `j`, `jal offset`, `jalr offset(rs1)`,
+/// `csrr`, ...).
+const REAL: &[Rule] = &[
+    Rule::new(
+        OPCODE | FUNCT3,
+        bits(BRANCH, 0, 0),
+        "beq",
+        &[Rs1, Rs2, Branch],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3,
+        bits(BRANCH, 1, 0),
+        "bne",
+        &[Rs1, Rs2, Branch],
+    ),
+    Rule::new(OPCODE, JAL, "jal", &[Rd, Jump]),
+    Rule::new(OPCODE | FUNCT3, bits(JALR, 0, 0), "jalr", &[Rd, Mem]),
+    Rule::new(
+        OPCODE | FUNCT3,
+        bits(SYSTEM, 1, 0),
+        "csrrw",
+        &[Rd, Csr, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3,
+        bits(SYSTEM, 2, 0),
+        "csrrs",
+        &[Rd, Csr, Rs1],
+    ),
+    Rule::new(
+        OPCODE | FUNCT3,
+        bits(SYSTEM, 3, 0),
+        "csrrc",
+        &[Rd, Csr, Rs1],
+    ),
+];
+
+/// Renders a 32-bit instruction word for `mode`.
+///
+/// Returns the mnemonic together with its operands, or `None` when the
+/// decoder's own rendering already matches `mode`. Callers must only pass
+/// words that decoded successfully.
+pub fn resolve(
+    mode: AliasMode,
+    xlen: Xlen,
+    word: u32,
+) -> Option<(&'static str, RiscVTextOperands)> {
+    let rule = match mode {
+        AliasMode::All => find(PSEUDO, word, xlen).or_else(|| find(REAL, word, xlen)),
+        AliasMode::None => find(REAL, word, xlen),
+        _ => None,
+    }?;
+    Some((rule.mnemonic, render(rule, word, xlen)))
+}
+
+/// Renders the real instruction behind an alias, for instruction detail.
+///
+/// Returns `None` when the word has no alternative rendering.
+pub fn real_instruction(xlen: Xlen, word: u32) -> Option<(&'static str, RiscVTextOperands)> {
+    resolve(AliasMode::None, xlen, word)
+}
+
+/// Picks, in order, the operands of the real instruction that the alias
+/// prints: `beqz a0, 8` keeps `a0` and `8` of `beq a0, zero, 8`.
+///
+/// Returns `None` when a printed operand has no counterpart in `real`.
+pub fn alias_operands(
+    printed: &[RiscVTextOperand],
+    real: &[RiscVOperand],
+) -> Option<RiscVOperands> {
+    let mut remaining = real.iter();
+    let mut operands = RiscVOperands::new();
+    for text in printed {
+        let operand = remaining.find(|operand| prints(text, operand))?;
+        operands.push(*operand);
+    }
+    Some(operands)
+}
+
+/// Returns `true` when the printed `text` operand stands for `operand`.
+fn prints(text: &RiscVTextOperand, operand: &RiscVOperand) -> bool {
+    match (*text, operand.value) {
+        (RiscVTextOperand::Register(num), RiscVOperandValue::Register(id)) => {
+            RiscVRegisterClass::of(id) == Some((RiscVRegisterClass::Gpr, num))
+        }
+        (RiscVTextOperand::FpRegister(num), RiscVOperandValue::Register(id)) => matches!(
+            RiscVRegisterClass::of(id),
+            Some((RiscVRegisterClass::Fpr32 | RiscVRegisterClass::Fpr64, n)) if n == num
+        ),
+        (
+            RiscVTextOperand::Memory { .. } | RiscVTextOperand::Address(_),
+            RiscVOperandValue::Memory(_),
+        ) => true,
+        (
+            RiscVTextOperand::Immediate(_)
+            | RiscVTextOperand::UpperImmediate(_)
+            | RiscVTextOperand::Zimm(_)
+            | RiscVTextOperand::Offset(_)
+            | RiscVTextOperand::UnsignedOffset(_)
+            | RiscVTextOperand::Csr(_),
+            RiscVOperandValue::Immediate(_),
+        ) => true,
+        _ => false,
+    }
+}
+
+fn find(rules: &'static [Rule], word: u32, xlen: Xlen) -> Option<&'static Rule> {
+    rules.iter().find(|rule| rule.applies(word, xlen))
+}
+
+fn render(rule: &Rule, word: u32, xlen: Xlen) -> RiscVTextOperands {
+    let field = |shift: u32| ((word >> shift) & 0x1f) as u8;
+    let mut operands = RiscVTextOperands::new();
+    for arg in rule.args {
+        operands.push(match arg {
+            Rd => RiscVTextOperand::Register(field(7)),
+            Rs1 => RiscVTextOperand::Register(field(15)),
+            Rs2 => RiscVTextOperand::Register(field(20)),
+            FRd => RiscVTextOperand::FpRegister(field(7)),
+            FRs1 => RiscVTextOperand::FpRegister(field(15)),
+            Imm => RiscVTextOperand::Immediate(extract_i_type(word).imm),
+            Csr => RiscVTextOperand::Csr((word >> 20) as u16),
+            Zimm => RiscVTextOperand::Zimm(field(15)),
+            Mem => RiscVTextOperand::Memory {
+                base: field(15),
+                disp: extract_i_type(word).imm,
+            },
+            Branch => unsigned_offset(extract_b_type(word).imm, Some(xlen)),
+            Jump => unsigned_offset(extract_j_type(word).imm, Some(xlen)),
+        });
+    }
+    operands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn render_word(mode: AliasMode, word: u32) -> Option<String> {
+        let (mnemonic, operands) = resolve(mode, Xlen::X64, word)?;
+        if operands.is_empty() {
+            Some(mnemonic.into())
+        } else {
+            Some(alloc::format!("{mnemonic} {operands}"))
+        }
+    }
+
+    #[test]
+    fn test_pseudo_instructions() {
+        let all = |word| render_word(AliasMode::All, word);
+        assert_eq!(all(0x0000_0013).as_deref(), Some("nop"));
+        assert_eq!(all(0x0050_0513).as_deref(), Some("li a0, 5"));
+        assert_eq!(all(0x0005_8513).as_deref(), Some("mv a0, a1"));
+        assert_eq!(all(0xfff5_c513).as_deref(), Some("not a0, a1"));
+        assert_eq!(all(0x40b0_0533).as_deref(), Some("neg a0, a1"));
+        assert_eq!(all(0x0005_851b).as_deref(), Some("sext.w a0, a1"));
+        assert_eq!(all(0x0015_b513).as_deref(), Some("seqz a0, a1"));
+        assert_eq!(all(0x0000_8067).as_deref(), Some("ret"));
+        assert_eq!(all(0x0005_0067).as_deref(), Some("jr a0"));
+        assert_eq!(all(0x0005_00e7).as_deref(), Some("jalr a0"));
+        assert_eq!(all(0xc000_2573).as_deref(), Some("rdcycle a0"));
+        assert_eq!(all(0x20b5_8553).as_deref(), Some("fmv.s fa0, fa1"));
+        assert_eq!(all(0x22b5_9553).as_deref(), Some("fneg.d fa0, fa1"));
+        assert_eq!(all(0x00a0_5463).as_deref(), Some("blez a0, 8"));
+        assert_eq!(all(0x0045_00e7).as_deref(), Some("jalr 4(a0)"));
+        assert_eq!(all(0x0045_0067).as_deref(), Some("jr 4(a0)"));
+        // Not an alias: falls back to the real form with explicit operands.
+        assert_eq!(all(0x0045_0567).as_deref(), Some("jalr a0, 4(a0)"));
+        assert_eq!(all(0x00b5_0533), None);
+    }
+
+    #[test]
+    fn test_no_aliases() {
+        let none = |word| render_word(AliasMode::None, word);
+        assert_eq!(none(0x0005_0463).as_deref(), Some("beq a0, zero, 8"));
+        assert_eq!(none(0x0080_006f).as_deref(), Some("jal zero, 8"));
+        assert_eq!(none(0x0080_00ef).as_deref(), Some("jal ra, 8"));
+        assert_eq!(none(0x0000_8067).as_deref(), Some("jalr zero, 0(ra)"));
+        assert_eq!(none(0x0030_2573).as_deref(), Some("csrrs a0, fcsr, zero"));
+        assert_eq!(none(0x0000_0013), None);
+        assert_eq!(render_word(AliasMode::Capstone, 0x0005_0463), None);
+    }
+
+    #[test]
+    fn test_rv32_only_aliases() {
+        let mnemonic = |xlen| resolve(AliasMode::All, xlen, 0xc800_2573).map(|(m, _)| m);
+        assert_eq!(mnemonic(Xlen::X32), Some("rdcycleh"));
+        assert_eq!(mnemonic(Xlen::X64), Some("csrr"));
+    }
+}
@@ -3,7 +3,13 @@
 //! This module defines RISC-V specific types and extensions used by
 //! the RISC-V instruction decoder and handler.
 
+use alloc::string::String;
+use alloc::vec::Vec;
 use robustone_core::traits::instruction::Detail;
+use robustone_core::types::operand::{Operand, OperandAccess};
+
+use crate::groups::RiscVInstructionGroup;
+use crate::types::{Access, RiscVOperand, RiscVOperandValue};
 
 /// RISC-V extensions bit mask type.
 ///
@@ -12,7 +18,7 @@ use robustone_core::traits::instruction::Detail;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct RiscVExtensions(u32);
 
-impl std::ops::BitOr for RiscVExtensions {
+impl core::ops::BitOr for RiscVExtensions {
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
@@ -121,6 +127,12 @@ pub struct RiscVInstructionDetail {
     pub regs_read: Vec<u32>,
     /// Registers written by this instruction
     pub regs_write: Vec<u32>,
+    /// `(mnemonic, operands)` of the real instruction when an alias was printed
+    pub real_instruction: Option<(&'static str, String)>,
+    /// Structured operands of the real instruction
+    pub operands: Vec<RiscVOperand>,
+    /// Semantic groups of the instruction
+    pub groups: &'static [RiscVInstructionGroup],
 }
 
 impl Default for RiscVInstructionDetail {
@@ -135,6 +147,9 @@ impl RiscVInstructionDetail {
         Self {
             regs_read: Vec::new(),
             regs_write: Vec::new(),
+            real_instruction: None,
+            operands: Vec::new(),
+            groups: &[],
         }
     }
 
@@ -166,11 +181,49 @@ impl Detail for RiscVInstructionDetail {
     fn registers_written(&self) -> &[u32] {
         &self.regs_write
     }
+
+    fn real_instruction(&self) -> Option<(&str, &str)> {
+        self.real_instruction
+            .as_ref()
+            .map(|(mnemonic, operands)| (*mnemonic, operands.as_str()))
+    }
+
+    fn operands(&self) -> Vec<Operand> {
+        let access = |access: Access| OperandAccess {
+            read: access.read,
+            write: access.write,
+        };
+        self.operands
+            .iter()
+            .map(|operand| match operand.value {
+                RiscVOperandValue::Register(reg) => Operand::Register {
+                    name: RiscVRegister::from_id(reg).name(),
+                    access: access(operand.access),
+                },
+                RiscVOperandValue::Immediate(imm) => Operand::Immediate(imm),
+                RiscVOperandValue::Memory(mem) => Operand::Memory {
+                    base: RiscVRegister::from_id(mem.base).name(),
+                    index: None,
+                    disp: mem.disp,
+                    access: access(operand.access),
+                },
+            })
+            .collect()
+    }
+
+    fn groups(&self) -> Vec<&str> {
+        self.groups.iter().map(|group| group.name()).collect()
+    }
+
+    fn register_name(&self, reg: u32) -> Option<&'static str> {
+        Some(RiscVRegister::from_id(reg).name())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_riscv_extensions() {
@@ -0,0 +1,332 @@
+//! Expansion of compressed (RVC) encodings.
+//!
+//! LLVM and GNU print a compressed instruction as the 32-bit instruction it
+//! stands for (`c.addi a0, 1` becomes `addi a0, a0, 1`), only keeping the
+//! `c.` mnemonics when aliases are turned off. [`expand`] produces that
+//! 32-bit encoding, in the spirit of LLVM's `RISCVRVC::uncompress`, so it
+//! can be decoded and alias-resolved like any other instruction word.
+//! LLVM decodes HINT encodings as instructions of their own instead, which
+//! [`hint`] renders the way `llvm-mc` prints them.
+
+use crate::decoder::Xlen;
+use crate::types::{RiscVTextOperand, RiscVTextOperands};
+
+const LOAD: u32 = 0x03;
+const LOAD_FP: u32 = 0x07;
+const OP_IMM: u32 = 0x13;
+const OP_IMM_32: u32 = 0x1b;
+const STORE: u32 = 0x23;
+const STORE_FP: u32 = 0x27;
+const OP: u32 = 0x33;
+const LUI: u32 = 0x37;
+const OP_32: u32 = 0x3b;
+const BRANCH: u32 = 0x63;
+const JALR: u32 = 0x67;
+const JAL: u32 = 0x6f;
+const EBREAK: u32 = 0x0010_0073;
+
+const SP: u32 = 2;
+const RA: u32 = 1;
+
+/// Returns the 32-bit instruction that the compressed `half` expands to,
+/// or `None` for reserved and unrecognised encodings.
+pub fn expand(half: u16, xlen: Xlen) -> Option<u32> {
+    let c = u32::from(half);
+    let bit = |n: u32| (c >> n) & 1;
+    let bits = |hi: u32, lo: u32| (c >> lo) & ((1 << (hi - lo + 1)) - 1);
+    let rd = bits(11, 7);
+    let rs2 = bits(6, 2);
+    // The three-bit register fields of the CIW/CL/CS/CA/CB formats.
+    let rd_short = bits(4, 2) + 8;
+    let rs1_short = bits(9, 7) + 8;
+    let imm6 = sign_extend(bit(12) << 5 | bits(6, 2), 6);
+    let rv64 = xlen == Xlen::X64;
+
+    // Offsets of the scaled loads and stores.
+    let word_offset = bits(12, 10) << 3 | bit(6) << 2 | bit(5) << 6;
+    let double_offset = bits(12, 10) << 3 | bits(6, 5) << 6;
+    let lwsp_offset = bit(12) << 5 | bits(6, 4) << 2 | bits(3, 2) << 6;
+    let ldsp_offset = bit(12) << 5 | bits(6, 5) << 3 | bits(4, 2) << 6;
+    let swsp_offset = bits(12, 9) << 2 | bits(8, 7) << 6;
+    let sdsp_offset = bits(12, 10) << 3 | bits(9, 7) << 6;
+
+    let expanded = match (c & 0b11, bits(15, 13)) {
+        (0b00, 0b000) => {
+            let imm = bits(12, 11) << 4 | bits(10, 7) << 6 | bit(6) << 2 | bit(5) << 3;
+            if imm == 0 {
+                return None;
+            }
+            i_type(OP_IMM, 0, rd_short, SP, imm)
+        }
+        (0b00, 0b001) => i_type(LOAD_FP, 3, rd_short, rs1_short, double_offset),
+        (0b00, 0b010) => i_type(LOAD, 2, rd_short, rs1_short, word_offset),
+        (0b00, 0b011) if rv64 => i_type(LOAD, 3, rd_short, rs1_short, double_offset),
+        (0b00, 0b011) => i_type(LOAD_FP, 2, rd_short, rs1_short, word_offset),
+        (0b00, 0b101) => s_type(STORE_FP, 3, rs1_short, rd_short, double_offset),
+        (0b00, 0b110) => s_type(STORE, 2, rs1_short, rd_short, word_offset),
+        (0b00, 0b111) if rv64 => s_type(STORE, 3, rs1_short, rd_short, double_offset),
+        (0b00, 0b111) => s_type(STORE_FP, 2, rs1_short, rd_short, word_offset),
+
+        (0b01, 0b000) => i_type(OP_IMM, 0, rd, rd, imm6),
+        (0b01, 0b001) if rv64 => {
+            if rd == 0 {
+                return None;
+            }
+            i_type(OP_IMM_32, 0, rd, rd, imm6)
+        }
+        (0b01, 0b001) => j_type(RA, cj_offset(c)),
+        (0b01, 0b010) => i_type(OP_IMM, 0, rd, 0, imm6),
+        (0b01, 0b011) if rd == SP => {
+            let imm = sign_extend(
+                bit(12) << 9 | bit(6) << 4 | bit(5) << 6 | bits(4, 3) << 7 | bit(2) << 5,
+                10,
+            );
+            if imm == 0 {
+                return None;
+            }
+            i_type(OP_IMM, 0, SP, SP, imm)
+        }
+        (0b01, 0b011) => {
+            if imm6 == 0 {
+                return None;
+            }
+            (imm6 & 0xfffff) << 12 | rd << 7 | LUI
+        }
+        (0b01, 0b100) => {
+            let rd = rs1_short;
+            let shamt = bit(12) << 5 | bits(6, 2);
+            match (bits(11, 10), bit(12), bits(6, 5)) {
+                (0b00, _, _) => shift(5, rd, shamt, 0, xlen)?,
+                (0b01, _, _) => shift(5, rd, shamt, 0x400, xlen)?,
+                (0b10, _, _) => i_type(OP_IMM, 7, rd, rd, imm6),
+                (0b11, 0, 0b00) => r_type(OP, 0, 0x20, rd, rd, rd_short),
+                (0b11, 0, 0b01) => r_type(OP, 4, 0, rd, rd, rd_short),
+                (0b11, 0, 0b10) => r_type(OP, 6, 0, rd, rd, rd_short),
+                (0b11, 0, 0b11) => r_type(OP, 7, 0, rd, rd, rd_short),
+                (0b11, 1, 0b00) if rv64 => r_type(OP_32, 0, 0x20, rd, rd, rd_short),
+                (0b11, 1, 0b01) if rv64 => r_type(OP_32, 0, 0, rd, rd, rd_short),
+                _ => return None,
+            }
+        }
+        (0b01, 0b101) => j_type(0, cj_offset(c)),
+        (0b01, 0b110 | 0b111) => {
+            let imm = sign_extend(
+                bit(12) << 8 | bits(11, 10) << 3 | bits(6, 5) << 6 | bits(4, 3) << 1 | bit(2) << 5,
+                9,
+            );
+            b_type(bits(13, 13), rs1_short, imm)
+        }
+
+        (0b10, 0b000) => shift(1, rd, bit(12) << 5 | bits(6, 2), 0, xlen)?,
+        (0b10, 0b001) => i_type(LOAD_FP, 3, rd, SP, ldsp_offset),
+        (0b10, 0b010) => {
+            if rd == 0 {
+                return None;
+            }
+            i_type(LOAD, 2, rd, SP, lwsp_offset)
+        }
+        (0b10, 0b011) if rv64 => {
+            if rd == 0 {
+                return None;
+            }
+            i_type(LOAD, 3, rd, SP, ldsp_offset)
+        }
+        (0b10, 0b011) => i_type(LOAD_FP, 2, rd, SP, lwsp_offset),
+        (0b10, 0b100) => match (bit(12), rd, rs2) {
+            (0, 0, 0) => return None,
+            (0, _, 0) => i_type(JALR, 0, 0, rd, 0),
+            (0, _, _) => i_type(OP_IMM, 0, rd, rs2, 0),
+            (1, 0, 0) => EBREAK,
+            (1, _, 0) => i_type(JALR, 0, RA, rd, 0),
+            _ => r_type(OP, 0, 0, rd, rd, rs2),
+        },
+        (0b10, 0b101) => s_type(STORE_FP, 3, SP, rs2, sdsp_offset),
+        (0b10, 0b110) => s_type(STORE, 2, SP, rs2, swsp_offset),
+        (0b10, 0b111) if rv64 => s_type(STORE, 3, SP, rs2, sdsp_offset),
+        (0b10, 0b111) => s_type(STORE_FP, 2, SP, rs2, swsp_offset),
+
+        _ => return None,
+    };
+    Some(expanded)
+}
+
+/// Returns LLVM's rendering of the compressed `half` when it is a HINT
+/// encoding (`c.nop 15`, `c.li zero, 20`, `c.slli64 t0`), which LLVM keeps
+/// compressed in every alias mode.
+pub fn hint(half: u16, xlen: Xlen) -> Option<(&'static str, RiscVTextOperands)> {
+    use RiscVTextOperand::{Immediate, Register};
+
+    let c = u32::from(half);
+    let bit = |n: u32| (c >> n) & 1;
+    let bits = |hi: u32, lo: u32| (c >> lo) & ((1 << (hi - lo + 1)) - 1);
+    let rd = bits(11, 7) as u8;
+    let rs2 = bits(6, 2) as u8;
+    let rd_short = bits(9, 7) as u8 + 8;
+    let shamt = bit(12) << 5 | bits(6, 2);
+    let imm6 = i64::from(sign_extend(shamt, 6) as i32);
+
+    let hint: (&'static str, RiscVTextOperands) = match (c & 0b11, bits(15, 13)) {
+        (0b01, 0b000) if rd == 0 && imm6 != 0 => ("c.nop", [Immediate(imm6)].into()),
+        (0b01, 0b010) if rd == 0 => ("c.li", [Register(0), Immediate(imm6)].into()),
+        (0b01, 0b011) if rd == 0 && imm6 != 0 => ("c.lui", [Register(0), Immediate(imm6)].into()),
+        (0b01, 0b100) if shamt == 0 => match bits(11, 10) {
+            0b00 => ("c.srli64", [Register(rd_short)].into()),
+            0b01 => ("c.srai64", [Register(rd_short)].into()),
+            _ => return None,
+        },
+        (0b10, 0b000) if shamt == 0 => ("c.slli64", [Register(rd)].into()),
+        (0b10, 0b000) if rd == 0 && (xlen == Xlen::X64 || shamt < 32) => {
+            ("c.slli", [Register(0), Immediate(i64::from(shamt))].into())
+        }
+        (0b10, 0b100) if rd == 0 && rs2 != 0 => {
+            let mnemonic = if bit(12) == 0 { "c.mv" } else { "c.add" };
+            (mnemonic, [Register(0), Register(rs2)].into())
+        }
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Sign-extends the low `width` bits of `value` to 32 bits.
+fn sign_extend(value: u32, width: u32) -> u32 {
+    let shift = 32 - width;
+    (((value << shift) as i32) >> shift) as u32
+}
+
+/// Decodes the CJ-format jump offset.
+fn cj_offset(c: u32) -> u32 {
+    let bit = |n: u32| (c >> n) & 1;
+    sign_extend(
+        bit(12) << 11
+            | bit(11) << 4
+            | ((c >> 9) & 0b11) << 8
+            | bit(8) << 10
+            | bit(7) << 6
+            | bit(6) << 7
+            | ((c >> 3) & 0b111) << 1
+            | bit(2) << 5,
+        12,
+    )
+}
+
+const fn i_type(opcode: u32, funct3: u32, rd: u32, rs1: u32, imm: u32) -> u32 {
+    (imm & 0xfff) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+const fn s_type(opcode: u32, funct3: u32, rs1: u32, rs2: u32, imm: u32) -> u32 {
+    (imm >> 5 & 0x7f) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | (imm & 0x1f) << 7 | opcode
+}
+
+const fn r_type(opcode: u32, funct3: u32, funct7: u32, rd: u32, rs1: u32, rs2: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+}
+
+/// Encodes `beq`/`bne rs1, zero, imm`.
+const fn b_type(funct3: u32, rs1: u32, imm: u32) -> u32 {
+    (imm >> 12 & 1) << 31
+        | (imm >> 5 & 0x3f) << 25
+        | rs1 << 15
+        | funct3 << 12
+        | (imm >> 1 & 0xf) << 8
+        | (imm >> 11 & 1) << 7
+        | BRANCH
+}
+
+/// Encodes `jal rd, imm`.
+const fn j_type(rd: u32, imm: u32) -> u32 {
+    (imm >> 20 & 1) << 31
+        | (imm >> 1 & 0x3ff) << 21
+        | (imm >> 11 & 1) << 20
+        | (imm >> 12 & 0xff) << 12
+        | rd << 7
+        | JAL
+}
+
+/// Encodes `slli`/`srli`/`srai rd, rd, shamt`, rejecting shift amounts that
+/// do not fit `xlen`. `funct6` carries the arithmetic-shift bit.
+fn shift(funct3: u32, rd: u32, shamt: u32, funct6: u32, xlen: Xlen) -> Option<u32> {
+    if xlen == Xlen::X32 && shamt >= 32 {
+        return None;
+    }
+    Some(i_type(OP_IMM, funct3, rd, rd, funct6 | shamt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_matches_standard_encodings() {
+        let rv64 = |half| expand(half, Xlen::X64);
+        // c.addi a0, 1 -> addi a0, a0, 1
+        assert_eq!(rv64(0x0505), Some(0x0015_0513));
+        // c.li a0, 5 -> addi a0, zero, 5
+        assert_eq!(rv64(0x4515), Some(0x0050_0513));
+        // c.addi16sp sp, -48 -> addi sp, sp, -48
+        assert_eq!(rv64(0x7179), Some(0xfd01_0113));
+        // c.lui a0, 1 -> lui a0, 1
+        assert_eq!(rv64(0x6505), Some(0x0000_1537));
+        // c.mv a0, a1 -> addi a0, a1, 0
+        assert_eq!(rv64(0x852e), Some(0x0005_8513));
+        // c.jr ra -> jalr zero, 0(ra)
+        assert_eq!(rv64(0x8082), Some(0x0000_8067));
+        // c.add a0, a1 -> add a0, a0, a1
+        assert_eq!(rv64(0x952e), Some(0x00b5_0533));
+        // c.ldsp ra, 8(sp) -> ld ra, 8(sp)
+        assert_eq!(rv64(0x60a2), Some(0x0081_3083));
+        // c.sdsp ra, 8(sp) -> sd ra, 8(sp)
+        assert_eq!(rv64(0xe406), Some(0x0011_3423));
+        // c.lw a0, 4(a1) -> lw a0, 4(a1)
+        assert_eq!(rv64(0x41c8), Some(0x0045_a503));
+        // c.beqz a0, 8 -> beq a0, zero, 8
+        assert_eq!(rv64(0xc501), Some(0x0005_0463));
+        // c.j -2 -> jal zero, -2
+        assert_eq!(rv64(0xbffd), Some(0xffff_f06f));
+        // c.sub a0, a1 -> sub a0, a0, a1
+        assert_eq!(rv64(0x8d0d), Some(0x40b5_0533));
+        // c.srai a0, 3 -> srai a0, a0, 3
+        assert_eq!(rv64(0x850d), Some(0x4035_5513));
+        // c.ebreak
+        assert_eq!(rv64(0x9002), Some(EBREAK));
+    }
+
+    #[test]
+    fn test_expand_depends_on_xlen() {
+        // c.jal 8 on RV32, c.addiw a0, 1 on RV64
+        assert_eq!(expand(0x2021, Xlen::X32), Some(0x0080_00ef));
+        assert_eq!(expand(0x2505, Xlen::X64), Some(0x0015_051b));
+        // c.slli a0, 32 is RV64-only.
+        assert_eq!(expand(0x1502, Xlen::X32), None);
+        assert_eq!(expand(0x1502, Xlen::X64), Some(0x0205_1513));
+    }
+
+    #[test]
+    fn test_hints_stay_compressed() {
+        let render = |half| {
+            let (mnemonic, operands) = hint(half, Xlen::X64)?;
+            Some((mnemonic, operands.len()))
+        };
+        assert_eq!(render(0x003d), Some(("c.nop", 1)));
+        assert_eq!(render(0x4051), Some(("c.li", 2)));
+        assert_eq!(render(0x0282), Some(("c.slli64", 1)));
+        assert_eq!(render(0x8401), Some(("c.srai64", 1)));
+        assert_eq!(render(0x802a), Some(("c.mv", 2)));
+        // c.nop and c.addi a0, 1 are not HINTs.
+        assert_eq!(render(0x0001), None);
+        assert_eq!(render(0x0505), None);
+        // c.slli zero, 32 is reserved on RV32.
+        assert_eq!(hint(0x1002, Xlen::X32), None);
+    }
+
+    #[test]
+    fn test_expand_rejects_reserved_encodings() {
+        // c.unimp (all zeros) and c.addi4spn with a zero immediate
+        assert_eq!(expand(0x0000, Xlen::X64), None);
+        // c.addiw with rd = zero
+        assert_eq!(expand(0x2001, Xlen::X64), None);
+        // c.lwsp with rd = zero
+        assert_eq!(expand(0x4002, Xlen::X64), None);
+        // c.jr with rs1 = zero
+        assert_eq!(expand(0x8002, Xlen::X64), None);
+    }
+}
@@ -0,0 +1,238 @@
+//! Constant propagation through `lui`/`auipc` chains.
+//!
+//! RISC-V builds 32-bit constants and addresses in two halves: `lui` or
+//! `auipc` sets the upper 20 bits and `addi`/`addiw`, a load, a store or a
+//! `jalr` supplies the low 12. [`ConstantTracker`] follows register values
+//! set by such chains through straight-line code and reports, for each
+//! instruction that completes one, the materialised value.
+//!
+//! Register values are read from `operands_detail`, so the decoder must
+//! have detail enabled. Every branch, jump or call forgets them, as does
+//! any other instruction writing a tracked register.
+
+use crate::decoder::Xlen;
+use crate::targets::{self, AUIPC, Flow, LUI, OP_IMM, OP_IMM_32};
+use crate::types::{RiscVOperand, RiscVOperandType, RiscVOperandValue};
+
+/// How an instruction uses a materialised value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueUse {
+    /// Computed into a register by `addi`/`addiw`.
+    Compute,
+    /// Address read by a load.
+    Load,
+    /// Address written by a store.
+    Store,
+    /// Target of a `jalr` that does not link.
+    Jump,
+    /// Target of a `jalr` that links.
+    Call,
+}
+
+/// A value completed by one instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Materialized {
+    pub value: u64,
+    /// Whether the chain starts with `auipc`, making the value an address
+    /// rather than an absolute constant.
+    pub pc_relative: bool,
+    pub usage: ValueUse,
+}
+
+/// Register values known from `lui`/`auipc` chains.
+#[derive(Debug, Clone)]
+pub struct ConstantTracker {
+    xlen: Xlen,
+    /// Value of each register and whether it came from `auipc`.
+    known: [Option<(u64, bool)>; 32],
+}
+
+impl ConstantTracker {
+    /// Create a tracker knowing no register.
+    pub fn new(xlen: Xlen) -> Self {
+        Self {
+            xlen,
+            known: [None; 32],
+        }
+    }
+
+    /// Forget every register, at a gap or an undecodable instruction.
+    pub fn reset(&mut self) {
+        self.known = [None; 32];
+    }
+
+    /// Advance past the instruction `word` at `pc`, with its detailed
+    /// `operands`, and return the value it completes, if any.
+    pub fn step(&mut self, word: u32, pc: u64, operands: &[RiscVOperand]) -> Option<Materialized> {
+        let xlen = self.xlen;
+        let flow = targets::control_flow(word, pc, xlen);
+        let with = |value: u64, pc_relative, usage| Materialized {
+            value: targets::wrap(value, xlen),
+            pc_relative,
+            usage,
+        };
+
+        // Value this instruction completes, and the one it leaves in rd.
+        let mut completed = None;
+        let mut result = None;
+        match word & 0x7f {
+            _ if matches!(flow, Flow::Indirect | Flow::IndirectCall) => {
+                let base = registers(operands).nth(1);
+                if let (Some((value, pc_relative)), Some(imm)) =
+                    (self.tracked(base), immediate(operands))
+                {
+                    let usage = if flow == Flow::Indirect {
+                        ValueUse::Jump
+                    } else {
+                        ValueUse::Call
+                    };
+                    completed = Some(with(value.wrapping_add(imm as u64), pc_relative, usage));
+                }
+            }
+            opcode @ (LUI | AUIPC) => {
+                if let Some(imm) = immediate(operands) {
+                    let high = i64::from((imm << 12) as i32) as u64;
+                    result = Some(if opcode == LUI {
+                        (targets::wrap(high, xlen), false)
+                    } else {
+                        (targets::wrap(pc.wrapping_add(high), xlen), true)
+                    });
+                }
+            }
+            opcode @ (OP_IMM | OP_IMM_32) if (word >> 12) & 0x7 == 0 => {
+                // `c.addi` lists its register once.
+                let source = registers(operands).last();
+                if let (Some((value, pc_relative)), Some(imm)) =
+                    (self.tracked(source), immediate(operands))
+                {
+                    let mut value = value.wrapping_add(imm as u64);
+                    if opcode == OP_IMM_32 {
+                        value = i64::from(value as i32) as u64;
+                    }
+                    let value = with(value, pc_relative, ValueUse::Compute);
+                    result = Some((value.value, pc_relative));
+                    completed = Some(value);
+                }
+            }
+            _ => {
+                if let Some((base, disp)) = memory(operands)
+                    && let Some((value, pc_relative)) = self.tracked(Some(base))
+                {
+                    // Loads write their first operand, stores read it.
+                    let usage = match operands.first() {
+                        Some(first)
+                            if first.op_type == RiscVOperandType::Register
+                                && first.access.write =>
+                        {
+                            ValueUse::Load
+                        }
+                        _ => ValueUse::Store,
+                    };
+                    completed = Some(with(value.wrapping_add(disp as u64), pc_relative, usage));
+                }
+            }
+        }
+
+        if flow != Flow::Next {
+            self.reset();
+            return completed;
+        }
+        for operand in operands {
+            if let RiscVOperandValue::Register(reg) = operand.value
+                && operand.access.write
+                && let Some(slot) = self.known.get_mut(reg as usize)
+            {
+                *slot = None;
+            }
+        }
+        if let (Some(rd), Some(value)) = (registers(operands).next(), result)
+            && rd != 0
+        {
+            self.known[rd as usize] = Some(value);
+        }
+        completed
+    }
+
+    /// The tracked value of `reg`.
+    fn tracked(&self, reg: Option<u32>) -> Option<(u64, bool)> {
+        self.known.get(reg? as usize).copied().flatten()
+    }
+}
+
+/// The register operands, in order.
+fn registers(operands: &[RiscVOperand]) -> impl Iterator<Item = u32> + '_ {
+    operands.iter().filter_map(|operand| match operand.value {
+        RiscVOperandValue::Register(reg) => Some(reg),
+        _ => None,
+    })
+}
+
+/// The last immediate operand.
+fn immediate(operands: &[RiscVOperand]) -> Option<i64> {
+    operands
+        .iter()
+        .rev()
+        .find_map(|operand| match operand.value {
+            RiscVOperandValue::Immediate(imm) => Some(imm),
+            _ => None,
+        })
+}
+
+/// The base register and displacement of the memory operand.
+fn memory(operands: &[RiscVOperand]) -> Option<(u32, i64)> {
+    operands.iter().find_map(|operand| match operand.value {
+        RiscVOperandValue::Memory(mem) => Some((mem.base, mem.disp)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::RiscVDecoder;
+    use crate::extensions::Extensions;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_step() {
+        // 1000: lui a0, 0x80001     1004: addi a0, a0, 0x234
+        // 1008: auipc t0, 0x1       100c: jalr ra, -8(t0)
+        // 1010: addi a0, a0, 1
+        let code: [u32; 5] = [
+            0x8000_1537,
+            0x2345_0513,
+            0x0000_1297,
+            0xff82_80e7,
+            0x0015_0513,
+        ];
+        let decoder = RiscVDecoder::new(Xlen::X32, Extensions::rv32gc());
+        let mut tracker = ConstantTracker::new(Xlen::X32);
+        let values: Vec<Option<Materialized>> = code
+            .iter()
+            .zip((0x1000u64..).step_by(4))
+            .map(|(&word, pc)| {
+                let decoded = decoder.decode(&word.to_le_bytes(), pc).unwrap();
+                tracker.step(word, pc, &decoded.operands_detail)
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                None,
+                Some(Materialized {
+                    value: 0x8000_1234,
+                    pc_relative: false,
+                    usage: ValueUse::Compute,
+                }),
+                None,
+                Some(Materialized {
+                    value: 0x2000,
+                    pc_relative: true,
+                    usage: ValueUse::Call,
+                }),
+                // The call forgets a0.
+                None,
+            ]
+        );
+    }
+}
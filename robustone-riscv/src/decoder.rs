@@ -4,9 +4,12 @@
 //! is implemented as a separate module, making the codebase more maintainable
 //! and easier to extend with new instructions.
 
-use super::extensions::standard::Standard;
 use super::extensions::{Extensions, InstructionExtension, create_extensions};
+use super::insn::RiscVInstructionId;
 use super::types::*;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use robustone_core::types::error::DisasmError;
 
 /// RISC-V XLEN (register width) indicator.
@@ -22,16 +25,18 @@ pub struct RiscVDecoder {
     xlen: Xlen,
     extensions: Extensions,
     extension_handlers: Vec<Box<dyn InstructionExtension>>,
+    detail: bool,
 }
 
 impl RiscVDecoder {
     /// Construct a decoder with the provided XLEN and extension bitmask.
     pub fn new(xlen: Xlen, extensions: Extensions) -> Self {
-        let extension_handlers = create_extensions(xlen);
+        let extension_handlers = create_extensions(xlen, true);
         Self {
             xlen,
             extensions,
             extension_handlers,
+            detail: true,
         }
     }
 
@@ -45,6 +50,27 @@ impl RiscVDecoder {
         Self::new(Xlen::X64, Extensions::rv64gc())
     }
 
+    /// Returns the register width this decoder targets.
+    pub fn xlen(&self) -> Xlen {
+        self.xlen
+    }
+
+    /// Returns whether decoded instructions carry `operands_detail`.
+    pub fn detail(&self) -> bool {
+        self.detail
+    }
+
+    /// Enables or disables building `operands_detail`.
+    ///
+    /// With detail off the mnemonic and operand text are unchanged, but
+    /// `operands_detail` is always empty; this is the decoder's fast path.
+    pub fn set_detail(&mut self, detail: bool) {
+        if detail != self.detail {
+            self.extension_handlers = create_extensions(self.xlen, detail);
+            self.detail = detail;
+        }
+    }
+
     /// Decode a single instruction located at `address`.
     pub fn decode(
         &self,
@@ -214,10 +240,6 @@ impl RiscVDecoder {
             | ((instruction >> 6) & 0x1) << 6          // imm[6] from instruction[6]
             | ((instruction >> 9) & 0x3) << 7; // imm[8:7] from instruction[9:8]
 
-        if !self.extensions.standard.contains(Standard::C) {
-            eprintln!("Warning: Decoding compressed instruction while C extension is disabled");
-        }
-
         // Try each enabled extension for compressed instructions
         for extension in &self.extension_handlers {
             if !extension.is_enabled(&self.extensions) {
@@ -277,21 +299,27 @@ impl RiscVDecoder {
         instruction: u32,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "unknown".to_string(),
-            operands: format!("0x{instruction:08x}"),
+            mnemonic: "unknown",
+            operands: RiscVTextOperands::from([RiscVTextOperand::Encoding {
+                value: instruction,
+                size: 4,
+            }]),
             format: RiscVInstructionFormat::I,
             size: 4,
-            operands_detail: vec![],
+            operands_detail: RiscVOperands::new(),
         })
     }
 
     fn decode_c_unknown(&self, instruction: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.unknown".to_string(),
-            operands: format!("0x{instruction:04x}"),
+            mnemonic: "c.unknown",
+            operands: RiscVTextOperands::from([RiscVTextOperand::Encoding {
+                value: u32::from(instruction),
+                size: 2,
+            }]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![],
+            operands_detail: RiscVOperands::new(),
         })
     }
 }
@@ -300,20 +328,31 @@ impl RiscVDecoder {
 #[derive(Debug, Clone)]
 pub struct RiscVDecodedInstruction {
     /// Instruction mnemonic.
-    pub mnemonic: String,
-    /// Formatted operand string.
-    pub operands: String,
+    pub mnemonic: &'static str,
+    /// Operands as printed, formatted by
+    /// [`RiscVPrinter`](crate::printer::RiscVPrinter) or `Display`.
+    pub operands: RiscVTextOperands,
     /// Instruction format discriminator.
     pub format: RiscVInstructionFormat,
     /// Size of the instruction in bytes.
     pub size: usize,
     /// Structured operand details for downstream consumption.
-    pub operands_detail: Vec<RiscVOperand>,
+    pub operands_detail: RiscVOperands,
+}
+
+impl RiscVDecodedInstruction {
+    /// Returns the instruction id; pseudo-instructions report the id of the
+    /// instruction they stand for.
+    pub fn id(&self) -> RiscVInstructionId {
+        RiscVInstructionId::from_mnemonic(self.mnemonic)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extensions::standard::Standard;
+    use alloc::format;
 
     #[test]
     fn test_refactored_decoder_creation() {
@@ -341,13 +380,7 @@ mod tests {
         let instruction = ((100u32 << 20) | (2u32 << 15)) | (1u32 << 7) | 0b0010011;
         let bytes = instruction.to_le_bytes();
 
-        println!("Testing ADDI instruction: 0x{instruction:08x}");
-        println!("Bytes: {bytes:?}");
-
         let result = decoder.decode(&bytes, 0);
-        if let Err(e) = &result {
-            println!("Decoding error: {e:?}");
-        }
         assert!(result.is_ok(), "Failed to decode instruction: {result:?}");
 
         let instr = result.unwrap();
@@ -368,4 +401,41 @@ mod tests {
         assert_eq!(instr.mnemonic, "c.addi");
         assert_eq!(instr.size, 2);
     }
+
+    #[test]
+    fn test_compressed_register_format() {
+        let decoder = RiscVDecoder::rv64gc();
+        let decode = |bytes: [u8; 2]| {
+            let instr = decoder.decode(&bytes, 0).unwrap();
+            format!("{} {}", instr.mnemonic, instr.operands)
+        };
+
+        assert_eq!(decode([0x82, 0x80]), "c.jr ra");
+        assert_eq!(decode([0x2e, 0x85]), "c.mv a0, a1");
+        assert_eq!(decode([0x02, 0x90]), "c.ebreak ");
+        assert_eq!(decode([0x82, 0x92]), "c.jalr t0");
+        assert_eq!(decode([0x2e, 0x95]), "c.add a0, a1");
+    }
+
+    #[test]
+    fn test_compressed_arithmetic() {
+        let decode = |decoder: &RiscVDecoder, bytes: [u8; 2]| {
+            let instr = decoder.decode(&bytes, 0).unwrap();
+            format!("{} {}", instr.mnemonic, instr.operands)
+        };
+        let rv64 = RiscVDecoder::rv64gc();
+
+        assert_eq!(decode(&rv64, [0x0d, 0x84]), "c.srai s0, 3");
+        assert_eq!(decode(&rv64, [0x85, 0x83]), "c.srli a5, 1");
+        assert_eq!(decode(&rv64, [0x71, 0x9b]), "c.andi a4, -4");
+        assert_eq!(decode(&rv64, [0x89, 0x8c]), "c.sub s1, a0");
+        assert_eq!(decode(&rv64, [0x89, 0x9c]), "c.subw s1, a0");
+        assert_eq!(decode(&rv64, [0x35, 0x9e]), "c.addw a2, a3");
+        assert_eq!(decode(&rv64, [0x79, 0x71]), "c.addi16sp sp, -0x30");
+        assert_eq!(decode(&rv64, [0x7d, 0x75]), "c.lui a0, 0xfffff");
+        assert_eq!(
+            decode(&RiscVDecoder::rv32gc(), [0x89, 0x9c]),
+            "c.unknown 0x9c89"
+        );
+    }
 }
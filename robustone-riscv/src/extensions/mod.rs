@@ -4,6 +4,10 @@
 //! extensions, organized into separate modules for better maintainability.
 
 use super::decoder::{RiscVDecodedInstruction, Xlen};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 use robustone_core::types::error::DisasmError;
 
 // Submodules grouping standard and custom-specific extensions.
@@ -38,11 +42,97 @@ impl Extensions {
         }
     }
 
+    /// Disables the compressed (C) extension on this configuration.
+    pub fn without_compressed(mut self) -> Self {
+        self.standard.remove(Standard::C);
+        self
+    }
+
     /// Enables all available T-Head custom extensions on this configuration.
     pub fn thead(mut self) -> Self {
         self.thead |= THead::all();
         self
     }
+
+    /// Parses an ISA string such as `rv64imafdc`, `rv32gc_zicsr` or the
+    /// versioned `Tag_RISCV_arch` form `rv64i2p1_m2p0_a2p1_c2p0`.
+    ///
+    /// Returns the XLEN and the extensions this decoder supports; other
+    /// extensions are ignored. The RV32E base decodes like RV32I.
+    pub fn from_isa(isa: &str) -> Option<(Xlen, Self)> {
+        let isa = isa.trim().to_ascii_lowercase();
+        let rest = isa.strip_prefix("rv")?;
+        let (xlen, rest) = if let Some(rest) = rest.strip_prefix("32") {
+            (Xlen::X32, rest)
+        } else if let Some(rest) = rest.strip_prefix("64") {
+            (Xlen::X64, rest)
+        } else {
+            return None;
+        };
+
+        let mut extensions = Self {
+            standard: Standard::empty(),
+            thead: THead::empty(),
+        };
+        // Single-letter extensions may be run together (`imac`) or, when
+        // versioned, separated like multi-letter ones (`i2p1_m2p0`).
+        for segment in rest.split('_') {
+            let name = segment.trim_end_matches(|c: char| c.is_ascii_digit() || c == 'p');
+            match name {
+                "zca" => extensions.standard |= Standard::C,
+                "xtheadcondmov" => extensions.thead |= THead::CMOV,
+                _ if name.starts_with(['z', 'x', 's']) => {}
+                _ => {
+                    for letter in segment.chars().filter(|c| !c.is_ascii_digit() && *c != 'p') {
+                        extensions.standard |= match letter {
+                            'i' | 'e' => Standard::I,
+                            'g' => Standard::G,
+                            'm' => Standard::M,
+                            'a' => Standard::A,
+                            'f' => Standard::F,
+                            'd' => Standard::D,
+                            'c' => Standard::C,
+                            _ => Standard::empty(),
+                        };
+                    }
+                }
+            }
+        }
+        if !extensions.standard.contains(Standard::I) {
+            return None;
+        }
+        Some((xlen, extensions))
+    }
+
+    /// Formats these extensions as an ISA string for `xlen`, the inverse of
+    /// [`from_isa`](Self::from_isa), e.g. `rv64imafdc_zicsr_zifencei`.
+    ///
+    /// The base decoder handles the CSR and `fence.i` instructions, which
+    /// current ISA specifications split out of I, so `zicsr` and `zifencei`
+    /// are always named.
+    pub fn to_isa(&self, xlen: Xlen) -> String {
+        let mut isa = String::from(match xlen {
+            Xlen::X32 => "rv32",
+            Xlen::X64 => "rv64",
+        });
+        for (flag, letter) in [
+            (Standard::I, 'i'),
+            (Standard::M, 'm'),
+            (Standard::A, 'a'),
+            (Standard::F, 'f'),
+            (Standard::D, 'd'),
+            (Standard::C, 'c'),
+        ] {
+            if self.standard.contains(flag) {
+                isa.push(letter);
+            }
+        }
+        isa.push_str("_zicsr_zifencei");
+        if self.thead.contains(THead::CMOV) {
+            isa.push_str("_xtheadcondmov");
+        }
+        isa
+    }
 }
 
 /// Trait that all instruction set extensions must implement.
@@ -108,14 +198,56 @@ pub trait InstructionExtension: Sync {
 }
 
 /// Create all available standard RISC-V extensions.
-pub fn create_extensions(xlen: Xlen) -> Vec<Box<dyn InstructionExtension>> {
+///
+/// When `detail` is false the extensions leave `operands_detail` empty.
+pub fn create_extensions(xlen: Xlen, detail: bool) -> Vec<Box<dyn InstructionExtension>> {
     vec![
-        Box::new(standard::Rvi::new_with_xlen(xlen)),
-        Box::new(standard::Rva::new()),
-        Box::new(standard::Rvm::new()),
-        Box::new(standard::Rvf::new()),
-        Box::new(standard::Rvd::new()),
-        Box::new(standard::Rvc::new()),
-        Box::new(thead::CMov::new()),
+        Box::new(standard::Rvi::new_with_xlen(xlen).with_detail(detail)),
+        Box::new(standard::Rva::new().with_detail(detail)),
+        Box::new(standard::Rvm::new().with_detail(detail)),
+        Box::new(standard::Rvf::new().with_detail(detail)),
+        Box::new(standard::Rvd::new().with_detail(detail)),
+        Box::new(standard::Rvc::new().with_detail(detail)),
+        Box::new(thead::CMov::new().with_detail(detail)),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_isa() {
+        let (xlen, extensions) = Extensions::from_isa("rv64gc").unwrap();
+        assert_eq!(xlen, Xlen::X64);
+        assert_eq!(
+            extensions.standard.bits(),
+            (Standard::G | Standard::C).bits()
+        );
+
+        let (xlen, extensions) =
+            Extensions::from_isa("rv32i2p1_m2p0_a2p1_c2p0_zicsr2p0_xtheadcondmov1p0").unwrap();
+        assert_eq!(xlen, Xlen::X32);
+        assert_eq!(
+            extensions.standard.bits(),
+            (Standard::I | Standard::M | Standard::A | Standard::C).bits()
+        );
+        assert!(extensions.thead.contains(THead::CMOV));
+
+        let (_, extensions) = Extensions::from_isa("rv32e_zca").unwrap();
+        assert_eq!(
+            extensions.standard.bits(),
+            (Standard::I | Standard::C).bits()
+        );
+
+        assert_eq!(
+            Extensions::rv64gc().thead().to_isa(Xlen::X64),
+            "rv64imafdc_zicsr_zifencei_xtheadcondmov"
+        );
+        let (xlen, extensions) = Extensions::from_isa("rv32imc").unwrap();
+        assert_eq!(extensions.to_isa(xlen), "rv32imc_zicsr_zifencei");
+
+        assert!(Extensions::from_isa("rv128i").is_none());
+        assert!(Extensions::from_isa("x86").is_none());
+    }
+}
@@ -6,24 +6,28 @@
 use super::Standard;
 use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
-use crate::shared::{
-    operands::convenience,
-    registers::{RegisterManager, RegisterNameProvider},
-};
+use crate::shared::operands::{convenience, detail_operands};
 use crate::types::*;
+use RiscVTextOperand::{Address, Register};
+use alloc::string::ToString;
 use robustone_core::types::error::DisasmError;
 
 /// RVA Atomic Instructions Extension
 pub struct Rva {
-    register_manager: RegisterManager,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl Rva {
     /// Create a new RVA extension instance.
     pub fn new() -> Self {
-        Self {
-            register_manager: RegisterManager::new(),
-        }
+        Self { detail: true }
+    }
+
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
     }
 
     // A-extension opcode
@@ -52,61 +56,53 @@ impl Rva {
 
     fn decode_amo(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}, ({})",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs2),
-                self.register_manager.int_register_name(rs1)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd), Register(rs2), Address(rs1)]),
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs2, Access::read()),
-                convenience::register(rs1, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::register(rs2, Access::read()),
+                    convenience::register(rs1, Access::read()),
+                ],
+            ),
         })
     }
 
     fn decode_lr_sc(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         let operands = if mnemonic == "lr.w" || mnemonic == "lr.d" {
-            format!(
-                "{}, ({})",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs1)
-            )
+            RiscVTextOperands::from([Register(rd), Address(rs1)])
         } else {
-            format!(
-                "{}, {}, ({})",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs2),
-                self.register_manager.int_register_name(rs1)
-            )
+            RiscVTextOperands::from([Register(rd), Register(rs2), Address(rs1)])
         };
 
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
+            mnemonic,
             operands,
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs2, Access::read()),
-                convenience::register(rs1, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::register(rs2, Access::read()),
+                    convenience::register(rs1, Access::read()),
+                ],
+            ),
         })
     }
 }
@@ -8,113 +8,130 @@ use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
 use crate::shared::{
     encoding::convenience as encoding_conv,
-    operands::convenience,
-    registers::{RegisterManager, RegisterNameProvider},
+    operands::{convenience, detail_operands},
 };
 use crate::types::*;
+use RiscVTextOperand::{Encoding, Immediate, Memory, Offset, Register, UpperImmediate};
 use robustone_core::types::error::DisasmError;
 
 /// RVC Compressed Instructions Extension
 pub struct Rvc {
-    register_manager: RegisterManager,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl Rvc {
     /// Create a new RVC extension instance.
     pub fn new() -> Self {
-        Self {
-            register_manager: RegisterManager::new(),
-        }
+        Self { detail: true }
+    }
+
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
     }
 
     fn decode_c_addi4spn(&self, rdp: u8, imm: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = imm as i64;
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.addi4spn".to_string(),
-            operands: format!(
-                "{}, sp, {}",
-                self.register_manager.int_register_name(rdp + 8),
-                convenience::format_immediate(imm_val)
-            ),
+            mnemonic: "c.addi4spn",
+            operands: RiscVTextOperands::from([Register(rdp + 8), Register(2), Immediate(imm_val)]),
             format: RiscVInstructionFormat::CIW,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rdp + 8, Access::write()),
-                convenience::register(2, Access::read()),
-                convenience::immediate(imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rdp + 8, Access::write()),
+                    convenience::register(2, Access::read()),
+                    convenience::immediate(imm_val),
+                ],
+            ),
         })
     }
 
     fn decode_c_addi16sp(&self, rd: u8, imm: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = encoding_conv::sign_extend_16(imm, 10);
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.addi16sp".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                convenience::format_immediate(imm_val)
-            ),
+            mnemonic: "c.addi16sp",
+            operands: RiscVTextOperands::from([Register(rd), Immediate(imm_val)]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::read_write()),
-                convenience::immediate(imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::read_write()),
+                    convenience::immediate(imm_val),
+                ],
+            ),
         })
     }
 
     fn decode_c_add(&self, rd: u8, rs2: u8) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.add".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs2)
-            ),
+            mnemonic: "c.add",
+            operands: RiscVTextOperands::from([Register(rd), Register(rs2)]),
             format: RiscVInstructionFormat::CR,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::read_write()),
-                convenience::register(rs2, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::read_write()),
+                    convenience::register(rs2, Access::read()),
+                ],
+            ),
         })
     }
 
     fn decode_c_mv(&self, rd: u8, rs2: u8) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.mv".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs2)
-            ),
+            mnemonic: "c.mv",
+            operands: RiscVTextOperands::from([Register(rd), Register(rs2)]),
             format: RiscVInstructionFormat::CR,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs2, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::register(rs2, Access::read()),
+                ],
+            ),
         })
     }
 
     fn decode_c_jr(&self, rd: u8) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.jr".to_string(),
-            operands: self.register_manager.int_register_name(rd).to_string(),
+            mnemonic: "c.jr",
+            operands: RiscVTextOperands::from([Register(rd)]),
             format: RiscVInstructionFormat::CR,
             size: 2,
-            operands_detail: vec![convenience::register(rd, Access::read())],
+            operands_detail: detail_operands(
+                self.detail,
+                [convenience::register(rd, Access::read())],
+            ),
         })
     }
 
     fn decode_c_jalr(&self, rd: u8) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.jalr".to_string(),
-            operands: self.register_manager.int_register_name(rd).to_string(),
+            mnemonic: "c.jalr",
+            operands: RiscVTextOperands::from([Register(rd)]),
+            format: RiscVInstructionFormat::CR,
+            size: 2,
+            operands_detail: detail_operands(
+                self.detail,
+                [convenience::register(rd, Access::read())],
+            ),
+        })
+    }
+
+    fn decode_c_ebreak(&self) -> Result<RiscVDecodedInstruction, DisasmError> {
+        Ok(RiscVDecodedInstruction {
+            mnemonic: "c.ebreak",
+            operands: RiscVTextOperands::new(),
             format: RiscVInstructionFormat::CR,
             size: 2,
-            operands_detail: vec![convenience::register(rd, Access::read())],
+            operands_detail: RiscVOperands::new(),
         })
     }
 
@@ -126,19 +143,23 @@ impl Rvc {
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = imm as i64;
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.lw".to_string(),
-            operands: format!(
-                "{}, {}({})",
-                self.register_manager.int_register_name(rd + 8),
-                convenience::format_immediate(imm_val),
-                self.register_manager.int_register_name(rs1 + 8)
-            ),
+            mnemonic: "c.lw",
+            operands: RiscVTextOperands::from([
+                Register(rd + 8),
+                Memory {
+                    base: rs1 + 8,
+                    disp: imm_val,
+                },
+            ]),
             format: RiscVInstructionFormat::CL,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd + 8, Access::write()),
-                convenience::memory(rs1 + 8, imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd + 8, Access::write()),
+                    convenience::memory(rs1 + 8, imm_val),
+                ],
+            ),
         })
     }
 
@@ -150,218 +171,252 @@ impl Rvc {
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = imm as i64;
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.sw".to_string(),
-            operands: format!(
-                "{}, {}({})",
-                self.register_manager.int_register_name(rs2 + 8),
-                convenience::format_immediate(imm_val),
-                self.register_manager.int_register_name(rs1 + 8)
-            ),
+            mnemonic: "c.sw",
+            operands: RiscVTextOperands::from([
+                Register(rs2 + 8),
+                Memory {
+                    base: rs1 + 8,
+                    disp: imm_val,
+                },
+            ]),
             format: RiscVInstructionFormat::CS,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rs2 + 8, Access::read()),
-                convenience::memory(rs1 + 8, imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rs2 + 8, Access::read()),
+                    convenience::memory(rs1 + 8, imm_val),
+                ],
+            ),
         })
     }
 
     fn decode_c_lwsp(&self, rd: u8, imm: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = imm as i64;
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.lwsp".to_string(),
-            operands: format!(
-                "{}, {}(sp)",
-                self.register_manager.int_register_name(rd),
-                convenience::format_immediate(imm_val)
-            ),
+            mnemonic: "c.lwsp",
+            operands: RiscVTextOperands::from([
+                Register(rd),
+                Memory {
+                    base: 2,
+                    disp: imm_val,
+                },
+            ]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::memory(2, imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::memory(2, imm_val),
+                ],
+            ),
         })
     }
 
     fn decode_c_swsp(&self, rs2: u8, imm: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         let imm_val = imm as i64;
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.swsp".to_string(),
-            operands: format!(
-                "{}, {}(sp)",
-                self.register_manager.int_register_name(rs2),
-                convenience::format_immediate(imm_val)
-            ),
+            mnemonic: "c.swsp",
+            operands: RiscVTextOperands::from([
+                Register(rs2),
+                Memory {
+                    base: 2,
+                    disp: imm_val,
+                },
+            ]),
             format: RiscVInstructionFormat::CSS,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rs2, Access::read()),
-                convenience::memory(2, imm_val),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rs2, Access::read()),
+                    convenience::memory(2, imm_val),
+                ],
+            ),
         })
     }
 
     fn decode_c_addi(&self, rd: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.addi".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                convenience::format_immediate(imm)
-            ),
+            mnemonic: "c.addi",
+            operands: RiscVTextOperands::from([Register(rd), Immediate(imm)]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::read_write()),
-                convenience::immediate(imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::read_write()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
     fn decode_c_li(&self, rd: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.li".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                convenience::format_immediate(imm)
-            ),
+            mnemonic: "c.li",
+            operands: RiscVTextOperands::from([Register(rd), Immediate(imm)]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::immediate(imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::immediate(imm),
+                ],
+            ),
+        })
+    }
+
+    /// `c.srli`, `c.srai` and `c.andi`: `rd'` is both source and destination.
+    fn decode_c_alu_imm(
+        &self,
+        mnemonic: &'static str,
+        rd: u8,
+        imm: i64,
+    ) -> Result<RiscVDecodedInstruction, DisasmError> {
+        Ok(RiscVDecodedInstruction {
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd + 8), Immediate(imm)]),
+            format: RiscVInstructionFormat::CB,
+            size: 2,
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd + 8, Access::read_write()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
+    /// `c.sub`, `c.xor`, `c.or`, `c.and`, `c.subw` and `c.addw`.
     fn decode_c_alu(
         &self,
-        funct6: u8,
+        mnemonic: &'static str,
         rd: u8,
         rs2: u8,
-        funct2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let mnemonic = match (funct6 & 0b11, funct2) {
-            (0b00, 0b00) => "c.srli",
-            (0b01, 0b00) => "c.srai",
-            (0b10, 0b00) => "c.andi",
-            (0b11, 0b00) => "c.sub",
-            (0b11, 0b01) => "c.xor",
-            (0b11, 0b10) => "c.or",
-            (0b11, 0b11) => "c.and",
-            _ => {
-                return Err(DisasmError::DecodingError(
-                    "Invalid C.ALU encoding".to_string(),
-                ));
-            }
-        };
-
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd + 8),
-                self.register_manager.int_register_name(rs2 + 8)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd + 8), Register(rs2 + 8)]),
             format: RiscVInstructionFormat::CA,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd + 8, Access::read_write()),
-                convenience::register(rs2 + 8, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd + 8, Access::read_write()),
+                    convenience::register(rs2 + 8, Access::read()),
+                ],
+            ),
+        })
+    }
+
+    fn decode_c_lui(&self, rd: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
+        Ok(RiscVDecodedInstruction {
+            mnemonic: "c.lui",
+            operands: RiscVTextOperands::from([Register(rd), UpperImmediate(imm)]),
+            format: RiscVInstructionFormat::CI,
+            size: 2,
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
     fn decode_c_j(&self, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.j".to_string(),
-            operands: convenience::format_immediate(imm),
+            mnemonic: "c.j",
+            operands: RiscVTextOperands::from([Offset(imm)]),
             format: RiscVInstructionFormat::CJ,
             size: 2,
-            operands_detail: vec![convenience::immediate(imm)],
+            operands_detail: detail_operands(self.detail, [convenience::immediate(imm)]),
         })
     }
 
     fn decode_c_jal(&self, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.jal".to_string(),
-            operands: convenience::format_immediate(imm),
+            mnemonic: "c.jal",
+            operands: RiscVTextOperands::from([Offset(imm)]),
             format: RiscVInstructionFormat::CJ,
             size: 2,
-            operands_detail: vec![convenience::immediate(imm)],
+            operands_detail: detail_operands(self.detail, [convenience::immediate(imm)]),
         })
     }
 
     fn decode_c_beqz(&self, rs1: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.beqz".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rs1 + 8),
-                convenience::format_immediate(imm)
-            ),
+            mnemonic: "c.beqz",
+            operands: RiscVTextOperands::from([Register(rs1 + 8), Offset(imm)]),
             format: RiscVInstructionFormat::CB,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rs1 + 8, Access::read()),
-                convenience::immediate(imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rs1 + 8, Access::read()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
     fn decode_c_bnez(&self, rs1: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.bnez".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rs1 + 8),
-                convenience::format_immediate(imm)
-            ),
+            mnemonic: "c.bnez",
+            operands: RiscVTextOperands::from([Register(rs1 + 8), Offset(imm)]),
             format: RiscVInstructionFormat::CB,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rs1 + 8, Access::read()),
-                convenience::immediate(imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rs1 + 8, Access::read()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
     fn decode_c_slli(&self, rd: u8, imm: i64) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.slli".to_string(),
-            operands: format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                convenience::format_immediate(imm)
-            ),
+            mnemonic: "c.slli",
+            operands: RiscVTextOperands::from([Register(rd), Immediate(imm)]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![
-                convenience::register(rd, Access::read_write()),
-                convenience::immediate(imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::read_write()),
+                    convenience::immediate(imm),
+                ],
+            ),
         })
     }
 
     fn decode_c_unimp(&self) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.unimp".to_string(),
-            operands: String::new(),
+            mnemonic: "c.unimp",
+            operands: RiscVTextOperands::new(),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![],
+            operands_detail: RiscVOperands::new(),
         })
     }
 
     fn decode_c_unknown(&self, instruction: u16) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "c.unknown".to_string(),
-            operands: format!("0x{instruction:04x}"),
+            mnemonic: "c.unknown",
+            operands: RiscVTextOperands::from([Encoding {
+                value: u32::from(instruction),
+                size: 2,
+            }]),
             format: RiscVInstructionFormat::CI,
             size: 2,
-            operands_detail: vec![],
+            operands_detail: RiscVOperands::new(),
         })
     }
 }
@@ -401,7 +456,7 @@ impl InstructionExtension for Rvc {
         instruction: u16,
         opcode: u8,
         funct3: u8,
-        _xlen: Xlen,
+        xlen: Xlen,
         rd_full: u8,
         _rs1_full: u8,
         rs2_full: u8,
@@ -435,18 +490,42 @@ impl InstructionExtension for Rvc {
             (0b01, 0b000) => Some(self.decode_c_addi(rd_full, imm_ci)),
             (0b01, 0b001) => Some(self.decode_c_jal(imm_cj)),
             (0b01, 0b010) => Some(self.decode_c_li(rd_full, imm_ci)),
-            (0b01, 0b011) => {
-                let imm_val = (((instruction >> 12) & 0x1) << 9)
-                    | (((instruction >> 3) & 0x3) << 7)
-                    | (((instruction >> 5) & 0x1) << 6)
-                    | (((instruction >> 2) & 0x3) << 4)
-                    | (((instruction >> 6) & 0x1) << 5);
-                Some(self.decode_c_addi16sp(rd_full, imm_val))
-            }
+            // c.addi16sp when rd is sp, c.lui otherwise.
+            (0b01, 0b011) => match rd_full {
+                0 => Some(self.decode_c_unknown(instruction)),
+                2 => {
+                    let imm_val = (((instruction >> 12) & 0x1) << 9)
+                        | (((instruction >> 3) & 0x3) << 7)
+                        | (((instruction >> 5) & 0x1) << 6)
+                        | (((instruction >> 2) & 0x1) << 5)
+                        | (((instruction >> 6) & 0x1) << 4);
+                    Some(self.decode_c_addi16sp(rd_full, imm_val))
+                }
+                // Printed like `lui`: the 20-bit field, not the sign-extended value.
+                _ => Some(self.decode_c_lui(rd_full, imm_ci & 0xF_FFFF)),
+            },
+            // CB/CA arithmetic on rd' (bits 9..7); bits 11..10 pick the group.
             (0b01, 0b100) => {
-                let funct6 = ((instruction >> 10) & 0x3F) as u8;
-                let funct2 = ((instruction >> 5) & 0x3) as u8;
-                Some(self.decode_c_alu(funct6, rdp, rs2p, funct2))
+                let shamt = (((instruction >> 7) & 0x20) | ((instruction >> 2) & 0x1F)) as i64;
+                let funct2 = (instruction >> 5) & 0x3;
+                Some(
+                    match ((instruction >> 10) & 0x3, (instruction >> 12) & 1, funct2) {
+                        (0b00, _, _) => self.decode_c_alu_imm("c.srli", rs1p, shamt),
+                        (0b01, _, _) => self.decode_c_alu_imm("c.srai", rs1p, shamt),
+                        (0b10, _, _) => self.decode_c_alu_imm("c.andi", rs1p, imm_ci),
+                        (_, 0, 0b00) => self.decode_c_alu("c.sub", rs1p, rs2p),
+                        (_, 0, 0b01) => self.decode_c_alu("c.xor", rs1p, rs2p),
+                        (_, 0, 0b10) => self.decode_c_alu("c.or", rs1p, rs2p),
+                        (_, 0, _) => self.decode_c_alu("c.and", rs1p, rs2p),
+                        (_, _, 0b00) if xlen == Xlen::X64 => {
+                            self.decode_c_alu("c.subw", rs1p, rs2p)
+                        }
+                        (_, _, 0b01) if xlen == Xlen::X64 => {
+                            self.decode_c_alu("c.addw", rs1p, rs2p)
+                        }
+                        _ => self.decode_c_unknown(instruction),
+                    },
+                )
             }
             (0b01, 0b101) => Some(self.decode_c_j(imm_cj)),
             (0b01, 0b110) => Some(self.decode_c_beqz(rs1p, imm_cb)),
@@ -455,16 +534,16 @@ impl InstructionExtension for Rvc {
             // C2 opcode (quarters 2)
             (0b10, 0b000) => Some(self.decode_c_slli(rd_full, imm_ci)),
             (0b10, 0b010) => Some(self.decode_c_lwsp(rd_full, uimm_clsp)),
-            (0b10, 0b100) => Some(self.decode_c_mv(rd_full, rs2_full)),
-            (0b10, 0b101) => {
-                if rs2_full == 0 {
-                    Some(self.decode_c_jr(rd_full))
-                } else if rd_full == 0 {
-                    Some(self.decode_c_jalr(rd_full))
-                } else {
-                    Some(self.decode_c_add(rd_full, rs2_full))
-                }
-            }
+            // CR format: bit 12 separates c.jr/c.mv from c.ebreak/c.jalr/c.add,
+            // and rs2 == 0 selects the jumps.
+            (0b10, 0b100) => Some(match ((instruction >> 12) & 1, rd_full, rs2_full) {
+                (0, 0, 0) => self.decode_c_unknown(instruction),
+                (0, _, 0) => self.decode_c_jr(rd_full),
+                (0, _, _) => self.decode_c_mv(rd_full, rs2_full),
+                (_, 0, 0) => self.decode_c_ebreak(),
+                (_, _, 0) => self.decode_c_jalr(rd_full),
+                (_, _, _) => self.decode_c_add(rd_full, rs2_full),
+            }),
             (0b10, 0b110) => Some(self.decode_c_swsp(rs2_full, uimm_css)),
 
             _ => Some(self.decode_c_unknown(instruction)),
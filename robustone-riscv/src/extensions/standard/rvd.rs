@@ -6,24 +6,32 @@
 use super::Standard;
 use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
-use crate::shared::{
-    operands::convenience,
-    registers::{RegisterManager, RegisterNameProvider},
-};
+use crate::shared::operands::{convenience, detail_operands};
 use crate::types::*;
+use RiscVRegisterClass::Gpr;
+use RiscVTextOperand::{FpRegister, Memory, Register};
+use alloc::string::ToString;
 use robustone_core::types::error::DisasmError;
 
+/// Class of the registers the extension computes on.
+const FP: RiscVRegisterClass = RiscVRegisterClass::Fpr64;
+
 /// RVD Double-Precision Floating-Point Extension
 pub struct Rvd {
-    register_manager: RegisterManager,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl Rvd {
     /// Create a new RVD extension instance.
     pub fn new() -> Self {
-        Self {
-            register_manager: RegisterManager::new(),
-        }
+        Self { detail: true }
+    }
+
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
     }
 
     // D-extension opcodes (same as F-extension)
@@ -46,19 +54,23 @@ impl Rvd {
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "fld".to_string(),
-            operands: format!(
-                "{}, {}({})",
-                self.register_manager.fp_register_name(rd),
-                convenience::format_immediate(imm),
-                self.register_manager.int_register_name(rs1)
-            ),
+            mnemonic: "fld",
+            operands: RiscVTextOperands::from([
+                FpRegister(rd),
+                Memory {
+                    base: rs1,
+                    disp: imm,
+                },
+            ]),
             format: RiscVInstructionFormat::I,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::memory(rs1, imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::class_register(FP, rd, Access::write()),
+                    convenience::memory(rs1, imm),
+                ],
+            ),
         })
     }
 
@@ -69,104 +81,127 @@ impl Rvd {
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: "fsd".to_string(),
-            operands: format!(
-                "{}, {}({})",
-                self.register_manager.fp_register_name(rs2),
-                convenience::format_immediate(imm),
-                self.register_manager.int_register_name(rs1)
-            ),
+            mnemonic: "fsd",
+            operands: RiscVTextOperands::from([
+                FpRegister(rs2),
+                Memory {
+                    base: rs1,
+                    disp: imm,
+                },
+            ]),
             format: RiscVInstructionFormat::S,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rs2, Access::read()),
-                convenience::memory(rs1, imm),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::class_register(FP, rs2, Access::read()),
+                    convenience::memory(rs1, imm),
+                ],
+            ),
         })
     }
 
     fn decode_fp_r_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}, {}",
-                self.register_manager.fp_register_name(rd),
-                self.register_manager.fp_register_name(rs1),
-                self.register_manager.fp_register_name(rs2)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([FpRegister(rd), FpRegister(rs1), FpRegister(rs2)]),
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs1, Access::read()),
-                convenience::register(rs2, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::class_register(FP, rd, Access::write()),
+                    convenience::class_register(FP, rs1, Access::read()),
+                    convenience::class_register(FP, rs2, Access::read()),
+                ],
+            ),
         })
     }
 
     fn decode_fp_r4_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
         rs3: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}, {}, {}",
-                self.register_manager.fp_register_name(rd),
-                self.register_manager.fp_register_name(rs1),
-                self.register_manager.fp_register_name(rs2),
-                self.register_manager.fp_register_name(rs3)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([
+                FpRegister(rd),
+                FpRegister(rs1),
+                FpRegister(rs2),
+                FpRegister(rs3),
+            ]),
             format: RiscVInstructionFormat::R4,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs1, Access::read()),
-                convenience::register(rs2, Access::read()),
-                convenience::register(rs3, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::class_register(FP, rd, Access::write()),
+                    convenience::class_register(FP, rs1, Access::read()),
+                    convenience::class_register(FP, rs2, Access::read()),
+                    convenience::class_register(FP, rs3, Access::read()),
+                ],
+            ),
         })
     }
 
     fn decode_fp_int_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         _rs2: u8,
-        rd_is_fp: bool,
-        rs1_is_fp: bool,
+        rd_class: RiscVRegisterClass,
+        rs1_class: RiscVRegisterClass,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let rd_name = if rd_is_fp {
-            self.register_manager.fp_register_name(rd)
-        } else {
-            self.register_manager.int_register_name(rd)
-        };
-        let rs1_name = if rs1_is_fp {
-            self.register_manager.fp_register_name(rs1)
-        } else {
-            self.register_manager.int_register_name(rs1)
-        };
+        Ok(RiscVDecodedInstruction {
+            mnemonic,
+            operands: RiscVTextOperands::from([
+                RiscVTextOperand::register(rd_class, rd),
+                RiscVTextOperand::register(rs1_class, rs1),
+            ]),
+            format: RiscVInstructionFormat::R,
+            size: 4,
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::class_register(rd_class, rd, Access::write()),
+                    convenience::class_register(rs1_class, rs1, Access::read()),
+                ],
+            ),
+        })
+    }
 
+    /// Decodes a comparison, writing its result to an integer register.
+    fn decode_fp_compare(
+        &self,
+        mnemonic: &'static str,
+        rd: u8,
+        rs1: u8,
+        rs2: u8,
+    ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!("{rd_name}, {rs1_name}"),
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd), FpRegister(rs1), FpRegister(rs2)]),
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs1, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::class_register(FP, rs1, Access::read()),
+                    convenience::class_register(FP, rs2, Access::read()),
+                ],
+            ),
         })
     }
 }
@@ -243,14 +278,14 @@ impl InstructionExtension for Rvd {
                     (0b00101, 0b000) => Some(self.decode_fp_r_type("fmin.d", rd, rs1, rs2)),
                     (0b00101, 0b001) => Some(self.decode_fp_r_type("fmax.d", rd, rs1, rs2)),
                     (0b11000, 0b000) => {
-                        Some(self.decode_fp_int_type("fcvt.w.d", rd, rs1, rs2, false, true))
+                        Some(self.decode_fp_int_type("fcvt.w.d", rd, rs1, rs2, Gpr, FP))
                     } // rs2 ignored
                     (0b11000, 0b001) => {
-                        Some(self.decode_fp_int_type("fcvt.wu.d", rd, rs1, rs2, false, true))
+                        Some(self.decode_fp_int_type("fcvt.wu.d", rd, rs1, rs2, Gpr, FP))
                     } // rs2 ignored
                     (0b11000, 0b010) => {
                         if xlen == Xlen::X64 {
-                            Some(self.decode_fp_int_type("fcvt.l.d", rd, rs1, rs2, false, true))
+                            Some(self.decode_fp_int_type("fcvt.l.d", rd, rs1, rs2, Gpr, FP))
                         // rs2 ignored
                         } else {
                             Some(Err(DisasmError::DecodingError(
@@ -260,7 +295,7 @@ impl InstructionExtension for Rvd {
                     }
                     (0b11000, 0b011) => {
                         if xlen == Xlen::X64 {
-                            Some(self.decode_fp_int_type("fcvt.lu.d", rd, rs1, rs2, false, true))
+                            Some(self.decode_fp_int_type("fcvt.lu.d", rd, rs1, rs2, Gpr, FP))
                         // rs2 ignored
                         } else {
                             Some(Err(DisasmError::DecodingError(
@@ -269,23 +304,23 @@ impl InstructionExtension for Rvd {
                         }
                     }
                     (0b11100, 0b000) => {
-                        Some(self.decode_fp_int_type("fmv.x.d", rd, rs1, rs2, false, true))
+                        Some(self.decode_fp_int_type("fmv.x.d", rd, rs1, rs2, Gpr, FP))
                     } // rs2 ignored
-                    (0b10100, 0b010) => Some(self.decode_fp_r_type("feq.d", rd, rs1, rs2)),
-                    (0b10100, 0b001) => Some(self.decode_fp_r_type("flt.d", rd, rs1, rs2)),
-                    (0b10100, 0b000) => Some(self.decode_fp_r_type("fle.d", rd, rs1, rs2)),
+                    (0b10100, 0b010) => Some(self.decode_fp_compare("feq.d", rd, rs1, rs2)),
+                    (0b10100, 0b001) => Some(self.decode_fp_compare("flt.d", rd, rs1, rs2)),
+                    (0b10100, 0b000) => Some(self.decode_fp_compare("fle.d", rd, rs1, rs2)),
                     (0b11100, 0b001) => {
-                        Some(self.decode_fp_int_type("fclass.d", rd, rs1, rs2, false, true))
+                        Some(self.decode_fp_int_type("fclass.d", rd, rs1, rs2, Gpr, FP))
                     } // rs2 ignored
                     (0b11010, 0b000) => {
-                        Some(self.decode_fp_int_type("fcvt.d.w", rd, rs1, rs2, true, false))
+                        Some(self.decode_fp_int_type("fcvt.d.w", rd, rs1, rs2, FP, Gpr))
                     } // rs2 ignored
                     (0b11010, 0b001) => {
-                        Some(self.decode_fp_int_type("fcvt.d.wu", rd, rs1, rs2, true, false))
+                        Some(self.decode_fp_int_type("fcvt.d.wu", rd, rs1, rs2, FP, Gpr))
                     } // rs2 ignored
                     (0b11010, 0b010) => {
                         if xlen == Xlen::X64 {
-                            Some(self.decode_fp_int_type("fcvt.d.l", rd, rs1, rs2, true, false))
+                            Some(self.decode_fp_int_type("fcvt.d.l", rd, rs1, rs2, FP, Gpr))
                         // rs2 ignored
                         } else {
                             Some(Err(DisasmError::DecodingError(
@@ -295,7 +330,7 @@ impl InstructionExtension for Rvd {
                     }
                     (0b11010, 0b011) => {
                         if xlen == Xlen::X64 {
-                            Some(self.decode_fp_int_type("fcvt.d.lu", rd, rs1, rs2, true, false))
+                            Some(self.decode_fp_int_type("fcvt.d.lu", rd, rs1, rs2, FP, Gpr))
                         // rs2 ignored
                         } else {
                             Some(Err(DisasmError::DecodingError(
@@ -304,10 +339,16 @@ impl InstructionExtension for Rvd {
                         }
                     }
                     (0b11110, 0b000) => {
-                        Some(self.decode_fp_int_type("fmv.d.x", rd, rs1, rs2, true, false))
+                        Some(self.decode_fp_int_type("fmv.d.x", rd, rs1, rs2, FP, Gpr))
                     } // rs2 ignored
-                    (0b01000, 0b000) => Some(self.decode_fp_r_type("fcvt.d.s", rd, rs1, rs2)), // fmt=00, rs2 is rs1
-                    (0b01000, 0b001) => Some(self.decode_fp_r_type("fcvt.s.d", rd, rs1, rs2)), // fmt=01, rs2 is rs1
+                    (0b01000, 0b000) if rs2 == 0 => Some(self.decode_fp_int_type(
+                        "fcvt.d.s",
+                        rd,
+                        rs1,
+                        rs2,
+                        FP,
+                        RiscVRegisterClass::Fpr32,
+                    )),
                     _ => Some(Err(DisasmError::DecodingError(
                         "Invalid D-extension encoding".to_string(),
                     ))),
@@ -8,21 +8,22 @@ use super::Standard;
 use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
 use crate::shared::{
-    InstructionFormatter, OperandFactory, RegisterNameProvider,
+    InstructionFormatter, OperandFactory,
     encoding::ShamtExtractor,
     formatting::DefaultInstructionFormatter,
-    operands::{DefaultOperandFactory, OperandBuilder, OperandFormatter},
-    registers::RegisterManager,
+    operands::{DefaultOperandFactory, OperandBuilder, detail_operands},
 };
 use crate::types::*;
+use alloc::string::ToString;
 use robustone_core::types::error::DisasmError;
 
 /// RV32I/RV64I Base Integer Extension
 pub struct Rvi {
     operand_factory: DefaultOperandFactory,
     formatter: DefaultInstructionFormatter,
-    register_manager: RegisterManager,
     operand_builder: OperandBuilder,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl Rvi {
@@ -36,11 +37,18 @@ impl Rvi {
         Self {
             operand_factory: DefaultOperandFactory::with_xlen(xlen),
             formatter: DefaultInstructionFormatter::with_xlen(xlen),
-            register_manager: RegisterManager::new(),
             operand_builder: OperandBuilder::with_xlen(xlen),
+            detail: true,
         }
     }
 
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self.formatter = self.formatter.with_detail(detail);
+        self
+    }
+
     // Opcode constants for base integer instructions
     const OPCODE_LUI: u32 = 0b011_0111;
     const OPCODE_AUIPC: u32 = 0b001_0111;
@@ -109,16 +117,19 @@ impl Rvi {
     // Instruction format decoding methods using shared utilities
     fn decode_u_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_u_type(mnemonic, rd, imm);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::write()),
-            self.operand_factory.make_immediate_operand(imm >> 12),
-        ];
+        let operands = self.operand_builder.u_type(mnemonic, rd, imm);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory.make_immediate_operand(imm >> 12),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -130,16 +141,19 @@ impl Rvi {
 
     fn decode_j_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_j_type(mnemonic, rd, imm);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::read_write()),
-            self.operand_factory.make_immediate_operand(imm),
-        ];
+        let operands = self.operand_builder.j_type(mnemonic, rd, imm);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory.make_immediate_operand(imm),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -151,19 +165,22 @@ impl Rvi {
 
     fn decode_i_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_i_type(mnemonic, rd, rs1, imm);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::write()),
-            self.operand_factory
-                .make_register_operand(rs1, Access::read()),
-            self.operand_factory.make_immediate_operand(imm),
-        ];
+        let operands = self.operand_builder.i_type(mnemonic, rd, rs1, imm);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory
+                    .make_register_operand(rs1, Access::read()),
+                self.operand_factory.make_immediate_operand(imm),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -175,20 +192,23 @@ impl Rvi {
 
     fn decode_r_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_r_type(mnemonic, rd, rs1, rs2);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::write()),
-            self.operand_factory
-                .make_register_operand(rs1, Access::read()),
-            self.operand_factory
-                .make_register_operand(rs2, Access::read()),
-        ];
+        let operands = self.operand_builder.r_type(mnemonic, rd, rs1, rs2);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory
+                    .make_register_operand(rs1, Access::read()),
+                self.operand_factory
+                    .make_register_operand(rs2, Access::read()),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -200,17 +220,20 @@ impl Rvi {
 
     fn decode_s_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rs2: u8,
         rs1: u8,
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_s_type(mnemonic, rs2, rs1, imm);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rs2, Access::read()),
-            self.operand_factory.make_memory_operand(rs1, imm),
-        ];
+        let operands = self.operand_builder.s_type(mnemonic, rs2, rs1, imm);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rs2, Access::read()),
+                self.operand_factory.make_memory_operand(rs1, imm),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -222,19 +245,22 @@ impl Rvi {
 
     fn decode_b_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rs1: u8,
         rs2: u8,
         imm: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let operands = self.operand_builder.format_b_type(mnemonic, rs1, rs2, imm);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rs1, Access::read()),
-            self.operand_factory
-                .make_register_operand(rs2, Access::read()),
-            self.operand_factory.make_immediate_operand(imm),
-        ];
+        let operands = self.operand_builder.b_type(mnemonic, rs1, rs2, imm);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rs1, Access::read()),
+                self.operand_factory
+                    .make_register_operand(rs2, Access::read()),
+                self.operand_factory.make_immediate_operand(imm),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -318,12 +344,15 @@ impl Rvi {
 
         let operands = self
             .operand_builder
-            .format_load_type(mnemonic, rd, rs1, imm_i, false);
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::write()),
-            self.operand_factory.make_memory_operand(rs1, imm_i),
-        ];
+            .load_type(mnemonic, rd, rs1, imm_i, false);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory.make_memory_operand(rs1, imm_i),
+            ],
+        );
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -408,13 +437,6 @@ impl Rvi {
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        // Skip M-extension instructions (funct7 == 0b0000001)
-        if funct7 == Self::FUNCT7_OP_MUL {
-            return Err(DisasmError::DecodingError(
-                "M-extension instruction".to_string(),
-            ));
-        }
-
         match (funct3, funct7) {
             (Self::FUNCT3_OP_ADD_SUB, Self::FUNCT7_OP_ADD) => {
                 self.decode_r_type("add", rd, rs1, rs2)
@@ -503,11 +525,11 @@ impl Rvi {
     fn decode_misc_mem(&self, funct3: u8) -> Result<RiscVDecodedInstruction, DisasmError> {
         match funct3 {
             Self::FUNCT3_MISC_MEM_FENCE => {
-                Ok(DefaultInstructionFormatter::simple_instruction("fence", ""))
+                Ok(DefaultInstructionFormatter::simple_instruction("fence"))
+            }
+            Self::FUNCT3_MISC_MEM_FENCE_I => {
+                Ok(DefaultInstructionFormatter::simple_instruction("fence.i"))
             }
-            Self::FUNCT3_MISC_MEM_FENCE_I => Ok(DefaultInstructionFormatter::simple_instruction(
-                "fence.i", "",
-            )),
             _ => Err(DisasmError::DecodingError(
                 "Invalid misc mem funct3".to_string(),
             )),
@@ -525,11 +547,11 @@ impl Rvi {
         match funct3 {
             Self::FUNCT3_SYSTEM_PRIV => match funct12 {
                 Self::FUNCT12_SYSTEM_ECALL => {
-                    Ok(DefaultInstructionFormatter::simple_instruction("ecall", ""))
+                    Ok(DefaultInstructionFormatter::simple_instruction("ecall"))
+                }
+                Self::FUNCT12_SYSTEM_EBREAK => {
+                    Ok(DefaultInstructionFormatter::simple_instruction("ebreak"))
                 }
-                Self::FUNCT12_SYSTEM_EBREAK => Ok(DefaultInstructionFormatter::simple_instruction(
-                    "ebreak", "",
-                )),
                 _ => self.decode_csr_instruction("csrrw", rd, rs1, funct12 as i64),
             },
             Self::FUNCT3_SYSTEM_CSRRW => {
@@ -558,12 +580,12 @@ impl Rvi {
 
     fn decode_csr_instruction(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         csr: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let csr_str = self.operand_factory.format_csr(csr);
+        let csr_operand = RiscVTextOperand::Csr(csr as u16);
 
         // Handle pseudo-instructions: csrr, csrc, csrw
         // csrrs with rs1=0 → csrr
@@ -576,31 +598,32 @@ impl Rvi {
                 "csrrw" => "csrw",
                 _ => mnemonic,
             };
-            let ops = format!(
-                "{}, {}",
-                self.register_manager.int_register_name(rd),
-                csr_str
+            let ops = RiscVTextOperands::from([RiscVTextOperand::Register(rd), csr_operand]);
+            let ops_detail = detail_operands(
+                self.detail,
+                [
+                    self.operand_factory
+                        .make_register_operand(rd, Access::write()),
+                    self.operand_factory.make_immediate_operand(csr),
+                ],
             );
-            let ops_detail = vec![
-                self.operand_factory
-                    .make_register_operand(rd, Access::write()),
-                self.operand_factory.make_immediate_operand(csr),
-            ];
             (pseudo_mnemonic, ops, ops_detail)
         } else {
-            let ops = format!(
-                "{}, {}, {}",
-                self.register_manager.int_register_name(rd),
-                csr_str,
-                self.register_manager.int_register_name(rs1)
+            let ops = RiscVTextOperands::from([
+                RiscVTextOperand::Register(rd),
+                csr_operand,
+                RiscVTextOperand::Register(rs1),
+            ]);
+            let ops_detail = detail_operands(
+                self.detail,
+                [
+                    self.operand_factory
+                        .make_register_operand(rd, Access::write()),
+                    self.operand_factory.make_immediate_operand(csr),
+                    self.operand_factory
+                        .make_register_operand(rs1, Access::read()),
+                ],
             );
-            let ops_detail = vec![
-                self.operand_factory
-                    .make_register_operand(rd, Access::write()),
-                self.operand_factory.make_immediate_operand(csr),
-                self.operand_factory
-                    .make_register_operand(rs1, Access::read()),
-            ];
             (mnemonic, ops, ops_detail)
         };
 
@@ -615,24 +638,25 @@ impl Rvi {
 
     fn decode_csr_instruction_imm(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         zimm: i64,
         csr: i64,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
-        let csr_str = self.operand_factory.format_csr(csr);
-        let operands = format!(
-            "{}, {}, {}",
-            self.register_manager.int_register_name(rd),
-            csr_str,
-            zimm
+        let operands = RiscVTextOperands::from([
+            RiscVTextOperand::Register(rd),
+            RiscVTextOperand::Csr(csr as u16),
+            RiscVTextOperand::Zimm(zimm as u8),
+        ]);
+        let operands_detail = detail_operands(
+            self.detail,
+            [
+                self.operand_factory
+                    .make_register_operand(rd, Access::write()),
+                self.operand_factory.make_immediate_operand(csr),
+                self.operand_factory.make_immediate_operand(zimm),
+            ],
         );
-        let operands_detail = vec![
-            self.operand_factory
-                .make_register_operand(rd, Access::write()),
-            self.operand_factory.make_immediate_operand(csr),
-            self.operand_factory.make_immediate_operand(zimm),
-        ];
         Ok(self.formatter.create_decoded_instruction(
             mnemonic,
             operands,
@@ -679,11 +703,9 @@ impl InstructionExtension for Rvi {
             Self::OPCODE_STORE => Some(self.decode_store(funct3, rs2, rs1, imm_s, xlen)),
             Self::OPCODE_MISC_MEM => Some(self.decode_misc_mem(funct3)),
             Self::OPCODE_OP_IMM => Some(self.decode_op_imm(funct3, funct7, rd, rs1, imm_i, xlen)),
-            Self::OPCODE_OP => match self.decode_op(funct3, funct7, rd, rs1, rs2) {
-                Ok(inst) => Some(Ok(inst)),
-                Err(DisasmError::DecodingError(msg)) if msg.contains("M-extension") => None,
-                Err(e) => Some(Err(e)),
-            },
+            // M-extension instructions are left to the RVM extension.
+            Self::OPCODE_OP if funct7 == Self::FUNCT7_OP_MUL => None,
+            Self::OPCODE_OP => Some(self.decode_op(funct3, funct7, rd, rs1, rs2)),
             Self::OPCODE_OP_IMM_32 if xlen == Xlen::X64 => {
                 Some(self.decode_op_imm_32(funct3, funct7, rd, rs1, imm_i))
             }
@@ -8,16 +8,18 @@ use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
 use crate::shared::{
     OperandFactory,
-    operands::DefaultOperandFactory,
-    registers::{RegisterManager, RegisterNameProvider},
+    operands::{DefaultOperandFactory, detail_operands},
 };
 use crate::types::*;
+use RiscVTextOperand::Register;
+use alloc::string::ToString;
 use robustone_core::types::error::DisasmError;
 
 /// RVM Multiply and Divide Extension
 pub struct Rvm {
     operand_factory: DefaultOperandFactory,
-    register_manager: RegisterManager,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl Rvm {
@@ -25,10 +27,16 @@ impl Rvm {
     pub fn new() -> Self {
         Self {
             operand_factory: DefaultOperandFactory::new(),
-            register_manager: RegisterManager::new(),
+            detail: true,
         }
     }
 
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
+    }
+
     // M-extension opcodes (same as base opcodes, but distinguished by funct7)
     const OPCODE_OP: u32 = 0b011_0011;
 
@@ -47,29 +55,27 @@ impl Rvm {
 
     fn decode_r_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}, {}",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs1),
-                self.register_manager.int_register_name(rs2)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd), Register(rs1), Register(rs2)]),
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                self.operand_factory
-                    .make_register_operand(rd, Access::write()),
-                self.operand_factory
-                    .make_register_operand(rs1, Access::read()),
-                self.operand_factory
-                    .make_register_operand(rs2, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    self.operand_factory
+                        .make_register_operand(rd, Access::write()),
+                    self.operand_factory
+                        .make_register_operand(rs1, Access::read()),
+                    self.operand_factory
+                        .make_register_operand(rs2, Access::read()),
+                ],
+            ),
         })
     }
 
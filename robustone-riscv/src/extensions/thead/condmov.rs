@@ -7,24 +7,28 @@
 use super::THead;
 use crate::decoder::{RiscVDecodedInstruction, Xlen};
 use crate::extensions::{Extensions, InstructionExtension};
-use crate::shared::{
-    operands::convenience,
-    registers::{RegisterManager, RegisterNameProvider},
-};
+use crate::shared::operands::{convenience, detail_operands};
 use crate::types::*;
+use RiscVTextOperand::Register;
+use alloc::string::ToString;
 use robustone_core::types::error::DisasmError;
 
 /// XTheadCondMov Conditional Move Extension
 pub struct CMov {
-    register_manager: RegisterManager,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl CMov {
     /// Create a new XTheadCondMov extension instance.
     pub fn new() -> Self {
-        Self {
-            register_manager: RegisterManager::new(),
-        }
+        Self { detail: true }
+    }
+
+    /// Enables or disables building structured operands.
+    pub fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
     }
 
     // XTheadCondMov encoding constants
@@ -39,26 +43,24 @@ impl CMov {
     /// Decode an R-type conditional move instruction.
     fn decode_r_type(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
     ) -> Result<RiscVDecodedInstruction, DisasmError> {
         Ok(RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
-            operands: format!(
-                "{}, {}, {}",
-                self.register_manager.int_register_name(rd),
-                self.register_manager.int_register_name(rs1),
-                self.register_manager.int_register_name(rs2)
-            ),
+            mnemonic,
+            operands: RiscVTextOperands::from([Register(rd), Register(rs1), Register(rs2)]),
             format: RiscVInstructionFormat::R,
             size: 4,
-            operands_detail: vec![
-                convenience::register(rd, Access::write()),
-                convenience::register(rs1, Access::read()),
-                convenience::register(rs2, Access::read()),
-            ],
+            operands_detail: detail_operands(
+                self.detail,
+                [
+                    convenience::register(rd, Access::write()),
+                    convenience::register(rs1, Access::read()),
+                    convenience::register(rs2, Access::read()),
+                ],
+            ),
         })
     }
 }
@@ -166,7 +168,7 @@ mod tests {
         assert!(result.is_some());
         let instr = result.unwrap().unwrap();
         assert_eq!(instr.mnemonic, "th.mveqz");
-        assert_eq!(instr.operands, "ra, sp, gp");
+        assert_eq!(instr.operands.to_string(), "ra, sp, gp");
         assert_eq!(instr.size, 4);
     }
 
@@ -181,7 +183,7 @@ mod tests {
         assert!(result.is_some());
         let instr = result.unwrap().unwrap();
         assert_eq!(instr.mnemonic, "th.mvnez");
-        assert_eq!(instr.operands, "ra, sp, gp");
+        assert_eq!(instr.operands.to_string(), "ra, sp, gp");
         assert_eq!(instr.size, 4);
     }
 
@@ -0,0 +1,136 @@
+//! Semantic instruction groups (mirrors Capstone's `riscv_insn_group`).
+//!
+//! Groups let consumers ask coarse control-flow questions ("is this a call?")
+//! without matching on mnemonics themselves. The numeric values match
+//! Capstone so they can be handed across the C ABI unchanged.
+
+use super::types::{RiscVOperand, RiscVOperandValue};
+
+/// Register number of `ra`, the conventional link register.
+const LINK_REGISTER: u32 = 1;
+
+/// Instruction group identifiers (compatible with `riscv_insn_group`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RiscVInstructionGroup {
+    Invalid = 0,
+    /// Transfers control to another location.
+    Jump = 1,
+    /// Calls a subroutine and records a return address.
+    Call = 2,
+    /// Returns from a subroutine.
+    Ret = 3,
+    /// Raises a software interrupt or trap.
+    Int = 4,
+    /// Returns from a trap handler.
+    Iret = 5,
+    /// Requires a privileged execution mode.
+    Privilege = 6,
+    /// Uses a PC-relative target.
+    BranchRelative = 7,
+}
+
+impl RiscVInstructionGroup {
+    /// Returns the Capstone group name.
+    pub fn name(self) -> &'static str {
+        match self {
+            RiscVInstructionGroup::Invalid => "invalid",
+            RiscVInstructionGroup::Jump => "jump",
+            RiscVInstructionGroup::Call => "call",
+            RiscVInstructionGroup::Ret => "ret",
+            RiscVInstructionGroup::Int => "int",
+            RiscVInstructionGroup::Iret => "iret",
+            RiscVInstructionGroup::Privilege => "privilege",
+            RiscVInstructionGroup::BranchRelative => "branch_relative",
+        }
+    }
+
+    /// Converts a raw Capstone group identifier into the enum representation.
+    pub fn from_id(id: u32) -> Self {
+        match id {
+            1 => RiscVInstructionGroup::Jump,
+            2 => RiscVInstructionGroup::Call,
+            3 => RiscVInstructionGroup::Ret,
+            4 => RiscVInstructionGroup::Int,
+            5 => RiscVInstructionGroup::Iret,
+            6 => RiscVInstructionGroup::Privilege,
+            7 => RiscVInstructionGroup::BranchRelative,
+            _ => RiscVInstructionGroup::Invalid,
+        }
+    }
+}
+
+/// Classifies a decoded instruction into its semantic groups.
+///
+/// `operands` is the structured operand list produced by the decoder; it is
+/// consulted to tell calls (`jal ra, ...`) and returns (`jalr zero, 0(ra)`)
+/// apart from plain jumps.
+pub fn classify(mnemonic: &str, operands: &[RiscVOperand]) -> &'static [RiscVInstructionGroup] {
+    use RiscVInstructionGroup::*;
+
+    let register = |index: usize| match operands.get(index).map(|op| &op.value) {
+        Some(RiscVOperandValue::Register(reg)) => Some(*reg),
+        _ => None,
+    };
+
+    match mnemonic {
+        "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" | "beqz" | "bnez" | "c.beqz" | "c.bnez"
+        | "j" | "c.j" => &[Jump, BranchRelative],
+        "jal" if register(0).is_some_and(|rd| rd != 0) => &[Call, BranchRelative],
+        "jal" => &[Jump, BranchRelative],
+        "c.jal" => &[Call, BranchRelative],
+        "jalr" => match (register(0), register(1)) {
+            (Some(0), Some(LINK_REGISTER)) => &[Ret],
+            (Some(0), _) => &[Jump],
+            _ => &[Call],
+        },
+        "c.jr" if register(0) == Some(LINK_REGISTER) => &[Ret],
+        "c.jr" => &[Jump],
+        "c.jalr" => &[Call],
+        "ecall" | "ebreak" | "c.ebreak" => &[Int],
+        "mret" | "sret" | "uret" => &[Iret, Privilege],
+        "wfi" | "sfence.vma" => &[Privilege],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::operands::convenience;
+    use crate::types::Access;
+
+    #[test]
+    fn test_classify_calls_and_returns() {
+        use RiscVInstructionGroup::*;
+
+        let call = [convenience::register(1, Access::write())];
+        assert_eq!(classify("jal", &call), &[Call, BranchRelative]);
+
+        let ret = [
+            convenience::register(0, Access::write()),
+            convenience::register(1, Access::read()),
+            convenience::immediate(0),
+        ];
+        assert_eq!(classify("jalr", &ret), &[Ret]);
+        assert_eq!(
+            classify("c.jr", &[convenience::register(1, Access::read())]),
+            &[Ret]
+        );
+        assert_eq!(
+            classify("c.jr", &[convenience::register(5, Access::read())]),
+            &[Jump]
+        );
+    }
+
+    #[test]
+    fn test_classify_other_groups() {
+        use RiscVInstructionGroup::*;
+
+        assert_eq!(classify("beqz", &[]), &[Jump, BranchRelative]);
+        assert_eq!(classify("ecall", &[]), &[Int]);
+        assert!(classify("addi", &[]).is_empty());
+        assert_eq!(RiscVInstructionGroup::from_id(7), BranchRelative);
+        assert_eq!(BranchRelative.name(), "branch_relative");
+    }
+}
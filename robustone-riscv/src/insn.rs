@@ -0,0 +1,490 @@
+//! Instruction identifiers.
+//!
+//! Every instruction the decoder can produce has a [`RiscVInstructionId`], so
+//! consumers can match on an integer instead of comparing mnemonics. The
+//! values are Capstone 5's `riscv_insn` ids, so `cs_insn.id` agrees with
+//! Capstone for the same encoding. The table below was generated from
+//! Capstone's `RISCVGenInsnNameMaps.inc`, including the `.aq`/`.rl` variants
+//! and instructions the decoder does not emit yet; the discriminants are
+//! frozen, so never renumber them. Compressed encodings get their own ids
+//! (`c.addi` is not `addi`), while pseudo-instructions such as `beqz` map to
+//! the instruction they expand to.
+//!
+//! Vendor instructions Capstone does not know are appended after its last id,
+//! starting at [`RiscVInstructionId::VENDOR_START`].
+
+use alloc::string::String;
+
+/// Declares the id enum together with its name table.
+macro_rules! instruction_ids {
+    ($($variant:ident = $id:literal => $name:literal,)*) => {
+        /// Instruction identifiers, numbered like Capstone's `riscv_insn`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(u16)]
+        pub enum RiscVInstructionId {
+            /// Not a valid instruction.
+            Invalid = 0,
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant = $id,
+            )*
+        }
+
+        impl RiscVInstructionId {
+            /// Every valid id, in numeric order.
+            pub const ALL: &'static [RiscVInstructionId] = &[$(RiscVInstructionId::$variant,)*];
+
+            /// Mnemonics indexed by `id - 1`.
+            const NAMES: &'static [&'static str] = &[$($name,)*];
+
+            /// Variant names indexed by `id - 1`, for [`Self::constant_name`].
+            const VARIANTS: &'static [&'static str] = &[$(stringify!($variant),)*];
+        }
+    };
+}
+
+instruction_ids! {
+    Add = 1 => "add",
+    Addi = 2 => "addi",
+    Addiw = 3 => "addiw",
+    Addw = 4 => "addw",
+    AmoaddD = 5 => "amoadd.d",
+    AmoaddDAq = 6 => "amoadd.d.aq",
+    AmoaddDAqRl = 7 => "amoadd.d.aqrl",
+    AmoaddDRl = 8 => "amoadd.d.rl",
+    AmoaddW = 9 => "amoadd.w",
+    AmoaddWAq = 10 => "amoadd.w.aq",
+    AmoaddWAqRl = 11 => "amoadd.w.aqrl",
+    AmoaddWRl = 12 => "amoadd.w.rl",
+    AmoandD = 13 => "amoand.d",
+    AmoandDAq = 14 => "amoand.d.aq",
+    AmoandDAqRl = 15 => "amoand.d.aqrl",
+    AmoandDRl = 16 => "amoand.d.rl",
+    AmoandW = 17 => "amoand.w",
+    AmoandWAq = 18 => "amoand.w.aq",
+    AmoandWAqRl = 19 => "amoand.w.aqrl",
+    AmoandWRl = 20 => "amoand.w.rl",
+    AmomaxuD = 21 => "amomaxu.d",
+    AmomaxuDAq = 22 => "amomaxu.d.aq",
+    AmomaxuDAqRl = 23 => "amomaxu.d.aqrl",
+    AmomaxuDRl = 24 => "amomaxu.d.rl",
+    AmomaxuW = 25 => "amomaxu.w",
+    AmomaxuWAq = 26 => "amomaxu.w.aq",
+    AmomaxuWAqRl = 27 => "amomaxu.w.aqrl",
+    AmomaxuWRl = 28 => "amomaxu.w.rl",
+    AmomaxD = 29 => "amomax.d",
+    AmomaxDAq = 30 => "amomax.d.aq",
+    AmomaxDAqRl = 31 => "amomax.d.aqrl",
+    AmomaxDRl = 32 => "amomax.d.rl",
+    AmomaxW = 33 => "amomax.w",
+    AmomaxWAq = 34 => "amomax.w.aq",
+    AmomaxWAqRl = 35 => "amomax.w.aqrl",
+    AmomaxWRl = 36 => "amomax.w.rl",
+    AmominuD = 37 => "amominu.d",
+    AmominuDAq = 38 => "amominu.d.aq",
+    AmominuDAqRl = 39 => "amominu.d.aqrl",
+    AmominuDRl = 40 => "amominu.d.rl",
+    AmominuW = 41 => "amominu.w",
+    AmominuWAq = 42 => "amominu.w.aq",
+    AmominuWAqRl = 43 => "amominu.w.aqrl",
+    AmominuWRl = 44 => "amominu.w.rl",
+    AmominD = 45 => "amomin.d",
+    AmominDAq = 46 => "amomin.d.aq",
+    AmominDAqRl = 47 => "amomin.d.aqrl",
+    AmominDRl = 48 => "amomin.d.rl",
+    AmominW = 49 => "amomin.w",
+    AmominWAq = 50 => "amomin.w.aq",
+    AmominWAqRl = 51 => "amomin.w.aqrl",
+    AmominWRl = 52 => "amomin.w.rl",
+    AmoorD = 53 => "amoor.d",
+    AmoorDAq = 54 => "amoor.d.aq",
+    AmoorDAqRl = 55 => "amoor.d.aqrl",
+    AmoorDRl = 56 => "amoor.d.rl",
+    AmoorW = 57 => "amoor.w",
+    AmoorWAq = 58 => "amoor.w.aq",
+    AmoorWAqRl = 59 => "amoor.w.aqrl",
+    AmoorWRl = 60 => "amoor.w.rl",
+    AmoswapD = 61 => "amoswap.d",
+    AmoswapDAq = 62 => "amoswap.d.aq",
+    AmoswapDAqRl = 63 => "amoswap.d.aqrl",
+    AmoswapDRl = 64 => "amoswap.d.rl",
+    AmoswapW = 65 => "amoswap.w",
+    AmoswapWAq = 66 => "amoswap.w.aq",
+    AmoswapWAqRl = 67 => "amoswap.w.aqrl",
+    AmoswapWRl = 68 => "amoswap.w.rl",
+    AmoxorD = 69 => "amoxor.d",
+    AmoxorDAq = 70 => "amoxor.d.aq",
+    AmoxorDAqRl = 71 => "amoxor.d.aqrl",
+    AmoxorDRl = 72 => "amoxor.d.rl",
+    AmoxorW = 73 => "amoxor.w",
+    AmoxorWAq = 74 => "amoxor.w.aq",
+    AmoxorWAqRl = 75 => "amoxor.w.aqrl",
+    AmoxorWRl = 76 => "amoxor.w.rl",
+    And = 77 => "and",
+    Andi = 78 => "andi",
+    Auipc = 79 => "auipc",
+    Beq = 80 => "beq",
+    Bge = 81 => "bge",
+    Bgeu = 82 => "bgeu",
+    Blt = 83 => "blt",
+    Bltu = 84 => "bltu",
+    Bne = 85 => "bne",
+    Csrrc = 86 => "csrrc",
+    Csrrci = 87 => "csrrci",
+    Csrrs = 88 => "csrrs",
+    Csrrsi = 89 => "csrrsi",
+    Csrrw = 90 => "csrrw",
+    Csrrwi = 91 => "csrrwi",
+    CAdd = 92 => "c.add",
+    CAddi = 93 => "c.addi",
+    CAddi16sp = 94 => "c.addi16sp",
+    CAddi4spn = 95 => "c.addi4spn",
+    CAddiw = 96 => "c.addiw",
+    CAddw = 97 => "c.addw",
+    CAnd = 98 => "c.and",
+    CAndi = 99 => "c.andi",
+    CBeqz = 100 => "c.beqz",
+    CBnez = 101 => "c.bnez",
+    CEbreak = 102 => "c.ebreak",
+    CFld = 103 => "c.fld",
+    CFldsp = 104 => "c.fldsp",
+    CFlw = 105 => "c.flw",
+    CFlwsp = 106 => "c.flwsp",
+    CFsd = 107 => "c.fsd",
+    CFsdsp = 108 => "c.fsdsp",
+    CFsw = 109 => "c.fsw",
+    CFswsp = 110 => "c.fswsp",
+    CJ = 111 => "c.j",
+    CJal = 112 => "c.jal",
+    CJalr = 113 => "c.jalr",
+    CJr = 114 => "c.jr",
+    CLd = 115 => "c.ld",
+    CLdsp = 116 => "c.ldsp",
+    CLi = 117 => "c.li",
+    CLui = 118 => "c.lui",
+    CLw = 119 => "c.lw",
+    CLwsp = 120 => "c.lwsp",
+    CMv = 121 => "c.mv",
+    CNop = 122 => "c.nop",
+    COr = 123 => "c.or",
+    CSd = 124 => "c.sd",
+    CSdsp = 125 => "c.sdsp",
+    CSlli = 126 => "c.slli",
+    CSrai = 127 => "c.srai",
+    CSrli = 128 => "c.srli",
+    CSub = 129 => "c.sub",
+    CSubw = 130 => "c.subw",
+    CSw = 131 => "c.sw",
+    CSwsp = 132 => "c.swsp",
+    CUnimp = 133 => "c.unimp",
+    CXor = 134 => "c.xor",
+    Div = 135 => "div",
+    Divu = 136 => "divu",
+    Divuw = 137 => "divuw",
+    Divw = 138 => "divw",
+    Ebreak = 139 => "ebreak",
+    Ecall = 140 => "ecall",
+    FaddD = 141 => "fadd.d",
+    FaddS = 142 => "fadd.s",
+    FclassD = 143 => "fclass.d",
+    FclassS = 144 => "fclass.s",
+    FcvtDL = 145 => "fcvt.d.l",
+    FcvtDLu = 146 => "fcvt.d.lu",
+    FcvtDS = 147 => "fcvt.d.s",
+    FcvtDW = 148 => "fcvt.d.w",
+    FcvtDWu = 149 => "fcvt.d.wu",
+    FcvtLuD = 150 => "fcvt.lu.d",
+    FcvtLuS = 151 => "fcvt.lu.s",
+    FcvtLD = 152 => "fcvt.l.d",
+    FcvtLS = 153 => "fcvt.l.s",
+    FcvtSD = 154 => "fcvt.s.d",
+    FcvtSL = 155 => "fcvt.s.l",
+    FcvtSLu = 156 => "fcvt.s.lu",
+    FcvtSW = 157 => "fcvt.s.w",
+    FcvtSWu = 158 => "fcvt.s.wu",
+    FcvtWuD = 159 => "fcvt.wu.d",
+    FcvtWuS = 160 => "fcvt.wu.s",
+    FcvtWD = 161 => "fcvt.w.d",
+    FcvtWS = 162 => "fcvt.w.s",
+    FdivD = 163 => "fdiv.d",
+    FdivS = 164 => "fdiv.s",
+    Fence = 165 => "fence",
+    FenceI = 166 => "fence.i",
+    FenceTso = 167 => "fence.tso",
+    FeqD = 168 => "feq.d",
+    FeqS = 169 => "feq.s",
+    Fld = 170 => "fld",
+    FleD = 171 => "fle.d",
+    FleS = 172 => "fle.s",
+    FltD = 173 => "flt.d",
+    FltS = 174 => "flt.s",
+    Flw = 175 => "flw",
+    FmaddD = 176 => "fmadd.d",
+    FmaddS = 177 => "fmadd.s",
+    FmaxD = 178 => "fmax.d",
+    FmaxS = 179 => "fmax.s",
+    FminD = 180 => "fmin.d",
+    FminS = 181 => "fmin.s",
+    FmsubD = 182 => "fmsub.d",
+    FmsubS = 183 => "fmsub.s",
+    FmulD = 184 => "fmul.d",
+    FmulS = 185 => "fmul.s",
+    FmvDX = 186 => "fmv.d.x",
+    FmvWX = 187 => "fmv.w.x",
+    FmvXD = 188 => "fmv.x.d",
+    FmvXW = 189 => "fmv.x.w",
+    FnmaddD = 190 => "fnmadd.d",
+    FnmaddS = 191 => "fnmadd.s",
+    FnmsubD = 192 => "fnmsub.d",
+    FnmsubS = 193 => "fnmsub.s",
+    Fsd = 194 => "fsd",
+    FsgnjnD = 195 => "fsgnjn.d",
+    FsgnjnS = 196 => "fsgnjn.s",
+    FsgnjxD = 197 => "fsgnjx.d",
+    FsgnjxS = 198 => "fsgnjx.s",
+    FsgnjD = 199 => "fsgnj.d",
+    FsgnjS = 200 => "fsgnj.s",
+    FsqrtD = 201 => "fsqrt.d",
+    FsqrtS = 202 => "fsqrt.s",
+    FsubD = 203 => "fsub.d",
+    FsubS = 204 => "fsub.s",
+    Fsw = 205 => "fsw",
+    Jal = 206 => "jal",
+    Jalr = 207 => "jalr",
+    Lb = 208 => "lb",
+    Lbu = 209 => "lbu",
+    Ld = 210 => "ld",
+    Lh = 211 => "lh",
+    Lhu = 212 => "lhu",
+    LrD = 213 => "lr.d",
+    LrDAq = 214 => "lr.d.aq",
+    LrDAqRl = 215 => "lr.d.aqrl",
+    LrDRl = 216 => "lr.d.rl",
+    LrW = 217 => "lr.w",
+    LrWAq = 218 => "lr.w.aq",
+    LrWAqRl = 219 => "lr.w.aqrl",
+    LrWRl = 220 => "lr.w.rl",
+    Lui = 221 => "lui",
+    Lw = 222 => "lw",
+    Lwu = 223 => "lwu",
+    Mret = 224 => "mret",
+    Mul = 225 => "mul",
+    Mulh = 226 => "mulh",
+    Mulhsu = 227 => "mulhsu",
+    Mulhu = 228 => "mulhu",
+    Mulw = 229 => "mulw",
+    Or = 230 => "or",
+    Ori = 231 => "ori",
+    Rem = 232 => "rem",
+    Remu = 233 => "remu",
+    Remuw = 234 => "remuw",
+    Remw = 235 => "remw",
+    Sb = 236 => "sb",
+    ScD = 237 => "sc.d",
+    ScDAq = 238 => "sc.d.aq",
+    ScDAqRl = 239 => "sc.d.aqrl",
+    ScDRl = 240 => "sc.d.rl",
+    ScW = 241 => "sc.w",
+    ScWAq = 242 => "sc.w.aq",
+    ScWAqRl = 243 => "sc.w.aqrl",
+    ScWRl = 244 => "sc.w.rl",
+    Sd = 245 => "sd",
+    SfenceVma = 246 => "sfence.vma",
+    Sh = 247 => "sh",
+    Sll = 248 => "sll",
+    Slli = 249 => "slli",
+    Slliw = 250 => "slliw",
+    Sllw = 251 => "sllw",
+    Slt = 252 => "slt",
+    Slti = 253 => "slti",
+    Sltiu = 254 => "sltiu",
+    Sltu = 255 => "sltu",
+    Sra = 256 => "sra",
+    Srai = 257 => "srai",
+    Sraiw = 258 => "sraiw",
+    Sraw = 259 => "sraw",
+    Sret = 260 => "sret",
+    Srl = 261 => "srl",
+    Srli = 262 => "srli",
+    Srliw = 263 => "srliw",
+    Srlw = 264 => "srlw",
+    Sub = 265 => "sub",
+    Subw = 266 => "subw",
+    Sw = 267 => "sw",
+    Unimp = 268 => "unimp",
+    Uret = 269 => "uret",
+    Wfi = 270 => "wfi",
+    Xor = 271 => "xor",
+    Xori = 272 => "xori",
+    ThMveqz = 273 => "th.mveqz",
+    ThMvnez = 274 => "th.mvnez",
+}
+
+/// Pseudo-instruction mnemonics emitted by the decoder, with the instruction
+/// each one expands to.
+const ALIASES: &[(&str, RiscVInstructionId)] = &[
+    ("beqz", RiscVInstructionId::Beq),
+    ("bnez", RiscVInstructionId::Bne),
+    ("csrc", RiscVInstructionId::Csrrc),
+    ("csrr", RiscVInstructionId::Csrrs),
+    ("csrw", RiscVInstructionId::Csrrw),
+    ("j", RiscVInstructionId::Jal),
+];
+
+impl RiscVInstructionId {
+    /// First id outside Capstone's range (its `RISCV_INS_ENDING`).
+    pub const VENDOR_START: u32 = RiscVInstructionId::ThMveqz as u32;
+
+    /// One past the largest valid id, like Capstone's `RISCV_INS_ENDING`.
+    pub const ENDING: u32 = Self::ALL.len() as u32 + 1;
+
+    /// Returns the canonical mnemonic, or `"invalid"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            RiscVInstructionId::Invalid => "invalid",
+            id => Self::NAMES[id as usize - 1],
+        }
+    }
+
+    /// Returns the suffix of the `RISCV_INS_*` constant, e.g. `AMOADD_W_AQ_RL`.
+    pub fn constant_name(self) -> String {
+        let variant = match self {
+            RiscVInstructionId::Invalid => "Invalid",
+            id => Self::VARIANTS[id as usize - 1],
+        };
+        let mut name = String::with_capacity(variant.len() + 4);
+        for (index, ch) in variant.chars().enumerate() {
+            if index > 0 && ch.is_ascii_uppercase() {
+                name.push('_');
+            }
+            name.push(ch.to_ascii_uppercase());
+        }
+        name
+    }
+
+    /// Converts a raw identifier into the enum representation.
+    pub fn from_id(id: u32) -> Self {
+        match id.checked_sub(1) {
+            Some(index) => Self::ALL
+                .get(index as usize)
+                .copied()
+                .unwrap_or(RiscVInstructionId::Invalid),
+            None => RiscVInstructionId::Invalid,
+        }
+    }
+
+    /// Looks up the id of a decoder mnemonic, resolving pseudo-instructions.
+    pub fn from_mnemonic(mnemonic: &str) -> Self {
+        // Capstone orders its ids by constant name, which is the mnemonic
+        // upper-cased with `.` spelled `_`.
+        let capstone = &Self::NAMES[..Self::VENDOR_START as usize - 1];
+        if let Ok(index) = capstone.binary_search_by(|name| sort_key(name).cmp(sort_key(mnemonic)))
+        {
+            return Self::ALL[index];
+        }
+        let vendor = Self::ALL[capstone.len()..]
+            .iter()
+            .find(|id| id.name() == mnemonic);
+        if let Some(&id) = vendor {
+            return id;
+        }
+        ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == mnemonic)
+            .map_or(RiscVInstructionId::Invalid, |&(_, id)| id)
+    }
+
+    /// Returns the raw identifier.
+    pub fn id(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Maps a mnemonic onto the byte order of Capstone's constant names.
+fn sort_key(mnemonic: &str) -> impl Iterator<Item = u8> + '_ {
+    mnemonic.bytes().map(|byte| {
+        if byte == b'.' {
+            b'_'
+        } else {
+            byte.to_ascii_uppercase()
+        }
+    })
+}
+
+/// Returns the mnemonic for a raw instruction id, or `None` when unknown.
+pub fn insn_name(id: u32) -> Option<&'static str> {
+    match RiscVInstructionId::from_id(id) {
+        RiscVInstructionId::Invalid => None,
+        id => Some(id.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_are_sorted_and_unique() {
+        let capstone = &RiscVInstructionId::NAMES[..RiscVInstructionId::VENDOR_START as usize - 1];
+        assert!(
+            capstone
+                .windows(2)
+                .all(|pair| sort_key(pair[0]).lt(sort_key(pair[1])))
+        );
+        assert_eq!(
+            RiscVInstructionId::NAMES.len(),
+            RiscVInstructionId::ALL.len()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (index, &id) in RiscVInstructionId::ALL.iter().enumerate() {
+            assert_eq!(id.id(), index as u32 + 1);
+            assert_eq!(RiscVInstructionId::from_id(id.id()), id);
+            assert_eq!(RiscVInstructionId::from_mnemonic(id.name()), id);
+        }
+        assert_eq!(RiscVInstructionId::from_id(0), RiscVInstructionId::Invalid);
+        assert_eq!(
+            RiscVInstructionId::from_id(RiscVInstructionId::ENDING),
+            RiscVInstructionId::Invalid
+        );
+    }
+
+    #[test]
+    fn test_capstone_ids() {
+        assert_eq!(RiscVInstructionId::Add.id(), 1);
+        assert_eq!(RiscVInstructionId::AmoaddWAqRl.id(), 11);
+        assert_eq!(RiscVInstructionId::CAddi.id(), 93);
+        assert_eq!(RiscVInstructionId::Ld.id(), 210);
+        assert_eq!(RiscVInstructionId::Xori.id(), 272);
+        assert_eq!(RiscVInstructionId::VENDOR_START, 273);
+        assert_eq!(
+            RiscVInstructionId::AmoaddWAqRl.constant_name(),
+            "AMOADD_W_AQ_RL"
+        );
+        assert_eq!(RiscVInstructionId::CAddi16sp.constant_name(), "C_ADDI16SP");
+        assert_eq!(
+            RiscVInstructionId::from_mnemonic("amomaxu.d"),
+            RiscVInstructionId::AmomaxuD
+        );
+    }
+
+    #[test]
+    fn test_compressed_and_pseudo_mnemonics() {
+        assert_ne!(
+            RiscVInstructionId::from_mnemonic("c.addi"),
+            RiscVInstructionId::from_mnemonic("addi")
+        );
+        assert_eq!(
+            RiscVInstructionId::from_mnemonic("beqz"),
+            RiscVInstructionId::Beq
+        );
+        assert_eq!(
+            RiscVInstructionId::from_mnemonic("unknown"),
+            RiscVInstructionId::Invalid
+        );
+        assert_eq!(insn_name(RiscVInstructionId::CAddi.id()), Some("c.addi"));
+        assert_eq!(insn_name(0), None);
+    }
+}
@@ -8,19 +8,44 @@
 //! This module implements the generic `Architecture` trait for RISC-V
 //! and provides both modern architecture-aware interfaces and legacy
 //! compatibility interfaces.
+//!
+//! # `no_std` Support
+//!
+//! Disabling the default `std` feature builds the decoder as `#![no_std]`
+//! on top of `alloc`, for use in debug monitors and hypervisors.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+pub mod alias;
 pub mod arch;
+pub mod compressed;
+pub mod constants;
 pub mod decoder;
 pub mod extensions;
+pub mod groups;
+pub mod insn;
 pub mod printer;
 pub mod shared;
+pub mod targets;
 pub mod types;
+pub mod xrefs;
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
 use arch::RiscVInstructionDetail;
-use decoder::{RiscVDecoder, Xlen};
+use core::fmt::Write;
+use decoder::{RiscVDecodedInstruction, RiscVDecoder, Xlen};
 use extensions::Extensions;
+use printer::RiscVPrinter;
 use robustone_core::{
-    traits::ArchitectureHandler, types::error::DisasmError, types::instruction::Instruction,
+    traits::{ArchitectureHandler, Symbolizer},
+    types::error::DisasmError,
+    types::instruction::Instruction,
+    types::options::{AliasMode, HandlerOptions, SyntaxStyle},
+    types::slot::InstructionSlot,
 };
 use types::*;
 
@@ -28,36 +53,174 @@ use types::*;
 pub struct RiscVHandler {
     /// Decoder used to translate raw bytes into structured instructions.
     decoder: RiscVDecoder,
+    /// Runtime options set through [`ArchitectureHandler::set_option`].
+    options: HandlerOptions,
+    /// Printer derived from `options`, used to format operands.
+    printer: RiscVPrinter,
+    /// Names branch targets and `auipc` addresses when installed.
+    symbolizer: Option<Box<dyn Symbolizer>>,
 }
 
 impl RiscVHandler {
     /// Creates a new handler configured for 64-bit RISC-V with GC extensions.
     pub fn new() -> Self {
-        Self {
-            decoder: RiscVDecoder::rv64gc(),
-        }
+        Self::from_decoder(RiscVDecoder::rv64gc())
     }
 
     /// Creates a handler targeting RV32GC.
     pub fn rv32() -> Self {
-        Self {
-            decoder: RiscVDecoder::rv32gc(),
-        }
+        Self::from_decoder(RiscVDecoder::rv32gc())
     }
 
     /// Creates a handler targeting RV64GC.
     pub fn rv64() -> Self {
-        Self {
-            decoder: RiscVDecoder::rv64gc(),
-        }
+        Self::from_decoder(RiscVDecoder::rv64gc())
     }
 
     /// Creates a handler with custom XLEN and extension flags.
     pub fn with_extensions(xlen: Xlen, extensions: Extensions) -> Self {
+        Self::from_decoder(RiscVDecoder::new(xlen, extensions))
+    }
+
+    fn from_decoder(decoder: RiscVDecoder) -> Self {
+        let options = HandlerOptions::default();
+        let printer = RiscVPrinter::from_options(&options, decoder.xlen());
         Self {
-            decoder: RiscVDecoder::new(xlen, extensions),
+            decoder,
+            options,
+            printer,
+            symbolizer: None,
+        }
+    }
+
+    /// Returns the underlying decoder, for callers that need structured operands.
+    pub fn decoder(&self) -> &RiscVDecoder {
+        &self.decoder
+    }
+
+    /// Returns the mnemonic and operands to print for `decoded`, resolved for
+    /// the configured aliases.
+    fn printed(
+        &self,
+        decoded: &RiscVDecodedInstruction,
+        bytes: &[u8],
+        addr: u64,
+    ) -> (&'static str, RiscVTextOperands) {
+        let mode = self.printer.alias_mode(self.options.aliases);
+        if mode == AliasMode::Capstone {
+            // The decoder already renders Capstone's aliases.
+            return (decoded.mnemonic, decoded.operands);
+        }
+        if decoded.size == 2 && self.options.syntax == SyntaxStyle::Llvm {
+            let hint = compressed::hint(
+                u16::from_le_bytes([bytes[0], bytes[1]]),
+                self.decoder.xlen(),
+            );
+            if let Some(hint) = hint {
+                return hint;
+            }
+        }
+        let expanded = self.expand(decoded, bytes, addr, mode);
+        let (decoded, word) = match &expanded {
+            Some((expanded, word)) => (expanded, Some(*word)),
+            None => (decoded, instruction_word(bytes, decoded.size)),
+        };
+        word.and_then(|word| alias::resolve(mode, self.decoder.xlen(), word))
+            .unwrap_or((decoded.mnemonic, decoded.operands))
+    }
+
+    /// Decodes the 32-bit equivalent of a compressed instruction, together
+    /// with its instruction word, for syntaxes that print it expanded.
+    fn expand(
+        &self,
+        decoded: &RiscVDecodedInstruction,
+        bytes: &[u8],
+        addr: u64,
+        mode: AliasMode,
+    ) -> Option<(RiscVDecodedInstruction, u32)> {
+        if decoded.size != 2 || !self.printer.expands_compressed(mode) {
+            return None;
+        }
+        let half = u16::from_le_bytes([bytes[0], bytes[1]]);
+        let word = compressed::expand(half, self.decoder.xlen())?;
+        let expanded = self.decoder.decode(&word.to_le_bytes(), addr).ok()?;
+        (expanded.mnemonic != "unknown").then_some((expanded, word))
+    }
+
+    /// Renders the real instruction behind `decoded`, styled like the
+    /// printed operands.
+    fn real_instruction(
+        &self,
+        decoded: &RiscVDecodedInstruction,
+        word: Option<u32>,
+        addr: u64,
+    ) -> (&'static str, String) {
+        let (mnemonic, real) = word
+            .and_then(|word| alias::real_instruction(self.decoder.xlen(), word))
+            .unwrap_or((decoded.mnemonic, decoded.operands));
+        let mut operands = String::new();
+        self.style(&real, addr, &mut operands);
+        (mnemonic, operands)
+    }
+
+    /// Replaces the branch target in `operands` with its symbol, or annotates
+    /// an `auipc` with the symbol of the address it forms together with the
+    /// next instruction in `bytes`.
+    fn symbolize(&self, bytes: &[u8], size: usize, addr: u64, operands: &mut String) {
+        let Some(symbolizer) = &self.symbolizer else {
+            return;
+        };
+        let Some(word) = self.instruction_word_at(bytes) else {
+            return;
+        };
+        let xlen = self.decoder.xlen();
+        let gnu = self.options.syntax == SyntaxStyle::Gnu;
+        if let Some(target) = targets::branch_target(word, addr, xlen) {
+            if symbolizer.symbolize(target).is_none() {
+                return;
+            }
+            if gnu {
+                // objdump keeps the address and adds the symbol after it.
+                operands.push(' ');
+            } else {
+                let start = operands.rfind(',').map_or(0, |comma| comma + 1);
+                let start = start + usize::from(operands[start..].starts_with(' '));
+                operands.truncate(start);
+            }
+            symbolizer.write_symbol(target, operands);
+            return;
+        }
+        let target = bytes
+            .get(size..)
+            .and_then(|next| self.instruction_word_at(next))
+            .and_then(|next| targets::auipc_pair_target(word, next, addr, xlen));
+        if let Some(target) = target.filter(|&target| symbolizer.symbolize(target).is_some()) {
+            let _ = if gnu {
+                write!(operands, " # {target:x} ")
+            } else {
+                write!(operands, " # 0x{target:x} ")
+            };
+            symbolizer.write_symbol(target, operands);
+        }
+    }
+
+    /// Returns the 32-bit form of the instruction at the start of `bytes`,
+    /// expanding compressed encodings.
+    fn instruction_word_at(&self, bytes: &[u8]) -> Option<u32> {
+        match bytes {
+            [b0, b1, ..] if b0 & 0b11 != 0b11 => {
+                compressed::expand(u16::from_le_bytes([*b0, *b1]), self.decoder.xlen())
+            }
+            _ => instruction_word(bytes, 4),
         }
     }
+
+    /// Appends `operands` of the instruction at `addr` to `out`, formatted
+    /// with the configured options.
+    fn style(&self, operands: &RiscVTextOperands, addr: u64, out: &mut String) {
+        // Writing into a `String` cannot fail.
+        let _ = self.printer.write_operands(operands.as_slice(), addr, out);
+    }
 }
 
 impl Default for RiscVHandler {
@@ -70,36 +233,106 @@ impl ArchitectureHandler for RiscVHandler {
     fn disassemble(&self, bytes: &[u8], addr: u64) -> Result<(Instruction, usize), DisasmError> {
         // Decode the instruction with the dedicated RISC-V decoder.
         let decoded = self.decoder.decode(bytes, addr)?;
+        let id = decoded.id().id();
+        let word = instruction_word(bytes, decoded.size);
+        let real = self
+            .options
+            .detail
+            .then(|| self.real_instruction(&decoded, word, addr));
 
-        // Create simple instruction detail with register information
-        let mut riscv_detail = RiscVInstructionDetail::new();
+        let (mnemonic, printed) = self.printed(&decoded, bytes, addr);
+        let mut operands = String::new();
+        self.style(&printed, addr, &mut operands);
 
-        // Track register usage from operands
-        for operand in &decoded.operands_detail {
-            if matches!(operand.op_type, RiscVOperandType::Register) {
-                if operand.access.read
-                    && let RiscVOperandValue::Register(reg) = operand.value
-                {
-                    riscv_detail = riscv_detail.reads_register(reg);
+        let mut instruction = Instruction::new(
+            addr,
+            bytes[..decoded.size].to_vec(),
+            mnemonic.to_string(),
+            operands,
+        )
+        .with_id(id);
+        if self.options.detail {
+            // Create simple instruction detail with register information
+            let mut riscv_detail = RiscVInstructionDetail::new();
+            for_each_register_access(&decoded.operands_detail, |reg, access| {
+                if access.read {
+                    riscv_detail.regs_read.push(reg);
                 }
-                if operand.access.write
-                    && let RiscVOperandValue::Register(reg) = operand.value
-                {
-                    riscv_detail = riscv_detail.writes_register(reg);
+                if access.write {
+                    riscv_detail.regs_write.push(reg);
                 }
-            }
+            });
+            riscv_detail.groups = groups::classify(decoded.mnemonic, &decoded.operands_detail);
+            riscv_detail.real_instruction = real.filter(|(real_mnemonic, real_operands)| {
+                (*real_mnemonic, real_operands.as_str())
+                    != (mnemonic, instruction.operands.as_str())
+            });
+            // The decoder describes the real instruction; an alias lists
+            // only the operands it prints unless real detail is requested.
+            let alias = (riscv_detail.real_instruction.is_some() && !self.options.real_detail)
+                .then(|| alias::alias_operands(printed.as_slice(), &decoded.operands_detail))
+                .flatten();
+            riscv_detail.operands = alias.unwrap_or(decoded.operands_detail).to_vec();
+            instruction.detail = Some(Box::new(riscv_detail));
+        }
+        self.symbolize(bytes, decoded.size, addr, &mut instruction.operands);
+
+        Ok((instruction, decoded.size))
+    }
+
+    fn disassemble_into(
+        &self,
+        bytes: &[u8],
+        addr: u64,
+        slot: &mut InstructionSlot,
+    ) -> Result<usize, DisasmError> {
+        let decoded = self.decoder.decode(bytes, addr)?;
+
+        slot.reset(addr);
+        slot.set_bytes(&bytes[..decoded.size]).map_err(|len| {
+            DisasmError::DecodingError(format!("instruction too long for slot: {len} bytes"))
+        })?;
+        slot.set_id(decoded.id().id());
+        let (mnemonic, operands) = self.printed(&decoded, bytes, addr);
+        slot.set_mnemonic(mnemonic);
+        if self.symbolizer.is_some() {
+            let text = slot.operands_mut();
+            self.style(&operands, addr, text);
+            self.symbolize(bytes, decoded.size, addr, text);
+        } else {
+            slot.defer_operands(self.printer.defer_operands(operands.as_slice(), addr));
+        }
+        if self.options.detail {
+            for_each_register_access(&decoded.operands_detail, |reg, access| {
+                if access.read {
+                    slot.reads_register(reg);
+                }
+                if access.write {
+                    slot.writes_register(reg);
+                }
+            });
         }
 
-        Ok((
-            Instruction::with_detail(
-                addr,
-                bytes[..decoded.size].to_vec(),
-                decoded.mnemonic,
-                decoded.operands,
-                Box::new(riscv_detail),
-            ),
-            decoded.size,
-        ))
+        Ok(decoded.size)
+    }
+
+    fn options(&self) -> HandlerOptions {
+        self.options
+    }
+
+    fn set_options(&mut self, options: HandlerOptions) -> Result<(), DisasmError> {
+        self.decoder.set_detail(options.detail);
+        self.printer = RiscVPrinter::from_options(&options, self.decoder.xlen());
+        self.options = options;
+        Ok(())
+    }
+
+    fn set_symbolizer(
+        &mut self,
+        symbolizer: Option<Box<dyn Symbolizer>>,
+    ) -> Result<(), DisasmError> {
+        self.symbolizer = symbolizer;
+        Ok(())
     }
 
     fn name(&self) -> &'static str {
@@ -111,6 +344,26 @@ impl ArchitectureHandler for RiscVHandler {
     }
 }
 
+/// Returns the instruction word of a standard 32-bit encoding.
+fn instruction_word(bytes: &[u8], size: usize) -> Option<u32> {
+    match (size, bytes) {
+        (4, [b0, b1, b2, b3, ..]) => Some(u32::from_le_bytes([*b0, *b1, *b2, *b3])),
+        _ => None,
+    }
+}
+
+/// Invokes `f` for every register operand together with its access flags,
+/// and for the base of every memory operand, which is read.
+fn for_each_register_access(operands: &[RiscVOperand], mut f: impl FnMut(u32, Access)) {
+    for operand in operands {
+        match operand.value {
+            RiscVOperandValue::Register(reg) => f(reg, operand.access),
+            RiscVOperandValue::Memory(mem) => f(mem.base, Access::read()),
+            RiscVOperandValue::Immediate(_) => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,7 +391,9 @@ mod tests {
     fn test_riscv_register_from_id() {
         assert_eq!(RiscVRegister::from_id(0), RiscVRegister::X0);
         assert_eq!(RiscVRegister::from_id(1), RiscVRegister::X1);
-        assert_eq!(RiscVRegister::from_id(32), RiscVRegister::Invalid);
+        assert_eq!(RiscVRegister::from_id(32), RiscVRegister::F0_32);
+        assert_eq!(RiscVRegister::from_id(74), RiscVRegister::F10_64);
+        assert_eq!(RiscVRegister::from_id(96), RiscVRegister::Invalid);
         assert_eq!(RiscVRegister::from_id(100), RiscVRegister::Invalid);
     }
 
@@ -156,4 +411,374 @@ mod tests {
         let none_access = Access::none();
         assert!(!none_access.read && !none_access.write);
     }
+
+    #[test]
+    fn test_disassemble_into_matches_owned_path() {
+        let handler = RiscVHandler::rv64();
+        let code = [
+            0x93, 0x00, 0x10, 0x00, // addi ra, zero, 1
+            0x33, 0x85, 0xc5, 0x00, // add a0, a1, a2
+            0x82, 0x80, // c.jr ra
+        ];
+
+        let mut slot = InstructionSlot::new();
+        let mut offset = 0;
+        while offset < code.len() {
+            let addr = 0x1000 + offset as u64;
+            let (owned, size) = handler.disassemble(&code[offset..], addr).unwrap();
+            let slot_size = handler
+                .disassemble_into(&code[offset..], addr, &mut slot)
+                .unwrap();
+
+            // The operand text is only formatted when read.
+            assert!(slot.deferred_operands().is_some());
+            assert_eq!(slot_size, size);
+            assert_eq!(slot.bytes(), owned.bytes.as_slice());
+            assert_eq!(slot.mnemonic(), owned.mnemonic);
+            assert_eq!(slot.operands(), owned.operands);
+            let detail = owned.detail.as_ref().unwrap();
+            assert_eq!(slot.registers_read(), detail.registers_read());
+            assert_eq!(slot.registers_written(), detail.registers_written());
+            offset += size;
+        }
+    }
+
+    #[test]
+    fn test_floating_point_register_detail() {
+        let handler = RiscVHandler::rv64();
+        let registers = |code: [u8; 4]| {
+            let (instruction, _) = handler.disassemble(&code, 0).unwrap();
+            let detail = instruction.detail.unwrap();
+            let names = |regs: &[u32]| {
+                regs.iter()
+                    .map(|reg| detail.register_name(*reg).unwrap())
+                    .collect::<alloc::vec::Vec<_>>()
+                    .join(" ")
+            };
+            let names = (
+                names(detail.registers_read()),
+                names(detail.registers_written()),
+            );
+            (instruction.operands, names)
+        };
+
+        // fadd.s fa0, fa1, fa2
+        let (text, (read, written)) = registers([0x53, 0x85, 0xc5, 0x00]);
+        assert_eq!((text.as_str(), read.as_str()), ("fa0, fa1, fa2", "fa1 fa2"));
+        assert_eq!(written, "fa0");
+        // feq.d a0, fa0, fa1 writes an integer register.
+        let (text, (read, written)) = registers([0x53, 0x25, 0xb5, 0xa2]);
+        assert_eq!((text.as_str(), read.as_str()), ("a0, fa0, fa1", "fa0 fa1"));
+        assert_eq!(written, "a0");
+
+        let (instruction, _) = handler.disassemble(&[0x53, 0x85, 0xc5, 0x02], 0).unwrap();
+        let written = instruction.detail.unwrap().registers_written().to_vec();
+        assert_eq!(written, [RiscVRegisterClass::Fpr64.id(10)]);
+    }
+
+    #[test]
+    fn test_handler_options() {
+        use robustone_core::types::options::{HandlerOption, ImmediateRadix, RegisterNaming};
+
+        let mut handler = RiscVHandler::rv32();
+        let code = [0x13, 0x01, 0x01, 0xff]; // addi sp, sp, -16
+
+        handler
+            .set_option(HandlerOption::RegisterNaming(RegisterNaming::Numeric))
+            .unwrap();
+        handler
+            .set_option(HandlerOption::UnsignedImmediates(true))
+            .unwrap();
+        let (instruction, _) = handler.disassemble(&code, 0).unwrap();
+        assert_eq!(instruction.operands, "x2, x2, 0xfffffff0");
+        assert!(instruction.detail.is_some());
+
+        handler
+            .set_options(
+                HandlerOptions::default()
+                    .with(HandlerOption::ImmediateRadix(ImmediateRadix::Hex))
+                    .with(HandlerOption::Detail(false)),
+            )
+            .unwrap();
+        let mut slot = InstructionSlot::new();
+        handler.disassemble_into(&code, 0, &mut slot).unwrap();
+        assert_eq!(slot.operands(), "sp, sp, -0x10");
+        assert!(slot.registers_read().is_empty());
+        let (instruction, _) = handler.disassemble(&code, 0).unwrap();
+        assert!(instruction.detail.is_none());
+    }
+
+    #[test]
+    fn test_detail_off_skips_operands() {
+        use robustone_core::types::options::HandlerOption;
+
+        let mut handler = RiscVHandler::rv64();
+        handler.set_option(HandlerOption::Detail(false)).unwrap();
+        assert!(!handler.decoder().detail());
+
+        let code = [0x03, 0x35, 0x81, 0x00]; // ld a0, 8(sp)
+        let decoded = handler.decoder().decode(&code, 0).unwrap();
+        assert_eq!(decoded.operands.to_string(), "a0, 8(sp)");
+        assert!(decoded.operands_detail.is_empty());
+
+        handler.set_option(HandlerOption::Detail(true)).unwrap();
+        assert_eq!(
+            handler
+                .decoder()
+                .decode(&code, 0)
+                .unwrap()
+                .operands_detail
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_dispatcher_disassemble_lite() {
+        use robustone_core::ArchitectureDispatcher;
+
+        let mut dispatcher = ArchitectureDispatcher::new();
+        dispatcher.register(Box::new(RiscVHandler::rv64()));
+        let code = [0x93, 0x00, 0x10, 0x00, 0x05, 0x05, 0xff, 0xff, 0xff, 0xff];
+
+        let lite = dispatcher
+            .disassemble_lite(&code, "riscv64", 0x1000)
+            .unwrap();
+        assert_eq!(
+            lite,
+            [
+                (0x1000, 4, "addi".into(), "ra, zero, 1".into()),
+                (0x1004, 2, "c.addi".into(), "a0, 1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instruction_ids() {
+        use insn::RiscVInstructionId;
+
+        let handler = RiscVHandler::rv64();
+        // addi ra, zero, 1 ; c.addi a0, 1 ; beqz a0, 8
+        let (addi, _) = handler.disassemble(&[0x93, 0x00, 0x10, 0x00], 0).unwrap();
+        assert_eq!(addi.id(), RiscVInstructionId::Addi.id());
+
+        let mut slot = InstructionSlot::new();
+        handler
+            .disassemble_into(&[0x05, 0x05], 0, &mut slot)
+            .unwrap();
+        assert_eq!(slot.id(), RiscVInstructionId::CAddi.id());
+        assert_eq!(slot.to_instruction().id(), RiscVInstructionId::CAddi.id());
+
+        let (beqz, _) = handler.disassemble(&[0x63, 0x04, 0x05, 0x00], 0).unwrap();
+        assert_eq!(beqz.mnemonic, "beqz");
+        assert_eq!(beqz.id(), RiscVInstructionId::Beq.id());
+    }
+
+    #[test]
+    fn test_alias_modes() {
+        use robustone_core::types::options::{HandlerOption, RegisterNaming};
+
+        let mut handler = RiscVHandler::rv64();
+        // beqz a0, 8 ; addi a0, zero, 5
+        let beqz = [0x63, 0x04, 0x05, 0x00];
+        let li = [0x13, 0x05, 0x50, 0x00];
+
+        let (insn, _) = handler.disassemble(&beqz, 0).unwrap();
+        assert_eq!(
+            (insn.mnemonic.as_str(), insn.operands.as_str()),
+            ("beqz", "a0, 8")
+        );
+        let real = insn.detail.as_ref().unwrap().real_instruction();
+        assert_eq!(real, Some(("beq", "a0, zero, 8")));
+        let (insn, _) = handler.disassemble(&li, 0).unwrap();
+        assert_eq!(insn.mnemonic, "addi");
+        assert_eq!(insn.detail.as_ref().unwrap().real_instruction(), None);
+
+        handler
+            .set_option(HandlerOption::Aliases(AliasMode::All))
+            .unwrap();
+        handler
+            .set_option(HandlerOption::RegisterNaming(RegisterNaming::Numeric))
+            .unwrap();
+        let (insn, _) = handler.disassemble(&li, 0).unwrap();
+        assert_eq!(
+            (insn.mnemonic.as_str(), insn.operands.as_str()),
+            ("li", "x10, 5")
+        );
+        assert_eq!(insn.id(), insn::RiscVInstructionId::Addi.id());
+        let real = insn.detail.as_ref().unwrap().real_instruction();
+        assert_eq!(real, Some(("addi", "x10, x0, 5")));
+
+        handler
+            .set_option(HandlerOption::Aliases(AliasMode::None))
+            .unwrap();
+        let mut slot = InstructionSlot::new();
+        handler.disassemble_into(&beqz, 0, &mut slot).unwrap();
+        assert_eq!((slot.mnemonic(), slot.operands()), ("beq", "x10, x0, 8"));
+    }
+
+    #[test]
+    fn test_real_detail() {
+        use robustone_core::Operand;
+        use robustone_core::types::options::HandlerOption;
+
+        let mut handler = RiscVHandler::rv64();
+        let operands = |handler: &RiscVHandler, code: &[u8]| {
+            let (instruction, _) = handler.disassemble(code, 0).unwrap();
+            let detail = instruction.detail.unwrap();
+            detail
+                .operands()
+                .into_iter()
+                .map(|operand| match operand {
+                    Operand::Register { name, .. } => String::from(name),
+                    Operand::Immediate(imm) => format!("{imm}"),
+                    Operand::Memory { base, disp, .. } => format!("{disp}({base})"),
+                })
+                .collect::<alloc::vec::Vec<_>>()
+        };
+        // beqz a0, 8 ; fmv.s fa0, fa1 ; ld a1, 8(sp)
+        let beqz = [0x63, 0x04, 0x05, 0x00];
+        let fmv = [0x53, 0x85, 0xb5, 0x20];
+        let ld = [0x83, 0x35, 0x81, 0x00];
+
+        assert_eq!(operands(&handler, &beqz), ["a0", "8"]);
+        assert_eq!(operands(&handler, &ld), ["a1", "8(sp)"]);
+        handler
+            .set_option(HandlerOption::Aliases(AliasMode::All))
+            .unwrap();
+        assert_eq!(operands(&handler, &fmv), ["fa0", "fa1"]);
+
+        handler.set_option(HandlerOption::RealDetail(true)).unwrap();
+        assert_eq!(operands(&handler, &beqz), ["a0", "zero", "8"]);
+        assert_eq!(operands(&handler, &fmv), ["fa0", "fa1", "fa1"]);
+    }
+
+    #[test]
+    fn test_syntax_profiles() {
+        use robustone_core::types::options::{HandlerOption, SyntaxStyle};
+
+        let mut handler = RiscVHandler::rv64();
+        let code = [
+            0x05, 0x05, // c.addi a0, 1
+            0x82, 0x80, // c.jr ra
+            0x01, 0xc5, // c.beqz a0, 8
+            0x13, 0x01, 0x01, 0xfe, // addi sp, sp, -0x20
+        ];
+        let render = |handler: &RiscVHandler| {
+            let mut lines = alloc::vec::Vec::new();
+            let mut offset = 0;
+            while offset < code.len() {
+                let (insn, size) = handler
+                    .disassemble(&code[offset..], 0x100 + offset as u64)
+                    .unwrap();
+                lines.push(format!("{} {}", insn.mnemonic, insn.operands));
+                offset += size;
+            }
+            lines
+        };
+
+        assert_eq!(
+            render(&handler),
+            [
+                "c.addi a0, 1",
+                "c.jr ra",
+                "c.beqz a0, 8",
+                "addi sp, sp, -0x20"
+            ]
+        );
+        let (insn, _) = handler.disassemble(&code[2..], 0).unwrap();
+        assert_eq!(insn.id(), insn::RiscVInstructionId::CJr.id());
+        let detail = insn.detail.as_ref().unwrap();
+        assert_eq!(detail.registers_read(), [1]);
+        assert!(detail.registers_written().is_empty());
+
+        handler
+            .set_option(HandlerOption::Syntax(SyntaxStyle::Llvm))
+            .unwrap();
+        assert_eq!(
+            render(&handler),
+            ["addi a0, a0, 1", "ret ", "beqz a0, 8", "addi sp, sp, -32"]
+        );
+        let (insn, _) = handler.disassemble(&code, 0).unwrap();
+        assert_eq!(insn.id(), insn::RiscVInstructionId::CAddi.id());
+        let real = insn.detail.as_ref().unwrap().real_instruction();
+        assert_eq!(real, Some(("c.addi", "a0, 1")));
+        // llvm-mc keeps HINTs compressed and prints unknown CSRs in decimal.
+        let hints = [
+            0x3d, 0x00, // c.nop 15
+            0x51, 0x40, // c.li zero, 20
+            0x82, 0x02, // c.slli64 t0
+            0x73, 0xb3, 0x70, 0xa1, // csrrc t1, 0xa17, ra
+        ];
+        let mut lines = alloc::vec::Vec::new();
+        let mut offset = 0;
+        while offset < hints.len() {
+            let (insn, size) = handler.disassemble(&hints[offset..], 0).unwrap();
+            lines.push(format!("{} {}", insn.mnemonic, insn.operands));
+            offset += size;
+        }
+        assert_eq!(
+            lines,
+            [
+                "c.nop 15",
+                "c.li zero, 20",
+                "c.slli64 t0",
+                "csrrc t1, 2583, ra"
+            ]
+        );
+
+        handler
+            .set_option(HandlerOption::Syntax(SyntaxStyle::Gnu))
+            .unwrap();
+        assert_eq!(
+            render(&handler),
+            ["addi a0,a0,1", "ret ", "beqz a0,10c", "addi sp,sp,-32"]
+        );
+
+        handler
+            .set_option(HandlerOption::Aliases(AliasMode::None))
+            .unwrap();
+        let mut slot = InstructionSlot::new();
+        handler.disassemble_into(&code, 0, &mut slot).unwrap();
+        assert_eq!((slot.mnemonic(), slot.operands()), ("c.addi", "a0,1"));
+    }
+
+    #[test]
+    fn test_symbolizer() {
+        use robustone_core::types::options::{HandlerOption, SyntaxStyle};
+        use robustone_core::types::symbols::SymbolMap;
+
+        let mut symbols = SymbolMap::new();
+        symbols.insert(0x1040, "memcpy");
+        symbols.insert(0x2000, "table");
+        let mut handler = RiscVHandler::rv64();
+        handler.set_symbolizer(Some(Box::new(symbols))).unwrap();
+
+        // jal ra, 0x40 ; auipc a0, 0x1 ; addi a0, a0, 0 ; c.j 56
+        let code = [
+            0xef, 0x00, 0x00, 0x04, 0x17, 0x15, 0x00, 0x00, 0x13, 0x05, 0x05, 0x00, 0x25, 0xa8,
+        ];
+        let (insn, _) = handler.disassemble(&code, 0x1000).unwrap();
+        assert_eq!(insn.operands, "<memcpy>");
+        let (insn, _) = handler.disassemble(&code[4..], 0x1004).unwrap();
+        assert_eq!(insn.operands, "a0, 1 # 0x2004 <table+0x4>");
+        let mut slot = InstructionSlot::new();
+        handler
+            .disassemble_into(&code[12..], 0x100c, &mut slot)
+            .unwrap();
+        assert_eq!((slot.mnemonic(), slot.operands()), ("c.j", "<memcpy+0x4>"));
+
+        handler
+            .set_option(HandlerOption::Syntax(SyntaxStyle::Gnu))
+            .unwrap();
+        let (insn, _) = handler.disassemble(&code, 0x1000).unwrap();
+        assert_eq!(
+            (insn.mnemonic.as_str(), insn.operands.as_str()),
+            ("jal", "1040 <memcpy>")
+        );
+
+        // Targets without a symbol keep their numeric form.
+        let (insn, _) = handler.disassemble(&code, 0).unwrap();
+        assert_eq!(insn.operands, "40");
+    }
 }
@@ -2,26 +2,100 @@
 //!
 //! Inspired by Capstone's printer to maintain compatible output formatting.
 
+use super::decoder::Xlen;
+use super::insn::insn_name;
+use super::shared::formatting::CsrFormatter;
+use super::shared::registers::{RegisterManager, RegisterNameProvider};
 use super::types::*;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 use robustone_core::Instruction;
+use robustone_core::types::options::{
+    AliasMode, HandlerOptions, ImmediateRadix, RegisterNaming, SyntaxStyle,
+};
+use robustone_core::types::slot::DeferredOperands;
+
+/// How Capstone prints a value, used while the radix is left on `Auto`.
+#[derive(Debug, Clone, Copy)]
+enum Auto {
+    /// Decimal up to [`HEX_THRESHOLD`] in magnitude, signed hexadecimal above.
+    Capstone,
+    /// Hexadecimal above [`HEX_THRESHOLD`], decimal otherwise.
+    Upper,
+    /// Always decimal.
+    Decimal,
+    /// These XLEN-wrapped bits, in hexadecimal above [`HEX_THRESHOLD`].
+    Unsigned(u64),
+}
 
 /// Pretty-printer for RISC-V instructions.
+#[derive(Debug, Clone, Copy)]
 pub struct RiscVPrinter {
+    /// Output profile operands are printed in.
+    syntax: SyntaxStyle,
     /// Whether register aliases should be printed instead of canonical names.
     alias_regs: bool,
     /// Whether immediates should be rendered as unsigned values when possible.
     unsigned_immediate: bool,
+    /// Radix immediates are printed in; `Auto` follows the syntax.
+    immediate_radix: ImmediateRadix,
+    /// Register width used to wrap negative immediates in unsigned mode.
+    xlen: Xlen,
 }
 
 impl RiscVPrinter {
     /// Creates a printer with default formatting behaviour.
     pub fn new() -> Self {
         Self {
+            syntax: SyntaxStyle::Capstone,
             alias_regs: false,
             unsigned_immediate: false,
+            immediate_radix: ImmediateRadix::Auto,
+            xlen: Xlen::X64,
         }
     }
 
+    /// Creates a printer that applies handler `options` for an `xlen` target.
+    pub fn from_options(options: &HandlerOptions, xlen: Xlen) -> Self {
+        Self::new()
+            .with_syntax(options.syntax)
+            .with_alias_regs(options.register_naming == RegisterNaming::Alias)
+            .with_unsigned_immediate(options.unsigned_immediates)
+            .with_immediate_radix(options.immediate_radix)
+            .with_xlen(xlen)
+    }
+
+    /// Selects the output profile.
+    ///
+    /// LLVM and GNU syntax print every pseudo-instruction and compressed
+    /// instructions in their expanded form unless aliases are turned off,
+    /// and default to decimal immediates. LLVM syntax also keeps HINT
+    /// encodings compressed and prints unknown CSR numbers in decimal, like
+    /// `llvm-mc`. GNU syntax drops the space after operand commas, prints
+    /// `lui`/`auipc` immediates in hexadecimal and prints branch targets as
+    /// absolute addresses.
+    pub fn with_syntax(mut self, syntax: SyntaxStyle) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// Resolves the alias mode to use when `requested` is configured.
+    pub fn alias_mode(&self, requested: AliasMode) -> AliasMode {
+        match (self.syntax, requested) {
+            (SyntaxStyle::Capstone, mode) => mode,
+            (_, AliasMode::Capstone) => AliasMode::All,
+            (_, mode) => mode,
+        }
+    }
+
+    /// Returns `true` when compressed instructions are printed as the
+    /// 32-bit instruction they expand to under alias `mode`.
+    pub fn expands_compressed(&self, mode: AliasMode) -> bool {
+        self.syntax != SyntaxStyle::Capstone && mode == AliasMode::All
+    }
+
     /// Enables or disables register alias printing.
     pub fn with_alias_regs(mut self, alias_regs: bool) -> Self {
         self.alias_regs = alias_regs;
@@ -34,6 +108,232 @@ impl RiscVPrinter {
         self
     }
 
+    /// Selects the radix used by [`write_operands`](Self::write_operands).
+    pub fn with_immediate_radix(mut self, immediate_radix: ImmediateRadix) -> Self {
+        self.immediate_radix = immediate_radix;
+        self
+    }
+
+    /// Sets the register width used for unsigned immediates.
+    pub fn with_xlen(mut self, xlen: Xlen) -> Self {
+        self.xlen = xlen;
+        self
+    }
+
+    /// Writes `operands`, of the instruction at `address`, as text.
+    ///
+    /// With the default configuration this is Capstone's rendering: ABI
+    /// register names and Capstone's automatic radix.
+    pub fn write_operands<W: fmt::Write + ?Sized>(
+        &self,
+        operands: &[RiscVTextOperand],
+        address: u64,
+        out: &mut W,
+    ) -> fmt::Result {
+        let separator = if self.syntax == SyntaxStyle::Gnu {
+            ","
+        } else {
+            ", "
+        };
+        for (index, operand) in operands.iter().enumerate() {
+            if index > 0 {
+                out.write_str(separator)?;
+            }
+            self.write_operand(*operand, address, out)?;
+        }
+        Ok(())
+    }
+
+    /// Records `operands`, of the instruction at `address`, so a slot
+    /// formats them with this printer when its text is first read.
+    pub fn defer_operands(&self, operands: &[RiscVTextOperand], address: u64) -> DeferredOperands {
+        let mut deferred = DeferredOperands::new(self.style(), address, write_deferred);
+        for &operand in operands {
+            deferred.push(operand.into());
+        }
+        deferred
+    }
+
+    /// Packs the settings into the style word of [`DeferredOperands`].
+    fn style(&self) -> u64 {
+        let syntax = match self.syntax {
+            SyntaxStyle::Llvm => 1,
+            SyntaxStyle::Gnu => 2,
+            _ => 0,
+        };
+        let radix = match self.immediate_radix {
+            ImmediateRadix::Decimal => 1,
+            ImmediateRadix::Hex => 2,
+            _ => 0,
+        };
+        syntax
+            | radix << 2
+            | u64::from(self.alias_regs) << 4
+            | u64::from(self.unsigned_immediate) << 5
+            | u64::from(self.xlen == Xlen::X32) << 6
+    }
+
+    /// Restores the settings packed by [`style`](Self::style).
+    fn from_style(style: u64) -> Self {
+        let syntax = match style & 0b11 {
+            1 => SyntaxStyle::Llvm,
+            2 => SyntaxStyle::Gnu,
+            _ => SyntaxStyle::Capstone,
+        };
+        let radix = match (style >> 2) & 0b11 {
+            1 => ImmediateRadix::Decimal,
+            2 => ImmediateRadix::Hex,
+            _ => ImmediateRadix::Auto,
+        };
+        let xlen = if style & (1 << 6) != 0 {
+            Xlen::X32
+        } else {
+            Xlen::X64
+        };
+        Self::new()
+            .with_syntax(syntax)
+            .with_immediate_radix(radix)
+            .with_alias_regs(style & (1 << 4) != 0)
+            .with_unsigned_immediate(style & (1 << 5) != 0)
+            .with_xlen(xlen)
+    }
+
+    /// Writes a single operand.
+    fn write_operand<W: fmt::Write + ?Sized>(
+        &self,
+        operand: RiscVTextOperand,
+        address: u64,
+        out: &mut W,
+    ) -> fmt::Result {
+        match operand {
+            RiscVTextOperand::Register(reg) => self.write_register(reg, false, out),
+            RiscVTextOperand::FpRegister(reg) => self.write_register(reg, true, out),
+            RiscVTextOperand::Immediate(value) => self.write_immediate(value, Auto::Capstone, out),
+            RiscVTextOperand::UpperImmediate(value) => {
+                self.write_immediate(value, Auto::Upper, out)
+            }
+            RiscVTextOperand::Zimm(value) => {
+                self.write_immediate(i64::from(value), Auto::Decimal, out)
+            }
+            RiscVTextOperand::Memory { base, disp } => {
+                self.write_immediate(disp, Auto::Capstone, out)?;
+                out.write_char('(')?;
+                self.write_register(base, false, out)?;
+                out.write_char(')')
+            }
+            RiscVTextOperand::Address(base) => {
+                out.write_char('(')?;
+                self.write_register(base, false, out)?;
+                out.write_char(')')
+            }
+            RiscVTextOperand::Offset(offset) => {
+                self.write_offset(offset, Auto::Capstone, address, out)
+            }
+            RiscVTextOperand::UnsignedOffset(bits) => {
+                let offset = match self.xlen {
+                    Xlen::X32 => i64::from(bits as u32 as i32),
+                    Xlen::X64 => bits as i64,
+                };
+                self.write_offset(offset, Auto::Unsigned(bits), address, out)
+            }
+            RiscVTextOperand::Csr(csr) => match CsrFormatter::csr_name_lookup(csr) {
+                Some(name) => out.write_str(name),
+                None if self.syntax == SyntaxStyle::Llvm => write!(out, "{csr}"),
+                None => write!(out, "0x{csr:x}"),
+            },
+            RiscVTextOperand::Encoding { value, size } => {
+                write!(out, "0x{value:0width$x}", width = usize::from(size) * 2)
+            }
+        }
+    }
+
+    /// Writes an integer or floating-point register.
+    fn write_register<W: fmt::Write + ?Sized>(
+        &self,
+        reg: u8,
+        fp: bool,
+        out: &mut W,
+    ) -> fmt::Result {
+        let manager = RegisterManager::instance();
+        match (self.alias_regs, fp) {
+            (true, false) => out.write_str(manager.int_register_name(reg)),
+            (true, true) => out.write_str(manager.fp_register_name(reg)),
+            (false, false) => write!(out, "x{reg}"),
+            (false, true) => write!(out, "f{reg}"),
+        }
+    }
+
+    /// Writes a PC-relative `offset`; GNU syntax prints the absolute target
+    /// in hexadecimal instead.
+    fn write_offset<W: fmt::Write + ?Sized>(
+        &self,
+        offset: i64,
+        auto: Auto,
+        address: u64,
+        out: &mut W,
+    ) -> fmt::Result {
+        if self.syntax != SyntaxStyle::Gnu {
+            return self.write_immediate(offset, auto, out);
+        }
+        let target = address.wrapping_add(offset as u64);
+        match self.xlen {
+            Xlen::X32 => write!(out, "{:x}", target as u32),
+            Xlen::X64 => write!(out, "{target:x}"),
+        }
+    }
+
+    /// Writes `value` in the configured radix, or the way Capstone prints
+    /// it (`auto`) when the radix is left on `Auto`.
+    fn write_immediate<W: fmt::Write + ?Sized>(
+        &self,
+        value: i64,
+        auto: Auto,
+        out: &mut W,
+    ) -> fmt::Result {
+        let radix = self.radix(auto);
+        if self.unsigned_immediate && value < 0 {
+            let value = match self.xlen {
+                Xlen::X32 => u64::from(value as u32),
+                Xlen::X64 => value as u64,
+            };
+            return match radix {
+                ImmediateRadix::Decimal => write!(out, "{value}"),
+                _ => write!(out, "0x{value:x}"),
+            };
+        }
+        let magnitude = value.unsigned_abs();
+        let sign = if value < 0 { "-" } else { "" };
+        match (radix, auto) {
+            (ImmediateRadix::Auto, Auto::Capstone) if magnitude > HEX_THRESHOLD as u64 => {
+                write!(out, "{sign}0x{magnitude:x}")
+            }
+            (ImmediateRadix::Auto, Auto::Upper) if value > HEX_THRESHOLD => {
+                write!(out, "0x{value:x}")
+            }
+            (ImmediateRadix::Auto, Auto::Unsigned(bits)) if bits > HEX_THRESHOLD as u64 => {
+                write!(out, "0x{bits:x}")
+            }
+            (ImmediateRadix::Hex, _) if value == 0 => out.write_char('0'),
+            (ImmediateRadix::Hex, _) => write!(out, "{sign}0x{magnitude:x}"),
+            _ => write!(out, "{value}"),
+        }
+    }
+
+    /// Resolves the radix for a value printed as `auto` by Capstone.
+    ///
+    /// LLVM and GNU syntax default to decimal, except that GNU prints
+    /// `lui`/`auipc` immediates in hexadecimal.
+    fn radix(&self, auto: Auto) -> ImmediateRadix {
+        match (self.syntax, self.immediate_radix) {
+            (SyntaxStyle::Capstone, radix) => radix,
+            (SyntaxStyle::Gnu, ImmediateRadix::Auto) if matches!(auto, Auto::Upper) => {
+                ImmediateRadix::Hex
+            }
+            (_, ImmediateRadix::Auto) => ImmediateRadix::Decimal,
+            (_, radix) => radix,
+        }
+    }
+
     /// Formats an immediate according to the active configuration.
     fn format_immediate(&self, imm: i64) -> String {
         if imm > 0xFF {
@@ -49,16 +349,13 @@ impl RiscVPrinter {
 
     /// Formats a register operand.
     fn format_register(&self, reg_id: u32) -> String {
-        let reg = RiscVRegister::from_id(reg_id);
-        if self.alias_regs {
-            reg.name().to_string()
-        } else {
-            // Use the x0-x31 naming scheme when aliases are disabled.
-            if reg_id <= 31 {
-                format!("x{reg_id}")
-            } else {
-                reg.name().to_string()
-            }
+        match RiscVRegisterClass::of(reg_id) {
+            _ if self.alias_regs => RiscVRegister::from_id(reg_id).name().to_string(),
+            // Use the x0-x31 and f0-f31 naming schemes when aliases are
+            // disabled.
+            Some((RiscVRegisterClass::Gpr, num)) => format!("x{num}"),
+            Some((_, num)) => format!("f{num}"),
+            None => RiscVRegister::from_id(reg_id).name().to_string(),
         }
     }
 
@@ -108,41 +405,21 @@ impl RiscVPrinter {
         format!("{} {}", instruction.mnemonic, instruction.operands)
     }
 
-    /// Renders the detailed instruction representation including metadata.
+    /// Renders the summary line followed by cstool's `-d` detail block.
     pub fn print_detailed(&self, instruction: &Instruction) -> String {
-        let mut result = Vec::new();
-
-        // Basic summary line.
-        result.push(format!(
+        let summary = format!(
             "0x{:016x}: {} {}",
             instruction.address,
             hex::encode(&instruction.bytes),
             self.print_basic(instruction)
-        ));
-
-        // Emit detailed sections when available.
-        if let Some(detail) = &instruction.detail {
-            // Register access lists.
-            let regs_read = detail.registers_read();
-            if !regs_read.is_empty() {
-                let regs_read: Vec<String> = regs_read
-                    .iter()
-                    .map(|&reg| format!("{} ({})", self.format_register(reg), reg))
-                    .collect();
-                result.push(format!("\tRegisters read: {}", regs_read.join(", ")));
-            }
-
-            let regs_write = detail.registers_written();
-            if !regs_write.is_empty() {
-                let regs_write: Vec<String> = regs_write
-                    .iter()
-                    .map(|&reg| format!("{} ({})", self.format_register(reg), reg))
-                    .collect();
-                result.push(format!("\tRegisters modified: {}", regs_write.join(", ")));
-            }
+        );
+        match instruction
+            .detail_listing(insn_name(instruction.id))
+            .trim_end()
+        {
+            "" => summary,
+            detail => format!("{summary}\n{detail}"),
         }
-
-        result.join("\n")
     }
 
     /// Formats instruction bytes as a hex string and pads to the requested width.
@@ -163,6 +440,28 @@ impl Default for RiscVPrinter {
     }
 }
 
+/// Formats operands recorded by [`RiscVPrinter::defer_operands`].
+fn write_deferred(deferred: &DeferredOperands, out: &mut dyn fmt::Write) -> fmt::Result {
+    let mut operands = RiscVTextOperands::new();
+    for &operand in deferred.operands() {
+        operands.push(operand.into());
+    }
+    RiscVPrinter::from_style(deferred.style).write_operands(
+        operands.as_slice(),
+        deferred.address,
+        out,
+    )
+}
+
+impl fmt::Display for RiscVTextOperands {
+    /// Formats the operands the way Capstone prints them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        RiscVPrinter::new()
+            .with_alias_regs(true)
+            .write_operands(self.as_slice(), 0, f)
+    }
+}
+
 /// Helper functions for common RISC-V printing scenarios.
 pub mod format {
     use super::*;
@@ -224,6 +523,57 @@ mod tests {
         assert!(printer.unsigned_immediate);
     }
 
+    #[test]
+    fn test_syntax_profiles() {
+        use RiscVTextOperand::*;
+
+        let print = |syntax, operands: &[RiscVTextOperand], address| {
+            let mut out = String::new();
+            RiscVPrinter::new()
+                .with_alias_regs(true)
+                .with_syntax(syntax)
+                .write_operands(operands, address, &mut out)
+                .unwrap();
+            out
+        };
+
+        let lui = [Register(10), UpperImmediate(0x12)];
+        assert_eq!(print(SyntaxStyle::Capstone, &lui, 0), "a0, 0x12");
+        assert_eq!(print(SyntaxStyle::Llvm, &lui, 0), "a0, 18");
+        assert_eq!(print(SyntaxStyle::Gnu, &lui, 0), "a0,0x12");
+        let ld = [
+            Register(1),
+            Memory {
+                base: 2,
+                disp: 0x18,
+            },
+        ];
+        assert_eq!(print(SyntaxStyle::Gnu, &ld, 0), "ra,24(sp)");
+        let beqz = [Register(10), UnsignedOffset(-8i64 as u64)];
+        assert_eq!(
+            print(SyntaxStyle::Capstone, &beqz, 0x1000),
+            "a0, 0xfffffffffffffff8"
+        );
+        assert_eq!(print(SyntaxStyle::Llvm, &beqz, 0x1000), "a0, -8");
+        assert_eq!(print(SyntaxStyle::Gnu, &beqz, 0x1000), "a0,ff8");
+        assert_eq!(print(SyntaxStyle::Gnu, &[Offset(0x20)], 0x1000), "1020");
+        // Unknown CSRs are decimal only in LLVM syntax; zimm is always decimal.
+        assert_eq!(
+            print(SyntaxStyle::Llvm, &[Csr(0x999), Zimm(31)], 0),
+            "2457, 31"
+        );
+        assert_eq!(
+            print(SyntaxStyle::Gnu, &[Csr(0x999), Zimm(31)], 0),
+            "0x999,31"
+        );
+
+        let gnu = RiscVPrinter::new().with_syntax(SyntaxStyle::Gnu);
+        assert_eq!(gnu.alias_mode(AliasMode::Capstone), AliasMode::All);
+        assert_eq!(gnu.alias_mode(AliasMode::None), AliasMode::None);
+        assert!(gnu.expands_compressed(AliasMode::All));
+        assert!(!RiscVPrinter::new().expands_compressed(AliasMode::All));
+    }
+
     #[test]
     fn test_format_immediate() {
         let printer = RiscVPrinter::new();
@@ -298,4 +648,112 @@ mod tests {
         };
         assert_eq!(printer.format_operand(&mem_op), "100(sp)");
     }
+
+    fn print(printer: RiscVPrinter, operands: &[RiscVTextOperand]) -> String {
+        let mut out = String::new();
+        printer.write_operands(operands, 0, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_write_operands() {
+        use RiscVTextOperand::*;
+
+        let sp_load = [
+            Register(10),
+            Memory {
+                base: 2,
+                disp: -0x10,
+            },
+        ];
+        let aliases = RiscVPrinter::new().with_alias_regs(true);
+        assert_eq!(print(aliases, &sp_load), "a0, -0x10(sp)");
+
+        let numeric = RiscVPrinter::new();
+        assert_eq!(
+            print(numeric, &[Register(1), Register(0), Immediate(1)]),
+            "x1, x0, 1"
+        );
+        assert_eq!(
+            print(
+                numeric,
+                &[FpRegister(10), FpRegister(1), FpRegister(18), FpRegister(0)]
+            ),
+            "f10, f1, f18, f0"
+        );
+        assert_eq!(print(numeric, &[Register(10), Csr(1)]), "x10, fflags");
+
+        let decimal = aliases.with_immediate_radix(ImmediateRadix::Decimal);
+        assert_eq!(print(decimal, &sp_load), "a0, -16(sp)");
+        assert_eq!(
+            print(decimal, &[Register(10), UpperImmediate(0x12345)]),
+            "a0, 74565"
+        );
+
+        let hex = aliases.with_immediate_radix(ImmediateRadix::Hex);
+        assert_eq!(
+            print(hex, &[Register(10), Register(11), Immediate(-1)]),
+            "a0, a1, -0x1"
+        );
+        assert_eq!(
+            print(hex, &[Register(10), Register(0), Immediate(0)]),
+            "a0, zero, 0"
+        );
+        assert_eq!(
+            print(
+                hex,
+                &[Encoding {
+                    value: 0x7f,
+                    size: 4
+                }]
+            ),
+            "0x0000007f"
+        );
+    }
+
+    #[test]
+    fn test_unsigned_immediates() {
+        use RiscVTextOperand::*;
+
+        let printer = RiscVPrinter::new()
+            .with_alias_regs(true)
+            .with_unsigned_immediate(true);
+        assert_eq!(
+            print(printer, &[Register(10), Register(11), Immediate(-1)]),
+            "a0, a1, 0xffffffffffffffff"
+        );
+        assert_eq!(
+            print(
+                printer.with_xlen(Xlen::X32),
+                &[
+                    Register(10),
+                    Memory {
+                        base: 2,
+                        disp: -0x10
+                    }
+                ]
+            ),
+            "a0, 0xfffffff0(sp)"
+        );
+        assert_eq!(
+            print(printer, &[Register(10), Register(11), Immediate(5)]),
+            "a0, a1, 5"
+        );
+    }
+
+    #[test]
+    fn test_capstone_text() {
+        use RiscVTextOperand::*;
+
+        let operands = RiscVTextOperands::from([Register(10), Memory { base: 2, disp: 10 }]);
+        assert_eq!(operands.to_string(), "a0, 0xa(sp)");
+        assert_eq!(
+            RiscVTextOperands::from([UnsignedOffset(0xffff_fff8)]).to_string(),
+            "0xfffffff8"
+        );
+        assert_eq!(
+            RiscVTextOperands::from([Register(1), UpperImmediate(-1)]).to_string(),
+            "ra, -1"
+        );
+    }
 }
@@ -5,6 +5,8 @@
 
 use super::super::decoder::{RiscVDecodedInstruction, Xlen};
 use super::super::types::*;
+use alloc::format;
+use alloc::string::{String, ToString};
 
 /// Trait for formatting decoded RISC-V instructions.
 #[allow(clippy::too_many_arguments)]
@@ -12,17 +14,17 @@ pub trait InstructionFormatter {
     /// Create a decoded instruction with the given parameters.
     fn create_decoded_instruction(
         &self,
-        mnemonic: &str,
-        operands: String,
+        mnemonic: &'static str,
+        operands: RiscVTextOperands,
         format: RiscVInstructionFormat,
         size: usize,
-        operands_detail: Vec<RiscVOperand>,
+        operands_detail: RiscVOperands,
     ) -> RiscVDecodedInstruction;
 
     /// Create a decoded instruction using the operand builder.
     fn create_instruction_from_parts(
         &self,
-        _mnemonic: &str,
+        _mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
@@ -54,19 +56,21 @@ pub trait ImmediateFormatter {
 /// Default implementation of instruction formatter.
 pub struct DefaultInstructionFormatter {
     xlen: Option<Xlen>,
+    /// Whether decoded instructions carry structured operands.
+    detail: bool,
 }
 
 impl InstructionFormatter for DefaultInstructionFormatter {
     fn create_decoded_instruction(
         &self,
-        mnemonic: &str,
-        operands: String,
+        mnemonic: &'static str,
+        operands: RiscVTextOperands,
         format: RiscVInstructionFormat,
         size: usize,
-        operands_detail: Vec<RiscVOperand>,
+        operands_detail: RiscVOperands,
     ) -> RiscVDecodedInstruction {
         RiscVDecodedInstruction {
-            mnemonic: mnemonic.to_string(),
+            mnemonic,
             operands,
             format,
             size,
@@ -77,7 +81,7 @@ impl InstructionFormatter for DefaultInstructionFormatter {
     #[allow(clippy::too_many_arguments)]
     fn create_instruction_from_parts(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
@@ -96,12 +100,24 @@ impl InstructionFormatter for DefaultInstructionFormatter {
 impl DefaultInstructionFormatter {
     /// Create a new default instruction formatter.
     pub const fn new() -> Self {
-        Self { xlen: None }
+        Self {
+            xlen: None,
+            detail: true,
+        }
     }
 
     /// Create a new default instruction formatter with XLEN.
     pub const fn with_xlen(xlen: Xlen) -> Self {
-        Self { xlen: Some(xlen) }
+        Self {
+            xlen: Some(xlen),
+            detail: true,
+        }
+    }
+
+    /// Enables or disables building structured operands.
+    pub const fn with_detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
     }
 
     /// Get the global default instruction formatter instance.
@@ -112,7 +128,7 @@ impl DefaultInstructionFormatter {
     #[allow(clippy::too_many_arguments)]
     fn create_instruction_from_parts(
         &self,
-        mnemonic: &str,
+        mnemonic: &'static str,
         rd: u8,
         rs1: u8,
         rs2: u8,
@@ -122,119 +138,113 @@ impl DefaultInstructionFormatter {
         rs1_access: Access,
         rs2_access: Access,
     ) -> RiscVDecodedInstruction {
-        use super::operands::convenience;
-        use super::registers::get_register_name;
+        use super::operands::{convenience, detail_operands, unsigned_offset};
+        use RiscVTextOperand::{Memory, Register, UpperImmediate};
 
+        let offset = unsigned_offset(imm, self.xlen);
         let (operands, operands_detail) = match format {
-            RiscVInstructionFormat::R => {
-                let ops = format!(
-                    "{}, {}, {}",
-                    get_register_name(rd),
-                    get_register_name(rs1),
-                    get_register_name(rs2)
-                );
-                let details = vec![
-                    convenience::register(rd, rd_access),
-                    convenience::register(rs1, rs1_access),
-                    convenience::register(rs2, rs2_access),
-                ];
-                (ops, details)
-            }
-            RiscVInstructionFormat::I => {
-                let (ops, details) = if mnemonic == "jalr" {
-                    (
-                        format!(
-                            "{}, {}({})",
-                            get_register_name(rd),
-                            self.format_immediate_auto(imm),
-                            get_register_name(rs1)
-                        ),
-                        vec![
-                            convenience::register(rd, rd_access),
-                            convenience::memory(rs1, imm),
-                        ],
-                    )
-                } else {
-                    (
-                        format!(
-                            "{}, {}, {}",
-                            get_register_name(rd),
-                            get_register_name(rs1),
-                            self.format_immediate_auto(imm)
-                        ),
-                        vec![
-                            convenience::register(rd, rd_access),
-                            convenience::register(rs1, rs1_access),
-                            convenience::immediate(imm),
-                        ],
-                    )
-                };
-                (ops, details)
-            }
-            RiscVInstructionFormat::S => {
-                let ops = format!(
-                    "{}, {}({})",
-                    get_register_name(rs2),
-                    self.format_immediate_auto(imm),
-                    get_register_name(rs1)
-                );
-                let details = vec![
-                    convenience::register(rs2, rs2_access),
-                    convenience::memory(rs1, imm),
-                ];
-                (ops, details)
-            }
+            RiscVInstructionFormat::R => (
+                RiscVTextOperands::from([Register(rd), Register(rs1), Register(rs2)]),
+                detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rd, rd_access),
+                        convenience::register(rs1, rs1_access),
+                        convenience::register(rs2, rs2_access),
+                    ],
+                ),
+            ),
+            RiscVInstructionFormat::I if mnemonic == "jalr" => (
+                RiscVTextOperands::from([
+                    Register(rd),
+                    Memory {
+                        base: rs1,
+                        disp: imm,
+                    },
+                ]),
+                detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rd, rd_access),
+                        convenience::memory(rs1, imm),
+                    ],
+                ),
+            ),
+            RiscVInstructionFormat::I => (
+                RiscVTextOperands::from([
+                    Register(rd),
+                    Register(rs1),
+                    RiscVTextOperand::Immediate(imm),
+                ]),
+                detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rd, rd_access),
+                        convenience::register(rs1, rs1_access),
+                        convenience::immediate(imm),
+                    ],
+                ),
+            ),
+            RiscVInstructionFormat::S => (
+                RiscVTextOperands::from([
+                    Register(rs2),
+                    Memory {
+                        base: rs1,
+                        disp: imm,
+                    },
+                ]),
+                detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rs2, rs2_access),
+                        convenience::memory(rs1, imm),
+                    ],
+                ),
+            ),
             RiscVInstructionFormat::B => {
-                let offset_str = self.format_control_offset(imm);
-                let ops = if rs2 == 0 && (mnemonic == "beqz" || mnemonic == "bnez") {
-                    format!("{}, {}", get_register_name(rs1), offset_str)
+                let operands = if rs2 == 0 && (mnemonic == "beqz" || mnemonic == "bnez") {
+                    RiscVTextOperands::from([Register(rs1), offset])
                 } else {
-                    format!(
-                        "{}, {}, {}",
-                        get_register_name(rs1),
-                        get_register_name(rs2),
-                        offset_str
-                    )
+                    RiscVTextOperands::from([Register(rs1), Register(rs2), offset])
                 };
-                let details = vec![
-                    convenience::register(rs1, rs1_access),
-                    convenience::register(rs2, rs2_access),
-                    convenience::immediate(imm),
-                ];
-                (ops, details)
+                let details = detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rs1, rs1_access),
+                        convenience::register(rs2, rs2_access),
+                        convenience::immediate(imm),
+                    ],
+                );
+                (operands, details)
             }
             RiscVInstructionFormat::U => {
                 let imm_val = imm >> 12;
-                let imm_str = if imm_val == 0 {
-                    "0".to_string()
-                } else {
-                    format!("0x{imm_val:x}")
-                };
-                let ops = format!("{}, {}", get_register_name(rd), imm_str);
-                let details = vec![
-                    convenience::register(rd, rd_access),
-                    convenience::immediate(imm_val),
-                ];
-                (ops, details)
+                (
+                    RiscVTextOperands::from([Register(rd), UpperImmediate(imm_val)]),
+                    detail_operands(
+                        self.detail,
+                        [
+                            convenience::register(rd, rd_access),
+                            convenience::immediate(imm_val),
+                        ],
+                    ),
+                )
             }
             RiscVInstructionFormat::J => {
-                let offset_str = self.format_control_offset(imm);
-                let ops = match (mnemonic, rd) {
-                    ("j", _) => offset_str,
-                    ("jal", 1) => offset_str,
-                    _ => format!("{}, {}", get_register_name(rd), offset_str),
+                let operands = match (mnemonic, rd) {
+                    ("j", _) | ("jal", 1) => RiscVTextOperands::from([offset]),
+                    _ => RiscVTextOperands::from([Register(rd), offset]),
                 };
-                let details = vec![
-                    convenience::register(rd, rd_access),
-                    convenience::immediate(imm),
-                ];
-                (ops, details)
-            }
-            _ => {
-                let ops = "unknown format".to_string();
-                let details = vec![];
-                (ops, details)
+                let details = detail_operands(
+                    self.detail,
+                    [
+                        convenience::register(rd, rd_access),
+                        convenience::immediate(imm),
+                    ],
+                );
+                (operands, details)
             }
+            _ => (RiscVTextOperands::new(), RiscVOperands::new()),
         };
 
         self.create_decoded_instruction(mnemonic, operands, format, 4, operands_detail)
@@ -287,30 +297,39 @@ impl ImmediateFormatter for DefaultInstructionFormatter {
 }
 
 impl DefaultInstructionFormatter {
-    /// Create a simple decoded instruction with just mnemonic and operands.
-    pub fn simple_instruction(mnemonic: &str, operands: &str) -> RiscVDecodedInstruction {
+    /// Create a simple decoded instruction without operands.
+    pub fn simple_instruction(mnemonic: &'static str) -> RiscVDecodedInstruction {
         Self::new().create_decoded_instruction(
             mnemonic,
-            operands.to_string(),
+            RiscVTextOperands::new(),
             RiscVInstructionFormat::I,
             4,
-            vec![],
+            RiscVOperands::new(),
         )
     }
 
     /// Create an unknown instruction placeholder.
     pub fn unknown_instruction(value: u32) -> RiscVDecodedInstruction {
-        Self::simple_instruction("unknown", &format!("0x{value:08x}"))
+        Self::new().create_decoded_instruction(
+            "unknown",
+            RiscVTextOperands::from([RiscVTextOperand::Encoding { value, size: 4 }]),
+            RiscVInstructionFormat::I,
+            4,
+            RiscVOperands::new(),
+        )
     }
 
     /// Create an unknown compressed instruction placeholder.
     pub fn unknown_compressed_instruction(value: u16) -> RiscVDecodedInstruction {
         Self::new().create_decoded_instruction(
             "c.unknown",
-            format!("0x{value:04x}"),
+            RiscVTextOperands::from([RiscVTextOperand::Encoding {
+                value: u32::from(value),
+                size: 2,
+            }]),
             RiscVInstructionFormat::CI,
             2,
-            vec![],
+            RiscVOperands::new(),
         )
     }
 }
@@ -515,8 +534,8 @@ pub mod convenience {
     }
 
     /// Create a simple decoded instruction.
-    pub fn simple_instruction(mnemonic: &str, operands: &str) -> RiscVDecodedInstruction {
-        DefaultInstructionFormatter::simple_instruction(mnemonic, operands)
+    pub fn simple_instruction(mnemonic: &'static str) -> RiscVDecodedInstruction {
+        DefaultInstructionFormatter::simple_instruction(mnemonic)
     }
 
     /// Create an unknown instruction.
@@ -540,14 +559,18 @@ mod tests {
 
         let instruction = formatter.create_decoded_instruction(
             "add",
-            "x1, x2, x3".to_string(),
+            RiscVTextOperands::from([
+                RiscVTextOperand::Register(1),
+                RiscVTextOperand::Register(2),
+                RiscVTextOperand::Register(3),
+            ]),
             RiscVInstructionFormat::R,
             4,
-            vec![],
+            RiscVOperands::new(),
         );
 
         assert_eq!(instruction.mnemonic, "add");
-        assert_eq!(instruction.operands, "x1, x2, x3");
+        assert_eq!(instruction.operands.to_string(), "ra, sp, gp");
         assert_eq!(instruction.format, RiscVInstructionFormat::R);
         assert_eq!(instruction.size, 4);
     }
@@ -602,6 +625,6 @@ mod tests {
 
         let unknown = convenience::unknown_instruction(0x12345678);
         assert_eq!(unknown.mnemonic, "unknown");
-        assert_eq!(unknown.operands, "0x12345678");
+        assert_eq!(unknown.operands.to_string(), "0x12345678");
     }
 }
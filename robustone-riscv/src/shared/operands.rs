@@ -5,6 +5,9 @@
 
 use super::super::types::*;
 use crate::decoder::Xlen;
+use RiscVTextOperand::{FpRegister, Immediate, Memory, Register, UpperImmediate};
+use alloc::format;
+use alloc::string::{String, ToString};
 
 /// Trait for creating RISC-V operands in extensions.
 pub trait OperandFactory {
@@ -197,121 +200,103 @@ impl OperandBuilder {
         Self::new()
     }
 
-    /// Format instruction operands for common instruction formats.
-    pub fn format_r_type(&self, _mnemonic: &str, rd: u8, rs1: u8, rs2: u8) -> String {
-        format!(
-            "{}, {}, {}",
-            super::registers::get_register_name(rd),
-            super::registers::get_register_name(rs1),
-            super::registers::get_register_name(rs2)
-        )
+    /// Branch or jump offset as printed for this builder's XLEN.
+    fn offset(&self, imm: i64) -> RiscVTextOperand {
+        unsigned_offset(imm, self.factory.xlen)
     }
 
-    pub fn format_i_type(&self, mnemonic: &str, rd: u8, rs1: u8, imm: i64) -> String {
+    /// Operands of an R-type instruction.
+    pub fn r_type(&self, _mnemonic: &str, rd: u8, rs1: u8, rs2: u8) -> RiscVTextOperands {
+        RiscVTextOperands::from([Register(rd), Register(rs1), Register(rs2)])
+    }
+
+    /// Operands of an I-type instruction; `jalr` prints only `imm(rs1)`.
+    pub fn i_type(&self, mnemonic: &str, rd: u8, rs1: u8, imm: i64) -> RiscVTextOperands {
         if mnemonic == "jalr" {
-            format!(
-                "{}({})",
-                self.factory.format_immediate(imm),
-                super::registers::get_register_name(rs1)
-            )
+            RiscVTextOperands::from([Memory {
+                base: rs1,
+                disp: imm,
+            }])
         } else {
-            format!(
-                "{}, {}, {}",
-                super::registers::get_register_name(rd),
-                super::registers::get_register_name(rs1),
-                self.factory.format_immediate(imm)
-            )
+            RiscVTextOperands::from([Register(rd), Register(rs1), Immediate(imm)])
         }
     }
 
-    pub fn format_s_type(&self, _mnemonic: &str, rs2: u8, rs1: u8, imm: i64) -> String {
-        format!(
-            "{}, {}({})",
-            super::registers::get_register_name(rs2),
-            self.factory.format_immediate(imm),
-            super::registers::get_register_name(rs1)
-        )
+    /// Operands of an S-type instruction.
+    pub fn s_type(&self, _mnemonic: &str, rs2: u8, rs1: u8, imm: i64) -> RiscVTextOperands {
+        RiscVTextOperands::from([
+            Register(rs2),
+            Memory {
+                base: rs1,
+                disp: imm,
+            },
+        ])
     }
 
-    pub fn format_b_type(&self, mnemonic: &str, rs1: u8, rs2: u8, imm: i64) -> String {
-        let offset_str = self.factory.format_control_offset(imm);
+    /// Operands of a B-type instruction; `beqz`/`bnez` omit the zero register.
+    pub fn b_type(&self, mnemonic: &str, rs1: u8, rs2: u8, imm: i64) -> RiscVTextOperands {
+        let offset = self.offset(imm);
         if (mnemonic == "beqz" || mnemonic == "bnez") && rs2 == 0 {
-            format!(
-                "{}, {}",
-                super::registers::get_register_name(rs1),
-                offset_str
-            )
+            RiscVTextOperands::from([Register(rs1), offset])
         } else {
-            format!(
-                "{}, {}, {}",
-                super::registers::get_register_name(rs1),
-                super::registers::get_register_name(rs2),
-                offset_str
-            )
+            RiscVTextOperands::from([Register(rs1), Register(rs2), offset])
         }
     }
 
-    pub fn format_u_type(&self, _mnemonic: &str, rd: u8, imm: i64) -> String {
-        let imm_val = imm >> 12;
-        let imm_str = if imm_val == 0 {
-            "0".to_string()
-        } else if imm_val > HEX_THRESHOLD {
-            format!("0x{imm_val:x}")
-        } else {
-            imm_val.to_string()
-        };
-        format!("{}, {}", super::registers::get_register_name(rd), imm_str)
+    /// Operands of a U-type instruction, with the immediate shifted down.
+    pub fn u_type(&self, _mnemonic: &str, rd: u8, imm: i64) -> RiscVTextOperands {
+        RiscVTextOperands::from([Register(rd), UpperImmediate(imm >> 12)])
     }
 
-    pub fn format_j_type(&self, mnemonic: &str, rd: u8, imm: i64) -> String {
-        let imm_str = self.factory.format_control_offset(imm);
+    /// Operands of a J-type instruction; `j` and `jal ra` omit the link register.
+    pub fn j_type(&self, mnemonic: &str, rd: u8, imm: i64) -> RiscVTextOperands {
+        let offset = self.offset(imm);
         match (mnemonic, rd) {
-            ("j", _) => imm_str,
-            ("jal", 1) => imm_str,
-            _ => format!("{}, {}", super::registers::get_register_name(rd), imm_str),
+            ("j", _) | ("jal", 1) => RiscVTextOperands::from([offset]),
+            _ => RiscVTextOperands::from([Register(rd), offset]),
         }
     }
 
-    pub fn format_load_type(
+    /// Operands of a load, `rd, imm(rs1)`.
+    pub fn load_type(
         &self,
         _mnemonic: &str,
         rd: u8,
         rs1: u8,
         imm: i64,
         is_fp: bool,
-    ) -> String {
-        let rd_name = if is_fp {
-            super::registers::get_fp_register_name(rd)
-        } else {
-            super::registers::get_register_name(rd)
-        };
-        format!(
-            "{}, {}({})",
-            rd_name,
-            self.factory.format_immediate(imm),
-            super::registers::get_register_name(rs1)
-        )
-    }
-
-    pub fn format_store_type(
+    ) -> RiscVTextOperands {
+        let rd = if is_fp { FpRegister(rd) } else { Register(rd) };
+        RiscVTextOperands::from([
+            rd,
+            Memory {
+                base: rs1,
+                disp: imm,
+            },
+        ])
+    }
+
+    /// Operands of a store, `rs2, imm(rs1)`.
+    pub fn store_type(
         &self,
         _mnemonic: &str,
         rs2: u8,
         rs1: u8,
         imm: i64,
         is_fp: bool,
-    ) -> String {
-        let rs2_name = if is_fp {
-            super::registers::get_fp_register_name(rs2)
+    ) -> RiscVTextOperands {
+        let rs2 = if is_fp {
+            FpRegister(rs2)
         } else {
-            super::registers::get_register_name(rs2)
+            Register(rs2)
         };
-        format!(
-            "{}, {}({})",
-            rs2_name,
-            self.factory.format_immediate(imm),
-            super::registers::get_register_name(rs1)
-        )
+        RiscVTextOperands::from([
+            rs2,
+            Memory {
+                base: rs1,
+                disp: imm,
+            },
+        ])
     }
 }
 
@@ -374,6 +359,29 @@ fn csr_name_lookup(csr: u16) -> Option<&'static str> {
     }
 }
 
+/// Branch or jump offset operand, wrapped to `xlen` (64 bits when unknown)
+/// the way Capstone prints negative 32-bit control offsets.
+pub fn unsigned_offset(imm: i64, xlen: Option<Xlen>) -> RiscVTextOperand {
+    let bits = match xlen {
+        Some(Xlen::X32) => u64::from(imm as u32),
+        Some(Xlen::X64) | None => imm as u64,
+    };
+    RiscVTextOperand::UnsignedOffset(bits)
+}
+
+/// Collects the structured operands of an instruction.
+///
+/// Returns an empty list when `detail` is off, so text-only decoding skips
+/// building the operands.
+#[inline]
+pub fn detail_operands<const N: usize>(detail: bool, operands: [RiscVOperand; N]) -> RiscVOperands {
+    if detail {
+        RiscVOperands::from(operands)
+    } else {
+        RiscVOperands::new()
+    }
+}
+
 /// Convenience functions for operand creation.
 pub mod convenience {
     use super::*;
@@ -383,6 +391,16 @@ pub mod convenience {
         DefaultOperandFactory::register(reg, access)
     }
 
+    /// Create a register operand of `class`, such as a floating-point
+    /// register.
+    pub fn class_register(class: RiscVRegisterClass, reg: u8, access: Access) -> RiscVOperand {
+        RiscVOperand {
+            op_type: RiscVOperandType::Register,
+            access,
+            value: RiscVOperandValue::Register(class.id(reg)),
+        }
+    }
+
     /// Create an immediate operand.
     pub fn immediate(imm: i64) -> RiscVOperand {
         DefaultOperandFactory::immediate(imm)
@@ -447,11 +465,18 @@ mod tests {
     fn test_operand_builder() {
         let builder = OperandBuilder::new();
 
-        assert_eq!(builder.format_r_type("add", 1, 2, 3), "ra, sp, gp");
-        assert_eq!(builder.format_i_type("addi", 1, 2, 10), "ra, sp, 0xa");
-        assert_eq!(builder.format_s_type("sw", 3, 4, 16), "gp, 0x10(tp)");
-        assert_eq!(builder.format_u_type("lui", 5, 0x1000), "t0, 1");
-        assert_eq!(builder.format_j_type("jal", 1, 100), "0x64");
+        assert_eq!(builder.r_type("add", 1, 2, 3).to_string(), "ra, sp, gp");
+        assert_eq!(builder.i_type("addi", 1, 2, 10).to_string(), "ra, sp, 0xa");
+        assert_eq!(builder.s_type("sw", 3, 4, 16).to_string(), "gp, 0x10(tp)");
+        assert_eq!(builder.u_type("lui", 5, 0x1000).to_string(), "t0, 1");
+        assert_eq!(builder.j_type("jal", 1, 100).to_string(), "0x64");
+
+        let rv32 = OperandBuilder::with_xlen(Xlen::X32);
+        assert_eq!(rv32.b_type("bnez", 10, 0, -8).to_string(), "a0, 0xfffffff8");
+        assert_eq!(
+            builder.b_type("bnez", 10, 0, -8).to_string(),
+            "a0, 0xfffffffffffffff8"
+        );
     }
 
     #[test]
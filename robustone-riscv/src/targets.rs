@@ -0,0 +1,176 @@
+//! Addresses computed by PC-relative instructions.
+//!
+//! These work on raw 32-bit instruction words (expand compressed encodings
+//! with [`compressed::expand`](crate::compressed::expand) first), so they
+//! are available whether or not instruction detail is enabled.
+
+use crate::decoder::Xlen;
+use crate::shared::encoding::convenience::{
+    extract_b_type, extract_i_type, extract_j_type, extract_s_type,
+};
+
+pub(crate) const LOAD: u32 = 0x03;
+pub(crate) const LOAD_FP: u32 = 0x07;
+pub(crate) const OP_IMM: u32 = 0x13;
+pub(crate) const AUIPC: u32 = 0x17;
+pub(crate) const OP_IMM_32: u32 = 0x1b;
+pub(crate) const STORE: u32 = 0x23;
+pub(crate) const STORE_FP: u32 = 0x27;
+pub(crate) const LUI: u32 = 0x37;
+pub(crate) const BRANCH: u32 = 0x63;
+pub(crate) const JALR: u32 = 0x67;
+pub(crate) const JAL: u32 = 0x6f;
+
+const MRET: u32 = 0x3020_0073;
+const SRET: u32 = 0x1020_0073;
+/// `unimp`, the canonical illegal instruction (`csrrw zero, cycle, zero`).
+const UNIMP: u32 = 0xc000_1073;
+
+/// How control leaves an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Continues with the next instruction.
+    Next,
+    /// Conditional branch to the target; otherwise continues.
+    Branch(u64),
+    /// `jal zero`: always goes to the target.
+    Jump(u64),
+    /// `jal` linking a register: calls the target, then continues.
+    Call(u64),
+    /// `jalr zero, 0(ra)`: returns to the caller.
+    Return,
+    /// Any other `jalr zero`: goes to a computed address.
+    Indirect,
+    /// `jalr` linking a register: calls a computed address, then continues.
+    IndirectCall,
+    /// Never continues: `mret`, `sret` and `unimp`.
+    Stop,
+}
+
+/// Returns how control leaves the instruction `word` at `address`.
+pub fn control_flow(word: u32, address: u64, xlen: Xlen) -> Flow {
+    let rd = (word >> 7) & 0x1f;
+    match word & 0x7f {
+        BRANCH | JAL => {
+            let target = branch_target(word, address, xlen).unwrap_or(address);
+            match (word & 0x7f, rd) {
+                (BRANCH, _) => Flow::Branch(target),
+                (_, 0) => Flow::Jump(target),
+                _ => Flow::Call(target),
+            }
+        }
+        JALR => {
+            let fields = extract_i_type(word);
+            match (rd, fields.rs1, fields.imm) {
+                (0, 1, 0) => Flow::Return,
+                (0, _, _) => Flow::Indirect,
+                _ => Flow::IndirectCall,
+            }
+        }
+        _ if matches!(word, MRET | SRET | UNIMP) => Flow::Stop,
+        _ => Flow::Next,
+    }
+}
+
+/// Returns the target of a conditional branch or `jal` at `address`.
+pub fn branch_target(word: u32, address: u64, xlen: Xlen) -> Option<u64> {
+    let offset = match word & 0x7f {
+        BRANCH => extract_b_type(word).imm,
+        JAL => extract_j_type(word).imm,
+        _ => return None,
+    };
+    Some(wrap(address.wrapping_add(offset as u64), xlen))
+}
+
+/// Returns the address formed by an `auipc` at `address` together with the
+/// instruction `next` that completes it, such as `addi`, a load, a store or
+/// `jalr` based on the `auipc` destination register.
+pub fn auipc_pair_target(auipc: u32, next: u32, address: u64, xlen: Xlen) -> Option<u64> {
+    if auipc & 0x7f != AUIPC {
+        return None;
+    }
+    let rd = (auipc >> 7) & 0x1f;
+    let (base, low) = match next & 0x7f {
+        OP_IMM if (next >> 12) & 0x7 == 0 => {
+            let fields = extract_i_type(next);
+            (fields.rs1, fields.imm)
+        }
+        LOAD | LOAD_FP | JALR => {
+            let fields = extract_i_type(next);
+            (fields.rs1, fields.imm)
+        }
+        STORE | STORE_FP => {
+            let fields = extract_s_type(next);
+            (fields.rs1, fields.imm)
+        }
+        _ => return None,
+    };
+    if rd == 0 || u32::from(base) != rd {
+        return None;
+    }
+    let high = i64::from((auipc & 0xffff_f000) as i32);
+    Some(wrap(
+        address.wrapping_add(high.wrapping_add(low) as u64),
+        xlen,
+    ))
+}
+
+/// Truncates `address` to the register width.
+pub(crate) fn wrap(address: u64, xlen: Xlen) -> u64 {
+    match xlen {
+        Xlen::X32 => u64::from(address as u32),
+        Xlen::X64 => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_targets() {
+        // beq a0, zero, 8 ; jal ra, -4 ; addi a0, a0, 1
+        assert_eq!(branch_target(0x0005_0463, 0x1000, Xlen::X64), Some(0x1008));
+        assert_eq!(branch_target(0xffdf_f0ef, 0x1000, Xlen::X64), Some(0xffc));
+        assert_eq!(branch_target(0x0015_0513, 0x1000, Xlen::X64), None);
+        assert_eq!(branch_target(0xffdf_f0ef, 0, Xlen::X32), Some(0xffff_fffc));
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let flow = |word| control_flow(word, 0x1000, Xlen::X64);
+        // beq a0, zero, 8 ; j -8 ; jal ra, -4 ; ret ; jr t0 ; jalr t0, 0(t0) ; mret ;
+        // addi a0, a0, 1
+        assert_eq!(flow(0x0005_0463), Flow::Branch(0x1008));
+        assert_eq!(flow(0xff9f_f06f), Flow::Jump(0xff8));
+        assert_eq!(flow(0xffdf_f0ef), Flow::Call(0xffc));
+        assert_eq!(flow(0x0000_8067), Flow::Return);
+        assert_eq!(flow(0x0002_8067), Flow::Indirect);
+        assert_eq!(flow(0x0002_82e7), Flow::IndirectCall);
+        assert_eq!(flow(0x3020_0073), Flow::Stop);
+        assert_eq!(flow(0x0015_0513), Flow::Next);
+    }
+
+    #[test]
+    fn test_auipc_pairs() {
+        // auipc a0, 0x1 followed by addi a0, a0, 64 / ld a1, -8(a0) / sd a1, 8(a0)
+        let auipc = 0x0000_1517;
+        assert_eq!(
+            auipc_pair_target(auipc, 0x0405_0513, 0x1000, Xlen::X64),
+            Some(0x2040)
+        );
+        assert_eq!(
+            auipc_pair_target(auipc, 0xff85_3583, 0x1000, Xlen::X64),
+            Some(0x1ff8)
+        );
+        assert_eq!(
+            auipc_pair_target(auipc, 0x00b5_3423, 0x1000, Xlen::X64),
+            Some(0x2008)
+        );
+        // addi a1, a1, 64 does not use the auipc result.
+        assert_eq!(
+            auipc_pair_target(auipc, 0x0405_8593, 0x1000, Xlen::X64),
+            None
+        );
+    }
+}
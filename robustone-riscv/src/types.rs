@@ -2,6 +2,10 @@
 //!
 //! These structures mirror Capstone's RISC-V bindings to ease interoperability.
 
+use alloc::string::String;
+use alloc::vec::Vec;
+use robustone_core::types::slot::SlotOperand;
+
 /// Kinds of operands that can appear in a RISC-V instruction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RiscVOperandType {
@@ -25,7 +29,7 @@ pub struct RiscVMemoryOperand {
 }
 
 /// Fully described operand (aligned with `cs_riscv_op`).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RiscVOperand {
     /// Operand classification.
     pub op_type: RiscVOperandType,
@@ -36,9 +40,9 @@ pub struct RiscVOperand {
 }
 
 /// Concrete value carried by an operand.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RiscVOperandValue {
-    /// Register identifier.
+    /// Register identifier, as returned by [`RiscVRegisterClass::id`].
     Register(u32),
     /// Immediate literal.
     Immediate(i64),
@@ -46,6 +50,45 @@ pub enum RiscVOperandValue {
     Memory(RiscVMemoryOperand),
 }
 
+/// Register file a register operand belongs to, and the width it is
+/// accessed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiscVRegisterClass {
+    /// Integer registers `x0`-`x31`.
+    Gpr,
+    /// Floating-point registers holding single-precision values.
+    Fpr32,
+    /// Floating-point registers holding double-precision values.
+    Fpr64,
+}
+
+impl RiscVRegisterClass {
+    /// Returns the identifier of register `num` of this class.
+    ///
+    /// `x0`-`x31` are 0-31, the single-precision `f0`-`f31` 32-63 and the
+    /// double-precision ones 64-95: one below Capstone's `riscv_reg`, so
+    /// integer register ids stay equal to their register numbers.
+    pub const fn id(self, num: u8) -> u32 {
+        let base = match self {
+            Self::Gpr => 0,
+            Self::Fpr32 => 32,
+            Self::Fpr64 => 64,
+        };
+        base + (num & 0x1f) as u32
+    }
+
+    /// Splits a register identifier into its class and register number.
+    pub const fn of(id: u32) -> Option<(Self, u8)> {
+        let class = match id >> 5 {
+            0 => Self::Gpr,
+            1 => Self::Fpr32,
+            2 => Self::Fpr64,
+            _ => return None,
+        };
+        Some((class, (id & 0x1f) as u8))
+    }
+}
+
 /// Register access flags (mirrors `cs_ac_type`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Access {
@@ -82,6 +125,248 @@ impl Access {
     }
 }
 
+/// Maximum number of structured operands of one instruction.
+pub const MAX_DETAIL_OPERANDS: usize = 4;
+
+/// Structured operands of a decoded instruction, stored inline so decoding
+/// does not allocate. Dereferences to a slice of [`RiscVOperand`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RiscVOperands {
+    operands: [RiscVOperand; MAX_DETAIL_OPERANDS],
+    len: usize,
+}
+
+impl RiscVOperands {
+    /// Creates an empty operand list.
+    pub const fn new() -> Self {
+        const EMPTY: RiscVOperand = RiscVOperand {
+            op_type: RiscVOperandType::Invalid,
+            access: Access {
+                read: false,
+                write: false,
+            },
+            value: RiscVOperandValue::Immediate(0),
+        };
+        Self {
+            operands: [EMPTY; MAX_DETAIL_OPERANDS],
+            len: 0,
+        }
+    }
+
+    /// Appends an operand, returning `false` when the list is already full.
+    pub fn push(&mut self, operand: RiscVOperand) -> bool {
+        if self.len == MAX_DETAIL_OPERANDS {
+            return false;
+        }
+        self.operands[self.len] = operand;
+        self.len += 1;
+        true
+    }
+
+    /// Returns the populated operands.
+    pub fn as_slice(&self) -> &[RiscVOperand] {
+        &self.operands[..self.len]
+    }
+}
+
+impl core::fmt::Debug for RiscVOperands {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl Default for RiscVOperands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::ops::Deref for RiscVOperands {
+    type Target = [RiscVOperand];
+
+    fn deref(&self) -> &[RiscVOperand] {
+        self.as_slice()
+    }
+}
+
+impl<'a> IntoIterator for &'a RiscVOperands {
+    type Item = &'a RiscVOperand;
+    type IntoIter = core::slice::Iter<'a, RiscVOperand>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<const N: usize> From<[RiscVOperand; N]> for RiscVOperands {
+    fn from(operands: [RiscVOperand; N]) -> Self {
+        const { assert!(N <= MAX_DETAIL_OPERANDS) };
+        let mut list = Self::new();
+        for operand in operands {
+            list.push(operand);
+        }
+        list
+    }
+}
+
+/// Maximum number of operands in the text of one instruction.
+pub const MAX_TEXT_OPERANDS: usize = 4;
+
+/// One operand of the printed instruction text.
+///
+/// [`RiscVOperand`] describes what an instruction accesses, as Capstone's
+/// detail does. This follows the printed syntax instead: operands the text
+/// leaves out, like the `rd` of `j`, are absent, and each value records how
+/// it is printed. [`RiscVPrinter`](crate::printer::RiscVPrinter) turns
+/// these into text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiscVTextOperand {
+    /// Integer register.
+    Register(u8),
+    /// Floating-point register.
+    FpRegister(u8),
+    /// Immediate value.
+    Immediate(i64),
+    /// `lui`/`auipc` immediate, shifted down by 12 bits.
+    UpperImmediate(i64),
+    /// 5-bit unsigned immediate of the CSR instructions, printed in decimal.
+    Zimm(u8),
+    /// `disp(base)` memory reference.
+    Memory { base: u8, disp: i64 },
+    /// `(base)` address of an atomic memory operation.
+    Address(u8),
+    /// PC-relative offset of a compressed branch or jump.
+    Offset(i64),
+    /// PC-relative offset of a 32-bit branch or jump, wrapped to XLEN
+    /// because Capstone prints it unsigned.
+    UnsignedOffset(u64),
+    /// CSR number, printed by name when known.
+    Csr(u16),
+    /// Encoding of an undecodable instruction of `size` bytes.
+    Encoding { value: u32, size: u8 },
+}
+
+impl RiscVTextOperand {
+    /// Register `num` of `class`, printed by its integer or floating-point
+    /// name.
+    pub const fn register(class: RiscVRegisterClass, num: u8) -> Self {
+        match class {
+            RiscVRegisterClass::Gpr => Self::Register(num),
+            RiscVRegisterClass::Fpr32 | RiscVRegisterClass::Fpr64 => Self::FpRegister(num),
+        }
+    }
+}
+
+/// Operands of the printed instruction text, stored inline.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RiscVTextOperands {
+    operands: [RiscVTextOperand; MAX_TEXT_OPERANDS],
+    len: usize,
+}
+
+impl RiscVTextOperands {
+    /// Creates an empty operand list.
+    pub const fn new() -> Self {
+        Self {
+            operands: [RiscVTextOperand::Immediate(0); MAX_TEXT_OPERANDS],
+            len: 0,
+        }
+    }
+
+    /// Appends an operand, returning `false` when the list is already full.
+    pub fn push(&mut self, operand: RiscVTextOperand) -> bool {
+        if self.len == MAX_TEXT_OPERANDS {
+            return false;
+        }
+        self.operands[self.len] = operand;
+        self.len += 1;
+        true
+    }
+
+    /// Returns the populated operands.
+    pub fn as_slice(&self) -> &[RiscVTextOperand] {
+        &self.operands[..self.len]
+    }
+
+    /// Returns the number of operands.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when the instruction prints no operand.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl core::fmt::Debug for RiscVTextOperands {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl Default for RiscVTextOperands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<[RiscVTextOperand; N]> for RiscVTextOperands {
+    fn from(operands: [RiscVTextOperand; N]) -> Self {
+        const { assert!(N <= MAX_TEXT_OPERANDS) };
+        let mut list = Self::new();
+        for operand in operands {
+            list.push(operand);
+        }
+        list
+    }
+}
+
+impl From<RiscVTextOperand> for SlotOperand {
+    fn from(operand: RiscVTextOperand) -> Self {
+        let (kind, value, extra) = match operand {
+            RiscVTextOperand::Register(reg) => (0, u64::from(reg), 0),
+            RiscVTextOperand::FpRegister(reg) => (1, u64::from(reg), 0),
+            RiscVTextOperand::Immediate(value) => (2, value as u64, 0),
+            RiscVTextOperand::UpperImmediate(value) => (3, value as u64, 0),
+            RiscVTextOperand::Zimm(value) => (4, u64::from(value), 0),
+            RiscVTextOperand::Memory { base, disp } => (5, disp as u64, u64::from(base)),
+            RiscVTextOperand::Address(base) => (6, u64::from(base), 0),
+            RiscVTextOperand::Offset(offset) => (7, offset as u64, 0),
+            RiscVTextOperand::UnsignedOffset(bits) => (8, bits, 0),
+            RiscVTextOperand::Csr(csr) => (9, u64::from(csr), 0),
+            RiscVTextOperand::Encoding { value, size } => (10, u64::from(value), u64::from(size)),
+        };
+        Self { kind, value, extra }
+    }
+}
+
+impl From<SlotOperand> for RiscVTextOperand {
+    /// Restores an operand recorded by `SlotOperand::from`.
+    fn from(operand: SlotOperand) -> Self {
+        let SlotOperand { kind, value, extra } = operand;
+        match kind {
+            0 => Self::Register(value as u8),
+            1 => Self::FpRegister(value as u8),
+            2 => Self::Immediate(value as i64),
+            3 => Self::UpperImmediate(value as i64),
+            4 => Self::Zimm(value as u8),
+            5 => Self::Memory {
+                base: extra as u8,
+                disp: value as i64,
+            },
+            6 => Self::Address(value as u8),
+            7 => Self::Offset(value as i64),
+            8 => Self::UnsignedOffset(value),
+            9 => Self::Csr(value as u16),
+            _ => Self::Encoding {
+                value: value as u32,
+                size: extra as u8,
+            },
+        }
+    }
+}
+
 /// Instruction-level detail (mirrors `cs_riscv`).
 #[derive(Debug, Clone)]
 pub struct RiscVInstructionDetail {
@@ -371,43 +656,26 @@ impl RiscVRegister {
         }
     }
 
-    /// Converts a raw register ID (x0=0, x1=1, …, x31=31) into the enum representation.
+    /// Converts a register identifier (see [`RiscVRegisterClass::id`]) into
+    /// the enum representation.
     pub fn from_id(id: u32) -> Self {
-        match id {
-            0 => RiscVRegister::X0,
-            1 => RiscVRegister::X1,
-            2 => RiscVRegister::X2,
-            3 => RiscVRegister::X3,
-            4 => RiscVRegister::X4,
-            5 => RiscVRegister::X5,
-            6 => RiscVRegister::X6,
-            7 => RiscVRegister::X7,
-            8 => RiscVRegister::X8,
-            9 => RiscVRegister::X9,
-            10 => RiscVRegister::X10,
-            11 => RiscVRegister::X11,
-            12 => RiscVRegister::X12,
-            13 => RiscVRegister::X13,
-            14 => RiscVRegister::X14,
-            15 => RiscVRegister::X15,
-            16 => RiscVRegister::X16,
-            17 => RiscVRegister::X17,
-            18 => RiscVRegister::X18,
-            19 => RiscVRegister::X19,
-            20 => RiscVRegister::X20,
-            21 => RiscVRegister::X21,
-            22 => RiscVRegister::X22,
-            23 => RiscVRegister::X23,
-            24 => RiscVRegister::X24,
-            25 => RiscVRegister::X25,
-            26 => RiscVRegister::X26,
-            27 => RiscVRegister::X27,
-            28 => RiscVRegister::X28,
-            29 => RiscVRegister::X29,
-            30 => RiscVRegister::X30,
-            31 => RiscVRegister::X31,
-            _ => RiscVRegister::Invalid,
-        }
+        const REGISTERS: [RiscVRegister; 96] = {
+            use RiscVRegister::*;
+            [
+                X0, X1, X2, X3, X4, X5, X6, X7, X8, X9, X10, X11, X12, X13, X14, X15, X16, X17,
+                X18, X19, X20, X21, X22, X23, X24, X25, X26, X27, X28, X29, X30, X31, F0_32, F1_32,
+                F2_32, F3_32, F4_32, F5_32, F6_32, F7_32, F8_32, F9_32, F10_32, F11_32, F12_32,
+                F13_32, F14_32, F15_32, F16_32, F17_32, F18_32, F19_32, F20_32, F21_32, F22_32,
+                F23_32, F24_32, F25_32, F26_32, F27_32, F28_32, F29_32, F30_32, F31_32, F0_64,
+                F1_64, F2_64, F3_64, F4_64, F5_64, F6_64, F7_64, F8_64, F9_64, F10_64, F11_64,
+                F12_64, F13_64, F14_64, F15_64, F16_64, F17_64, F18_64, F19_64, F20_64, F21_64,
+                F22_64, F23_64, F24_64, F25_64, F26_64, F27_64, F28_64, F29_64, F30_64, F31_64,
+            ]
+        };
+        REGISTERS
+            .get(id as usize)
+            .copied()
+            .unwrap_or(RiscVRegister::Invalid)
     }
 }
 
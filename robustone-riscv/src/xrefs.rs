@@ -0,0 +1,235 @@
+//! Cross-references: the addresses a region of code refers to.
+//!
+//! [`XrefIndex::scan`] sweeps a region linearly and records, for every
+//! instruction, the addresses it uses:
+//!
+//! - branch, jump and call targets;
+//! - `auipc`-based addresses, completed by `addi`, loads, stores and
+//!   `jalr`;
+//! - `lui`-based absolute constants, completed by `addi`/`addiw`, and the
+//!   loads and stores based on them.
+//!
+//! Register values are tracked with a [`ConstantTracker`], so the decoder
+//! must have detail enabled.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::compressed;
+use crate::constants::{ConstantTracker, ValueUse};
+use crate::decoder::RiscVDecoder;
+use crate::targets::{self, Flow};
+
+/// How an instruction refers to an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum XrefKind {
+    /// Conditional branch target.
+    Branch,
+    /// Jump target.
+    Jump,
+    /// Called function.
+    Call,
+    /// Memory read by a load.
+    Read,
+    /// Memory written by a store.
+    Write,
+    /// PC-relative address formed with `auipc`.
+    Address,
+    /// Absolute constant formed with `lui`.
+    Constant,
+}
+
+impl XrefKind {
+    /// Lower-case name used in reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Branch => "branch",
+            Self::Jump => "jump",
+            Self::Call => "call",
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Address => "address",
+            Self::Constant => "constant",
+        }
+    }
+}
+
+/// A reference from the instruction at `from` to the address `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xref {
+    pub from: u64,
+    pub to: u64,
+    pub kind: XrefKind,
+}
+
+/// Cross-references of a region, queryable by either end.
+#[derive(Debug, Clone, Default)]
+pub struct XrefIndex {
+    /// All references, ordered by `from`.
+    xrefs: Vec<Xref>,
+    /// References grouped by `to`, in `from` order.
+    by_target: BTreeMap<u64, Vec<Xref>>,
+}
+
+impl XrefIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sweep `bytes`, loaded at `address`, and index what each instruction
+    /// refers to. Bytes that do not decode are skipped two at a time.
+    pub fn scan(decoder: &RiscVDecoder, bytes: &[u8], address: u64) -> Self {
+        let xlen = decoder.xlen();
+        let mut index = Self::new();
+        let mut tracker = ConstantTracker::new(xlen);
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let pc = address.wrapping_add(offset as u64);
+            let decoded = match decoder.decode(&bytes[offset..], pc) {
+                Ok(decoded) if decoded.size > 0 && decoded.mnemonic != "c.unknown" => decoded,
+                _ => {
+                    tracker.reset();
+                    offset += 2;
+                    continue;
+                }
+            };
+            let size = decoded.size;
+            let word = match bytes[offset..offset + size] {
+                [b0, b1] => compressed::expand(u16::from_le_bytes([b0, b1]), xlen),
+                [b0, b1, b2, b3] => Some(u32::from_le_bytes([b0, b1, b2, b3])),
+                _ => None,
+            };
+            offset += size;
+            let Some(word) = word else {
+                tracker.reset();
+                continue;
+            };
+            let mut add = |to, kind| index.add(Xref { from: pc, to, kind });
+
+            match targets::control_flow(word, pc, xlen) {
+                Flow::Branch(target) => add(target, XrefKind::Branch),
+                Flow::Jump(target) => add(target, XrefKind::Jump),
+                Flow::Call(target) => add(target, XrefKind::Call),
+                _ => {}
+            }
+            if let Some(materialized) = tracker.step(word, pc, &decoded.operands_detail) {
+                let kind = match materialized.usage {
+                    ValueUse::Compute if materialized.pc_relative => XrefKind::Address,
+                    ValueUse::Compute => XrefKind::Constant,
+                    ValueUse::Load => XrefKind::Read,
+                    ValueUse::Store => XrefKind::Write,
+                    ValueUse::Jump => XrefKind::Jump,
+                    ValueUse::Call => XrefKind::Call,
+                };
+                add(materialized.value, kind);
+            }
+        }
+        index
+    }
+
+    /// Record one reference. References must be added in `from` order.
+    pub fn add(&mut self, xref: Xref) {
+        self.xrefs.push(xref);
+        self.by_target.entry(xref.to).or_default().push(xref);
+    }
+
+    /// Add the references of `other`, such as a later region.
+    pub fn extend(&mut self, other: XrefIndex) {
+        for xref in other.xrefs {
+            self.add(xref);
+        }
+    }
+
+    /// References to `address`, in `from` order.
+    pub fn xrefs_to(&self, address: u64) -> &[Xref] {
+        self.by_target.get(&address).map_or(&[], Vec::as_slice)
+    }
+
+    /// References made by the instruction at `address`.
+    pub fn xrefs_from(&self, address: u64) -> &[Xref] {
+        let start = self.xrefs.partition_point(|xref| xref.from < address);
+        let end = self.xrefs.partition_point(|xref| xref.from <= address);
+        &self.xrefs[start..end]
+    }
+
+    /// Every reference, ordered by `from`.
+    pub fn iter(&self) -> impl Iterator<Item = &Xref> {
+        self.xrefs.iter()
+    }
+
+    /// Referenced addresses in ascending order, each with its references.
+    pub fn targets(&self) -> impl Iterator<Item = (u64, &[Xref])> {
+        self.by_target
+            .iter()
+            .map(|(address, xrefs)| (*address, xrefs.as_slice()))
+    }
+
+    /// Number of references.
+    pub fn len(&self) -> usize {
+        self.xrefs.len()
+    }
+
+    /// Whether no reference was found.
+    pub fn is_empty(&self) -> bool {
+        self.xrefs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Xlen;
+    use crate::extensions::Extensions;
+
+    #[test]
+    fn test_scan() {
+        // 1000: jal ra, 1010        1004: auipc a0, 0x1
+        // 1008: ld a1, 8(a0)        100c: lui a2, 0x80001
+        // 1010: addiw a2, a2, 0x234 1014: sw a1, 4(a2)
+        // 1018: auipc t1, 0         101c: jalr zero, 0x20(t1)
+        // 1020: beqz a0, 1018
+        let code: [u32; 9] = [
+            0x0100_00ef,
+            0x0000_1517,
+            0x0085_3583,
+            0x8000_1637,
+            0x2346_061b,
+            0x00b6_2223,
+            0x0000_0317,
+            0x0203_0067,
+            0xfe05_0ce3,
+        ];
+        let bytes: Vec<u8> = code.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let decoder = RiscVDecoder::new(Xlen::X64, Extensions::rv64gc());
+        let index = XrefIndex::scan(&decoder, &bytes, 0x1000);
+
+        let xref = |from, to, kind| Xref { from, to, kind };
+        assert_eq!(
+            index.iter().copied().collect::<Vec<_>>(),
+            [
+                xref(0x1000, 0x1010, XrefKind::Call),
+                xref(0x1008, 0x200c, XrefKind::Read),
+                xref(0x1010, 0xffff_ffff_8000_1234, XrefKind::Constant),
+                xref(0x1014, 0xffff_ffff_8000_1238, XrefKind::Write),
+                xref(0x101c, 0x1038, XrefKind::Jump),
+                xref(0x1020, 0x1018, XrefKind::Branch),
+            ]
+        );
+        assert_eq!(
+            index.xrefs_to(0x1018),
+            [xref(0x1020, 0x1018, XrefKind::Branch)]
+        );
+        assert_eq!(
+            index.xrefs_from(0x1008),
+            [xref(0x1008, 0x200c, XrefKind::Read)]
+        );
+        assert!(index.xrefs_from(0x1004).is_empty());
+
+        // The jump forgets a0, so nothing is based on it afterwards.
+        let mut bytes = bytes;
+        bytes.extend_from_slice(&0x0005_3583u32.to_le_bytes());
+        let index = XrefIndex::scan(&decoder, &bytes, 0x1000);
+        assert!(index.xrefs_from(0x1024).is_empty());
+    }
+}
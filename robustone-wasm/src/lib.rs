@@ -0,0 +1,343 @@
+//! WebAssembly bindings for Robustone.
+//!
+//! This crate exposes the RISC-V and AArch64 disassemblers to JavaScript
+//! through `wasm-bindgen`. Build it for `wasm32-unknown-unknown` (for example
+//! with `wasm-pack build robustone-wasm --target web`) and use it from the
+//! browser:
+//!
+//! ```js
+//! import init, { Disassembler } from "./pkg/robustone_wasm.js";
+//!
+//! await init();
+//! const dis = new Disassembler("riscv64");
+//! dis.detail = true;
+//! const { instructions, error } = dis.disassembleHex("9300100082803385c500", 0x1000);
+//! for (const insn of instructions) {
+//!   console.log(insn.address.toString(16), insn.mnemonic, insn.operands, insn.detail);
+//! }
+//! ```
+//!
+//! Results are plain objects (see [`model`]), so they can be passed straight
+//! to `JSON.stringify`. Decoding stops at the first invalid instruction; the
+//! instructions before it are returned together with an `error` giving its
+//! offset, address and reason.
+
+pub mod model;
+
+use robustone_aarch64::AArch64Handler;
+use robustone_core::types::options::HandlerOption;
+use robustone_core::utils::HexParser;
+use robustone_core::{ArchitectureDispatcher, DisasmError, InstructionSlot};
+use robustone_riscv::RiscVHandler;
+use wasm_bindgen::prelude::*;
+
+use model::{DecodeError, Disassembly, InstructionInfo};
+
+/// Architecture names accepted by [`Disassembler::new`].
+const ARCHITECTURES: [&str; 4] = ["riscv32", "riscv64", "aarch64", "aarch64be"];
+
+/// A disassembler for one architecture, exported to JavaScript.
+#[wasm_bindgen]
+pub struct Disassembler {
+    arch: &'static str,
+    dispatcher: ArchitectureDispatcher,
+    slot: InstructionSlot,
+    hex_parser: HexParser,
+    detail: bool,
+}
+
+impl Disassembler {
+    /// Creates a disassembler for `arch`, one of [`supported_architectures`]
+    /// or the aliases `"riscv"` and `"arm64"`.
+    pub fn try_new(arch: &str) -> Result<Self, DisasmError> {
+        let mut dispatcher = ArchitectureDispatcher::new();
+        let arch = match arch {
+            "riscv32" => {
+                dispatcher.register(Box::new(RiscVHandler::rv32()));
+                "riscv32"
+            }
+            "riscv64" | "riscv" => {
+                dispatcher.register(Box::new(RiscVHandler::rv64()));
+                "riscv64"
+            }
+            "aarch64" | "arm64" => {
+                dispatcher.register(Box::new(AArch64Handler::new()));
+                "aarch64"
+            }
+            "aarch64be" => {
+                dispatcher.register(Box::new(AArch64Handler::big_endian()));
+                "aarch64be"
+            }
+            _ => return Err(DisasmError::UnsupportedArchitecture(arch.to_string())),
+        };
+        dispatcher.set_option(arch, HandlerOption::Detail(false))?;
+        Ok(Self {
+            arch,
+            dispatcher,
+            slot: InstructionSlot::new(),
+            hex_parser: HexParser::new(),
+            detail: false,
+        })
+    }
+
+    /// Turns detail on or off; with detail off the decoder skips operand
+    /// bookkeeping entirely.
+    pub fn set_detail_mode(&mut self, detail: bool) -> Result<(), DisasmError> {
+        self.dispatcher
+            .set_option(self.arch, HandlerOption::Detail(detail))?;
+        self.detail = detail;
+        Ok(())
+    }
+
+    /// Decodes `code` as if it were loaded at `address`, up to the first
+    /// invalid instruction.
+    pub fn decode(&mut self, code: &[u8], address: u64) -> Disassembly {
+        let mut result = Disassembly::default();
+        let mut offset = 0;
+        while offset < code.len() {
+            let address = address.wrapping_add(offset as u64);
+            match self.decode_one(&code[offset..], address) {
+                Ok(insn) => {
+                    offset += insn.size;
+                    result.instructions.push(insn);
+                }
+                Err(err) => {
+                    result.error = Some(DecodeError {
+                        offset,
+                        address,
+                        message: err.to_string(),
+                    });
+                    break;
+                }
+            }
+        }
+        result
+    }
+
+    /// Parses `hex` like the CLI does and decodes the resulting bytes.
+    pub fn decode_hex(&mut self, hex: &str, address: u64) -> Result<Disassembly, DisasmError> {
+        let bytes = self.hex_parser.parse_for_architecture(hex, self.arch)?;
+        Ok(self.decode(&bytes, address))
+    }
+
+    fn decode_one(&mut self, code: &[u8], address: u64) -> Result<InstructionInfo, DisasmError> {
+        let insn = if self.detail {
+            let (instruction, _) = self
+                .dispatcher
+                .disassemble_bytes(code, self.arch, address)?;
+            InstructionInfo::from_instruction(instruction)
+        } else {
+            self.dispatcher
+                .disassemble_into(code, self.arch, address, &mut self.slot)?;
+            InstructionInfo::from_slot(&self.slot)
+        };
+        if insn.size == 0 || is_unknown(&insn.mnemonic) {
+            return Err(DisasmError::DecodingError(format!(
+                "invalid instruction {}",
+                insn.operands
+            )));
+        }
+        Ok(insn)
+    }
+}
+
+#[wasm_bindgen]
+impl Disassembler {
+    /// Creates a disassembler for `arch`, such as `"riscv64"` or `"aarch64"`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(arch: &str) -> Result<Disassembler, JsError> {
+        Self::try_new(arch).map_err(js_error)
+    }
+
+    /// Canonical name of the selected architecture.
+    #[wasm_bindgen(getter)]
+    pub fn arch(&self) -> String {
+        self.arch.to_string()
+    }
+
+    /// Whether instructions carry structured operands, registers and groups.
+    #[wasm_bindgen(getter)]
+    pub fn detail(&self) -> bool {
+        self.detail
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_detail(&mut self, detail: bool) -> Result<(), JsError> {
+        self.set_detail_mode(detail).map_err(js_error)
+    }
+
+    /// Disassembles raw bytes loaded at `address`, returning
+    /// `{ instructions, error }`.
+    #[wasm_bindgen(js_name = disassemble)]
+    pub fn disassemble_js(&mut self, code: &[u8], address: f64) -> Result<JsValue, JsError> {
+        let result = self.decode(code, js_address(address)?);
+        to_js(&result)
+    }
+
+    /// Disassembles a hex string such as `"93001000"` loaded at `address`,
+    /// returning `{ instructions, error }`; throws when `hex` does not parse.
+    #[wasm_bindgen(js_name = disassembleHex)]
+    pub fn disassemble_hex_js(&mut self, hex: &str, address: f64) -> Result<JsValue, JsError> {
+        let result = self
+            .decode_hex(hex, js_address(address)?)
+            .map_err(js_error)?;
+        to_js(&result)
+    }
+}
+
+/// Returns the architecture names accepted by `new Disassembler()`.
+#[wasm_bindgen(js_name = supportedArchitectures)]
+pub fn supported_architectures() -> Vec<String> {
+    ARCHITECTURES.iter().map(|arch| arch.to_string()).collect()
+}
+
+/// Returns the `robustone-wasm` package version.
+#[wasm_bindgen]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Returns `true` for the decoder's placeholder mnemonics.
+fn is_unknown(mnemonic: &str) -> bool {
+    matches!(mnemonic, "unknown" | "c.unknown")
+}
+
+/// Converts a JavaScript number into an address, rejecting unsafe values.
+fn js_address(address: f64) -> Result<u64, JsError> {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if address.fract() != 0.0 || !(0.0..=MAX_SAFE_INTEGER).contains(&address) {
+        return Err(JsError::new(&format!("invalid address: {address}")));
+    }
+    Ok(address as u64)
+}
+
+fn to_js(result: &Disassembly) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(result).map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn js_error(err: DisasmError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robustone_riscv::insn::RiscVInstructionId;
+
+    #[test]
+    fn test_rejects_unknown_architecture() {
+        assert!(matches!(
+            Disassembler::try_new("x86"),
+            Err(DisasmError::UnsupportedArchitecture(arch)) if arch == "x86"
+        ));
+        assert_eq!(
+            supported_architectures(),
+            ["riscv32", "riscv64", "aarch64", "aarch64be"]
+        );
+        assert_eq!(Disassembler::try_new("arm64").unwrap().arch(), "aarch64");
+    }
+
+    #[test]
+    fn test_decode_hex_without_detail() {
+        let mut dis = Disassembler::try_new("riscv64").unwrap();
+        let result = dis.decode_hex("930010003385c500ffff", 0x1000).unwrap();
+        let instructions = &result.instructions;
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].address, 0x1000);
+        assert_eq!(instructions[0].bytes, [0x93, 0x00, 0x10, 0x00]);
+        assert_eq!(instructions[0].mnemonic, "addi");
+        // Capstone's RISCV_INS_ADDI.
+        assert_eq!(instructions[0].id, 2);
+        assert_eq!(instructions[0].operands, "ra, zero, 1");
+        assert!(instructions[0].detail.is_none());
+        assert_eq!(instructions[1].address, 0x1004);
+        assert_eq!(instructions[1].mnemonic, "add");
+
+        let error = result.error.unwrap();
+        assert_eq!((error.offset, error.address), (8, 0x1008));
+    }
+
+    #[test]
+    fn test_decode_reports_invalid_instruction() {
+        let mut dis = Disassembler::try_new("riscv64").unwrap();
+        // addi ra, zero, 1 ; an invalid word ; add a0, a1, a2
+        let code = [
+            0x93, 0x00, 0x10, 0x00, 0xff, 0xff, 0xff, 0xff, 0x33, 0x85, 0xc5, 0x00,
+        ];
+        for detail in [false, true] {
+            dis.set_detail_mode(detail).unwrap();
+            let result = dis.decode(&code, 0x1000);
+            assert_eq!(result.instructions.len(), 1);
+            assert_eq!(
+                result.error,
+                Some(DecodeError {
+                    offset: 4,
+                    address: 0x1004,
+                    message: "ERROR: Decoding failed: invalid instruction 0xffffffff".to_string(),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_with_detail() {
+        use model::OperandInfo;
+
+        let mut dis = Disassembler::try_new("riscv64").unwrap();
+        dis.set_detail_mode(true).unwrap();
+        // jal ra, 8 ; ld a0, 8(sp) ; fadd.s fa0, fa1, fa2
+        let code = [
+            0xef, 0x00, 0x80, 0x00, 0x03, 0x35, 0x81, 0x00, 0x53, 0x85, 0xc5, 0x00,
+        ];
+        let result = dis.decode(&code, 0);
+        let instructions = &result.instructions;
+        assert!(result.error.is_none());
+
+        assert_eq!(instructions[0].id, RiscVInstructionId::Jal.id());
+        let call = instructions[0].detail.as_ref().unwrap();
+        assert_eq!(call.groups, ["call", "branch_relative"]);
+        assert_eq!(call.regs_write, ["ra"]);
+
+        let load = instructions[1].detail.as_ref().unwrap();
+        assert_eq!(
+            load.operands,
+            [
+                OperandInfo::Reg {
+                    reg: "a0",
+                    access: "write"
+                },
+                OperandInfo::Mem {
+                    base: "sp",
+                    index: None,
+                    disp: 8,
+                    access: "read"
+                },
+            ]
+        );
+        assert_eq!(load.regs_read, ["sp"]);
+        assert_eq!(load.regs_write, ["a0"]);
+
+        let fadd = instructions[2].detail.as_ref().unwrap();
+        assert_eq!(fadd.regs_read, ["fa1", "fa2"]);
+        assert_eq!(fadd.regs_write, ["fa0"]);
+    }
+
+    #[test]
+    fn test_decode_aarch64() {
+        let mut dis = Disassembler::try_new("aarch64").unwrap();
+        dis.set_detail_mode(true).unwrap();
+        // mov w0, #1 ; ldr x0, [sp, #8]
+        let result = dis.decode_hex("20008052e00740f9", 0).unwrap();
+        assert!(result.error.is_none());
+        assert_eq!(result.instructions[0].mnemonic, "mov");
+        assert_eq!(result.instructions[0].operands, "w0, #1");
+        assert_eq!(
+            result.instructions[1].id,
+            robustone_aarch64::insn::insn_id("ldr")
+        );
+        let load = result.instructions[1].detail.as_ref().unwrap();
+        assert_eq!(load.regs_read, ["sp"]);
+        assert_eq!(load.regs_write, ["x0"]);
+    }
+}
@@ -0,0 +1,147 @@
+//! JSON-friendly instruction objects handed to JavaScript.
+//!
+//! Everything here is plain `serde` data so it can be unit tested on the host
+//! and converted with `serde-wasm-bindgen` at the boundary.
+
+use serde::Serialize;
+
+use robustone_core::{Instruction, InstructionSlot, Operand};
+
+/// The result of disassembling a buffer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Disassembly {
+    /// Instructions decoded before the first failure.
+    pub instructions: Vec<InstructionInfo>,
+    /// Why decoding stopped early; absent when the whole buffer decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<DecodeError>,
+}
+
+/// Where and why decoding stopped.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodeError {
+    /// Offset of the failing bytes in the input.
+    pub offset: usize,
+    /// Address of the failing bytes.
+    pub address: u64,
+    pub message: String,
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionInfo {
+    /// Architecture-specific instruction id; RISC-V ids match Capstone's `riscv_insn`
+    /// and AArch64 ids its `arm64_insn`.
+    pub id: u32,
+    pub address: u64,
+    pub size: usize,
+    pub bytes: Vec<u8>,
+    pub mnemonic: String,
+    pub operands: String,
+    /// Present only when detail mode is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<InstructionDetail>,
+}
+
+/// Structured operands, register accesses and semantic groups.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionDetail {
+    pub operands: Vec<OperandInfo>,
+    pub regs_read: Vec<&'static str>,
+    pub regs_write: Vec<&'static str>,
+    pub groups: Vec<String>,
+}
+
+/// One operand, tagged by `type` (`"reg"`, `"imm"` or `"mem"`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OperandInfo {
+    Reg {
+        reg: &'static str,
+        access: &'static str,
+    },
+    Imm {
+        imm: i64,
+    },
+    Mem {
+        base: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<&'static str>,
+        disp: i64,
+        access: &'static str,
+    },
+}
+
+impl InstructionInfo {
+    /// Builds an instruction without detail from a decoded slot.
+    pub fn from_slot(slot: &InstructionSlot) -> Self {
+        Self {
+            id: slot.id(),
+            address: slot.address(),
+            size: slot.size(),
+            bytes: slot.bytes().to_vec(),
+            mnemonic: slot.mnemonic().to_string(),
+            operands: slot.operands().to_string(),
+            detail: None,
+        }
+    }
+
+    /// Builds an instruction, with detail when the handler recorded it.
+    pub fn from_instruction(instruction: Instruction) -> Self {
+        let detail = instruction.detail.as_ref().map(|detail| {
+            let names = |regs: &[u32]| {
+                let mut names = Vec::new();
+                for name in regs.iter().filter_map(|reg| detail.register_name(*reg)) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                names
+            };
+            InstructionDetail {
+                operands: detail
+                    .operands()
+                    .into_iter()
+                    .map(OperandInfo::from)
+                    .collect(),
+                regs_read: names(detail.registers_read()),
+                regs_write: names(detail.registers_written()),
+                groups: detail.groups().into_iter().map(str::to_string).collect(),
+            }
+        });
+        Self {
+            id: instruction.id,
+            address: instruction.address,
+            size: instruction.size,
+            bytes: instruction.bytes,
+            mnemonic: instruction.mnemonic,
+            operands: instruction.operands,
+            detail,
+        }
+    }
+}
+
+impl From<Operand> for OperandInfo {
+    fn from(operand: Operand) -> Self {
+        match operand {
+            Operand::Register { name, access } => OperandInfo::Reg {
+                reg: name,
+                access: access.name(),
+            },
+            Operand::Immediate(imm) => OperandInfo::Imm { imm },
+            Operand::Memory {
+                base,
+                index,
+                disp,
+                access,
+            } => OperandInfo::Mem {
+                base,
+                index,
+                disp,
+                access: access.name(),
+            },
+        }
+    }
+}
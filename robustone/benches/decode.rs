@@ -0,0 +1,105 @@
+//! Compares the allocating decode path with the reusable slot path, with
+//! detail on and off.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use robustone::types::options::HandlerOption;
+use robustone::{InstructionSlot, dispatcher};
+use std::hint::black_box;
+
+/// A mix of 32-bit and compressed RISC-V instructions, repeated to form a
+/// buffer large enough for stable measurements.
+fn sample_code() -> Vec<u8> {
+    let pattern: &[u8] = &[
+        0x13, 0x01, 0x01, 0xff, // addi sp, sp, -16
+        0x23, 0x34, 0x11, 0x00, // sd ra, 8(sp)
+        0x33, 0x85, 0xc5, 0x00, // add a0, a1, a2
+        0xb7, 0x52, 0x34, 0x12, // lui t0, 0x12345
+        0x63, 0x04, 0xb5, 0x00, // beq a0, a1, 8
+        0x03, 0x35, 0x81, 0x00, // ld a0, 8(sp)
+        0x05, 0x05, // c.addi a0, 1
+        0x82, 0x80, // c.jr ra
+    ];
+    pattern.repeat(512)
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let code = sample_code();
+    let dispatcher = dispatcher();
+
+    let mut group = c.benchmark_group("riscv64");
+    group.throughput(Throughput::Bytes(code.len() as u64));
+
+    group.bench_function("disassemble_bytes", |b| {
+        b.iter(|| {
+            let mut offset = 0;
+            while offset < code.len() {
+                let (instruction, size) = dispatcher
+                    .disassemble_bytes(&code[offset..], "riscv64", offset as u64)
+                    .unwrap();
+                black_box(&instruction);
+                offset += size;
+            }
+        })
+    });
+
+    group.bench_function("disassemble_into", |b| {
+        let mut slot = InstructionSlot::new();
+        b.iter(|| {
+            let mut offset = 0;
+            while offset < code.len() {
+                let size = dispatcher
+                    .disassemble_into(&code[offset..], "riscv64", offset as u64, &mut slot)
+                    .unwrap();
+                black_box(&slot);
+                offset += size;
+            }
+        })
+    });
+
+    let mut lite_dispatcher = robustone::dispatcher();
+    lite_dispatcher
+        .set_option("riscv64", HandlerOption::Detail(false))
+        .unwrap();
+
+    group.bench_function("disassemble_bytes_detail_off", |b| {
+        b.iter(|| {
+            let mut offset = 0;
+            while offset < code.len() {
+                let (instruction, size) = lite_dispatcher
+                    .disassemble_bytes(&code[offset..], "riscv64", offset as u64)
+                    .unwrap();
+                black_box(&instruction);
+                offset += size;
+            }
+        })
+    });
+
+    group.bench_function("disassemble_into_detail_off", |b| {
+        let mut slot = InstructionSlot::new();
+        b.iter(|| {
+            let mut offset = 0;
+            while offset < code.len() {
+                let size = lite_dispatcher
+                    .disassemble_into(&code[offset..], "riscv64", offset as u64, &mut slot)
+                    .unwrap();
+                black_box(&slot);
+                offset += size;
+            }
+        })
+    });
+
+    group.bench_function("disassemble_lite", |b| {
+        b.iter(|| {
+            black_box(
+                lite_dispatcher
+                    .disassemble_lite(&code, "riscv64", 0)
+                    .unwrap(),
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -3,11 +3,15 @@
 #[doc(inline)]
 pub use robustone_core::*;
 
+#[doc(inline)]
+pub use robustone_aarch64 as aarch64;
 #[doc(inline)]
 pub use robustone_riscv as riscv;
 
 pub fn dispatcher() -> ArchitectureDispatcher {
     let mut dispatcher = ArchitectureDispatcher::new();
     dispatcher.register(Box::new(riscv::RiscVHandler::new()));
+    dispatcher.register(Box::new(aarch64::AArch64Handler::new()));
+    dispatcher.register(Box::new(aarch64::AArch64Handler::big_endian()));
     dispatcher
 }
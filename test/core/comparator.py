@@ -86,6 +86,9 @@ class OutputComparator:
         """
         Check if documented expected output differs from actual cstool output.
 
+        Test cases document the instruction line only, so the indented
+        detail block printed with `-d` is ignored.
+
         Args:
             expected: Expected output from documentation
             actual: Actual output from cstool
@@ -95,7 +98,10 @@ class OutputComparator:
         """
         if not expected:
             return False
-        return normalize_output(expected) != normalize_output(actual)
+        instruction_lines = "\n".join(
+            line for line in actual.splitlines() if not line.startswith("\t")
+        )
+        return normalize_output(expected) != normalize_output(instruction_lines)
 
     def classify_result(
         self,
@@ -0,0 +1,85 @@
+"""
+In-process Robustone backend built on the robustone_py bindings.
+"""
+
+from typing import Dict, List, Tuple
+
+# Capstone mode used for each robustone architecture name. The CLI decodes
+# with the GC profile, so the compressed extension is always enabled.
+_MODES = {
+    "riscv32": ("CS_MODE_RISCV32", "CS_MODE_RISCVC"),
+    "riscv64": ("CS_MODE_RISCV64", "CS_MODE_RISCVC"),
+    "riscv": ("CS_MODE_RISCV64", "CS_MODE_RISCVC"),
+}
+
+
+class InProcessDisassembler:
+    """Produces plain `robustone` output without spawning the CLI."""
+
+    def __init__(self):
+        """
+        Import the bindings.
+
+        Raises:
+            RuntimeError: If robustone_py is not installed
+        """
+        try:
+            # pylint: disable-next=import-outside-toplevel
+            import robustone_py
+        except ImportError as exc:
+            raise RuntimeError(
+                "robustone_py is not installed; build it with `make python-dev`"
+            ) from exc
+
+        self.module = robustone_py
+        self.handles: Dict[str, object] = {}
+
+    def _handle(self, arch: str):
+        """Return a cached `Cs` handle for a robustone architecture name."""
+        if arch not in self.handles:
+            names = _MODES.get(arch)
+            if names is None:
+                raise ValueError(
+                    f"Unsupported architecture for in-process mode: {arch}"
+                )
+            mode = 0
+            for name in names:
+                mode |= getattr(self.module, name)
+            self.handles[arch] = self.module.Cs(self.module.CS_ARCH_RISCV, mode)
+        return self.handles[arch]
+
+    def run(self, arch: str, hex_input: str, flags: List[str]) -> Tuple[int, str, str]:
+        """
+        Disassemble a hex string the way the CLI does.
+
+        Args:
+            arch: Robustone architecture name
+            hex_input: Hexadecimal instruction bytes
+            flags: Extra robustone CLI flags (unsupported in-process)
+
+        Returns:
+            Tuple of (returncode, stdout, stderr), like run_command
+        """
+        if flags:
+            return 2, "", f"Flags are not supported in-process: {' '.join(flags)}"
+
+        try:
+            handle = self._handle(arch)
+            code = bytes.fromhex(hex_input.strip().lower().removeprefix("0x"))
+        except ValueError as exc:
+            return 1, "", str(exc)
+
+        lines = []
+        consumed = 0
+        for insn in handle.disasm(code, 0):
+            hex_bytes = " ".join(f"{byte:02x}" for byte in insn.bytes)
+            line = f"{insn.address:2x}  {hex_bytes:>8}  {insn.mnemonic}"
+            if insn.op_str:
+                line += f"\t{insn.op_str}"
+            lines.append(line)
+            consumed += insn.size
+
+        if consumed < len(code):
+            return 1, "", f"Failed to decode instruction at offset {consumed}"
+        # run_command strips CLI output the same way
+        return 0, "\n".join(lines).strip(), ""
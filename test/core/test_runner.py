@@ -15,6 +15,7 @@ sys.path.append(PROJECT_ROOT)
 from arch_config import ArchConfig, validate_config
 from comparator import OutputComparator, TestCaseResult, ArchTestSummary
 from utils import run_command, parse_test_case, find_repo_root
+from inprocess import InProcessDisassembler
 
 # pylint: enable=wrong-import-position
 
@@ -26,6 +27,8 @@ class TestRunner:
         self,
         repo_root: Optional[Path] = None,
         comparator: Optional[OutputComparator] = None,
+        in_process: bool = False,
+        detail: bool = False,
     ):
         """
         Initialize the test runner.
@@ -33,9 +36,16 @@ class TestRunner:
         Args:
             repo_root: Path to repository root (auto-detected if None)
             comparator: Output comparator instance (default created if None)
+            in_process: Call the robustone_py bindings instead of the CLI binary
+            detail: Compare the `-d` detail output of both tools
         """
+        if in_process and detail:
+            raise ValueError("Detail mode is not supported in-process")
         self.repo_root = repo_root or find_repo_root()
         self.comparator = comparator or OutputComparator()
+        self.in_process = in_process
+        self.detail = detail
+        self.in_process_backend: Optional[InProcessDisassembler] = None
         self.robustone_bin = self.repo_root / "target" / "debug" / "robustone"
         self.cstool_bin = (
             self.repo_root / "third_party" / "capstone" / "cstool" / "cstool"
@@ -51,20 +61,24 @@ class TestRunner:
         Raises:
             RuntimeError: If binaries cannot be built or found
         """
-        # Build robustone if needed
-        if verbose:
-            print("Building robustone...")
-        build_cmd = [
-            "cargo",
-            "build",
-            "--manifest-path",
-            str(self.repo_root / "robustone" / "Cargo.toml"),
-            "--bin",
-            "robustone",
-        ]
-        code, _, err = run_command(build_cmd)
-        if code != 0:
-            raise RuntimeError(f"Failed to build robustone: {err}")
+        if self.in_process:
+            # The bindings are built out of band (see `make python-dev`)
+            self.in_process_backend = InProcessDisassembler()
+        else:
+            # Build robustone if needed
+            if verbose:
+                print("Building robustone...")
+            build_cmd = [
+                "cargo",
+                "build",
+                "--manifest-path",
+                str(self.repo_root / "robustone" / "Cargo.toml"),
+                "--bin",
+                "robustone",
+            ]
+            code, _, err = run_command(build_cmd)
+            if code != 0:
+                raise RuntimeError(f"Failed to build robustone: {err}")
 
         # Check cstool binary
         if not self.cstool_bin.exists():
@@ -108,25 +122,37 @@ class TestRunner:
         """
         start_time = time.time()
 
-        # Build commands
-        robustone_cmd = [
-            str(self.robustone_bin),
-            "--detailed",
-            config.robustone_arch,
-            hex_input,
-        ] + config.robustone_flags
+        detail_flags = ["-d"] if self.detail else []
+        cstool_cmd = (
+            [
+                str(self.cstool_bin),
+                config.cstool_arch,
+                hex_input,
+            ]
+            + config.cstool_flags
+            + detail_flags
+        )
 
-        if verbose:
-            print(f"Running Command: {robustone_cmd}")
+        # Execute commands
+        if self.in_process_backend is not None:
+            rob_code, rob_out, rob_err = self.in_process_backend.run(
+                config.robustone_arch, hex_input, config.robustone_flags
+            )
+        else:
+            robustone_cmd = (
+                [
+                    str(self.robustone_bin),
+                    config.robustone_arch,
+                    hex_input,
+                ]
+                + config.robustone_flags
+                + detail_flags
+            )
 
-        cstool_cmd = [
-            str(self.cstool_bin),
-            config.cstool_arch,
-            hex_input,
-        ] + config.cstool_flags
+            if verbose:
+                print(f"Running Command: {robustone_cmd}")
 
-        # Execute commands
-        rob_code, rob_out, rob_err = run_command(robustone_cmd)
+            rob_code, rob_out, rob_err = run_command(robustone_cmd)
         cs_code, cs_out, cs_err = run_command(cstool_cmd)
 
         if verbose:
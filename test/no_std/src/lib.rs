@@ -0,0 +1,61 @@
+//! `no_std` build check for `robustone-core` and the decoder crates.
+//!
+//! This crate defines its own panic handler and global allocator, so the
+//! compiler rejects it with a duplicate lang item error if anything in the
+//! dependency graph pulls in `std`. Building it on a regular Linux host is
+//! therefore enough to verify the bare-metal configuration:
+//!
+//! ```sh
+//! cargo build --manifest-path test/no_std/Cargo.toml
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+use core::ptr;
+
+use robustone_core::{ArchitectureDispatcher, ArchitectureHandler, Instruction, InstructionSlot};
+use robustone_aarch64::AArch64Handler;
+use robustone_riscv::RiscVHandler;
+
+/// Allocator stub; the check only needs the crate graph to link.
+struct NullAllocator;
+
+unsafe impl GlobalAlloc for NullAllocator {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NullAllocator = NullAllocator;
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+/// Decodes one instruction through the owned handler API.
+pub fn decode(bytes: &[u8], address: u64) -> Option<(Instruction, usize)> {
+    RiscVHandler::rv64().disassemble(bytes, address).ok()
+}
+
+/// Decodes one instruction through the dispatcher into a reusable slot.
+pub fn decode_into(bytes: &[u8], address: u64, slot: &mut InstructionSlot) -> Option<usize> {
+    let mut dispatcher = ArchitectureDispatcher::new();
+    dispatcher.register(Box::new(RiscVHandler::rv64()));
+    dispatcher
+        .disassemble_into(bytes, "riscv64", address, slot)
+        .ok()
+}
+
+/// Decodes one AArch64 instruction through the owned handler API.
+pub fn decode_aarch64(bytes: &[u8], address: u64) -> Option<(Instruction, usize)> {
+    AArch64Handler::new().disassemble(bytes, address).ok()
+}
@@ -10,6 +10,8 @@ Usage:
   python3 test/run_tests.py --arch riscv32 --limit 20
   python3 test/run_tests.py --list
   python3 test/run_tests.py --init new_arch
+  python3 test/run_tests.py --all --in-process
+  python3 test/run_tests.py --all --detail
 """
 
 import argparse
@@ -75,7 +77,10 @@ def run_tests(args: argparse.Namespace) -> int:
     """Run tests based on command line arguments."""
     # Setup
     test_root = Path(__file__).parent
-    runner = TestRunner()
+    if args.in_process and args.detail:
+        print("--detail cannot be combined with --in-process")
+        return 2
+    runner = TestRunner(in_process=args.in_process, detail=args.detail)
 
     # Discover architectures
     archs = discover_arch_configs(test_root)
@@ -184,6 +189,8 @@ Examples:
   python3 test/run_tests.py --arch riscv32 --limit 20  # Limit test cases
   python3 test/run_tests.py --list                  # List available architectures
   python3 test/run_tests.py --init new_arch         # Create new architecture config
+  python3 test/run_tests.py --all --in-process       # Call robustone_py in-process
+  python3 test/run_tests.py --all --detail           # Compare -d detail output
         """,
     )
 
@@ -216,6 +223,11 @@ Examples:
     parser.add_argument(
         "--loose-match", action="store_true", help="Use loose output matching"
     )
+    parser.add_argument(
+        "--detail",
+        action="store_true",
+        help="Run both tools with -d and compare the operand detail too",
+    )
     parser.add_argument(
         "--ignore-whitespace",
         action="store_true",
@@ -223,6 +235,13 @@ Examples:
         help="Ignore whitespace differences (default: True)",
     )
 
+    # Backend selection
+    parser.add_argument(
+        "--in-process",
+        action="store_true",
+        help="Call the robustone_py bindings instead of the robustone binary",
+    )
+
     # General options
     parser.add_argument("--verbose", "-v", action="store_true", help="Verbose output")
 